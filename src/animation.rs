@@ -0,0 +1,449 @@
+//! Contains the animation system, interpolating values over time along easing curves.
+//!
+//! A [`Tween`] describes how a value changes from a start to an end value over a
+//! duration, along an [`Easing`] curve. An [`Animation`] plays a tween from the instant
+//! it is created, according to the [`clock`](`crate::clock`), so widgets can embed
+//! animations and read their current value on `recipe()`.
+//!
+//! Animations of a widget's properties (e.g. its position, size, color or opacity)
+//! can instead be handed to the context's [`AnimationController`], which the event
+//! loop ticks every frame: it assigns the interpolated value to the widget and marks
+//! the widget as dirty, until the animation finishes.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::animation::{Easing, Tween};
+//! use std::time::Duration;
+//!
+//! let tween = Tween::new(0., 100., Duration::from_millis(200)).with_easing(Easing::EaseInOut);
+//!
+//! assert_eq!(tween.value_at(Duration::from_millis(0)), 0.);
+//! assert_eq!(tween.value_at(Duration::from_millis(100)), 50.);
+//! assert_eq!(tween.value_at(Duration::from_millis(300)), 100.);
+//! ```
+
+use crate::clock;
+use crate::util::{Color, Vector2D};
+use crate::widget::{Widget, WidgetRef};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Number of iterations used to find the progress of a cubic Bézier curve at a given time
+const BEZIER_ITERATIONS: usize = 16;
+
+/// Curve mapping the time elapsed into the progress of an animation, both from 0 to 1
+///
+/// _**Note:** Based on the CSS easing functions, at
+/// https://developer.mozilla.org/en-US/docs/Web/CSS/easing-function
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Starts slowly and accelerates (i.e., cubic)
+    EaseIn,
+    /// Starts quickly and decelerates (i.e., cubic)
+    EaseOut,
+    /// Starts and ends slowly, being quickest halfway (i.e., cubic)
+    EaseInOut,
+    /// Cubic Bézier curve from (0, 0) to (1, 1), given its two control points
+    /// `(x1, y1, x2, y2)`, whose x-coordinates must be between 0 and 1
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    /// Maps the time elapsed into the progress of an animation
+    ///
+    /// # Returns
+    /// The progress, from 0 to 1 (possibly beyond, for Bézier curves overshooting)
+    ///
+    /// # Arguments
+    /// * `t` - the fraction of the duration elapsed, from 0 to 1
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0., 1.);
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1. - (1. - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4. * t * t * t,
+            Easing::EaseInOut => 1. - (-2. * t + 2.).powi(3) / 2.,
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                // Find the curve's parameter whose x-coordinate is the time, by bisection
+                let (mut low, mut high) = (0., 1.);
+                let mut s = t;
+                for _ in 0..BEZIER_ITERATIONS {
+                    if bezier(s, x1, x2) < t {
+                        low = s;
+                    } else {
+                        high = s;
+                    }
+                    s = (low + high) / 2.;
+                }
+                bezier(s, y1, y2)
+            }
+        }
+    }
+}
+
+/// Computes a coordinate of a cubic Bézier curve from 0 to 1, for internal use only
+///
+/// # Returns
+/// The coordinate at the given parameter
+///
+/// # Arguments
+/// * `s` - the curve's parameter, from 0 to 1
+/// * `p1` - the coordinate of the first control point
+/// * `p2` - the coordinate of the second control point
+fn bezier(s: f64, p1: f64, p2: f64) -> f64 {
+    let r = 1. - s;
+    3. * r * r * s * p1 + 3. * r * s * s * p2 + s * s * s
+}
+
+/// Value that can be interpolated by an animation
+pub trait Interpolate: Copy {
+    /// Interpolates between the value and another one
+    ///
+    /// # Returns
+    /// The value the given fraction of the way to the other one
+    ///
+    /// # Arguments
+    /// * `other` - the value at the end
+    /// * `progress` - the fraction of the way, from 0 (the value) to 1 (the other one)
+    fn interpolate(self, other: Self, progress: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(self, other: Self, progress: f64) -> Self {
+        self + (other - self) * progress
+    }
+}
+
+impl Interpolate for Vector2D {
+    fn interpolate(self, other: Self, progress: f64) -> Self {
+        Vector2D::new(
+            self.x.interpolate(other.x, progress),
+            self.y.interpolate(other.y, progress),
+        )
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, other: Self, progress: f64) -> Self {
+        let channel = |from: u8, to: u8| {
+            (from as f64)
+                .interpolate(to as f64, progress)
+                .round()
+                .clamp(0., 255.) as u8
+        };
+        Color::new(
+            channel(self.a, other.a),
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// Change of a value from a start to an end value over a duration
+#[derive(Clone, Copy)]
+pub struct Tween<T: Interpolate> {
+    /// The value at the start
+    from: T,
+    /// The value at the end
+    to: T,
+    /// The time taken to reach the end value
+    duration: Duration,
+    /// The curve along which the value changes
+    easing: Easing,
+}
+
+impl<T: Interpolate> Tween<T> {
+    /// Creates a new `Tween`, changing at constant speed
+    ///
+    /// # Returns
+    /// The tween created
+    ///
+    /// # Arguments
+    /// * `from` - the value at the start
+    /// * `to` - the value at the end
+    /// * `duration` - the time taken to reach the end value
+    pub fn new(from: T, to: T, duration: Duration) -> Tween<T> {
+        Tween {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Sets the tween's easing curve, returning the tween (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tween with the easing curve assigned
+    ///
+    /// # Arguments
+    /// * `easing` - the curve along which the value changes
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Gets the time taken to reach the end value
+    ///
+    /// # Returns
+    /// The tween's duration
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Gets the value after some time
+    ///
+    /// # Returns
+    /// The interpolated value, or the end value once the duration elapsed
+    ///
+    /// # Arguments
+    /// * `elapsed` - the time elapsed since the start
+    pub fn value_at(&self, elapsed: Duration) -> T {
+        if elapsed >= self.duration {
+            return self.to;
+        }
+        let t = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        self.from.interpolate(self.to, self.easing.apply(t))
+    }
+}
+
+/// Tween played from the instant the animation was created
+#[derive(Clone, Copy)]
+pub struct Animation<T: Interpolate> {
+    /// The tween played
+    tween: Tween<T>,
+    /// The instant when the animation started
+    start: Instant,
+}
+
+impl<T: Interpolate> Animation<T> {
+    /// Creates a new `Animation`, starting now
+    ///
+    /// # Returns
+    /// The animation created
+    ///
+    /// # Arguments
+    /// * `tween` - the tween to be played
+    pub fn new(tween: Tween<T>) -> Animation<T> {
+        Animation {
+            tween,
+            start: clock::now(),
+        }
+    }
+
+    /// Gets the animation's current value
+    ///
+    /// # Returns
+    /// The value of the tween at the time elapsed since the animation started
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn value(&self) -> T {
+        self.tween.value_at(clock::elapsed(self.start))
+    }
+
+    /// Checks whether the animation reached its end value
+    ///
+    /// # Returns
+    /// True, if the tween's duration elapsed since the animation started, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_finished(&self) -> bool {
+        clock::elapsed(self.start) >= self.tween.duration
+    }
+}
+
+/// Callback assigning an animation's current value to its widget, returning whether
+/// the animation is still running, for internal use only
+type AnimationTick = Box<dyn FnMut() -> bool>;
+
+/// Animations of the widgets' properties, ticked by the event loop every frame
+///
+/// It is owned by the [`Context`](`crate::context::Context`) and reached through
+/// [`animations`](`crate::context::Context::animations`). The widgets animated are
+/// held as weak pointers, and their animations stop once they are dropped.
+#[derive(Default)]
+pub struct AnimationController {
+    /// The identifier to be given to the next animation
+    next_id: usize,
+    /// The animations running, along with their identifiers
+    animations: Vec<(usize, AnimationTick)>,
+}
+
+impl AnimationController {
+    /// Creates a new `AnimationController`, with no animations
+    ///
+    /// # Returns
+    /// The animation controller created
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> AnimationController {
+        AnimationController::default()
+    }
+
+    /// Animates a property of a widget, starting now
+    ///
+    /// Every frame, until the tween ends, the interpolated value is assigned to the
+    /// widget through `apply` and the widget is marked as dirty.
+    ///
+    /// # Returns
+    /// The identifier of the animation, to cancel it
+    ///
+    /// # Arguments
+    /// * `widget` - the widget to be animated, of type `W`
+    /// * `tween` - the change of the property's value
+    /// * `apply` - the callback assigning the value to the widget's property (e.g. a closure calling `PanelWidget::set_corner_radius`)
+    pub fn animate<W, T, F>(
+        &mut self,
+        widget: &Rc<RefCell<dyn Widget>>,
+        tween: Tween<T>,
+        apply: F,
+    ) -> usize
+    where
+        W: Widget + 'static,
+        T: Interpolate + 'static,
+        F: Fn(&mut W, T) + 'static,
+    {
+        let widget = Rc::downgrade(widget);
+        let animation = Animation::new(tween);
+        self.push(Box::new(move || {
+            let widget = match widget.upgrade() {
+                Some(widget) => widget,
+                None => return false,
+            };
+            let mut widget = widget.borrow_mut();
+            if let Some(widget) = widget.as_any().downcast_mut::<W>() {
+                apply(widget, animation.value());
+            }
+            widget.set_dirty(true);
+            !animation.is_finished()
+        }))
+    }
+
+    /// Animates the size a widget desires (i.e., its original size), starting now
+    ///
+    /// Unlike [`animate`](`AnimationController::animate`), it applies to widgets of
+    /// any type, so that their layout follows the animation.
+    ///
+    /// # Returns
+    /// The identifier of the animation, to cancel it
+    ///
+    /// # Arguments
+    /// * `widget` - the widget to be animated
+    /// * `tween` - the change of the widget's size
+    pub fn animate_size(
+        &mut self,
+        widget: &Rc<RefCell<dyn Widget>>,
+        tween: Tween<Vector2D>,
+    ) -> usize {
+        let widget = Rc::downgrade(widget);
+        let animation = Animation::new(tween);
+        self.push(Box::new(move || match widget.upgrade() {
+            Some(widget) => {
+                widget.borrow_mut().set_original_size(animation.value());
+                !animation.is_finished()
+            }
+            None => false,
+        }))
+    }
+
+    /// Animates the opacity of a widget, starting now (e.g. to fade it in or out)
+    ///
+    /// Like [`animate_size`](`AnimationController::animate_size`), it applies to widgets
+    /// of any type, fading their children along with them (see [`WidgetRef::set_opacity`]).
+    ///
+    /// # Returns
+    /// The identifier of the animation, to cancel it
+    ///
+    /// # Arguments
+    /// * `widget` - the widget to be animated
+    /// * `tween` - the change of the widget's opacity, from 0 (invisible) to 1 (opaque)
+    pub fn animate_opacity(
+        &mut self,
+        widget: &Rc<RefCell<dyn Widget>>,
+        tween: Tween<f64>,
+    ) -> usize {
+        let widget = Rc::downgrade(widget);
+        let animation = Animation::new(tween);
+        self.push(Box::new(move || match widget.upgrade() {
+            Some(widget) => {
+                widget.set_opacity(animation.value());
+                !animation.is_finished()
+            }
+            None => false,
+        }))
+    }
+
+    /// Cancels an animation, leaving its widget's property with the current value
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the animation
+    pub fn cancel(&mut self, id: usize) {
+        self.animations.retain(|(other, _)| *other != id);
+    }
+
+    /// Checks whether any animation is running
+    ///
+    /// # Returns
+    /// True, if some animation did not finish yet, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_animating(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Assigns the current value of every animation to its widget, removing the
+    /// animations finished
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn tick(&mut self) {
+        self.animations.retain_mut(|(_, tick)| tick());
+    }
+
+    /// Adds the animations of another controller after the ones running, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `other` - the controller whose animations are moved
+    pub(crate) fn append(&mut self, other: &mut AnimationController) {
+        self.next_id = self.next_id.max(other.next_id);
+        self.animations.append(&mut other.animations);
+    }
+
+    /// Adds an animation, for internal use only
+    ///
+    /// # Returns
+    /// The identifier of the animation
+    ///
+    /// # Arguments
+    /// * `tick` - the callback assigning the animation's current value to its widget
+    fn push(&mut self, tick: AnimationTick) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.animations.push((id, tick));
+        id
+    }
+}
@@ -0,0 +1,252 @@
+//! Contains the busy overlay, blocking the application while a task is running.
+//!
+//! A [`BusyOverlay`] covers the whole display with a
+//! [`BusyOverlayWidget`](`crate::widget::busy_overlay::BusyOverlayWidget`) (i.e., a
+//! scrim with a spinner, a message and an optional progress bar), opened as a popup
+//! so that the widgets behind it cannot be interacted with until it is hidden.
+//!
+//! Tasks running on other threads report their progress through a
+//! [`ProgressReporter`], which sends the reports on a thread-safe channel. While the
+//! overlay is shown, the reports received are applied to it before every frame.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::busy::BusyOverlay;
+//! use hyber::context::Context;
+//! use std::thread;
+//!
+//! let mut context = Context::new();
+//! let mut overlay = BusyOverlay::new();
+//! overlay.show("Loading...", &mut context);
+//!
+//! let reporter = overlay.reporter();
+//! thread::spawn(move || {
+//!     reporter.report_message("Almost done");
+//!     reporter.report_progress(90.);
+//! })
+//! .join()
+//! .unwrap();
+//!
+//! // Done before every frame while the overlay is shown
+//! overlay.update();
+//! assert_eq!(overlay.widget().borrow().message(), "Almost done");
+//! assert_eq!(overlay.widget().borrow().progress(), Some(90.));
+//!
+//! overlay.hide(&mut context);
+//! assert!(context.popups().is_empty());
+//! ```
+
+use crate::context::Context;
+use crate::widget::busy_overlay::BusyOverlayWidget;
+use crate::widget::Widget;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Report sent by a task to the busy overlay, for internal use only
+enum BusyReport {
+    /// The task's progress, from 0 to 100
+    Progress(f64),
+    /// The message describing what the task is doing
+    Message(String),
+}
+
+/// Handle sending the progress of a task to a busy overlay, which can be moved to
+/// other threads
+#[derive(Clone)]
+pub struct ProgressReporter {
+    /// The sending half of the overlay's channel
+    sender: Sender<BusyReport>,
+}
+
+impl ProgressReporter {
+    /// Reports the task's progress, displayed on the overlay's progress bar
+    ///
+    /// The reports sent after the overlay was dropped are discarded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `progress` - the task's progress, from 0 to 100
+    pub fn report_progress(&self, progress: f64) {
+        let _ = self.sender.send(BusyReport::Progress(progress));
+    }
+
+    /// Reports the message describing what the task is doing, displayed on the overlay
+    ///
+    /// The reports sent after the overlay was dropped are discarded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `message` - the message to be displayed
+    pub fn report_message(&self, message: &str) {
+        let _ = self.sender.send(BusyReport::Message(String::from(message)));
+    }
+}
+
+/// Overlay blocking the application while a task is running, which displays the
+/// progress reported by the task
+pub struct BusyOverlay {
+    /// The widget drawn over the whole display while the overlay is shown
+    widget: Rc<RefCell<BusyOverlayWidget>>,
+
+    /// The sending half of the channel, cloned by the reporters
+    sender: Sender<BusyReport>,
+
+    /// The receiving half of the channel, shared with the frame hook
+    receiver: Rc<Receiver<BusyReport>>,
+
+    /// The identifier of the frame hook applying the reports, while the overlay is shown
+    hook: Option<usize>,
+}
+
+impl BusyOverlay {
+    /// Creates a new `BusyOverlay`, hidden
+    ///
+    /// # Returns
+    /// The busy overlay created
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> BusyOverlay {
+        BusyOverlay::with_widget(BusyOverlayWidget::default())
+    }
+
+    /// Creates a new `BusyOverlay`, hidden, drawn by a styled widget
+    ///
+    /// # Returns
+    /// The busy overlay created
+    ///
+    /// # Arguments
+    /// * `widget` - the widget drawn over the whole display while the overlay is shown
+    pub fn with_widget(widget: BusyOverlayWidget) -> BusyOverlay {
+        let (sender, receiver) = mpsc::channel();
+        BusyOverlay {
+            widget: Rc::new(RefCell::new(widget)),
+            sender,
+            receiver: Rc::new(receiver),
+            hook: None,
+        }
+    }
+
+    /// Gets the widget drawn over the whole display while the overlay is shown
+    ///
+    /// # Returns
+    /// The overlay's widget
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn widget(&self) -> Rc<RefCell<BusyOverlayWidget>> {
+        self.widget.clone()
+    }
+
+    /// Gets a reporter sending progress to the overlay, to be moved to the task's thread
+    ///
+    /// # Returns
+    /// The reporter created
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn reporter(&self) -> ProgressReporter {
+        ProgressReporter {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Checks whether the overlay is shown
+    ///
+    /// # Returns
+    /// True, if the overlay is shown, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_shown(&self) -> bool {
+        self.hook.is_some()
+    }
+
+    /// Shows the overlay over the whole display, blocking every event until hidden
+    ///
+    /// The progress bar is hidden until a progress is reported, while the reports left
+    /// over from previous tasks are discarded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `message` - the message to be displayed
+    /// * `context` - the context of the event loop
+    pub fn show(&mut self, message: &str, context: &mut Context) {
+        while self.receiver.try_recv().is_ok() {}
+        {
+            let mut widget = self.widget.borrow_mut();
+            widget.set_message(message);
+            widget.set_progress(None);
+        }
+        let popup: Rc<RefCell<dyn Widget>> = self.widget.clone();
+        context.open_popup(&popup);
+        if self.hook.is_none() {
+            let widget = Rc::downgrade(&self.widget);
+            let receiver = self.receiver.clone();
+            self.hook = Some(context.add_before_frame_hook(Box::new(move |_| {
+                if let Some(widget) = widget.upgrade() {
+                    receive(&receiver, &mut widget.borrow_mut());
+                }
+            })));
+        }
+    }
+
+    /// Applies the reports received to the overlay, which is done before every frame
+    /// while the overlay is shown
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn update(&self) {
+        receive(&self.receiver, &mut self.widget.borrow_mut());
+    }
+
+    /// Hides the overlay, releasing the events it blocked
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the context of the event loop
+    pub fn hide(&mut self, context: &mut Context) {
+        let popup: Rc<RefCell<dyn Widget>> = self.widget.clone();
+        context.close_popup(&popup);
+        if let Some(hook) = self.hook.take() {
+            context.remove_frame_hook(hook);
+        }
+    }
+}
+
+impl Default for BusyOverlay {
+    fn default() -> Self {
+        BusyOverlay::new()
+    }
+}
+
+/// Applies the reports received on a channel to an overlay's widget, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `receiver` - the receiving half of the channel
+/// * `widget` - the overlay's widget
+fn receive(receiver: &Receiver<BusyReport>, widget: &mut BusyOverlayWidget) {
+    while let Ok(report) = receiver.try_recv() {
+        match report {
+            BusyReport::Progress(progress) => widget.set_progress(Some(progress)),
+            BusyReport::Message(message) => widget.set_message(&message),
+        }
+    }
+}
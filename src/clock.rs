@@ -0,0 +1,160 @@
+//! Contains the clock from which widgets and subsystems read the current time.
+//!
+//! Widgets measure time (e.g. long presses, tooltips and animations) with [`now`] and
+//! [`elapsed`] instead of [`Instant::now`], so that the time can be replaced, for example
+//! by a [`VirtualClock`] advanced deterministically by tests.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::clock::{self, VirtualClock};
+//! use std::rc::Rc;
+//! use std::time::Duration;
+//!
+//! let clock = VirtualClock::new();
+//! clock::set_clock(Rc::new(clock.clone()));
+//!
+//! let start = clock::now();
+//! clock.advance(Duration::from_millis(500));
+//! assert_eq!(clock::elapsed(start), Duration::from_millis(500));
+//!
+//! clock::reset_clock();
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+pub trait Clock {
+    /// Gets the current time
+    ///
+    /// # Returns
+    /// The current time
+    ///
+    /// # Arguments
+    /// No arguments
+    fn now(&self) -> Instant;
+}
+
+/// Clock reading the time of the system (i.e., [`Instant::now`]), used by default
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock whose time only changes when it is advanced, so that time-based behavior
+/// (e.g. long presses, tooltips and animations) can be tested deterministically
+///
+/// Clones of a virtual clock share the same time.
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    /// The time at which the clock started
+    origin: Instant,
+    /// The time elapsed since the clock started
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl VirtualClock {
+    /// Creates a new `VirtualClock`
+    ///
+    /// # Returns
+    /// The virtual clock created, with no time elapsed
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> VirtualClock {
+        VirtualClock {
+            origin: Instant::now(),
+            elapsed: Rc::new(Cell::new(Duration::from_secs(0))),
+        }
+    }
+
+    /// Advances the time of the clock
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `duration` - the time to be advanced
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+
+    /// Gets the time elapsed since the clock started
+    ///
+    /// # Returns
+    /// The time elapsed since the clock started
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.get()
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        VirtualClock::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.origin + self.elapsed.get()
+    }
+}
+
+thread_local! {
+    /// The clock read by the widgets and subsystems of the current thread
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(Rc::new(SystemClock));
+}
+
+/// Sets the clock read by the widgets and subsystems of the current thread
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `clock` - the clock to be read
+pub fn set_clock(clock: Rc<dyn Clock>) {
+    CLOCK.with(|current| *current.borrow_mut() = clock);
+}
+
+/// Sets the clock read by the widgets and subsystems of the current thread back to
+/// the [`SystemClock`]
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// No arguments
+pub fn reset_clock() {
+    set_clock(Rc::new(SystemClock));
+}
+
+/// Gets the current time from the clock of the current thread
+///
+/// # Returns
+/// The current time
+///
+/// # Arguments
+/// No arguments
+pub fn now() -> Instant {
+    CLOCK.with(|current| current.borrow().now())
+}
+
+/// Gets the time elapsed since an instant, according to the clock of the current thread
+///
+/// # Returns
+/// The time elapsed since the instant, or zero if the instant is in the future
+///
+/// # Arguments
+/// * `instant` - the instant from which the time is measured
+pub fn elapsed(instant: Instant) -> Duration {
+    now().saturating_duration_since(instant)
+}
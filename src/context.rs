@@ -0,0 +1,3211 @@
+//! Contains the shared state of the event loop.
+//! The [`Context`] is created by the application and handed to the [`event_loop`](`crate::renderer::Renderer::event_loop`) as a weak pointer, such that messages can keep a pointer to it and interact with the event loop (e.g. request a frame callback).
+
+use crate::animation::AnimationController;
+use crate::clock::{self, Clock};
+use crate::display::{AttentionLevel, WindowEdge};
+use crate::event::{self, Event, Mouse, MouseButton, Window};
+use crate::renderer::{
+    AtlasHandle, BlendMode, ClipShape, DrawImageOptions, ImageAtlas, RenderInstruction,
+    RenderInstructionCollection,
+};
+use crate::resources::ResourceRegistry;
+use crate::style;
+use crate::util::{Color, MemoKey, Queue, Rect, Transform, Vector2D};
+use crate::watchdog::{FramePhase, Watchdog};
+use crate::widget::context_menu::{ContextMenuItem, ContextMenuWidget};
+use crate::widget::{self, TabIndex, Widget};
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::{Rc, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Information about a frame that was drawn on the display
+///
+/// It is given to every frame callback requested with
+/// [`request_frame_callback`](`Context::request_frame_callback`)
+#[derive(Clone, Copy)]
+pub struct FrameInfo {
+    /// The number of the frame, starting at 1 for the first frame drawn
+    pub frame: u64,
+    /// The display's size (width and height) used to build the frame
+    pub display_size: Vector2D,
+    /// The time taken to handle the events, build the widgets and draw the frame
+    pub frame_time: Duration,
+}
+
+/// How the event loop behaves while the window is idle (i.e., unfocused or minimized)
+///
+/// The window becomes idle when a [`Window::Focused(false)`](`Window::Focused`) or a
+/// [`Window::Minimized`] event arrives, and stops being idle when a
+/// [`Window::Focused(true)`](`Window::Focused`) or a [`Window::Restored`] event arrives.
+/// Any event received while idle wakes the event loop up for one frame.
+#[derive(Clone, Copy)]
+pub enum IdlePolicy {
+    /// Frames are drawn at full speed, even when the window is idle
+    Continuous,
+    /// The interval between frames doubles on every idle frame (exponential backoff)
+    Backoff {
+        /// The interval between the first two idle frames
+        initial: Duration,
+        /// The maximum interval between two idle frames
+        max: Duration,
+    },
+    /// Drawing is fully suspended until an event arrives or the wake timer expires
+    Suspend {
+        /// The interval between two consecutive polls of the display events
+        poll_interval: Duration,
+        /// The interval after which a frame is drawn even without events, if any
+        wake_interval: Option<Duration>,
+    },
+}
+
+/// How the event loop proceeds between frames
+///
+/// _**Note:** Based on the winit control flow, at
+/// https://docs.rs/winit/latest/winit/event_loop/enum.ControlFlow.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControlFlow {
+    /// Frames are drawn continuously, as fast as the target frame rate allows
+    #[default]
+    Poll,
+    /// Frames are only drawn when events arrive, an animation is running or a redraw
+    /// is requested, so idle applications do not redraw. The time-driven state of the
+    /// widgets and the frame hooks are not updated while waiting
+    Wait,
+    /// Frames are only drawn as with [`ControlFlow::Wait`], or once the given instant
+    /// is reached, after which the event loop waits as with [`ControlFlow::Wait`]
+    WaitUntil(Instant),
+    /// The event loop stops, once the current frame ends
+    Exit,
+}
+
+/// How the mouse events are dispatched to the widgets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventRouting {
+    /// Every event is dispatched to the root (or the popup on top), which forwards it
+    /// to its children, so every widget sees every event
+    #[default]
+    Broadcast,
+    /// The mouse events located by the cursor (i.e., moving the cursor, pressing and
+    /// releasing buttons and scrolling) are only dispatched to the widgets under the
+    /// cursor, from the topmost one up to the root (i.e., bubbling), while the other
+    /// events are broadcast. The widgets the cursor left get its last move as well.
+    ///
+    /// Widgets relying on seeing every mouse event (e.g. a textbox losing the focus
+    /// when clicked outside) should capture the mouse instead (see
+    /// [`capture_pointer`](`crate::widget::Widget::capture_pointer`)).
+    HitTest,
+}
+
+/// Phase of the event loop in which a widget panicked
+#[derive(Clone, Copy, Debug)]
+pub enum WidgetPhase {
+    /// The widget panicked while handling an event (i.e., on `on_event`)
+    Event,
+    /// The widget panicked while generating its render instructions (i.e., on `recipe`)
+    Recipe,
+}
+
+/// A panic caught on a widget by the panic guard of the event loop
+#[derive(Clone, Debug)]
+pub struct WidgetPanic {
+    /// The identifier of the widget that panicked
+    pub widget_id: usize,
+    /// The phase of the event loop in which the widget panicked
+    pub phase: WidgetPhase,
+    /// The panic message, if any
+    pub message: String,
+}
+
+impl WidgetPanic {
+    /// Creates a new `WidgetPanic` from the payload of a caught panic
+    ///
+    /// # Returns
+    /// The widget panic created
+    ///
+    /// # Arguments
+    /// * `widget_id` - the identifier of the widget that panicked
+    /// * `phase` - the phase of the event loop in which the widget panicked
+    /// * `payload` - the payload of the caught panic
+    pub fn new(widget_id: usize, phase: WidgetPhase, payload: Box<dyn Any + Send>) -> WidgetPanic {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            String::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        };
+
+        WidgetPanic {
+            widget_id,
+            phase,
+            message,
+        }
+    }
+}
+
+/// Time spent rebuilding a widget during a frame
+#[derive(Clone, Copy, Debug)]
+pub struct WidgetTiming {
+    /// The identifier of the widget rebuilt
+    pub widget_id: usize,
+    /// The time taken to rebuild the widget itself, excluding its children
+    pub build_time: Duration,
+    /// The time taken to generate the widget's render instructions (i.e., its recipe)
+    pub recipe_time: Duration,
+}
+
+/// Diagnostic of a frame whose build and draw exceeded the frame budget
+#[derive(Clone, Debug)]
+pub struct LongFrame {
+    /// The number of the frame
+    pub frame: u64,
+    /// The time taken to build the widgets and draw the frame
+    pub build_and_draw_time: Duration,
+    /// The frame budget exceeded
+    pub budget: Duration,
+    /// The widgets rebuilt during the frame that took the longest, slowest first
+    pub slowest_widgets: Vec<WidgetTiming>,
+}
+
+impl fmt::Display for LongFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {} took {:?} to build and draw, over the budget of {:?}",
+            self.frame, self.build_and_draw_time, self.budget
+        )?;
+        for timing in self.slowest_widgets.iter() {
+            write!(
+                f,
+                "\n  widget {}: built in {:?} (recipe {:?})",
+                timing.widget_id, timing.build_time, timing.recipe_time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Diagnostic of a widget whose desired size exceeds the size assigned to it by its
+/// parent, so that it is clipped or overlaps its siblings
+#[derive(Clone, Copy)]
+pub struct LayoutOverflow {
+    /// The identifier of the overflowing widget
+    pub widget_id: usize,
+    /// The size (width and height) desired by the widget (i.e., its original size)
+    pub desired_size: Vector2D,
+    /// The size (width and height) assigned to the widget by its parent
+    pub assigned_size: Vector2D,
+}
+
+impl LayoutOverflow {
+    /// Gets the amount by which the widget's desired size exceeds its assigned size
+    ///
+    /// # Returns
+    /// The overflow on each axis, zero if the widget fits on that axis
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn overflow(&self) -> Vector2D {
+        Vector2D::new(
+            (self.desired_size.x - self.assigned_size.x).max(0.),
+            (self.desired_size.y - self.assigned_size.y).max(0.),
+        )
+    }
+}
+
+impl fmt::Display for LayoutOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let overflow = self.overflow();
+        write!(
+            f,
+            "widget {} overflows its constraints by {}x{} (desired {}x{}, assigned {}x{})",
+            self.widget_id,
+            overflow.x,
+            overflow.y,
+            self.desired_size.x,
+            self.desired_size.y,
+            self.assigned_size.x,
+            self.assigned_size.y
+        )
+    }
+}
+
+/// Direction in which the content of the user interface is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutDirection {
+    /// Content is read from left to right (e.g. English)
+    LeftToRight,
+    /// Content is read from right to left (e.g. Arabic or Hebrew)
+    RightToLeft,
+}
+
+/// Request made by a widget to the display, forwarded by the event loop
+///
+/// Widgets have no access to the display, so they place their requests on the
+/// context through [`request_window`](`Context::request_window`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowRequest {
+    /// Start resizing the display from one of its edges or corners
+    BeginResize(WindowEdge),
+    /// Start moving the display
+    BeginMove,
+    /// Minimize the display
+    Minimize,
+    /// Maximize the display, or restore it if already maximized
+    ToggleMaximized,
+    /// Close the display
+    Close,
+    /// Set the display's title
+    SetTitle(String),
+    /// Request the user's attention while the display is in background
+    RequestAttention(AttentionLevel),
+    /// Make the display always appear on top of those that are not topmost, or not
+    SetTopmost(bool),
+    /// Set the opacity of the whole display, from 0 (invisible) to 1 (opaque)
+    SetOpacity(f64),
+}
+
+/// Handle to control the display at runtime (e.g. from a message)
+///
+/// It places [`WindowRequest`]s on the context, which are forwarded to the display by
+/// the event loop right after the events of the current frame are handled. The handle
+/// holds a weak pointer to the context, so it can be cloned into any message.
+///
+/// # Examples
+///
+/// ```ignore
+/// let window = WindowController::new(Rc::downgrade(&context));
+///
+/// window.set_topmost(true);
+/// window.set_opacity(0.8);
+/// ```
+#[derive(Clone)]
+pub struct WindowController {
+    /// The context where the requests are placed
+    context: Weak<RefCell<Context>>,
+}
+
+impl WindowController {
+    /// Creates a new `WindowController`
+    ///
+    /// # Returns
+    /// The window controller created
+    ///
+    /// # Arguments
+    /// * `context` - the context of the event loop driving the display
+    pub fn new(context: Weak<RefCell<Context>>) -> WindowController {
+        WindowController { context }
+    }
+
+    /// Places a request on the context, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `request` - the request to be forwarded to the display
+    fn request(&self, request: WindowRequest) {
+        if let Some(context) = self.context.upgrade() {
+            context.borrow_mut().request_window(request);
+        }
+    }
+
+    /// Sets the display's title
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the display
+    pub fn set_title(&self, title: &str) {
+        self.request(WindowRequest::SetTitle(String::from(title)));
+    }
+
+    /// Makes the display always appear on top of those that are not topmost
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `topmost` - boolean indicating if the display is topmost
+    pub fn set_topmost(&self, topmost: bool) {
+        self.request(WindowRequest::SetTopmost(topmost));
+    }
+
+    /// Sets the opacity of the whole display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `opacity` - the opacity to be assigned to the display, from 0 (invisible) to 1 (opaque)
+    pub fn set_opacity(&self, opacity: f64) {
+        self.request(WindowRequest::SetOpacity(opacity.clamp(0., 1.)));
+    }
+
+    /// Minimizes the display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn minimize(&self) {
+        self.request(WindowRequest::Minimize);
+    }
+
+    /// Maximizes the display, or restores it if already maximized
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn toggle_maximized(&self) {
+        self.request(WindowRequest::ToggleMaximized);
+    }
+
+    /// Closes the display, stopping the event loop
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn close(&self) {
+        self.request(WindowRequest::Close);
+    }
+
+    /// Requests the user's attention while the display is in background
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `level` - the urgency of the request
+    pub fn request_attention(&self, level: AttentionLevel) {
+        self.request(WindowRequest::RequestAttention(level));
+    }
+}
+
+/// Callback that receives the panics caught on widgets
+pub type PanicHandler = Box<dyn FnMut(&WidgetPanic)>;
+
+/// Callback that receives the diagnostics of the frames exceeding the frame budget
+pub type LongFrameHandler = Box<dyn FnMut(&LongFrame)>;
+
+/// Callback that receives the diagnostics of the widgets overflowing their constraints
+pub type OverflowHandler = Box<dyn FnMut(&LayoutOverflow)>;
+
+/// Width of the striped marker drawn along the overflowing edges of a widget
+const OVERFLOW_MARKER_WIDTH: f64 = 6.;
+
+/// Number of widgets listed on the diagnostic of a long frame
+const SLOWEST_WIDGETS: usize = 5;
+
+/// Interval between two consecutive polls of the display events while the event loop waits
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Default maximum time between the presses of a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maximum distance, in pixels along each axis, between the presses of a double-click
+const DOUBLE_CLICK_DISTANCE: f64 = 4.;
+
+/// One-shot callback executed right after a frame is drawn
+pub type FrameCallback = Box<dyn FnOnce(&FrameInfo)>;
+
+/// Callback executed on every frame, before the events are handled or after the frame is drawn
+pub type FrameHook = Box<dyn FnMut(&FrameInfo)>;
+
+/// Widget registered with the items of its context menu
+type ContextMenuRegistration = (Weak<RefCell<dyn Widget>>, Vec<ContextMenuItem>);
+
+/// Moment of the frame in which a frame hook is executed
+#[derive(Clone, Copy)]
+enum HookPhase {
+    /// Before the events are handled
+    Before,
+    /// After the frame is drawn
+    After,
+}
+
+/// Shared state of the event loop
+///
+/// The context should be encapsulated inside a Reference Counter pointer when
+/// created, such that messages can hold a weak pointer to it.
+pub struct Context {
+    /// The number of frames drawn so far
+    frame: u64,
+    /// The callbacks to be executed right after the next frame is drawn
+    frame_callbacks: Vec<FrameCallback>,
+    /// The hooks executed on every frame before the events are handled, with their identifiers
+    before_frame_hooks: Vec<(usize, FrameHook)>,
+    /// The hooks executed on every frame after the frame is drawn, with their identifiers
+    after_frame_hooks: Vec<(usize, FrameHook)>,
+    /// The identifier to be assigned to the next frame hook
+    next_hook_id: usize,
+    /// The identifiers of the hooks removed while the hooks were being executed
+    removed_hooks: Vec<usize>,
+    /// The time taken by the last frame drawn
+    last_frame_time: Duration,
+    /// How the event loop behaves while the window is idle
+    idle_policy: IdlePolicy,
+    /// How the event loop proceeds between frames
+    control_flow: ControlFlow,
+    /// Whether a frame must be drawn even if the event loop is waiting
+    needs_redraw: bool,
+    /// The earliest instant a frame was requested at, drawn even if the event loop is
+    /// waiting (e.g. to show a tooltip once its delay elapsed)
+    wake_up: Option<Instant>,
+    /// The display's size (width and height) as of the last frame, bounding the
+    /// widgets placed over the display (e.g. tooltips)
+    display_size: Option<Vector2D>,
+    /// The minimum time between the start of two frames, if the frame rate is limited
+    frame_interval: Option<Duration>,
+    /// Whether the frames are paced by waiting for the display's vertical blank
+    vsync: bool,
+    /// Whether the window is idle (i.e., unfocused or minimized)
+    is_idle: bool,
+    /// The current interval between idle frames, when using [`IdlePolicy::Backoff`]
+    idle_interval: Option<Duration>,
+    /// The instant when the last idle frame was drawn
+    last_idle_frame: Instant,
+    /// The callback that receives the panics caught on widgets. The panic guard
+    /// is only enabled when there is a panic handler
+    panic_handler: Option<PanicHandler>,
+    /// The panics caught during the current frame
+    panics: Vec<WidgetPanic>,
+    /// The identifiers of the widgets that panicked since they were last built, which
+    /// get no more events nor frames meanwhile
+    failed_widgets: HashSet<usize>,
+    /// The maximum time to build the widgets and draw a frame, before a diagnostic
+    /// is emitted. The widgets are only timed when there is a budget
+    frame_budget: Option<Duration>,
+    /// The callback that receives the diagnostics of the long frames. When there is
+    /// none, the diagnostics are dropped
+    long_frame_handler: Option<LongFrameHandler>,
+    /// The time spent rebuilding every widget during the current frame
+    widget_timings: Vec<WidgetTiming>,
+    /// The watchdog diagnosing the frames exceeding its threshold, if any
+    watchdog: Option<Watchdog>,
+    /// Whether the widgets overflowing their constraints are diagnosed
+    overflow_diagnostics: bool,
+    /// The callback that receives the diagnostics of the overflowing widgets. When
+    /// there is none, the diagnostics are dropped
+    overflow_handler: Option<OverflowHandler>,
+    /// The diagnostics of the widgets overflowing during the current frame
+    overflows: Vec<LayoutOverflow>,
+    /// Whether the render instructions are snapped to device pixels by default
+    pixel_snapping: bool,
+    /// The number of device pixels per logical unit
+    scale_factor: f64,
+    /// The direction in which the content of the user interface is read
+    layout_direction: LayoutDirection,
+    /// Whether every widget needs to be rebuilt (e.g. after the scale factor changed)
+    needs_rebuild: bool,
+    /// How the mouse events are dispatched to the widgets
+    event_routing: EventRouting,
+    /// The widgets under the cursor as of its last move, when routing by hit test,
+    /// from the root down to the topmost one
+    hovered_path: Vec<Weak<RefCell<dyn Widget>>>,
+    /// The mouse buttons currently held, in the order they were pressed
+    pressed_buttons: Vec<MouseButton>,
+    /// The button, instant and cursor's position of the last press that may start a
+    /// double-click
+    last_press: Option<(MouseButton, Instant, Option<Vector2D>)>,
+    /// The maximum time between the presses of a double-click
+    double_click_interval: Duration,
+    /// Whether text font sizes are interpreted in logical points and converted
+    /// to device pixels according to the scale factor
+    font_scaling: bool,
+    /// The realized geometry (i.e., position and size after layout) of every
+    /// widget built, having the widget's identifier as key
+    geometry: HashMap<usize, Rect>,
+    /// The tab index of every widget built that takes part in the focus traversal,
+    /// having the widget's identifier as key
+    tab_stops: HashMap<usize, TabIndex>,
+    /// The widgets confining the keyboard and the focus traversal (e.g. dialogs), the
+    /// last one being active
+    modal_scopes: Vec<Weak<RefCell<dyn Widget>>>,
+    /// The number of modal scopes being built, nested in each other (i.e., greater than
+    /// zero while building the active modal scope's descendants)
+    modal_depth: usize,
+    /// The widgets drawn above the widget tree and grabbing every event (e.g. dialogs),
+    /// the last one being on top
+    popups: Vec<Weak<RefCell<dyn Widget>>>,
+    /// The popups closed, whose render instructions are yet to be removed
+    closed_popups: Vec<Weak<RefCell<dyn Widget>>>,
+    /// The items of the context menu of every widget registered, opened when the
+    /// widget is right-clicked
+    context_menus: Vec<ContextMenuRegistration>,
+    /// The context menu opened as a popup when a registered widget is right-clicked
+    context_menu: Rc<RefCell<ContextMenuWidget>>,
+    /// The identifier of the overlay instructions (i.e., drawn over the children)
+    /// of every widget built, having the widget's identifier as key
+    overlays: HashMap<usize, usize>,
+    /// The memo key (i.e., hash of the recipe's inputs) of every memoized widget
+    /// built, having the widget's identifier as key
+    memos: HashMap<usize, u64>,
+    /// Whether the next widget built must be moved to a new identifier, even if its
+    /// render instructions are memoized
+    is_moving: bool,
+    /// The requests to be forwarded to the display during the current frame
+    window_requests: Vec<WindowRequest>,
+    /// Whether the user's attention was requested and not yet acknowledged
+    is_attention_requested: bool,
+    /// The shapes clipping the widgets being built, pushed by their ancestors
+    clip_stack: Vec<ClipShape>,
+    /// The transforms of the widgets being built, pushed by their ancestors, along with
+    /// the number of shapes clipping them when pushed
+    transform_stack: Vec<(usize, Transform)>,
+    /// The opacities of the widgets being built, each multiplied by the ones of their
+    /// ancestors
+    opacity_stack: Vec<f64>,
+    /// Whether the renderer draws groups of instructions with an opacity
+    supports_opacity: bool,
+    /// The blend modes composited by the renderer
+    supported_blend_modes: Vec<BlendMode>,
+    /// The image atlases registered, having their handle as index
+    atlases: Vec<ImageAtlas>,
+    /// The number of atlases already loaded by the renderer
+    loaded_atlases: usize,
+    /// The images and fonts loaded once by the renderer
+    resources: ResourceRegistry,
+    /// The stable identifier (i.e., kept across rebuilds) of every widget and overlay
+    /// built, having the identifier as key, if stable identifiers are enabled
+    stable_ids: Option<HashMap<usize, u64>>,
+    /// The stable identifier of the widgets whose children are being built, along
+    /// with the index of each child, having the child's address as key
+    stable_scopes: Vec<(u64, HashMap<usize, usize>)>,
+    /// The animations of the widgets' properties, ticked on every frame
+    animations: AnimationController,
+}
+
+impl Context {
+    /// Creates a new `Context`
+    ///
+    /// # Returns
+    /// An empty context, with no frames drawn
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> Context {
+        Context {
+            frame: 0,
+            frame_callbacks: Vec::new(),
+            before_frame_hooks: Vec::new(),
+            after_frame_hooks: Vec::new(),
+            next_hook_id: 0,
+            removed_hooks: Vec::new(),
+            last_frame_time: Duration::from_secs(0),
+            idle_policy: IdlePolicy::Continuous,
+            control_flow: ControlFlow::Poll,
+            needs_redraw: false,
+            wake_up: None,
+            display_size: None,
+            frame_interval: None,
+            vsync: false,
+            is_idle: false,
+            idle_interval: None,
+            last_idle_frame: clock::now(),
+            panic_handler: None,
+            panics: Vec::new(),
+            failed_widgets: HashSet::new(),
+            frame_budget: None,
+            long_frame_handler: None,
+            widget_timings: Vec::new(),
+            watchdog: None,
+            overflow_diagnostics: false,
+            overflow_handler: None,
+            overflows: Vec::new(),
+            pixel_snapping: false,
+            scale_factor: 1.,
+            font_scaling: true,
+            layout_direction: LayoutDirection::LeftToRight,
+            needs_rebuild: false,
+            event_routing: EventRouting::default(),
+            hovered_path: Vec::new(),
+            pressed_buttons: Vec::new(),
+            last_press: None,
+            double_click_interval: DOUBLE_CLICK_INTERVAL,
+            geometry: HashMap::new(),
+            tab_stops: HashMap::new(),
+            modal_scopes: Vec::new(),
+            modal_depth: 0,
+            popups: Vec::new(),
+            closed_popups: Vec::new(),
+            context_menus: Vec::new(),
+            context_menu: Rc::new(RefCell::new(ContextMenuWidget::default())),
+            overlays: HashMap::new(),
+            memos: HashMap::new(),
+            is_moving: false,
+            window_requests: Vec::new(),
+            is_attention_requested: false,
+            clip_stack: Vec::new(),
+            transform_stack: Vec::new(),
+            opacity_stack: Vec::new(),
+            supports_opacity: false,
+            supported_blend_modes: vec![BlendMode::Normal],
+            atlases: Vec::new(),
+            loaded_atlases: 0,
+            resources: ResourceRegistry::new(),
+            stable_ids: None,
+            stable_scopes: Vec::new(),
+            animations: AnimationController::new(),
+        }
+    }
+
+    /// Gets the number of frames drawn so far
+    ///
+    /// # Returns
+    /// The number of frames drawn so far
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Requests a one-shot callback to be executed right after the next frame is drawn
+    ///
+    /// Useful to measure realized sizes of widgets, take a capture of the display
+    /// or start an animation once the widgets are on the display. Callbacks requested
+    /// while executing a frame callback are executed after the following frame.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `callback` - the callback to be executed after the next frame
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// context.borrow_mut().request_frame_callback(Box::new(|info: &FrameInfo| {
+    ///     println!("Frame {} took {:?}", info.frame, info.frame_time);
+    /// }));
+    /// ```
+    pub fn request_frame_callback(&mut self, callback: FrameCallback) {
+        self.frame_callbacks.push(callback);
+    }
+
+    /// Adds a hook to be executed on every frame, before the events are handled
+    ///
+    /// Useful to integrate polling or game logic into the event loop. The frame
+    /// information given to the hook has the number of the frame about to be drawn
+    /// and the time taken by the previous frame.
+    ///
+    /// # Returns
+    /// The identifier of the hook, used to remove it
+    ///
+    /// # Arguments
+    /// * `hook` - the hook to be executed before every frame
+    pub fn add_before_frame_hook(&mut self, hook: FrameHook) -> usize {
+        self.next_hook_id += 1;
+        self.before_frame_hooks.push((self.next_hook_id, hook));
+        self.next_hook_id
+    }
+
+    /// Adds a hook to be executed on every frame, right after the frame is drawn
+    ///
+    /// Useful to collect metrics about the frames drawn. Unlike the frame callbacks,
+    /// the hook is kept until removed.
+    ///
+    /// # Returns
+    /// The identifier of the hook, used to remove it
+    ///
+    /// # Arguments
+    /// * `hook` - the hook to be executed after every frame
+    pub fn add_after_frame_hook(&mut self, hook: FrameHook) -> usize {
+        self.next_hook_id += 1;
+        self.after_frame_hooks.push((self.next_hook_id, hook));
+        self.next_hook_id
+    }
+
+    /// Removes a frame hook, so that it is no longer executed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the hook, as returned when it was added
+    pub fn remove_frame_hook(&mut self, id: usize) {
+        let count = self.before_frame_hooks.len() + self.after_frame_hooks.len();
+        self.before_frame_hooks.retain(|(hook_id, _)| *hook_id != id);
+        self.after_frame_hooks.retain(|(hook_id, _)| *hook_id != id);
+        if count == self.before_frame_hooks.len() + self.after_frame_hooks.len() {
+            // The hook may be executing, so it is removed once the hooks are put back
+            self.removed_hooks.push(id);
+        }
+    }
+
+    /// Sets how the event loop behaves while the window is idle
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `idle_policy` - the policy to be used while the window is unfocused or minimized
+    pub fn set_idle_policy(&mut self, idle_policy: IdlePolicy) {
+        self.idle_policy = idle_policy;
+    }
+
+    /// Checks if the window is idle (i.e., unfocused or minimized)
+    ///
+    /// # Returns
+    /// True, if the window is idle, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_idle(&self) -> bool {
+        self.is_idle
+    }
+
+    /// Sets how the event loop proceeds between frames (e.g. [`ControlFlow::Exit`] to
+    /// stop it from a message)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `control_flow` - the control flow of the event loop
+    pub fn set_control_flow(&mut self, control_flow: ControlFlow) {
+        self.control_flow = control_flow;
+    }
+
+    /// Gets how the event loop proceeds between frames
+    ///
+    /// # Returns
+    /// The control flow of the event loop
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn control_flow(&self) -> ControlFlow {
+        self.control_flow
+    }
+
+    /// Sets how the mouse events are dispatched to the widgets
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event_routing` - the routing of the mouse events
+    pub fn set_event_routing(&mut self, event_routing: EventRouting) {
+        self.event_routing = event_routing;
+        self.hovered_path.clear();
+    }
+
+    /// Sets the maximum time between the presses of a double-click (e.g. to follow the
+    /// platform's setting)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `double_click_interval` - the maximum time between the presses of a double-click
+    pub fn set_double_click_interval(&mut self, double_click_interval: Duration) {
+        self.double_click_interval = double_click_interval;
+    }
+
+    /// Gets the maximum time between the presses of a double-click
+    ///
+    /// # Returns
+    /// The maximum time between the presses of a double-click
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn double_click_interval(&self) -> Duration {
+        self.double_click_interval
+    }
+
+    /// Gets how the mouse events are dispatched to the widgets
+    ///
+    /// # Returns
+    /// The routing of the mouse events
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn event_routing(&self) -> EventRouting {
+        self.event_routing
+    }
+
+    /// Checks whether an event is only dispatched to the widgets under the cursor, for
+    /// internal use only
+    ///
+    /// # Returns
+    /// True, if the event is routed by hit test, false if it is broadcast
+    ///
+    /// # Arguments
+    /// * `event` - the event about to be dispatched
+    pub(crate) fn is_hit_test_routed(&self, event: &Event) -> bool {
+        self.event_routing == EventRouting::HitTest
+            && matches!(
+                event,
+                Event::Mouse(
+                    Mouse::CursorMoved { .. }
+                        | Mouse::ButtonPressed(_)
+                        | Mouse::ButtonReleased(_)
+                        | Mouse::WheelScrolled { .. }
+                        | Mouse::Chord { .. }
+                        | Mouse::ButtonDoubleClicked(_)
+                )
+            )
+    }
+
+    /// Replaces the widgets under the cursor as of its last move, for internal use only
+    ///
+    /// # Returns
+    /// The widgets that were under the cursor and no longer are, from the root down
+    ///
+    /// # Arguments
+    /// * `path` - the widgets now under the cursor, from the root down to the topmost one
+    pub(crate) fn replace_hovered_path(
+        &mut self,
+        path: &[Rc<RefCell<dyn Widget>>],
+    ) -> Vec<Rc<RefCell<dyn Widget>>> {
+        let left = self
+            .hovered_path
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|widget| !path.iter().any(|other| Rc::ptr_eq(widget, other)))
+            .collect();
+        self.hovered_path = path.iter().map(Rc::downgrade).collect();
+        left
+    }
+
+    /// Requests a frame to be drawn, even if the event loop is waiting (e.g. after
+    /// changing a widget outside of the event handling)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    /// Requests a frame to be drawn once an instant is reached, even if the event loop
+    /// is waiting (e.g. to show a tooltip once its delay elapsed)
+    ///
+    /// Only the earliest instant requested is kept until its frame is drawn, such that
+    /// widgets requesting later ones request them again from that frame.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instant` - the instant the frame is drawn at
+    pub fn request_redraw_at(&mut self, instant: Instant) {
+        self.wake_up = Some(match self.wake_up {
+            Some(wake_up) => wake_up.min(instant),
+            None => instant,
+        });
+    }
+
+    /// Gets the display's size, as of the last frame
+    ///
+    /// # Returns
+    /// The display's size (width and height), or `None` before the first frame
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn display_size(&self) -> Option<Vector2D> {
+        self.display_size
+    }
+
+    /// Sets the display's size the frame is built at, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `display_size` - the display's size (width and height)
+    pub(crate) fn set_display_size(&mut self, display_size: Vector2D) {
+        self.display_size = Some(display_size);
+    }
+
+    /// Sets the maximum number of frames drawn per second, unless they are paced by vsync
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `frame_rate` - the target frame rate, or `None` to draw frames as fast as possible
+    pub fn set_target_frame_rate(&mut self, frame_rate: Option<u32>) {
+        self.frame_interval = frame_rate
+            .filter(|frame_rate| *frame_rate > 0)
+            .map(|frame_rate| Duration::from_secs(1) / frame_rate);
+    }
+
+    /// Gets the minimum time between the start of two frames
+    ///
+    /// # Returns
+    /// The interval of the target frame rate, or `None` if the frame rate is not limited
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn frame_interval(&self) -> Option<Duration> {
+        self.frame_interval
+    }
+
+    /// Sets whether the frames are paced by waiting for the display's vertical blank
+    /// (see [`Display::wait_vsync`](`crate::display::Display::wait_vsync`)), falling
+    /// back to the target frame rate on displays without vsync
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `vsync` - whether to wait for the vertical blank after every frame
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.vsync = vsync;
+    }
+
+    /// Checks whether the frames are paced by waiting for the display's vertical blank
+    ///
+    /// # Returns
+    /// True, if vsync is enabled, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_vsync(&self) -> bool {
+        self.vsync
+    }
+
+    /// Waits between frames according to the control flow, for internal use only
+    ///
+    /// # Returns
+    /// True, if a frame must be drawn, false if the event loop keeps waiting
+    ///
+    /// # Arguments
+    /// * `events` - queue of the events received since the last frame
+    pub(crate) fn wait_control_flow(&mut self, events: &Queue<Event>) -> bool {
+        let deadline = match self.control_flow {
+            ControlFlow::Poll | ControlFlow::Exit => return true,
+            ControlFlow::Wait => None,
+            ControlFlow::WaitUntil(deadline) => Some(deadline),
+        };
+        if !events.is_empty()
+            || self.needs_rebuild
+            || self.animations.is_animating()
+            || std::mem::take(&mut self.needs_redraw)
+        {
+            return true;
+        }
+        let now = clock::now();
+        if self.wake_up.is_some_and(|wake_up| now >= wake_up) {
+            self.wake_up = None;
+            return true;
+        }
+        match deadline {
+            Some(deadline) if now >= deadline => {
+                self.control_flow = ControlFlow::Wait;
+                true
+            }
+            Some(deadline) => {
+                thread::sleep((deadline - now).min(WAIT_POLL_INTERVAL));
+                false
+            }
+            None => {
+                thread::sleep(WAIT_POLL_INTERVAL);
+                false
+            }
+        }
+    }
+
+    /// Sleeps for the rest of the target frame rate's interval, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `frame_start` - the instant when the frame started
+    /// * `is_vsynced` - whether the display already waited for its vertical blank
+    pub(crate) fn pace_frame(&self, frame_start: Instant, is_vsynced: bool) {
+        if let Some(frame_interval) = self.frame_interval.filter(|_| !is_vsynced) {
+            if let Some(remaining) = frame_interval.checked_sub(frame_start.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+    }
+
+    /// Sets the clock from which the widgets read the time (e.g. a
+    /// [`VirtualClock`](`crate::clock::VirtualClock`) for tests, replays or headless rendering)
+    ///
+    /// The clock is shared by every widget on the current thread, such that widgets
+    /// measuring time (e.g. long presses) do not need a pointer to the context.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `clock` - the clock from which the time is read
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        clock::set_clock(clock);
+        self.last_idle_frame = clock::now();
+    }
+
+    /// Gets the current time, read from the clock set on the context
+    ///
+    /// # Returns
+    /// The current time
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn now(&self) -> Instant {
+        clock::now()
+    }
+
+    /// Detects the gestures made of several events (i.e., mouse button chords, double-clicks
+    /// and acknowledged attention requests) and adds their events to the queue, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `events` - queue of events detected on the current iteration
+    pub(crate) fn detect_gestures(&mut self, events: &mut Queue<Event>) {
+        let mut detected = Vec::with_capacity(events.queue.len());
+        // The events of the queue are not tracked yet
+        let mut cursor = event::pointer_position();
+        for event in events.queue.drain(..) {
+            detected.push(event);
+            match event {
+                Event::Mouse(Mouse::CursorMoved { x, y }) => {
+                    cursor = Some(Vector2D::new(x as f64, y as f64));
+                }
+                Event::Mouse(Mouse::ButtonPressed(button)) => {
+                    let now = clock::now();
+                    let is_double_click = self.last_press.is_some_and(|(last, instant, position)| {
+                        last == button
+                            && now.duration_since(instant) <= self.double_click_interval
+                            && match (position, cursor) {
+                                (Some(position), Some(cursor)) => {
+                                    (position.x - cursor.x).abs() <= DOUBLE_CLICK_DISTANCE
+                                        && (position.y - cursor.y).abs() <= DOUBLE_CLICK_DISTANCE
+                                }
+                                _ => position == cursor,
+                            }
+                    });
+                    if is_double_click {
+                        // A third press starts a new double-click
+                        self.last_press = None;
+                        detected.push(Event::Mouse(Mouse::ButtonDoubleClicked(button)));
+                    } else {
+                        self.last_press = Some((button, now, cursor));
+                    }
+                    if let Some(first) = self.pressed_buttons.last() {
+                        if *first != button {
+                            detected.push(Event::Mouse(Mouse::Chord {
+                                first: *first,
+                                second: button,
+                            }));
+                        }
+                    }
+                    if !self.pressed_buttons.contains(&button) {
+                        self.pressed_buttons.push(button);
+                    }
+                }
+                Event::Mouse(Mouse::ButtonReleased(button)) => {
+                    self.pressed_buttons.retain(|pressed| *pressed != button);
+                }
+                // The user focused the window after its attention was requested
+                Event::Window(Window::Focused(true)) if self.is_attention_requested => {
+                    self.is_attention_requested = false;
+                    detected.push(Event::Window(Window::AttentionAcknowledged));
+                }
+                // Releases are not reported outside of the window
+                Event::Mouse(Mouse::CursorLeft) | Event::Window(Window::Focused(false)) => {
+                    self.pressed_buttons.clear();
+                    self.last_press = None;
+                }
+                _ => {}
+            }
+        }
+        events.queue = detected;
+    }
+
+    /// Updates the idle state according to the window events and waits as
+    /// stated by the idle policy, for internal use only
+    ///
+    /// # Returns
+    /// True, if a frame should be drawn, false otherwise
+    ///
+    /// # Arguments
+    /// * `events` - queue of events detected on the current iteration
+    pub(crate) fn wait_idle(&mut self, events: &Queue<Event>) -> bool {
+        for event in events.queue.iter() {
+            match event {
+                Event::Window(Window::Focused(false)) | Event::Window(Window::Minimized) => {
+                    self.is_idle = true;
+                }
+                Event::Window(Window::Focused(true)) | Event::Window(Window::Restored) => {
+                    self.is_idle = false;
+                }
+                _ => {}
+            }
+        }
+
+        // Any event wakes the event loop up and resets the backoff
+        if !self.is_idle || !events.is_empty() {
+            self.idle_interval = None;
+            return true;
+        }
+
+        match self.idle_policy {
+            IdlePolicy::Continuous => true,
+            IdlePolicy::Backoff { initial, max } => {
+                let interval = match self.idle_interval {
+                    Some(interval) => (interval * 2).min(max),
+                    None => initial,
+                };
+                self.idle_interval = Some(interval);
+                thread::sleep(interval);
+                true
+            }
+            IdlePolicy::Suspend {
+                poll_interval,
+                wake_interval,
+            } => {
+                thread::sleep(poll_interval);
+                match wake_interval {
+                    Some(wake_interval) if clock::elapsed(self.last_idle_frame) >= wake_interval => {
+                        self.last_idle_frame = clock::now();
+                        true
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Sets the callback that receives the panics caught on widgets
+    ///
+    /// When a panic handler is set, panics on a widget's `on_event`, `on_frame` or
+    /// `recipe` are caught instead of crashing the application: the offending widget is
+    /// marked as failed (i.e., it gets no more events nor frames and renders an error
+    /// placeholder until it is rebuilt), the other widgets still get the event, and the
+    /// panic is reported to the handler after the frame is drawn.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `panic_handler` - the callback to receive the panics, or `None` to disable the guard
+    pub fn set_panic_handler(&mut self, panic_handler: Option<PanicHandler>) {
+        self.panic_handler = panic_handler;
+    }
+
+    /// Checks if panics on widgets are caught by the event loop
+    ///
+    /// # Returns
+    /// True, if there is a panic handler, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_panic_guarded(&self) -> bool {
+        self.panic_handler.is_some()
+    }
+
+    /// Stores a panic caught on a widget to be reported after the frame, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget_panic` - the panic caught
+    pub(crate) fn report_panic(&mut self, widget_panic: WidgetPanic) {
+        self.panics.push(widget_panic);
+    }
+
+    /// Starts delivering the events and frames to the widgets, catching their panics
+    /// if the panic guard is enabled and skipping the ones failed, until
+    /// [`report_caught_panics`](`Context::report_caught_panics`), for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn begin_widget_calls(&mut self) {
+        let failed_widgets = std::mem::take(&mut self.failed_widgets);
+        widget::begin_guarded_calls(self.is_panic_guarded(), failed_widgets);
+    }
+
+    /// Ends delivering the events and frames to the widgets, storing the panics caught
+    /// meanwhile to be reported after the frame and drawing an error placeholder in
+    /// place of each widget that panicked, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `collection` - the render instructions collection drawn
+    pub(crate) fn report_caught_panics(&mut self, collection: &mut RenderInstructionCollection) {
+        let (panics, failed_widgets) = widget::end_guarded_calls();
+        self.failed_widgets = failed_widgets;
+        for widget_panic in panics {
+            if let Some(area) = self.geometry(widget_panic.widget_id) {
+                collection.replace_or_insert(
+                    widget_panic.widget_id,
+                    Context::error_placeholder(area.position, area.size),
+                );
+            }
+            self.report_panic(widget_panic);
+        }
+    }
+
+    /// Gets the render instructions of a widget, replacing them by an error placeholder
+    /// if the widget panics and the panic guard is enabled, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the widget, or the error placeholder
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `position` - the position of the widget
+    /// * `size` - the size of the widget
+    /// * `recipe` - the function that generates the render instructions of the widget
+    pub(crate) fn guard_recipe<F>(
+        &mut self,
+        id: usize,
+        position: Vector2D,
+        size: Vector2D,
+        recipe: F,
+    ) -> Vec<RenderInstruction>
+    where
+        F: FnOnce() -> Vec<RenderInstruction>,
+    {
+        // The widget is being rebuilt, so it is no longer failed unless it panics again
+        self.forget_failure(id);
+        if !self.is_panic_guarded() {
+            return recipe();
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(recipe)) {
+            Ok(instructions) => instructions,
+            Err(payload) => {
+                self.failed_widgets.insert(id);
+                self.report_panic(WidgetPanic::new(id, WidgetPhase::Recipe, payload));
+                Context::error_placeholder(position, size)
+            }
+        }
+    }
+
+    /// Forgets a widget failed, once it is rebuilt, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the widget's identifier
+    pub(crate) fn forget_failure(&mut self, id: usize) {
+        self.failed_widgets.remove(&id);
+    }
+
+    /// Gets the render instructions of the placeholder drawn in place of a failed
+    /// widget (i.e., a red rectangle crossed out)
+    ///
+    /// # Returns
+    /// The render instructions of the error placeholder
+    ///
+    /// # Arguments
+    /// * `position` - the position of the failed widget
+    /// * `size` - the size of the failed widget
+    fn error_placeholder(position: Vector2D, size: Vector2D) -> Vec<RenderInstruction> {
+        let color = Color::from_hex(0xffffffff);
+        vec![
+            RenderInstruction::DrawRect {
+                point: position,
+                size,
+                color: Color::from_hex(0xffd32f2f),
+                clip_point: position,
+                clip_size: size,
+                blend_mode: BlendMode::Normal,
+            },
+            RenderInstruction::DrawLine {
+                point_a: position,
+                point_b: position + size,
+                color,
+                clip_point: position,
+                clip_size: size,
+            },
+            RenderInstruction::DrawLine {
+                point_a: Vector2D::new(position.x + size.x, position.y),
+                point_b: Vector2D::new(position.x, position.y + size.y),
+                color,
+                clip_point: position,
+                clip_size: size,
+            },
+        ]
+    }
+
+    /// Reports the panics caught during the frame to the panic handler, for internal use only
+    ///
+    /// The handler is taken out of the context while it is executed, so it can
+    /// borrow the context itself.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the context holding the panics and the handler
+    pub(crate) fn dispatch_panics(context: &Rc<RefCell<Context>>) {
+        let panics = std::mem::take(&mut context.borrow_mut().panics);
+        if panics.is_empty() {
+            return;
+        }
+
+        let handler = context.borrow_mut().panic_handler.take();
+        if let Some(mut handler) = handler {
+            for widget_panic in panics.iter() {
+                handler(widget_panic);
+            }
+            // Restore the handler, unless a new one was set meanwhile
+            let mut context = context.borrow_mut();
+            if context.panic_handler.is_none() {
+                context.panic_handler = Some(handler);
+            }
+        }
+    }
+
+    /// Reports the widgets overflowing during the frame to the overflow handler, for
+    /// internal use only
+    ///
+    /// The handler is taken out of the context while it is executed, so it can
+    /// borrow the context itself.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the context holding the overflows and the handler
+    pub(crate) fn dispatch_overflows(context: &Rc<RefCell<Context>>) {
+        let overflows = std::mem::take(&mut context.borrow_mut().overflows);
+        if overflows.is_empty() {
+            return;
+        }
+
+        let handler = context.borrow_mut().overflow_handler.take();
+        if let Some(mut handler) = handler {
+            for overflow in overflows.iter() {
+                handler(overflow);
+            }
+            // Restore the handler, unless a new one was set meanwhile
+            let mut context = context.borrow_mut();
+            if context.overflow_handler.is_none() {
+                context.overflow_handler = Some(handler);
+            }
+        }
+    }
+
+    /// Sets the maximum time to build the widgets and draw a frame
+    ///
+    /// When a frame exceeds the budget, a [`LongFrame`] diagnostic listing the slowest
+    /// widgets rebuilt is given to the long frame handler (see
+    /// [`set_long_frame_handler`](`Context::set_long_frame_handler`)), or dropped if
+    /// there is none. The widgets are only timed while there is a budget.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `frame_budget` - the budget of every frame, or `None` to disable the diagnostics
+    pub fn set_frame_budget(&mut self, frame_budget: Option<Duration>) {
+        self.frame_budget = frame_budget;
+    }
+
+    /// Gets the maximum time to build the widgets and draw a frame
+    ///
+    /// # Returns
+    /// The budget of every frame, or `None` if the diagnostics are disabled
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn frame_budget(&self) -> Option<Duration> {
+        self.frame_budget
+    }
+
+    /// Sets the callback that receives the diagnostics of the frames exceeding the
+    /// frame budget (e.g. to forward them to the application's logs or metrics)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `long_frame_handler` - the callback receiving the diagnostics, or `None` to drop them
+    pub fn set_long_frame_handler(&mut self, long_frame_handler: Option<LongFrameHandler>) {
+        self.long_frame_handler = long_frame_handler;
+    }
+
+    /// Sets the watchdog diagnosing the frames exceeding its threshold, including the
+    /// updates of the messages (e.g. a message handler stuck in a loop)
+    ///
+    /// Unlike the frame budget, the watchdog checks the frame in progress on its own
+    /// thread, so a frame that never ends is diagnosed while it stalls.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `watchdog` - the watchdog, or `None` to stop watching the frames
+    pub fn set_watchdog(&mut self, watchdog: Option<Watchdog>) {
+        if let Some(watchdog) = watchdog.as_ref() {
+            watchdog.set_next_frame(self.frame + 1);
+        }
+        self.watchdog = watchdog;
+    }
+
+    /// Gets the watchdog diagnosing the frames exceeding its threshold
+    ///
+    /// # Returns
+    /// The watchdog, or `None` if the frames are not watched
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn watchdog(&self) -> Option<&Watchdog> {
+        self.watchdog.as_ref()
+    }
+
+    /// Enters a phase of the frame in progress, if there is a watchdog, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `phase` - the phase entered
+    pub(crate) fn enter_frame_phase(&self, phase: FramePhase) {
+        if let Some(watchdog) = self.watchdog.as_ref() {
+            watchdog.enter_phase(phase);
+        }
+    }
+
+    /// Sets the widget being processed, if there is a watchdog, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget_id` - the identifier of the widget being processed
+    pub(crate) fn watch_widget(&self, widget_id: usize) {
+        if let Some(watchdog) = self.watchdog.as_ref() {
+            watchdog.set_widget(Some(widget_id));
+        }
+    }
+
+    /// Ends the frame in progress, if there is a watchdog, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn end_watched_frame(&self) {
+        if let Some(watchdog) = self.watchdog.as_ref() {
+            watchdog.end_frame();
+        }
+    }
+
+    /// Records the time spent rebuilding a widget, if there is a frame budget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget_id` - the identifier of the widget rebuilt
+    /// * `build_time` - the time taken to rebuild the widget itself, excluding its children
+    /// * `recipe_time` - the time taken to generate the widget's render instructions
+    pub(crate) fn record_widget_timing(
+        &mut self,
+        widget_id: usize,
+        build_time: Duration,
+        recipe_time: Duration,
+    ) {
+        if self.frame_budget.is_some() {
+            self.widget_timings.push(WidgetTiming {
+                widget_id,
+                build_time,
+                recipe_time,
+            });
+        }
+    }
+
+    /// Emits a diagnostic if the frame exceeded the frame budget, for internal use only
+    ///
+    /// The handler is taken out of the context while it is executed, so it can
+    /// borrow the context itself.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the context holding the frame budget and the handler
+    /// * `frame` - the number of the frame
+    /// * `build_and_draw_time` - the time taken to build the widgets and draw the frame
+    pub(crate) fn check_frame_budget(
+        context: &Rc<RefCell<Context>>,
+        frame: u64,
+        build_and_draw_time: Duration,
+    ) {
+        let mut widget_timings = std::mem::take(&mut context.borrow_mut().widget_timings);
+        let budget = match context.borrow().frame_budget {
+            Some(budget) if build_and_draw_time > budget => budget,
+            _ => return,
+        };
+
+        widget_timings.sort_by_key(|timing| Reverse(timing.build_time));
+        widget_timings.truncate(SLOWEST_WIDGETS);
+        let long_frame = LongFrame {
+            frame,
+            build_and_draw_time,
+            budget,
+            slowest_widgets: widget_timings,
+        };
+
+        let handler = context.borrow_mut().long_frame_handler.take();
+        if let Some(mut handler) = handler {
+            handler(&long_frame);
+            // Restore the handler, unless a new one was set meanwhile
+            let mut context = context.borrow_mut();
+            if context.long_frame_handler.is_none() {
+                context.long_frame_handler = Some(handler);
+            }
+        }
+    }
+
+    /// Sets whether the widgets whose desired size exceeds the size assigned by their
+    /// parent are diagnosed
+    ///
+    /// Each overflowing widget is reported, whenever its parent is rebuilt, as a
+    /// [`LayoutOverflow`] given to the overflow handler (see
+    /// [`set_overflow_handler`](`Context::set_overflow_handler`)), or dropped if there
+    /// is none. On debug builds, a striped marker is also drawn along the overflowing
+    /// edges of the widget.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `overflow_diagnostics` - whether to diagnose the overflowing widgets
+    pub fn set_overflow_diagnostics(&mut self, overflow_diagnostics: bool) {
+        self.overflow_diagnostics = overflow_diagnostics;
+        self.needs_rebuild = true;
+    }
+
+    /// Gets whether the widgets overflowing their constraints are diagnosed
+    ///
+    /// # Returns
+    /// True if the overflowing widgets are diagnosed, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_overflow_diagnostics(&self) -> bool {
+        self.overflow_diagnostics
+    }
+
+    /// Sets the callback that receives the diagnostics of the widgets overflowing
+    /// their constraints (e.g. to forward them to the application's logs)
+    ///
+    /// The diagnostics are given to the handler once the frame is drawn, so it can
+    /// borrow the context itself.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `overflow_handler` - the callback receiving the diagnostics, or `None` to drop them
+    pub fn set_overflow_handler(&mut self, overflow_handler: Option<OverflowHandler>) {
+        self.overflow_handler = overflow_handler;
+    }
+
+    /// Reports a widget overflowing its constraints, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the striped marker to be drawn along the overflowing
+    /// edges of the widget, empty on release builds
+    ///
+    /// # Arguments
+    /// * `position` - the position of the area assigned to the widget
+    /// * `overflow` - the diagnostic of the overflowing widget
+    pub(crate) fn report_overflow(
+        &mut self,
+        position: Vector2D,
+        overflow: &LayoutOverflow,
+    ) -> Vec<RenderInstruction> {
+        if self.overflow_handler.is_some() {
+            self.overflows.push(*overflow);
+        }
+
+        let mut instructions = Vec::new();
+        if !cfg!(debug_assertions) {
+            return instructions;
+        }
+        let size = overflow.assigned_size;
+        let amount = overflow.overflow();
+        // Alternate yellow and black stripes along the right and bottom edges
+        let mut stripe = |point: Vector2D, stripe_size: Vector2D, index: usize| {
+            let colors = [Color::from_hex(0xffffeb3b), Color::from_hex(0xff000000)];
+            instructions.push(RenderInstruction::DrawRect {
+                point,
+                size: stripe_size,
+                color: colors[index % 2],
+                clip_point: position,
+                clip_size: size,
+                blend_mode: BlendMode::Normal,
+            });
+        };
+        if amount.x > 0. {
+            let x = position.x + (size.x - OVERFLOW_MARKER_WIDTH).max(0.);
+            let stripes = (size.y / OVERFLOW_MARKER_WIDTH).ceil() as usize;
+            for index in 0..stripes {
+                stripe(
+                    Vector2D::new(x, position.y + index as f64 * OVERFLOW_MARKER_WIDTH),
+                    Vector2D::new(OVERFLOW_MARKER_WIDTH, OVERFLOW_MARKER_WIDTH),
+                    index,
+                );
+            }
+        }
+        if amount.y > 0. {
+            let y = position.y + (size.y - OVERFLOW_MARKER_WIDTH).max(0.);
+            let stripes = (size.x / OVERFLOW_MARKER_WIDTH).ceil() as usize;
+            for index in 0..stripes {
+                stripe(
+                    Vector2D::new(position.x + index as f64 * OVERFLOW_MARKER_WIDTH, y),
+                    Vector2D::new(OVERFLOW_MARKER_WIDTH, OVERFLOW_MARKER_WIDTH),
+                    index,
+                );
+            }
+        }
+        instructions
+    }
+
+    /// Sets whether the render instructions of the widgets are snapped to device
+    /// pixels, unless a widget states otherwise
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap the render instructions to device pixels
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: bool) {
+        self.pixel_snapping = pixel_snapping;
+    }
+
+    /// Checks if the render instructions of the widgets are snapped to device pixels by default
+    ///
+    /// # Returns
+    /// True, if the render instructions are snapped by default, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_pixel_snapping(&self) -> bool {
+        self.pixel_snapping
+    }
+
+    /// Sets the number of device pixels per logical unit of the display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `scale_factor` - the number of device pixels per logical unit (e.g. 2 on HiDPI displays)
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if self.font_scaling && self.scale_factor != scale_factor {
+            self.needs_rebuild = true;
+        }
+        self.scale_factor = scale_factor;
+    }
+
+    /// Gets the number of device pixels per logical unit of the display
+    ///
+    /// # Returns
+    /// The number of device pixels per logical unit
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Applies the passes shared by every render instruction of a widget (e.g. pixel
+    /// snapping, disabled styling, opacity and clipping), in the order they depend on
+    /// each other, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    /// * `widget` - the widget whose render instructions are finished
+    pub(crate) fn finish_recipe<W: Widget + ?Sized>(
+        &self,
+        instructions: &mut Vec<RenderInstruction>,
+        widget: &W,
+    ) {
+        // Snap the render instructions to device pixels, if needed
+        self.snap_recipe(instructions, widget.pixel_snapping());
+        // Convert the font sizes from logical points to device pixels, if needed
+        self.resolve_font_sizes(instructions);
+        // Append the font fallback chain to the fonts of the texts, if any
+        self.resolve_font_fallbacks(instructions);
+        // Pick the resolution of the images matching the scale factor
+        self.resolve_image_sources(instructions);
+        self.resolve_atlas_regions(instructions);
+        // Adjust the colors of the render instructions if the widget is disabled
+        self.style_disabled(instructions, widget.is_disabled());
+        // Apply the opacity of the widget and its ancestors, if translucent
+        self.fade_recipe(instructions);
+        // Fall back to the normal blend mode where the renderer lacks support
+        self.blend_recipe(instructions);
+        // Mirror the images of the widget on right-to-left layouts, if needed
+        self.mirror_recipe(instructions, widget.mirror_in_rtl());
+        // Clip the widget to the shapes of its ancestors, if any
+        self.clip_recipe(instructions);
+    }
+
+    /// Snaps the render instructions of a widget to device pixels, if enabled
+    /// for the widget or globally, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    /// * `widget_pixel_snapping` - the widget's pixel snapping setting, if any
+    pub(crate) fn snap_recipe(
+        &self,
+        instructions: &mut [RenderInstruction],
+        widget_pixel_snapping: Option<bool>,
+    ) {
+        if widget_pixel_snapping.unwrap_or(self.pixel_snapping) {
+            for instruction in instructions.iter_mut() {
+                instruction.snap_to_pixels(self.scale_factor);
+            }
+        }
+    }
+
+    /// Applies the disabled styling of the current theme to the render instructions of
+    /// a widget, if the widget is disabled, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    /// * `is_disabled` - whether the widget is disabled
+    pub(crate) fn style_disabled(&self, instructions: &mut [RenderInstruction], is_disabled: bool) {
+        if !is_disabled {
+            return;
+        }
+        let style = style::with_current_theme(|theme| theme.disabled);
+        for instruction in instructions.iter_mut() {
+            instruction.map_color(|color| {
+                color
+                    .desaturate(style.desaturation)
+                    .with_opacity(style.opacity)
+            });
+        }
+    }
+
+    /// Sets the blend modes composited by the renderer, for internal use only
+    ///
+    /// Every widget is rebuilt on the next frame after the supported modes change.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `blend_modes` - the blend modes supported by the renderer
+    pub(crate) fn set_supported_blend_modes(&mut self, blend_modes: Vec<BlendMode>) {
+        if self.supported_blend_modes != blend_modes {
+            self.needs_rebuild = true;
+        }
+        self.supported_blend_modes = blend_modes;
+    }
+
+    /// Sets whether the renderer draws groups of instructions with an opacity, for
+    /// internal use only
+    ///
+    /// Every widget is rebuilt on the next frame after the support changes.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `supports_opacity` - whether the renderer supports opacity groups
+    pub(crate) fn set_supports_opacity(&mut self, supports_opacity: bool) {
+        if self.supports_opacity != supports_opacity {
+            self.needs_rebuild = true;
+        }
+        self.supports_opacity = supports_opacity;
+    }
+
+    /// Whether the renderer draws groups of instructions with an opacity, such that
+    /// the images of translucent widgets are translucent too
+    ///
+    /// # Returns
+    /// True if opacity groups are supported, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn supports_opacity(&self) -> bool {
+        self.supports_opacity
+    }
+
+    /// Whether the renderer composites fill instructions with a blend mode, such
+    /// that widgets can pick another effect when it does not
+    ///
+    /// # Returns
+    /// True if the blend mode is supported, false otherwise
+    ///
+    /// # Arguments
+    /// * `mode` - the blend mode to check
+    pub fn supports_blend_mode(&self, mode: BlendMode) -> bool {
+        mode == BlendMode::Normal || self.supported_blend_modes.contains(&mode)
+    }
+
+    /// Replaces the blend modes not supported by the renderer with the normal one
+    /// on the render instructions of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn blend_recipe(&self, instructions: &mut [RenderInstruction]) {
+        for instruction in instructions.iter_mut() {
+            if let Some(mode) = instruction.blend_mode() {
+                if !self.supports_blend_mode(mode) {
+                    instruction.set_blend_mode(BlendMode::Normal);
+                }
+            }
+        }
+    }
+
+    /// Sets the direction in which the content of the user interface is read
+    ///
+    /// Every widget is rebuilt on the next frame after the direction changes.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `layout_direction` - the direction in which the content is read
+    pub fn set_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        if self.layout_direction != layout_direction {
+            self.needs_rebuild = true;
+        }
+        self.layout_direction = layout_direction;
+    }
+
+    /// Gets the direction in which the content of the user interface is read
+    ///
+    /// # Returns
+    /// The direction in which the content is read
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn layout_direction(&self) -> LayoutDirection {
+        self.layout_direction
+    }
+
+    /// Mirrors the images of a widget's render instructions, if the widget requests
+    /// it and the layout direction is right-to-left, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    /// * `mirror_in_rtl` - whether the widget's images are mirrored on right-to-left layouts
+    pub(crate) fn mirror_recipe(&self, instructions: &mut [RenderInstruction], mirror_in_rtl: bool) {
+        if !mirror_in_rtl || self.layout_direction != LayoutDirection::RightToLeft {
+            return;
+        }
+        for instruction in instructions.iter_mut() {
+            if let RenderInstruction::DrawImage { mirrored, .. }
+            | RenderInstruction::DrawImageRegion { mirrored, .. }
+            | RenderInstruction::DrawImageData { mirrored, .. }
+            | RenderInstruction::DrawImageHandle { mirrored, .. } = instruction
+            {
+                *mirrored = !*mirrored;
+            }
+        }
+    }
+
+    /// Takes whether every widget needs to be rebuilt, for internal use only
+    ///
+    /// # Returns
+    /// True if every widget needs to be rebuilt since the last call, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_needs_rebuild(&mut self) -> bool {
+        // The memoized render instructions are outdated as well
+        if self.needs_rebuild {
+            self.memos.clear();
+        }
+        std::mem::take(&mut self.needs_rebuild)
+    }
+
+    /// Sets whether text font sizes are interpreted in logical points
+    ///
+    /// When enabled (the default), the font size of every `DrawText` instruction is
+    /// multiplied by the scale factor before reaching the renderer, and the widgets are
+    /// rebuilt whenever the scale factor changes (e.g. the window moves to another monitor).
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `font_scaling` - boolean indicating if font sizes are interpreted in logical points
+    pub fn set_font_scaling(&mut self, font_scaling: bool) {
+        if self.font_scaling != font_scaling && self.scale_factor != 1. {
+            self.needs_rebuild = true;
+        }
+        self.font_scaling = font_scaling;
+    }
+
+    /// Checks if text font sizes are interpreted in logical points
+    ///
+    /// # Returns
+    /// True if font sizes are converted according to the scale factor, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_font_scaling(&self) -> bool {
+        self.font_scaling
+    }
+
+    /// Converts the font sizes of the render instructions of a widget from logical
+    /// points to device pixels, if enabled, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn resolve_font_sizes(&self, instructions: &mut [RenderInstruction]) {
+        if !self.font_scaling || self.scale_factor == 1. {
+            return;
+        }
+        for instruction in instructions.iter_mut() {
+            if let RenderInstruction::DrawText { font_size, .. }
+            | RenderInstruction::DrawTextHandle { font_size, .. } = instruction
+            {
+                *font_size = (*font_size as f64 * self.scale_factor).round() as usize;
+            }
+        }
+    }
+
+    /// Appends the font fallback chain of the current theme to the fonts of the render
+    /// instructions of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn resolve_font_fallbacks(&self, instructions: &mut [RenderInstruction]) {
+        style::with_current_theme(|theme| {
+            for instruction in instructions.iter_mut() {
+                if let RenderInstruction::DrawText { font, .. } = instruction {
+                    for family in theme.font_fallbacks.iter() {
+                        if !font.families().any(|font_family| font_family == family) {
+                            font.fallbacks.push(family.clone());
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Picks the resolution of the images of a widget best matching the number of
+    /// device pixels they are drawn with, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn resolve_image_sources(&self, instructions: &mut [RenderInstruction]) {
+        for instruction in instructions.iter_mut() {
+            if let RenderInstruction::DrawImage {
+                path,
+                density,
+                source,
+                options,
+                ..
+            } = instruction
+            {
+                if let Some(source) = source.take() {
+                    // Images enlarged by a multiplier cover more device pixels
+                    let required_density = match options {
+                        DrawImageOptions::ResizeMultiplyer { mult } => {
+                            self.scale_factor * *mult as f64
+                        }
+                        _ => self.scale_factor,
+                    };
+                    let (variant_density, variant_path) = source.best_match(required_density);
+                    *density = variant_density;
+                    *path = variant_path.to_string();
+                }
+            }
+        }
+    }
+
+    /// Registers an image atlas, such that the renderer loads its image once and the
+    /// widgets draw its regions (e.g. an [`IconWidget`](`crate::widget::icon::IconWidget`)
+    /// with an atlas region)
+    ///
+    /// # Returns
+    /// The handle of the atlas, referred to by its regions
+    ///
+    /// # Arguments
+    /// * `atlas` - the atlas to be registered
+    pub fn register_atlas(&mut self, atlas: ImageAtlas) -> AtlasHandle {
+        self.atlases.push(atlas);
+        AtlasHandle(self.atlases.len() - 1)
+    }
+
+    /// Gets an image atlas registered
+    ///
+    /// # Returns
+    /// The atlas, or `None` if no atlas was registered with the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the atlas
+    pub fn atlas(&self, handle: AtlasHandle) -> Option<&ImageAtlas> {
+        self.atlases.get(handle.0)
+    }
+
+    /// Gets the images and fonts registered to be loaded once by the renderer
+    ///
+    /// # Returns
+    /// The registry of the resources
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn resources(&self) -> &ResourceRegistry {
+        &self.resources
+    }
+
+    /// Gets the images and fonts registered to be loaded once by the renderer, to
+    /// register more (e.g. the icons of a widget when it is created)
+    ///
+    /// # Returns
+    /// The mutable registry of the resources
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn resources_mut(&mut self) -> &mut ResourceRegistry {
+        &mut self.resources
+    }
+
+    /// Takes the atlases registered and not loaded by the renderer yet, for internal use only
+    ///
+    /// # Returns
+    /// The handle and the atlas of every atlas to be loaded
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_unloaded_atlases(&mut self) -> Vec<(AtlasHandle, ImageAtlas)> {
+        let unloaded = self.atlases[self.loaded_atlases..]
+            .iter()
+            .enumerate()
+            .map(|(index, atlas)| (AtlasHandle(self.loaded_atlases + index), atlas.clone()))
+            .collect();
+        self.loaded_atlases = self.atlases.len();
+        unloaded
+    }
+
+    /// Replaces the names of the atlas regions drawn by a widget with their area on
+    /// the atlas, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn resolve_atlas_regions(&self, instructions: &mut [RenderInstruction]) {
+        for instruction in instructions.iter_mut() {
+            if let RenderInstruction::DrawImageRegion {
+                atlas,
+                region,
+                region_name,
+                ..
+            } = instruction
+            {
+                if let Some(name) = region_name.take() {
+                    match self.atlas(*atlas).and_then(|atlas| atlas.region(&name)) {
+                        Some(atlas_region) => *region = atlas_region,
+                        // The name of a missing region is kept, so that renderers skip it
+                        None => *region_name = Some(name),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Requests the display to perform an action (e.g. start moving the window)
+    ///
+    /// The request is forwarded to the display by the event loop, right after the
+    /// events of the current frame are handled.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `request` - the request to be forwarded to the display
+    pub fn request_window(&mut self, request: WindowRequest) {
+        if let WindowRequest::RequestAttention(_) = request {
+            self.is_attention_requested = true;
+        }
+        self.window_requests.push(request);
+    }
+
+    /// Takes the requests to be forwarded to the display, for internal use only
+    ///
+    /// # Returns
+    /// The requests made since the last call
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_window_requests(&mut self) -> Vec<WindowRequest> {
+        std::mem::take(&mut self.window_requests)
+    }
+
+    /// Gets the realized geometry of a widget (i.e., its position and size after layout)
+    ///
+    /// # Returns
+    /// The widget's position and size assigned on the last build, or `None` if no
+    /// widget with the given identifier was built
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub fn geometry(&self, id: usize) -> Option<Rect> {
+        self.geometry.get(&id).copied()
+    }
+
+    /// Gets the notifications of the widgets whose area the cursor entered or exited
+    /// when it moved, for internal use only
+    ///
+    /// # Returns
+    /// The [`Mouse::PointerExited`] events, followed by the [`Mouse::PointerEntered`] events
+    ///
+    /// # Arguments
+    /// * `from` - the cursor's previous position, or `None` if it was outside the window
+    /// * `to` - the cursor's current position, or `None` if it is outside the window
+    pub(crate) fn pointer_crossings(
+        &self,
+        from: Option<Vector2D>,
+        to: Option<Vector2D>,
+    ) -> Vec<Event> {
+        let mut exited = Vec::new();
+        let mut entered = Vec::new();
+        for (id, geometry) in self.geometry.iter() {
+            let was_inside = from.is_some_and(|point| geometry.contains(point));
+            let is_inside = to.is_some_and(|point| geometry.contains(point));
+            if was_inside && !is_inside {
+                exited.push(*id);
+            } else if is_inside && !was_inside {
+                entered.push(*id);
+            }
+        }
+        exited.sort_unstable();
+        entered.sort_unstable();
+        exited
+            .into_iter()
+            .map(|id| Event::Mouse(Mouse::PointerExited { id }))
+            .chain(
+                entered
+                    .into_iter()
+                    .map(|id| Event::Mouse(Mouse::PointerEntered { id })),
+            )
+            .collect()
+    }
+
+    /// Records the realized geometry of a widget after it is built, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `geometry` - the widget's position and size
+    pub(crate) fn record_geometry(&mut self, id: usize, geometry: Rect) {
+        self.geometry.insert(id, geometry);
+    }
+
+    /// Forgets the realized geometry of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn remove_geometry(&mut self, id: usize) {
+        self.geometry.remove(&id);
+    }
+
+    /// Gets the order in which tab moves the focus among the focusable widgets that
+    /// are not disabled, as of the last build
+    ///
+    /// Widgets with an explicit [`TabIndex::Index`] come first, in ascending order of
+    /// index. The remaining ones follow in reading order of their realized geometry:
+    /// row by row, from top to bottom, and within each row in the layout direction.
+    /// Widgets whose tab index is [`TabIndex::Excluded`] are left out. The application
+    /// moves the focus along it (see [`next_tab_stop`](`Context::next_tab_stop`)).
+    ///
+    /// # Returns
+    /// The identifiers of the widgets, in traversal order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn tab_order(&self) -> Vec<usize> {
+        let mut indexed = Vec::new();
+        let mut automatic = Vec::new();
+        for (id, tab_index) in self.tab_stops.iter() {
+            let geometry = match self.geometry.get(id) {
+                Some(geometry) => *geometry,
+                None => continue,
+            };
+            match tab_index {
+                TabIndex::Index(index) => indexed.push((*index, *id, geometry)),
+                TabIndex::Auto => automatic.push((*id, geometry)),
+                TabIndex::Excluded => {}
+            }
+        }
+        indexed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| self.reading_order(a.2, b.2)));
+        automatic.sort_by(|a, b| {
+            let (a_top, b_top) = (a.1.position.y, b.1.position.y);
+            a_top.total_cmp(&b_top).then(a.0.cmp(&b.0))
+        });
+
+        // Split the widgets into rows: a widget starts a new row once its top edge is
+        // below the vertical center of the first widget of the current row
+        let mut rows: Vec<Vec<(usize, Rect)>> = Vec::new();
+        for (id, geometry) in automatic {
+            match rows.last_mut() {
+                Some(row) if geometry.position.y < row[0].1.position.y + row[0].1.size.y / 2. => {
+                    row.push((id, geometry))
+                }
+                _ => rows.push(vec![(id, geometry)]),
+            }
+        }
+        let mut order: Vec<usize> = indexed.into_iter().map(|(_, id, _)| id).collect();
+        for mut row in rows {
+            row.sort_by(|a, b| self.reading_order(a.1, b.1).then(a.0.cmp(&b.0)));
+            order.extend(row.into_iter().map(|(id, _)| id));
+        }
+        order
+    }
+
+    /// Gets the widget that receives the focus when tab is pressed
+    ///
+    /// The library does not track which widget has the focus, so the event loop leaves
+    /// tab and shift and tab to the application, which drives the traversal: on those
+    /// key presses, it gets the next widget and moves the focus to it itself (e.g. by
+    /// handling its own focus and blur messages).
+    ///
+    /// # Returns
+    /// The identifier of the next widget in the [`tab_order`](`Context::tab_order`),
+    /// wrapping around at the ends, or `None` if no widget takes part in the traversal
+    ///
+    /// # Arguments
+    /// * `current` - the identifier of the widget with the focus, or `None` to start from the first (or last) widget
+    /// * `reverse` - whether the focus moves backwards (i.e., shift and tab are pressed)
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Event::Keyboard(Keyboard::KeyPressed { key_code: KeyCode::Tab, modifiers }) = event {
+    ///     focused = context.borrow().next_tab_stop(focused, modifiers.shift);
+    /// }
+    /// ```
+    pub fn next_tab_stop(&self, current: Option<usize>, reverse: bool) -> Option<usize> {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return None;
+        }
+        let position = current.and_then(|id| order.iter().position(|other| *other == id));
+        let index = match (position, reverse) {
+            (Some(position), false) => (position + 1) % order.len(),
+            (Some(position), true) => (position + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+        Some(order[index])
+    }
+
+    /// Compares two widgets' horizontal positions in the layout direction, for internal use only
+    ///
+    /// # Returns
+    /// The ordering of the widgets, in which they are read
+    ///
+    /// # Arguments
+    /// * `a` - the first widget's realized geometry
+    /// * `b` - the second widget's realized geometry
+    fn reading_order(&self, a: Rect, b: Rect) -> std::cmp::Ordering {
+        match self.layout_direction {
+            LayoutDirection::LeftToRight => a.position.x.total_cmp(&b.position.x),
+            LayoutDirection::RightToLeft => {
+                (b.position.x + b.size.x).total_cmp(&(a.position.x + a.size.x))
+            }
+        }
+    }
+
+    /// Pushes a modal scope (e.g. a dialog), confining the keyboard events and the focus
+    /// traversal to it until it is popped
+    ///
+    /// While a modal scope is active, the keyboard events are only dispatched to it and its
+    /// descendants, so the widgets behind it ignore their keys and shortcuts, and only its
+    /// descendants take part in the [`tab_order`](`Context::tab_order`). Every widget is
+    /// rebuilt on the next frame.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget` - the widget confining the keyboard and the focus
+    pub fn push_modal_scope(&mut self, widget: &Rc<RefCell<dyn Widget>>) {
+        self.modal_scopes.push(Rc::downgrade(widget));
+        self.needs_rebuild = true;
+    }
+
+    /// Pops the modal scope pushed last, activating the previous one, if any
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn pop_modal_scope(&mut self) {
+        if self.modal_scopes.pop().is_some() {
+            self.needs_rebuild = true;
+        }
+    }
+
+    /// Gets the active modal scope
+    ///
+    /// # Returns
+    /// The widget confining the keyboard and the focus, or `None` if no modal scope is active
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn modal_scope(&self) -> Option<Rc<RefCell<dyn Widget>>> {
+        self.modal_scopes.iter().rev().find_map(Weak::upgrade)
+    }
+
+    /// Opens a popup (e.g. a [`DialogWidget`](`crate::widget::dialog::DialogWidget`)),
+    /// drawn above the widget tree over the whole display
+    ///
+    /// While open, the popup on top grabs every event, so the widgets behind it cannot
+    /// be interacted with, and it is the active modal scope (see
+    /// [`push_modal_scope`](`Context::push_modal_scope`)). The context only holds a weak
+    /// pointer to the popup, which must be kept alive while it is open.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `popup` - the widget to be opened as a popup
+    pub fn open_popup(&mut self, popup: &Rc<RefCell<dyn Widget>>) {
+        self.close_popup(popup);
+        self.closed_popups
+            .retain(|other| !Weak::ptr_eq(other, &Rc::downgrade(popup)));
+        popup.borrow_mut().set_dirty(true);
+        self.popups.push(Rc::downgrade(popup));
+        self.push_modal_scope(popup);
+    }
+
+    /// Closes a popup, removing it from the display and releasing the events it grabbed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `popup` - the popup to be closed
+    pub fn close_popup(&mut self, popup: &Rc<RefCell<dyn Widget>>) {
+        let popup = Rc::downgrade(popup);
+        let count = self.popups.len();
+        self.popups.retain(|other| !Weak::ptr_eq(other, &popup));
+        if count != self.popups.len() {
+            self.modal_scopes.retain(|other| !Weak::ptr_eq(other, &popup));
+            self.closed_popups.push(popup);
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Gets the popups open
+    ///
+    /// # Returns
+    /// The popups open and still alive, the last one being on top
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn popups(&self) -> Vec<Rc<RefCell<dyn Widget>>> {
+        self.popups.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    /// Takes the popups closed since the last frame, for internal use only
+    ///
+    /// # Returns
+    /// The popups whose render instructions must be removed
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_closed_popups(&mut self) -> Vec<Rc<RefCell<dyn Widget>>> {
+        std::mem::take(&mut self.closed_popups)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
+    /// Gets the widget an event is dispatched to instead of the root, for internal use only
+    ///
+    /// # Returns
+    /// The popup on top, if any, which grabs every event, the active modal scope, if
+    /// the event is a keyboard event, or `None` otherwise
+    ///
+    /// # Arguments
+    /// * `event` - the event about to be dispatched
+    pub(crate) fn event_target(&self, event: &Event) -> Option<Rc<RefCell<dyn Widget>>> {
+        if let Some(popup) = self.popups.iter().rev().find_map(Weak::upgrade) {
+            return Some(popup);
+        }
+        match event {
+            Event::Keyboard(_) => self.modal_scope(),
+            _ => None,
+        }
+    }
+
+    /// Registers the context menu of a widget, opened at the cursor when the widget is
+    /// right-clicked, instead of dispatching the click
+    ///
+    /// The chosen item's message is handled like any other widget message. Registering
+    /// the items again replaces them, while registering no items unregisters the widget.
+    /// The context only holds a weak pointer to the widget.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget` - the widget to be registered
+    /// * `items` - the items of the widget's context menu
+    pub fn set_context_menu(
+        &mut self,
+        widget: &Rc<RefCell<dyn Widget>>,
+        items: Vec<ContextMenuItem>,
+    ) {
+        let widget = Rc::downgrade(widget);
+        self.context_menus
+            .retain(|(other, _)| other.strong_count() > 0 && !Weak::ptr_eq(other, &widget));
+        if !items.is_empty() {
+            self.context_menus.push((widget, items));
+        }
+    }
+
+    /// Gets the context menu opened when a registered widget is right-clicked, to be styled
+    ///
+    /// # Returns
+    /// The context menu
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn context_menu(&self) -> Rc<RefCell<ContextMenuWidget>> {
+        self.context_menu.clone()
+    }
+
+    /// Opens the context menu of the registered widget under the cursor, if the event is
+    /// a right-click and there are no popups open, for internal use only
+    ///
+    /// When several registered widgets are under the cursor, the one built last (i.e.,
+    /// drawn on top) is chosen.
+    ///
+    /// # Returns
+    /// True, if the context menu was opened, consuming the event, false otherwise
+    ///
+    /// # Arguments
+    /// * `event` - the event about to be dispatched
+    pub(crate) fn open_context_menu(&mut self, event: &Event) -> bool {
+        if !matches!(event, Event::Mouse(Mouse::ButtonPressed(MouseButton::Right)))
+            || !self.popups().is_empty()
+        {
+            return false;
+        }
+        let point = match event::pointer_position() {
+            Some(point) => point,
+            None => return false,
+        };
+        let items = self
+            .context_menus
+            .iter()
+            .filter_map(|(widget, items)| {
+                let id = widget.upgrade()?.borrow().id();
+                self.geometry(id)
+                    .filter(|area| area.contains(point))
+                    .map(|_| (id, items))
+            })
+            .max_by_key(|(id, _)| *id)
+            .map(|(_, items)| items.clone());
+        match items {
+            Some(items) => {
+                self.context_menu.borrow_mut().open_at(point, items);
+                let popup: Rc<RefCell<dyn Widget>> = self.context_menu.clone();
+                self.open_popup(&popup);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Closes the context menu once dismissed (i.e., an item was chosen or the menu was
+    /// cancelled), for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn close_dismissed_context_menu(&mut self) {
+        if self.context_menu.borrow().is_dismissed() {
+            let popup: Rc<RefCell<dyn Widget>> = self.context_menu.clone();
+            self.close_popup(&popup);
+        }
+    }
+
+    /// Enters a widget about to be built, if it is the active modal scope, for internal use only
+    ///
+    /// # Returns
+    /// True, if the widget is the active modal scope, to be exited once its children are built
+    ///
+    /// # Arguments
+    /// * `address` - the address of the widget
+    pub(crate) fn enter_modal_scope(&mut self, address: usize) -> bool {
+        let is_modal_scope = self
+            .modal_scope()
+            .is_some_and(|widget| widget.as_ptr() as *const () as usize == address);
+        if is_modal_scope {
+            self.modal_depth += 1;
+        }
+        is_modal_scope
+    }
+
+    /// Exits the active modal scope once its children are built, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn exit_modal_scope(&mut self) {
+        self.modal_depth = self.modal_depth.saturating_sub(1);
+    }
+
+    /// Checks whether the widget being built can take part in the focus traversal,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// True, if no modal scope is active or the widget is inside it, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn is_in_modal_scope(&self) -> bool {
+        self.modal_depth > 0 || self.modal_scope().is_none()
+    }
+
+    /// Records the tab index of a focusable widget after it is built, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `tab_index` - the widget's position in the focus traversal
+    pub(crate) fn record_tab_stop(&mut self, id: usize, tab_index: TabIndex) {
+        self.tab_stops.insert(id, tab_index);
+    }
+
+    /// Forgets the tab index of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn remove_tab_stop(&mut self, id: usize) {
+        self.tab_stops.remove(&id);
+    }
+
+    /// Gets the memo key of a widget, for internal use only
+    ///
+    /// # Returns
+    /// The memo key of the widget's current render instructions, or `None` if it is not memoized
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn memo(&self, id: usize) -> Option<u64> {
+        self.memos.get(&id).copied()
+    }
+
+    /// Records the memo key of a widget's render instructions, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `memo_key` - the memo key of the widget's render instructions
+    pub(crate) fn set_memo(&mut self, id: usize, memo_key: u64) {
+        self.memos.insert(id, memo_key);
+    }
+
+    /// Forgets the memo key of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn remove_memo(&mut self, id: usize) {
+        self.memos.remove(&id);
+    }
+
+    /// Requires the next widget built to be moved to a new identifier, even if its
+    /// render instructions are memoized, so that it keeps being drawn over the siblings
+    /// rebuilt before it, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn move_next_widget(&mut self) {
+        self.is_moving = true;
+    }
+
+    /// Takes whether the widget being built must be moved to a new identifier, for
+    /// internal use only
+    ///
+    /// # Returns
+    /// True, if the widget must be moved, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_is_moving(&mut self) -> bool {
+        std::mem::take(&mut self.is_moving)
+    }
+
+    /// Records the identifier of a widget's overlay instructions, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `overlay_id` - the identifier of the widget's overlay instructions
+    pub(crate) fn set_overlay(&mut self, id: usize, overlay_id: usize) {
+        self.overlays.insert(id, overlay_id);
+        if let Some(stable_ids) = self.stable_ids.as_mut() {
+            if let Some(stable_id) = stable_ids.get(&id).copied() {
+                stable_ids.insert(overlay_id, MemoKey::new().with(&stable_id).with("overlay").finish());
+            }
+        }
+    }
+
+    /// Takes the identifier of a widget's overlay instructions, for internal use only
+    ///
+    /// # Returns
+    /// The identifier of the widget's overlay instructions, if any
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn take_overlay(&mut self, id: usize) -> Option<usize> {
+        let overlay_id = self.overlays.remove(&id);
+        if let (Some(overlay_id), Some(stable_ids)) = (overlay_id, self.stable_ids.as_mut()) {
+            stable_ids.remove(&overlay_id);
+        }
+        overlay_id
+    }
+
+    /// Sets whether every widget built gets a stable identifier, which is kept across
+    /// rebuilds (unlike the widget's identifier), so that the render instructions
+    /// captured on different frames can be compared (e.g. by remote rendering or
+    /// recording tools)
+    ///
+    /// A widget's stable identifier is derived from its path on the widget tree (i.e.,
+    /// the index of each of its ancestors), or from its [`stable_key`](`crate::widget::Widget::stable_key`)
+    /// instead of its index, if any. Absolute widgets keep their identifiers already.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `stable_ids` - whether stable identifiers are recorded
+    pub fn set_stable_ids(&mut self, stable_ids: bool) {
+        if stable_ids != self.stable_ids.is_some() {
+            self.stable_ids = if stable_ids { Some(HashMap::new()) } else { None };
+            // The widgets not rebuilt would be missing from the mapping table
+            self.needs_rebuild = stable_ids;
+        }
+    }
+
+    /// Gets the mapping table from the identifiers of the widgets and overlays built
+    /// to their stable identifiers
+    ///
+    /// # Returns
+    /// The stable identifiers, having the identifier as key, or `None` if stable
+    /// identifiers are disabled
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn stable_ids(&self) -> Option<&HashMap<usize, u64>> {
+        self.stable_ids.as_ref()
+    }
+
+    /// Gets the stable identifier of a widget or overlay
+    ///
+    /// # Returns
+    /// The stable identifier, or `None` if there is no widget or overlay with the
+    /// identifier or stable identifiers are disabled
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget or overlay
+    pub fn stable_id(&self, id: usize) -> Option<u64> {
+        self.stable_ids.as_ref()?.get(&id).copied()
+    }
+
+    /// Computes the stable identifier of a widget about to be built, from its parent's
+    /// and its own index or key, for internal use only
+    ///
+    /// # Returns
+    /// The widget's stable identifier, or `None` if stable identifiers are disabled
+    ///
+    /// # Arguments
+    /// * `address` - the address of the widget
+    /// * `stable_key` - the key identifying the widget among its siblings, if any
+    pub(crate) fn enter_stable_id(&self, address: usize, stable_key: Option<u64>) -> Option<u64> {
+        self.stable_ids.as_ref()?;
+        let (parent_id, index) = match self.stable_scopes.last() {
+            Some((parent_id, indexes)) => {
+                (*parent_id, indexes.get(&address).copied().unwrap_or(0))
+            }
+            None => (0, 0),
+        };
+        let memo_key = MemoKey::new().with(&parent_id);
+        Some(match stable_key {
+            Some(key) => memo_key.with("key").with(&key).finish(),
+            None => memo_key.with("index").with(&index).finish(),
+        })
+    }
+
+    /// Records the stable identifier of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    /// * `stable_id` - the widget's stable identifier
+    pub(crate) fn set_stable_id(&mut self, id: usize, stable_id: u64) {
+        if let Some(stable_ids) = self.stable_ids.as_mut() {
+            stable_ids.insert(id, stable_id);
+        }
+    }
+
+    /// Forgets the stable identifier of a widget, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    pub(crate) fn remove_stable_id(&mut self, id: usize) {
+        if let Some(stable_ids) = self.stable_ids.as_mut() {
+            stable_ids.remove(&id);
+        }
+    }
+
+    /// Pushes the stable identifier of the widget whose children are about to be built,
+    /// along with its children's addresses, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `stable_id` - the widget's stable identifier
+    /// * `child_addresses` - the addresses of the widget's children, in order
+    pub(crate) fn push_stable_scope(&mut self, stable_id: u64, child_addresses: Vec<usize>) {
+        let indexes = child_addresses
+            .into_iter()
+            .enumerate()
+            .map(|(index, address)| (address, index))
+            .collect();
+        self.stable_scopes.push((stable_id, indexes));
+    }
+
+    /// Pops the stable identifier pushed by the last [`push_stable_scope`](`Context::push_stable_scope`),
+    /// for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn pop_stable_scope(&mut self) {
+        self.stable_scopes.pop();
+    }
+
+    /// Pushes the shape clipping the children of the widget being built, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `shape` - the shape clipping the children
+    pub(crate) fn push_clip(&mut self, shape: ClipShape) {
+        self.clip_stack.push(shape);
+    }
+
+    /// Pops the shape pushed by the last [`push_clip`](`Context::push_clip`), for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    /// Pushes the transform of the children of the widget being built, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `transform` - the transform of the children
+    pub(crate) fn push_transform(&mut self, transform: Transform) {
+        self.transform_stack.push((self.clip_stack.len(), transform));
+    }
+
+    /// Pops the transform pushed by the last [`push_transform`](`Context::push_transform`),
+    /// for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn pop_transform(&mut self) {
+        self.transform_stack.pop();
+    }
+
+    /// Pushes the opacity of the widget being built, applied to it and to its children,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `opacity` - the widget's own opacity, from 0 (invisible) to 1 (opaque)
+    pub(crate) fn push_opacity(&mut self, opacity: f64) {
+        let opacity = self.opacity() * opacity.clamp(0., 1.);
+        self.opacity_stack.push(opacity);
+    }
+
+    /// Pops the opacity pushed by the last [`push_opacity`](`Context::push_opacity`),
+    /// for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn pop_opacity(&mut self) {
+        self.opacity_stack.pop();
+    }
+
+    /// Gets the opacity of the widget being built, for internal use only
+    ///
+    /// # Returns
+    /// The widget's opacity multiplied by the ones of its ancestors, or 1 if none was pushed
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn opacity(&self) -> f64 {
+        self.opacity_stack.last().copied().unwrap_or(1.)
+    }
+
+    /// Applies the opacity of the widget being built to its render instructions, for
+    /// internal use only
+    ///
+    /// The instructions are wrapped by opacity instructions if the renderer supports
+    /// them, or their colors are made translucent otherwise.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn fade_recipe(&self, instructions: &mut Vec<RenderInstruction>) {
+        let opacity = self.opacity();
+        if opacity >= 1. || instructions.is_empty() {
+            return;
+        }
+        if self.supports_opacity {
+            instructions.insert(0, RenderInstruction::PushOpacity { alpha: opacity });
+            instructions.push(RenderInstruction::PopOpacity);
+        } else {
+            for instruction in instructions.iter_mut() {
+                instruction.map_color(|color| color.with_opacity(opacity));
+            }
+        }
+    }
+
+    /// Gets the hash of the shapes clipping the widget being built and of its transforms,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// The hash of the shapes and transforms pushed by the ancestors of the widget being built
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn clip_key(&self) -> u64 {
+        let mut memo_key = MemoKey::new();
+        for shape in self.clip_stack.iter() {
+            memo_key = match shape {
+                ClipShape::Rect { point, size } => {
+                    memo_key.with(&0).with_vector(*point).with_vector(*size)
+                }
+                ClipShape::RoundedRect {
+                    point,
+                    size,
+                    radius,
+                } => memo_key
+                    .with(&1)
+                    .with_vector(*point)
+                    .with_vector(*size)
+                    .with_f64(*radius),
+                ClipShape::Circle { point, r } => {
+                    memo_key.with(&2).with_vector(*point).with_f64(*r)
+                }
+                ClipShape::Path { points } => {
+                    points.iter().fold(memo_key.with(&3), |memo_key, point| {
+                        memo_key.with_vector(*point)
+                    })
+                }
+            };
+        }
+        for (depth, transform) in self.transform_stack.iter() {
+            memo_key = memo_key
+                .with(depth)
+                .with_vector(transform.translation)
+                .with_f64(transform.rotation)
+                .with_vector(transform.scale);
+        }
+        memo_key.finish()
+    }
+
+    /// Clips a widget's render instructions to the shapes pushed by its ancestors, and
+    /// transforms them by the transforms pushed, for internal use only
+    ///
+    /// Every widget's instructions are wrapped by their own clip and transform
+    /// instructions, so that they do not leak to the widgets drawn between them. The
+    /// clips and transforms are nested in the order they were pushed, since the shapes
+    /// pushed below a transform are given in the transformed coordinates.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `instructions` - the render instructions of the widget
+    pub(crate) fn clip_recipe(&self, instructions: &mut Vec<RenderInstruction>) {
+        if (self.clip_stack.is_empty() && self.transform_stack.is_empty())
+            || instructions.is_empty()
+        {
+            return;
+        }
+        let push_transform = |transform: &Transform| RenderInstruction::PushTransform {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            scale: transform.scale,
+        };
+        let mut wrapped = Vec::new();
+        let mut pops = Vec::new();
+        let mut transforms = self.transform_stack.iter().peekable();
+        for (depth, shape) in self.clip_stack.iter().enumerate() {
+            // The transforms pushed before the shape wrap it
+            while let Some((_, transform)) = transforms.next_if(|(pushed, _)| *pushed <= depth) {
+                wrapped.push(push_transform(transform));
+                pops.push(RenderInstruction::PopTransform);
+            }
+            wrapped.push(RenderInstruction::PushClip {
+                shape: shape.clone(),
+            });
+            pops.push(RenderInstruction::PopClip);
+        }
+        for (_, transform) in transforms {
+            wrapped.push(push_transform(transform));
+            pops.push(RenderInstruction::PopTransform);
+        }
+        wrapped.append(instructions);
+        wrapped.extend(pops.into_iter().rev());
+        *instructions = wrapped;
+    }
+
+    /// Starts a new frame, for internal use only
+    ///
+    /// # Returns
+    /// The number of the frame started
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn begin_frame(&mut self) -> u64 {
+        self.frame += 1;
+        self.frame
+    }
+
+    /// Takes all the frame callbacks requested so far, for internal use only
+    ///
+    /// # Returns
+    /// The callbacks to be executed after the current frame
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_frame_callbacks(&mut self) -> Vec<FrameCallback> {
+        std::mem::take(&mut self.frame_callbacks)
+    }
+
+    /// Gets the information about the frame about to be drawn, for internal use only
+    ///
+    /// # Returns
+    /// The number of the next frame and the time taken by the previous frame
+    ///
+    /// # Arguments
+    /// * `display_size` - the display's size (width and height) used to build the frame
+    pub(crate) fn next_frame_info(&self, display_size: Vector2D) -> FrameInfo {
+        FrameInfo {
+            frame: self.frame + 1,
+            display_size,
+            frame_time: self.last_frame_time,
+        }
+    }
+
+    /// Executes the hooks to be executed before the events are handled, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    /// * `frame_info` - the information about the frame about to be drawn
+    pub(crate) fn run_before_frame_hooks(context: &Rc<RefCell<Context>>, frame_info: &FrameInfo) {
+        Context::run_frame_hooks(context, HookPhase::Before, frame_info);
+    }
+
+    /// Executes the hooks to be executed after the frame is drawn, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    /// * `frame_info` - the information about the frame drawn
+    pub(crate) fn run_after_frame_hooks(context: &Rc<RefCell<Context>>, frame_info: &FrameInfo) {
+        context.borrow_mut().last_frame_time = frame_info.frame_time;
+        Context::run_frame_hooks(context, HookPhase::After, frame_info);
+    }
+
+    /// Gets the animations of the widgets' properties, to start or cancel them
+    ///
+    /// # Returns
+    /// The animation controller ticked by the event loop on every frame
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn animations(&mut self) -> &mut AnimationController {
+        &mut self.animations
+    }
+
+    /// Checks whether any animation is running, such that frames must keep being drawn
+    ///
+    /// # Returns
+    /// True, if some animation did not finish yet, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_animating(&self) -> bool {
+        self.animations.is_animating()
+    }
+
+    /// Ticks the animations, marking the widgets animated as dirty, for internal use only
+    ///
+    /// The animations are taken out of the context while ticking, so the widgets can
+    /// borrow the context themselves (e.g. to start other animations).
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    pub(crate) fn tick_animations(context: &Rc<RefCell<Context>>) {
+        let mut animations = std::mem::take(&mut context.borrow_mut().animations);
+        animations.tick();
+        let mut context = context.borrow_mut();
+        // Keep the animations started while ticking
+        animations.append(&mut context.animations);
+        context.animations = animations;
+    }
+
+    /// Executes the frame hooks of the given phase, for internal use only
+    ///
+    /// The hooks are taken out of the context while executing, so they can borrow
+    /// the context themselves (e.g. to add or remove hooks).
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    /// * `phase` - the moment of the frame whose hooks are executed
+    /// * `frame_info` - the information about the frame
+    fn run_frame_hooks(context: &Rc<RefCell<Context>>, phase: HookPhase, frame_info: &FrameInfo) {
+        let mut hooks = match phase {
+            HookPhase::Before => std::mem::take(&mut context.borrow_mut().before_frame_hooks),
+            HookPhase::After => std::mem::take(&mut context.borrow_mut().after_frame_hooks),
+        };
+        for (_, hook) in hooks.iter_mut() {
+            hook(frame_info);
+        }
+
+        let mut context = context.borrow_mut();
+        let removed_hooks = std::mem::take(&mut context.removed_hooks);
+        hooks.retain(|(id, _)| !removed_hooks.contains(id));
+        // Keep the hooks added while executing after the existing ones
+        let added_hooks = match phase {
+            HookPhase::Before => &mut context.before_frame_hooks,
+            HookPhase::After => &mut context.after_frame_hooks,
+        };
+        hooks.append(added_hooks);
+        *added_hooks = hooks;
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
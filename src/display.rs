@@ -63,6 +63,37 @@ impl DisplayDescritor {
     }
 }
 
+/// Edge or corner of a display, from which the display is resized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowEdge {
+    /// The top edge
+    Top,
+    /// The bottom edge
+    Bottom,
+    /// The left edge
+    Left,
+    /// The right edge
+    Right,
+    /// The top left corner
+    TopLeft,
+    /// The top right corner
+    TopRight,
+    /// The bottom left corner
+    BottomLeft,
+    /// The bottom right corner
+    BottomRight,
+}
+
+/// Urgency of a request for the user's attention
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttentionLevel {
+    /// The display is briefly highlighted (e.g. the taskbar icon flashes once
+    /// or the dock icon bounces once)
+    Informational,
+    /// The display is highlighted until the user focuses it
+    Critical,
+}
+
 /// Display is the window that is presented to the user. Widgets are 
 /// rendered within this display, composing the user interface.
 pub trait Display {
@@ -426,4 +457,184 @@ pub trait Display {
     /// let display_active = display.is_active();
     /// ```
     fn is_active(&mut self) -> bool;
+
+    /// Starts resizing the display from one of its edges or corners, following the
+    /// cursor while the mouse button is held (e.g. on borderless displays with custom chrome)
+    ///
+    /// It should be called while a mouse button is pressed. By default it does nothing,
+    /// for display systems unable to resize their displays interactively.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `edge` - the edge or corner from which the display is resized
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn begin_resize(&mut self, edge: WindowEdge) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn begin_resize(&mut self, _edge: WindowEdge) {}
+
+    /// Starts moving the display, following the cursor while the mouse button is held
+    /// (e.g. when dragging a custom title bar)
+    ///
+    /// It should be called while a mouse button is pressed. By default it does nothing,
+    /// for display systems unable to move their displays interactively.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn begin_move(&mut self) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn begin_move(&mut self) {}
+
+    /// Minimizes the display
+    ///
+    /// By default it does nothing, for display systems unable to minimize their displays.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn minimize(&mut self) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn minimize(&mut self) {}
+
+    /// Maximizes the display, or restores its previous size if it is already maximized
+    ///
+    /// By default it does nothing, for display systems unable to maximize their displays.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn toggle_maximized(&mut self) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn toggle_maximized(&mut self) {}
+
+    /// Closes the display, after which [`is_open`](`Display::is_open`) should return false
+    ///
+    /// By default it does nothing, for display systems whose displays are only closed
+    /// by the user.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn close(&mut self) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn close(&mut self) {}
+
+    /// Requests the user's attention while the display is in background (e.g. flashes
+    /// the taskbar icon or bounces the dock icon)
+    ///
+    /// The request is acknowledged once the display gains focus, which is reported
+    /// by the event loop as [`Window::AttentionAcknowledged`](`crate::event::Window::AttentionAcknowledged`).
+    /// By default it does nothing, for display systems unable to highlight their displays.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `level` - the urgency of the request
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn request_attention(&mut self, level: AttentionLevel) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn request_attention(&mut self, _level: AttentionLevel) {}
+
+    /// Sets the opacity of the whole display, including its decorations
+    ///
+    /// By default it does nothing, for display systems without translucent displays.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `opacity` - the opacity to be assigned to the display, from 0 (invisible) to 1 (opaque)
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn set_opacity(&mut self, opacity: f64) {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn set_opacity(&mut self, _opacity: f64) {}
+
+    /// Blocks until the display's next vertical blank (i.e., vsync), to pace the frames
+    /// at the display's refresh rate when vsync is enabled on the context
+    ///
+    /// By default it does not wait, for display systems without vsync, in which case
+    /// the frames are paced at the context's target frame rate, if any.
+    ///
+    /// # Returns
+    /// True, if the display waited for the vertical blank, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// impl Display for Implementor {
+    ///     fn wait_vsync(&mut self) -> bool {
+    ///         ...
+    ///     }
+    /// }
+    /// ```
+    fn wait_vsync(&mut self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,445 @@
+//! Contains the drag and drop of typed payloads between widgets.
+//!
+//! A widget starts dragging a [`DragPayload`] (e.g. a text, a list of file paths or
+//! any application object) with [`start_drag`], usually once the cursor moved far
+//! enough with the left button held. Widgets accepting drops embed a [`DropTarget`]
+//! declaring the types of payload they accept and feed it the events they receive.
+//!
+//! While dragging, every target under the cursor negotiates whether it accepts the
+//! payload, and the ghost following the cursor is drawn dimmed, with a red badge,
+//! where dropping is not allowed. The drag ends when the left button is released,
+//! dropping the payload on the accepting target under the cursor, if any.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::drag::{DragPayload, DropTarget};
+//! use std::path::PathBuf;
+//!
+//! let target = DropTarget::new(None).accept::<Vec<PathBuf>>();
+//!
+//! assert!(target.accepts(&DragPayload::paths(vec![PathBuf::from("notes.txt")])));
+//! assert!(!target.accepts(&DragPayload::text(String::from("notes"))));
+//! ```
+
+use crate::event::{pointer_position, Event, Mouse, MouseButton, Window};
+use crate::message::Dropped;
+use crate::renderer::{BlendMode, Message, RenderInstruction, RenderInstructionCollection};
+use crate::util::{Color, Queue, Rect, Vector2D};
+
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Identifier of the ghost's render instructions, drawn over every widget
+const GHOST_ID: usize = usize::MAX;
+
+/// Opacity of the ghost where dropping is allowed
+const ALLOWED_OPACITY: f64 = 0.8;
+
+/// Opacity of the ghost where dropping is not allowed
+const DENIED_OPACITY: f64 = 0.35;
+
+/// Radius of the badge drawn on the ghost where dropping is not allowed
+const DENIED_BADGE_RADIUS: usize = 6;
+
+/// Data carried by a drag, of any type
+///
+/// Common payloads are a [`String`] (see [`text`](`DragPayload::text`)) and a
+/// `Vec<PathBuf>` (see [`paths`](`DragPayload::paths`)), but applications may drag
+/// their own objects, which are only accepted by the targets declaring their type.
+#[derive(Clone)]
+pub struct DragPayload {
+    /// The data dragged
+    data: Rc<dyn Any>,
+}
+
+impl DragPayload {
+    /// Creates a new `DragPayload`
+    ///
+    /// # Returns
+    /// The payload created
+    ///
+    /// # Arguments
+    /// * `data` - the data to be dragged
+    pub fn new<T: Any>(data: T) -> DragPayload {
+        DragPayload {
+            data: Rc::new(data),
+        }
+    }
+
+    /// Creates a new `DragPayload` carrying a text
+    ///
+    /// # Returns
+    /// The payload created, of type `String`
+    ///
+    /// # Arguments
+    /// * `text` - the text to be dragged
+    pub fn text(text: String) -> DragPayload {
+        DragPayload::new(text)
+    }
+
+    /// Creates a new `DragPayload` carrying a list of file paths
+    ///
+    /// # Returns
+    /// The payload created, of type `Vec<PathBuf>`
+    ///
+    /// # Arguments
+    /// * `paths` - the file paths to be dragged
+    pub fn paths(paths: Vec<PathBuf>) -> DragPayload {
+        DragPayload::new(paths)
+    }
+
+    /// Gets the type of the data dragged
+    ///
+    /// # Returns
+    /// The identifier of the data's type
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn payload_type(&self) -> TypeId {
+        (*self.data).type_id()
+    }
+
+    /// Gets the data dragged, if it is of the given type
+    ///
+    /// # Returns
+    /// The reference to the data, or `None` if it is of another type
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.data.downcast_ref::<T>()
+    }
+}
+
+/// Appearance of the ghost following the cursor while dragging
+#[derive(Clone, Copy)]
+pub struct DragGhost {
+    /// The ghost's size (width and height)
+    pub size: Vector2D,
+    /// The cursor's position relative to the ghost's upper left corner
+    pub grab: Vector2D,
+    /// The ghost's color
+    pub color: Color,
+}
+
+impl DragGhost {
+    /// Creates a new `DragGhost` from the area of the widget dragged, grabbed where the
+    /// cursor is
+    ///
+    /// # Returns
+    /// The ghost created
+    ///
+    /// # Arguments
+    /// * `area` - the area of the widget dragged
+    /// * `color` - the color to be assigned to the ghost
+    pub fn from_area(area: Rect, color: Color) -> DragGhost {
+        let cursor = pointer_position().unwrap_or(area.position);
+        DragGhost {
+            size: area.size,
+            grab: cursor - area.position,
+            color,
+        }
+    }
+}
+
+/// The drag in progress, for internal use only
+struct DragSession {
+    /// The data dragged
+    payload: DragPayload,
+    /// The ghost following the cursor, if any
+    ghost: Option<DragGhost>,
+    /// The keys of the targets under the cursor accepting the payload
+    accepting: Vec<usize>,
+}
+
+thread_local! {
+    /// The drag in progress on the current thread, if any
+    static DRAG_SESSION: RefCell<Option<DragSession>> = const { RefCell::new(None) };
+
+    /// The key to be given to the next drop target created
+    static NEXT_TARGET_KEY: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Starts dragging a payload, replacing the drag in progress, if any
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `payload` - the data to be dragged
+/// * `ghost` - the ghost to follow the cursor, or `None` to draw nothing
+pub fn start_drag(payload: DragPayload, ghost: Option<DragGhost>) {
+    DRAG_SESSION.with(|session| {
+        *session.borrow_mut() = Some(DragSession {
+            payload,
+            ghost,
+            accepting: Vec::new(),
+        })
+    });
+}
+
+/// Cancels the drag in progress, if any, without dropping its payload
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// No arguments
+pub fn cancel_drag() {
+    DRAG_SESSION.with(|session| session.borrow_mut().take());
+}
+
+/// Gets the payload of the drag in progress
+///
+/// # Returns
+/// The payload dragged, or `None` if nothing is being dragged
+///
+/// # Arguments
+/// No arguments
+pub fn dragged_payload() -> Option<DragPayload> {
+    DRAG_SESSION.with(|session| {
+        session
+            .borrow()
+            .as_ref()
+            .map(|session| session.payload.clone())
+    })
+}
+
+/// Checks whether the payload dragged would be accepted if dropped where the cursor is
+///
+/// # Returns
+/// True, if a target under the cursor accepts the payload, false otherwise
+///
+/// # Arguments
+/// No arguments
+pub fn is_drop_allowed() -> bool {
+    DRAG_SESSION.with(|session| {
+        session
+            .borrow()
+            .as_ref()
+            .is_some_and(|session| !session.accepting.is_empty())
+    })
+}
+
+/// Ends the drag in progress once an event was dispatched to every widget, if the
+/// event releases the left button or the window lost the focus, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `event` - the event dispatched
+pub(crate) fn finish_drag(event: &Event) {
+    match event {
+        Event::Mouse(Mouse::ButtonReleased(MouseButton::Left))
+        | Event::Window(Window::Focused(false)) => cancel_drag(),
+        _ => {}
+    }
+}
+
+/// Draws the ghost of the drag in progress over every widget, or removes it if
+/// nothing is being dragged, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `collection` - the collection where the widgets' render instructions are
+pub(crate) fn draw_ghost(collection: &mut RenderInstructionCollection) {
+    let ghost = DRAG_SESSION.with(|session| {
+        let session = session.borrow();
+        let session = session.as_ref()?;
+        Some((session.ghost?, !session.accepting.is_empty()))
+    });
+    let (ghost, is_allowed, cursor) = match (ghost, pointer_position()) {
+        (Some((ghost, is_allowed)), Some(cursor)) => (ghost, is_allowed, cursor),
+        _ => {
+            collection.remove(GHOST_ID);
+            return;
+        }
+    };
+    let point = cursor - ghost.grab;
+    let opacity = if is_allowed {
+        ALLOWED_OPACITY
+    } else {
+        DENIED_OPACITY
+    };
+    let mut instructions = vec![RenderInstruction::DrawRect {
+        point,
+        size: ghost.size,
+        color: ghost.color.with_opacity(opacity),
+        clip_point: point,
+        clip_size: ghost.size,
+        blend_mode: BlendMode::Normal,
+    }];
+    if !is_allowed {
+        let radius = DENIED_BADGE_RADIUS as f64;
+        instructions.push(RenderInstruction::DrawCircle {
+            point: Vector2D::new(point.x + ghost.size.x - radius, point.y + radius),
+            r: DENIED_BADGE_RADIUS,
+            color: Color::from_hex(0xffd32f2f),
+            clip_point: point,
+            clip_size: ghost.size,
+            blend_mode: BlendMode::Normal,
+        });
+    }
+    collection.replace_or_insert(GHOST_ID, instructions);
+}
+
+/// Change of a drop target's state, caused by an event
+#[derive(Clone)]
+pub enum DropEvent {
+    /// A drag whose payload the target accepts moved over it
+    Entered,
+    /// The drag moved away from the target, or ended elsewhere
+    Exited,
+    /// The payload was dropped on the target
+    Dropped(DragPayload),
+}
+
+/// Area of a widget where payloads of the declared types can be dropped
+///
+/// The widget feeds it the events it receives, along with its area, and reacts to the
+/// [`DropEvent`]s returned (e.g. highlights itself while a drag it accepts is over it).
+#[derive(Clone)]
+pub struct DropTarget {
+    /// The key identifying the target on the drag in progress
+    key: usize,
+    /// The types of payload accepted
+    accepted: Vec<TypeId>,
+    /// The message to be handled when a payload is dropped, along with the payload when
+    /// it is a [`Dropped`]
+    on_drop: Option<Box<dyn Message>>,
+    /// The last position of the cursor
+    cursor: Vector2D,
+    /// Whether a drag whose payload is accepted is over the target
+    is_over: bool,
+}
+
+impl DropTarget {
+    /// Creates a new `DropTarget`, accepting no payloads until their types are declared
+    ///
+    /// # Returns
+    /// The drop target created
+    ///
+    /// # Arguments
+    /// * `on_drop` - the message to be handled when a payload is dropped
+    pub fn new(on_drop: Option<Box<dyn Message>>) -> DropTarget {
+        let key = NEXT_TARGET_KEY.with(|key| key.replace(key.get() + 1));
+        DropTarget {
+            key,
+            accepted: Vec::new(),
+            on_drop,
+            cursor: Vector2D::new(-1., -1.),
+            is_over: false,
+        }
+    }
+
+    /// Declares a type of payload accepted, returning the drop target (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The drop target accepting the type
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn accept<T: Any>(mut self) -> Self {
+        self.accepted.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Checks whether a payload can be dropped on the target
+    ///
+    /// # Returns
+    /// True, if the payload's type was declared as accepted, false otherwise
+    ///
+    /// # Arguments
+    /// * `payload` - the payload to be checked
+    pub fn accepts(&self, payload: &DragPayload) -> bool {
+        self.accepted.contains(&payload.payload_type())
+    }
+
+    /// Checks whether a drag whose payload is accepted is over the target
+    ///
+    /// # Returns
+    /// True, if dropping now would drop the payload on the target, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_over(&self) -> bool {
+        self.is_over
+    }
+
+    /// Updates the target according to an event received by the widget
+    ///
+    /// # Returns
+    /// The change of the target's state caused by the event, if any
+    ///
+    /// # Arguments
+    /// * `event` - the event received by the widget
+    /// * `area` - the widget's area, where payloads are dropped
+    /// * `messages` - queue of messages
+    pub fn on_event(
+        &mut self,
+        event: Event,
+        area: Rect,
+        messages: &mut Queue<Box<dyn Message>>,
+    ) -> Option<DropEvent> {
+        match event {
+            Event::Mouse(Mouse::CursorMoved { x, y }) => {
+                self.cursor = Vector2D::new(x as f64, y as f64);
+                let is_over = area.contains(self.cursor)
+                    && dragged_payload().is_some_and(|payload| self.accepts(&payload));
+                self.set_over(is_over)
+            }
+            Event::Mouse(Mouse::CursorLeft) | Event::Window(Window::Focused(false)) => {
+                self.set_over(false)
+            }
+            Event::Mouse(Mouse::ButtonReleased(MouseButton::Left)) if self.is_over => {
+                self.set_over(false);
+                let payload = dragged_payload()?;
+                if let Some(mut message) = self.on_drop.clone() {
+                    message.set_event(event);
+                    if let Some(message) = message.downcast_mut::<Dropped>() {
+                        message.payload = Some(payload.clone());
+                        message.position = self.cursor;
+                    }
+                    messages.enqueue(message);
+                }
+                Some(DropEvent::Dropped(payload))
+            }
+            _ => None,
+        }
+    }
+
+    /// Updates whether a drag whose payload is accepted is over the target, negotiating
+    /// it with the drag in progress, for internal use only
+    ///
+    /// # Returns
+    /// The change of the target's state, if any
+    ///
+    /// # Arguments
+    /// * `is_over` - whether the drag is over the target and its payload is accepted
+    fn set_over(&mut self, is_over: bool) -> Option<DropEvent> {
+        let key = self.key;
+        DRAG_SESSION.with(|session| {
+            if let Some(session) = session.borrow_mut().as_mut() {
+                session.accepting.retain(|other| *other != key);
+                if is_over {
+                    session.accepting.push(key);
+                }
+            }
+        });
+        if is_over == self.is_over {
+            return None;
+        }
+        self.is_over = is_over;
+        if is_over {
+            Some(DropEvent::Entered)
+        } else {
+            Some(DropEvent::Exited)
+        }
+    }
+}
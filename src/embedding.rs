@@ -0,0 +1,562 @@
+//! Contains the composition of several independent widget trees (i.e., roots) into
+//! sub-rectangles of one display, so that hyber panels can be embedded inside
+//! applications not built with hyber.
+//!
+//! Each root is built at its own size, into its own collection of render instructions and
+//! with its own [`Context`], as if it had a display of its own. The host application
+//! forwards the events of its display to the [`Embedding`], which routes the mouse
+//! events to the root under the cursor and the keyboard events to the root last clicked,
+//! relative to their sub-rectangles. Once a frame is run, the host draws the collection
+//! composed of every root with its [`Renderer`](`crate::renderer::Renderer`).
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::embedding::Embedding;
+//! use hyber::event::{Event, Mouse};
+//! use hyber::util::{Color, Rect, Vector2D};
+//! use hyber::widget::label::LabelWidget;
+//! use hyber::widget::Widget;
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! let label = |text: &str| {
+//!     Rc::new(RefCell::new(LabelWidget::new(
+//!         String::from(text),
+//!         Vector2D::new(100., 20.),
+//!         14,
+//!         Color::from_hex(0xffffffff),
+//!         Color::from_hex(0xff000000),
+//!     ))) as Rc<RefCell<dyn Widget>>
+//! };
+//!
+//! let mut embedding = Embedding::new();
+//! let sidebar = embedding.add_root(
+//!     label("Sidebar"),
+//!     Rect::new(Vector2D::new(0., 0.), Vector2D::new(100., 300.)),
+//! );
+//! let footer = embedding.add_root(
+//!     label("Footer"),
+//!     Rect::new(Vector2D::new(100., 280.), Vector2D::new(300., 20.)),
+//! );
+//! assert_eq!(embedding.root_at(Vector2D::new(150., 290.)), Some(footer));
+//!
+//! // Delivered to the sidebar, 10 units right and 20 units down its upper left corner
+//! embedding.send(Event::Mouse(Mouse::CursorMoved { x: 10, y: 20 }));
+//! embedding.frame();
+//! assert!(!embedding.collection().flatten().is_empty());
+//! assert!(!embedding.drain_damage().is_empty());
+//! # let _ = sidebar;
+//! ```
+
+use crate::clock;
+use crate::context::Context;
+use crate::event::{Event, Mouse, MouseButton, Window};
+use crate::renderer::{
+    run_frame, set_tree_dirty, BlendMode, ClipShape, FrameTree, Headless, Message,
+    RenderInstruction, RenderInstructionCollection,
+};
+use crate::util::{IDMachine, Queue, Rect, Vector2D};
+use crate::widget::Widget;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Handle of a root added to an [`Embedding`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RootHandle(usize);
+
+/// Root embedded into a sub-rectangle of the display, for internal use only
+struct EmbeddedRoot {
+    /// The root's handle, also its key on the composed collection
+    handle: RootHandle,
+    /// The root of the widget tree
+    root: Rc<RefCell<dyn Widget>>,
+    /// The sub-rectangle of the display the root is drawn into
+    area: Rect,
+    /// Whether the root's instructions need to be composed again (e.g. once moved)
+    needs_compose: bool,
+    /// The events routed to the root, relative to its sub-rectangle
+    events: Queue<Event>,
+    /// The identifiers given to the root's widgets
+    id_machine: IDMachine,
+    /// The render instructions of the root's widgets, relative to its sub-rectangle
+    collection: RenderInstructionCollection,
+    /// The shared state of the root's frames
+    context: Rc<RefCell<Context>>,
+}
+
+impl EmbeddedRoot {
+    /// Runs a frame of the root: handles the events routed to it, updates the
+    /// time-driven state of its widgets and builds them, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `messages` - the queue of the messages enqueued by the widgets
+    fn frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        run_frame(
+            &mut Headless,
+            FrameTree {
+                root: &self.root,
+                id_machine: &mut self.id_machine,
+                collection: &mut self.collection,
+                absolute_widgets: None,
+                context: &self.context,
+            },
+            &mut self.events,
+            messages,
+            self.area.size,
+            clock::now(),
+        );
+    }
+
+    /// Gets the root's render instructions moved into its sub-rectangle and clipped by
+    /// it, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the root, in display coordinates
+    ///
+    /// # Arguments
+    /// No arguments
+    fn compose(&self) -> Vec<RenderInstruction> {
+        let origin = Vector2D::new(0., 0.);
+        let mut instructions = vec![
+            RenderInstruction::PushClip {
+                shape: ClipShape::Rect {
+                    point: self.area.position,
+                    size: self.area.size,
+                },
+            },
+            RenderInstruction::PushTransform {
+                translation: self.area.position,
+                rotation: 0.,
+                scale: Vector2D::new(1., 1.),
+            },
+        ];
+        // Clearing the whole display would erase the host application and the other
+        // roots, so only the root's sub-rectangle is filled instead
+        instructions.extend(self.collection.flatten().into_iter().map(|instruction| {
+            match instruction {
+                RenderInstruction::Clear { color } => RenderInstruction::DrawRect {
+                    point: origin,
+                    size: self.area.size,
+                    color,
+                    clip_point: origin,
+                    clip_size: self.area.size,
+                    blend_mode: BlendMode::Normal,
+                },
+                instruction => instruction,
+            }
+        }));
+        instructions.push(RenderInstruction::PopTransform);
+        instructions.push(RenderInstruction::PopClip);
+        instructions
+    }
+
+    /// Maps an area of the root to the display, for internal use only
+    ///
+    /// # Returns
+    /// The area moved into the root's sub-rectangle and clipped by it, or `None` if
+    /// nothing is left of it
+    ///
+    /// # Arguments
+    /// * `local` - the area, relative to the root's sub-rectangle
+    fn to_display(&self, local: Rect) -> Option<Rect> {
+        let area_end = self.area.position + self.area.size;
+        let start = (local.position + self.area.position).max(self.area.position);
+        let end = (local.position + self.area.position + local.size).min(area_end);
+        if end.x <= start.x || end.y <= start.y {
+            return None;
+        }
+        Some(Rect::new(start, end - start))
+    }
+
+    /// Maps a mouse event to the root's sub-rectangle, for internal use only
+    ///
+    /// # Returns
+    /// The event, with the cursor's position relative to the sub-rectangle's upper left
+    /// corner (clamped at zero, once the cursor is grabbed outside of it)
+    ///
+    /// # Arguments
+    /// * `event` - the event, in display coordinates
+    fn to_local(&self, event: Event) -> Event {
+        match event {
+            Event::Mouse(Mouse::CursorMoved { x, y }) => Event::Mouse(Mouse::CursorMoved {
+                x: (x as f64 - self.area.position.x).max(0.).round() as usize,
+                y: (y as f64 - self.area.position.y).max(0.).round() as usize,
+            }),
+            event => event,
+        }
+    }
+}
+
+/// Composes several independent roots into sub-rectangles of one display
+///
+/// The roots are drawn in the order they were added, each one above the previous ones.
+/// The mouse events are routed to the topmost root under the cursor or, while a button
+/// is held, to the root it was pressed on, and the keyboard events to the root last
+/// clicked (see [`set_focused_root`](`Embedding::set_focused_root`)).
+///
+/// _**Note:** The roots share the state of the current thread (e.g. the cursor's position
+/// or the drag and drop in progress), so that only one of them receives the mouse at a time.
+pub struct Embedding {
+    /// The roots embedded, in drawing order
+    roots: Vec<EmbeddedRoot>,
+    /// The handle to be given to the next root added
+    next_handle: usize,
+    /// The root the cursor is over, if any
+    hovered_root: Option<RootHandle>,
+    /// The root receiving the mouse while a button is held, if any
+    grabbing_root: Option<RootHandle>,
+    /// The mouse buttons held
+    buttons_held: Vec<MouseButton>,
+    /// The root receiving the keyboard events, if any
+    focused_root: Option<RootHandle>,
+    /// The cursor's position on the display, if over it
+    pointer: Option<Vector2D>,
+    /// The messages enqueued by the widgets of every root
+    messages: Queue<Box<dyn Message>>,
+    /// The render instructions of every root, in display coordinates
+    collection: RenderInstructionCollection,
+    /// The areas of the display to be redrawn since the damage was last drained
+    damage: Vec<Rect>,
+}
+
+impl Embedding {
+    /// Creates a new `Embedding`
+    ///
+    /// # Returns
+    /// The embedding created, with no roots
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> Embedding {
+        Embedding {
+            roots: Vec::new(),
+            next_handle: 0,
+            hovered_root: None,
+            grabbing_root: None,
+            buttons_held: Vec::new(),
+            focused_root: None,
+            pointer: None,
+            messages: Queue::new(),
+            collection: RenderInstructionCollection::new(),
+            damage: Vec::new(),
+        }
+    }
+
+    /// Adds a root, drawn above the roots added before
+    ///
+    /// # Returns
+    /// The handle of the root added
+    ///
+    /// # Arguments
+    /// * `root` - the root of the widget tree
+    /// * `area` - the sub-rectangle of the display the root is built at and drawn into
+    pub fn add_root(&mut self, root: Rc<RefCell<dyn Widget>>, area: Rect) -> RootHandle {
+        let handle = RootHandle(self.next_handle);
+        self.next_handle += 1;
+        self.roots.push(EmbeddedRoot {
+            handle,
+            root,
+            area,
+            needs_compose: true,
+            events: Queue::new(),
+            id_machine: IDMachine::new(),
+            collection: RenderInstructionCollection::new(),
+            context: Rc::new(RefCell::new(Context::new())),
+        });
+        handle
+    }
+
+    /// Removes a root, erasing its sub-rectangle on the next frame drawn
+    ///
+    /// # Returns
+    /// The root of the widget tree removed, or `None` if no root has the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root
+    pub fn remove_root(&mut self, handle: RootHandle) -> Option<Rc<RefCell<dyn Widget>>> {
+        let index = self.roots.iter().position(|root| root.handle == handle)?;
+        let removed = self.roots.remove(index);
+        self.collection.remove(handle.0);
+        self.collection.clear_damage();
+        self.damage.push(removed.area);
+        for root in [
+            &mut self.hovered_root,
+            &mut self.grabbing_root,
+            &mut self.focused_root,
+        ] {
+            if *root == Some(handle) {
+                *root = None;
+            }
+        }
+        Some(removed.root)
+    }
+
+    /// Gets the sub-rectangle of the display a root is drawn into
+    ///
+    /// # Returns
+    /// The root's area, or `None` if no root has the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root
+    pub fn area(&self, handle: RootHandle) -> Option<Rect> {
+        self.root(handle).map(|root| root.area)
+    }
+
+    /// Sets the sub-rectangle of the display a root is drawn into (e.g. once the host
+    /// application's layout changed), rebuilding it at its new size
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root
+    /// * `area` - the sub-rectangle of the display the root is built at and drawn into
+    pub fn set_area(&mut self, handle: RootHandle, area: Rect) {
+        if let Some(index) = self.roots.iter().position(|root| root.handle == handle) {
+            let root = &mut self.roots[index];
+            self.damage.push(root.area);
+            root.area = area;
+            root.needs_compose = true;
+            set_tree_dirty(&root.root);
+        }
+    }
+
+    /// Gets the shared state of a root's frames (e.g. to set the scale factor)
+    ///
+    /// # Returns
+    /// The pointer to the root's context, or `None` if no root has the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root
+    pub fn context(&self, handle: RootHandle) -> Option<Rc<RefCell<Context>>> {
+        self.root(handle).map(|root| root.context.clone())
+    }
+
+    /// Gets the topmost root whose sub-rectangle contains a point of the display
+    ///
+    /// # Returns
+    /// The handle of the root, or `None` if the point is outside every root
+    ///
+    /// # Arguments
+    /// * `point` - the point, relative to the display's upper left corner
+    pub fn root_at(&self, point: Vector2D) -> Option<RootHandle> {
+        self.roots
+            .iter()
+            .rev()
+            .find(|root| root.area.contains(point))
+            .map(|root| root.handle)
+    }
+
+    /// Gets the root receiving the keyboard events
+    ///
+    /// # Returns
+    /// The handle of the root, or `None` if no root has the focus
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn focused_root(&self) -> Option<RootHandle> {
+        self.focused_root
+    }
+
+    /// Sets the root receiving the keyboard events, until another root is clicked
+    ///
+    /// The host application takes the keyboard from every root (e.g. once one of its own
+    /// controls is focused) by setting no root.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root, or `None` to keep the keyboard events from every root
+    pub fn set_focused_root(&mut self, handle: Option<RootHandle>) {
+        self.focused_root = handle.filter(|handle| self.root(*handle).is_some());
+    }
+
+    /// Adds an event of the display to be routed to the roots on the next frame
+    ///
+    /// The cursor's position is made relative to the sub-rectangle of the root
+    /// receiving it. The window events are routed to every root, except for the
+    /// resizes, since the host application sets the roots' areas.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event, relative to the display's upper left corner
+    pub fn send(&mut self, event: Event) {
+        match event {
+            Event::Mouse(Mouse::CursorMoved { x, y }) => {
+                let pointer = Vector2D::new(x as f64, y as f64);
+                self.pointer = Some(pointer);
+                let hovered_root = self.grabbing_root.or_else(|| self.root_at(pointer));
+                self.hover(hovered_root);
+                self.send_to(hovered_root, event);
+            }
+            Event::Mouse(Mouse::CursorLeft) => {
+                self.pointer = None;
+                if self.grabbing_root.is_none() {
+                    self.hover(None);
+                }
+            }
+            // The roots are entered once the cursor moves over them
+            Event::Mouse(Mouse::CursorEntered) => {}
+            Event::Mouse(Mouse::ButtonPressed(button)) => {
+                let target = self.grabbing_root.or(self.hovered_root);
+                if target.is_some() {
+                    self.grabbing_root = target;
+                    self.focused_root = target;
+                    self.buttons_held.push(button);
+                }
+                self.send_to(target, event);
+            }
+            Event::Mouse(Mouse::ButtonReleased(button)) => {
+                let target = self.grabbing_root.or(self.hovered_root);
+                self.send_to(target, event);
+                self.buttons_held.retain(|held| *held != button);
+                if self.buttons_held.is_empty() && self.grabbing_root.take().is_some() {
+                    // The cursor may have been released over another root
+                    if let Some(pointer) = self.pointer {
+                        let hovered_root = self.root_at(pointer);
+                        if hovered_root != self.hovered_root {
+                            self.hover(hovered_root);
+                            let event = Event::Mouse(Mouse::CursorMoved {
+                                x: pointer.x as usize,
+                                y: pointer.y as usize,
+                            });
+                            self.send_to(hovered_root, event);
+                        }
+                    }
+                }
+            }
+            Event::Mouse(_) => {
+                let target = self.grabbing_root.or(self.hovered_root);
+                self.send_to(target, event);
+            }
+            Event::Keyboard(_) => self.send_to(self.focused_root, event),
+            Event::Window(Window::Resized { .. }) => {}
+            Event::Window(_) => {
+                for root in self.roots.iter_mut() {
+                    root.events.enqueue(event);
+                }
+            }
+        }
+    }
+
+    /// Runs a frame of every root, composes their render instructions and updates the
+    /// messages enqueued by their widgets
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn frame(&mut self) {
+        for root in self.roots.iter_mut() {
+            root.frame(&mut self.messages);
+            let damage = root.collection.damage();
+            root.collection.clear_damage();
+            if damage.is_empty() && !root.needs_compose {
+                continue;
+            }
+            // A root moved (or just added) is redrawn whole
+            if root.needs_compose {
+                self.damage.push(root.area);
+                root.needs_compose = false;
+            } else {
+                self.damage
+                    .extend(damage.into_iter().filter_map(|area| root.to_display(area)));
+            }
+            self.collection
+                .replace_or_insert(root.handle.0, root.compose());
+        }
+        // The damage of the composed collection covers whole roots, while the roots'
+        // own damage covers only their widgets changed
+        self.collection.clear_damage();
+
+        for message in self.messages.queue.drain(..) {
+            message.update();
+        }
+    }
+
+    /// Gets the render instructions of every root, as composed on the last frame, to be
+    /// drawn with [`draw_collection`](`crate::renderer::Renderer::draw_collection`)
+    ///
+    /// # Returns
+    /// The collection of render instructions, in display coordinates
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn collection(&self) -> &RenderInstructionCollection {
+        &self.collection
+    }
+
+    /// Gets and clears the damage (i.e., the areas of the display to be redrawn)
+    ///
+    /// # Returns
+    /// The areas of the display whose render instructions changed since the damage
+    /// was last drained, each one within a root's sub-rectangle
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn drain_damage(&mut self) -> Vec<Rect> {
+        self.damage.drain(..).collect()
+    }
+
+    /// Gets an embedded root, for internal use only
+    ///
+    /// # Returns
+    /// The root, or `None` if no root has the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root
+    fn root(&self, handle: RootHandle) -> Option<&EmbeddedRoot> {
+        self.roots.iter().find(|root| root.handle == handle)
+    }
+
+    /// Routes an event to a root, relative to its sub-rectangle, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root, or `None` to drop the event
+    /// * `event` - the event, in display coordinates
+    fn send_to(&mut self, handle: Option<RootHandle>, event: Event) {
+        if let Some(root) = self
+            .roots
+            .iter_mut()
+            .find(|root| Some(root.handle) == handle)
+        {
+            let event = root.to_local(event);
+            root.events.enqueue(event);
+        }
+    }
+
+    /// Moves the cursor over another root, notifying the root left and the root
+    /// entered (i.e., before the cursor's position on it), for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the root the cursor is over, or `None` if none
+    fn hover(&mut self, handle: Option<RootHandle>) {
+        if handle == self.hovered_root {
+            return;
+        }
+        self.send_to(self.hovered_root, Event::Mouse(Mouse::CursorLeft));
+        self.send_to(handle, Event::Mouse(Mouse::CursorEntered));
+        self.hovered_root = handle;
+    }
+}
+
+impl Default for Embedding {
+    fn default() -> Self {
+        Embedding::new()
+    }
+}
@@ -3,6 +3,9 @@
 //! [`hyber`](`crate`) has a long list of events that should cover the majority of events.
 
 use crate::key_code::KeyCode;
+use crate::util::Vector2D;
+
+use std::cell::Cell;
 
 /// The current state of the keyboard modifiers
 #[derive(Debug, Copy, Clone)]
@@ -70,6 +73,10 @@ pub enum Keyboard {
 
     /// The keyboard modifiers have changed
     ModifiersChanged(ModifiersState),
+
+    /// A character was typed (i.e., the text input produced by the keys pressed,
+    /// according to the keyboard layout)
+    CharacterReceived(char),
 }
 
 /// A mouse event
@@ -101,10 +108,41 @@ pub enum Mouse {
         /// The scroll movement
         delta: ScrollDelta,
     },
+
+    /// The mouse cursor entered the area of a widget. It is synthesized by the event
+    /// loop, by hit testing consecutive cursor positions, and delivered right after the
+    /// `CursorMoved` or `CursorLeft` that caused it, to be handled by the widget with
+    /// the given identifier
+    PointerEntered {
+        /// The identifier of the widget entered
+        id: usize,
+    },
+
+    /// The mouse cursor exited the area of a widget, synthesized like `PointerEntered`
+    PointerExited {
+        /// The identifier of the widget exited
+        id: usize,
+    },
+
+    /// A mouse button was pressed while another one was held (e.g. left and right
+    /// buttons pressed together). It is detected by the event loop and delivered
+    /// right after the `ButtonPressed` of the second button
+    Chord {
+        /// The button held
+        first: MouseButton,
+
+        /// The button pressed while the first one was held
+        second: MouseButton,
+    },
+
+    /// A mouse button was pressed twice in a row, within the double-click interval and
+    /// near the cursor's position of the first press. It is detected by the event loop
+    /// and delivered right after the second `ButtonPressed`
+    ButtonDoubleClicked(MouseButton),
 }
 
 /// A mouse button
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseButton {
     /// The left mouse button.
     Left,
@@ -145,6 +183,24 @@ pub enum Window {
         /// The new height of the window
         height: u32,
     },
+
+    /// The window gained (true) or lost (false) the focus
+    Focused(bool),
+
+    /// The window was minimized
+    Minimized,
+
+    /// The window was restored after being minimized
+    Restored,
+
+    /// The number of device pixels per logical unit changed (e.g. the window
+    /// moved to a monitor with a different DPI)
+    ScaleFactorChanged(f64),
+
+    /// The window gained the focus after the user's attention was requested through
+    /// [`WindowRequest::RequestAttention`](`crate::context::WindowRequest::RequestAttention`).
+    /// It is detected by the event loop and delivered right after the `Focused` event
+    AttentionAcknowledged,
 }
 
 /// An user interface event
@@ -159,3 +215,190 @@ pub enum Event {
     ///A windown event (eg. Resize, ...)
     Window(Window),
 }
+
+/// The position of the mouse cursor, both on the window and relative to a widget
+#[derive(Copy, Clone)]
+pub struct PointerInfo {
+    /// The cursor's position relative to the window's upper left corner
+    pub absolute: Vector2D,
+
+    /// The cursor's position relative to the widget's upper left corner
+    pub local: Vector2D,
+}
+
+impl PointerInfo {
+    /// Creates a new `PointerInfo`
+    ///
+    /// # Returns
+    /// The position of the cursor relative to the window and to the widget
+    ///
+    /// # Arguments
+    /// * `absolute` - the cursor's position relative to the window's upper left corner
+    /// * `origin` - the widget's upper left corner
+    pub fn new(absolute: Vector2D, origin: Vector2D) -> PointerInfo {
+        PointerInfo {
+            absolute,
+            local: absolute - origin,
+        }
+    }
+
+    /// Checks if the cursor is inside an area starting at the widget's upper left corner
+    ///
+    /// # Returns
+    /// True, if the cursor is inside the area, false otherwise
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) of the area
+    pub fn is_inside(&self, size: Vector2D) -> bool {
+        self.local.x >= 0. && self.local.y >= 0. && self.local.x <= size.x && self.local.y <= size.y
+    }
+}
+
+thread_local! {
+    /// The position of the mouse cursor on the window, as of the event being dispatched
+    static POINTER_POSITION: Cell<Option<Vector2D>> = const { Cell::new(None) };
+
+    /// The address of the widget capturing the mouse, if any
+    static POINTER_CAPTURE: Cell<Option<usize>> = const { Cell::new(None) };
+
+    /// The state of the keyboard modifiers, as of the event being dispatched
+    static MODIFIERS: Cell<ModifiersState> = const {
+        Cell::new(ModifiersState {
+            shift: false,
+            control: false,
+            alt: false,
+            logo: false,
+        })
+    };
+}
+
+/// Updates the position of the mouse cursor with an event about to be dispatched to
+/// the widgets, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `event` - the event about to be dispatched
+pub(crate) fn track_pointer(event: &Event) {
+    match event {
+        Event::Mouse(Mouse::CursorMoved { x, y }) => POINTER_POSITION
+            .with(|position| position.set(Some(Vector2D::new(*x as f64, *y as f64)))),
+        Event::Mouse(Mouse::CursorLeft) => POINTER_POSITION.with(|position| position.set(None)),
+        _ => {}
+    }
+}
+
+/// Updates the state of the keyboard modifiers with an event about to be dispatched to
+/// the widgets, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `event` - the event about to be dispatched
+pub(crate) fn track_modifiers(event: &Event) {
+    match event {
+        Event::Keyboard(Keyboard::ModifiersChanged(modifiers))
+        | Event::Keyboard(Keyboard::KeyPressed { modifiers, .. })
+        | Event::Keyboard(Keyboard::KeyReleased { modifiers, .. }) => {
+            MODIFIERS.with(|current| current.set(*modifiers))
+        }
+        _ => {}
+    }
+}
+
+/// Gets the state of the keyboard modifiers, as of the event being dispatched
+///
+/// Lets the widgets handling mouse events tell whether a modifier is held (e.g. Shift
+/// while turning the wheel to scroll horizontally).
+///
+/// # Returns
+/// The modifiers held
+///
+/// # Arguments
+/// No arguments
+pub fn modifiers() -> ModifiersState {
+    MODIFIERS.with(|current| current.get())
+}
+
+/// Gets the position of the mouse cursor on the window, as of the event being dispatched
+///
+/// Widgets usually get it relative to themselves through
+/// [`pointer_info`](`crate::widget::Widget::pointer_info`) instead.
+///
+/// # Returns
+/// The cursor's position relative to the window's upper left corner, or `None` if the
+/// cursor is outside the window or did not move yet
+///
+/// # Arguments
+/// No arguments
+pub fn pointer_position() -> Option<Vector2D> {
+    POINTER_POSITION.with(|position| position.get())
+}
+
+/// Sets the widget capturing the mouse, for internal use only (see
+/// [`capture_pointer`](`crate::widget::Widget::capture_pointer`))
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `address` - the address of the widget capturing the mouse, or `None` to release it
+pub(crate) fn set_pointer_capture(address: Option<usize>) {
+    POINTER_CAPTURE.with(|capture| capture.set(address));
+}
+
+/// Gets the widget capturing the mouse, for internal use only
+///
+/// # Returns
+/// The address of the widget capturing the mouse, or `None` if the mouse is not captured
+///
+/// # Arguments
+/// No arguments
+pub(crate) fn pointer_capture() -> Option<usize> {
+    POINTER_CAPTURE.with(|capture| capture.get())
+}
+
+/// Releases the mouse captured by a widget, if any, dispatching the mouse events to
+/// the widget tree again
+///
+/// The mouse is released on its own once the button is released, so widgets only
+/// need to release it to end a capture earlier (e.g. when a drag is cancelled).
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// No arguments
+pub fn release_pointer() {
+    set_pointer_capture(None);
+}
+
+/// Checks whether the mouse is captured by a widget
+///
+/// # Returns
+/// True, if a widget is capturing the mouse, false otherwise
+///
+/// # Arguments
+/// No arguments
+pub fn is_pointer_captured() -> bool {
+    pointer_capture().is_some()
+}
+
+/// Releases the mouse captured once an event was dispatched, if the event releases a
+/// mouse button or the window lost the focus, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `event` - the event dispatched
+pub(crate) fn finish_capture(event: &Event) {
+    match event {
+        Event::Mouse(Mouse::ButtonReleased(_)) | Event::Window(Window::Focused(false)) => {
+            release_pointer()
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,419 @@
+//! Contains a gallery of every built-in widget, enabled by the `gallery` feature.
+//!
+//! The gallery builds a widget tree with one instance of every built-in widget,
+//! with their interactions wired to messages that do nothing. Together, they emit
+//! every kind of [`RenderInstruction`](`crate::renderer::RenderInstruction`), so
+//! authors of a [`Renderer`](`crate::renderer::Renderer`) can mount the gallery to
+//! validate their implementation.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! let gallery = Gallery::new(Vector2D::new(1280., 720.));
+//!
+//! renderer.event_loop(
+//!     events,
+//!     messages,
+//!     gallery.root(),
+//!     ...
+//! );
+//! ```
+
+use crate::message::Clicked;
+use crate::renderer::{Fill, FontDescriptor, FontStyle, FontWeight, Message};
+use crate::util::{Color, ImageData, Vector2D};
+use crate::widget::button_group::ButtonGroupWidget;
+use crate::widget::button_view::ButtonViewWidget;
+use crate::widget::calendar::CalendarWidget;
+use crate::widget::checkbox::CheckBoxWidget;
+use crate::widget::circular_progress::CircularProgressWidget;
+use crate::widget::command_palette::CommandPaletteWidget;
+use crate::widget::console::{ConsoleSpan, ConsoleWidget};
+use crate::widget::desktop::DesktopWidget;
+use crate::widget::dock_area::DockAreaWidget;
+use crate::widget::dropdown::DropdownWidget;
+use crate::widget::expandable_text::ExpandableTextWidget;
+use crate::widget::grid_view::GridViewWidget;
+use crate::widget::icon::IconWidget;
+use crate::widget::image::ImageWidget;
+use crate::widget::internal_window::InternalWindowWidget;
+use crate::widget::keyboard_hints::KeyboardHintsWidget;
+use crate::widget::label::LabelWidget;
+use crate::widget::list_view::ListViewWidget;
+use crate::widget::palette::PaletteWidget;
+use crate::widget::panel::PanelWidget;
+use crate::widget::path_input::PathInputWidget;
+use crate::widget::progress_bar::ProgressBarWidget;
+use crate::widget::radio::RadioButtonWidget;
+use crate::widget::root::{BackgroundLayer, RootWidget};
+use crate::widget::scroll_view::ScrollViewWidget;
+use crate::widget::scrollbar::{ScrollBarWidget, ScrollExtent};
+use crate::widget::skeleton::{SkeletonShape, SkeletonWidget};
+use crate::widget::slider::SliderWidget;
+use crate::widget::sliver_view::SliverViewWidget;
+use crate::widget::stack::{StackAlignment, StackWidget};
+use crate::widget::tab::TabWidget;
+use crate::widget::tab_bar::TabBarWidget;
+use crate::widget::textbox::TextBoxWidget;
+use crate::widget::time_picker::TimePickerWidget;
+use crate::widget::title_bar::TitleBarWidget;
+use crate::widget::tooltip_view::TooltipViewWidget;
+use crate::widget::window_resize_handle::WindowResizeHandleWidget;
+use crate::widget::{Axis, Layout, Widget, WidgetRef};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Number of columns of the gallery's grid
+const COLUMNS: usize = 6;
+
+/// Widget tree with one instance of every built-in widget
+///
+/// The gallery owns every widget of the tree, since widgets only hold weak
+/// pointers to their children, so it must be kept alive while the tree is displayed.
+pub struct Gallery {
+    /// The root of the gallery's widget tree
+    root: Rc<RefCell<dyn Widget>>,
+    /// The widgets of the gallery, in the order they were added
+    widgets: Vec<Rc<RefCell<dyn Widget>>>,
+}
+
+impl Gallery {
+    /// Creates a new `Gallery`, laying out the widgets on a grid
+    ///
+    /// # Returns
+    /// The gallery created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) of the gallery's root
+    pub fn new(size: Vector2D) -> Gallery {
+        let root = RootWidget::new(
+            size,
+            Color::from_hex(0xffeeeeee),
+            Layout::Grid(Axis::Vertical, COLUMNS),
+        )
+        .with_background_layers(vec![BackgroundLayer::Fill(Fill::LinearGradient {
+            start_color: Color::from_hex(0xffeeeeee),
+            end_color: Color::from_hex(0xffdddddd),
+            direction: Vector2D::new(0., 1.),
+        })]);
+        let mut gallery = Gallery {
+            root: Rc::new(RefCell::new(root)),
+            widgets: Vec::new(),
+        };
+
+        let text_color = Color::from_hex(0xff000000);
+        let background_color = Color::from_hex(0xffffffff);
+        let accent_color = Color::from_hex(0xff2196f3);
+
+        let button = gallery.add(
+            None,
+            ButtonViewWidget::new(
+                Vector2D::new(120., 40.),
+                true,
+                accent_color,
+                Some(no_op("button")),
+                Some(no_op("button long press")),
+            ),
+        );
+        gallery.add(
+            Some(&button),
+            LabelWidget::new(
+                String::from("Button"),
+                Vector2D::new(120., 40.),
+                16,
+                accent_color,
+                background_color,
+            ),
+        );
+
+        let button_group = gallery.add(
+            None,
+            ButtonGroupWidget::new(
+                Vector2D::new(120., 40.),
+                Axis::Horizontal,
+                true,
+                accent_color,
+                Some(no_op("button group")),
+            ),
+        );
+        for _ in 0..3 {
+            gallery.add(Some(&button_group), CheckBoxWidget::default());
+        }
+
+        gallery.add(None, CalendarWidget::default());
+        gallery.add(
+            None,
+            CheckBoxWidget::new(
+                Vector2D::new(24., 24.),
+                background_color,
+                accent_color,
+                text_color,
+                Some(no_op("checkbox")),
+                true,
+                2.,
+                0.2,
+            ),
+        );
+        gallery.add(
+            None,
+            CircularProgressWidget::default()
+                .with_progress(60.)
+                .with_foreground_color(accent_color),
+        );
+        gallery.add(None, CommandPaletteWidget::default());
+        let mut console = ConsoleWidget::default();
+        console.push_text("Console");
+        console.push_spans(vec![
+            ConsoleSpan::new("error: ", Color::from_hex(0xfff44747)),
+            ConsoleSpan::new("colored span", Color::from_hex(0xffd4d4d4)),
+        ]);
+        gallery.add(None, console);
+        gallery.add(None, DesktopWidget::default());
+        gallery.add(None, DockAreaWidget::default());
+        gallery.add(
+            None,
+            DropdownWidget::default()
+                .with_items(vec![String::from("First"), String::from("Second")])
+                .with_selected(Some(0))
+                .with_on_select(Some(no_op("dropdown"))),
+        );
+        gallery.add(
+            None,
+            ExpandableTextWidget::default()
+                .with_text(String::from(
+                    "A long description, wrapped on many lines, of which only the first \
+                     ones are shown until the text is expanded with its toggle",
+                ))
+                .with_max_lines(2)
+                .with_on_toggle(Some(no_op("expandable text"))),
+        );
+
+        let grid_view = gallery.add(None, GridViewWidget::default());
+        for text in ["A", "B", "C", "D"].iter() {
+            gallery.add(Some(&grid_view), label(text));
+        }
+
+        gallery.add(None, IconWidget::default());
+        // Checkerboard generated in memory, not read from a file
+        gallery.add(
+            None,
+            ImageWidget::default().with_data(Rc::new(ImageData::from_fn(8, 8, |x, y| {
+                if (x + y) % 2 == 0 {
+                    background_color
+                } else {
+                    accent_color
+                }
+            }))),
+        );
+        gallery.add(None, InternalWindowWidget::default());
+        gallery.add(None, KeyboardHintsWidget::default());
+        gallery.add(None, label("Label"));
+        gallery.add(
+            None,
+            label("Bold italic label").with_font(
+                FontDescriptor::default()
+                    .with_weight(FontWeight::BOLD)
+                    .with_style(FontStyle::Italic),
+            ),
+        );
+
+        let list_view = gallery.add(None, ListViewWidget::default());
+        for text in ["First", "Second", "Third"].iter() {
+            gallery.add(Some(&list_view), label(text));
+        }
+
+        gallery.add(
+            None,
+            PaletteWidget::default().with_on_color_selected(Some(no_op("palette"))),
+        );
+
+        let mut panel = PanelWidget::new(
+            Vector2D::new(120., 80.),
+            true,
+            accent_color,
+            Some(no_op("panel")),
+            Some(no_op("panel long press")),
+        );
+        panel.set_corner_radius(Some(8.));
+        panel.set_background_fill(Some(Fill::RadialGradient {
+            start_color: background_color,
+            end_color: accent_color,
+        }));
+        let panel = gallery.add(None, panel);
+        gallery.add(Some(&panel), label("Panel"));
+
+        gallery.add(None, PathInputWidget::default());
+        gallery.add(
+            None,
+            ProgressBarWidget::new(Vector2D::new(160., 24.), 14, 40., background_color, accent_color)
+                .with_foreground_fill(Fill::LinearGradient {
+                    start_color: accent_color,
+                    end_color: Color::from_hex(0xff9c27b0),
+                    direction: Vector2D::new(1., 0.),
+                }),
+        );
+        gallery.add(
+            None,
+            RadioButtonWidget::default()
+                .with_label("Radio button")
+                .with_is_checked(true),
+        );
+
+        // Content overflowing along both axes, showing both scroll bars and the gutter
+        let scroll_view = gallery.add(
+            None,
+            ScrollViewWidget::default().with_on_scroll(Some(no_op("scroll view"))),
+        );
+        gallery.add(
+            Some(&scroll_view),
+            PanelWidget::default()
+                .with_size(Vector2D::new(400., 300.))
+                .with_background_fill(Fill::LinearGradient {
+                    start_color: background_color,
+                    end_color: accent_color,
+                    direction: Vector2D::new(1., 1.),
+                }),
+        );
+
+        gallery.add(
+            None,
+            ScrollBarWidget::default()
+                .with_extent(ScrollExtent::new(100., 400., 160.))
+                .with_on_scroll(Some(no_op("scroll bar"))),
+        );
+        gallery.add(None, SkeletonWidget::default());
+        gallery.add(None, SkeletonWidget::default().with_shape(SkeletonShape::Circle));
+        gallery.add(
+            None,
+            SliderWidget::new(
+                Vector2D::new(160., 24.),
+                background_color,
+                accent_color,
+                Vector2D::new(12., 24.),
+                (0, 100),
+                10,
+                50,
+                Some(no_op("slider")),
+            ),
+        );
+
+        let stack = gallery.add(
+            None,
+            StackWidget::default().with_alignment(StackAlignment::Center),
+        );
+        gallery.add(Some(&stack), IconWidget::default());
+        gallery.add(Some(&stack), label("Stack"));
+
+        let sliver_view = gallery.add(None, SliverViewWidget::default());
+        for text in ["One", "Two", "Three", "Four", "Five"].iter() {
+            gallery.add(Some(&sliver_view), label(text));
+        }
+
+        let tab = gallery.add(
+            None,
+            TabWidget::new(
+                Vector2D::new(120., 40.),
+                background_color,
+                Some(no_op("tab")),
+                Some(no_op("tab moved")),
+            ),
+        );
+        gallery.add(Some(&tab), label("Tab"));
+
+        let tab_bar = gallery.add(None, TabBarWidget::default());
+        for text in ["First", "Second"].iter() {
+            gallery.add(Some(&tab_bar), label(text));
+        }
+        tab_bar.with_widget(|tab_bar: &mut TabBarWidget| {
+            tab_bar.set_tab_title(0, "First");
+            tab_bar.set_tab_title(1, "Second");
+        });
+
+        gallery.add(
+            None,
+            TextBoxWidget::new(
+                Vector2D::new(160., 32.),
+                background_color,
+                text_color,
+                1.,
+                String::from("Text box"),
+                Some(no_op("textbox")),
+            ),
+        );
+        gallery.add(None, TimePickerWidget::default());
+        gallery.add(None, TitleBarWidget::default());
+        gallery.add(None, TooltipViewWidget::default());
+        gallery.add(None, WindowResizeHandleWidget::default());
+
+        gallery
+    }
+
+    /// Adds a widget to the gallery, owned by it
+    ///
+    /// # Returns
+    /// The widget added
+    ///
+    /// # Arguments
+    /// * `parent` - the widget's parent, or `None` to add it to the gallery's grid
+    /// * `widget` - the widget to be added
+    fn add<W: Widget + 'static>(
+        &mut self,
+        parent: Option<&Rc<RefCell<dyn Widget>>>,
+        widget: W,
+    ) -> Rc<RefCell<dyn Widget>> {
+        let widget = Rc::new(RefCell::new(widget)) as Rc<RefCell<dyn Widget>>;
+        parent
+            .unwrap_or(&self.root)
+            .borrow_mut()
+            .add_as_child(Rc::downgrade(&widget));
+        self.widgets.push(widget.clone());
+        widget
+    }
+
+    /// Gets the root of the gallery's widget tree, to be handed to the event loop
+    ///
+    /// # Returns
+    /// The pointer to the gallery's root
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn root(&self) -> Rc<RefCell<dyn Widget>> {
+        self.root.clone()
+    }
+
+    /// Gets the widgets of the gallery (e.g. to inspect their render instructions)
+    ///
+    /// # Returns
+    /// The widgets of the gallery, in the order they were added
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn widgets(&self) -> &[Rc<RefCell<dyn Widget>>] {
+        &self.widgets
+    }
+}
+
+/// Creates a message that does nothing when handled, for internal use only
+///
+/// # Returns
+/// The message created
+///
+/// # Arguments
+/// * `widget_key` - the key identifying the widget handling the message
+fn no_op(widget_key: &str) -> Box<dyn Message> {
+    Box::new(Clicked::new(widget_key, |_| {}))
+}
+
+/// Creates a label displaying a text, for internal use only
+///
+/// # Returns
+/// The label created
+///
+/// # Arguments
+/// * `text` - the text to be displayed
+fn label(text: &str) -> LabelWidget {
+    LabelWidget::default()
+        .with_text(String::from(text))
+        .with_size(Vector2D::new(80., 20.))
+}
@@ -0,0 +1,220 @@
+//! Contains a harness to run the frames of a widget tree without a display, with
+//! a virtual clock, to test the behavior of widgets over time.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::harness::Harness;
+//! use hyber::util::{Color, Vector2D};
+//! use hyber::widget::label::LabelWidget;
+//! use hyber::widget::Widget;
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//! use std::time::Duration;
+//!
+//! let label = Rc::new(RefCell::new(LabelWidget::new(
+//!     String::from("Hello"),
+//!     Vector2D::new(100., 20.),
+//!     14,
+//!     Color::from_hex(0xffffffff),
+//!     Color::from_hex(0xff000000),
+//! )));
+//! let mut harness = Harness::new(label as Rc<RefCell<dyn Widget>>, Vector2D::new(100., 20.));
+//!
+//! harness.frame();
+//! assert!(!harness.instructions().is_empty());
+//!
+//! harness.advance(Duration::from_secs(1));
+//! assert_eq!(harness.clock().elapsed(), Duration::from_secs(1));
+//! ```
+
+use crate::clock::{self, VirtualClock};
+use crate::context::Context;
+use crate::event::Event;
+use crate::renderer::{
+    run_frame, set_tree_dirty, FrameTree, Headless, Message, RenderInstruction,
+    RenderInstructionCollection,
+};
+use crate::util::{IDMachine, Queue, Rect, Vector2D};
+use crate::widget::Widget;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Runs the frames of a widget tree like the event loop does, without a display
+///
+/// While the harness exists, the widgets of the current thread read the time from its
+/// [`VirtualClock`], which only changes when advanced. The messages are collected
+/// instead of being updated, so that they can be inspected.
+pub struct Harness {
+    /// The root of the widget tree
+    root: Rc<RefCell<dyn Widget>>,
+    /// The size (width and height) at which the widget tree is built
+    size: Vector2D,
+    /// The clock read by the widgets
+    clock: VirtualClock,
+    /// The events to be handled on the next frame
+    events: Queue<Event>,
+    /// The messages enqueued by the widgets, not taken yet
+    messages: Queue<Box<dyn Message>>,
+    /// The identifiers given to the widgets built
+    id_machine: IDMachine,
+    /// The render instructions of the widgets built
+    collection: RenderInstructionCollection,
+    /// The areas whose render instructions changed on the last frame
+    damage: Vec<Rect>,
+    /// The shared state of the frames
+    context: Rc<RefCell<Context>>,
+}
+
+impl Harness {
+    /// Creates a new `Harness`, setting its virtual clock as the clock of the current thread
+    ///
+    /// # Returns
+    /// The harness created, with no frames run
+    ///
+    /// # Arguments
+    /// * `root` - the root of the widget tree
+    /// * `size` - the size (width and height) at which the widget tree is built
+    pub fn new(root: Rc<RefCell<dyn Widget>>, size: Vector2D) -> Harness {
+        let clock = VirtualClock::new();
+        let mut context = Context::new();
+        context.set_clock(Rc::new(clock.clone()));
+        Harness {
+            root,
+            size,
+            clock,
+            events: Queue::new(),
+            messages: Queue::new(),
+            id_machine: IDMachine::new(),
+            collection: RenderInstructionCollection::new(),
+            damage: Vec::new(),
+            context: Rc::new(RefCell::new(context)),
+        }
+    }
+
+    /// Gets the clock read by the widgets
+    ///
+    /// # Returns
+    /// The virtual clock of the harness
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Gets the shared state of the frames (e.g. to change the scale factor)
+    ///
+    /// # Returns
+    /// The pointer to the context
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn context(&self) -> Rc<RefCell<Context>> {
+        self.context.clone()
+    }
+
+    /// Sets the size (width and height) at which the widget tree is built
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) at which the widget tree is built
+    pub fn set_size(&mut self, size: Vector2D) {
+        self.size = size;
+        set_tree_dirty(&self.root);
+    }
+
+    /// Adds an event to be handled on the next frame
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event to be handled
+    pub fn send(&mut self, event: Event) {
+        self.events.enqueue(event);
+    }
+
+    /// Runs a frame: handles the events sent, updates the time-driven state of the
+    /// widgets and builds them
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn frame(&mut self) {
+        run_frame(
+            &mut Headless,
+            FrameTree {
+                root: &self.root,
+                id_machine: &mut self.id_machine,
+                collection: &mut self.collection,
+                absolute_widgets: None,
+                context: &self.context,
+            },
+            &mut self.events,
+            &mut self.messages,
+            self.size,
+            clock::now(),
+        );
+        self.damage = self.collection.damage();
+        self.collection.clear_damage();
+    }
+
+    /// Advances the virtual clock and runs a frame
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `duration` - the time to be advanced before the frame
+    pub fn advance(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.frame();
+    }
+
+    /// Gets the render instructions of the widget tree, as built on the last frame
+    ///
+    /// # Returns
+    /// The render instructions, in drawing order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn instructions(&self) -> Vec<RenderInstruction> {
+        self.collection.flatten()
+    }
+
+    /// Gets the damage of the last frame (i.e., the areas a renderer would redraw)
+    ///
+    /// # Returns
+    /// The areas whose render instructions changed on the last frame, with the
+    /// overlapping ones merged
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn damage(&self) -> &[Rect] {
+        &self.damage
+    }
+
+    /// Takes the messages enqueued by the widgets so far, without updating them
+    ///
+    /// # Returns
+    /// The messages enqueued, in order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn take_messages(&mut self) -> Vec<Box<dyn Message>> {
+        self.messages.queue.drain(..).collect()
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        clock::reset_clock();
+    }
+}
@@ -0,0 +1,416 @@
+//! Contains an immediate-mode facade over the retained widget tree, for quick prototypes.
+//!
+//! Instead of creating widgets once and updating them by hand, the application declares
+//! its widgets on every frame through a [`Ui`] (e.g. `ui.button("OK")`, which tells
+//! whether the button was clicked since the last frame, or `ui.slider(&mut value)`),
+//! and [`end_frame`](`Ui::end_frame`) reconciles them against a container of the
+//! retained tree. Each widget is identified by its call site (and by the number of
+//! times the call site was declared before, e.g. in a loop), so the widgets declared
+//! again are updated, the new ones are created and the ones no longer declared are
+//! removed. Call sites declaring a variable number of widgets may be wrapped by a
+//! [`scope`](`Ui::scope`) with a key of their own (e.g. an item's name), so that the
+//! widgets follow their items when these are reordered.
+//!
+//! The widgets report their changes through messages, so the values of the frame
+//! declared after the messages are updated by the event loop (e.g. in a before-frame
+//! hook of the [`Context`](`crate::context::Context`)) are the ones changed by the user.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::immediate::Ui;
+//! use hyber::widget::list_view::ListViewWidget;
+//! use hyber::widget::Widget;
+//!
+//! let mut ui = Ui::new();
+//! let mut list = ListViewWidget::default();
+//! let mut volume = 50;
+//!
+//! for show_volume in [true, false] {
+//!     ui.label("Settings");
+//!     if show_volume {
+//!         ui.slider(&mut volume, (0, 100));
+//!     }
+//!     if ui.button("Reset") {
+//!         volume = 50;
+//!     }
+//!     ui.end_frame(&mut list);
+//!     assert_eq!(list.get_children().len(), if show_volume { 3 } else { 2 });
+//! }
+//! ```
+
+use crate::message::{Clicked, ValueChanged};
+use crate::util::MemoKey;
+use crate::widget::button_view::ButtonViewWidget;
+use crate::widget::checkbox::CheckBoxWidget;
+use crate::widget::label::LabelWidget;
+use crate::widget::slider::SliderWidget;
+use crate::widget::Widget;
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::panic::Location;
+use std::rc::{Rc, Weak};
+
+/// Key identifying a widget declared on a [`Ui`], for internal use only
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct UiKey {
+    /// The hash of the keys of the scopes the widget is declared in
+    scope: u64,
+    /// The file of the call site declaring the widget
+    file: &'static str,
+    /// The line of the call site declaring the widget
+    line: u32,
+    /// The column of the call site declaring the widget
+    column: u32,
+    /// The number of times the call site was declared before on the frame, in the scope
+    occurrence: usize,
+}
+
+/// Widget declared on a [`Ui`], along with the state read back when declared again,
+/// for internal use only
+enum UiWidget {
+    /// A label, with the text last declared
+    Label {
+        /// The label's widget
+        label: Rc<RefCell<LabelWidget>>,
+        /// The text last declared
+        text: String,
+    },
+    /// A button showing a label
+    Button {
+        /// The button's widget
+        button: Rc<RefCell<ButtonViewWidget>>,
+        /// The label shown on the button, owned here since the button holds it weakly
+        label: Rc<RefCell<LabelWidget>>,
+        /// The text last declared
+        text: String,
+        /// Whether the button was clicked since it was last declared
+        clicked: Rc<Cell<bool>>,
+    },
+    /// A checkbox
+    CheckBox {
+        /// The checkbox's widget
+        checkbox: Rc<RefCell<CheckBoxWidget>>,
+        /// The value set by the user since the checkbox was last declared, if any
+        changed: Rc<Cell<Option<bool>>>,
+    },
+    /// A slider
+    Slider {
+        /// The slider's widget
+        slider: Rc<RefCell<SliderWidget>>,
+        /// The range last declared
+        range: (i32, i32),
+        /// The value set by the user since the slider was last declared, if any
+        changed: Rc<Cell<Option<i32>>>,
+    },
+}
+
+impl UiWidget {
+    /// Gets the widget to be placed on the container, for internal use only
+    ///
+    /// # Returns
+    /// The widget declared
+    ///
+    /// # Arguments
+    /// No arguments
+    fn widget(&self) -> Rc<RefCell<dyn Widget>> {
+        match self {
+            UiWidget::Label { label, .. } => label.clone(),
+            UiWidget::Button { button, .. } => button.clone(),
+            UiWidget::CheckBox { checkbox, .. } => checkbox.clone(),
+            UiWidget::Slider { slider, .. } => slider.clone(),
+        }
+    }
+}
+
+/// Immediate-mode facade declaring widgets on every frame, reconciled against a
+/// container of the retained widget tree
+///
+/// The `Ui` owns the widgets it creates, since containers only hold weak pointers to
+/// their children, so it must be kept alive while they are displayed.
+#[derive(Default)]
+pub struct Ui {
+    /// The widgets declared so far, by key
+    widgets: HashMap<UiKey, UiWidget>,
+    /// The keys of the widgets declared on the current frame, in order
+    declared: Vec<UiKey>,
+    /// The number of times each call site was declared on the current frame, by the
+    /// key of the call site's first declaration
+    occurrences: HashMap<UiKey, usize>,
+    /// The hashes of the scopes entered, the innermost last
+    scopes: Vec<u64>,
+}
+
+impl Ui {
+    /// Creates a new `Ui`, with no widgets declared
+    ///
+    /// # Returns
+    /// The facade created
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> Ui {
+        Ui::default()
+    }
+
+    /// Declares the widgets of a closure within a scope, identifying them by the
+    /// scope's key besides their call sites (e.g. the widgets of an item, keyed by the
+    /// item's name, so that they follow the item when the items are reordered)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `key` - the key of the scope, unique among the scopes of the same call site
+    /// * `declare` - the closure declaring the widgets of the scope
+    pub fn scope<F: FnOnce(&mut Ui)>(&mut self, key: &str, declare: F) {
+        let scope = MemoKey::new().with(&self.scopes.last()).with(key).finish();
+        self.scopes.push(scope);
+        declare(self);
+        self.scopes.pop();
+    }
+
+    /// Declares a label
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `text` - the label's text
+    #[track_caller]
+    pub fn label(&mut self, text: &str) {
+        let widget = self.declare(Location::caller(), "label", || UiWidget::Label {
+            label: Rc::new(RefCell::new(
+                LabelWidget::default().with_text(String::from(text)),
+            )),
+            text: String::from(text),
+        });
+        if let UiWidget::Label {
+            label,
+            text: last_text,
+        } = widget
+        {
+            if last_text != text {
+                label.borrow_mut().set_text(String::from(text));
+                *last_text = String::from(text);
+            }
+        }
+    }
+
+    /// Declares a button showing a label
+    ///
+    /// # Returns
+    /// True, if the button was clicked since it was last declared, false otherwise
+    ///
+    /// # Arguments
+    /// * `text` - the text of the button's label
+    #[track_caller]
+    pub fn button(&mut self, text: &str) -> bool {
+        let widget = self.declare(Location::caller(), "button", || {
+            let clicked = Rc::new(Cell::new(false));
+            let on_press = {
+                let clicked = clicked.clone();
+                Clicked::new(text, move |_| clicked.set(true))
+            };
+            let label = Rc::new(RefCell::new(
+                LabelWidget::default().with_text(String::from(text)),
+            ));
+            let mut button = ButtonViewWidget::default().with_on_press(Some(Box::new(on_press)));
+            button.add_as_child(Rc::downgrade(&label) as Weak<RefCell<dyn Widget>>);
+            UiWidget::Button {
+                button: Rc::new(RefCell::new(button)),
+                label,
+                text: String::from(text),
+                clicked,
+            }
+        });
+        match widget {
+            UiWidget::Button {
+                label,
+                text: last_text,
+                clicked,
+                ..
+            } => {
+                if last_text != text {
+                    label.borrow_mut().set_text(String::from(text));
+                    *last_text = String::from(text);
+                }
+                clicked.replace(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Declares a checkbox, bound to a value
+    ///
+    /// # Returns
+    /// True, if the user changed the value since the checkbox was last declared, false otherwise
+    ///
+    /// # Arguments
+    /// * `value` - the value, set to the user's choice if changed, or shown otherwise
+    #[track_caller]
+    pub fn checkbox(&mut self, value: &mut bool) -> bool {
+        let is_checked = *value;
+        let widget = self.declare(Location::caller(), "checkbox", || {
+            let changed = Rc::new(Cell::new(None));
+            let on_change = {
+                let changed = changed.clone();
+                ValueChanged::new(move |message: &ValueChanged<bool>| {
+                    changed.set(Some(message.value))
+                })
+            };
+            UiWidget::CheckBox {
+                checkbox: Rc::new(RefCell::new(
+                    CheckBoxWidget::default()
+                        .with_is_checked(is_checked)
+                        .with_on_change(Some(Box::new(on_change))),
+                )),
+                changed,
+            }
+        });
+        match widget {
+            UiWidget::CheckBox { checkbox, changed } => match changed.take() {
+                Some(is_checked) => {
+                    *value = is_checked;
+                    true
+                }
+                None => {
+                    if checkbox.borrow().get_is_checked() != *value {
+                        checkbox.borrow_mut().set_checked(*value);
+                    }
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Declares a slider, bound to a value
+    ///
+    /// # Returns
+    /// True, if the user changed the value since the slider was last declared, false otherwise
+    ///
+    /// # Arguments
+    /// * `value` - the value, set to the user's choice if changed, or shown otherwise
+    /// * `range` - the minimum and maximum values of the slider
+    #[track_caller]
+    pub fn slider(&mut self, value: &mut i32, range: (i32, i32)) -> bool {
+        let slider_value = *value;
+        let create = move || {
+            let changed = Rc::new(Cell::new(None));
+            let on_slide = {
+                let changed = changed.clone();
+                ValueChanged::new(move |message: &ValueChanged<i32>| {
+                    changed.set(Some(message.value))
+                })
+            };
+            UiWidget::Slider {
+                slider: Rc::new(RefCell::new(
+                    SliderWidget::default()
+                        .with_range(range)
+                        .with_value(slider_value)
+                        .with_on_slide(Some(Box::new(on_slide))),
+                )),
+                range,
+                changed,
+            }
+        };
+        let widget = self.declare(Location::caller(), "slider", create);
+        // The slider's positions depend on its range, so it is created again
+        if let UiWidget::Slider {
+            range: last_range, ..
+        } = widget
+        {
+            if *last_range != range {
+                *widget = create();
+            }
+        }
+        match widget {
+            UiWidget::Slider {
+                slider, changed, ..
+            } => match changed.take() {
+                Some(slider_value) => {
+                    *value = slider_value;
+                    true
+                }
+                None => {
+                    if slider.borrow().get_slider_value() != *value {
+                        slider.borrow_mut().set_value(*value);
+                    }
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Reconciles the widgets declared since the last frame against a container,
+    /// setting them as its children, in the order they were declared, and removing
+    /// the widgets no longer declared
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `container` - the widget holding the declared widgets (e.g. a list view)
+    pub fn end_frame(&mut self, container: &mut dyn Widget) {
+        let declared = mem::take(&mut self.declared);
+        self.occurrences.clear();
+        self.scopes.clear();
+
+        let keys: HashSet<&UiKey> = declared.iter().collect();
+        self.widgets.retain(|key, _| keys.contains(key));
+
+        let children: Vec<Weak<RefCell<dyn Widget>>> = declared
+            .iter()
+            .filter_map(|key| self.widgets.get(key))
+            .map(|widget| Rc::downgrade(&widget.widget()))
+            .collect();
+        // The container is only laid out again when its children changed
+        let is_changed = {
+            let current = container.get_children();
+            current.len() != children.len()
+                || current
+                    .iter()
+                    .zip(children.iter())
+                    .any(|(current, child)| !Weak::ptr_eq(current, child))
+        };
+        if is_changed {
+            *container.get_children() = children;
+            container.set_dirty(true);
+        }
+    }
+
+    /// Declares a widget at a call site, creating it if it was not declared on the
+    /// previous frames, for internal use only
+    ///
+    /// # Returns
+    /// The widget declared, to be updated
+    ///
+    /// # Arguments
+    /// * `location` - the call site declaring the widget
+    /// * `kind` - the kind of the widget, telling apart the widgets declared on the same line
+    /// * `create` - the closure creating the widget
+    fn declare<F: FnOnce() -> UiWidget>(
+        &mut self,
+        location: &'static Location<'static>,
+        kind: &str,
+        create: F,
+    ) -> &mut UiWidget {
+        let mut key = UiKey {
+            scope: MemoKey::new().with(&self.scopes.last()).with(kind).finish(),
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+            occurrence: 0,
+        };
+        let occurrence = self.occurrences.entry(key.clone()).or_insert(0);
+        key.occurrence = *occurrence;
+        *occurrence += 1;
+
+        self.declared.push(key.clone());
+        self.widgets.entry(key).or_insert_with(create)
+    }
+}
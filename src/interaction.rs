@@ -0,0 +1,209 @@
+//! Contains a state machine tracking the interaction of the pointer with a widget.
+//!
+//! Interactive widgets (e.g. buttons, checkboxes and tabs) embed an
+//! [`InteractionStateMachine`], feed it the events they receive and query its state
+//! on `recipe()`, instead of hand-coding the hovered and pressed logic.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::event::{Event, Mouse, MouseButton};
+//! use hyber::interaction::{Interaction, InteractionState, InteractionStateMachine};
+//! use hyber::util::{Rect, Vector2D};
+//!
+//! let area = Rect::new(Vector2D::new(0., 0.), Vector2D::new(100., 30.));
+//! let mut interaction = InteractionStateMachine::new();
+//!
+//! let moved = Event::Mouse(Mouse::CursorMoved { x: 10, y: 10 });
+//! assert_eq!(interaction.on_event(moved, area), Some(Interaction::Entered));
+//! assert_eq!(interaction.state(), InteractionState::Hovered);
+//!
+//! let pressed = Event::Mouse(Mouse::ButtonPressed(MouseButton::Left));
+//! assert_eq!(interaction.on_event(pressed, area), Some(Interaction::Pressed));
+//! assert_eq!(interaction.state(), InteractionState::Pressed);
+//!
+//! let released = Event::Mouse(Mouse::ButtonReleased(MouseButton::Left));
+//! assert_eq!(interaction.on_event(released, area), Some(Interaction::Clicked));
+//! ```
+
+use crate::event::{Event, Mouse, MouseButton, Window};
+use crate::util::{Rect, Vector2D};
+
+/// Visual state of an interactive widget
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InteractionState {
+    /// The pointer is away from the widget
+    #[default]
+    Idle,
+    /// The pointer is over the widget
+    Hovered,
+    /// The widget is being pressed, with the pointer over it
+    Pressed,
+    /// The widget does not react to the pointer
+    Disabled,
+}
+
+/// Transition of the interaction with a widget, caused by an event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interaction {
+    /// The pointer moved over the widget
+    Entered,
+    /// The pointer moved away from the widget
+    Exited,
+    /// The left button was pressed over the widget
+    Pressed,
+    /// The left button was released over the widget, after being pressed over it
+    Clicked,
+    /// The press was cancelled, as the left button was released away from the widget
+    /// or the window lost the focus
+    Cancelled,
+}
+
+/// State machine tracking whether the pointer hovers and presses a widget
+///
+/// A press only ends in a click if the left button is released over the widget,
+/// so a press dragged away and released outside is cancelled instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InteractionStateMachine {
+    /// The last position of the cursor, if known
+    cursor: Option<(f64, f64)>,
+    /// Whether the pointer is over the widget
+    is_inside: bool,
+    /// Whether the left button was pressed over the widget and is still held
+    is_held: bool,
+    /// Whether the widget does not react to the pointer
+    is_disabled: bool,
+}
+
+impl InteractionStateMachine {
+    /// Creates a new `InteractionStateMachine`
+    ///
+    /// # Returns
+    /// The state machine created, idle
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> InteractionStateMachine {
+        InteractionStateMachine::default()
+    }
+
+    /// Gets the visual state of the widget
+    ///
+    /// # Returns
+    /// The state to be displayed by the widget
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn state(&self) -> InteractionState {
+        if self.is_disabled {
+            InteractionState::Disabled
+        } else if self.is_held && self.is_inside {
+            InteractionState::Pressed
+        } else if self.is_inside {
+            InteractionState::Hovered
+        } else {
+            InteractionState::Idle
+        }
+    }
+
+    /// Checks whether the left button was pressed over the widget and is still held,
+    /// even if the pointer moved away meanwhile
+    ///
+    /// # Returns
+    /// True, if a press is in progress, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_held(&self) -> bool {
+        self.is_held
+    }
+
+    /// Sets whether the widget does not react to the pointer, cancelling any press in progress
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_disabled` - boolean indicating if the widget is disabled
+    pub fn set_disabled(&mut self, is_disabled: bool) {
+        self.is_disabled = is_disabled;
+        if is_disabled {
+            self.is_held = false;
+        }
+    }
+
+    /// Updates the state according to an event received by the widget
+    ///
+    /// # Returns
+    /// The transition caused by the event, if any
+    ///
+    /// # Arguments
+    /// * `event` - the event received by the widget
+    /// * `area` - the widget's area, where the pointer interacts with it
+    pub fn on_event(&mut self, event: Event, area: Rect) -> Option<Interaction> {
+        match event {
+            Event::Mouse(Mouse::CursorMoved { x, y }) => {
+                self.cursor = Some((x as f64, y as f64));
+                self.update_inside(area)
+            }
+            Event::Mouse(Mouse::CursorLeft) => {
+                self.cursor = None;
+                self.update_inside(area)
+            }
+            Event::Mouse(Mouse::ButtonPressed(MouseButton::Left))
+                if !self.is_disabled && self.contains(area) =>
+            {
+                self.is_inside = true;
+                self.is_held = true;
+                Some(Interaction::Pressed)
+            }
+            Event::Mouse(Mouse::ButtonReleased(MouseButton::Left)) if self.is_held => {
+                self.is_held = false;
+                self.is_inside = self.contains(area);
+                if self.is_inside {
+                    Some(Interaction::Clicked)
+                } else {
+                    Some(Interaction::Cancelled)
+                }
+            }
+            // The release may never arrive when the window loses the focus
+            Event::Window(Window::Focused(false)) if self.is_held => {
+                self.is_held = false;
+                Some(Interaction::Cancelled)
+            }
+            _ => None,
+        }
+    }
+
+    /// Updates whether the pointer is over the widget, for internal use only
+    ///
+    /// # Returns
+    /// The transition, if the pointer entered or exited the widget
+    ///
+    /// # Arguments
+    /// * `area` - the widget's area, where the pointer interacts with it
+    fn update_inside(&mut self, area: Rect) -> Option<Interaction> {
+        let is_inside = self.contains(area);
+        if is_inside == self.is_inside {
+            return None;
+        }
+        self.is_inside = is_inside;
+        if is_inside {
+            Some(Interaction::Entered)
+        } else {
+            Some(Interaction::Exited)
+        }
+    }
+
+    /// Checks whether the last position of the cursor is inside the widget's area, for internal use only
+    ///
+    /// # Returns
+    /// True, if the cursor is known and inside the area, false otherwise
+    ///
+    /// # Arguments
+    /// * `area` - the widget's area, where the pointer interacts with it
+    fn contains(&self, area: Rect) -> bool {
+        self.cursor
+            .is_some_and(|(x, y)| area.contains(Vector2D::new(x, y)))
+    }
+}
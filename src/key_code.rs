@@ -1,5 +1,12 @@
 //! Contains the key codes for all the keyboard events.
 //!
+//! Key codes and keyboard shortcuts can be converted to and from strings (e.g.
+//! "Ctrl+Shift+P"), so applications can load user-configurable keybindings.
+
+use crate::event::ModifiersState;
+
+use std::fmt;
+use std::str::FromStr;
 
 /// Key codification that is being handled
 ///
@@ -10,7 +17,7 @@
 /// _**Note:** Since the keys are being mapped according to the keyboard
 /// configuration, there may be mapping problems. This solution was tested
 /// with the default Portuguese keyboard settings
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum KeyCode {
     /// The number 1 key.
     Key1,
@@ -345,4 +352,306 @@ pub enum KeyCode {
     Paste,
     /// The cut shortcut key.
     Cut,
-}
\ No newline at end of file
+}
+/// Display names of every key code, used to convert key codes to and from strings
+const KEY_NAMES: [(KeyCode, &str); 163] = [
+    (KeyCode::Key1, "1"),
+    (KeyCode::Key2, "2"),
+    (KeyCode::Key3, "3"),
+    (KeyCode::Key4, "4"),
+    (KeyCode::Key5, "5"),
+    (KeyCode::Key6, "6"),
+    (KeyCode::Key7, "7"),
+    (KeyCode::Key8, "8"),
+    (KeyCode::Key9, "9"),
+    (KeyCode::Key0, "0"),
+    (KeyCode::A, "A"),
+    (KeyCode::B, "B"),
+    (KeyCode::C, "C"),
+    (KeyCode::D, "D"),
+    (KeyCode::E, "E"),
+    (KeyCode::F, "F"),
+    (KeyCode::G, "G"),
+    (KeyCode::H, "H"),
+    (KeyCode::I, "I"),
+    (KeyCode::J, "J"),
+    (KeyCode::K, "K"),
+    (KeyCode::L, "L"),
+    (KeyCode::M, "M"),
+    (KeyCode::N, "N"),
+    (KeyCode::O, "O"),
+    (KeyCode::P, "P"),
+    (KeyCode::Q, "Q"),
+    (KeyCode::R, "R"),
+    (KeyCode::S, "S"),
+    (KeyCode::T, "T"),
+    (KeyCode::U, "U"),
+    (KeyCode::V, "V"),
+    (KeyCode::W, "W"),
+    (KeyCode::X, "X"),
+    (KeyCode::Y, "Y"),
+    (KeyCode::Z, "Z"),
+    (KeyCode::Escape, "Esc"),
+    (KeyCode::F1, "F1"),
+    (KeyCode::F2, "F2"),
+    (KeyCode::F3, "F3"),
+    (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"),
+    (KeyCode::F6, "F6"),
+    (KeyCode::F7, "F7"),
+    (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"),
+    (KeyCode::F10, "F10"),
+    (KeyCode::F11, "F11"),
+    (KeyCode::F12, "F12"),
+    (KeyCode::F13, "F13"),
+    (KeyCode::F14, "F14"),
+    (KeyCode::F15, "F15"),
+    (KeyCode::F16, "F16"),
+    (KeyCode::F17, "F17"),
+    (KeyCode::F18, "F18"),
+    (KeyCode::F19, "F19"),
+    (KeyCode::F20, "F20"),
+    (KeyCode::F21, "F21"),
+    (KeyCode::F22, "F22"),
+    (KeyCode::F23, "F23"),
+    (KeyCode::F24, "F24"),
+    (KeyCode::Snapshot, "PrintScreen"),
+    (KeyCode::Scroll, "ScrollLock"),
+    (KeyCode::Pause, "Pause"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::Left, "Left"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Compose, "Compose"),
+    (KeyCode::Caret, "Caret"),
+    (KeyCode::Numlock, "Numlock"),
+    (KeyCode::Numpad0, "Num0"),
+    (KeyCode::Numpad1, "Num1"),
+    (KeyCode::Numpad2, "Num2"),
+    (KeyCode::Numpad3, "Num3"),
+    (KeyCode::Numpad4, "Num4"),
+    (KeyCode::Numpad5, "Num5"),
+    (KeyCode::Numpad6, "Num6"),
+    (KeyCode::Numpad7, "Num7"),
+    (KeyCode::Numpad8, "Num8"),
+    (KeyCode::Numpad9, "Num9"),
+    (KeyCode::NumpadAdd, "NumpadAdd"),
+    (KeyCode::NumpadDivide, "NumpadDivide"),
+    (KeyCode::NumpadDecimal, "NumpadDecimal"),
+    (KeyCode::NumpadComma, "NumpadComma"),
+    (KeyCode::NumpadEnter, "NumpadEnter"),
+    (KeyCode::NumpadEquals, "NumpadEquals"),
+    (KeyCode::NumpadMultiply, "NumpadMultiply"),
+    (KeyCode::NumpadSubtract, "NumpadSubtract"),
+    (KeyCode::AbntC1, "AbntC1"),
+    (KeyCode::AbntC2, "AbntC2"),
+    (KeyCode::Apostrophe, "Apostrophe"),
+    (KeyCode::Apps, "Apps"),
+    (KeyCode::Asterisk, "Asterisk"),
+    (KeyCode::At, "At"),
+    (KeyCode::Ax, "Ax"),
+    (KeyCode::Backslash, "Backslash"),
+    (KeyCode::Calculator, "Calculator"),
+    (KeyCode::Capital, "CapsLock"),
+    (KeyCode::Colon, "Colon"),
+    (KeyCode::Comma, "Comma"),
+    (KeyCode::Convert, "Convert"),
+    (KeyCode::Equals, "Equals"),
+    (KeyCode::Grave, "Grave"),
+    (KeyCode::Kana, "Kana"),
+    (KeyCode::Kanji, "Kanji"),
+    (KeyCode::LAlt, "LAlt"),
+    (KeyCode::LBracket, "LBracket"),
+    (KeyCode::LControl, "LCtrl"),
+    (KeyCode::LShift, "LShift"),
+    (KeyCode::LWin, "LWin"),
+    (KeyCode::Mail, "Mail"),
+    (KeyCode::MediaSelect, "MediaSelect"),
+    (KeyCode::MediaStop, "MediaStop"),
+    (KeyCode::Minus, "Minus"),
+    (KeyCode::Mute, "Mute"),
+    (KeyCode::MyComputer, "MyComputer"),
+    (KeyCode::NavigateForward, "NavigateForward"),
+    (KeyCode::NavigateBackward, "NavigateBackward"),
+    (KeyCode::NextTrack, "NextTrack"),
+    (KeyCode::NoConvert, "NoConvert"),
+    (KeyCode::OEM102, "OEM102"),
+    (KeyCode::Period, "Period"),
+    (KeyCode::PlayPause, "PlayPause"),
+    (KeyCode::Plus, "Plus"),
+    (KeyCode::Power, "Power"),
+    (KeyCode::PrevTrack, "PrevTrack"),
+    (KeyCode::RAlt, "RAlt"),
+    (KeyCode::RBracket, "RBracket"),
+    (KeyCode::RControl, "RCtrl"),
+    (KeyCode::RShift, "RShift"),
+    (KeyCode::RWin, "RWin"),
+    (KeyCode::Semicolon, "Semicolon"),
+    (KeyCode::Slash, "Slash"),
+    (KeyCode::Sleep, "Sleep"),
+    (KeyCode::Stop, "Stop"),
+    (KeyCode::Sysrq, "Sysrq"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Underline, "Underline"),
+    (KeyCode::Unlabeled, "Unlabeled"),
+    (KeyCode::VolumeDown, "VolumeDown"),
+    (KeyCode::VolumeUp, "VolumeUp"),
+    (KeyCode::Wake, "Wake"),
+    (KeyCode::WebBack, "WebBack"),
+    (KeyCode::WebFavorites, "WebFavorites"),
+    (KeyCode::WebForward, "WebForward"),
+    (KeyCode::WebHome, "WebHome"),
+    (KeyCode::WebRefresh, "WebRefresh"),
+    (KeyCode::WebSearch, "WebSearch"),
+    (KeyCode::WebStop, "WebStop"),
+    (KeyCode::Yen, "Yen"),
+    (KeyCode::Copy, "Copy"),
+    (KeyCode::Paste, "Paste"),
+    (KeyCode::Cut, "Cut"),
+];
+
+/// Error returned when a string does not describe a key code or a keyboard shortcut
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyError {
+    /// The part of the string that could not be parsed
+    pub input: String,
+}
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown key or modifier: \"{}\"", self.input)
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl KeyCode {
+    /// Gets the name of the key code to be displayed to the user (e.g. on a settings UI)
+    ///
+    /// # Returns
+    /// The display name of the key code (e.g. "1" for `Key1`, "Esc" for `Escape`)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn to_display_string(&self) -> String {
+        KEY_NAMES
+            .iter()
+            .find(|(key_code, _)| key_code == self)
+            .map(|(_, name)| String::from(*name))
+            .unwrap_or_else(|| format!("{:?}", self))
+    }
+}
+
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_display_string())
+    }
+}
+
+impl FromStr for KeyCode {
+    type Err = ParseKeyError;
+
+    /// Parses a key code from its display name or its variant name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        KEY_NAMES
+            .iter()
+            .find(|(key_code, display_name)| {
+                display_name.eq_ignore_ascii_case(name)
+                    || format!("{:?}", key_code).eq_ignore_ascii_case(name)
+            })
+            .map(|(key_code, _)| *key_code)
+            .ok_or_else(|| ParseKeyError {
+                input: String::from(name),
+            })
+    }
+}
+
+/// Parses a keyboard shortcut from a string like "Ctrl+Shift+P"
+///
+/// Modifiers and key are separated by `+` and case is ignored. The accepted
+/// modifiers are `Ctrl` (or `Control`), `Alt` (or `Option`), `Shift` and
+/// `Super` (or `Logo`, `Win`, `Cmd`, `Meta`). The last part is the key code.
+///
+/// # Returns
+/// The modifiers and the key code of the shortcut, or an error with the part
+/// of the string that could not be parsed
+///
+/// # Arguments
+/// * `shortcut` - the string describing the shortcut
+///
+/// # Examples
+///
+/// ```
+/// use hyber::key_code::{parse_shortcut, shortcut_to_string, KeyCode};
+///
+/// let (modifiers, key_code) = parse_shortcut("Ctrl+Shift+P").unwrap();
+/// assert!(modifiers.control && modifiers.shift && !modifiers.alt);
+/// assert_eq!(key_code, KeyCode::P);
+/// assert_eq!(shortcut_to_string(modifiers, key_code), "Ctrl+Shift+P");
+/// ```
+pub fn parse_shortcut(shortcut: &str) -> Result<(ModifiersState, KeyCode), ParseKeyError> {
+    let mut modifiers = ModifiersState {
+        shift: false,
+        control: false,
+        alt: false,
+        logo: false,
+    };
+    let mut parts: Vec<&str> = shortcut.split('+').map(|part| part.trim()).collect();
+    let key = parts.pop().unwrap_or("");
+
+    for part in parts {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers.control = true,
+            "alt" | "option" => modifiers.alt = true,
+            "shift" => modifiers.shift = true,
+            "super" | "logo" | "win" | "cmd" | "meta" => modifiers.logo = true,
+            _ => {
+                return Err(ParseKeyError {
+                    input: String::from(part),
+                })
+            }
+        }
+    }
+
+    Ok((modifiers, key.parse()?))
+}
+
+/// Converts a keyboard shortcut into a string like "Ctrl+Shift+P"
+///
+/// The modifiers are written in the order `Ctrl`, `Alt`, `Shift` and `Super`, such
+/// that the string can be parsed back with [`parse_shortcut`].
+///
+/// # Returns
+/// The string describing the shortcut
+///
+/// # Arguments
+/// * `modifiers` - the modifiers of the shortcut
+/// * `key_code` - the key code of the shortcut
+pub fn shortcut_to_string(modifiers: ModifiersState, key_code: KeyCode) -> String {
+    let mut parts = Vec::new();
+    if modifiers.control {
+        parts.push(String::from("Ctrl"));
+    }
+    if modifiers.alt {
+        parts.push(String::from("Alt"));
+    }
+    if modifiers.shift {
+        parts.push(String::from("Shift"));
+    }
+    if modifiers.logo {
+        parts.push(String::from("Super"));
+    }
+    parts.push(key_code.to_display_string());
+    parts.join("+")
+}
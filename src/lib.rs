@@ -9,21 +9,85 @@
 //! Afterwards, programmers that wish to use that specific crate should implement the following trait:
 //! - [`renderer::Message`]
 //!
+//! For the most common interactions, the reusable messages of module [`message`] may be used instead.
+//!
 //! # Widgets
 //! Hyber has a number of basic widgets implemented, which can be found in module [`widget`].
 //!
 //! Programmers may implement their own custom widget by creating a struct that implements the [`widget::Widget`] trait.
 //!
+//! A widget consumes an event by returning [`widget::EventStatus::Captured`] from [`widget::Widget::on_event`], so that the widgets stacked under it do not handle it (e.g. a click on overlapping buttons), while its children are reached with [`widget::propagate_event`].
+//!
+//! The colors, font sizes, border thicknesses and paddings of the widgets, as well as the styling of the disabled ones and the font fallback chain, are read from the [`style::Theme`] set on the [`widget::root::RootWidget`], which cascades it to the whole tree (e.g. to switch between light and dark modes), while each widget may override some of them with a [`style::Style`], as do the colors and font sizes given explicitly to its constructor and setters.
+//!
+//! Popups (e.g. a [`widget::dialog::DialogWidget`]) are opened with [`context::Context::open_popup`], which draws them above the widget tree and makes them grab every event while open.
+//!
+//! Areas are filled with a flat color or a linear or radial gradient described by a [`renderer::Fill`] (e.g. on a [`widget::progress_bar::ProgressBarWidget`] or a [`widget::panel::PanelWidget`]), drawn with a [`renderer::RenderInstruction::DrawGradientRect`].
+//!
+//! Colors are picked from a grid of predefined swatches with a [`widget::palette::PaletteWidget`], which optionally keeps a row of the colors picked most recently.
+//!
+//! Context menus are registered on any widget with [`widget::WidgetRef::set_context_menu`], which opens a [`widget::context_menu::ContextMenuWidget`] at the cursor when the widget is right-clicked.
+//!
+//! The [`busy`] module blocks the application with an overlay while a task is running, on which tasks running on other threads report their progress.
+//!
+//! Containers can be populated from a list of items with the [`template`] module, which binds each item's data to a widget created (or recycled) by a template. The rows of a table model (e.g. records read from CSV) are bound the same way by the [`table_model`] module, which repopulates the container whenever the model changes.
+//!
+//! Many small pictures (e.g. icons) can be packed into a [`renderer::ImageAtlas`] registered with [`context::Context::register_atlas`], whose named regions are drawn by [`widget::icon::IconWidget`]s from the image loaded once by the renderer.
+//!
+//! Texts are wrapped and aligned on multiple lines by the [`text`] module, emitting one draw instruction per line.
+//!
+//! Long texts show only their first lines with a [`widget::expandable_text::ExpandableTextWidget`], whose "show more" toggle expands them and lays out the widgets after them again.
+//!
+//! Composite widgets keep the widgets composing them private with [`widget::Widget::internal_children`], which are built, themed and drawn along with the widget while being hidden from [`widget::Widget::get_children`] and [`widget::hit_test`].
+//!
+//! Containers rotate or scale their children by returning a [`util::Transform`] from [`widget::Widget::transform`], drawn with [`renderer::RenderInstruction::PushTransform`], and map the cursor back to their children's coordinates with [`util::Transform::inverse_apply`].
+//!
+//! Any widget is made translucent, along with its children, with [`widget::WidgetRef::set_opacity`] (e.g. to fade it in or out), drawn with [`renderer::RenderInstruction::PushOpacity`] by the renderers supporting it.
+//!
+//! The window is painted behind the widgets with the [`widget::root::BackgroundLayer`]s of the [`widget::root::RootWidget`] (e.g. a gradient or a stretched or tiled wallpaper image), switched at runtime with [`widget::root::RootWidget::set_background_layers`].
+//!
+//! Content larger than its container is scrolled along both axes by a [`widget::scroll_view::ScrollViewWidget`], with independent offsets and a scroll bar per axis, the mouse wheel scrolling horizontally while Shift is held (see [`event::modifiers`]).
+//!
+//! Pictures are displayed by a [`widget::image::ImageWidget`], read either from a file or from RGBA pixels held in memory (see [`util::ImageData`]), the latter drawn with [`renderer::RenderInstruction::DrawImageData`].
+//!
+//! Several independent roots are embedded into sub-rectangles of one display (e.g. hyber panels inside an application not built with hyber) by an [`embedding::Embedding`], which routes the display's events to the root under the cursor and composes their render instructions.
+//!
+//! Images and fonts are registered once on the [`context::Context`]'s [`resources::ResourceRegistry`], loaded once by the renderer and drawn through cheap handles (see [`renderer::RenderInstruction::DrawImageHandle`]) instead of paths copied on every rebuild.
+//!
+//! Texts are drawn with a [`renderer::FontDescriptor`], picking a font family other than the renderer's default one and its weight and style (e.g. bold or italic), set on labels, textboxes and progress bars (whose text is their percentage) with `set_font`.
+//!
+//! Tooltips are shown once the cursor rests over their widget for a show delay, placed above, below, left or right of it and flipped or clamped to stay within the display (see [`widget::tooltip_view::TooltipPlacement`]).
+//!
+//! Two presses of a mouse button in a row, within the [`context::Context`]'s double-click interval and near each other, are detected as a [`event::Mouse::ButtonDoubleClicked`] event, handled by buttons with `with_on_double_press`.
+//!
+//! Tabbed interfaces are built with the [`widget::tab_bar::TabBarWidget`], which places the tabs' headers along an axis and displays the content of the selected tab, optionally closing tabs from their headers.
+//!
+//! Grids size their rows and columns with a [`widget::GridTemplate`] (e.g. fixed, percentage or automatic lengths) and place their children on explicit cells spanning several rows and columns (see [`widget::grid_view::GridViewWidget::add_child_at`]), as needed by form-like layouts.
+//!
+//! Widgets are overlaid (e.g. a badge over an icon) with the [`widget::stack::StackWidget`], which places its children on top of each other, each one aligned within the stack and moved by an offset.
+//!
+//! Widgets' properties (e.g. position, size, color or opacity) can be animated with the [`animation`] module, whose tweens are ticked on every frame by the [`context::Context`]'s animation controller.
+//!
+//! With the `immediate` feature enabled, module `immediate` lets applications declare their widgets on every frame (e.g. `ui.button("OK")` returning whether it was clicked), reconciled by call site against the retained widget tree.
+//!
+//! With the `gallery` feature enabled, module `gallery` builds a widget tree with every built-in widget, which authors of a [`renderer::Renderer`] can mount to validate their implementation.
+//!
+//! # Testing
+//! Widgets read the time from the [`clock`] module instead of the system, so their time-based behavior can be tested deterministically with the [`harness::Harness`], which runs the frames of a widget tree without a display and with a virtual clock.
+//!
 //! # Basic example
 //!
 //! Here follows a simple example of a program that has a [`widget::button_view::ButtonViewWidget`] with a [`widget::label::LabelWidget`] inside it that counts the number of clicks, acting as a counter. In this example, the aforementioned crate using a renderer to implement [`hyber`](`self`) is called `hyber_renderer`.
 //!
 //! ```
 //! // Import needed widgets and structs from [`hyber`]
+//! use hyber::context::Context;
 //! use hyber::display::Display;
 //! use hyber::event::Event;
 //! use hyber::event::Mouse::CursorMoved;
-//! use hyber::renderer::{AbsoluteWidgetCollection, Message, RenderInstructionCollection, Renderer};
+//! use hyber::renderer::{
+//!     AbsoluteWidgetCollection, EventLoopState, Message, RenderInstructionCollection, Renderer,
+//! };
 //! use hyber::util::{Color, IDMachine, Vector2D};
 //! use hyber::widget::button_view::ButtonViewWidget;
 //! use hyber::widget::grid_view::GridViewWidget;
@@ -127,12 +191,14 @@
 //!         },
 //!     );
 //! 
-//!     let mut id_machine = IDMachine::new();
+//!     let id_machine = IDMachine::new();
 //!
 //!     let collection = Rc::new(RefCell::new(RenderInstructionCollection::new()));
 //!
 //!     let absolute_collection = Rc::new(RefCell::new(AbsoluteWidgetCollection::new()));
 //!
+//!     let context = Rc::new(RefCell::new(Context::new()));
+//!
 //!     let mut renderer = hyber_renderer::RendererXPTO::new(WIDTH as i32, HEIGHT as i32);
 //!
 //!     let events = renderer.create_events_queue();
@@ -203,9 +269,12 @@
 //!         Rc::downgrade(&root) as Weak<RefCell<dyn Widget>>,
 //!         &mut display,
 //!         Vector2D::new(WIDTH, HEIGHT),
-//!         &mut id_machine,
-//!         Rc::downgrade(&collection),
-//!         Rc::downgrade(&absolute_collection),
+//!         EventLoopState::new(
+//!             id_machine,
+//!             Rc::downgrade(&collection),
+//!             Rc::downgrade(&absolute_collection),
+//!             Rc::downgrade(&context),
+//!         ),
 //!     );
 //! }
 //! ```
@@ -214,13 +283,34 @@
 //! 
 //! Known issues are marked as `TODO` in the code. Here follows a list of planned features that are not implemented yet:
 //! 
-//! - `SliverLayout` for scrollables and infinite scrolling
-//! - Animation system
-//! - Flex system
+//! - Disabled state on the built-in widgets (any widget reporting [`widget::Widget::is_disabled`] is already styled as disabled)
+//! - Table widget, whose columns should be virtualized horizontally for very wide tables, with leading columns frozen so they stay visible while scrolling horizontally
+//! - Hot reload of declarative UI descriptions, diffing a new description against the live widget tree and applying the minimal changes, which requires a declarative UI loader first
 
+pub mod animation;
+pub mod busy;
+pub mod clock;
+pub mod context;
 pub mod display;
+pub mod drag;
+pub mod embedding;
 pub mod event;
+#[cfg(feature = "gallery")]
+pub mod gallery;
+pub mod harness;
+#[cfg(feature = "immediate")]
+pub mod immediate;
+pub mod interaction;
 pub mod key_code;
+pub mod message;
 pub mod renderer;
+pub mod resources;
+pub mod selection;
+pub mod style;
+pub mod table_model;
+pub mod template;
+pub mod text;
 pub mod util;
+pub mod value_editor;
+pub mod watchdog;
 pub mod widget;
@@ -0,0 +1,225 @@
+//! Contains reusable messages for the most common interactions.
+//!
+//! Simple applications may use these messages instead of implementing their own
+//! [`Message`] for every interaction. Each message holds a handler that is called
+//! with the message itself when the message is updated, so the application can
+//! read the data filled in by the widget that triggered it (e.g. the new value of
+//! a slider).
+//!
+//! Widgets fill in the data of these messages by downcasting the messages they
+//! enqueue, through [`downcast_mut`](`Message::downcast_mut`).
+
+use crate::drag::DragPayload;
+use crate::event::Event;
+use crate::renderer::Message;
+use crate::util::Vector2D;
+
+use std::rc::Rc;
+
+/// Handler called with a message when the message is updated
+pub type Handler<M> = Rc<dyn Fn(&M)>;
+
+/// Message handled when the value of a widget changes (e.g. a slider or a checkbox)
+#[derive(Clone)]
+pub struct ValueChanged<T: Clone + 'static> {
+    /// The widget's new value
+    pub value: T,
+
+    /// The event that triggered the message
+    pub event: Option<Event>,
+
+    /// The handler called when the message is updated
+    handler: Handler<ValueChanged<T>>,
+}
+
+impl<T: Clone + Default + 'static> ValueChanged<T> {
+    /// Creates a new `ValueChanged`
+    ///
+    /// # Returns
+    /// The message created, holding the default value until filled in by a widget
+    ///
+    /// # Arguments
+    /// * `handler` - the handler to be called with the message when it is updated
+    pub fn new<F: Fn(&ValueChanged<T>) + 'static>(handler: F) -> ValueChanged<T> {
+        ValueChanged {
+            value: T::default(),
+            event: None,
+            handler: Rc::new(handler),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Message for ValueChanged<T> {
+    fn update(&self) {
+        (self.handler)(self);
+    }
+
+    fn set_event(&mut self, event: Event) {
+        self.event = Some(event);
+    }
+}
+
+/// Message handled when a widget is clicked (e.g. a button or a panel)
+#[derive(Clone)]
+pub struct Clicked {
+    /// The key identifying the clicked widget, chosen by the application
+    pub widget_key: String,
+
+    /// The event that triggered the message
+    pub event: Option<Event>,
+
+    /// The handler called when the message is updated
+    handler: Handler<Clicked>,
+}
+
+impl Clicked {
+    /// Creates a new `Clicked`
+    ///
+    /// # Returns
+    /// The message created
+    ///
+    /// # Arguments
+    /// * `widget_key` - the key identifying the widget, used to tell widgets sharing a handler apart
+    /// * `handler` - the handler to be called with the message when it is updated
+    pub fn new<F: Fn(&Clicked) + 'static>(widget_key: &str, handler: F) -> Clicked {
+        Clicked {
+            widget_key: String::from(widget_key),
+            event: None,
+            handler: Rc::new(handler),
+        }
+    }
+}
+
+impl Message for Clicked {
+    fn update(&self) {
+        (self.handler)(self);
+    }
+
+    fn set_event(&mut self, event: Event) {
+        self.event = Some(event);
+    }
+}
+
+/// Message handled when the text of a widget changes (e.g. a textbox)
+#[derive(Clone)]
+pub struct TextChanged {
+    /// The widget's new text
+    pub text: String,
+
+    /// The event that triggered the message
+    pub event: Option<Event>,
+
+    /// The handler called when the message is updated
+    handler: Handler<TextChanged>,
+}
+
+impl TextChanged {
+    /// Creates a new `TextChanged`
+    ///
+    /// # Returns
+    /// The message created, holding an empty text until filled in by a widget
+    ///
+    /// # Arguments
+    /// * `handler` - the handler to be called with the message when it is updated
+    pub fn new<F: Fn(&TextChanged) + 'static>(handler: F) -> TextChanged {
+        TextChanged {
+            text: String::new(),
+            event: None,
+            handler: Rc::new(handler),
+        }
+    }
+}
+
+impl Message for TextChanged {
+    fn update(&self) {
+        (self.handler)(self);
+    }
+
+    fn set_event(&mut self, event: Event) {
+        self.event = Some(event);
+    }
+}
+
+/// Message handled when a context menu is requested on a widget (e.g. right click on a panel)
+#[derive(Clone)]
+pub struct ContextMenuRequested {
+    /// The cursor's position when the menu was requested, where the menu should be displayed
+    pub position: Vector2D,
+
+    /// The event that triggered the message
+    pub event: Option<Event>,
+
+    /// The handler called when the message is updated
+    handler: Handler<ContextMenuRequested>,
+}
+
+impl ContextMenuRequested {
+    /// Creates a new `ContextMenuRequested`
+    ///
+    /// # Returns
+    /// The message created, positioned at the origin until filled in by a widget
+    ///
+    /// # Arguments
+    /// * `handler` - the handler to be called with the message when it is updated
+    pub fn new<F: Fn(&ContextMenuRequested) + 'static>(handler: F) -> ContextMenuRequested {
+        ContextMenuRequested {
+            position: Vector2D::new(0., 0.),
+            event: None,
+            handler: Rc::new(handler),
+        }
+    }
+}
+
+impl Message for ContextMenuRequested {
+    fn update(&self) {
+        (self.handler)(self);
+    }
+
+    fn set_event(&mut self, event: Event) {
+        self.event = Some(event);
+    }
+}
+
+/// Message handled when a payload is dropped on a widget (e.g. files dropped on a path input)
+#[derive(Clone)]
+pub struct Dropped {
+    /// The payload dropped, filled in by the widget
+    pub payload: Option<DragPayload>,
+
+    /// The cursor's position when the payload was dropped
+    pub position: Vector2D,
+
+    /// The event that triggered the message
+    pub event: Option<Event>,
+
+    /// The handler called when the message is updated
+    handler: Handler<Dropped>,
+}
+
+impl Dropped {
+    /// Creates a new `Dropped`
+    ///
+    /// # Returns
+    /// The message created, holding no payload until filled in by a widget
+    ///
+    /// # Arguments
+    /// * `handler` - the handler to be called with the message when it is updated
+    pub fn new<F: Fn(&Dropped) + 'static>(handler: F) -> Dropped {
+        Dropped {
+            payload: None,
+            position: Vector2D::new(0., 0.),
+            event: None,
+            handler: Rc::new(handler),
+        }
+    }
+}
+
+impl Message for Dropped {
+    fn update(&self) {
+        (self.handler)(self);
+    }
+
+    fn set_event(&mut self, event: Event) {
+        self.event = Some(event);
+    }
+}
@@ -1,15 +1,28 @@
 //! Contains the foundational elements for a renderer. Programmers will need to implement the trait [`Renderer`] into their renderer of choice.
 
+use crate::clock;
+use crate::context::{Context, ControlFlow, FrameInfo, WindowRequest};
+use crate::display::Display;
+use crate::drag;
+use crate::event;
 use crate::event::Event;
+use crate::resources::{FontHandle, FontResource, ImageHandle, ImageResource};
+use crate::text::{self, TextMetrics};
 use crate::util::Color;
+use crate::util::ImageData;
 use crate::util::IDMachine;
 use crate::util::Queue;
+use crate::util::Rect;
+use crate::util::Transform;
 use crate::util::Vector2D;
-use crate::widget::Widget;
+use crate::watchdog::FramePhase;
+use crate::widget::{self, all_children, Widget, WidgetRef};
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
-use std::rc::Weak;
+use std::rc::{Rc, Weak};
+use std::time::Instant;
 
 /// Images resize configuration type
 /// 
@@ -33,6 +46,657 @@ pub enum DrawImageOptions {
     },
 }
 
+/// Set of resolutions of the same image (e.g. 1x, 2x and 3x), so that images stay
+/// sharp on high density displays without wasting memory on the others
+///
+/// Each resolution is given by its density, the number of image pixels per logical
+/// unit. When widgets are built, the resolution best matching the display's scale
+/// factor is picked for their [`DrawImage`](`RenderInstruction::DrawImage`) instructions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageSource {
+    /// The image's resolutions, as pairs of density and path, sorted by density
+    variants: Vec<(f64, String)>,
+}
+
+impl ImageSource {
+    /// Creates a new `ImageSource`
+    ///
+    /// # Returns
+    /// The image source created, with a single resolution of density 1
+    ///
+    /// # Arguments
+    /// * `path` - the path of the image at its original resolution (i.e., 1x)
+    pub fn new(path: String) -> ImageSource {
+        ImageSource {
+            variants: vec![(1., path)],
+        }
+    }
+
+    /// Adds a resolution to the image source, replacing the one with the same density
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `density` - the number of image pixels per logical unit (e.g. 2 for a 2x image)
+    /// * `path` - the path of the image at this resolution
+    pub fn add_variant(&mut self, density: f64, path: String) {
+        self.variants.retain(|(variant_density, _)| *variant_density != density);
+        let index = self
+            .variants
+            .iter()
+            .position(|(variant_density, _)| *variant_density > density)
+            .unwrap_or(self.variants.len());
+        self.variants.insert(index, (density, path));
+    }
+
+    /// Adds a resolution to the image source, returning the image source (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The image source with the resolution added
+    ///
+    /// # Arguments
+    /// * `density` - the number of image pixels per logical unit (e.g. 2 for a 2x image)
+    /// * `path` - the path of the image at this resolution
+    pub fn with_variant(mut self, density: f64, path: String) -> Self {
+        self.add_variant(density, path);
+        self
+    }
+
+    /// Gets the image's resolutions
+    ///
+    /// # Returns
+    /// The pairs of density and path of the image, sorted by density
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn variants(&self) -> &[(f64, String)] {
+        &self.variants
+    }
+
+    /// Picks the resolution best matching a density: the smallest one which is not
+    /// upscaled, or the largest one if every resolution would be upscaled
+    ///
+    /// # Returns
+    /// The density and path of the resolution picked
+    ///
+    /// # Arguments
+    /// * `density` - the number of device pixels per logical unit the image is drawn with
+    pub fn best_match(&self, density: f64) -> (f64, &str) {
+        let (variant_density, path) = self
+            .variants
+            .iter()
+            .find(|(variant_density, _)| *variant_density >= density)
+            .or_else(|| self.variants.last())
+            .expect("an image source has at least one resolution");
+        (*variant_density, path)
+    }
+}
+
+/// Image packing many small pictures (e.g. icons), each one a named region of the image
+///
+/// Atlases are registered on the [`Context`] with
+/// [`register_atlas`](`Context::register_atlas`), such that the renderer loads the
+/// image once (see [`load_atlas`](`Renderer::load_atlas`)) and draws each picture from
+/// it with a [`DrawImageRegion`](`RenderInstruction::DrawImageRegion`) instruction.
+#[derive(Clone)]
+pub struct ImageAtlas {
+    /// The atlas' image path
+    path: String,
+    /// The atlas' regions, having their name as key and their area on the image,
+    /// in image pixels, as value
+    regions: HashMap<String, Rect>,
+}
+
+impl ImageAtlas {
+    /// Creates a new `ImageAtlas`
+    ///
+    /// # Returns
+    /// The atlas created, with no regions
+    ///
+    /// # Arguments
+    /// * `path` - the path of the atlas' image
+    pub fn new(path: String) -> ImageAtlas {
+        ImageAtlas {
+            path,
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Adds a named region to the atlas, replacing the one with the same name
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `name` - the name of the region (e.g. the name of the icon)
+    /// * `region` - the area of the region on the atlas' image, in image pixels
+    pub fn add_region(&mut self, name: &str, region: Rect) {
+        self.regions.insert(String::from(name), region);
+    }
+
+    /// Adds a named region to the atlas, returning the atlas (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The atlas with the region added
+    ///
+    /// # Arguments
+    /// * `name` - the name of the region (e.g. the name of the icon)
+    /// * `region` - the area of the region on the atlas' image, in image pixels
+    pub fn with_region(mut self, name: &str, region: Rect) -> Self {
+        self.add_region(name, region);
+        self
+    }
+
+    /// Gets the path of the atlas' image
+    ///
+    /// # Returns
+    /// The path of the atlas' image
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Gets a named region of the atlas
+    ///
+    /// # Returns
+    /// The area of the region on the atlas' image, or `None` if there is no region
+    /// with the name
+    ///
+    /// # Arguments
+    /// * `name` - the name of the region
+    pub fn region(&self, name: &str) -> Option<Rect> {
+        self.regions.get(name).copied()
+    }
+}
+
+/// Handle of an [`ImageAtlas`] registered on the [`Context`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasHandle(pub(crate) usize);
+
+/// Named region of an [`ImageAtlas`], drawn in place of a standalone image (e.g. by an
+/// [`IconWidget`](`crate::widget::icon::IconWidget`))
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtlasRegion {
+    /// The handle of the atlas
+    pub atlas: AtlasHandle,
+    /// The name of the region on the atlas
+    pub name: String,
+}
+
+impl AtlasRegion {
+    /// Creates a new `AtlasRegion`
+    ///
+    /// # Returns
+    /// The atlas region created
+    ///
+    /// # Arguments
+    /// * `atlas` - the handle of the atlas
+    /// * `name` - the name of the region on the atlas
+    pub fn new(atlas: AtlasHandle, name: &str) -> AtlasRegion {
+        AtlasRegion {
+            atlas,
+            name: String::from(name),
+        }
+    }
+}
+
+/// Thickness of the strokes of a font's glyphs, from 100 (thin) to 900 (black), as in CSS
+///
+/// Renderers without a font of the exact weight draw the closest one available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// The thinnest weight (100)
+    pub const THIN: FontWeight = FontWeight(100);
+    /// The light weight (300)
+    pub const LIGHT: FontWeight = FontWeight(300);
+    /// The regular weight (400)
+    pub const NORMAL: FontWeight = FontWeight(400);
+    /// The medium weight (500)
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    /// The semi-bold weight (600)
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    /// The bold weight (700)
+    pub const BOLD: FontWeight = FontWeight(700);
+    /// The thickest weight (900)
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+/// Slant of a font's glyphs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    /// Upright glyphs
+    #[default]
+    Normal,
+    /// Glyphs of the font's italic variant
+    Italic,
+    /// Upright glyphs slanted by the renderer, for fonts without an italic variant
+    Oblique,
+}
+
+/// Font used to draw a [`DrawText`](`RenderInstruction::DrawText`) instruction
+///
+/// # Examples
+///
+/// ```
+/// use hyber::renderer::{FontDescriptor, FontStyle, FontWeight};
+///
+/// let heading = FontDescriptor::new(String::from("Inter"))
+///     .with_weight(FontWeight::BOLD)
+///     .with_style(FontStyle::Italic);
+///
+/// assert_eq!(heading.weight, FontWeight(700));
+/// assert_eq!(FontDescriptor::default().weight, FontWeight::NORMAL);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FontDescriptor {
+    /// The font family, or an empty string for the renderer's default font
+    pub family: String,
+    /// The font families used, in order, for the glyphs missing from the family,
+    /// resolved from the fallback chain of the [`Theme`](`crate::style::Theme`) when
+    /// widgets are built
+    pub fallbacks: Vec<String>,
+    /// The thickness of the font's glyphs (e.g. bold)
+    pub weight: FontWeight,
+    /// The slant of the font's glyphs (e.g. italic)
+    pub style: FontStyle,
+}
+
+impl FontDescriptor {
+    /// Creates a new `FontDescriptor`
+    ///
+    /// # Returns
+    /// The font descriptor created, with no fallbacks
+    ///
+    /// # Arguments
+    /// * `family` - the font family, or an empty string for the renderer's default font
+    pub fn new(family: String) -> FontDescriptor {
+        FontDescriptor {
+            family,
+            fallbacks: Vec::new(),
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+        }
+    }
+
+    /// Sets the font's weight, returning the font (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The font with the weight assigned
+    ///
+    /// # Arguments
+    /// * `weight` - the thickness of the font's glyphs
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the font's style, returning the font (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The font with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the slant of the font's glyphs
+    pub fn with_style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Gets the font families to try, in order, for each glyph (i.e., the family
+    /// followed by its fallbacks)
+    ///
+    /// # Returns
+    /// An iterator over the font families
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.family.as_str()).chain(self.fallbacks.iter().map(String::as_str))
+    }
+}
+
+/// Shape clipping the render instructions between a [`PushClip`](`RenderInstruction::PushClip`)
+/// and its matching [`PopClip`](`RenderInstruction::PopClip`)
+#[derive(Clone)]
+pub enum ClipShape {
+    /// Rectangle based on its upper left corner and its size
+    Rect {
+        /// The rectangle's upper left corner, on a two-dimensional space
+        point: Vector2D,
+        /// The rectangle's size, width and height
+        size: Vector2D,
+    },
+    /// Rectangle with rounded corners, based on its upper left corner and its size
+    RoundedRect {
+        /// The rectangle's upper left corner, on a two-dimensional space
+        point: Vector2D,
+        /// The rectangle's size, width and height
+        size: Vector2D,
+        /// The radius of the corners
+        radius: f64,
+    },
+    /// Circle centered on a specific point
+    Circle {
+        /// The circle center point, on a two-dimensional space
+        point: Vector2D,
+        /// The circle radius
+        r: f64,
+    },
+    /// Closed polygon through its vertices, filled according to the even-odd rule
+    Path {
+        /// The polygon's vertices, on a two-dimensional space
+        points: Vec<Vector2D>,
+    },
+}
+
+impl ClipShape {
+    /// Gets the bounding rectangle of the shape, which backends that do not support
+    /// the shape may clip to instead
+    ///
+    /// # Returns
+    /// The smallest rectangle containing the shape
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn bounds(&self) -> Rect {
+        match self {
+            ClipShape::Rect { point, size } | ClipShape::RoundedRect { point, size, .. } => {
+                Rect::new(*point, *size)
+            }
+            ClipShape::Circle { point, r } => {
+                Rect::new(*point - *r, Vector2D::new(*r * 2., *r * 2.))
+            }
+            ClipShape::Path { points } => {
+                let start = points.iter().fold(
+                    Vector2D::new(f64::INFINITY, f64::INFINITY),
+                    |start, point| Vector2D::new(start.x.min(point.x), start.y.min(point.y)),
+                );
+                let end = points.iter().fold(
+                    Vector2D::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+                    |end, point| Vector2D::new(end.x.max(point.x), end.y.max(point.y)),
+                );
+                if points.is_empty() {
+                    Rect::new(Vector2D::new(0., 0.), Vector2D::new(0., 0.))
+                } else {
+                    Rect::new(start, end - start)
+                }
+            }
+        }
+    }
+
+    /// Checks if a point is inside the shape, which software backends may use to
+    /// clip pixel by pixel
+    ///
+    /// # Returns
+    /// True, if the point is inside the shape, false otherwise
+    ///
+    /// # Arguments
+    /// * `position` - the point to be checked
+    pub fn contains(&self, position: Vector2D) -> bool {
+        match self {
+            ClipShape::Rect { point, size } => Rect::new(*point, *size).contains(position),
+            ClipShape::RoundedRect {
+                point,
+                size,
+                radius,
+            } => {
+                if !Rect::new(*point, *size).contains(position) {
+                    return false;
+                }
+                // Outside the corners, the point must be within the radius of the
+                // corner's center
+                let radius = radius.min(size.x * 0.5).min(size.y * 0.5).max(0.);
+                let x = position.x.clamp(point.x + radius, point.x + size.x - radius);
+                let y = position.y.clamp(point.y + radius, point.y + size.y - radius);
+                let distance = position - Vector2D::new(x, y);
+                distance.x * distance.x + distance.y * distance.y <= radius * radius
+            }
+            ClipShape::Circle { point, r } => {
+                let distance = position - *point;
+                distance.x * distance.x + distance.y * distance.y <= r * r
+            }
+            ClipShape::Path { points } => {
+                // Even-odd rule: count the edges crossed by a ray to the right of the point
+                let mut is_inside = false;
+                for (index, point_a) in points.iter().enumerate() {
+                    let point_b = points[(index + 1) % points.len()];
+                    if (point_a.y > position.y) != (point_b.y > position.y) {
+                        let x = point_a.x
+                            + (position.y - point_a.y) * (point_b.x - point_a.x)
+                                / (point_b.y - point_a.y);
+                        if position.x < x {
+                            is_inside = !is_inside;
+                        }
+                    }
+                }
+                is_inside
+            }
+        }
+    }
+}
+
+/// How the color of a fill instruction is composited with the colors already drawn
+/// beneath it
+///
+/// Backends composite the modes they support, as reported by
+/// [`supports_blend_mode`](`Renderer::supports_blend_mode`); the instructions using any
+/// other mode fall back to [`Normal`](`BlendMode::Normal`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// The color is drawn over the destination, according to its transparency
+    #[default]
+    Normal,
+    /// The color is multiplied by the destination, darkening it (e.g. shadows)
+    Multiply,
+    /// The inverse of the color is multiplied by the inverse of the destination,
+    /// lightening it (e.g. highlights)
+    Screen,
+    /// The color is added to the destination, saturating at white (e.g. glows)
+    Additive,
+}
+
+impl BlendMode {
+    /// Every blend mode, starting with the one supported by every backend
+    pub const ALL: [BlendMode; 4] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Additive,
+    ];
+
+    /// Composites a color over another, as expected from a backend supporting
+    /// the blend mode (e.g. a software renderer blending its buffer's pixels)
+    ///
+    /// # Returns
+    /// The color resulting from blending the source color over the destination one
+    ///
+    /// # Arguments
+    /// * `source` - the color being drawn
+    /// * `destination` - the color already drawn beneath it
+    pub fn blend(self, source: Color, destination: Color) -> Color {
+        let blend_channel = |source: u8, destination: u8| -> f64 {
+            let (source, destination) = (source as f64 / 255., destination as f64 / 255.);
+            match self {
+                BlendMode::Normal => source,
+                BlendMode::Multiply => source * destination,
+                BlendMode::Screen => 1. - (1. - source) * (1. - destination),
+                BlendMode::Additive => (source + destination).min(1.),
+            }
+        };
+        // The blended color is then drawn over the destination according to the
+        // source's transparency
+        let alpha = source.a as f64 / 255.;
+        let composite = |source: u8, destination: u8| -> u8 {
+            let blended = blend_channel(source, destination);
+            ((blended * alpha + destination as f64 / 255. * (1. - alpha)) * 255.).round() as u8
+        };
+        Color::new(
+            (source.a as f64 + destination.a as f64 * (1. - alpha))
+                .round()
+                .min(255.) as u8,
+            composite(source.r, destination.r),
+            composite(source.g, destination.g),
+            composite(source.b, destination.b),
+        )
+    }
+}
+
+/// How the colors of a [`DrawGradientRect`](`RenderInstruction::DrawGradientRect`)
+/// are blended across its rectangle
+#[derive(Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// The colors are blended along a direction, from the corner of the rectangle
+    /// furthest behind it (the start color) to the corner furthest ahead (the end color)
+    Linear {
+        /// The direction of the gradient (e.g. `(1, 0)` from left to right)
+        direction: Vector2D,
+    },
+    /// The colors are blended from the center of the rectangle (the start color) to
+    /// its edges (the end color), along the ellipse inscribed in it
+    Radial,
+}
+
+impl GradientKind {
+    /// Gets how far a position is along the gradient, such that backends interpolate
+    /// the start and end colors (e.g. with [`Interpolate`](`crate::animation::Interpolate`))
+    ///
+    /// # Returns
+    /// The fraction of the way, from 0 (the start color) to 1 (the end color)
+    ///
+    /// # Arguments
+    /// * `position` - the position, on a two-dimensional space
+    /// * `point` - the upper left corner of the gradient's rectangle
+    /// * `size` - the size (width and height) of the gradient's rectangle
+    pub fn progress_at(&self, position: Vector2D, point: Vector2D, size: Vector2D) -> f64 {
+        let progress = match self {
+            GradientKind::Linear { direction } => {
+                // Project the position and the corners on the direction
+                let project = |corner: Vector2D| corner.x * direction.x + corner.y * direction.y;
+                let corners = [
+                    point,
+                    Vector2D::new(point.x + size.x, point.y),
+                    Vector2D::new(point.x, point.y + size.y),
+                    point + size,
+                ];
+                let start = corners
+                    .iter()
+                    .map(|corner| project(*corner))
+                    .fold(f64::INFINITY, f64::min);
+                let end = corners
+                    .iter()
+                    .map(|corner| project(*corner))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                if end > start {
+                    (project(position) - start) / (end - start)
+                } else {
+                    0.
+                }
+            }
+            GradientKind::Radial => {
+                let radius = size * 0.5;
+                let distance = position - (point + radius);
+                if radius.x > 0. && radius.y > 0. {
+                    (distance.x / radius.x).hypot(distance.y / radius.y)
+                } else {
+                    0.
+                }
+            }
+        };
+        progress.clamp(0., 1.)
+    }
+}
+
+/// How a widget's area is filled: with a flat color or with a gradient
+#[derive(Clone, Copy, PartialEq)]
+pub enum Fill {
+    /// The area is filled with a single color
+    Solid(Color),
+    /// The area is filled with colors blended along a direction
+    LinearGradient {
+        /// The color where the gradient starts
+        start_color: Color,
+        /// The color where the gradient ends
+        end_color: Color,
+        /// The direction of the gradient (e.g. `(1, 0)` from left to right)
+        direction: Vector2D,
+    },
+    /// The area is filled with colors blended from its center to its edges
+    RadialGradient {
+        /// The color on the center
+        start_color: Color,
+        /// The color on the edges
+        end_color: Color,
+    },
+}
+
+impl Fill {
+    /// Creates the render instruction filling a rectangle
+    ///
+    /// # Returns
+    /// A `DrawRect` for a solid fill, or a `DrawGradientRect` for a gradient
+    ///
+    /// # Arguments
+    /// * `point` - the rectangle's upper left corner, on a two-dimensional space
+    /// * `size` - the rectangle's size (width and height)
+    /// * `clip_point` - the clipping area start point, on a two-dimensional space
+    /// * `clip_size` - the clipping area size, width and height
+    pub fn instruction(
+        &self,
+        point: Vector2D,
+        size: Vector2D,
+        clip_point: Vector2D,
+        clip_size: Vector2D,
+    ) -> RenderInstruction {
+        let (start_color, end_color, kind) = match *self {
+            Fill::Solid(color) => {
+                return RenderInstruction::DrawRect {
+                    point,
+                    size,
+                    color,
+                    clip_point,
+                    clip_size,
+                    blend_mode: BlendMode::Normal,
+                }
+            }
+            Fill::LinearGradient {
+                start_color,
+                end_color,
+                direction,
+            } => (start_color, end_color, GradientKind::Linear { direction }),
+            Fill::RadialGradient {
+                start_color,
+                end_color,
+            } => (start_color, end_color, GradientKind::Radial),
+        };
+        RenderInstruction::DrawGradientRect {
+            point,
+            size,
+            start_color,
+            end_color,
+            kind,
+            clip_point,
+            clip_size,
+            blend_mode: BlendMode::Normal,
+        }
+    }
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Fill::Solid(color)
+    }
+}
 /// Instructions to be executed by the renderer on the next clipping frame
 /// 
 /// This instructions are responsible for invoking primitive methods in the renderer
@@ -89,6 +753,8 @@ pub enum RenderInstruction {
         clip_point: Vector2D,
         /// The clipping area size, width and height
         clip_size: Vector2D,
+        /// How the arc's fill color is composited with the colors beneath it
+        blend_mode: BlendMode,
     },
 
     /// Draw a circle centered on a specific point
@@ -104,6 +770,8 @@ pub enum RenderInstruction {
         clip_point: Vector2D,
         /// The clipping area size, width and height
         clip_size: Vector2D,
+        /// How the circle's fill color is composited with the colors beneath it
+        blend_mode: BlendMode,
     },
 
     /// Draw a rectangle based on the upper left and bottom right corners
@@ -119,6 +787,32 @@ pub enum RenderInstruction {
         clip_point: Vector2D,
         /// The clipping area size, width and height
         clip_size: Vector2D,
+        /// How the rectangle's fill color is composited with the colors beneath it
+        blend_mode: BlendMode,
+    },
+
+    /// Draw a rectangle filled with a gradient between two colors
+    ///
+    /// Backends compute the color of each pixel from how far it is along the gradient
+    /// (see [`GradientKind::progress_at`]).
+    DrawGradientRect {
+        /// The rectangle start point, on a two-dimensional space - upper left corner
+        point: Vector2D,
+        /// The rectangle size, width and height
+        size: Vector2D,
+        /// The color where the gradient starts
+        start_color: Color,
+        /// The color where the gradient ends
+        end_color: Color,
+        /// How the colors are blended across the rectangle (i.e., linear or radial)
+        kind: GradientKind,
+        /// The clipping area start point, on a two-dimensional space - window's 
+        /// upper left corner
+        clip_point: Vector2D,
+        /// The clipping area size, width and height
+        clip_size: Vector2D,
+        /// How the rectangle's colors are composited with the colors beneath it
+        blend_mode: BlendMode,
     },
 
     /// Draw a triangle based on his vertices locations
@@ -136,6 +830,8 @@ pub enum RenderInstruction {
         clip_point: Vector2D,
         /// The clipping area size, width and height
         clip_size: Vector2D,
+        /// How the triangle's fill color is composited with the colors beneath it
+        blend_mode: BlendMode,
     },
 
     /// Draw an image centered on a speciic point
@@ -144,8 +840,88 @@ pub enum RenderInstruction {
         point: Vector2D,
         /// The image's relative path
         path: String,
+        /// The number of image pixels per logical unit (e.g. 2 for a 2x image), so that
+        /// the image is drawn at its logical size
+        density: f64,
+        /// The image's resolutions, if several, replaced by the best match for the scale
+        /// factor (i.e., `path` and `density`) before reaching the renderer
+        source: Option<ImageSource>,
         /// The image's resize configuration
         options: DrawImageOptions,
+        /// Whether the image is mirrored horizontally (e.g. directional icons on
+        /// right-to-left layouts)
+        mirrored: bool,
+        /// The clipping area start point, on a two-dimensional space - window's 
+        /// upper left corner
+        clip_point: Vector2D,
+        /// The clipping area size, width and height
+        clip_size: Vector2D,
+    },
+
+    /// Draw a region of an [`ImageAtlas`] (e.g. an icon), stretched to a specific area
+    ///
+    /// Unlike [`DrawImage`](`RenderInstruction::DrawImage`), the image is not loaded
+    /// again for each instruction, since every region refers to the atlas loaded by the
+    /// renderer when it was registered (see [`load_atlas`](`Renderer::load_atlas`)).
+    DrawImageRegion {
+        /// The area's upper left corner, on a two-dimensional space
+        point: Vector2D,
+        /// The area's size, width and height
+        size: Vector2D,
+        /// The handle of the atlas
+        atlas: AtlasHandle,
+        /// The region drawn, on the atlas' image and in image pixels
+        region: Rect,
+        /// The name of the region drawn, if any, replaced by its area on the atlas (i.e.,
+        /// `region`) before reaching the renderer, unless the atlas has no such region
+        region_name: Option<String>,
+        /// Whether the region is mirrored horizontally (e.g. directional icons on
+        /// right-to-left layouts)
+        mirrored: bool,
+        /// The clipping area start point, on a two-dimensional space - window's 
+        /// upper left corner
+        clip_point: Vector2D,
+        /// The clipping area size, width and height
+        clip_size: Vector2D,
+    },
+
+    /// Draw an image held in memory (see [`ImageData`]), stretched to a specific area
+    ///
+    /// Unlike [`DrawImage`](`RenderInstruction::DrawImage`), the image is not read from
+    /// a file, the renderer uploads its pixels (e.g. to a texture) instead.
+    DrawImageData {
+        /// The area's upper left corner, on a two-dimensional space
+        point: Vector2D,
+        /// The area's size, width and height
+        size: Vector2D,
+        /// The image's pixels, shared with the widget drawing it
+        image: Rc<ImageData>,
+        /// Whether the image is mirrored horizontally (e.g. directional icons on
+        /// right-to-left layouts)
+        mirrored: bool,
+        /// The clipping area start point, on a two-dimensional space - window's 
+        /// upper left corner
+        clip_point: Vector2D,
+        /// The clipping area size, width and height
+        clip_size: Vector2D,
+    },
+
+    /// Draw an image registered on the [`ResourceRegistry`](`crate::resources::ResourceRegistry`),
+    /// stretched to a specific area
+    ///
+    /// Unlike [`DrawImage`](`RenderInstruction::DrawImage`), the instruction refers to
+    /// the image by its handle, so that no path is copied on every rebuild and the
+    /// renderer draws the image loaded once (see [`load_image`](`Renderer::load_image`)).
+    DrawImageHandle {
+        /// The area's upper left corner, on a two-dimensional space
+        point: Vector2D,
+        /// The area's size, width and height
+        size: Vector2D,
+        /// The handle of the image
+        image: ImageHandle,
+        /// Whether the image is mirrored horizontally (e.g. directional icons on
+        /// right-to-left layouts)
+        mirrored: bool,
         /// The clipping area start point, on a two-dimensional space - window's 
         /// upper left corner
         clip_point: Vector2D,
@@ -155,7 +931,9 @@ pub enum RenderInstruction {
 
     /// Draw text from a specific point forward
     ///
-    /// _**Note:** The text alignment property is not considered here
+    /// _**Note:** The text alignment property is not considered here, texts on multiple
+    /// lines are laid out by a [`TextLayout`](`crate::text::TextLayout`) into one
+    /// `DrawText` per line
     DrawText {
         /// The text starting point, on a two-dimensional space
         point: Vector2D,
@@ -170,43 +948,509 @@ pub enum RenderInstruction {
         clip_point: Vector2D,
         /// The clipping area size, width and height
         clip_size: Vector2D,
+        /// The text font, with the fallbacks for the glyphs it lacks
+        font: FontDescriptor,
+    },
+
+    /// Draw text from a specific point forward, with a font registered on the
+    /// [`ResourceRegistry`](`crate::resources::ResourceRegistry`)
+    ///
+    /// Unlike [`DrawText`](`RenderInstruction::DrawText`), the instruction refers to the
+    /// font by its handle, so that the renderer draws it with the font loaded once
+    /// (see [`load_font`](`Renderer::load_font`)) instead of looking up its family.
+    DrawTextHandle {
+        /// The text starting point, on a two-dimensional space
+        point: Vector2D,
+        /// The text font size
+        font_size: usize,
+        /// The vector of characters (i.e., text) to be render
+        string: String,
+        /// The text font color
+        color: Color,
+        /// The handle of the text font
+        font: FontHandle,
+        /// The clipping area start point, on a two-dimensional space - window's 
+        /// upper left corner
+        clip_point: Vector2D,
+        /// The clipping area size, width and height
+        clip_size: Vector2D,
+    },
+
+    /// Clip the following instructions, until the matching [`PopClip`](`RenderInstruction::PopClip`),
+    /// to a shape
+    ///
+    /// The clips are nested: each instruction is clipped to the intersection of every
+    /// shape pushed and not popped yet, and of its own clipping area. Backends that do
+    /// not support a shape may clip to its [`bounds`](`ClipShape::bounds`) instead.
+    PushClip {
+        /// The shape clipping the following instructions
+        shape: ClipShape,
+    },
+
+    /// Remove the shape pushed by the last [`PushClip`](`RenderInstruction::PushClip`)
+    PopClip,
+
+    /// Draw the following instructions, until the matching
+    /// [`PopOpacity`](`RenderInstruction::PopOpacity`), with their transparency
+    /// multiplied by an opacity
+    ///
+    /// The opacities are nested: each instruction is drawn with the product of every
+    /// opacity pushed and not popped yet. Only sent to the backends supporting them (see
+    /// [`supports_opacity`](`Renderer::supports_opacity`)).
+    PushOpacity {
+        /// The opacity, from 0 (invisible) to 1 (unchanged)
+        alpha: f64,
     },
+
+    /// Remove the opacity pushed by the last [`PushOpacity`](`RenderInstruction::PushOpacity`)
+    PopOpacity,
+
+    /// Draw the following instructions, until the matching
+    /// [`PopTransform`](`RenderInstruction::PopTransform`), scaled, then rotated, then
+    /// translated (see [`Transform`])
+    ///
+    /// The transforms are nested: each instruction is transformed by the last transform
+    /// pushed, then by the previous ones, up to the first one. Clipping areas are
+    /// transformed as well, as they are given in the same coordinates as the shapes.
+    PushTransform {
+        /// The distance the instructions are moved by, after being scaled and rotated
+        translation: Vector2D,
+        /// The angle the instructions are rotated by around the origin, in radians
+        rotation: f64,
+        /// The factors the instructions are scaled by around the origin, along each axis
+        scale: Vector2D,
+    },
+
+    /// Remove the transform pushed by the last [`PushTransform`](`RenderInstruction::PushTransform`)
+    PopTransform,
+}
+
+impl RenderInstruction {
+    /// Maps the color of the render instruction, if it has one (i.e., every
+    /// instruction but the images, the clip, the opacity and the transform instructions)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `map` - the function mapping the current color into the new one
+    pub fn map_color<F: Fn(Color) -> Color>(&mut self, map: F) {
+        match self {
+            RenderInstruction::Clear { color }
+            | RenderInstruction::DrawPoint { color, .. }
+            | RenderInstruction::DrawLine { color, .. }
+            | RenderInstruction::DrawArc { color, .. }
+            | RenderInstruction::DrawCircle { color, .. }
+            | RenderInstruction::DrawRect { color, .. }
+            | RenderInstruction::DrawTriangle { color, .. }
+            | RenderInstruction::DrawText { color, .. }
+            | RenderInstruction::DrawTextHandle { color, .. } => *color = map(*color),
+            RenderInstruction::DrawGradientRect {
+                start_color,
+                end_color,
+                ..
+            } => {
+                *start_color = map(*start_color);
+                *end_color = map(*end_color);
+            }
+            RenderInstruction::DrawImage { .. }
+            | RenderInstruction::DrawImageRegion { .. }
+            | RenderInstruction::DrawImageData { .. }
+            | RenderInstruction::DrawImageHandle { .. }
+            | RenderInstruction::PushClip { .. }
+            | RenderInstruction::PopClip
+            | RenderInstruction::PushOpacity { .. }
+            | RenderInstruction::PopOpacity
+            | RenderInstruction::PushTransform { .. }
+            | RenderInstruction::PopTransform => {}
+        }
+    }
+
+    /// Rounds the geometry of rectangle, line and point instructions to device
+    /// pixels, such that they are rendered crisp instead of landing on half pixels
+    ///
+    /// _**Note:** Rectangles are snapped by their edges, so adjacent rectangles
+    /// remain adjacent after snapping
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `scale_factor` - the number of device pixels per logical unit
+    pub fn snap_to_pixels(&mut self, scale_factor: f64) {
+        // Snaps both edges of a rectangle and recomputes its size
+        let snap_rect = |point: &mut Vector2D, size: &mut Vector2D| {
+            let end = (*point + *size).snap(scale_factor);
+            *point = point.snap(scale_factor);
+            *size = end - *point;
+        };
+
+        match self {
+            RenderInstruction::DrawPoint {
+                point,
+                clip_point,
+                clip_size,
+                ..
+            } => {
+                *point = point.snap(scale_factor);
+                snap_rect(clip_point, clip_size);
+            }
+            RenderInstruction::DrawLine {
+                point_a,
+                point_b,
+                clip_point,
+                clip_size,
+                ..
+            } => {
+                *point_a = point_a.snap(scale_factor);
+                *point_b = point_b.snap(scale_factor);
+                snap_rect(clip_point, clip_size);
+            }
+            RenderInstruction::DrawRect {
+                point,
+                size,
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawGradientRect {
+                point,
+                size,
+                clip_point,
+                clip_size,
+                ..
+            } => {
+                snap_rect(point, size);
+                snap_rect(clip_point, clip_size);
+            }
+            _ => {}
+        }
+    }
+
+    /// Gets the area of the display that the render instruction may draw on, used to
+    /// track the damage (see [`RenderInstructionCollection::damage`])
+    ///
+    /// # Returns
+    /// The instruction's clipping area, the whole display (i.e., an infinite size)
+    /// for `Clear`, or `None` for the clip, opacity and transform instructions, which
+    /// draw nothing themselves
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn bounds(&self) -> Option<Rect> {
+        match self {
+            RenderInstruction::Clear { .. } => Some(Rect::new(
+                Vector2D::new(0., 0.),
+                Vector2D::new(f64::INFINITY, f64::INFINITY),
+            )),
+            RenderInstruction::DrawPoint {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawLine {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawArc {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawCircle {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawRect {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawGradientRect {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawTriangle {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawImage {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawImageRegion {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawImageData {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawImageHandle {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawText {
+                clip_point,
+                clip_size,
+                ..
+            }
+            | RenderInstruction::DrawTextHandle {
+                clip_point,
+                clip_size,
+                ..
+            } => Some(Rect::new(*clip_point, *clip_size)),
+            RenderInstruction::PushClip { .. }
+            | RenderInstruction::PopClip
+            | RenderInstruction::PushOpacity { .. }
+            | RenderInstruction::PopOpacity
+            | RenderInstruction::PushTransform { .. }
+            | RenderInstruction::PopTransform => None,
+        }
+    }
+
+    /// Gets the blend mode of the render instruction
+    ///
+    /// # Returns
+    /// The blend mode of the fill instructions, or `None` for the other ones
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn blend_mode(&self) -> Option<BlendMode> {
+        match self {
+            RenderInstruction::DrawArc { blend_mode, .. }
+            | RenderInstruction::DrawCircle { blend_mode, .. }
+            | RenderInstruction::DrawRect { blend_mode, .. }
+            | RenderInstruction::DrawGradientRect { blend_mode, .. }
+            | RenderInstruction::DrawTriangle { blend_mode, .. } => Some(*blend_mode),
+            _ => None,
+        }
+    }
+
+    /// Sets the blend mode of the render instruction, if it is a fill instruction
+    /// (i.e., `DrawArc`, `DrawCircle`, `DrawRect`, `DrawGradientRect` and `DrawTriangle`)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `mode` - how the fill color is composited with the colors beneath it
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        match self {
+            RenderInstruction::DrawArc { blend_mode, .. }
+            | RenderInstruction::DrawCircle { blend_mode, .. }
+            | RenderInstruction::DrawRect { blend_mode, .. }
+            | RenderInstruction::DrawGradientRect { blend_mode, .. }
+            | RenderInstruction::DrawTriangle { blend_mode, .. } => *blend_mode = mode,
+            _ => {}
+        }
+    }
+
+    /// Sets the blend mode of the render instruction, if it is a fill instruction,
+    /// returning the render instruction (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The render instruction with the new blend mode
+    ///
+    /// # Arguments
+    /// * `mode` - how the fill color is composited with the colors beneath it
+    pub fn with_blend_mode(mut self, mode: BlendMode) -> Self {
+        self.set_blend_mode(mode);
+        self
+    }
 }
 
-/// Event messages used to execute instructions on the application when triggered by a Widget.
-/// 
-/// When messages are created and enqueued by the widgets, the widget should call their [`set_event`](`self::set_event`) such that the application knows which event triggered the Message.
-/// 
-/// During the [`event_loop`](`self::event_loop`) method, messages are iterated over and their update() function is called.
-pub trait Message: MessageClone {
-    /// Executes the code implemented in the Message struct.
-    fn update(&self);
+/// Event messages used to execute instructions on the application when triggered by a Widget.
+/// 
+/// When messages are created and enqueued by the widgets, the widget should call their [`set_event`](`self::set_event`) such that the application knows which event triggered the Message.
+/// 
+/// During the [`event_loop`](`self::event_loop`) method, messages are iterated over and their update() function is called.
+pub trait Message: MessageClone {
+    /// Executes the code implemented in the Message struct.
+    fn update(&self);
+
+    /// Sets the event associated to the message
+    fn set_event(&mut self, event: Event);
+}
+
+/// Trait that allows to provide a blanket implementation of Clone for all compatible 
+/// types, without having to implement the rest of Message.
+///
+/// This Clone is used to solve problems from cloning vector or boxes of messages
+pub trait MessageClone {
+    fn clone_box(&self) -> Box<dyn Message>;
+
+    /// Gets the message as `Any`, used to downcast it to its concrete type
+    fn as_any(&self) -> &dyn Any;
+
+    /// Gets the message as mutable `Any`, used to downcast it to its concrete type
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> MessageClone for T
+where
+    T: 'static + Message + Clone,
+{
+    fn clone_box(&self) -> Box<dyn Message> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl dyn Message {
+    /// Checks if the message is of the given concrete type
+    ///
+    /// # Returns
+    /// True if the message is a `T`, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is<T: Message + 'static>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Downcasts the message to its concrete type
+    ///
+    /// # Returns
+    /// A reference to the message as `T`, or `None` if the message is of another type
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Some(clicked) = message.downcast_ref::<Clicked>() {
+    ///     println!("{} was clicked", clicked.widget_key);
+    /// }
+    /// ```
+    pub fn downcast_ref<T: Message + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Downcasts the message to its concrete type, mutably
+    ///
+    /// Useful for widgets to fill in the data of common messages (e.g. the new
+    /// value on a [`ValueChanged`](`crate::message::ValueChanged`)) before enqueuing them.
+    ///
+    /// # Returns
+    /// A mutable reference to the message as `T`, or `None` if the message is of another type
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn downcast_mut<T: Message + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
 
-    /// Sets the event associated to the message
-    fn set_event(&mut self, event: Event);
+impl Clone for Box<dyn Message> {
+    fn clone(&self) -> Box<dyn Message> {
+        self.clone_box()
+    }
 }
 
-/// Trait that allows to provide a blanket implementation of Clone for all compatible 
-/// types, without having to implement the rest of Message.
+/// Marks a widget and all of its descendants as dirty, for internal use only
 ///
-/// This Clone is used to solve problems from cloning vector or boxes of messages
-pub trait MessageClone {
-    fn clone_box(&self) -> Box<dyn Message>;
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `widget` - the widget to be marked as dirty along with its descendants
+pub(crate) fn set_tree_dirty(widget: &Rc<RefCell<dyn Widget>>) {
+    widget.borrow_mut().set_dirty(true);
+    let children = all_children(&mut *widget.borrow_mut());
+    for child in children.iter() {
+        set_tree_dirty(child);
+    }
 }
 
-impl<T> MessageClone for T
-where
-    T: 'static + Message + Clone,
-{
-    fn clone_box(&self) -> Box<dyn Message> {
-        Box::new(self.clone())
+/// Gets the identifiers of a widget and all of its descendants, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `widget` - the widget whose identifiers are collected along with its descendants
+/// * `ids` - the pairs of widget and identifier collected so far
+fn collect_tree_ids(
+    widget: &Rc<RefCell<dyn Widget>>,
+    ids: &mut Vec<(Weak<RefCell<dyn Widget>>, usize)>,
+) {
+    ids.push((Rc::downgrade(widget), widget.borrow().id()));
+    let children = all_children(&mut *widget.borrow_mut());
+    for child in children.iter() {
+        collect_tree_ids(child, ids);
     }
 }
 
-impl Clone for Box<dyn Message> {
-    fn clone(&self) -> Box<dyn Message> {
-        self.clone_box()
+/// Builds a widget tree at an arbitrary size and returns its render instructions,
+/// independently of any display (e.g. for applications converting a frame to SVG or
+/// PDF to print or preview it, or for documentation tooling)
+///
+/// The widgets are built with their own identifiers and settings from the given
+/// context, so a context dedicated to exports should be used. Afterwards, the widgets
+/// get their identifiers back and are rebuilt on the next frame of the event loop.
+///
+/// # Returns
+/// The render instructions of the widget tree, in drawing order
+///
+/// # Arguments
+/// * `root` - the root of the widget tree to export
+/// * `size` - the size (width and height) at which the widget tree is built
+/// * `context` - the context used to build the widget tree (e.g. with the scale factor to export at)
+///
+/// # Examples
+/// ```ignore
+/// let mut context = Context::new();
+/// context.set_scale_factor(2.);
+/// let instructions = export_instructions(
+///     &(root.clone() as Rc<RefCell<dyn Widget>>),
+///     Vector2D::new(595., 842.),
+///     &mut context,
+/// );
+/// ```
+pub fn export_instructions(
+    root: &Rc<RefCell<dyn Widget>>,
+    size: Vector2D,
+    context: &mut Context,
+) -> Vec<RenderInstruction> {
+    let mut ids = Vec::new();
+    collect_tree_ids(root, &mut ids);
+
+    let mut collection = RenderInstructionCollection::new();
+    set_tree_dirty(root);
+    root.borrow_mut().build(
+        Vector2D::new(0., 0.),
+        size,
+        &mut IDMachine::new(),
+        &mut collection,
+        context,
+    );
+
+    // Restore the identifiers, such that the instructions of the widgets are still
+    // replaced on the collection drawn by the event loop
+    for (value, id) in ids {
+        if let Some(widget) = value.upgrade() {
+            widget.borrow_mut().set_id(id);
+        }
     }
+    set_tree_dirty(root);
+    collection.flatten()
 }
 
 /// Trait with the necessary methods that the programmer must implement for their renderer of choice. Every method must be implemented, except one: [`event_loop`](`self::event_loop`).
@@ -271,8 +1515,75 @@ pub trait Renderer<D, E> {
     /// * `display` - generic type to access display events
     fn detect_display_events(events: &mut Queue<Event>, display: &mut D);
 
+    /// Whether the renderer composites fill instructions with a blend mode
+    ///
+    /// The fill instructions using an unsupported blend mode are drawn with
+    /// [`BlendMode::Normal`] instead. By default, only the normal mode is supported.
+    ///
+    /// # Returns
+    /// True if the renderer supports the blend mode, false otherwise
+    ///
+    /// # Arguments
+    /// * `mode` - the blend mode to check
+    fn supports_blend_mode(&self, mode: BlendMode) -> bool {
+        mode == BlendMode::Normal
+    }
+
+    /// Whether the renderer draws groups of instructions with an opacity (i.e.,
+    /// [`PushOpacity`](`RenderInstruction::PushOpacity`) and
+    /// [`PopOpacity`](`RenderInstruction::PopOpacity`))
+    ///
+    /// Otherwise, the colors of the instructions of the translucent widgets are made
+    /// translucent instead, leaving their images opaque. By default, opacity groups are
+    /// not supported.
+    ///
+    /// # Returns
+    /// True if the renderer supports opacity groups, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn supports_opacity(&self) -> bool {
+        false
+    }
+
+    /// Gets the metrics of the renderer's fonts, used by the widgets to measure texts
+    /// (e.g. to size a label to its text)
+    ///
+    /// The metrics are set for the widgets of the current thread, with
+    /// [`text::set_text_metrics`], when the event loop starts. By default, the
+    /// renderer has no metrics and the texts' size is estimated from their font size.
+    ///
+    /// # Returns
+    /// The callback measuring a single line of text, or `None` if not supported
+    ///
+    /// # Arguments
+    /// No arguments
+    fn text_metrics(&self) -> Option<TextMetrics> {
+        None
+    }
+
+    /// Measures a single line of text with the renderer's fonts
+    ///
+    /// # Returns
+    /// The size (width and height) of the text, measured by the renderer's
+    /// [`text_metrics`](`Renderer::text_metrics`) or, if none, estimated from the font size
+    ///
+    /// # Arguments
+    /// * `string` - the text to be measured
+    /// * `font_size` - the font size of the text
+    fn measure_text(&self, string: &str, font_size: usize) -> Vector2D {
+        match self.text_metrics() {
+            Some(text_metrics) => text_metrics(string, font_size),
+            None => text::estimate_text(string, font_size),
+        }
+    }
+
     /// Event loop that handles the events within hyber
     ///
+    /// It runs until the display is closed or the context's control flow is set to
+    /// [`ControlFlow::Exit`], waiting between frames according to the control flow and
+    /// pacing them by vsync or the target frame rate (see [`Context::set_control_flow`]).
+    ///
     /// # Returns
     /// No returns
     ///
@@ -282,9 +1593,7 @@ pub trait Renderer<D, E> {
     /// * `root_ptr` - reference to the root widget
     /// * `display` - generic type to access display events
     /// * `display_size` - the display bottom right corner (i.e., the maximum window width and height)
-    /// * `id_machine` - identifier of the machine
-    /// * `render_instruction_collection_ptr` - collection of instructions to be rendered
-    /// * `absolute_widget_collection_ptr` - collection of widgets to be rendered on absolute positions
+    /// * `state` - the state kept by the event loop across frames (see [`EventLoopState`])
     fn event_loop(
         &mut self,
         mut events: Queue<Event>,
@@ -292,78 +1601,97 @@ pub trait Renderer<D, E> {
         root_ptr: Weak<RefCell<dyn Widget>>,
         display: &mut D,
         display_size: Vector2D,
-        id_machine: &mut IDMachine,
-        render_instruction_collection_ptr: Weak<RefCell<RenderInstructionCollection>>,
-        absolute_widget_collection_ptr: Weak<RefCell<AbsoluteWidgetCollection>>,
-    ) {
+        mut state: EventLoopState,
+    ) where
+        D: Display,
+    {
+        let id_machine = &mut state.id_machine;
+        let render_instruction_collection_ptr = state.render_instruction_collection;
+        let absolute_widget_collection_ptr = state.absolute_widget_collection;
+        let context_ptr = state.context;
+
+        // Let the widgets fall back to the normal blend mode when the renderer does not
+        // support theirs
+        if let Some(context) = context_ptr.upgrade() {
+            let blend_modes = BlendMode::ALL
+                .iter()
+                .copied()
+                .filter(|mode| self.supports_blend_mode(*mode))
+                .collect();
+            context.borrow_mut().set_supported_blend_modes(blend_modes);
+            context.borrow_mut().set_supports_opacity(self.supports_opacity());
+            // Let the widgets measure their texts with the renderer's fonts
+            text::set_text_metrics(self.text_metrics());
+        }
+
         loop {
             // Detects and map the system events into hyber events
             Self::detect_display_events(&mut events, display);
 
-            // Get the root "object" - allocation 
-            if let Some(root) = root_ptr.upgrade() {
-                // Get the render instructions collection "object" - allocation 
-                if let Some(render_instruction_collection) =
-                    render_instruction_collection_ptr.upgrade()
-                {
-                    // Iterate over the events queue
-                    for event in events.queue.drain(..) {
-                        // Call on_event method to detect if the event is being done on this 
-                        // widget, update the state of the widget based on event and place a 
-                        // message in the message queue.
-                        root.borrow_mut().on_event(event, &mut messages);
-                    }
+            // Slow down or skip the frame while the window is idle, according to
+            // the idle policy of the application, or while waiting, according to
+            // the control flow
+            if let Some(context) = context_ptr.upgrade() {
+                if context.borrow().control_flow() == ControlFlow::Exit {
+                    return;
+                }
+                if !context.borrow_mut().wait_idle(&events) {
+                    continue;
+                }
+                if !context.borrow_mut().wait_control_flow(&events) {
+                    continue;
+                }
+            }
 
-                    // Iterate over all elements of the widget tree (i.e., starting from the
-                    // root widget through all is childrens) to build them, if needed, and 
-                    // decomposes the layout constraints to the children
-                    root.borrow_mut().build(
-                        Vector2D::new(0., 0.),
-                        display_size,
-                        id_machine,
-                        &mut render_instruction_collection.borrow_mut(),
-                    );
+            // Instant when the frame started, used to compute the frame time
+            let frame_start = clock::now();
 
-                    // Iterate over all elements of the absolute widgets collection to build 
-                    // them, if needed, and decomposes the layout constraints to the children
-                    if let Some(absolute_widgets) = absolute_widget_collection_ptr.upgrade() {
-                        for (id, (value, position, size)) in
-                            absolute_widgets.borrow_mut().widgets.iter()
-                        {
-                            // Get the widget "object" - allocation 
-                            if let Some(widget) = value.upgrade() {
-                                // If the widget needs to be rebuilt
-                                if widget.borrow_mut().is_dirty() {
-                                    // Assign position of widget
-                                    widget.borrow_mut().set_position(*position);
-                                    // Assign size of widget
-                                    widget.borrow_mut().set_size(*size);
-
-                                    render_instruction_collection.borrow_mut().remove(*id);
-                                    // Add the render instructions of the widget to the render
-                                    // instructions collection so that the widget is drawn
-                                    render_instruction_collection
-                                        .borrow_mut()
-                                        .replace_or_insert(
-                                            *id,
-                                            widget.borrow_mut().recipe().clone(),
-                                        );
-                                
-                                    // Update the dirty flag, set the widget as clean now
-                                    widget.borrow_mut().set_dirty(false);
-                                }
-                            }
-                        }
-                    }
+            // Get the root, the render instructions collection and the context "objects"
+            // - allocation
+            if let (Some(root), Some(render_instruction_collection), Some(context)) = (
+                root_ptr.upgrade(),
+                render_instruction_collection_ptr.upgrade(),
+                context_ptr.upgrade(),
+            ) {
+                let absolute_widgets = absolute_widget_collection_ptr.upgrade();
+                let mut host = DisplayHost {
+                    renderer: &mut *self,
+                    display: &mut *display,
+                    events: std::marker::PhantomData,
+                };
+                let is_open = run_frame(
+                    &mut host,
+                    FrameTree {
+                        root: &root,
+                        id_machine: &mut *id_machine,
+                        collection: &mut render_instruction_collection.borrow_mut(),
+                        absolute_widgets: absolute_widgets.as_deref(),
+                        context: &context,
+                    },
+                    &mut events,
+                    &mut messages,
+                    display_size,
+                    frame_start,
+                );
 
-                    // Draws the collection of render instructions on the display
-                    self.draw_collection(&mut render_instruction_collection.borrow_mut(), display);
-                    
-                    // Update messages
-                    for message in messages.queue.drain(..) {
-                        message.update();
-                    }
+                // Stop the event loop once the display was closed
+                if !is_open {
+                    context.borrow().end_watched_frame();
+                    return;
+                }
+
+                // Update messages
+                context.borrow().enter_frame_phase(FramePhase::Messages);
+                for message in messages.queue.drain(..) {
+                    message.update();
                 }
+                context.borrow().end_watched_frame();
+            }
+
+            // Wait for the display's vertical blank or the target frame rate, if any
+            if let Some(context) = context_ptr.upgrade() {
+                let is_vsynced = context.borrow().is_vsync() && display.wait_vsync();
+                context.borrow().pace_frame(frame_start, is_vsynced);
             }
         }
     }
@@ -377,12 +1705,413 @@ pub trait Renderer<D, E> {
     /// * `collection` - collection of instructions to render
     /// * `display` - generic type to access display events
     fn draw_collection(&mut self, collection: &RenderInstructionCollection, display: &mut D);
+
+    /// Loads the image of an atlas registered on the [`Context`], such that the
+    /// [`DrawImageRegion`](`RenderInstruction::DrawImageRegion`) instructions referring
+    /// to it are drawn from the image loaded once
+    ///
+    /// The event loop loads each atlas once, before the first frame drawn after it
+    /// was registered. By default, atlases are not supported and nothing is loaded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the atlas, referred to by the instructions
+    /// * `atlas` - the atlas, with the path of its image
+    fn load_atlas(&mut self, _handle: AtlasHandle, _atlas: &ImageAtlas) {}
+
+    /// Loads an image registered on the [`Context`]'s resources, such that the
+    /// [`DrawImageHandle`](`RenderInstruction::DrawImageHandle`) instructions referring
+    /// to it are drawn from the image loaded once (e.g. decoded into a texture)
+    ///
+    /// The event loop loads each image once, before the first frame drawn after it
+    /// was registered. By default, nothing is loaded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the image, referred to by the instructions
+    /// * `image` - the image, either its files or its pixels
+    fn load_image(&mut self, _handle: ImageHandle, _image: &ImageResource) {}
+
+    /// Loads a font registered on the [`Context`]'s resources, such that the
+    /// [`DrawTextHandle`](`RenderInstruction::DrawTextHandle`) instructions referring
+    /// to it are drawn with the font loaded once
+    ///
+    /// The event loop loads each font once, before the first frame drawn after it
+    /// was registered. By default, nothing is loaded.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the font, referred to by the instructions
+    /// * `font` - the font, with its family name and the path of its file
+    fn load_font(&mut self, _handle: FontHandle, _font: &FontResource) {}
+}
+
+/// The host of the frames run by [`run_frame`], which owns the window of the widget tree
+/// (e.g. the display of the event loop, or none for the harness), for internal use only
+pub(crate) trait FrameHost {
+    /// Forwards a request made by the widgets to the window
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `request` - the request made by the widgets
+    fn request(&mut self, request: WindowRequest);
+
+    /// Checks whether the window is still open
+    ///
+    /// # Returns
+    /// True, if the window is open, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_open(&self) -> bool;
+
+    /// Draws the collection of render instructions built on the frame, loading the
+    /// resources registered meanwhile and clearing the damage drawn
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the context of the widget tree, with the resources to be loaded
+    /// * `collection` - the collection of render instructions built
+    fn draw(&mut self, context: &mut Context, collection: &mut RenderInstructionCollection);
+}
+
+/// The host of the frames run without a window, which drops the requests made by the
+/// widgets and leaves the collection built undrawn (e.g. for the harness), for
+/// internal use only
+pub(crate) struct Headless;
+
+impl FrameHost for Headless {
+    fn request(&mut self, _request: WindowRequest) {}
+
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    fn draw(&mut self, _context: &mut Context, _collection: &mut RenderInstructionCollection) {}
+}
+
+/// The host of the frames run by the [`event_loop`](`Renderer::event_loop`), which
+/// forwards the requests to the display and draws with the renderer, for internal use
+/// only
+struct DisplayHost<'a, R: ?Sized, D, E> {
+    /// The renderer drawing the collection
+    renderer: &'a mut R,
+    /// The display drawn to
+    display: &'a mut D,
+    /// The type of the events of the display
+    events: std::marker::PhantomData<E>,
+}
+
+impl<R, D, E> FrameHost for DisplayHost<'_, R, D, E>
+where
+    R: Renderer<D, E> + ?Sized,
+    D: Display,
+{
+    fn request(&mut self, request: WindowRequest) {
+        match request {
+            WindowRequest::BeginResize(edge) => self.display.begin_resize(edge),
+            WindowRequest::BeginMove => self.display.begin_move(),
+            WindowRequest::Minimize => self.display.minimize(),
+            WindowRequest::ToggleMaximized => self.display.toggle_maximized(),
+            WindowRequest::Close => self.display.close(),
+            WindowRequest::SetTitle(title) => self.display.set_title(&title),
+            WindowRequest::RequestAttention(level) => self.display.request_attention(level),
+            WindowRequest::SetTopmost(topmost) => self.display.topmost(topmost),
+            WindowRequest::SetOpacity(opacity) => self.display.set_opacity(opacity),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.display.is_open()
+    }
+
+    fn draw(&mut self, context: &mut Context, collection: &mut RenderInstructionCollection) {
+        for (handle, atlas) in context.take_unloaded_atlases() {
+            self.renderer.load_atlas(handle, &atlas);
+        }
+        for (handle, image) in context.resources_mut().take_unloaded_images() {
+            self.renderer.load_image(handle, &image);
+        }
+        for (handle, font) in context.resources_mut().take_unloaded_fonts() {
+            self.renderer.load_font(handle, &font);
+        }
+        self.renderer.draw_collection(collection, self.display);
+        // The damage was drawn, so the next frame only tracks its own
+        collection.clear_damage();
+    }
+}
+
+/// The widget tree whose frames are run by [`run_frame`], for internal use only
+pub(crate) struct FrameTree<'a> {
+    /// The root of the widget tree
+    pub root: &'a Rc<RefCell<dyn Widget>>,
+    /// The machine giving the widgets their identifiers
+    pub id_machine: &'a mut IDMachine,
+    /// The collection of instructions built
+    pub collection: &'a mut RenderInstructionCollection,
+    /// The collection of widgets built on absolute positions, if any
+    pub absolute_widgets: Option<&'a RefCell<AbsoluteWidgetCollection>>,
+    /// The context of the widget tree
+    pub context: &'a Rc<RefCell<Context>>,
+}
+
+/// Runs a frame of a widget tree: handles the events queued, delivers the frame to the
+/// widgets, builds them and draws them on the host, leaving the messages enqueued to
+/// the caller, for internal use only
+///
+/// The event loop, the [`Harness`](`crate::harness::Harness`) and the
+/// [`Embedding`](`crate::embedding::Embedding`) run their frames through it, so that
+/// they behave the same.
+///
+/// # Returns
+/// False, if the host's window was closed meanwhile (i.e., nothing was built), true
+/// otherwise
+///
+/// # Arguments
+/// * `host` - the host of the widget tree
+/// * `tree` - the widget tree
+/// * `events` - the events to be handled, drained
+/// * `messages` - the queue of the messages enqueued by the widgets
+/// * `display_size` - the size at which the widget tree is built
+/// * `frame_start` - the instant when the frame started, to compute the frame time
+pub(crate) fn run_frame<H: FrameHost>(
+    host: &mut H,
+    tree: FrameTree,
+    events: &mut Queue<Event>,
+    messages: &mut Queue<Box<dyn Message>>,
+    display_size: Vector2D,
+    frame_start: Instant,
+) -> bool {
+    let FrameTree {
+        root,
+        id_machine,
+        collection,
+        absolute_widgets,
+        context,
+    } = tree;
+
+    // Detect the gestures made of several events (e.g. mouse button chords)
+    context.borrow_mut().detect_gestures(events);
+
+    // Execute the hooks to be executed before the events are handled
+    context.borrow().enter_frame_phase(FramePhase::Hooks);
+    context.borrow_mut().set_display_size(display_size);
+    let frame_info = context.borrow().next_frame_info(display_size);
+    Context::run_before_frame_hooks(context, &frame_info);
+
+    // Catch the panics on widgets instead of crashing, if guarded
+    context.borrow_mut().begin_widget_calls();
+
+    // Update the scale factor so that the font sizes are resolved for the new display's
+    // scale
+    for event in events.queue.iter() {
+        if let Event::Window(event::Window::ScaleFactorChanged(scale_factor)) = event {
+            context.borrow_mut().set_scale_factor(*scale_factor);
+        }
+    }
+
+    // Rebuild every widget if a setting affecting all of them changed (e.g. the scale
+    // factor or the layout direction)
+    if context.borrow_mut().take_needs_rebuild() {
+        set_tree_dirty(root);
+        if let Some(absolute_widgets) = absolute_widgets {
+            for (value, _, _) in absolute_widgets.borrow().widgets.values() {
+                if let Some(widget) = value.upgrade() {
+                    set_tree_dirty(&widget);
+                }
+            }
+        }
+    }
+
+    // Iterate over the events queue
+    context.borrow().enter_frame_phase(FramePhase::Events);
+    for event in events.queue.drain(..) {
+        // Keep the cursor's position for the widgets to get it relative to themselves
+        let previous = event::pointer_position();
+        event::track_pointer(&event);
+        event::track_modifiers(&event);
+        // Notify the widgets the cursor entered or exited, right after the event
+        let crossings = context
+            .borrow()
+            .pointer_crossings(previous, event::pointer_position());
+        for event in std::iter::once(event).chain(crossings) {
+            // Open the context menu of the widget right-clicked, if any, instead of
+            // dispatching the click
+            if context.borrow_mut().open_context_menu(&event) {
+                continue;
+            }
+            // Dispatch the mouse events to the widget capturing the mouse and the
+            // keyboard events to the active modal scope only
+            let mut roots = context.borrow().popups();
+            roots.insert(0, root.clone());
+            let target = widget::pointer_capture_target(&event, &roots)
+                .or_else(|| context.borrow().event_target(&event));
+            let target = target.unwrap_or_else(|| root.clone());
+            context.borrow().watch_widget(target.borrow().id());
+            // Call on_event method to detect if the event is being done on this widget,
+            // update the state of the widget based on event and place a message in the
+            // message queue.
+            widget::dispatch_event(&target, event, messages, context);
+            context.borrow_mut().close_dismissed_context_menu();
+        }
+        // End the drag once every widget saw the release
+        drag::finish_drag(&event);
+        event::finish_capture(&event);
+    }
+
+    // Update the time-driven state of the widgets, once per frame
+    widget::deliver_frame(&mut *root.borrow_mut(), messages);
+
+    // Draw a placeholder in place of the widgets that panicked meanwhile
+    context.borrow_mut().report_caught_panics(collection);
+
+    // Interpolate the animated properties, marking their widgets as dirty
+    Context::tick_animations(context);
+
+    // Forward the requests made by the widgets to the host (e.g. start resizing the
+    // window from a resize handle)
+    let window_requests = context.borrow_mut().take_window_requests();
+    for request in window_requests {
+        host.request(request);
+    }
+
+    // Stop once the host's window was closed
+    if !host.is_open() {
+        return false;
+    }
+
+    // Instant when the widgets started being built, used to check the frame budget
+    let build_start = clock::now();
+    context.borrow().enter_frame_phase(FramePhase::Build);
+
+    // Iterate over all elements of the widget tree (i.e., starting from the root widget
+    // through all is childrens) to build them, if needed, and decomposes the layout
+    // constraints to the children
+    let last_id = id_machine.last_id();
+    root.borrow_mut().build(
+        Vector2D::new(0., 0.),
+        display_size,
+        id_machine,
+        collection,
+        &mut context.borrow_mut(),
+    );
+
+    // Iterate over all elements of the absolute widgets collection to build them, if
+    // needed, and decomposes the layout constraints to the children
+    if let Some(absolute_widgets) = absolute_widgets {
+        for (id, (value, position, size)) in absolute_widgets.borrow_mut().widgets.iter() {
+            // Get the widget "object" - allocation
+            if let Some(widget) = value.upgrade() {
+                // If the widget needs to be rebuilt
+                if widget.borrow_mut().is_dirty() {
+                    // Assign position of widget
+                    widget.borrow_mut().set_position(*position);
+                    // Assign size of widget
+                    widget.borrow_mut().set_size(*size);
+
+                    let widget_build_start = clock::now();
+                    context.borrow().watch_widget(*id);
+                    collection.remove(*id);
+                    // Record the realized geometry of the widget
+                    context
+                        .borrow_mut()
+                        .record_geometry(*id, Rect::new(*position, *size));
+                    // Add the render instructions of the widget to the render
+                    // instructions collection so that the widget is drawn
+                    let recipe_start = clock::now();
+                    let mut instructions =
+                        context
+                            .borrow_mut()
+                            .guard_recipe(*id, *position, *size, || widget.borrow().recipe());
+                    let recipe_time = clock::elapsed(recipe_start);
+                    // Fade the widget by its own opacity, as it has no ancestors
+                    context.borrow_mut().push_opacity(widget.opacity());
+                    context
+                        .borrow()
+                        .finish_recipe(&mut instructions, &*widget.borrow());
+                    context.borrow_mut().pop_opacity();
+                    collection.replace_or_insert(*id, instructions);
+
+                    // Update the dirty flag, set the widget as clean now
+                    widget.borrow_mut().set_dirty(false);
+                    context.borrow_mut().record_widget_timing(
+                        *id,
+                        clock::elapsed(widget_build_start),
+                        recipe_time,
+                    );
+                }
+            }
+        }
+    }
+
+    // Build the popups open over the whole display, above the widget tree
+    widget::build_popups(
+        display_size,
+        id_machine.last_id() != last_id,
+        id_machine,
+        collection,
+        &mut context.borrow_mut(),
+    );
+
+    // Draw the ghost of the payload being dragged over every widget
+    drag::draw_ghost(collection);
+
+    // Draws the collection of render instructions on the host
+    context.borrow().enter_frame_phase(FramePhase::Draw);
+    host.draw(&mut context.borrow_mut(), collection);
+    let build_and_draw_time = clock::elapsed(build_start);
+
+    // Execute the one-shot callbacks requested for after this frame. The callbacks are
+    // taken first so they can borrow the context themselves
+    let frame = context.borrow_mut().begin_frame();
+    let callbacks = context.borrow_mut().take_frame_callbacks();
+    let frame_info = FrameInfo {
+        frame,
+        display_size,
+        frame_time: clock::elapsed(frame_start),
+    };
+    for callback in callbacks {
+        callback(&frame_info);
+    }
+
+    // Emit a diagnostic if building and drawing exceeded the frame budget
+    Context::check_frame_budget(context, frame, build_and_draw_time);
+
+    // Execute the hooks to be executed after every frame
+    Context::run_after_frame_hooks(context, &frame_info);
+
+    // Report the panics caught during this frame
+    Context::dispatch_panics(context);
+
+    // Report the widgets overflowing their constraints during this frame
+    Context::dispatch_overflows(context);
+
+    // Draw the next frame even if waiting, since the messages may change the widgets
+    if !messages.is_empty() {
+        context.borrow_mut().request_redraw();
+    }
+    true
 }
 
 /// Collection of render instructions to be rendered each frame
 ///
 /// An ordered key-value collection which contains as key the identifier of the widget and as
 /// value a vector of all his render instructions
+///
+/// The collection tracks the damage (i.e., the areas of the display whose render
+/// instructions changed) since the last frame drawn, so that renderers may redraw only
+/// those areas on [`draw_collection`](`Renderer::draw_collection`) instead of every
+/// render instruction. The event loop clears the damage after every frame drawn.
 pub struct RenderInstructionCollection {
     /// TreeMap of render instructions having the widget's identifier as key and the 
     /// vector of instructions to be rendered as value. The vector type was developed 
@@ -394,6 +2123,9 @@ pub struct RenderInstructionCollection {
     ///
     /// [`BTreeMap`]: https://doc.rust-lang.org/beta/std/collections/struct.BTreeMap.html
     pub pairs: BTreeMap<usize, Vec<RenderInstruction>>,
+    /// The areas covered by the render instructions inserted, replaced or removed
+    /// since the last frame drawn
+    damage: Vec<Rect>,
 }
 
 impl RenderInstructionCollection {
@@ -408,6 +2140,7 @@ impl RenderInstructionCollection {
         RenderInstructionCollection {
             // Instantiates a new empty BTreeMap
             pairs: BTreeMap::<usize, Vec<RenderInstruction>>::new(),
+            damage: Vec::new(),
         }
     }
 
@@ -420,9 +2153,13 @@ impl RenderInstructionCollection {
     /// * `id` - the identifier of the widget that needs to be rendered
     /// * `instructions` - the widget's instructions to the renderer knows how to draw it
     pub fn replace_or_insert(&mut self, id: usize, instructions: Vec<RenderInstruction>) {
+        // Both the areas drawn before and after are damaged
+        self.damage.extend(damage_of(&instructions));
         // The BTreeMap replaces the value if the key already exists, otherwise insert a 
         // new map entry
-        self.pairs.insert(id, instructions);
+        if let Some(previous) = self.pairs.insert(id, instructions) {
+            self.damage.extend(damage_of(&previous));
+        }
     }
 
     /// Remove the pair key-value from the render instructions collection
@@ -434,7 +2171,140 @@ impl RenderInstructionCollection {
     /// * `id` - the key of the entry to be removed
     pub fn remove(&mut self, id: usize) {
         // The BTreeMap removes the map entry if the key exists there
-        self.pairs.remove(&id);
+        if let Some(previous) = self.pairs.remove(&id) {
+            self.damage.extend(damage_of(&previous));
+        }
+    }
+
+    /// Flattens the collection into a single list of render instructions
+    ///
+    /// # Returns
+    /// The render instructions of every widget, in drawing order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn flatten(&self) -> Vec<RenderInstruction> {
+        self.pairs.values().flatten().cloned().collect()
+    }
+
+    /// Gets the damage (i.e., the areas of the display to be redrawn) since the last frame
+    /// drawn
+    ///
+    /// The event loop clears the damage once
+    /// [`draw_collection`](`Renderer::draw_collection`) returns, so the renderers redrawing
+    /// only the areas changed read it from the collection they are given.
+    ///
+    /// # Returns
+    /// The areas whose render instructions changed, with the overlapping ones merged,
+    /// or no areas if nothing changed
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn damage(&self) -> Vec<Rect> {
+        let mut merged: Vec<Rect> = Vec::new();
+        for area in self.damage.iter() {
+            if area.size.x <= 0. || area.size.y <= 0. {
+                continue;
+            }
+            // Absorb the areas overlapping this one, until none does
+            let mut area = *area;
+            while let Some(index) = merged.iter().position(|other| other.intersects(area)) {
+                area = area.union(merged.swap_remove(index));
+            }
+            merged.push(area);
+        }
+        merged
+    }
+
+    /// Clears the damage once it was drawn, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn clear_damage(&mut self) {
+        self.damage.clear();
+    }
+}
+
+/// Gets the areas of the display that render instructions may draw on, mapped by the
+/// transforms wrapping them, for internal use only
+///
+/// # Returns
+/// The bounds of the instructions drawing something, once transformed
+///
+/// # Arguments
+/// * `instructions` - the render instructions of a widget
+fn damage_of(instructions: &[RenderInstruction]) -> Vec<Rect> {
+    let mut transforms: Vec<Transform> = Vec::new();
+    let mut damage = Vec::new();
+    for instruction in instructions {
+        match instruction {
+            RenderInstruction::PushTransform {
+                translation,
+                rotation,
+                scale,
+            } => transforms.push(Transform::new(*translation, *rotation, *scale)),
+            RenderInstruction::PopTransform => {
+                transforms.pop();
+            }
+            _ => {
+                if let Some(bounds) = instruction.bounds() {
+                    // The whole display (e.g. `Clear`) stays the whole display
+                    if !bounds.size.x.is_finite() || !bounds.size.y.is_finite() {
+                        damage.push(bounds);
+                        continue;
+                    }
+                    // The innermost transform is applied first
+                    damage.push(
+                        transforms
+                            .iter()
+                            .rev()
+                            .fold(bounds, |bounds, transform| transform.apply_rect(bounds)),
+                    );
+                }
+            }
+        }
+    }
+    damage
+}
+
+/// State kept by the [`event_loop`](`Renderer::event_loop`) across frames
+pub struct EventLoopState {
+    /// The machine giving the widgets their identifiers
+    pub id_machine: IDMachine,
+    /// The collection of instructions to be rendered
+    pub render_instruction_collection: Weak<RefCell<RenderInstructionCollection>>,
+    /// The collection of widgets to be rendered on absolute positions
+    pub absolute_widget_collection: Weak<RefCell<AbsoluteWidgetCollection>>,
+    /// The shared state of the event loop (e.g. frame callbacks, idle policy, panic guard)
+    pub context: Weak<RefCell<Context>>,
+}
+
+impl EventLoopState {
+    /// Creates a new `EventLoopState`
+    ///
+    /// # Returns
+    /// The state of the event loop created
+    ///
+    /// # Arguments
+    /// * `id_machine` - the machine giving the widgets their identifiers
+    /// * `render_instruction_collection` - collection of instructions to be rendered
+    /// * `absolute_widget_collection` - collection of widgets to be rendered on absolute positions
+    /// * `context` - shared state of the event loop (e.g. frame callbacks, idle policy, panic guard)
+    pub fn new(
+        id_machine: IDMachine,
+        render_instruction_collection: Weak<RefCell<RenderInstructionCollection>>,
+        absolute_widget_collection: Weak<RefCell<AbsoluteWidgetCollection>>,
+        context: Weak<RefCell<Context>>,
+    ) -> EventLoopState {
+        EventLoopState {
+            id_machine,
+            render_instruction_collection,
+            absolute_widget_collection,
+            context,
+        }
     }
 }
 
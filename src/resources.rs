@@ -0,0 +1,243 @@
+//! Contains the registry of the images and fonts shared by the widgets, loaded once by
+//! the renderer and referred to by cheap handles instead of paths.
+//!
+//! The resources are registered on the [`Context`](`crate::context::Context`)'s
+//! [`ResourceRegistry`]. Before drawing the next frame, the event loop hands the resources
+//! registered since the last frame to the renderer (see
+//! [`load_image`](`crate::renderer::Renderer::load_image`) and
+//! [`load_font`](`crate::renderer::Renderer::load_font`)), which may decode and cache
+//! them. The widgets then draw them with the
+//! [`DrawImageHandle`](`crate::renderer::RenderInstruction::DrawImageHandle`) and
+//! [`DrawTextHandle`](`crate::renderer::RenderInstruction::DrawTextHandle`) instructions,
+//! copying a handle instead of allocating a path on every rebuild.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::renderer::ImageSource;
+//! use hyber::resources::ResourceRegistry;
+//!
+//! let mut resources = ResourceRegistry::new();
+//! let logo = resources.register_image(ImageSource::new(String::from("logo.png")));
+//!
+//! // Registering the same image again gives the same handle
+//! assert_eq!(logo, resources.register_image(ImageSource::new(String::from("logo.png"))));
+//!
+//! let font = resources.register_font(String::from("Inter"), String::from("inter.ttf"));
+//! assert_eq!(resources.font(font).unwrap().family(), "Inter");
+//! ```
+
+use crate::renderer::ImageSource;
+use crate::util::ImageData;
+
+use std::rc::Rc;
+
+/// Handle of an image registered on a [`ResourceRegistry`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageHandle(pub(crate) usize);
+
+/// Handle of a font registered on a [`ResourceRegistry`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontHandle(pub(crate) usize);
+
+/// Image registered on a [`ResourceRegistry`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageResource {
+    /// Image read from a file, in one or more resolutions
+    File(ImageSource),
+    /// Image held in memory, shared with the registry instead of being copied
+    Data(Rc<ImageData>),
+}
+
+impl ImageResource {
+    /// Checks if two resources are the same image, for internal use only
+    ///
+    /// # Returns
+    /// True if both read the same files or share the same pixels, false otherwise
+    ///
+    /// # Arguments
+    /// * `other` - the resource to be compared with
+    fn is_same(&self, other: &ImageResource) -> bool {
+        match (self, other) {
+            (ImageResource::File(source), ImageResource::File(other)) => source == other,
+            // Comparing the pixels would cost as much as loading the image twice
+            (ImageResource::Data(image), ImageResource::Data(other)) => Rc::ptr_eq(image, other),
+            _ => false,
+        }
+    }
+}
+
+impl From<ImageSource> for ImageResource {
+    fn from(source: ImageSource) -> Self {
+        ImageResource::File(source)
+    }
+}
+
+impl From<Rc<ImageData>> for ImageResource {
+    fn from(image: Rc<ImageData>) -> Self {
+        ImageResource::Data(image)
+    }
+}
+
+/// Font file registered on a [`ResourceRegistry`] under a family name
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FontResource {
+    /// The font's family name
+    family: String,
+    /// The path of the font's file
+    path: String,
+}
+
+impl FontResource {
+    /// Gets the font's family name
+    ///
+    /// # Returns
+    /// The family name the font was registered under
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    /// Gets the path of the font's file
+    ///
+    /// # Returns
+    /// The path of the font's file
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Registry of the images and fonts loaded once by the renderer, having their
+/// handle as index
+#[derive(Clone, Default)]
+pub struct ResourceRegistry {
+    /// The images registered
+    images: Vec<ImageResource>,
+    /// The number of images already loaded by the renderer
+    loaded_images: usize,
+    /// The fonts registered
+    fonts: Vec<FontResource>,
+    /// The number of fonts already loaded by the renderer
+    loaded_fonts: usize,
+}
+
+impl ResourceRegistry {
+    /// Creates a new `ResourceRegistry`
+    ///
+    /// # Returns
+    /// The registry created, with no resources
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> ResourceRegistry {
+        ResourceRegistry::default()
+    }
+
+    /// Registers an image, such that the renderer loads it once
+    ///
+    /// # Returns
+    /// The handle of the image, the same as before if it was already registered
+    ///
+    /// # Arguments
+    /// * `image` - the image to be registered (e.g. an [`ImageSource`] or an `Rc<ImageData>`)
+    pub fn register_image<I: Into<ImageResource>>(&mut self, image: I) -> ImageHandle {
+        let image = image.into();
+        if let Some(index) = self.images.iter().position(|other| other.is_same(&image)) {
+            return ImageHandle(index);
+        }
+        self.images.push(image);
+        ImageHandle(self.images.len() - 1)
+    }
+
+    /// Gets an image registered
+    ///
+    /// # Returns
+    /// The image, or `None` if no image was registered with the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the image
+    pub fn image(&self, handle: ImageHandle) -> Option<&ImageResource> {
+        self.images.get(handle.0)
+    }
+
+    /// Registers a font file under a family name, such that the renderer loads it once
+    ///
+    /// # Returns
+    /// The handle of the font, the same as before if it was already registered
+    ///
+    /// # Arguments
+    /// * `family` - the font's family name
+    /// * `path` - the path of the font's file
+    pub fn register_font(&mut self, family: String, path: String) -> FontHandle {
+        let font = FontResource { family, path };
+        if let Some(index) = self.fonts.iter().position(|other| *other == font) {
+            return FontHandle(index);
+        }
+        self.fonts.push(font);
+        FontHandle(self.fonts.len() - 1)
+    }
+
+    /// Gets a font registered
+    ///
+    /// # Returns
+    /// The font, or `None` if no font was registered with the handle
+    ///
+    /// # Arguments
+    /// * `handle` - the handle of the font
+    pub fn font(&self, handle: FontHandle) -> Option<&FontResource> {
+        self.fonts.get(handle.0)
+    }
+
+    /// Gets the handle of a font registered under a family name
+    ///
+    /// # Returns
+    /// The handle of the first font registered under the family, or `None` if none was
+    ///
+    /// # Arguments
+    /// * `family` - the font's family name
+    pub fn font_by_family(&self, family: &str) -> Option<FontHandle> {
+        self.fonts
+            .iter()
+            .position(|font| font.family == family)
+            .map(FontHandle)
+    }
+
+    /// Takes the images registered and not loaded by the renderer yet, for internal use only
+    ///
+    /// # Returns
+    /// The handle and the image of every image to be loaded
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_unloaded_images(&mut self) -> Vec<(ImageHandle, ImageResource)> {
+        let unloaded = self.images[self.loaded_images..]
+            .iter()
+            .enumerate()
+            .map(|(index, image)| (ImageHandle(self.loaded_images + index), image.clone()))
+            .collect();
+        self.loaded_images = self.images.len();
+        unloaded
+    }
+
+    /// Takes the fonts registered and not loaded by the renderer yet, for internal use only
+    ///
+    /// # Returns
+    /// The handle and the font of every font to be loaded
+    ///
+    /// # Arguments
+    /// No arguments
+    pub(crate) fn take_unloaded_fonts(&mut self) -> Vec<(FontHandle, FontResource)> {
+        let unloaded = self.fonts[self.loaded_fonts..]
+            .iter()
+            .enumerate()
+            .map(|(index, font)| (FontHandle(self.loaded_fonts + index), font.clone()))
+            .collect();
+        self.loaded_fonts = self.fonts.len();
+        unloaded
+    }
+}
@@ -0,0 +1,273 @@
+//! Contains the rubber band selection (i.e., selecting widgets by dragging a rectangle
+//! over them) shared by the container widgets.
+//!
+//! Containers (e.g. a [`ListViewWidget`](`crate::widget::list_view::ListViewWidget`) or a
+//! [`GridViewWidget`](`crate::widget::grid_view::GridViewWidget`)) embed a
+//! [`RubberBandSelection`], feed it the events they receive and draw its
+//! [`overlay_recipe`](`RubberBandSelection::overlay_recipe`) over their children.
+
+use crate::event::{Event, Mouse, MouseButton, Window};
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::Widget;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Distance the cursor must move with the left button held to start drawing the rectangle
+const DRAG_THRESHOLD: f64 = 4.;
+
+/// Thickness of the selection rectangle's border
+const BORDER_THICKNESS: f64 = 1.;
+
+/// Rubber band selection of a container's children
+///
+/// Dragging with the left button from the container's empty space (i.e., not over
+/// any child) draws a translucent rectangle. When the button is released, the
+/// children intersecting the rectangle are marked as selected (see
+/// [`Widget::set_selected`]) and the others are unselected. The drag keeps being
+/// tracked while the cursor is outside the container, clamping the rectangle to it.
+#[derive(Clone)]
+pub struct RubberBandSelection {
+    /// The message to be handled when the selection changes, along with the indexes
+    /// of the selected children when it is a [`ValueChanged<Vec<usize>>`](`ValueChanged`)
+    on_change: Option<Box<dyn Message>>,
+    /// The rectangle's fill color
+    fill_color: Color,
+    /// The rectangle's border color
+    border_color: Color,
+    /// The last position of the cursor
+    cursor: Vector2D,
+    /// The position where the left button was pressed on the empty space, if it is held
+    origin: Option<Vector2D>,
+    /// Whether the cursor moved far enough from the origin to draw the rectangle
+    is_dragging: bool,
+    /// The indexes of the selected children
+    selected: Vec<usize>,
+}
+
+impl RubberBandSelection {
+    /// Creates a new `RubberBandSelection`
+    ///
+    /// # Returns
+    /// The rubber band selection created, with no children selected
+    ///
+    /// # Arguments
+    /// * `on_change` - the message to be handled when the selection changes
+    /// * `fill_color` - the color to be assigned to the rectangle's fill, usually translucent
+    /// * `border_color` - the color to be assigned to the rectangle's border
+    pub fn new(
+        on_change: Option<Box<dyn Message>>,
+        fill_color: Color,
+        border_color: Color,
+    ) -> RubberBandSelection {
+        RubberBandSelection {
+            on_change,
+            fill_color,
+            border_color,
+            cursor: Vector2D::new(0., 0.),
+            origin: None,
+            is_dragging: false,
+            selected: Vec::new(),
+        }
+    }
+
+    /// Gets the indexes of the selected children
+    ///
+    /// # Returns
+    /// The indexes of the children selected on the last release, in ascending order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    /// Checks whether the rectangle is being drawn
+    ///
+    /// # Returns
+    /// True, if the left button is held and the cursor moved away from the origin, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_dragging(&self) -> bool {
+        self.is_dragging
+    }
+
+    /// Updates the selection according to an event received by the container
+    ///
+    /// # Returns
+    /// True, if the rectangle changed and the container's overlay must be redrawn, false otherwise
+    ///
+    /// # Arguments
+    /// * `event` - the event received by the container
+    /// * `area` - the container's area
+    /// * `children` - the container's children
+    /// * `messages` - queue of messages
+    pub fn on_event(
+        &mut self,
+        event: Event,
+        area: Rect,
+        children: &[Weak<RefCell<dyn Widget>>],
+        messages: &mut Queue<Box<dyn Message>>,
+    ) -> bool {
+        match event {
+            Event::Mouse(Mouse::CursorMoved { x, y }) => {
+                self.cursor = Vector2D::new(x as f64, y as f64);
+                match self.origin {
+                    Some(origin) if !self.is_dragging => {
+                        let distance = self.cursor - origin;
+                        self.is_dragging =
+                            distance.x.abs() > DRAG_THRESHOLD || distance.y.abs() > DRAG_THRESHOLD;
+                        self.is_dragging
+                    }
+                    Some(_) => true,
+                    None => false,
+                }
+            }
+            Event::Mouse(Mouse::ButtonPressed(MouseButton::Left))
+                if area.contains(self.cursor) && !is_over_child(children, self.cursor) =>
+            {
+                self.origin = Some(self.cursor);
+                false
+            }
+            Event::Mouse(Mouse::ButtonReleased(MouseButton::Left)) if self.origin.is_some() => {
+                if self.is_dragging {
+                    let band = self.band(area);
+                    self.select(event, band, children, messages);
+                }
+                self.origin = None;
+                std::mem::replace(&mut self.is_dragging, false)
+            }
+            // The release may never arrive when the window loses the focus
+            Event::Window(Window::Focused(false)) if self.origin.is_some() => {
+                self.origin = None;
+                std::mem::replace(&mut self.is_dragging, false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Gets the render instructions of the rectangle, to be drawn over the container's children
+    ///
+    /// # Returns
+    /// The rectangle's fill and border, or no instructions if it is not being drawn
+    ///
+    /// # Arguments
+    /// * `area` - the container's area
+    pub fn overlay_recipe(&self, area: Rect) -> Vec<RenderInstruction> {
+        if !self.is_dragging {
+            return Vec::new();
+        }
+        let band = self.band(area);
+        let (point, size) = (band.position, band.size);
+        let thickness = BORDER_THICKNESS.min(size.x / 2.).min(size.y / 2.);
+        let rect = |point: Vector2D, size: Vector2D, color: Color| RenderInstruction::DrawRect {
+            point,
+            size,
+            color,
+            clip_point: area.position,
+            clip_size: area.size,
+            blend_mode: BlendMode::Normal,
+        };
+        vec![
+            rect(point, size, self.fill_color),
+            rect(point, Vector2D::new(size.x, thickness), self.border_color),
+            rect(
+                Vector2D::new(point.x, point.y + size.y - thickness),
+                Vector2D::new(size.x, thickness),
+                self.border_color,
+            ),
+            rect(point, Vector2D::new(thickness, size.y), self.border_color),
+            rect(
+                Vector2D::new(point.x + size.x - thickness, point.y),
+                Vector2D::new(thickness, size.y),
+                self.border_color,
+            ),
+        ]
+    }
+
+    /// Gets the rectangle between the origin and the cursor, clamped to the container's
+    /// area, for internal use only
+    ///
+    /// # Returns
+    /// The rectangle dragged
+    ///
+    /// # Arguments
+    /// * `area` - the container's area
+    fn band(&self, area: Rect) -> Rect {
+        let origin = self.origin.unwrap_or(self.cursor);
+        let end = area.position + area.size;
+        let clamp = |point: Vector2D| {
+            Vector2D::new(
+                point.x.max(area.position.x).min(end.x),
+                point.y.max(area.position.y).min(end.y),
+            )
+        };
+        let (a, b) = (clamp(origin), clamp(self.cursor));
+        let start = Vector2D::new(a.x.min(b.x), a.y.min(b.y));
+        Rect::new(
+            start,
+            Vector2D::new(a.x.max(b.x) - start.x, a.y.max(b.y) - start.y),
+        )
+    }
+
+    /// Marks the children intersecting the rectangle as selected and the others as not
+    /// selected, handling the message if the selection changed, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that ended the drag
+    /// * `band` - the rectangle dragged
+    /// * `children` - the container's children
+    /// * `messages` - queue of messages
+    fn select(
+        &mut self,
+        event: Event,
+        band: Rect,
+        children: &[Weak<RefCell<dyn Widget>>],
+        messages: &mut Queue<Box<dyn Message>>,
+    ) {
+        let mut selected = Vec::new();
+        for (index, value) in children.iter().enumerate() {
+            if let Some(child) = value.upgrade() {
+                let mut child = child.borrow_mut();
+                let geometry = Rect::new(child.position(), child.size());
+                let is_selected = band.intersects(geometry);
+                child.set_selected(is_selected);
+                if is_selected {
+                    selected.push(index);
+                }
+            }
+        }
+        if selected == self.selected {
+            return;
+        }
+        self.selected = selected;
+        if let Some(mut message) = self.on_change.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<Vec<usize>>>() {
+                message.value = self.selected.clone();
+            }
+            messages.enqueue(message);
+        }
+    }
+}
+
+/// Checks whether a point is over any of a container's children, for internal use only
+///
+/// # Returns
+/// True, if the point is inside the area of a child, false otherwise
+///
+/// # Arguments
+/// * `children` - the container's children
+/// * `point` - the point to be checked
+fn is_over_child(children: &[Weak<RefCell<dyn Widget>>], point: Vector2D) -> bool {
+    children.iter().filter_map(Weak::upgrade).any(|child| {
+        let mut child = child.borrow_mut();
+        Rect::new(child.position(), child.size()).contains(point)
+    })
+}
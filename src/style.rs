@@ -0,0 +1,429 @@
+//! Contains the theme of the widgets (i.e., their colors, font sizes, border thicknesses
+//! and paddings), so that the look of an application is switched at once (e.g. between
+//! light and dark modes).
+//!
+//! The [`Theme`] is set on the [`RootWidget`](`crate::widget::root::RootWidget`) with
+//! [`set_theme`](`crate::widget::Widget::set_theme`), which cascades it to every widget
+//! of the tree and marks them dirty. Widgets created afterwards with their `Default`
+//! read the [`current_theme`], and each widget may override some properties of the
+//! theme with its own [`Style`].
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::style::{self, Style, Theme};
+//! use hyber::util::Color;
+//! use hyber::widget::checkbox::CheckBoxWidget;
+//! use hyber::widget::root::RootWidget;
+//! use hyber::widget::Widget;
+//!
+//! let mut root = RootWidget::default();
+//! root.set_theme(&Theme::dark());
+//! assert_eq!(style::current_theme(), Theme::dark());
+//!
+//! // The checkbox keeps its red accent whatever the theme
+//! let red = Color::from_hex(0xfff44336);
+//! let checkbox = CheckBoxWidget::default().with_style(Style::new().with_accent_color(red));
+//! ```
+
+use crate::util::Color;
+use crate::widget::{all_children, Widget};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Colors of a theme, by the role they play on the widgets
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThemePalette {
+    /// The color behind every widget (e.g. the root's background)
+    pub background: Color,
+    /// The background color of the widgets (e.g. panels, text boxes and checkboxes)
+    pub surface: Color,
+    /// The color of the texts and icons
+    pub foreground: Color,
+    /// The color of secondary content (e.g. disabled days or slider tracks)
+    pub muted: Color,
+    /// The color of the active parts of the widgets (e.g. checked boxes or progress)
+    pub accent: Color,
+    /// The color of the borders
+    pub border: Color,
+    /// The background color of the content selected (e.g. a day on a calendar)
+    pub selection: Color,
+}
+
+/// Styling applied to the render instructions of every disabled widget
+///
+/// Widgets only need to report whether they are disabled, through
+/// [`is_disabled`](`crate::widget::Widget::is_disabled`), for their colors
+/// to be consistently adjusted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisabledStyle {
+    /// How much the colors are desaturated, from 0 (unchanged) to 1 (fully gray)
+    pub desaturation: f64,
+    /// The multiplier applied to the transparency of the colors, from 0 to 1
+    pub opacity: f64,
+}
+
+impl Default for DisabledStyle {
+    fn default() -> Self {
+        DisabledStyle {
+            desaturation: 1.,
+            opacity: 0.5,
+        }
+    }
+}
+
+/// Theme of the widgets: their colors, font sizes, border thicknesses and paddings
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// The theme's colors
+    pub palette: ThemePalette,
+    /// The font size of the texts
+    pub font_size: usize,
+    /// The font size of the headings (e.g. titles)
+    pub heading_font_size: usize,
+    /// The thickness of the borders
+    pub border_thickness: f64,
+    /// The space between the edges of the widgets and their content
+    pub padding: f64,
+    /// The styling applied to the disabled widgets
+    pub disabled: DisabledStyle,
+    /// The font families used, in order, for the glyphs missing from a text's font (e.g.
+    /// to draw other scripts or emoji)
+    pub font_fallbacks: Vec<String>,
+}
+
+impl Theme {
+    /// Creates the light theme, used by default
+    ///
+    /// # Returns
+    /// The light theme, with dark texts on light backgrounds
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn light() -> Theme {
+        Theme {
+            palette: ThemePalette {
+                background: Color::from_hex(0xffffffff),
+                surface: Color::from_hex(0xffffffff),
+                foreground: Color::from_hex(0xff000000),
+                muted: Color::from_hex(0xffc0c0c0),
+                accent: Color::from_hex(0xff0078d7),
+                border: Color::from_hex(0xff000000),
+                selection: Color::from_hex(0xff90caf9),
+            },
+            font_size: 14,
+            heading_font_size: 20,
+            border_thickness: 1.,
+            padding: 10.,
+            disabled: DisabledStyle::default(),
+            font_fallbacks: Vec::new(),
+        }
+    }
+
+    /// Creates the dark theme
+    ///
+    /// # Returns
+    /// The dark theme, with light texts on dark backgrounds
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn dark() -> Theme {
+        Theme {
+            palette: ThemePalette {
+                background: Color::from_hex(0xff1e1e1e),
+                surface: Color::from_hex(0xff2d2d2d),
+                foreground: Color::from_hex(0xffe0e0e0),
+                muted: Color::from_hex(0xff6e6e6e),
+                accent: Color::from_hex(0xff4fc3f7),
+                border: Color::from_hex(0xff8a8a8a),
+                selection: Color::from_hex(0xff264f78),
+            },
+            ..Theme::light()
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+/// Properties of a widget overriding those of the theme, kept when the theme changes
+///
+/// Every property is optional, the ones not set being read from the theme. The
+/// properties a widget is given explicitly (e.g. the colors passed to its constructor or
+/// to its fluent setters) are recorded on its style, so that no theme overwrites them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Style {
+    /// The widget's background color, overriding the background and surface colors
+    pub background_color: Option<Color>,
+    /// The widget's foreground color (i.e., text color)
+    pub foreground_color: Option<Color>,
+    /// The widget's accent color
+    pub accent_color: Option<Color>,
+    /// The widget's border color
+    pub border_color: Option<Color>,
+    /// The widget's muted color (i.e., color of its secondary content)
+    pub muted_color: Option<Color>,
+    /// The widget's selection color
+    pub selection_color: Option<Color>,
+    /// The widget's font size
+    pub font_size: Option<usize>,
+    /// The widget's border thickness
+    pub border_thickness: Option<f64>,
+    /// The widget's padding
+    pub padding: Option<f64>,
+}
+
+impl Style {
+    /// Creates a new `Style`
+    ///
+    /// # Returns
+    /// The style created, overriding no property of the theme
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Sets the style's background color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color overriding the theme's
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.background_color = Some(background_color);
+        self
+    }
+
+    /// Sets the style's foreground color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the foreground color assigned
+    ///
+    /// # Arguments
+    /// * `foreground_color` - the foreground color overriding the theme's
+    pub fn with_foreground_color(mut self, foreground_color: Color) -> Self {
+        self.foreground_color = Some(foreground_color);
+        self
+    }
+
+    /// Sets the style's accent color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the accent color assigned
+    ///
+    /// # Arguments
+    /// * `accent_color` - the accent color overriding the theme's
+    pub fn with_accent_color(mut self, accent_color: Color) -> Self {
+        self.accent_color = Some(accent_color);
+        self
+    }
+
+    /// Sets the style's border color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the border color assigned
+    ///
+    /// # Arguments
+    /// * `border_color` - the border color overriding the theme's
+    pub fn with_border_color(mut self, border_color: Color) -> Self {
+        self.border_color = Some(border_color);
+        self
+    }
+
+    /// Sets the style's muted color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the muted color assigned
+    ///
+    /// # Arguments
+    /// * `muted_color` - the muted color overriding the theme's
+    pub fn with_muted_color(mut self, muted_color: Color) -> Self {
+        self.muted_color = Some(muted_color);
+        self
+    }
+
+    /// Sets the style's selection color, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the selection color assigned
+    ///
+    /// # Arguments
+    /// * `selection_color` - the selection color overriding the theme's
+    pub fn with_selection_color(mut self, selection_color: Color) -> Self {
+        self.selection_color = Some(selection_color);
+        self
+    }
+
+    /// Sets the style's font size, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size overriding the theme's
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Sets the style's border thickness, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the border thickness assigned
+    ///
+    /// # Arguments
+    /// * `border_thickness` - the border thickness overriding the theme's
+    pub fn with_border_thickness(mut self, border_thickness: f64) -> Self {
+        self.border_thickness = Some(border_thickness);
+        self
+    }
+
+    /// Sets the style's padding, returning the style (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The style with the padding assigned
+    ///
+    /// # Arguments
+    /// * `padding` - the padding overriding the theme's
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Overrides the properties of a theme with the ones set on the style
+    ///
+    /// # Returns
+    /// The theme with the style's properties
+    ///
+    /// # Arguments
+    /// * `theme` - the theme to be overridden
+    pub fn apply(&self, theme: &Theme) -> Theme {
+        let mut theme = theme.clone();
+        if let Some(background_color) = self.background_color {
+            theme.palette.background = background_color;
+            theme.palette.surface = background_color;
+        }
+        if let Some(foreground_color) = self.foreground_color {
+            theme.palette.foreground = foreground_color;
+        }
+        if let Some(accent_color) = self.accent_color {
+            theme.palette.accent = accent_color;
+        }
+        if let Some(border_color) = self.border_color {
+            theme.palette.border = border_color;
+        }
+        if let Some(muted_color) = self.muted_color {
+            theme.palette.muted = muted_color;
+        }
+        if let Some(selection_color) = self.selection_color {
+            theme.palette.selection = selection_color;
+        }
+        theme.font_size = self.font_size.unwrap_or(theme.font_size);
+        theme.border_thickness = self.border_thickness.unwrap_or(theme.border_thickness);
+        theme.padding = self.padding.unwrap_or(theme.padding);
+        theme
+    }
+
+    /// Overrides the properties of the style with the ones set on another style
+    ///
+    /// # Returns
+    /// The style with the properties set on both styles, those of `style` first
+    ///
+    /// # Arguments
+    /// * `style` - the style whose properties are kept over the ones of this style
+    pub fn merge(&self, style: &Style) -> Style {
+        Style {
+            background_color: style.background_color.or(self.background_color),
+            foreground_color: style.foreground_color.or(self.foreground_color),
+            accent_color: style.accent_color.or(self.accent_color),
+            border_color: style.border_color.or(self.border_color),
+            muted_color: style.muted_color.or(self.muted_color),
+            selection_color: style.selection_color.or(self.selection_color),
+            font_size: style.font_size.or(self.font_size),
+            border_thickness: style.border_thickness.or(self.border_thickness),
+            padding: style.padding.or(self.padding),
+        }
+    }
+}
+
+thread_local! {
+    /// The theme last set on a root widget of the current thread
+    static THEME: RefCell<Theme> = RefCell::new(Theme::light());
+}
+
+/// Gets the theme last set on a root widget of the current thread, read by the
+/// widgets created with their `Default`
+///
+/// # Returns
+/// The current theme, or the light theme if none was set
+///
+/// # Arguments
+/// No arguments
+pub fn current_theme() -> Theme {
+    THEME.with(|theme| theme.borrow().clone())
+}
+
+/// Sets the theme read by the widgets created afterwards, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `theme` - the theme to be set
+pub(crate) fn set_current_theme(theme: &Theme) {
+    THEME.with(|current| *current.borrow_mut() = theme.clone());
+}
+
+/// Reads the theme last set on a root widget of the current thread without cloning it,
+/// for internal use only
+///
+/// # Returns
+/// The result of the function reading the theme
+///
+/// # Arguments
+/// * `read` - the function reading the current theme
+pub(crate) fn with_current_theme<R>(read: impl FnOnce(&Theme) -> R) -> R {
+    THEME.with(|theme| read(&theme.borrow()))
+}
+
+/// Applies a theme to a list of widgets and all their descendants, marking them dirty
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `children` - the widgets to be themed
+/// * `theme` - the theme to be applied
+pub fn cascade_theme(children: &[Weak<RefCell<dyn Widget>>], theme: &Theme) {
+    for child in children.iter().filter_map(Weak::upgrade) {
+        theme_tree(&child, theme);
+    }
+}
+
+/// Applies a theme to a widget and all its descendants, including their internal
+/// children, marking them dirty, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `widget` - the widget to be themed
+/// * `theme` - the theme to be applied
+fn theme_tree(widget: &Rc<RefCell<dyn Widget>>, theme: &Theme) {
+    let children = {
+        let mut widget = widget.borrow_mut();
+        widget.set_theme(theme);
+        widget.set_dirty(true);
+        all_children(&mut *widget)
+    };
+    for child in children.iter() {
+        theme_tree(child, theme);
+    }
+}
@@ -0,0 +1,537 @@
+//! Contains the table models feeding containers from records.
+//!
+//! A [`TableModel`] holds the data of a table (i.e., rows of cells, with named
+//! columns) apart from the widgets displaying it, and notifies its observers whenever
+//! it changes. The [`RecordModel`] is a table model of text cells, which can be read
+//! from CSV. A [`TableSource`] populates a container (e.g. a
+//! [`ListViewWidget`](`crate::widget::list_view::ListViewWidget`)) with a widget per
+//! row of a model, bound through an [`ItemTemplate`], optionally sorted by a column,
+//! and repopulates it once the model changes.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::table_model::{RecordModel, TableSource};
+//! use hyber::widget::label::LabelWidget;
+//! use hyber::widget::list_view::ListViewWidget;
+//! use hyber::widget::Widget;
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! let model = Rc::new(RefCell::new(RecordModel::from_csv("name,age\nBob,42\nAlice,7\n")));
+//! let mut source = TableSource::new(
+//!     model.clone(),
+//!     Rc::new(|row: &Vec<String>, _recycled| {
+//!         let text = row.join(" ");
+//!         Rc::new(RefCell::new(LabelWidget::default().with_text(text))) as Rc<RefCell<dyn Widget>>
+//!     }),
+//! );
+//!
+//! // Sorted by age, ascending
+//! source.set_sort(Some((1, true)));
+//! let mut list = ListViewWidget::default();
+//! source.populate(&mut list);
+//! assert_eq!(source.rows()[0][0], "Alice");
+//!
+//! // The source is stale once the model changes, and is refreshed when populating
+//! model.borrow_mut().push_row(vec![String::from("Carol"), String::from("30")]);
+//! assert!(source.is_stale());
+//! source.populate(&mut list);
+//! assert_eq!(list.get_children().len(), 3);
+//! ```
+
+use crate::context::Context;
+use crate::template::{ItemTemplate, ItemsSource};
+use crate::widget::Widget;
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::rc::{Rc, Weak};
+
+/// Change of a table model, notified to its observers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModelChange {
+    /// Every row may have changed (e.g. the rows were replaced)
+    Reset,
+    /// Rows were inserted
+    RowsInserted {
+        /// The index of the first row inserted
+        start: usize,
+        /// The number of rows inserted
+        count: usize,
+    },
+    /// Rows were removed
+    RowsRemoved {
+        /// The index of the first row removed
+        start: usize,
+        /// The number of rows removed
+        count: usize,
+    },
+    /// A cell changed
+    CellChanged {
+        /// The row of the cell
+        row: usize,
+        /// The column of the cell
+        column: usize,
+    },
+}
+
+/// Callback notified of the changes of a table model
+pub type ModelObserver = Rc<dyn Fn(&ModelChange)>;
+
+/// Data of a table, apart from the widgets displaying it
+pub trait TableModel {
+    /// Gets the number of rows
+    ///
+    /// # Returns
+    /// The number of rows of the table
+    ///
+    /// # Arguments
+    /// No arguments
+    fn row_count(&self) -> usize;
+
+    /// Gets the number of columns
+    ///
+    /// # Returns
+    /// The number of columns of the table
+    ///
+    /// # Arguments
+    /// No arguments
+    fn column_count(&self) -> usize;
+
+    /// Gets the text of a cell
+    ///
+    /// # Returns
+    /// The text of the cell, or an empty text if there is no such cell
+    ///
+    /// # Arguments
+    /// * `row` - the row of the cell
+    /// * `column` - the column of the cell
+    fn cell(&self, row: usize, column: usize) -> String;
+
+    /// Gets the name of a column (e.g. to be displayed on a header)
+    ///
+    /// # Returns
+    /// The name of the column, or an empty text if the columns are not named
+    ///
+    /// # Arguments
+    /// * `column` - the column
+    fn column_name(&self, _column: usize) -> String {
+        String::new()
+    }
+
+    /// Compares two rows by a column, to sort the table
+    ///
+    /// By default, the cells are compared as numbers if both are numbers, or as texts
+    /// otherwise.
+    ///
+    /// # Returns
+    /// The ordering of the first row relative to the second one
+    ///
+    /// # Arguments
+    /// * `first` - the first row compared
+    /// * `second` - the second row compared
+    /// * `column` - the column compared
+    fn compare(&self, first: usize, second: usize, column: usize) -> Ordering {
+        let first = self.cell(first, column);
+        let second = self.cell(second, column);
+        match (first.trim().parse::<f64>(), second.trim().parse::<f64>()) {
+            (Ok(first), Ok(second)) => first.total_cmp(&second),
+            _ => first.cmp(&second),
+        }
+    }
+
+    /// Adds an observer, notified whenever the table changes
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `observer` - the callback to be notified of the changes
+    fn observe(&mut self, observer: ModelObserver);
+}
+
+/// Table model of text cells (e.g. read from CSV)
+#[derive(Clone, Default)]
+pub struct RecordModel {
+    /// The names of the columns
+    columns: Vec<String>,
+    /// The rows, each with its cells
+    rows: Vec<Vec<String>>,
+    /// The callbacks notified of the changes
+    observers: Vec<ModelObserver>,
+}
+
+impl RecordModel {
+    /// Creates a new `RecordModel`, with no rows
+    ///
+    /// # Returns
+    /// The record model created
+    ///
+    /// # Arguments
+    /// * `columns` - the names of the columns
+    pub fn new(columns: Vec<String>) -> RecordModel {
+        RecordModel {
+            columns,
+            rows: Vec::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Creates a new `RecordModel` from CSV, whose first record names the columns
+    ///
+    /// The fields are separated by commas, and those containing commas, quotes or
+    /// line breaks are enclosed in quotes, with their quotes doubled.
+    ///
+    /// # Returns
+    /// The record model created
+    ///
+    /// # Arguments
+    /// * `csv` - the CSV text
+    pub fn from_csv(csv: &str) -> RecordModel {
+        let mut records = parse_csv(csv).into_iter();
+        let columns = records.next().unwrap_or_default();
+        RecordModel {
+            columns,
+            rows: records.collect(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Gets the rows
+    ///
+    /// # Returns
+    /// The rows, each with its cells
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Replaces every row
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `rows` - the rows, each with its cells
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.rows = rows;
+        self.notify(ModelChange::Reset);
+    }
+
+    /// Appends a row
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `row` - the cells of the row
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+        self.notify(ModelChange::RowsInserted {
+            start: self.rows.len() - 1,
+            count: 1,
+        });
+    }
+
+    /// Removes a row
+    ///
+    /// # Returns
+    /// The cells of the row removed, or `None` if there is no such row
+    ///
+    /// # Arguments
+    /// * `row` - the index of the row
+    pub fn remove_row(&mut self, row: usize) -> Option<Vec<String>> {
+        if row >= self.rows.len() {
+            return None;
+        }
+        let removed = self.rows.remove(row);
+        self.notify(ModelChange::RowsRemoved {
+            start: row,
+            count: 1,
+        });
+        Some(removed)
+    }
+
+    /// Sets the text of a cell, adding the cells missing on its row
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `row` - the row of the cell
+    /// * `column` - the column of the cell
+    /// * `text` - the text to be assigned to the cell
+    pub fn set_cell(&mut self, row: usize, column: usize, text: &str) {
+        if let Some(cells) = self.rows.get_mut(row) {
+            if cells.len() <= column {
+                cells.resize(column + 1, String::new());
+            }
+            cells[column] = String::from(text);
+            self.notify(ModelChange::CellChanged { row, column });
+        }
+    }
+
+    /// Notifies the observers of a change, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `change` - the change to be notified
+    fn notify(&self, change: ModelChange) {
+        for observer in self.observers.iter() {
+            observer(&change);
+        }
+    }
+}
+
+impl TableModel for RecordModel {
+    fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn column_count(&self) -> usize {
+        self.rows
+            .iter()
+            .map(Vec::len)
+            .chain(std::iter::once(self.columns.len()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn cell(&self, row: usize, column: usize) -> String {
+        self.rows
+            .get(row)
+            .and_then(|cells| cells.get(column))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn column_name(&self, column: usize) -> String {
+        self.columns.get(column).cloned().unwrap_or_default()
+    }
+
+    fn observe(&mut self, observer: ModelObserver) {
+        self.observers.push(observer);
+    }
+}
+
+/// Rows of a table model bound to widgets through a template, to populate a container
+///
+/// The table source observes its model, becoming stale whenever the model changes,
+/// and reads the rows again the next time it populates a container. Like an
+/// [`ItemsSource`], it owns the widgets it creates, so it must be kept alive while
+/// they are displayed.
+pub struct TableSource {
+    /// The model whose rows are displayed
+    model: Rc<RefCell<dyn TableModel>>,
+    /// The column the rows are sorted by, with whether the order is ascending
+    sort: Option<(usize, bool)>,
+    /// The rows' cells, in display order, bound to widgets
+    items: ItemsSource<Vec<String>>,
+    /// The index on the model of each row displayed
+    order: Vec<usize>,
+    /// Whether the model changed since the rows were read
+    is_stale: Rc<Cell<bool>>,
+}
+
+impl TableSource {
+    /// Creates a new `TableSource`, observing the model
+    ///
+    /// # Returns
+    /// The table source created
+    ///
+    /// # Arguments
+    /// * `model` - the model whose rows are displayed
+    /// * `template` - the callback to be used to bind each row's cells to a widget
+    pub fn new(
+        model: Rc<RefCell<dyn TableModel>>,
+        template: ItemTemplate<Vec<String>>,
+    ) -> TableSource {
+        let is_stale = Rc::new(Cell::new(true));
+        let observed = Rc::downgrade(&is_stale);
+        model.borrow_mut().observe(Rc::new(move |_: &ModelChange| {
+            if let Some(is_stale) = observed.upgrade() {
+                is_stale.set(true);
+            }
+        }));
+        TableSource {
+            model,
+            sort: None,
+            items: ItemsSource::new(template),
+            order: Vec::new(),
+            is_stale,
+        }
+    }
+
+    /// Sets the column the rows are sorted by
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `sort` - the column with whether the order is ascending, or `None` to keep the model's order
+    pub fn set_sort(&mut self, sort: Option<(usize, bool)>) {
+        self.sort = sort;
+        self.is_stale.set(true);
+    }
+
+    /// Gets the column the rows are sorted by
+    ///
+    /// # Returns
+    /// The column with whether the order is ascending, or `None` if the rows keep the
+    /// model's order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn sort(&self) -> Option<(usize, bool)> {
+        self.sort
+    }
+
+    /// Checks whether the model changed since the rows were read
+    ///
+    /// # Returns
+    /// True, if the rows are read again on the next refresh, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_stale(&self) -> bool {
+        self.is_stale.get()
+    }
+
+    /// Reads the rows of the model again, if stale, rebinding their widgets
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn refresh(&mut self) {
+        if !self.is_stale.replace(false) {
+            return;
+        }
+        let model = self.model.borrow();
+        let mut order: Vec<usize> = (0..model.row_count()).collect();
+        if let Some((column, is_ascending)) = self.sort {
+            order.sort_by(|first, second| {
+                let ordering = model.compare(*first, *second, column);
+                if is_ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+        let rows = order
+            .iter()
+            .map(|row| {
+                (0..model.column_count())
+                    .map(|column| model.cell(*row, column))
+                    .collect()
+            })
+            .collect();
+        drop(model);
+        self.items.set_items(rows);
+        self.order = order;
+    }
+
+    /// Gets the rows' cells, as read on the last refresh
+    ///
+    /// # Returns
+    /// The rows' cells, in display order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn rows(&self) -> &[Vec<String>] {
+        self.items.items()
+    }
+
+    /// Gets the index on the model of a row displayed (e.g. to edit the row clicked)
+    ///
+    /// # Returns
+    /// The index of the row on the model, or `None` if there is no such row
+    ///
+    /// # Arguments
+    /// * `index` - the index of the row, in display order
+    pub fn model_row(&self, index: usize) -> Option<usize> {
+        self.order.get(index).copied()
+    }
+
+    /// Refreshes the rows, if stale, and sets their widgets as a container's children, in order
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `container` - the widget to be populated (e.g. a list view)
+    pub fn populate(&mut self, container: &mut dyn Widget) {
+        self.refresh();
+        self.items.populate(container);
+    }
+
+    /// Keeps a container populated with the rows of a table source, repopulating it
+    /// before the frames following a change of the model
+    ///
+    /// # Returns
+    /// The identifier of the frame hook repopulating the container, used to remove it
+    ///
+    /// # Arguments
+    /// * `source` - the pointer to the table source
+    /// * `container` - the widget to be populated (e.g. a list view)
+    /// * `context` - the context whose frames repopulate the container
+    pub fn bind(
+        source: &Rc<RefCell<TableSource>>,
+        container: &Rc<RefCell<dyn Widget>>,
+        context: &mut Context,
+    ) -> usize {
+        source.borrow_mut().populate(&mut *container.borrow_mut());
+        let source = Rc::downgrade(source);
+        let container: Weak<RefCell<dyn Widget>> = Rc::downgrade(container);
+        context.add_before_frame_hook(Box::new(move |_| {
+            if let (Some(source), Some(container)) = (source.upgrade(), container.upgrade()) {
+                if source.borrow().is_stale() {
+                    source.borrow_mut().populate(&mut *container.borrow_mut());
+                }
+            }
+        }))
+    }
+}
+
+/// Parses CSV into records, for internal use only
+///
+/// # Returns
+/// The records, each with its fields
+///
+/// # Arguments
+/// * `csv` - the CSV text
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '"' if is_quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => is_quoted = !is_quoted,
+            ',' if !is_quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !is_quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            '\r' if !is_quoted => {}
+            character => field.push(character),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
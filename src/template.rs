@@ -0,0 +1,192 @@
+//! Contains the data context used to populate containers from a list of items.
+//!
+//! Instead of creating and updating their children by hand, containers (e.g. a
+//! [`ListViewWidget`](`crate::widget::list_view::ListViewWidget`) or a
+//! [`GridViewWidget`](`crate::widget::grid_view::GridViewWidget`)) are populated by an
+//! [`ItemsSource`], which binds each item's data to a widget through an [`ItemTemplate`].
+//! Widgets no longer needed are kept to be rebound to other items, so the same
+//! template serves both the containers holding every item and the virtualized ones
+//! (e.g. a [`SliverViewWidget`](`crate::widget::sliver_view::SliverViewWidget`)),
+//! which only bind the items being displayed.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::template::ItemsSource;
+//! use hyber::widget::label::LabelWidget;
+//! use hyber::widget::list_view::ListViewWidget;
+//! use hyber::widget::{Widget, WidgetRef};
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//!
+//! let mut source = ItemsSource::new(Rc::new(|name: &String, recycled| {
+//!     match recycled {
+//!         Some(widget) => {
+//!             widget.batch_update(|label: &mut LabelWidget| label.set_text(name.clone()));
+//!             widget
+//!         }
+//!         None => Rc::new(RefCell::new(LabelWidget::default().with_text(name.clone())))
+//!             as Rc<RefCell<dyn Widget>>,
+//!     }
+//! }));
+//!
+//! let mut list = ListViewWidget::default();
+//! source.set_items(vec![String::from("Alice"), String::from("Bob")]);
+//! source.populate(&mut list);
+//! assert_eq!(list.get_children().len(), 2);
+//!
+//! // The label bound to "Alice" is rebound to "Carol", instead of creating a new one
+//! source.set_items(vec![String::from("Carol")]);
+//! source.populate(&mut list);
+//! assert_eq!(list.get_children().len(), 1);
+//! ```
+
+use crate::widget::sliver_view::SliverDataSource;
+use crate::widget::Widget;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Callback that binds an item's data to a widget
+///
+/// It receives the item's data along with a widget previously created by the template
+/// and no longer bound to an item, if any. The template rebinds that widget (e.g. sets
+/// its text) and returns it, or creates a new widget when none is given.
+pub type ItemTemplate<T> =
+    Rc<dyn Fn(&T, Option<Rc<RefCell<dyn Widget>>>) -> Rc<RefCell<dyn Widget>>>;
+
+/// Items bound to widgets through a template, to populate a container
+///
+/// The items source owns the widgets it creates, since containers only hold weak
+/// pointers to their children, so it must be kept alive while they are displayed.
+pub struct ItemsSource<T> {
+    /// The callback that binds each item's data to a widget
+    template: ItemTemplate<T>,
+    /// The items' data
+    items: Vec<T>,
+    /// The widget bound to each item, if any, by index
+    widgets: Vec<Option<Rc<RefCell<dyn Widget>>>>,
+    /// The widgets no longer bound to an item, to be rebound
+    recycled: Vec<Rc<RefCell<dyn Widget>>>,
+}
+
+impl<T> ItemsSource<T> {
+    /// Creates a new `ItemsSource`, with no items
+    ///
+    /// # Returns
+    /// The items source created
+    ///
+    /// # Arguments
+    /// * `template` - the callback to be used to bind each item's data to a widget
+    pub fn new(template: ItemTemplate<T>) -> ItemsSource<T> {
+        ItemsSource {
+            template,
+            items: Vec::new(),
+            widgets: Vec::new(),
+            recycled: Vec::new(),
+        }
+    }
+
+    /// Sets the items' data, rebinding every widget bound so far to the item now at its index
+    ///
+    /// The widgets left over are kept to be rebound later, and the items without a
+    /// widget are bound when [`populate`](`ItemsSource::populate`) or
+    /// [`bind`](`ItemsSource::bind`) need them.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `items` - the items' data
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        let widgets = std::mem::take(&mut self.widgets);
+        for (index, widget) in widgets.into_iter().enumerate() {
+            match (widget, self.items.get(index)) {
+                (Some(widget), Some(item)) => {
+                    let widget = (self.template)(item, Some(widget));
+                    self.widgets.push(Some(widget));
+                }
+                (Some(widget), None) => self.recycled.push(widget),
+                (None, _) => self.widgets.push(None),
+            }
+        }
+        self.widgets.resize(self.items.len(), None);
+    }
+
+    /// Gets the items' data
+    ///
+    /// # Returns
+    /// The items' data, in order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Binds an item's data to a widget, if it is not bound yet, rebinding a widget no
+    /// longer needed when available
+    ///
+    /// # Returns
+    /// The widget bound to the item, or `None` if there is no item at the given index
+    ///
+    /// # Arguments
+    /// * `index` - the index of the item
+    pub fn bind(&mut self, index: usize) -> Option<Rc<RefCell<dyn Widget>>> {
+        let item = self.items.get(index)?;
+        if let Some(widget) = &self.widgets[index] {
+            return Some(widget.clone());
+        }
+        let widget = (self.template)(item, self.recycled.pop());
+        self.widgets[index] = Some(widget.clone());
+        Some(widget)
+    }
+
+    /// Unbinds the widget of an item that is no longer displayed (e.g. scrolled out of
+    /// a virtualized container), keeping it to be rebound to another item
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the item
+    pub fn recycle(&mut self, index: usize) {
+        if let Some(widget) = self.widgets.get_mut(index).and_then(Option::take) {
+            self.recycled.push(widget);
+        }
+    }
+
+    /// Binds every item and sets their widgets as a container's children, in order
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `container` - the widget to be populated (e.g. a list view or a grid view)
+    pub fn populate(&mut self, container: &mut dyn Widget) {
+        let children = (0..self.items.len())
+            .filter_map(|index| self.bind(index))
+            .map(|widget| Rc::downgrade(&widget))
+            .collect();
+        *container.get_children() = children;
+        container.set_dirty(true);
+    }
+}
+
+impl<T: 'static> ItemsSource<T> {
+    /// Creates a data source for a virtualized container, binding each item as it is
+    /// requested
+    ///
+    /// # Returns
+    /// The data source, which creates the widget of the item at a given index
+    ///
+    /// # Arguments
+    /// * `source` - the pointer to the items source
+    pub fn data_source(source: &Rc<RefCell<ItemsSource<T>>>) -> SliverDataSource {
+        let source = Rc::downgrade(source);
+        Rc::new(RefCell::new(move |index| {
+            source.upgrade()?.borrow_mut().bind(index)
+        }))
+    }
+}
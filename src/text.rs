@@ -0,0 +1,357 @@
+//! Contains the layout of texts on multiple lines.
+//!
+//! A [`RenderInstruction::DrawText`](`crate::renderer::RenderInstruction::DrawText`) only
+//! draws a single run of text from a point. The [`TextLayout`] breaks a text into lines,
+//! at its line breaks and, when wrapping, wherever the next word would not fit the width,
+//! and aligns the lines, so that widgets emit one `DrawText` per line laid out.
+//!
+//! Widgets measure texts with [`measure_text`], which uses the [`TextMetrics`] of the
+//! renderer's fonts once the event loop starts (see
+//! [`Renderer::text_metrics`](`crate::renderer::Renderer::text_metrics`)), or estimates
+//! the size of the texts from their font size otherwise.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::text::{TextAlignment, TextLayout};
+//!
+//! let layout = TextLayout::default()
+//!     .with_wrap(true)
+//!     .with_alignment(TextAlignment::Right);
+//! // Every glyph is 10 pixels wide
+//! let runs = layout.layout("lorem ipsum dolor", 10, 120., &|text: &str| {
+//!     text.chars().count() as f64 * 10.
+//! });
+//!
+//! assert_eq!(runs.len(), 2);
+//! assert_eq!(runs[0].text, "lorem ipsum");
+//! assert_eq!(runs[0].point.x, 10.);
+//! assert_eq!(runs[1].text, "dolor");
+//! assert_eq!(runs[1].point.y, 12.);
+//! ```
+
+use crate::util::Vector2D;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Width of a glyph relative to the font size, used to estimate the texts' width
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Callback measuring the size (width and height) of a single line of text with the
+/// given font size
+pub type TextMetrics = Rc<dyn Fn(&str, usize) -> Vector2D>;
+
+/// Horizontal alignment of the lines of a text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextAlignment {
+    /// The lines start at the left edge
+    #[default]
+    Left,
+    /// The lines are centered
+    Center,
+    /// The lines end at the right edge
+    Right,
+    /// The lines wrapped fill the whole width, by spreading their words, while the
+    /// last line of each paragraph starts at the left edge
+    Justify,
+}
+
+/// Run of text laid out, drawn by a single `DrawText`
+#[derive(Clone)]
+pub struct TextRun {
+    /// The run's text
+    pub text: String,
+
+    /// The run's starting point, relative to the left edge and to the baseline of the
+    /// first line
+    pub point: Vector2D,
+}
+
+/// Layout of a text on multiple lines, with wrapping and alignment
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextLayout {
+    /// How the lines are aligned
+    alignment: TextAlignment,
+
+    /// Whether the lines wider than the width are wrapped
+    is_wrapping: bool,
+
+    /// The distance between the baselines of consecutive lines, as a multiple of the font size
+    line_spacing: f64,
+}
+
+impl TextLayout {
+    /// Creates a new `TextLayout`
+    ///
+    /// # Returns
+    /// The text layout created
+    ///
+    /// # Arguments
+    /// * `alignment` - how the lines are aligned
+    /// * `is_wrapping` - whether the lines wider than the width are wrapped
+    /// * `line_spacing` - the distance between the baselines of consecutive lines, as a multiple of the font size
+    pub fn new(alignment: TextAlignment, is_wrapping: bool, line_spacing: f64) -> TextLayout {
+        TextLayout {
+            alignment,
+            is_wrapping,
+            line_spacing,
+        }
+    }
+
+    /// Gets how the lines are aligned
+    ///
+    /// # Returns
+    /// The alignment of the lines
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn alignment(&self) -> TextAlignment {
+        self.alignment
+    }
+
+    /// Checks whether the lines wider than the width are wrapped
+    ///
+    /// # Returns
+    /// True, if the lines are wrapped, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_wrapping(&self) -> bool {
+        self.is_wrapping
+    }
+
+    /// Gets the distance between the baselines of consecutive lines
+    ///
+    /// # Returns
+    /// The distance between the baselines of consecutive lines
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size of the text
+    pub fn line_height(&self, font_size: usize) -> f64 {
+        font_size as f64 * self.line_spacing
+    }
+
+    /// Breaks a text into lines, at its line breaks and, when wrapping, wherever the
+    /// next word would not fit the width
+    ///
+    /// Words wider than the width on their own are broken between their characters.
+    ///
+    /// # Returns
+    /// The lines, each with whether it ends a paragraph (i.e., it is not wrapped)
+    ///
+    /// # Arguments
+    /// * `text` - the text to be broken into lines
+    /// * `width` - the width available for the lines
+    /// * `measure` - the callback measuring the width of a text
+    pub fn lines(
+        &self,
+        text: &str,
+        width: f64,
+        measure: &dyn Fn(&str) -> f64,
+    ) -> Vec<(String, bool)> {
+        // Nothing fits a negative width, but every character still gets a line
+        let width = width.max(0.);
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            if !self.is_wrapping {
+                lines.push((String::from(paragraph), true));
+                continue;
+            }
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() {
+                    String::from(word)
+                } else {
+                    format!("{} {}", line, word)
+                };
+                if measure(&candidate) <= width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push((std::mem::take(&mut line), false));
+                }
+                // Break the words too wide on their own between their characters, taking
+                // at least one character per line
+                let mut rest = word;
+                while !rest.is_empty() && measure(rest) > width {
+                    let split = Self::fitting_prefix(rest, width, measure);
+                    lines.push((String::from(&rest[..split]), false));
+                    rest = &rest[split..];
+                }
+                line = String::from(rest);
+            }
+            lines.push((line, true));
+        }
+        lines
+    }
+
+    /// Lays out a text, breaking it into lines and aligning them within the width
+    ///
+    /// # Returns
+    /// The runs of text laid out: one per line, or one per word on the lines justified
+    ///
+    /// # Arguments
+    /// * `text` - the text to be laid out
+    /// * `font_size` - the font size of the text
+    /// * `width` - the width available for the lines
+    /// * `measure` - the callback measuring the width of a text
+    pub fn layout(
+        &self,
+        text: &str,
+        font_size: usize,
+        width: f64,
+        measure: &dyn Fn(&str) -> f64,
+    ) -> Vec<TextRun> {
+        let width = width.max(0.);
+        let mut runs = Vec::new();
+        let lines = self.lines(text, width, measure);
+        for (index, (line, is_paragraph_end)) in lines.into_iter().enumerate() {
+            let y = index as f64 * self.line_height(font_size);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match self.alignment {
+                TextAlignment::Justify if !is_paragraph_end && words.len() > 1 => {
+                    // Spread the words, such that the last one ends at the right edge
+                    let words_width: f64 = words.iter().map(|word| measure(word)).sum();
+                    let gap = (width - words_width) / (words.len() - 1) as f64;
+                    let mut x = 0.;
+                    for word in words {
+                        runs.push(TextRun {
+                            text: String::from(word),
+                            point: Vector2D::new(x, y),
+                        });
+                        x += measure(word) + gap;
+                    }
+                }
+                alignment => {
+                    let free = width - measure(&line);
+                    let x = match alignment {
+                        TextAlignment::Center => free * 0.5,
+                        TextAlignment::Right => free,
+                        _ => 0.,
+                    };
+                    runs.push(TextRun {
+                        text: line,
+                        point: Vector2D::new(x, y),
+                    });
+                }
+            }
+        }
+        runs
+    }
+
+    /// Gets the length, in bytes, of the longest start of a text fitting a width, for
+    /// internal use only
+    ///
+    /// # Returns
+    /// The length of the start fitting the width, with at least one character
+    ///
+    /// # Arguments
+    /// * `text` - the text to be measured
+    /// * `width` - the width available
+    /// * `measure` - the callback measuring the width of a text
+    fn fitting_prefix(text: &str, width: f64, measure: &dyn Fn(&str) -> f64) -> usize {
+        let mut ends = text.char_indices().map(|(index, _)| index).skip(1);
+        let mut fitting = ends.next().unwrap_or(text.len());
+        for end in ends {
+            if measure(&text[..end]) > width {
+                break;
+            }
+            fitting = end;
+        }
+        fitting
+    }
+
+    /// Sets how the lines are aligned, returning the text layout (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The text layout with the alignment assigned
+    ///
+    /// # Arguments
+    /// * `alignment` - how the lines are aligned
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets whether the lines are wrapped, returning the text layout (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The text layout with whether the lines are wrapped assigned
+    ///
+    /// # Arguments
+    /// * `is_wrapping` - whether the lines wider than the width are wrapped
+    pub fn with_wrap(mut self, is_wrapping: bool) -> Self {
+        self.is_wrapping = is_wrapping;
+        self
+    }
+
+    /// Sets the distance between the baselines of consecutive lines, returning the text
+    /// layout (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The text layout with the line spacing assigned
+    ///
+    /// # Arguments
+    /// * `line_spacing` - the distance between the baselines of consecutive lines, as a multiple of the font size
+    pub fn with_line_spacing(mut self, line_spacing: f64) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        TextLayout::new(TextAlignment::Left, false, 1.2)
+    }
+}
+
+thread_local! {
+    /// The metrics measuring the texts of the widgets of the current thread, if any
+    static TEXT_METRICS: RefCell<Option<TextMetrics>> = const { RefCell::new(None) };
+}
+
+/// Sets the metrics measuring the texts of the widgets of the current thread
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `text_metrics` - the metrics of the renderer's fonts, or `None` to estimate the texts' size
+pub fn set_text_metrics(text_metrics: Option<TextMetrics>) {
+    TEXT_METRICS.with(|current| *current.borrow_mut() = text_metrics);
+}
+
+/// Measures a single line of text with the metrics of the current thread
+///
+/// # Returns
+/// The size (width and height) of the text, measured by the metrics set or, if none,
+/// estimated from the font size
+///
+/// # Arguments
+/// * `string` - the text to be measured
+/// * `font_size` - the font size of the text
+pub fn measure_text(string: &str, font_size: usize) -> Vector2D {
+    let text_metrics = TEXT_METRICS.with(|current| current.borrow().clone());
+    match text_metrics {
+        Some(text_metrics) => text_metrics(string, font_size),
+        None => estimate_text(string, font_size),
+    }
+}
+
+/// Estimates the size of a single line of text from its font size, as if every glyph
+/// had the same width
+///
+/// # Returns
+/// The estimated size (width and height) of the text
+///
+/// # Arguments
+/// * `string` - the text to be measured
+/// * `font_size` - the font size of the text
+pub fn estimate_text(string: &str, font_size: usize) -> Vector2D {
+    Vector2D::new(
+        string.chars().count() as f64 * font_size as f64 * GLYPH_WIDTH,
+        font_size as f64,
+    )
+}
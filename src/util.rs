@@ -1,6 +1,9 @@
 //! Contains helper functions and structures.
 //!
 
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 /// A structure to give an unique identifier within the library to the application in use
@@ -32,6 +35,17 @@ impl IDMachine {
         self.id += 1;
         self.id
     }
+
+    /// Gets the last unique identifier fetched
+    ///
+    /// # Returns
+    /// The last unique identifier fetched (i.e., 0 if no identifier was fetched yet)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn last_id(&self) -> usize {
+        self.id
+    }
 }
 
 /// Queue data structure
@@ -104,7 +118,7 @@ impl<T> Queue<T> {
 }
 
 /// A color represented in the RGB 8-bit color space
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Color {
     /// Transparency component, 0 to 255
     pub a: u8,
@@ -151,12 +165,95 @@ impl Color {
             b: (hex & 0xff) as u8,
         }
     }
+
+    /// Computes the relative luminance of the color, as defined by WCAG 2.0
+    ///
+    /// _**Note:** The transparency component is not considered
+    ///
+    /// # Returns
+    /// The relative luminance, from 0 (darkest black) to 1 (lightest white)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn relative_luminance(&self) -> f64 {
+        // Converts an 8-bit sRGB component to its linear value
+        let linear = |component: u8| {
+            let value = component as f64 / 255.;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Computes the contrast ratio between two colors, as defined by WCAG 2.0
+    ///
+    /// WCAG recommends a contrast ratio of at least 4.5 for normal text and
+    /// of at least 3 for large text.
+    ///
+    /// # Returns
+    /// The contrast ratio, from 1 (no contrast) to 21 (black on white)
+    ///
+    /// # Arguments
+    /// * `other` - the color to be compared
+    pub fn contrast_ratio(&self, other: Color) -> f64 {
+        let luminance_a = self.relative_luminance();
+        let luminance_b = other.relative_luminance();
+        (luminance_a.max(luminance_b) + 0.05) / (luminance_a.min(luminance_b) + 0.05)
+    }
+
+    /// Chooses a readable text color (black or white) for the color as background
+    ///
+    /// # Returns
+    /// Opaque black or opaque white, whichever has the highest contrast ratio with
+    /// the color
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn safe_text_color(&self) -> Color {
+        let black = Color::from_hex(0xff000000);
+        let white = Color::from_hex(0xffffffff);
+        if self.contrast_ratio(black) >= self.contrast_ratio(white) {
+            black
+        } else {
+            white
+        }
+    }
+
+    /// Desaturates the color, moving it towards its gray level
+    ///
+    /// # Returns
+    /// The desaturated color, with the same transparency
+    ///
+    /// # Arguments
+    /// * `amount` - how much to desaturate, from 0 (unchanged) to 1 (fully gray)
+    pub fn desaturate(&self, amount: f64) -> Color {
+        let amount = amount.clamp(0., 1.);
+        let gray = 0.299 * self.r as f64 + 0.587 * self.g as f64 + 0.114 * self.b as f64;
+        let mix = |component: u8| (component as f64 + (gray - component as f64) * amount).round() as u8;
+        Color::new(self.a, mix(self.r), mix(self.g), mix(self.b))
+    }
+
+    /// Multiplies the color's transparency component by an opacity
+    ///
+    /// # Returns
+    /// The color with the transparency component multiplied
+    ///
+    /// # Arguments
+    /// * `opacity` - the opacity multiplier, from 0 (transparent) to 1 (unchanged)
+    pub fn with_opacity(&self, opacity: f64) -> Color {
+        let a = (self.a as f64 * opacity.clamp(0., 1.)).round() as u8;
+        Color::new(a, self.r, self.g, self.b)
+    }
 }
 
 /// A 2D vector, containing an x and a y component. Vector2Ds can be added to or 
 /// subtracted from one-another, and they can be mulitplied and divided by scalar 
 /// values.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct Vector2D {
     // Position of the 2D vector on x-axis of a two-dimensional space
     pub x: f64,
@@ -205,6 +302,543 @@ impl Vector2D {
             y: self.y.min(other.y),
         }
     }
+
+    /// Creates a new `Vector2D` based on the maximum position values between
+    /// the vector itself and another one
+    ///
+    /// # Returns
+    /// The repositioned vector
+    ///
+    /// # Arguments
+    /// * `other` - vector to be compared
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+
+    /// Creates a new `Vector2D` restricted to an interval on both axes
+    ///
+    /// # Returns
+    /// The repositioned vector, no smaller than `min` and no larger than `max`
+    /// (`min` taking precedence if both are violated)
+    ///
+    /// # Arguments
+    /// * `min` - the minimum position values
+    /// * `max` - the maximum position values
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.min(max).max(min)
+    }
+
+    /// Creates a new `Vector2D` rounded to the nearest device pixel
+    ///
+    /// # Returns
+    /// The repositioned vector, in logical units, lying on a device pixel
+    ///
+    /// # Arguments
+    /// * `scale_factor` - the number of device pixels per logical unit
+    pub fn snap(self, scale_factor: f64) -> Self {
+        Self {
+            x: (self.x * scale_factor).round() / scale_factor,
+            y: (self.y * scale_factor).round() / scale_factor,
+        }
+    }
+}
+
+/// A rectangle, defined by its top left corner and its size
+#[derive(Clone, Copy)]
+pub struct Rect {
+    /// The rectangle's top left corner, on a two-dimensional space
+    pub position: Vector2D,
+    /// The rectangle's size (width and height)
+    pub size: Vector2D,
+}
+
+impl Rect {
+    /// Creates a new `Rect`
+    ///
+    /// # Returns
+    /// The rectangle created
+    ///
+    /// # Arguments
+    /// * `position` - the rectangle's top left corner
+    /// * `size` - the rectangle's size (width and height)
+    pub fn new(position: Vector2D, size: Vector2D) -> Self {
+        Self { position, size }
+    }
+
+    /// Checks if a point is inside the rectangle
+    ///
+    /// # Returns
+    /// True, if the point is inside the rectangle (edges included), false otherwise
+    ///
+    /// # Arguments
+    /// * `point` - the point to be checked
+    pub fn contains(&self, point: Vector2D) -> bool {
+        point.x >= self.position.x
+            && point.x <= self.position.x + self.size.x
+            && point.y >= self.position.y
+            && point.y <= self.position.y + self.size.y
+    }
+
+    /// Checks if two rectangles overlap
+    ///
+    /// # Returns
+    /// True, if the rectangles share some area, false otherwise
+    ///
+    /// # Arguments
+    /// * `other` - the rectangle to be compared
+    pub fn intersects(&self, other: Rect) -> bool {
+        self.position.x < other.position.x + other.size.x
+            && other.position.x < self.position.x + self.size.x
+            && self.position.y < other.position.y + other.size.y
+            && other.position.y < self.position.y + self.size.y
+    }
+
+    /// Creates the smallest rectangle containing two rectangles
+    ///
+    /// # Returns
+    /// The bounding rectangle of both rectangles
+    ///
+    /// # Arguments
+    /// * `other` - the rectangle to be contained along with this one
+    pub fn union(&self, other: Rect) -> Rect {
+        let start = self.position.min(other.position);
+        let end = (self.position + self.size).max(other.position + other.size);
+        Rect::new(start, end - start)
+    }
+
+    /// Checks if a point is inside the rectangle once transformed (e.g. the cursor
+    /// over a widget whose ancestor is rotated)
+    ///
+    /// # Returns
+    /// True, if the point, mapped back by the inverse of the transform, is inside the
+    /// rectangle (edges included), false otherwise
+    ///
+    /// # Arguments
+    /// * `point` - the point to be checked, after the transform
+    /// * `transform` - the transform applied to the rectangle
+    pub fn contains_transformed(&self, point: Vector2D, transform: &Transform) -> bool {
+        transform
+            .inverse_apply(point)
+            .is_some_and(|point| self.contains(point))
+    }
+}
+
+/// An affine transform, scaling, then rotating, then translating the points
+///
+/// The points are scaled and rotated around the origin (i.e., the window's upper left
+/// corner), so a transform around another point is created with
+/// [`around`](`Transform::around`).
+///
+/// # Examples
+///
+/// ```
+/// use hyber::util::{Transform, Vector2D};
+///
+/// // Rotate by a quarter turn around the point (10, 10)
+/// let transform = Transform::around(
+///     Vector2D::new(10., 10.),
+///     std::f64::consts::FRAC_PI_2,
+///     Vector2D::new(1., 1.),
+/// );
+/// let point = transform.apply(Vector2D::new(20., 10.));
+/// assert!((point.x - 10.).abs() < 1e-9 && (point.y - 20.).abs() < 1e-9);
+///
+/// let back = transform.inverse_apply(point).unwrap();
+/// assert!((back.x - 20.).abs() < 1e-9 && (back.y - 10.).abs() < 1e-9);
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// The distance the points are moved by, after being scaled and rotated
+    pub translation: Vector2D,
+    /// The angle the points are rotated by, in radians, clockwise on the screen
+    pub rotation: f64,
+    /// The factors the points are scaled by, along each axis
+    pub scale: Vector2D,
+}
+
+impl Transform {
+    /// Creates a new `Transform`
+    ///
+    /// # Returns
+    /// The transform created
+    ///
+    /// # Arguments
+    /// * `translation` - the distance the points are moved by, after being scaled and rotated
+    /// * `rotation` - the angle the points are rotated by, in radians
+    /// * `scale` - the factors the points are scaled by, along each axis
+    pub fn new(translation: Vector2D, rotation: f64, scale: Vector2D) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Creates the transform leaving the points unchanged
+    ///
+    /// # Returns
+    /// The identity transform
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn identity() -> Self {
+        Self::new(Vector2D::new(0., 0.), 0., Vector2D::new(1., 1.))
+    }
+
+    /// Creates a transform scaling and rotating the points around a given point
+    /// (e.g. the center of a spinner)
+    ///
+    /// # Returns
+    /// The transform created, leaving the given point unchanged
+    ///
+    /// # Arguments
+    /// * `origin` - the point the others are scaled and rotated around
+    /// * `rotation` - the angle the points are rotated by, in radians
+    /// * `scale` - the factors the points are scaled by, along each axis
+    pub fn around(origin: Vector2D, rotation: f64, scale: Vector2D) -> Self {
+        let transform = Self::new(Vector2D::new(0., 0.), rotation, scale);
+        Self::new(origin - transform.apply(origin), rotation, scale)
+    }
+
+    /// Checks if the transform leaves the points unchanged
+    ///
+    /// # Returns
+    /// True, if the transform is the identity, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Transforms a point
+    ///
+    /// # Returns
+    /// The point scaled, rotated and translated
+    ///
+    /// # Arguments
+    /// * `point` - the point to be transformed
+    pub fn apply(&self, point: Vector2D) -> Vector2D {
+        let (sin, cos) = self.rotation.sin_cos();
+        let scaled = point * self.scale;
+        Vector2D::new(
+            scaled.x * cos - scaled.y * sin,
+            scaled.x * sin + scaled.y * cos,
+        ) + self.translation
+    }
+
+    /// Transforms a rectangle
+    ///
+    /// # Returns
+    /// The smallest rectangle containing the corners of the rectangle transformed
+    ///
+    /// # Arguments
+    /// * `rect` - the rectangle to be transformed
+    pub fn apply_rect(&self, rect: Rect) -> Rect {
+        let corners = [
+            rect.position,
+            rect.position + Vector2D::new(rect.size.x, 0.),
+            rect.position + Vector2D::new(0., rect.size.y),
+            rect.position + rect.size,
+        ]
+        .map(|corner| self.apply(corner));
+        let start = corners.iter().fold(corners[0], |start, corner| start.min(*corner));
+        let end = corners.iter().fold(corners[0], |end, corner| end.max(*corner));
+        Rect::new(start, end - start)
+    }
+
+    /// Maps a transformed point back to its original position (e.g. the cursor over a
+    /// rotated widget to the widget's own coordinates)
+    ///
+    /// # Returns
+    /// The point before the transform, or `None` if the transform scales by zero
+    ///
+    /// # Arguments
+    /// * `point` - the transformed point
+    pub fn inverse_apply(&self, point: Vector2D) -> Option<Vector2D> {
+        if self.scale.x == 0. || self.scale.y == 0. {
+            return None;
+        }
+        let (sin, cos) = self.rotation.sin_cos();
+        let moved = point - self.translation;
+        let rotated = Vector2D::new(moved.x * cos + moved.y * sin, moved.y * cos - moved.x * sin);
+        Some(rotated / self.scale)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+/// Image decoded in memory, as RGBA pixels (e.g. generated procedurally, or decoded from
+/// a network response), drawn without going through the file system
+///
+/// # Examples
+///
+/// ```
+/// use hyber::util::{Color, ImageData};
+///
+/// let image = ImageData::from_fn(2, 2, |x, y| {
+///     if x == y { Color::from_hex(0xffffffff) } else { Color::from_hex(0xff000000) }
+/// });
+/// assert_eq!(image.pixels().len(), 16);
+/// assert_eq!(image.pixel(1, 1), Some(Color::from_hex(0xffffffff)));
+///
+/// assert!(ImageData::new(2, 2, vec![0; 3]).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ImageData {
+    /// The image's width, in pixels
+    width: usize,
+    /// The image's height, in pixels
+    height: usize,
+    /// The image's pixels, row by row, as four bytes each (red, green, blue and alpha)
+    pixels: Vec<u8>,
+}
+
+/// Error returned when the pixels of an [`ImageData`] do not match its dimensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageDataError {
+    /// The number of bytes expected, four per pixel
+    pub expected: usize,
+    /// The number of bytes given
+    pub actual: usize,
+}
+
+impl fmt::Display for ImageDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} bytes of RGBA pixels, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ImageDataError {}
+
+impl ImageData {
+    /// Creates a new `ImageData` from its RGBA pixels
+    ///
+    /// # Returns
+    /// The image created, or an error if there are not exactly four bytes per pixel
+    ///
+    /// # Arguments
+    /// * `width` - the image's width, in pixels
+    /// * `height` - the image's height, in pixels
+    /// * `pixels` - the image's pixels, row by row, as four bytes each (red, green, blue and alpha)
+    pub fn new(width: usize, height: usize, pixels: Vec<u8>) -> Result<Self, ImageDataError> {
+        let expected = width * height * 4;
+        if pixels.len() != expected {
+            return Err(ImageDataError {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Creates a new `ImageData` by computing the color of each pixel
+    ///
+    /// # Returns
+    /// The image created
+    ///
+    /// # Arguments
+    /// * `width` - the image's width, in pixels
+    /// * `height` - the image's height, in pixels
+    /// * `color` - the function giving the color of the pixel on a given column and row
+    pub fn from_fn<F: FnMut(usize, usize) -> Color>(
+        width: usize,
+        height: usize,
+        mut color: F,
+    ) -> Self {
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = color(x, y);
+                pixels.extend_from_slice(&[pixel.r, pixel.g, pixel.b, pixel.a]);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Gets the image's width
+    ///
+    /// # Returns
+    /// The image's width, in pixels
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the image's height
+    ///
+    /// # Returns
+    /// The image's height, in pixels
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the image's size
+    ///
+    /// # Returns
+    /// The image's width and height, in pixels
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn size(&self) -> Vector2D {
+        Vector2D::new(self.width as f64, self.height as f64)
+    }
+
+    /// Gets the image's pixels
+    ///
+    /// # Returns
+    /// The image's pixels, row by row, as four bytes each (red, green, blue and alpha)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Gets the color of a pixel
+    ///
+    /// # Returns
+    /// The color of the pixel, or `None` if it is outside the image
+    ///
+    /// # Arguments
+    /// * `x` - the pixel's column
+    /// * `y` - the pixel's row
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let index = (y * self.width + x) * 4;
+        let rgba = &self.pixels[index..index + 4];
+        Some(Color::new(rgba[3], rgba[0], rgba[1], rgba[2]))
+    }
+}
+
+/// Hash of the inputs a widget's recipe depends on, used to memoize the widget
+///
+/// See [`memo_key`](`crate::widget::Widget::memo_key`).
+///
+/// # Examples
+///
+/// ```
+/// use hyber::util::{Color, MemoKey, Vector2D};
+///
+/// let key = MemoKey::new()
+///     .with(&"Hello")
+///     .with_color(Color::from_hex(0xff000000))
+///     .with_vector(Vector2D::new(100., 20.))
+///     .finish();
+///
+/// assert_eq!(key, MemoKey::new()
+///     .with(&"Hello")
+///     .with_color(Color::from_hex(0xff000000))
+///     .with_vector(Vector2D::new(100., 20.))
+///     .finish());
+/// ```
+pub struct MemoKey {
+    /// The hasher accumulating the inputs
+    hasher: DefaultHasher,
+}
+
+impl MemoKey {
+    /// Creates a new `MemoKey`
+    ///
+    /// # Returns
+    /// A memo key with no inputs
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> Self {
+        Self {
+            hasher: DefaultHasher::new(),
+        }
+    }
+
+    /// Adds an input to the memo key
+    ///
+    /// # Returns
+    /// The memo key with the input added
+    ///
+    /// # Arguments
+    /// * `input` - the input to be added
+    pub fn with<T: Hash + ?Sized>(mut self, input: &T) -> Self {
+        input.hash(&mut self.hasher);
+        self
+    }
+
+    /// Adds a floating point input to the memo key
+    ///
+    /// # Returns
+    /// The memo key with the input added
+    ///
+    /// # Arguments
+    /// * `input` - the input to be added
+    pub fn with_f64(mut self, input: f64) -> Self {
+        input.to_bits().hash(&mut self.hasher);
+        self
+    }
+
+    /// Adds a vector input to the memo key (e.g. a size)
+    ///
+    /// # Returns
+    /// The memo key with the input added
+    ///
+    /// # Arguments
+    /// * `input` - the input to be added
+    pub fn with_vector(self, input: Vector2D) -> Self {
+        self.with_f64(input.x).with_f64(input.y)
+    }
+
+    /// Adds a color input to the memo key
+    ///
+    /// # Returns
+    /// The memo key with the input added
+    ///
+    /// # Arguments
+    /// * `input` - the input to be added
+    pub fn with_color(self, input: Color) -> Self {
+        self.with(&input)
+    }
+
+    /// Finishes the memo key
+    ///
+    /// # Returns
+    /// The hash of every input added
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+impl Default for MemoKey {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Add for Vector2D {
@@ -0,0 +1,397 @@
+//! Contains the temporary text field used to type the value of a numeric widget.
+//!
+//! Numeric widgets (e.g. a [`SliderWidget`](`crate::widget::slider::SliderWidget`))
+//! embed a [`ValueEditor`], which swaps in a text field pre-filled with their current
+//! value when they are double-clicked (i.e., when they receive a
+//! [`ButtonDoubleClicked`](`crate::event::Mouse::ButtonDoubleClicked`) event). While
+//! editing, the widget feeds the editor the events it receives and draws the editor's
+//! [`recipe`](`ValueEditor::recipe`) instead of its own. The text typed is evaluated as
+//! an arithmetic expression (e.g. `50 + 25`) and committed on Enter, leaving the widget
+//! to clamp it to its range, or reverted on Escape.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::value_editor::evaluate;
+//!
+//! assert_eq!(evaluate("42"), Some(42.));
+//! assert_eq!(evaluate("(10 + 5) * 2 - -1"), Some(31.));
+//! assert_eq!(evaluate("1 / 0"), None);
+//! assert_eq!(evaluate("12 apples"), None);
+//! ```
+
+use crate::event::{Event, Keyboard, Mouse, Window};
+use crate::key_code::KeyCode;
+use crate::renderer::{BlendMode, FontDescriptor, RenderInstruction};
+use crate::util::{Color, Rect, Vector2D};
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Average width of a glyph, as a fraction of the font size, used to place the caret
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Font size of the text field, as a fraction of its height
+const FONT_SIZE: f64 = 0.7;
+
+/// Outcome of an event received by the value editor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EditEvent {
+    /// The text or the validation changed, so the editor must be redrawn
+    Changed,
+    /// The text was evaluated into the given value, which the widget must assign
+    /// after validating and clamping it
+    Committed(f64),
+    /// The edition was abandoned, so the widget keeps its value
+    Reverted,
+}
+
+/// Temporary text field to type the value of a numeric widget
+///
+/// The editor starts with its text selected, so that typing replaces the value.
+/// Pressing Enter, or clicking outside the field, commits the value typed if it is
+/// a valid expression, while an invalid expression is highlighted until corrected.
+/// Pressing Escape, or the window losing the focus, reverts the value.
+#[derive(Clone)]
+pub struct ValueEditor {
+    /// The field's background color
+    background_color: Color,
+    /// The field's text color
+    text_color: Color,
+    /// The color of the field's border when the text is not a valid expression
+    error_color: Color,
+    /// The text typed
+    text: String,
+    /// Whether the text field is displayed
+    is_editing: bool,
+    /// Whether the whole text is selected (i.e., replaced by the next character typed)
+    is_selected: bool,
+    /// Whether the text failed to be evaluated on the last commit
+    is_invalid: bool,
+}
+
+impl ValueEditor {
+    /// Creates a new `ValueEditor`, not editing
+    ///
+    /// # Returns
+    /// The value editor created
+    ///
+    /// # Arguments
+    /// * `background_color` - the color to be assigned to the field's background
+    /// * `text_color` - the color to be assigned to the field's text and border
+    /// * `error_color` - the color to be assigned to the field's border when the text is invalid
+    pub fn new(background_color: Color, text_color: Color, error_color: Color) -> ValueEditor {
+        ValueEditor {
+            background_color,
+            text_color,
+            error_color,
+            text: String::new(),
+            is_editing: false,
+            is_selected: false,
+            is_invalid: false,
+        }
+    }
+
+    /// Checks whether the text field is displayed
+    ///
+    /// # Returns
+    /// True, if the value is being edited, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_editing(&self) -> bool {
+        self.is_editing
+    }
+
+    /// Gets the text typed
+    ///
+    /// # Returns
+    /// The text of the field
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Swaps in the text field, pre-filled with the widget's value and selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `value` - the widget's current value, as displayed in the field
+    pub fn begin(&mut self, value: &str) {
+        self.text = String::from(value);
+        self.is_editing = true;
+        self.is_selected = true;
+        self.is_invalid = false;
+    }
+
+    /// Updates the text field according to an event received by the widget, while editing
+    ///
+    /// # Returns
+    /// The outcome of the event, or `None` if it did not affect the field
+    ///
+    /// # Arguments
+    /// * `event` - the event received by the widget
+    /// * `area` - the text field's area
+    /// * `cursor` - the cursor's position
+    pub fn on_event(&mut self, event: Event, area: Rect, cursor: Vector2D) -> Option<EditEvent> {
+        if !self.is_editing {
+            return None;
+        }
+        match event {
+            Event::Keyboard(Keyboard::CharacterReceived(character)) if !character.is_control() => {
+                if std::mem::replace(&mut self.is_selected, false) {
+                    self.text.clear();
+                }
+                self.text.push(character);
+                self.is_invalid = false;
+                Some(EditEvent::Changed)
+            }
+            Event::Keyboard(Keyboard::KeyPressed { key_code, .. }) => match key_code {
+                KeyCode::Backspace if self.is_selected => {
+                    self.text.clear();
+                    self.is_selected = false;
+                    self.is_invalid = false;
+                    Some(EditEvent::Changed)
+                }
+                KeyCode::Backspace => {
+                    self.text.pop();
+                    self.is_invalid = false;
+                    Some(EditEvent::Changed)
+                }
+                KeyCode::Enter | KeyCode::NumpadEnter => Some(self.commit()),
+                KeyCode::Escape => Some(self.revert()),
+                _ => None,
+            },
+            Event::Mouse(Mouse::ButtonPressed(_)) if !area.contains(cursor) => {
+                match evaluate(&self.text) {
+                    Some(_) => Some(self.commit()),
+                    None => Some(self.revert()),
+                }
+            }
+            Event::Window(Window::Focused(false)) => Some(self.revert()),
+            _ => None,
+        }
+    }
+
+    /// Gets the render instructions of the text field
+    ///
+    /// # Returns
+    /// The field's border, background, selection, text and caret
+    ///
+    /// # Arguments
+    /// * `area` - the text field's area
+    pub fn recipe(&self, area: Rect) -> Vec<RenderInstruction> {
+        let (point, size) = (area.position, area.size);
+        let font_size = (size.y * FONT_SIZE).max(1.) as usize;
+        let text_width = self.text.chars().count() as f64 * font_size as f64 * GLYPH_WIDTH;
+        let text_point = Vector2D::new(point.x + 4., point.y + (size.y + font_size as f64) * 0.5);
+        let border_color = if self.is_invalid {
+            self.error_color
+        } else {
+            self.text_color
+        };
+        let rect = |point: Vector2D, size: Vector2D, color: Color| RenderInstruction::DrawRect {
+            point,
+            size,
+            color,
+            clip_point: area.position,
+            clip_size: area.size,
+            blend_mode: BlendMode::Normal,
+        };
+
+        let mut instructions = vec![
+            rect(point, size, border_color),
+            rect(point + 1., size - 2., self.background_color),
+        ];
+        if self.is_selected {
+            instructions.push(rect(
+                Vector2D::new(text_point.x, point.y + 2.),
+                Vector2D::new(text_width, size.y - 4.),
+                self.text_color.with_opacity(0.25),
+            ));
+        }
+        instructions.push(RenderInstruction::DrawText {
+            point: text_point,
+            font_size,
+            string: self.text.clone(),
+            color: self.text_color,
+            clip_point: area.position,
+            clip_size: area.size,
+            font: FontDescriptor::default(),
+        });
+        if !self.is_selected {
+            instructions.push(rect(
+                Vector2D::new(text_point.x + text_width, point.y + 2.),
+                Vector2D::new(1., size.y - 4.),
+                self.text_color,
+            ));
+        }
+        instructions
+    }
+
+    /// Evaluates the text typed, ending the edition if it is valid, for internal use only
+    ///
+    /// # Returns
+    /// The value committed, or a change to highlight the text as invalid
+    ///
+    /// # Arguments
+    /// No arguments
+    fn commit(&mut self) -> EditEvent {
+        match evaluate(&self.text) {
+            Some(value) => {
+                self.is_editing = false;
+                EditEvent::Committed(value)
+            }
+            None => {
+                self.is_invalid = true;
+                EditEvent::Changed
+            }
+        }
+    }
+
+    /// Ends the edition without a value, for internal use only
+    ///
+    /// # Returns
+    /// The reversion of the edition
+    ///
+    /// # Arguments
+    /// No arguments
+    fn revert(&mut self) -> EditEvent {
+        self.is_editing = false;
+        EditEvent::Reverted
+    }
+}
+
+impl Default for ValueEditor {
+    fn default() -> Self {
+        ValueEditor::new(
+            Color::from_hex(0xffffffff),
+            Color::from_hex(0xff000000),
+            Color::from_hex(0xffd32f2f),
+        )
+    }
+}
+
+/// Evaluates an arithmetic expression made of decimal numbers, the operators `+`,
+/// `-`, `*` and `/` and parentheses
+///
+/// # Returns
+/// The value of the expression, or `None` if it is malformed or its value is not finite
+///
+/// # Arguments
+/// * `expression` - the expression to be evaluated (e.g. `"(10 + 5) * 2"`)
+pub fn evaluate(expression: &str) -> Option<f64> {
+    let mut chars = expression.chars().peekable();
+    let value = parse_sum(&mut chars)?;
+    skip_whitespace(&mut chars);
+    match chars.peek() {
+        None if value.is_finite() => Some(value),
+        _ => None,
+    }
+}
+
+/// Parses a sum or difference of products, for internal use only
+///
+/// # Returns
+/// The value parsed, or `None` if it is malformed
+///
+/// # Arguments
+/// * `chars` - the characters left to be parsed
+fn parse_sum(chars: &mut Peekable<Chars>) -> Option<f64> {
+    let mut value = parse_product(chars)?;
+    loop {
+        skip_whitespace(chars);
+        match chars.peek() {
+            Some('+') => {
+                chars.next();
+                value += parse_product(chars)?;
+            }
+            Some('-') => {
+                chars.next();
+                value -= parse_product(chars)?;
+            }
+            _ => return Some(value),
+        }
+    }
+}
+
+/// Parses a product or quotient of factors, for internal use only
+///
+/// # Returns
+/// The value parsed, or `None` if it is malformed
+///
+/// # Arguments
+/// * `chars` - the characters left to be parsed
+fn parse_product(chars: &mut Peekable<Chars>) -> Option<f64> {
+    let mut value = parse_factor(chars)?;
+    loop {
+        skip_whitespace(chars);
+        match chars.peek() {
+            Some('*') => {
+                chars.next();
+                value *= parse_factor(chars)?;
+            }
+            Some('/') => {
+                chars.next();
+                value /= parse_factor(chars)?;
+            }
+            _ => return Some(value),
+        }
+    }
+}
+
+/// Parses a signed number or parenthesized expression, for internal use only
+///
+/// # Returns
+/// The value parsed, or `None` if it is malformed
+///
+/// # Arguments
+/// * `chars` - the characters left to be parsed
+fn parse_factor(chars: &mut Peekable<Chars>) -> Option<f64> {
+    skip_whitespace(chars);
+    match chars.peek()? {
+        '-' => {
+            chars.next();
+            parse_factor(chars).map(|value| -value)
+        }
+        '+' => {
+            chars.next();
+            parse_factor(chars)
+        }
+        '(' => {
+            chars.next();
+            let value = parse_sum(chars)?;
+            skip_whitespace(chars);
+            match chars.next() {
+                Some(')') => Some(value),
+                _ => None,
+            }
+        }
+        _ => {
+            let mut number = String::new();
+            while let Some(&character) = chars.peek() {
+                if !character.is_ascii_digit() && character != '.' {
+                    break;
+                }
+                number.push(character);
+                chars.next();
+            }
+            number.parse().ok()
+        }
+    }
+}
+
+/// Skips the whitespace before the next token, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `chars` - the characters left to be parsed
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(char::is_ascii_whitespace).is_some() {}
+}
@@ -0,0 +1,322 @@
+//! Contains the watchdog of the event loop, diagnosing the frames that stall.
+//!
+//! A [`Watchdog`] is set on the [`Context`](`crate::context::Context`) with
+//! [`set_watchdog`](`crate::context::Context::set_watchdog`). The event loop reports the
+//! phase of the frame in progress (i.e., handling the events, building the widgets,
+//! drawing or updating the messages) and the widget being processed, while a thread
+//! of the watchdog checks that no frame exceeds the threshold.
+//!
+//! Since the check is done on its own thread, a frame that never ends (e.g. a message
+//! handler stuck in a loop) is diagnosed while it stalls, with a [`StalledFrame`]
+//! telling the phase and the widget it is stuck on.
+//!
+//! # Examples
+//!
+//! ```
+//! use hyber::watchdog::{FramePhase, Watchdog};
+//! use std::sync::mpsc;
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! let (sender, receiver) = mpsc::channel();
+//! let watchdog = Watchdog::new(
+//!     Duration::from_millis(20),
+//!     Some(Box::new(move |stalled| sender.send(stalled.clone()).unwrap())),
+//! );
+//!
+//! // Done by the event loop
+//! watchdog.enter_phase(FramePhase::Messages);
+//! thread::sleep(Duration::from_millis(100));
+//!
+//! let stalled = receiver.recv().unwrap();
+//! assert_eq!(stalled.phase, FramePhase::Messages);
+//! assert!(stalled.elapsed >= Duration::from_millis(20));
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of checks done by the watchdog's thread during the threshold
+const CHECKS_PER_THRESHOLD: u32 = 4;
+
+/// Phase of a frame of the event loop
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramePhase {
+    /// Executing the hooks before the events are handled
+    Hooks,
+    /// Dispatching the events to the widgets
+    Events,
+    /// Building the widgets
+    Build,
+    /// Drawing the render instructions on the display
+    Draw,
+    /// Updating the messages enqueued by the widgets
+    Messages,
+}
+
+/// Time spent on a phase of a frame
+#[derive(Clone, Copy, Debug)]
+pub struct PhaseTiming {
+    /// The phase
+    pub phase: FramePhase,
+    /// The time spent on the phase
+    pub time: Duration,
+}
+
+/// Diagnostic of a frame exceeding the watchdog's threshold
+#[derive(Clone, Debug)]
+pub struct StalledFrame {
+    /// The number of the frame (i.e., the number of frames drawn before it, plus one)
+    pub frame: u64,
+    /// The time elapsed since the frame started, when it was diagnosed
+    pub elapsed: Duration,
+    /// The threshold exceeded
+    pub threshold: Duration,
+    /// The phase in progress when the frame was diagnosed
+    pub phase: FramePhase,
+    /// The identifier of the widget being processed when the frame was diagnosed, if any
+    pub widget_id: Option<usize>,
+    /// The time spent on every phase of the frame so far, including the one in progress
+    pub phase_timings: Vec<PhaseTiming>,
+}
+
+impl fmt::Display for StalledFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame {} stalled for {:?}, over the threshold of {:?}, during {:?}",
+            self.frame, self.elapsed, self.threshold, self.phase
+        )?;
+        if let Some(widget_id) = self.widget_id {
+            write!(f, " on widget {}", widget_id)?;
+        }
+        for timing in self.phase_timings.iter() {
+            write!(f, "\n  {:?}: {:?}", timing.phase, timing.time)?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback that receives the diagnostics of the stalled frames, executed on the
+/// watchdog's thread
+pub type StallHandler = Box<dyn FnMut(&StalledFrame) + Send>;
+
+/// State of the frame in progress, shared with the watchdog's thread, for internal use only
+struct WatchState {
+    /// The number of the frame in progress
+    frame: u64,
+    /// The instant when the frame in progress started, or `None` between frames
+    frame_start: Option<Instant>,
+    /// The phase in progress
+    phase: FramePhase,
+    /// The instant when the phase in progress started
+    phase_start: Instant,
+    /// The time spent on the phases of the frame already finished
+    phase_timings: Vec<PhaseTiming>,
+    /// The identifier of the widget being processed, if any
+    widget_id: Option<usize>,
+    /// Whether the frame in progress was already diagnosed
+    is_reported: bool,
+    /// The diagnostic of a frame that ended over the threshold before being checked
+    pending: Option<StalledFrame>,
+    /// Whether the watchdog was dropped, stopping its thread
+    is_stopped: bool,
+}
+
+impl WatchState {
+    /// Creates the diagnostic of the frame in progress, for internal use only
+    ///
+    /// # Returns
+    /// The diagnostic of the frame in progress
+    ///
+    /// # Arguments
+    /// * `now` - the instant when the frame is diagnosed
+    /// * `threshold` - the threshold exceeded
+    fn diagnose(&self, now: Instant, threshold: Duration) -> StalledFrame {
+        let mut phase_timings = self.phase_timings.clone();
+        phase_timings.push(PhaseTiming {
+            phase: self.phase,
+            time: now - self.phase_start,
+        });
+        StalledFrame {
+            frame: self.frame,
+            elapsed: self
+                .frame_start
+                .map_or(Duration::from_secs(0), |start| now - start),
+            threshold,
+            phase: self.phase,
+            widget_id: self.widget_id,
+            phase_timings,
+        }
+    }
+}
+
+/// Watchdog diagnosing the frames of the event loop exceeding a threshold, checked on
+/// its own thread
+pub struct Watchdog {
+    /// The maximum time a frame may take before being diagnosed
+    threshold: Duration,
+    /// The state of the frame in progress, shared with the watchdog's thread
+    state: Arc<Mutex<WatchState>>,
+}
+
+impl Watchdog {
+    /// Creates a new `Watchdog`, starting its thread
+    ///
+    /// # Returns
+    /// The watchdog created
+    ///
+    /// # Arguments
+    /// * `threshold` - the maximum time a frame may take before being diagnosed
+    /// * `stall_handler` - the callback receiving the diagnostics, or `None` to drop them
+    pub fn new(threshold: Duration, stall_handler: Option<StallHandler>) -> Watchdog {
+        let state = Arc::new(Mutex::new(WatchState {
+            frame: 1,
+            frame_start: None,
+            phase: FramePhase::Hooks,
+            phase_start: Instant::now(),
+            phase_timings: Vec::new(),
+            widget_id: None,
+            is_reported: false,
+            pending: None,
+            is_stopped: false,
+        }));
+        let shared_state = state.clone();
+        let mut stall_handler = stall_handler;
+        let interval = (threshold / CHECKS_PER_THRESHOLD).max(Duration::from_millis(1));
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let stalled = {
+                let mut state = match shared_state.lock() {
+                    Ok(state) => state,
+                    Err(_) => return,
+                };
+                if state.is_stopped {
+                    return;
+                }
+                let now = Instant::now();
+                let is_stalled = state
+                    .frame_start
+                    .is_some_and(|start| !state.is_reported && now - start > threshold);
+                if is_stalled {
+                    state.is_reported = true;
+                    Some(state.diagnose(now, threshold))
+                } else {
+                    state.pending.take()
+                }
+            };
+            if let (Some(stalled), Some(handler)) = (stalled, stall_handler.as_mut()) {
+                handler(&stalled);
+            }
+        });
+        Watchdog { threshold, state }
+    }
+
+    /// Gets the maximum time a frame may take before being diagnosed
+    ///
+    /// # Returns
+    /// The watchdog's threshold
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    /// Sets the number of the next frame started, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `frame` - the number of the next frame
+    pub(crate) fn set_next_frame(&self, frame: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.frame_start.is_none() {
+                state.frame = frame;
+            }
+        }
+    }
+
+    /// Enters a phase of the frame in progress, starting the frame if there is none
+    ///
+    /// This is done by the event loop, so applications only need it when running
+    /// their own loop.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `phase` - the phase entered
+    pub fn enter_phase(&self, phase: FramePhase) {
+        if let Ok(mut state) = self.state.lock() {
+            let now = Instant::now();
+            if state.frame_start.is_none() {
+                state.frame_start = Some(now);
+                state.phase_timings.clear();
+                state.is_reported = false;
+            } else {
+                let timing = PhaseTiming {
+                    phase: state.phase,
+                    time: now - state.phase_start,
+                };
+                state.phase_timings.push(timing);
+            }
+            state.phase = phase;
+            state.phase_start = now;
+            state.widget_id = None;
+        }
+    }
+
+    /// Sets the widget being processed during the phase in progress
+    ///
+    /// This is done by the event loop, so applications only need it when running
+    /// their own loop.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget_id` - the identifier of the widget being processed, or `None` if there is none
+    pub fn set_widget(&self, widget_id: Option<usize>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.widget_id = widget_id;
+        }
+    }
+
+    /// Ends the frame in progress, diagnosing it if it exceeded the threshold and was
+    /// not diagnosed yet
+    ///
+    /// This is done by the event loop, so applications only need it when running
+    /// their own loop.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn end_frame(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            let now = Instant::now();
+            let is_late = state
+                .frame_start
+                .is_some_and(|start| !state.is_reported && now - start > self.threshold);
+            if is_late {
+                state.pending = Some(state.diagnose(now, self.threshold));
+            }
+            state.frame += 1;
+            state.frame_start = None;
+            state.widget_id = None;
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.is_stopped = true;
+        }
+    }
+}
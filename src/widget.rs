@@ -2,31 +2,69 @@
 //! Widgets implement the [`Widget`] trait, containing a set of basic functions shared among all widgets.
 //! [`hyber`](`crate`) has a set of basic widgets implemented, each with their own module.
 
-use crate::event::Event;
+use crate::context::{Context, LayoutOverflow, WidgetPanic, WidgetPhase};
+use crate::event::{self, pointer_position, Event, PointerInfo};
+use crate::renderer::ClipShape;
 use crate::renderer::Message;
 use crate::renderer::RenderInstruction;
 use crate::renderer::RenderInstructionCollection;
+use crate::renderer::set_tree_dirty;
+use crate::style::Theme;
 use crate::util::IDMachine;
+use crate::util::MemoKey;
 use crate::util::Queue;
+use crate::util::Rect;
+use crate::util::Transform;
 use crate::util::Vector2D;
+use crate::widget::context_menu::ContextMenuItem;
 
-use std::cell::RefCell;
-use std::rc::Weak;
+use std::any::Any;
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::{Rc, Weak};
+use std::time::Instant;
 
+pub mod busy_overlay;
+pub mod button_group;
 pub mod button_view;
+pub mod calendar;
 pub mod checkbox;
+pub mod circular_progress;
+pub mod command_palette;
+pub mod console;
+pub mod context_menu;
+pub mod desktop;
+pub mod dialog;
+pub mod dock_area;
+pub mod dropdown;
+pub mod expandable_text;
 pub mod grid_view;
 pub mod icon;
+pub mod image;
+pub mod internal_window;
+pub mod keyboard_hints;
 pub mod label;
 pub mod list_view;
+pub mod palette;
 pub mod panel;
+pub mod path_input;
 pub mod progress_bar;
+pub mod radio;
 pub mod root;
+pub mod scroll_view;
+pub mod scrollbar;
+pub mod skeleton;
 pub mod slider;
 pub mod tab;
+pub mod tab_bar;
 pub mod textbox;
+pub mod time_picker;
+pub mod title_bar;
 pub mod sliver_view;
+pub mod stack;
 pub mod tooltip_view;
+pub mod window_resize_handle;
 
 /// Constraints that a parent imposes to its children
 ///
@@ -47,19 +85,187 @@ pub enum ConstraintType {
     },
 }
 
-// TODO: Not implemented
-/// <span style="color:red">NOT IMPLEMENTED.</span> Struct for flex properties (whether to fill the maximum possible area or have a specific size)
+/// Flex properties of a widget (i.e., how it is sized by a [`Layout::Flex`] parent)
 ///
-pub enum Num {
-    Num(usize),
-    Infinity,
+/// _**Note:** Based on Flutter documentation about flexible widgets at
+/// https://api.flutter.dev/flutter/widgets/Flexible-class.html
+#[derive(Clone, Copy, PartialEq)]
+pub struct Flex {
+    /// The widget's share of the space left along the parent's axis, relative to the
+    /// factors of its siblings, or zero to keep the size the widget desires
+    pub factor: f64,
+    /// The widget's minimum size (width and height)
+    pub min: Vector2D,
+    /// The widget's maximum size (width and height), infinite to be unbounded
+    pub max: Vector2D,
 }
 
-// TODO: Not implemented
-/// <span style="color:red">NOT IMPLEMENTED.</span> To be used for animation type. Part of the initial work of [`panel::PanelWidget`] widget.
-pub enum Animation {
-    Reveal,
-    Push,
+impl Flex {
+    /// Creates a new `Flex`, unbounded
+    ///
+    /// # Returns
+    /// The flex properties created
+    ///
+    /// # Arguments
+    /// * `factor` - the widget's share of the space left, or zero to keep its desired size
+    pub fn new(factor: f64) -> Flex {
+        Flex {
+            factor,
+            ..Flex::default()
+        }
+    }
+
+    /// Sets the widget's minimum size, returning the flex properties (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The flex properties with the minimum size assigned
+    ///
+    /// # Arguments
+    /// * `min` - the minimum size (width and height)
+    pub fn with_min(mut self, min: Vector2D) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Sets the widget's maximum size, returning the flex properties (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The flex properties with the maximum size assigned
+    ///
+    /// # Arguments
+    /// * `max` - the maximum size (width and height), infinite to be unbounded
+    pub fn with_max(mut self, max: Vector2D) -> Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Default for Flex {
+    fn default() -> Self {
+        Flex {
+            factor: 0.,
+            min: Vector2D::new(0., 0.),
+            max: Vector2D::new(f64::INFINITY, f64::INFINITY),
+        }
+    }
+}
+
+/// Sizing of a row or a column of a [`Layout::Grid`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GridTrack {
+    /// The track has a fixed length, in pixels
+    Fixed(f64),
+    /// The track takes a percentage (from 0 to 100) of the grid's length
+    Percentage(f64),
+    /// The track is as long as the largest length desired by the children placed only on it
+    Auto,
+}
+
+/// Cell of a [`Layout::Grid`] where a child is placed, spanning one or more rows and columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GridCell {
+    /// The index of the cell's first row
+    pub row: usize,
+    /// The index of the cell's first column
+    pub column: usize,
+    /// The number of rows spanned by the cell
+    pub row_span: usize,
+    /// The number of columns spanned by the cell
+    pub column_span: usize,
+}
+
+impl GridCell {
+    /// Creates a new `GridCell`, spanning a single row and column
+    ///
+    /// # Returns
+    /// The cell created
+    ///
+    /// # Arguments
+    /// * `row` - the index of the cell's row
+    /// * `column` - the index of the cell's column
+    pub fn new(row: usize, column: usize) -> GridCell {
+        GridCell {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    /// Sets the number of rows and columns spanned by the cell, returning the cell (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The cell with the spans assigned
+    ///
+    /// # Arguments
+    /// * `row_span` - the number of rows spanned by the cell, at least one
+    /// * `column_span` - the number of columns spanned by the cell, at least one
+    pub fn with_span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span.max(1);
+        self.column_span = column_span.max(1);
+        self
+    }
+}
+
+/// Sizing of the rows and columns of a [`Layout::Grid`] (see [`Widget::grid_template`])
+///
+/// The tracks (i.e., rows and columns) beyond the ones described share the space left
+/// evenly, so the default template lays out equally sized cells.
+///
+/// # Examples
+///
+/// ```
+/// use hyber::widget::{GridTemplate, GridTrack};
+///
+/// // A form with fixed width labels next to the fields taking the space left
+/// let template = GridTemplate::new()
+///     .with_columns(vec![GridTrack::Fixed(120.)])
+///     .with_rows(vec![GridTrack::Auto]);
+/// assert_eq!(template.columns[0], GridTrack::Fixed(120.));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GridTemplate {
+    /// The sizing of the grid's first columns, from the left one
+    pub columns: Vec<GridTrack>,
+    /// The sizing of the grid's first rows, from the top one
+    pub rows: Vec<GridTrack>,
+}
+
+impl GridTemplate {
+    /// Creates a new `GridTemplate`, laying out equally sized cells
+    ///
+    /// # Returns
+    /// The template created
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn new() -> GridTemplate {
+        GridTemplate::default()
+    }
+
+    /// Sets the sizing of the grid's first columns, returning the template (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The template with the columns assigned
+    ///
+    /// # Arguments
+    /// * `columns` - the sizing of the columns, from the left one
+    pub fn with_columns(mut self, columns: Vec<GridTrack>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the sizing of the grid's first rows, returning the template (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The template with the rows assigned
+    ///
+    /// # Arguments
+    /// * `rows` - the sizing of the rows, from the top one
+    pub fn with_rows(mut self, rows: Vec<GridTrack>) -> Self {
+        self.rows = rows;
+        self
+    }
 }
 
 /// Type of widget's layout
@@ -73,11 +279,21 @@ pub enum Layout {
     /// Box layout either stacks its components on top of each other
     /// or places them in a row
     Box(Axis),
-    /// Grid layout places components in a grid of cells
+    /// Grid layout places components in a grid of cells, flowing along the axis with
+    /// the given number of cells per line, unless sized and placed as described by the
+    /// widget's [`grid_template`](`Widget::grid_template`) and
+    /// [`grid_cell`](`Widget::grid_cell`)
     Grid(Axis, usize),
+    /// Flex layout places its components in a row or a column, like the box
+    /// layout, sharing the space left among the components with a flex factor
+    /// (see [`Widget::flex`])
+    Flex(Axis),
     /// Sliver layout is a portion of a scrollable area that can be
     /// defined to behave in a special way
     Sliver(Axis, f64, usize),
+    /// Custom layout places each component on the area given by the widget's
+    /// [`child_areas`](`Widget::child_areas`)
+    Custom,
     /// Layout undefined
     None,
 }
@@ -94,59 +310,1018 @@ pub enum Axis {
     Vertical,
 }
 
+/// Position of a widget in the focus traversal (i.e., the order in which tab moves the focus)
+///
+/// _**Note:** Based on the HTML `tabindex` attribute, at
+/// https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/tabindex
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TabIndex {
+    /// The widget is traversed in the order of its position after layout (i.e., row by row)
+    #[default]
+    Auto,
+    /// The widget is traversed before the automatic ones, in ascending order of index
+    Index(u32),
+    /// The widget is skipped by the traversal, although it can still be focused otherwise
+    Excluded,
+}
+
+/// Whether a widget consumed an event it handled, stopping its propagation
+///
+/// _**Note:** Based on the iced event status, at
+/// https://docs.rs/iced/latest/iced/event/enum.Status.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EventStatus {
+    /// The event was consumed by the widget (e.g. a click on a button), so the widgets
+    /// stacked under it and its ancestors do not handle it
+    Captured,
+    /// The event was not consumed, so it keeps propagating
+    #[default]
+    Ignored,
+}
+
+impl EventStatus {
+    /// Checks if the event was consumed
+    ///
+    /// # Returns
+    /// True, if the event was captured, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_captured(self) -> bool {
+        self == EventStatus::Captured
+    }
+
+    /// Merges the status of an event handled by several widgets
+    ///
+    /// # Returns
+    /// The captured status, if the event was captured by any of the widgets, or the
+    /// ignored status otherwise
+    ///
+    /// # Arguments
+    /// * `other` - the status to be merged with
+    pub fn merge(self, other: EventStatus) -> EventStatus {
+        if self.is_captured() || other.is_captured() {
+            EventStatus::Captured
+        } else {
+            EventStatus::Ignored
+        }
+    }
+}
+
+/// Propagates an event to the children of a widget, from the last one (i.e., drawn on
+/// top) to the first one
+///
+/// Once a child captures the event, the children under the cursor are skipped, so that
+/// stacked widgets do not handle the same click twice, while the children away from
+/// the cursor still get it (e.g. for a textbox to lose the focus when clicked outside).
+///
+/// # Returns
+/// The captured status, if any child captured the event, or the ignored status otherwise
+///
+/// # Arguments
+/// * `children` - the children of the widget
+/// * `event` - the event to be propagated
+/// * `messages` - the queue where the children place their messages
+pub fn propagate_event(
+    children: &[Weak<RefCell<dyn Widget>>],
+    event: Event,
+    messages: &mut Queue<Box<dyn Message>>,
+) -> EventStatus {
+    let pointer = pointer_position();
+    let mut status = EventStatus::Ignored;
+    for child in children.iter().rev().filter_map(Weak::upgrade) {
+        let mut child = child.borrow_mut();
+        if status.is_captured() {
+            let area = Rect::new(child.position(), child.size());
+            if pointer.is_some_and(|point| area.contains(point)) {
+                continue;
+            }
+        }
+        status = status.merge(deliver_event(&mut *child, event, messages));
+    }
+    status
+}
+
+/// Sends an event to a widget, unless it failed
+///
+/// While the panic guard is enabled (i.e., the context has a panic handler), a panic
+/// on the widget is caught and the widget is marked as failed, such that it gets no
+/// more events nor frames until it is rebuilt, while the other widgets still get the
+/// event.
+///
+/// # Returns
+/// The widget's status for the event, or the ignored status if the widget failed
+///
+/// # Arguments
+/// * `widget` - the widget to get the event
+/// * `event` - the event to be sent
+/// * `messages` - the queue where the widget places its messages
+pub fn deliver_event(
+    widget: &mut dyn Widget,
+    event: Event,
+    messages: &mut Queue<Box<dyn Message>>,
+) -> EventStatus {
+    guard_widget(widget, |widget| widget.on_event(event, messages)).unwrap_or(EventStatus::Ignored)
+}
+
+/// Updates the time-driven state of a widget (see [`Widget::on_frame`]), unless it
+/// failed, catching its panic the same way as [`deliver_event`]
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `widget` - the widget to be updated
+/// * `messages` - the queue where the widget places its messages
+pub fn deliver_frame(widget: &mut dyn Widget, messages: &mut Queue<Box<dyn Message>>) {
+    guard_widget(widget, |widget| widget.on_frame(messages));
+}
+
+/// Calls a widget while it has not failed, catching its panic if the panic guard is
+/// enabled, for internal use only
+///
+/// # Returns
+/// The result of the call, or `None` if the widget failed
+///
+/// # Arguments
+/// * `widget` - the widget to be called
+/// * `call` - the call to the widget
+fn guard_widget<R>(widget: &mut dyn Widget, call: impl FnOnce(&mut dyn Widget) -> R) -> Option<R> {
+    let id = widget.id();
+    if FAILED_WIDGETS.with(|failed| failed.borrow().contains(&id)) {
+        return None;
+    }
+    if !PANIC_GUARD.with(Cell::get) {
+        return Some(call(widget));
+    }
+    match panic::catch_unwind(AssertUnwindSafe(|| call(widget))) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            FAILED_WIDGETS.with(|failed| failed.borrow_mut().insert(id));
+            CAUGHT_PANICS.with(|panics| {
+                panics
+                    .borrow_mut()
+                    .push(WidgetPanic::new(id, WidgetPhase::Event, payload))
+            });
+            None
+        }
+    }
+}
+
+/// Starts delivering the events and frames of a context to its widgets on the current
+/// thread, guarding them with the context's panic guard and failed widgets, for
+/// internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `is_panic_guarded` - whether the panics on widgets are caught
+/// * `failed_widgets` - the identifiers of the context's widgets that failed
+pub(crate) fn begin_guarded_calls(is_panic_guarded: bool, failed_widgets: HashSet<usize>) {
+    PANIC_GUARD.with(|current| current.set(is_panic_guarded));
+    FAILED_WIDGETS.with(|failed| *failed.borrow_mut() = failed_widgets);
+}
+
+/// Ends delivering the events and frames of a context to its widgets, for internal
+/// use only
+///
+/// # Returns
+/// The panics caught meanwhile, and the identifiers of the widgets failed, to be
+/// kept by the context
+///
+/// # Arguments
+/// No arguments
+pub(crate) fn end_guarded_calls() -> (Vec<WidgetPanic>, HashSet<usize>) {
+    PANIC_GUARD.with(|current| current.set(false));
+    let panics = CAUGHT_PANICS.with(|panics| std::mem::take(&mut *panics.borrow_mut()));
+    let failed_widgets = FAILED_WIDGETS.with(|failed| std::mem::take(&mut *failed.borrow_mut()));
+    (panics, failed_widgets)
+}
+
+/// Gets the children of a widget along with its internal children, walked by the
+/// library (e.g. to mark a tree as dirty), for internal use only
+///
+/// # Returns
+/// The children still alive, followed by the internal children
+///
+/// # Arguments
+/// * `widget` - the widget whose children are read
+pub(crate) fn all_children(widget: &mut dyn Widget) -> Vec<Rc<RefCell<dyn Widget>>> {
+    let mut children: Vec<Rc<RefCell<dyn Widget>>> = widget
+        .get_children()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+    children.extend(widget.internal_children());
+    children
+}
+
+/// Trait that allows to provide a blanket implementation of downcasting for all
+/// widgets, without having to implement it on every widget.
+///
+/// This downcasting is used to get back to the concrete type of a widget stored
+/// as `dyn Widget` (e.g. to call [`label::LabelWidget::set_text`])
+pub trait WidgetAny {
+    /// Gets the widget as a mutable [`Any`], such that it can be downcasted
+    /// to its concrete type
+    ///
+    /// # Returns
+    /// The widget as a mutable [`Any`]
+    ///
+    /// # Arguments
+    /// No arguments
+    fn as_any(&mut self) -> &mut dyn Any;
+}
+
+impl<T> WidgetAny for T
+where
+    T: 'static + Widget,
+{
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Typed accessors for widget handles (i.e., pointers to `dyn Widget`)
+///
+/// Implemented for both [`Weak`] and [`Rc`] pointers to widgets.
+pub trait WidgetRef {
+    /// Executes a function over the concrete widget behind the handle
+    ///
+    /// # Returns
+    /// The value returned by `function`, or `None` if the widget no longer exists
+    /// or is not of type `T`
+    ///
+    /// # Arguments
+    /// * `function` - the function to be executed over the concrete widget
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let handle = Rc::downgrade(&label) as Weak<RefCell<dyn Widget>>;
+    ///
+    /// handle.with_widget(|label: &mut LabelWidget| label.set_text(String::from("Hello")));
+    /// ```
+    fn with_widget<T: Widget + 'static, R>(
+        &self,
+        function: impl FnOnce(&mut T) -> R,
+    ) -> Option<R>;
+
+    /// Gets the realized geometry of the widget behind the handle (i.e., its
+    /// position and size after layout)
+    ///
+    /// # Returns
+    /// The widget's position and size assigned on the last build, or `None` if
+    /// the widget no longer exists or was not built yet
+    ///
+    /// # Arguments
+    /// * `context` - the context of the event loop that built the widget
+    fn geometry(&self, context: &Context) -> Option<Rect>;
+
+    /// Checks if the widget behind the handle is of type `T`
+    ///
+    /// # Returns
+    /// True, if the widget exists and is of type `T`, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is<T: Widget + 'static>(&self) -> bool {
+        self.with_widget(|_: &mut T| ()).is_some()
+    }
+
+    /// Updates several properties of the concrete widget behind the handle at once,
+    /// marking its children as dirty only once, when the update ends
+    ///
+    /// Unlike [`with_widget`](`WidgetRef::with_widget`), it does not panic if the
+    /// widget is already borrowed (e.g. when called re-entrantly while the widget
+    /// handles an event).
+    ///
+    /// # Returns
+    /// The value returned by `update`, or `None` if the widget no longer exists, is
+    /// already borrowed or is not of type `T`
+    ///
+    /// # Arguments
+    /// * `update` - the function setting the properties of the concrete widget
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// panel.batch_update(|panel: &mut PanelWidget| {
+    ///     panel.set_is_clickable(true);
+    ///     panel.set_corner_radius(Some(4.));
+    /// });
+    /// ```
+    fn batch_update<T: Widget + 'static, R>(
+        &self,
+        update: impl FnOnce(&mut T) -> R,
+    ) -> Option<R>;
+
+    /// Sets the context menu of the widget behind the handle, opened at the cursor
+    /// when the widget is right-clicked (see [`Context::set_context_menu`])
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `items` - the items of the widget's context menu, or none to remove it
+    /// * `context` - the context of the event loop that builds the widget
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// label.set_context_menu(
+    ///     vec![ContextMenuItem::new("Copy", Some(Box::new(Copy::new())))],
+    ///     &mut context.borrow_mut(),
+    /// );
+    /// ```
+    fn set_context_menu(&self, items: Vec<ContextMenuItem>, context: &mut Context);
+
+    /// Attaches application data to the widget behind the handle (e.g. the identifier
+    /// of the row it displays), replacing the data attached before
+    ///
+    /// The data is stored on the widget (see [`Widget::widget_data`]) and dropped along
+    /// with it, so applications can retrieve it in their message handlers without
+    /// keeping maps alongside the widgets. The widgets holding no data ignore it.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `data` - the data to be attached to the widget
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// row.set_user_data(Box::new(RecordId(42)));
+    ///
+    /// assert_eq!(row.user_data::<RecordId>().map(|id| id.0), Some(42));
+    /// ```
+    fn set_user_data(&self, data: Box<dyn Any>);
+
+    /// Gets the application data attached to the widget behind the handle
+    ///
+    /// # Returns
+    /// The data attached, or `None` if the widget no longer exists, has no data
+    /// attached or the data is not of type `T`
+    ///
+    /// # Arguments
+    /// No arguments
+    fn user_data<T: 'static>(&self) -> Option<Rc<T>>;
+
+    /// Removes the application data attached to the widget behind the handle
+    ///
+    /// # Returns
+    /// The data removed, or `None` if the widget no longer exists or had no data attached
+    ///
+    /// # Arguments
+    /// No arguments
+    fn remove_user_data(&self) -> Option<Rc<dyn Any>>;
+
+    /// Sets the opacity of the widget behind the handle, multiplied into its render
+    /// instructions and the ones of its children (e.g. to fade it in or out), marking
+    /// them as dirty
+    ///
+    /// The opacity is stored on the widget (see [`Widget::widget_data`]), so the widgets
+    /// holding no data stay opaque.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `opacity` - the opacity to be assigned to the widget, from 0 (invisible) to 1 (opaque)
+    fn set_opacity(&self, opacity: f64);
+
+    /// Gets the opacity of the widget behind the handle
+    ///
+    /// # Returns
+    /// The widget's own opacity, from 0 (invisible) to 1 (opaque), regardless of its ancestors
+    ///
+    /// # Arguments
+    /// No arguments
+    fn opacity(&self) -> f64;
+}
+
+impl WidgetRef for Rc<RefCell<dyn Widget>> {
+    fn with_widget<T: Widget + 'static, R>(
+        &self,
+        function: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        let mut widget = self.borrow_mut();
+        widget.as_any().downcast_mut::<T>().map(function)
+    }
+
+    fn geometry(&self, context: &Context) -> Option<Rect> {
+        context.geometry(self.borrow().id())
+    }
+
+    fn batch_update<T: Widget + 'static, R>(
+        &self,
+        update: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        let mut widget = self.try_borrow_mut().ok()?;
+        let was_dirty = widget.is_dirty();
+        let result = {
+            let _batch = BatchDepth::enter(BATCH_DEPTH.with(Cell::get) + 1);
+            widget.as_any().downcast_mut::<T>().map(update)?
+        };
+        // Mark the children once, outside of any enclosing batch update
+        if !was_dirty && widget.is_dirty() {
+            let _batch = BatchDepth::enter(0);
+            widget.set_dirty(true);
+        }
+        Some(result)
+    }
+
+    fn set_context_menu(&self, items: Vec<ContextMenuItem>, context: &mut Context) {
+        context.set_context_menu(self, items);
+    }
+
+    fn set_user_data(&self, data: Box<dyn Any>) {
+        if let Some(widget_data) = self.borrow_mut().widget_data() {
+            widget_data.user_data = Some(Rc::from(data));
+        }
+    }
+
+    fn user_data<T: 'static>(&self) -> Option<Rc<T>> {
+        let data = self.borrow_mut().widget_data()?.user_data.clone();
+        data?.downcast::<T>().ok()
+    }
+
+    fn remove_user_data(&self) -> Option<Rc<dyn Any>> {
+        self.borrow_mut().widget_data()?.user_data.take()
+    }
+
+    fn set_opacity(&self, opacity: f64) {
+        if let Some(widget_data) = self.borrow_mut().widget_data() {
+            widget_data.opacity = Some(opacity.clamp(0., 1.)).filter(|opacity| *opacity < 1.);
+        }
+        // The children are faded when they are built
+        set_tree_dirty(self);
+    }
+
+    fn opacity(&self) -> f64 {
+        widget_opacity(&mut *self.borrow_mut())
+    }
+}
+
+impl WidgetRef for Weak<RefCell<dyn Widget>> {
+    fn with_widget<T: Widget + 'static, R>(
+        &self,
+        function: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        self.upgrade().and_then(|widget| widget.with_widget(function))
+    }
+
+    fn geometry(&self, context: &Context) -> Option<Rect> {
+        self.upgrade().and_then(|widget| widget.geometry(context))
+    }
+
+    fn batch_update<T: Widget + 'static, R>(
+        &self,
+        update: impl FnOnce(&mut T) -> R,
+    ) -> Option<R> {
+        self.upgrade().and_then(|widget| widget.batch_update(update))
+    }
+
+    fn set_context_menu(&self, items: Vec<ContextMenuItem>, context: &mut Context) {
+        if let Some(widget) = self.upgrade() {
+            widget.set_context_menu(items, context);
+        }
+    }
+
+    fn set_user_data(&self, data: Box<dyn Any>) {
+        if let Some(widget) = self.upgrade() {
+            widget.set_user_data(data);
+        }
+    }
+
+    fn user_data<T: 'static>(&self) -> Option<Rc<T>> {
+        self.upgrade().and_then(|widget| widget.user_data())
+    }
+
+    fn remove_user_data(&self) -> Option<Rc<dyn Any>> {
+        self.upgrade().and_then(|widget| widget.remove_user_data())
+    }
+
+    fn set_opacity(&self, opacity: f64) {
+        if let Some(widget) = self.upgrade() {
+            widget.set_opacity(opacity);
+        }
+    }
+
+    fn opacity(&self) -> f64 {
+        self.upgrade().map_or(1., |widget| widget.opacity())
+    }
+}
+
+/// Data attached to a widget through its handle (see [`WidgetRef`]), stored on the
+/// widget such that it is dropped along with it
+#[derive(Clone, Default)]
+pub struct WidgetData {
+    /// The application data attached to the widget
+    user_data: Option<Rc<dyn Any>>,
+
+    /// The widget's own opacity, or `None` if the widget is opaque
+    opacity: Option<f64>,
+}
+
+thread_local! {
+    /// The number of batch updates in progress on the current thread
+    static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// Whether the panics on widgets are caught (i.e., the context has a panic handler)
+    static PANIC_GUARD: Cell<bool> = const { Cell::new(false) };
+
+    /// The panics caught on widgets while handling events or frames, to be reported
+    static CAUGHT_PANICS: RefCell<Vec<WidgetPanic>> = const { RefCell::new(Vec::new()) };
+
+    /// The identifiers of the widgets that panicked since they were last built, among
+    /// the ones of the context whose events and frames are being delivered
+    static FAILED_WIDGETS: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
+/// Gets the opacity assigned to a widget, for internal use only
+///
+/// # Returns
+/// The widget's own opacity, or 1 if none was assigned
+///
+/// # Arguments
+/// * `widget` - the widget
+fn widget_opacity<W: Widget + ?Sized>(widget: &mut W) -> f64 {
+    widget
+        .widget_data()
+        .and_then(|widget_data| widget_data.opacity)
+        .unwrap_or(1.)
+}
+
+/// Gets the address of a widget, which identifies it across rebuilds, for internal use only
+///
+/// # Returns
+/// The widget's address
+///
+/// # Arguments
+/// * `widget` - the widget
+fn widget_address(widget: &Rc<RefCell<dyn Widget>>) -> usize {
+    widget.as_ptr() as *const () as usize
+}
+
+/// Sets the number of batch updates in progress, restoring the previous number when
+/// dropped (even if the update panics)
+struct BatchDepth {
+    /// The number of batch updates in progress before it was set
+    previous: usize,
+}
+
+impl BatchDepth {
+    /// Sets the number of batch updates in progress
+    ///
+    /// # Returns
+    /// The guard restoring the previous number when dropped
+    ///
+    /// # Arguments
+    /// * `depth` - the number of batch updates in progress
+    fn enter(depth: usize) -> BatchDepth {
+        BatchDepth {
+            previous: BATCH_DEPTH.with(|current| current.replace(depth)),
+        }
+    }
+}
+
+impl Drop for BatchDepth {
+    fn drop(&mut self) {
+        BATCH_DEPTH.with(|current| current.set(self.previous));
+    }
+}
+
+/// Checks whether a [`batch_update`](`WidgetRef::batch_update`) is in progress, in which
+/// case widgets only mark themselves as dirty, leaving their children to be marked
+/// once the update ends
+///
+/// # Returns
+/// True, if a batch update is in progress on the current thread, false otherwise
+///
+/// # Arguments
+/// No arguments
+pub fn is_batching_updates() -> bool {
+    BATCH_DEPTH.with(|current| current.get() > 0)
+}
+
 /// Widgets are part of a user interface. They can be rendered on the
 /// display and they can contain as many childs as they need. The root
 /// widget is at the top of the widget tree. He manages all the widgets
 /// to be displayed since they are childs of him. Then, all widgets
 /// have their own child tree.
-pub trait Widget {
+pub trait Widget: WidgetAny {
     /// Detect if the event is being done on this widget and then update the
     /// widget's state based on event. After that, a message is enqueded into
     /// the message queue.
     ///
+    /// Widgets usually propagate the events to their children with
+    /// [`propagate_event`], handling them only if no child captured them, such that
+    /// a click on a nested button is not handled by its parent as well.
+    ///
     /// # Returns
-    /// No returns
+    /// The status of the event, captured if the widget (or any of its children)
+    /// consumed it, stopping its propagation
     ///
     /// # Arguments
     /// * `event` - an hyber event
     /// * `messages` - queue of messages
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>);
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus;
+
+    /// Updates the widget's time-driven state once per frame, after the events are
+    /// handled (e.g. auto-scrolling while the cursor rests near an edge). By default
+    /// the call is passed to the children.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `messages` - queue of messages
+    fn on_frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        let count = self.get_children().len();
+        for index in 0..count {
+            if let Some(child) = self.get_children()[index].upgrade() {
+                deliver_frame(&mut *child.borrow_mut(), messages);
+            }
+        }
+        for child in self.internal_children() {
+            deliver_frame(&mut *child.borrow_mut(), messages);
+        }
+    }
+
+    /// Gets widget's identifier
+    ///
+    /// # Returns
+    /// The widget's identifier
+    ///
+    /// # Arguments
+    /// No arguments
+    fn id(&self) -> usize;
+
+    /// Sets widget's identifier
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `id` - the identifier of the widget
+    fn set_id(&mut self, id: usize);
+
+    /// Detect if the cursor is in the widget's area
+    ///
+    /// # Returns
+    /// True, if the cursor is in the widget's area, false otherwise
+    ///
+    /// # Arguments
+    /// * `cursor_pos` - the position of the cursor
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool;
+
+    /// Gets the collection of renderer instructions needed to draw this widget
+    ///
+    /// # Returns
+    /// The collection of renderer instructions needed to draw this widget
+    ///
+    /// # Arguments
+    /// No arguments
+    fn recipe(&self) -> Vec<RenderInstruction>;
+
+    /// Gets the hash of the inputs the widget's recipe depends on (e.g. text, colors
+    /// and size), to opt in to memoization
+    ///
+    /// While the hash and the area assigned to the widget stay the same, rebuilding
+    /// the widget keeps its previous render instructions instead of generating them
+    /// again. Widgets whose recipe depends on anything else (e.g. time) must not opt in.
+    ///
+    /// # Returns
+    /// The hash of the recipe's inputs, or `None` if the widget is not memoized
+    ///
+    /// # Arguments
+    /// No arguments
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn memo_key(&self) -> Option<u64> {
+    ///     Some(MemoKey::new().with(&self.text).with_color(self.color).finish())
+    /// }
+    /// ```
+    fn memo_key(&self) -> Option<u64> {
+        None
+    }
+
+    /// Gets the data attached to the widget through its handle (e.g. its user data)
+    ///
+    /// Widgets store it on a field, created with [`WidgetData::default`], such that it
+    /// is dropped along with them. The widgets that do not override this method hold
+    /// no data.
+    ///
+    /// # Returns
+    /// The widget's data, or `None` if the widget holds no data
+    ///
+    /// # Arguments
+    /// No arguments
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        None
+    }
+
+    /// Gets the key identifying the widget among its siblings, used to derive its
+    /// stable identifier instead of its index (e.g. for list items which are reordered)
+    ///
+    /// # Returns
+    /// The key identifying the widget, or `None` to be identified by its index
+    ///
+    /// # Arguments
+    /// No arguments
+    fn stable_key(&self) -> Option<u64> {
+        None
+    }
+
+    /// Checks if the widget is disabled
+    ///
+    /// The render instructions of disabled widgets are adjusted according to the
+    /// theme's [`DisabledStyle`](`crate::style::DisabledStyle`).
+    ///
+    /// # Returns
+    /// True if the widget is disabled, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_disabled(&self) -> bool {
+        false
+    }
+
+    /// Gets the position of the mouse cursor, both on the window and relative to the
+    /// widget, as of the event being handled (e.g. to handle a drag without computing
+    /// the position relative to the widget on every event)
+    ///
+    /// # Returns
+    /// The position of the cursor, or `None` if the cursor is outside the window or did
+    /// not move yet
+    ///
+    /// # Arguments
+    /// No arguments
+    fn pointer_info(&mut self) -> Option<PointerInfo> {
+        let origin = self.position();
+        pointer_position().map(|absolute| PointerInfo::new(absolute, origin))
+    }
+
+    /// Captures the mouse, such that every mouse event is dispatched to the widget only,
+    /// instead of the widget tree, until the mouse button is released (e.g. to keep a
+    /// drag going while the cursor is over other widgets or outside the window)
+    ///
+    /// The mouse is usually captured when a button is pressed on the widget. The
+    /// capture follows the widget across rebuilds, and is released with
+    /// [`release_pointer`](`crate::event::release_pointer`) or once the widget is no
+    /// longer in the widget tree.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn capture_pointer(&self) {
+        event::set_pointer_capture(Some(self as *const Self as *const () as usize));
+    }
+
+    /// Checks whether the widget is capturing the mouse
+    ///
+    /// # Returns
+    /// True, if the widget is capturing the mouse, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn has_pointer_capture(&self) -> bool {
+        event::pointer_capture() == Some(self as *const Self as *const () as usize)
+    }
+
+    /// Checks if the widget can be activated from the keyboard (e.g. by the hints of a
+    /// [`KeyboardHintsWidget`](`keyboard_hints::KeyboardHintsWidget`))
+    ///
+    /// # Returns
+    /// True if the widget can be activated, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Gets the widget's position in the focus traversal, among the focusable widgets
+    /// that are not disabled (see [`Context::tab_order`](`crate::context::Context::tab_order`))
+    ///
+    /// # Returns
+    /// The widget's tab index, automatic by default
+    ///
+    /// # Arguments
+    /// No arguments
+    fn tab_index(&self) -> TabIndex {
+        TabIndex::Auto
+    }
+
+    /// Gets how the widget is sized when its parent has a [`Layout::Flex`]
+    ///
+    /// # Returns
+    /// The widget's flex properties, keeping the size it desires by default
+    ///
+    /// # Arguments
+    /// No arguments
+    fn flex(&self) -> Flex {
+        Flex::default()
+    }
+
+    /// Activates the widget from the keyboard, as if it was clicked (e.g. a button
+    /// handles its message and a textbox gets the focus)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the keyboard event that activated the widget
+    /// * `messages` - queue of messages
+    fn activate(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) {}
+
+    /// Marks the widget as selected or not (e.g. by the
+    /// [`RubberBandSelection`](`crate::selection::RubberBandSelection`) of its parent),
+    /// such that it can display its selected state
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `_is_selected` - boolean indicating if the widget is selected
+    fn set_selected(&mut self, _is_selected: bool) {}
+
+    /// Checks if the widget's content is ready to be displayed
+    ///
+    /// Widgets loading their content (e.g. fetching data or decoding images) can
+    /// return false meanwhile, such that a [`skeleton::SkeletonWidget`] wrapping them
+    /// displays a placeholder until they are ready.
+    ///
+    /// # Returns
+    /// True, if the widget's content is ready, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_ready(&self) -> bool {
+        true
+    }
+
+    /// Checks if the widget's images are mirrored when the layout direction is right-to-left
+    ///
+    /// Useful for directional images (e.g. arrows or back buttons).
+    ///
+    /// # Returns
+    /// True if the widget's images are mirrored on right-to-left layouts, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn mirror_in_rtl(&self) -> bool {
+        false
+    }
+
+    /// Applies a theme to the widget (e.g. its colors and font size), except the
+    /// properties overridden by its [`Style`](`crate::style::Style`)
+    ///
+    /// The theme is cascaded to every widget of the tree by the root's `set_theme`
+    /// (see [`cascade_theme`](`crate::style::cascade_theme`)). By default, widgets are
+    /// not themed and keep their own colors.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `theme` - the theme to be applied
+    fn set_theme(&mut self, _theme: &Theme) {}
+
+    /// Gets the shape clipping the widget's children (e.g. the rounded corners of a card)
+    ///
+    /// The render instructions of every descendant are clipped to the shape, besides
+    /// their own clipping area. Since the widget's children are only rebuilt when dirty,
+    /// the widget must mark them as dirty whenever its shape changes.
+    ///
+    /// # Returns
+    /// The shape clipping the widget's children, or `None` if they are not clipped
+    ///
+    /// # Arguments
+    /// No arguments
+    fn clip_shape(&self) -> Option<ClipShape> {
+        None
+    }
+
+    /// Gets the transform of the widget's children (e.g. the rotation of a spinner or
+    /// the scale of a collapsing panel)
+    ///
+    /// The render instructions of every descendant are wrapped by transform
+    /// instructions, so that the children are rotated or scaled without recomputing
+    /// their geometry. Since the widget's children are only rebuilt when dirty, the
+    /// widget must mark them as dirty whenever its transform changes, and since the
+    /// children receive the cursor's position on the window, the widget should map it
+    /// back to their coordinates with [`Transform::inverse_apply`] before propagating
+    /// the mouse events.
+    ///
+    /// # Returns
+    /// The transform of the widget's children, or `None` if they are not transformed
+    ///
+    /// # Arguments
+    /// No arguments
+    fn transform(&self) -> Option<Transform> {
+        None
+    }
+
+    /// Gets the areas of the widget's children, when the widget has a [`Layout::Custom`]
+    ///
+    /// Each child is built on the area with the same index, while the children without
+    /// an area are hidden (i.e., their render instructions are removed until they get an
+    /// area again). Since the widget's children are only rebuilt when dirty, the widget
+    /// must mark them as dirty whenever their areas change.
+    ///
+    /// # Returns
+    /// The areas of the widget's children, in the order of the children, or `None` for
+    /// the hidden ones
+    ///
+    /// # Arguments
+    /// * `area` - the area assigned to the widget
+    fn child_areas(&mut self, _area: Rect) -> Vec<Option<Rect>> {
+        vec![]
+    }
+
+    /// Gets the sizing of the rows and columns, when the widget has a [`Layout::Grid`]
+    ///
+    /// # Returns
+    /// The grid's template, or `None` to lay out equally sized cells
+    ///
+    /// # Arguments
+    /// No arguments
+    fn grid_template(&self) -> Option<GridTemplate> {
+        None
+    }
 
-    /// Gets widget's identifier
+    /// Gets the cell where a child is placed, when the widget has a [`Layout::Grid`]
+    ///
+    /// The children with a cell are placed first, and the other ones flow along the
+    /// grid's axis into the cells left free. Since the cell is found by child, it
+    /// follows the child when the children before it are added or removed.
     ///
     /// # Returns
-    /// The widget's identifier
+    /// The child's cell, or `None` to flow the child along the grid's axis
     ///
     /// # Arguments
-    /// No arguments
-    fn id(&self) -> usize;
+    /// * `child` - the child
+    fn grid_cell(&self, _child: &Weak<RefCell<dyn Widget>>) -> Option<GridCell> {
+        None
+    }
 
-    /// Sets widget's identifier
+    /// Gets the widget's internal children (i.e., the widgets composing it, e.g. the
+    /// buttons of a spin box), which are private to the widget
+    ///
+    /// Unlike the children, the internal children are owned by the widget and are not
+    /// listed by [`get_children`](`Widget::get_children`), so outside code (e.g.
+    /// [`hit_test`] or the [`template`](`crate::template`) module) can neither reach nor
+    /// replace them. They are built after the children, over them, on the areas given
+    /// by [`internal_child_areas`](`Widget::internal_child_areas`), and are clipped,
+    /// transformed, themed and marked dirty along with the widget's tree, while the
+    /// widget passes them the events itself.
     ///
     /// # Returns
-    /// No returns
+    /// The widget's internal children, in drawing order
     ///
     /// # Arguments
-    /// * `id` - the identifier of the widget
-    fn set_id(&mut self, id: usize);
+    /// No arguments
+    fn internal_children(&self) -> Vec<Rc<RefCell<dyn Widget>>> {
+        vec![]
+    }
 
-    /// Detect if the cursor is in the widget's area
+    /// Gets the areas of the widget's internal children
+    ///
+    /// Each internal child is built on the area with the same index, while the ones
+    /// without an area are hidden, as with [`child_areas`](`Widget::child_areas`).
     ///
     /// # Returns
-    /// True, if the cursor is in the widget's area, false otherwise
+    /// The areas of the widget's internal children, in the order of the internal
+    /// children, or `None` for the hidden ones
     ///
     /// # Arguments
-    /// * `cursor_pos` - the position of the cursor
-    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool;
+    /// * `area` - the area assigned to the widget
+    fn internal_child_areas(&mut self, _area: Rect) -> Vec<Option<Rect>> {
+        vec![]
+    }
 
-    /// Gets the collection of renderer instructions needed to draw this widget
+    /// Gets the collection of renderer instructions to be drawn over the widget's children
+    ///
+    /// Useful for decorations that must not be covered by the children (e.g. scroll
+    /// indicators). The overlay is regenerated whenever the widget or any of its
+    /// children is rebuilt.
     ///
     /// # Returns
-    /// The collection of renderer instructions needed to draw this widget
+    /// The collection of renderer instructions to be drawn over the widget's children
     ///
     /// # Arguments
     /// No arguments
-    fn recipe(&self) -> Vec<RenderInstruction>;
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
 
     /// Mark the widget as dirty
     ///
@@ -375,6 +1550,18 @@ pub trait Widget {
     /// `offset` - the offset to be assigned to the widget
     fn set_offset(&mut self, offset: Vector2D);
 
+    /// Gets whether the widget's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// Whether the widget's render instructions are snapped to device pixels, or
+    /// `None` to follow the global setting of the [`Context`]
+    ///
+    /// # Arguments
+    /// No arguments
+    fn pixel_snapping(&self) -> Option<bool> {
+        None
+    }
+
     /// TODO: documentar
     fn set_clip_point(&mut self, clip_point: Option<Vector2D>);
 
@@ -388,6 +1575,7 @@ pub trait Widget {
     /// `y` - y-coordinate for the topleft corner
     /// `max_width` - maximum width that children can occupy. Should be equal to the width of the parent widget.
     /// `max_height` - maximum height that children can occupy. Should be equal to the height of the parent widget.
+    /// `context` - shared state of the event loop (e.g. the panic guard)
     ///
     /// # Examples
     ///
@@ -412,19 +1600,157 @@ pub trait Widget {
         mut max: Vector2D,
         id_machine: &mut IDMachine,
         instruction_collection: &mut RenderInstructionCollection,
+        context: &mut Context,
     ) {
-        if self.is_dirty() {
+        // The area assigned to the widget
+        let area = Rect::new(position, max);
+        // Whether the widget must be moved after the siblings rebuilt before it
+        let is_moving = context.take_is_moving();
+        // Fade the widget and its children by the widget's opacity
+        context.push_opacity(widget_opacity(self));
+        // The memo key of the widget, including the area, whether it is disabled, its
+        // opacity and the shapes clipping it
+        let memo_key = self.memo_key().map(|key| {
+            MemoKey::new()
+                .with(&key)
+                .with_vector(position)
+                .with_vector(max)
+                .with(&self.is_disabled())
+                .with_f64(context.opacity())
+                .with(&context.clip_key())
+                .finish()
+        });
+        // The stable identifier of the widget, if enabled, found by its address since
+        // the widgets never built share the same identifier
+        let stable_id =
+            context.enter_stable_id(self as *const Self as *const () as usize, self.stable_key());
+        // Whether the widget is the active modal scope, confining the focus traversal
+        let is_modal_scope = context.enter_modal_scope(self as *const Self as *const () as usize);
+        // Whether the widget itself is rebuilt
+        let is_rebuilt = self.is_dirty();
+        // Whether the widget kept its memoized render instructions and identifier
+        let mut is_kept = false;
+
+        if is_rebuilt {
             // Assign position of widget
             self.set_position(position);
             // Assign size of widget
             self.set_size(max);
 
+            // Instant when the widget started being rebuilt, used to diagnose long frames
+            let build_start = Instant::now();
+
+            // Keep the previous render instructions if the widget's inputs did not change,
+            // as well as their identifier, unless they must be drawn after the siblings
+            // rebuilt before. Either way, they are not redrawn
+            let memoized = match memo_key {
+                Some(memo_key) if context.memo(self.id()) == Some(memo_key) => {
+                    instruction_collection.pairs.remove(&self.id())
+                }
+                _ => None,
+            };
+            is_kept = memoized.is_some() && !is_moving;
+
             instruction_collection.remove(self.id());
-            self.set_id(id_machine.fetch_id());
-            instruction_collection.replace_or_insert(self.id(), self.recipe().clone());
+            context.remove_geometry(self.id());
+            context.remove_tab_stop(self.id());
+            context.remove_memo(self.id());
+            context.remove_stable_id(self.id());
+            context.forget_failure(self.id());
+            if !is_kept {
+                if let Some(overlay_id) = context.take_overlay(self.id()) {
+                    instruction_collection.remove(overlay_id);
+                }
+                self.set_id(id_machine.fetch_id());
+            }
+            context.watch_widget(self.id());
+            // Record the realized geometry of the widget
+            context.record_geometry(self.id(), area);
+            if self.is_focusable() && !self.is_disabled() && context.is_in_modal_scope() {
+                context.record_tab_stop(self.id(), self.tab_index());
+            }
+            let recipe_start = Instant::now();
+            match memoized {
+                Some(instructions) => {
+                    instruction_collection.pairs.insert(self.id(), instructions);
+                }
+                None => {
+                    // Get the widget's recipe, or an error placeholder if the widget panics
+                    // while the panic guard is enabled
+                    let mut instructions =
+                        context.guard_recipe(self.id(), position, max, || self.recipe());
+                    context.finish_recipe(&mut instructions, self);
+                    instruction_collection.replace_or_insert(self.id(), instructions);
+                }
+            }
+            let recipe_time = recipe_start.elapsed();
             self.set_dirty(false);
+            if let Some(memo_key) = memo_key {
+                context.set_memo(self.id(), memo_key);
+            }
+            context.record_widget_timing(self.id(), build_start.elapsed(), recipe_time);
+        }
+        // Record the stable identifier even if the widget was not rebuilt, since its
+        // index may have changed
+        if let Some(stable_id) = stable_id {
+            context.set_stable_id(self.id(), stable_id);
+        }
+
+        // Last identifier fetched before building the children, used to know
+        // whether any of them was rebuilt
+        let last_id = id_machine.last_id();
+
+        // Clip the children to the widget's shape, if any
+        let is_clipping = match self.clip_shape() {
+            Some(shape) => {
+                context.push_clip(shape);
+                true
+            }
+            None => false,
+        };
+        // Transform the children by the widget's transform, if any, within its shape
+        let is_transforming = match self.transform() {
+            Some(transform) if !transform.is_identity() => {
+                context.push_transform(transform);
+                true
+            }
+            _ => false,
+        };
+
+        // Let the children derive their stable identifiers from the widget's
+        if let Some(stable_id) = stable_id {
+            let child_addresses = self
+                .get_children()
+                .iter()
+                .map(|value| {
+                    value
+                        .upgrade()
+                        .map_or(0, |child| child.as_ptr() as *const () as usize)
+                })
+                .collect();
+            context.push_stable_scope(stable_id, child_addresses);
         }
 
+        // Areas of the children, if the widget places them itself
+        let child_areas = match self.layout() {
+            Layout::Custom => self.child_areas(area),
+            _ => vec![],
+        };
+        // Sizing of the rows and columns and cells of the children, if laid out on a grid
+        let (grid_template, grid_cells) = match self.layout() {
+            Layout::Grid(..) => {
+                let children = self.get_children().clone();
+                let cells = children.iter().map(|child| self.grid_cell(child)).collect();
+                (self.grid_template().unwrap_or_default(), cells)
+            }
+            _ => (GridTemplate::default(), vec![]),
+        };
+
+        // Children built with a size smaller than they desire, along with the position
+        // of their area, if diagnosed
+        let is_overflow_diagnostics = context.is_overflow_diagnostics();
+        let mut overflows: Vec<(Vector2D, LayoutOverflow)> = Vec::new();
+
         // Get children, layout, and offset of widget
         let (_, children, _, size, _, layout, offset) = self.get_fields();
 
@@ -450,13 +1776,14 @@ pub trait Widget {
                     if let Some(child) = value.upgrade() {
                         if children_dirty {
                             child.borrow_mut().set_dirty(true);
-                        } else if child.borrow_mut().is_dirty() {
-                            children_dirty = true;
+                            context.move_next_widget();
                         }
+                        let id = child.borrow().id();
 
                         // Get original child dimensions and do something to handle
                         // the dimensions assigned to the child
-                        child_size = child.borrow_mut().original_size().min(max);
+                        let desired_size = child.borrow_mut().original_size();
+                        child_size = desired_size.min(max);
 
                         // Pass the child the assigned dimensions
                         child.borrow_mut().build(
@@ -464,7 +1791,20 @@ pub trait Widget {
                             child_size,
                             id_machine,
                             instruction_collection,
+                            context,
                         );
+                        // Rebuild the next children only if this one moved to a new identifier
+                        if child.borrow().id() != id {
+                            children_dirty = true;
+                        }
+                        if is_overflow_diagnostics {
+                            overflows.extend(find_overflow(
+                                &*child.borrow(),
+                                position,
+                                desired_size,
+                                child_size,
+                            ));
+                        }
                         // Update the constraints and position of next child
                         match axis {
                             Axis::Horizontal => {
@@ -479,157 +1819,823 @@ pub trait Widget {
                     }
                 }
             }
-            Layout::Grid(axis, axis_length) => match axis {
-                Axis::Vertical => {
-                    let cell_size = size
-                        / Vector2D::new(
-                            *axis_length as f64,
-                            children.len() as f64 / *axis_length as f64,
-                        );
+            Layout::Flex(axis) => {
+                // Update maximum dimensions according to offset
+                max -= offset * 2.;
 
-                    let mut i: usize = 0;
-                    for value in children.iter_mut() {
-                        if let Some(child) = value.upgrade() {
-                            let child_size = child.borrow_mut().original_size().min(cell_size);
-
-                            // Pass the child the assigned dimensions
-                            child.borrow_mut().build(
-                                position
-                                    + cell_size
-                                        * Vector2D::new(
-                                            (i % *axis_length) as f64,
-                                            (i / *axis_length) as f64,
-                                        ),
-                                child_size,
-                                id_machine,
-                                instruction_collection,
-                            );
-                            i += 1;
+                // Update position of first child
+                position += offset;
+
+                let children: Vec<Rc<RefCell<dyn Widget>>> =
+                    children.iter().filter_map(Weak::upgrade).collect();
+                let properties: Vec<(Vector2D, Flex)> = children
+                    .iter()
+                    .map(|child| {
+                        let mut child = child.borrow_mut();
+                        (child.original_size(), child.flex())
+                    })
+                    .collect();
+                let sizes = flex_sizes(*axis, max, &properties);
+
+                let mut children_dirty = false;
+
+                for ((child, child_size), (desired_size, _)) in
+                    children.iter().zip(sizes).zip(properties)
+                {
+                    // Rebuild the children whose area changed, since the space left is
+                    // shared among them, and the children after a rebuilt one, so that
+                    // they keep being drawn over it
+                    {
+                        let mut child = child.borrow_mut();
+                        if children_dirty {
+                            child.set_dirty(true);
+                            context.move_next_widget();
+                        } else if child.position() != position || child.size() != child_size {
+                            child.set_dirty(true);
                         }
                     }
+                    let id = child.borrow().id();
+
+                    // Pass the child the assigned dimensions
+                    child.borrow_mut().build(
+                        position,
+                        child_size,
+                        id_machine,
+                        instruction_collection,
+                        context,
+                    );
+                    // Rebuild the next children only if this one moved to a new identifier
+                    if child.borrow().id() != id {
+                        children_dirty = true;
+                    }
+                    if is_overflow_diagnostics {
+                        overflows.extend(find_overflow(
+                            &*child.borrow(),
+                            position,
+                            desired_size,
+                            child_size,
+                        ));
+                    }
+                    // Update the position of next child
+                    match axis {
+                        Axis::Horizontal => position.x += child_size.x,
+                        Axis::Vertical => position.y += child_size.y,
+                    };
                 }
-                Axis::Horizontal => {
-                    let cell_size = size
-                        / Vector2D::new(
-                            children.len() as f64 / *axis_length as f64,
-                            *axis_length as f64,
-                        );
+            }
+            Layout::Grid(axis, axis_length) => {
+                let desired_sizes: Vec<Option<Vector2D>> = children
+                    .iter()
+                    .map(|value| value.upgrade().map(|child| child.borrow_mut().original_size()))
+                    .collect();
+                let areas = grid_areas(
+                    *axis,
+                    *axis_length,
+                    &grid_template,
+                    &grid_cells,
+                    Rect::new(position, size),
+                    &desired_sizes,
+                );
 
-                    let mut i: usize = 0;
-                    for value in children.iter_mut() {
-                        if let Some(child) = value.upgrade() {
-                            let child_size = child.borrow_mut().original_size().min(cell_size);
-                            // Pass the child the assigned dimensions
-                            child.borrow_mut().build(
-                                position
-                                    + cell_size
-                                        * Vector2D::new(
-                                            (i / *axis_length) as f64,
-                                            (i % *axis_length) as f64,
-                                        ),
+                for ((value, child_area), desired_size) in
+                    children.iter_mut().zip(areas).zip(desired_sizes)
+                {
+                    if let (Some(child), Some(child_area), Some(desired_size)) =
+                        (value.upgrade(), child_area, desired_size)
+                    {
+                        let child_size = desired_size.min(child_area.size);
+                        // Pass the child the assigned dimensions
+                        child.borrow_mut().build(
+                            child_area.position,
+                            child_size,
+                            id_machine,
+                            instruction_collection,
+                            context,
+                        );
+                        if is_overflow_diagnostics {
+                            overflows.extend(find_overflow(
+                                &*child.borrow(),
+                                child_area.position,
+                                desired_size,
                                 child_size,
-                                id_machine,
-                                instruction_collection,
-                            );
-                            i += 1;
+                            ));
                         }
                     }
                 }
-            },
+            }
             Layout::Sliver(axis, shift, start) => {
-                // For children size
-                let mut child_size: Vector2D;
+                // The visible part of the content, where the children are clipped (see
+                // the sliver's clip shape)
+                let viewport = Rect::new(position, max);
 
                 let mut children_dirty = false;
 
-                let mut mutable_shift = *shift;
-
-                for value in children.as_mut_slice()[*start..].iter_mut() {
-                    // Stop if no more widgets can be drawn
-                    if max.x == 0. || max.y == 0. {
-                        break;
-                    }
+                // The position of the next child, scrolled by the shift along the axis
+                let mut child_position = match axis {
+                    Axis::Horizontal => Vector2D::new(position.x - *shift, position.y),
+                    Axis::Vertical => Vector2D::new(position.x, position.y - *shift),
+                };
 
+                for value in children.get_mut(*start..).unwrap_or_default().iter_mut() {
                     if let Some(child) = value.upgrade() {
-                        if children_dirty {
-                            child.borrow_mut().set_dirty(true);
-                        } else if child.borrow_mut().is_dirty() {
-                            children_dirty = true;
-                        }
-
-                        // Get original child dimensions
-                        child_size = child.borrow_mut().original_size();
-
-                        // Update the constraints and position of next child
-                        match axis {
+                        // Get original child dimensions, fitting the viewport across the axis
+                        let mut child_size = child.borrow_mut().original_size();
+                        let child_area = match axis {
                             Axis::Horizontal => {
-                                // Update position of child
-                                position.x -= mutable_shift;
-                                // Update maximum constraints
-                                max.x += mutable_shift;
-                                
-                                // Update height of child
                                 child_size.y = child_size.y.min(max.y);
-
-                                // Update clipping of child
-                                child.borrow_mut().set_clip_point(Some(position));
-                                child.borrow_mut().set_clip_size(Some(child_size.min(max)));
-
-                                // Pass the child the assigned dimensions
-                                child.borrow_mut().build(
-                                    position,
-                                    child_size,
-                                    id_machine,
-                                    instruction_collection,
-                                );
-                                
-                                max.x -= child_size.x;
-                                position.x += child_size.x;
+                                let child_area = Rect::new(child_position, child_size);
+                                child_position.x += child_size.x;
+                                child_area
                             }
                             Axis::Vertical => {
-                                // Update position of child
-                                position.y -= mutable_shift;
-                                // Update maximum constraints
-                                max.y += mutable_shift;
-                                
-                                // Update width of child
                                 child_size.x = child_size.x.min(max.x);
+                                let child_area = Rect::new(child_position, child_size);
+                                child_position.y += child_size.y;
+                                child_area
+                            }
+                        };
 
-                                // Update clipping of child
-                                child.borrow_mut().set_clip_point(Some(position));
-                                child.borrow_mut().set_clip_size(Some(child_size.min(max)));
-
-                                // Pass the child the assigned dimensions
-                                child.borrow_mut().build(
-                                    position,
-                                    child_size,
-                                    id_machine,
-                                    instruction_collection,
-                                );
-                                
-                                max.y -= child_size.y;
-                                position.y += child_size.y;
+                        // Only the children intersecting the viewport are built, so the
+                        // ones scrolled out of it are removed from the display, once
+                        if !viewport.intersects(child_area) {
+                            let id = child.borrow().id();
+                            if context.geometry(id).is_some() {
+                                hide_tree(&mut *child.borrow_mut(), instruction_collection, context);
+                            }
+                            continue;
+                        }
+
+                        // Rebuild the children after a rebuilt one, so that they keep
+                        // being drawn over it
+                        if children_dirty {
+                            child.borrow_mut().set_dirty(true);
+                            context.move_next_widget();
+                        }
+                        let id = child.borrow().id();
+
+                        // Pass the child the assigned dimensions
+                        child.borrow_mut().build(
+                            child_area.position,
+                            child_size,
+                            id_machine,
+                            instruction_collection,
+                            context,
+                        );
+                        // Rebuild the next children only if this one moved to a new identifier
+                        if child.borrow().id() != id {
+                            children_dirty = true;
+                        }
+                    }
+                }
+            }
+            Layout::Custom => {
+                let mut children_dirty = false;
+
+                for (index, value) in children.iter_mut().enumerate() {
+                    if let Some(child) = value.upgrade() {
+                        let child_area = match child_areas.get(index).copied().flatten() {
+                            Some(child_area) => child_area,
+                            None => {
+                                // Remove the hidden child from the display, once
+                                let id = child.borrow().id();
+                                if context.geometry(id).is_some() {
+                                    hide_tree(&mut *child.borrow_mut(), instruction_collection, context);
+                                }
+                                continue;
                             }
                         };
+
+                        // Rebuild the children after a rebuilt one, so that they keep
+                        // being drawn over it
+                        if children_dirty {
+                            child.borrow_mut().set_dirty(true);
+                            context.move_next_widget();
+                        }
+                        let id = child.borrow().id();
+
+                        // Pass the child the assigned area
+                        child.borrow_mut().build(
+                            child_area.position,
+                            child_area.size,
+                            id_machine,
+                            instruction_collection,
+                            context,
+                        );
+                        // Rebuild the next children only if this one moved to a new identifier
+                        if child.borrow().id() != id {
+                            children_dirty = true;
+                        }
                     }
-                    
-                    mutable_shift = 0.;
                 }
             }
             Layout::None => {
                 for value in children.iter_mut() {
                     if let Some(child) = value.upgrade() {
-                        let child_size = child.borrow_mut().original_size().min(max);
+                        let desired_size = child.borrow_mut().original_size();
+                        let child_size = desired_size.min(max);
                         // Pass the child the assigned dimensions
                         child.borrow_mut().build(
                             position,
                             child_size,
                             id_machine,
                             instruction_collection,
+                            context,
                         );
+                        if is_overflow_diagnostics {
+                            overflows.extend(find_overflow(
+                                &*child.borrow(),
+                                position,
+                                desired_size,
+                                child_size,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Build the internal children over the children, within the widget's shape
+        let internal_children = self.internal_children();
+        if !internal_children.is_empty() {
+            let internal_areas = self.internal_child_areas(area);
+            // Rebuild them after the widget or a rebuilt child, so that they keep being
+            // drawn over them
+            let mut children_dirty = (is_rebuilt && !is_kept) || id_machine.last_id() != last_id;
+            for (index, child) in internal_children.iter().enumerate() {
+                let child_area = match internal_areas.get(index).copied().flatten() {
+                    Some(child_area) => child_area,
+                    None => {
+                        // Remove the hidden internal child from the display, once
+                        let id = child.borrow().id();
+                        if context.geometry(id).is_some() {
+                            hide_tree(&mut *child.borrow_mut(), instruction_collection, context);
+                        }
+                        continue;
                     }
+                };
+                if children_dirty {
+                    child.borrow_mut().set_dirty(true);
+                    context.move_next_widget();
+                }
+                let id = child.borrow().id();
+                child.borrow_mut().build(
+                    child_area.position,
+                    child_area.size,
+                    id_machine,
+                    instruction_collection,
+                    context,
+                );
+                // Rebuild the next children only if this one moved to a new identifier
+                if child.borrow().id() != id {
+                    children_dirty = true;
+                }
+            }
+        }
+
+        if is_transforming {
+            context.pop_transform();
+        }
+        if is_clipping {
+            context.pop_clip();
+        }
+        if stable_id.is_some() {
+            context.pop_stable_scope();
+        }
+        if is_modal_scope {
+            context.exit_modal_scope();
+        }
+
+        // Draw the overlay over the children, with an identifier fetched after them,
+        // whenever the widget or any of its children was rebuilt
+        if (is_rebuilt && !is_kept) || id_machine.last_id() != last_id {
+            if let Some(overlay_id) = context.take_overlay(self.id()) {
+                instruction_collection.remove(overlay_id);
+            }
+            let mut overlay = context.guard_recipe(self.id(), area.position, area.size, || {
+                self.overlay_recipe()
+            });
+            // Report the children overflowing their constraints, marking them on the overlay
+            for (position, overflow) in overflows.iter() {
+                overlay.extend(context.report_overflow(*position, overflow));
+            }
+            if !overlay.is_empty() {
+                context.finish_recipe(&mut overlay, self);
+                let overlay_id = id_machine.fetch_id();
+                instruction_collection.replace_or_insert(overlay_id, overlay);
+                context.set_overlay(self.id(), overlay_id);
+            }
+        }
+        context.pop_opacity();
+    }
+}
+
+/// Gets the diagnostic of a child built with a size smaller than the size it desires
+///
+/// # Returns
+/// The diagnostic of the overflowing child, or `None` if it fits its assigned size
+///
+/// # Arguments
+/// * `child` - the child built
+/// * `position` - the position of the area assigned to the child
+/// * `desired_size` - the size (width and height) desired by the child
+/// * `assigned_size` - the size (width and height) assigned to the child
+fn find_overflow(
+    child: &dyn Widget,
+    position: Vector2D,
+    desired_size: Vector2D,
+    assigned_size: Vector2D,
+) -> Option<(Vector2D, LayoutOverflow)> {
+    if desired_size.x <= assigned_size.x && desired_size.y <= assigned_size.y {
+        return None;
+    }
+    Some((
+        position,
+        LayoutOverflow {
+            widget_id: child.id(),
+            desired_size,
+            assigned_size,
+        },
+    ))
+}
+
+/// Computes the areas of the children of a [`Layout::Grid`], for internal use only
+///
+/// The children with a cell are placed first, and the other ones flow
+/// along the axis into the free cells, with `axis_length` cells per line (i.e., per
+/// row when vertical, or per column when horizontal). The grid has as many rows and
+/// columns as needed to hold every cell.
+///
+/// # Returns
+/// The area of each child, in order, or `None` for the children no longer alive
+///
+/// # Arguments
+/// * `axis` - the axis along which the children flow
+/// * `axis_length` - the number of cells per line
+/// * `template` - the sizing of the rows and columns
+/// * `child_cells` - the cell of each child, or `None` to flow it
+/// * `area` - the area of the grid
+/// * `desired_sizes` - the size desired by each child, or `None` if no longer alive
+fn grid_areas(
+    axis: Axis,
+    axis_length: usize,
+    template: &GridTemplate,
+    child_cells: &[Option<GridCell>],
+    area: Rect,
+    desired_sizes: &[Option<Vector2D>],
+) -> Vec<Option<Rect>> {
+    let axis_length = axis_length.max(1);
+    let mut cells: Vec<Option<GridCell>> = vec![None; desired_sizes.len()];
+    let mut occupied: HashSet<(usize, usize)> = HashSet::new();
+
+    // The children with a cell are placed first
+    for (index, desired_size) in desired_sizes.iter().enumerate() {
+        if let (Some(_), Some(Some(cell))) = (desired_size, child_cells.get(index)) {
+            let cell = cell.with_span(cell.row_span, cell.column_span);
+            for row in cell.row..cell.row + cell.row_span {
+                for column in cell.column..cell.column + cell.column_span {
+                    occupied.insert((row, column));
                 }
             }
+            cells[index] = Some(cell);
+        }
+    }
+
+    // The other children flow into the free cells
+    let mut next = 0;
+    for (index, desired_size) in desired_sizes.iter().enumerate() {
+        if desired_size.is_none() || cells[index].is_some() {
+            continue;
+        }
+        loop {
+            let (row, column) = match axis {
+                Axis::Vertical => (next / axis_length, next % axis_length),
+                Axis::Horizontal => (next % axis_length, next / axis_length),
+            };
+            next += 1;
+            if occupied.insert((row, column)) {
+                cells[index] = Some(GridCell::new(row, column));
+                break;
+            }
+        }
+    }
+
+    let column_count = cells
+        .iter()
+        .flatten()
+        .map(|cell| cell.column + cell.column_span)
+        .chain([template.columns.len()])
+        .chain(matches!(axis, Axis::Vertical).then_some(axis_length))
+        .max()
+        .unwrap_or(0);
+    let row_count = cells
+        .iter()
+        .flatten()
+        .map(|cell| cell.row + cell.row_span)
+        .chain([template.rows.len()])
+        .chain(matches!(axis, Axis::Horizontal).then_some(axis_length))
+        .max()
+        .unwrap_or(0);
+    let column_sizes = grid_track_sizes(
+        &template.columns,
+        column_count,
+        area.size.x,
+        cells.iter().zip(desired_sizes).filter_map(|(cell, desired_size)| {
+            Some((cell.as_ref()?.column, cell.as_ref()?.column_span, desired_size.as_ref()?.x))
+        }),
+    );
+    let row_sizes = grid_track_sizes(
+        &template.rows,
+        row_count,
+        area.size.y,
+        cells.iter().zip(desired_sizes).filter_map(|(cell, desired_size)| {
+            Some((cell.as_ref()?.row, cell.as_ref()?.row_span, desired_size.as_ref()?.y))
+        }),
+    );
+
+    let start = |sizes: &[f64], index: usize| -> f64 { sizes[..index].iter().sum() };
+    cells
+        .iter()
+        .map(|cell| {
+            cell.map(|cell| {
+                Rect::new(
+                    area.position
+                        + Vector2D::new(
+                            start(&column_sizes, cell.column),
+                            start(&row_sizes, cell.row),
+                        ),
+                    Vector2D::new(
+                        column_sizes[cell.column..cell.column + cell.column_span]
+                            .iter()
+                            .sum(),
+                        row_sizes[cell.row..cell.row + cell.row_span].iter().sum(),
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Computes the lengths of the rows or columns of a [`Layout::Grid`], for internal use only
+///
+/// # Returns
+/// The length of each track, in order
+///
+/// # Arguments
+/// * `tracks` - the sizing of the first tracks, the other ones sharing the space left evenly
+/// * `count` - the number of tracks
+/// * `length` - the grid's length across the tracks
+/// * `children` - the first track, the number of tracks spanned and the length desired by each child
+fn grid_track_sizes<I: Iterator<Item = (usize, usize, f64)>>(
+    tracks: &[GridTrack],
+    count: usize,
+    length: f64,
+    children: I,
+) -> Vec<f64> {
+    // The automatic tracks fit the children placed only on them
+    let mut auto_sizes = vec![0.; count];
+    for (track, span, desired_length) in children {
+        if span == 1 {
+            auto_sizes[track] = f64::max(auto_sizes[track], desired_length);
+        }
+    }
+    let mut sizes: Vec<Option<f64>> = (0..count)
+        .map(|index| match tracks.get(index) {
+            Some(GridTrack::Fixed(fixed)) => Some(*fixed),
+            Some(GridTrack::Percentage(percentage)) => Some(length * percentage / 100.),
+            Some(GridTrack::Auto) => Some(auto_sizes[index]),
+            None => None,
+        })
+        .collect();
+    let taken: f64 = sizes.iter().flatten().sum();
+    let shared_count = sizes.iter().filter(|size| size.is_none()).count();
+    let share = (length - taken).max(0.) / shared_count.max(1) as f64;
+    for size in sizes.iter_mut() {
+        size.get_or_insert(share);
+    }
+    sizes.into_iter().flatten().collect()
+}
+
+/// Computes the sizes of the children of a [`Layout::Flex`], for internal use only
+///
+/// The children without a flex factor keep their desired size, in order, as long as
+/// there is space left along the axis. The space they leave is then shared among the
+/// children with a flex factor, proportionally to it. The children whose share
+/// violates their minimum or maximum size are assigned that size instead, and the
+/// space left is shared again among the others.
+///
+/// # Returns
+/// The size (width and height) assigned to each child, in order
+///
+/// # Arguments
+/// * `axis` - the axis along which the children are placed
+/// * `available` - the size (width and height) available to the children
+/// * `children` - the size desired by each child, along with its flex properties
+fn flex_sizes(axis: Axis, available: Vector2D, children: &[(Vector2D, Flex)]) -> Vec<Vector2D> {
+    let main = |size: Vector2D| match axis {
+        Axis::Horizontal => size.x,
+        Axis::Vertical => size.y,
+    };
+    let with_main = |size: Vector2D, length: f64| match axis {
+        Axis::Horizontal => Vector2D::new(length, size.y),
+        Axis::Vertical => Vector2D::new(size.x, length),
+    };
+
+    // The children fit the available space across the axis
+    let mut sizes: Vec<Vector2D> = children
+        .iter()
+        .map(|(desired_size, flex)| desired_size.clamp(flex.min, flex.max.min(available)))
+        .collect();
+
+    // The children without a flex factor take their size first
+    let mut space = main(available);
+    let mut is_frozen: Vec<bool> = Vec::with_capacity(children.len());
+    for (size, (_, flex)) in sizes.iter_mut().zip(children) {
+        let is_fixed = flex.factor <= 0.;
+        if is_fixed {
+            let length = main(*size).min(space).max(main(flex.min));
+            *size = with_main(*size, length);
+            space = (space - length).max(0.);
+        }
+        is_frozen.push(is_fixed);
+    }
+
+    // The flexible children share the space left, until none violates its constraints
+    let space_left = space;
+    loop {
+        let factors: f64 = children
+            .iter()
+            .zip(&is_frozen)
+            .filter(|(_, is_frozen)| !**is_frozen)
+            .map(|((_, flex), _)| flex.factor)
+            .sum();
+        if factors <= 0. {
+            break;
+        }
+        let mut is_violated = false;
+        for ((size, is_frozen), (_, flex)) in
+            sizes.iter_mut().zip(is_frozen.iter_mut()).zip(children)
+        {
+            if *is_frozen {
+                continue;
+            }
+            let share = space * flex.factor / factors;
+            let length = share.min(main(flex.max)).max(main(flex.min));
+            *size = with_main(*size, length);
+            if length != share {
+                *is_frozen = true;
+                is_violated = true;
+            }
+        }
+        if !is_violated {
+            break;
+        }
+        // Share again the space left by the children frozen at their constraints
+        let frozen_length: f64 = sizes
+            .iter()
+            .zip(&is_frozen)
+            .zip(children)
+            .filter(|((_, is_frozen), (_, flex))| **is_frozen && flex.factor > 0.)
+            .map(|((size, _), _)| main(*size))
+            .sum();
+        space = (space_left - frozen_length).max(0.);
+    }
+    sizes
+}
+
+/// Gets the widget capturing the mouse, which a mouse event is dispatched to instead
+/// of the widget tree, for internal use only
+///
+/// The capture is released if the widget is no longer in any of the trees.
+///
+/// # Returns
+/// The widget capturing the mouse, if the event is a mouse event, or `None` otherwise
+///
+/// # Arguments
+/// * `event` - the event about to be dispatched
+/// * `roots` - the roots of the trees where the widget is searched (e.g. the popups)
+pub(crate) fn pointer_capture_target(
+    event: &Event,
+    roots: &[Rc<RefCell<dyn Widget>>],
+) -> Option<Rc<RefCell<dyn Widget>>> {
+    let address = match event {
+        Event::Mouse(_) => event::pointer_capture()?,
+        _ => return None,
+    };
+    let widget = roots.iter().find_map(|root| find_widget(root, address));
+    if widget.is_none() {
+        event::release_pointer();
+    }
+    widget
+}
+
+/// Finds the widgets under a point, from a widget down to the topmost widget under
+/// the point (i.e., the last child built containing the point, at every level)
+///
+/// The widgets are hit tested against their realized geometry, so only the widgets
+/// built are found.
+///
+/// # Returns
+/// The widgets under the point, starting with the given widget
+///
+/// # Arguments
+/// * `widget` - the widget where the search starts (e.g. the root)
+/// * `point` - the point, relative to the window's upper left corner
+/// * `context` - the context of the event loop that built the widgets
+pub fn hit_test(
+    widget: &Rc<RefCell<dyn Widget>>,
+    point: Vector2D,
+    context: &Context,
+) -> Vec<Rc<RefCell<dyn Widget>>> {
+    let mut path = vec![widget.clone()];
+    loop {
+        let children = path[path.len() - 1].borrow_mut().get_children().clone();
+        let topmost = children.iter().rev().filter_map(Weak::upgrade).find(|child| {
+            context
+                .geometry(child.borrow().id())
+                .is_some_and(|area| area.contains(point))
+        });
+        match topmost {
+            Some(child) => path.push(child),
+            None => return path,
+        }
+    }
+}
+
+/// Dispatches an event to a widget, which forwards it to its children, unless the
+/// event is routed by hit test, for internal use only
+///
+/// The events routed by hit test are delivered to the widgets under the cursor
+/// instead, from the topmost one up to the widget (i.e., bubbling) until a widget
+/// captures them, each without its children so that no widget gets the event twice.
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `target` - the widget the event is dispatched to (e.g. the root)
+/// * `event` - the event to be dispatched
+/// * `messages` - the queue where the widgets place their messages
+/// * `context` - the context of the event loop
+pub(crate) fn dispatch_event(
+    target: &Rc<RefCell<dyn Widget>>,
+    event: Event,
+    messages: &mut Queue<Box<dyn Message>>,
+    context: &RefCell<Context>,
+) {
+    let point = match pointer_position() {
+        Some(point)
+            if context.borrow().is_hit_test_routed(&event) && !event::is_pointer_captured() =>
+        {
+            point
+        }
+        _ => {
+            deliver_event(&mut *target.borrow_mut(), event, messages);
+            return;
+        }
+    };
+    let path = hit_test(target, point, &context.borrow());
+    let left = match event {
+        Event::Mouse(event::Mouse::CursorMoved { .. }) => {
+            context.borrow_mut().replace_hovered_path(&path)
+        }
+        _ => Vec::new(),
+    };
+    for widget in left.iter().rev() {
+        let mut widget = HiddenChildren::hide(widget.borrow_mut());
+        deliver_event(&mut *widget.widget, event, messages);
+    }
+    // Bubble the event up from the topmost widget, until a widget captures it
+    for widget in path.iter().rev() {
+        let mut widget = HiddenChildren::hide(widget.borrow_mut());
+        if deliver_event(&mut *widget.widget, event, messages).is_captured() {
+            break;
+        }
+    }
+}
+
+/// Widget whose children are hidden, restoring them when dropped (even if the widget
+/// panics), for internal use only
+struct HiddenChildren<'a> {
+    /// The widget
+    widget: RefMut<'a, dyn Widget>,
+    /// The widget's children hidden
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+}
+
+impl<'a> HiddenChildren<'a> {
+    /// Hides the children of a widget
+    ///
+    /// # Returns
+    /// The guard restoring the children when dropped
+    ///
+    /// # Arguments
+    /// * `widget` - the widget whose children are hidden
+    fn hide(mut widget: RefMut<'a, dyn Widget>) -> HiddenChildren<'a> {
+        let children = std::mem::take(widget.get_children());
+        HiddenChildren { widget, children }
+    }
+}
+
+impl Drop for HiddenChildren<'_> {
+    fn drop(&mut self) {
+        // Keep the children added meanwhile after the ones hidden
+        let added = std::mem::replace(
+            self.widget.get_children(),
+            std::mem::take(&mut self.children),
+        );
+        self.widget.get_children().extend(added);
+    }
+}
+
+/// Finds a widget in a tree by its address, for internal use only
+///
+/// # Returns
+/// The widget found, or `None` if it is not in the tree
+///
+/// # Arguments
+/// * `widget` - the root of the tree
+/// * `address` - the address of the widget
+fn find_widget(
+    widget: &Rc<RefCell<dyn Widget>>,
+    address: usize,
+) -> Option<Rc<RefCell<dyn Widget>>> {
+    if widget_address(widget) == address {
+        return Some(widget.clone());
+    }
+    let children = all_children(&mut *widget.borrow_mut());
+    children
+        .iter()
+        .find_map(|child| find_widget(child, address))
+}
+
+/// Builds the popups open over the whole display, above the widget tree, and removes
+/// the ones closed, for internal use only
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `size` - the display's size (width and height)
+/// * `is_covered` - whether any widget of the tree was rebuilt, and drawn over the popups
+/// * `id_machine` - the identifiers given to the widgets built
+/// * `instruction_collection` - the collection where the popups' render instructions are
+/// * `context` - shared state of the event loop, where the popups are open
+pub(crate) fn build_popups(
+    size: Vector2D,
+    is_covered: bool,
+    id_machine: &mut IDMachine,
+    instruction_collection: &mut RenderInstructionCollection,
+    context: &mut Context,
+) {
+    for popup in context.take_closed_popups() {
+        hide_tree(&mut *popup.borrow_mut(), instruction_collection, context);
+    }
+    // Rebuild the popups after a rebuilt widget, so that they keep being drawn over it
+    let mut popups_dirty = is_covered;
+    for popup in context.popups() {
+        if popups_dirty {
+            set_tree_dirty(&popup);
+        } else if popup.borrow().is_dirty() {
+            popups_dirty = true;
         }
+        let last_id = id_machine.last_id();
+        popup.borrow_mut().build(
+            Vector2D::new(0., 0.),
+            size,
+            id_machine,
+            instruction_collection,
+            context,
+        );
+        popups_dirty |= id_machine.last_id() != last_id;
+    }
+}
+
+/// Removes the render instructions of a widget and its children, which are no longer
+/// built, marking them as dirty so that they are rebuilt when built again
+///
+/// # Returns
+/// No returns
+///
+/// # Arguments
+/// * `widget` - the widget to be hidden
+/// * `instruction_collection` - the collection where the widget's render instructions are
+/// * `context` - shared state of the event loop, where the widget's geometry is recorded
+fn hide_tree(
+    widget: &mut dyn Widget,
+    instruction_collection: &mut RenderInstructionCollection,
+    context: &mut Context,
+) {
+    instruction_collection.remove(widget.id());
+    context.remove_geometry(widget.id());
+    context.remove_tab_stop(widget.id());
+    context.remove_memo(widget.id());
+    context.remove_stable_id(widget.id());
+    if let Some(overlay_id) = context.take_overlay(widget.id()) {
+        instruction_collection.remove(overlay_id);
+    }
+    widget.set_dirty(true);
+
+    for child in all_children(widget) {
+        hide_tree(&mut *child.borrow_mut(), instruction_collection, context);
     }
 }
@@ -0,0 +1,508 @@
+use crate::clock;
+use crate::event::Event;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text;
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use std::time::Instant;
+
+/// Space between the overlay's box, the spinner, the message and the progress bar
+const PADDING: f64 = 16.;
+
+/// Diameter of the spinner, as a multiple of the font size
+const SPINNER_SIZE: f64 = 3.;
+
+/// Height of the progress bar
+const PROGRESS_BAR_HEIGHT: f64 = 6.;
+
+/// Time taken by the spinner to perform a full turn, in milliseconds
+const SPIN_PERIOD: u128 = 1000;
+
+/// Angle swept by the spinner, in degrees
+const SPIN_SWEEP: usize = 90;
+
+/// Busy overlay is a box with a spinner, a message and an optional progress bar,
+/// drawn over a scrim covering the whole display while the application is busy.
+///
+/// The overlay is meant to be opened as a popup (see
+/// [`Context::open_popup`](`crate::context::Context::open_popup`)), which grabs every
+/// event, so the widgets behind it cannot be interacted with while it is open. The
+/// overlay ignores every event itself. Usually, it is shown and hidden through a
+/// [`BusyOverlay`](`crate::busy::BusyOverlay`), which also updates its message and
+/// progress with the reports of background tasks.
+#[derive(Clone)]
+pub struct BusyOverlayWidget {
+    /// The overlay's identifier
+    id: usize,
+
+    /// The data attached to the overlay through its handle
+    widget_data: WidgetData,
+
+    /// The message displayed below the spinner
+    message: String,
+
+    /// The progress displayed on the progress bar, from 0 to 100, or `None` to hide it
+    progress: Option<f64>,
+
+    /// The overlay's font size
+    font_size: usize,
+
+    /// The color drawn over the widgets behind the overlay
+    scrim_color: Color,
+
+    /// The background color of the overlay's box
+    background_color: Color,
+
+    /// The overlay's text color
+    text_color: Color,
+
+    /// The color of the spinner and of the progress filled
+    accent_color: Color,
+
+    /// The instant when the spinner started spinning
+    spin_start: Instant,
+
+    /// The overlay's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The overlay's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The overlay's current size (width and height), covering the whole display
+    size: Vector2D,
+
+    /// The overlay's original size (width and height), which is the size of its box
+    original_size: Vector2D,
+
+    /// The overlay's layout
+    layout: Layout,
+
+    /// The overlay's offset vector coordinates
+    offset: Vector2D,
+
+    /// The overlay's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl BusyOverlayWidget {
+    /// Creates a new `BusyOverlayWidget`
+    ///
+    /// # Returns
+    /// The overlay created, without message nor progress
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the overlay's box
+    /// * `font_size` - the font size to be assigned to the overlay
+    /// * `background_color` - the color to be assigned to the background of the overlay's box
+    /// * `text_color` - the color to be assigned to the overlay's text
+    /// * `accent_color` - the color to be assigned to the spinner and to the progress filled
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        text_color: Color,
+        accent_color: Color,
+    ) -> BusyOverlayWidget {
+        BusyOverlayWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color)
+                .with_accent_color(accent_color),
+            message: String::new(),
+            progress: None,
+            font_size,
+            scrim_color: Color::from_hex(0x80000000),
+            background_color,
+            text_color,
+            accent_color,
+            spin_start: clock::now(),
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Gets the message displayed below the spinner
+    ///
+    /// # Returns
+    /// The overlay's message
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets the message displayed below the spinner
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `message` - the message to be assigned to the overlay
+    pub fn set_message(&mut self, message: &str) {
+        self.message = String::from(message);
+    }
+
+    /// Gets the progress displayed on the progress bar
+    ///
+    /// # Returns
+    /// The progress, from 0 to 100, or `None` if the progress bar is hidden
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn progress(&self) -> Option<f64> {
+        self.progress
+    }
+
+    /// Sets the progress displayed on the progress bar
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `progress` - the progress, from 0 to 100, or `None` to hide the progress bar
+    pub fn set_progress(&mut self, progress: Option<f64>) {
+        self.progress = progress.map(|progress| progress.clamp(0., 100.));
+    }
+
+    /// Sets the color drawn over the widgets behind the overlay
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `scrim_color` - the color to be assigned, usually translucent
+    pub fn set_scrim_color(&mut self, scrim_color: Color) {
+        self.scrim_color = scrim_color;
+    }
+
+    /// Gets the area of the overlay's box, centered on the display, for internal use only
+    ///
+    /// # Returns
+    /// The area of the overlay's box
+    ///
+    /// # Arguments
+    /// No arguments
+    fn box_area(&self) -> Rect {
+        let size = Vector2D::new(
+            self.original_size.x.min(self.size.x),
+            self.original_size.y.min(self.size.y),
+        );
+        Rect::new(self.position + (self.size - size) * 0.5, size)
+    }
+
+    /// Sets the overlay's size (width and height) of the box, returning the overlay (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The overlay with the size (width and height) of the box assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the overlay's box
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the overlay's message, returning the overlay (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The overlay with the message assigned
+    ///
+    /// # Arguments
+    /// * `message` - the message to be assigned to the overlay
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.set_message(message);
+        self
+    }
+
+    /// Sets the overlay's progress, returning the overlay (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The overlay with the progress assigned
+    ///
+    /// # Arguments
+    /// * `progress` - the progress, from 0 to 100, or `None` to hide the progress bar
+    pub fn with_progress(mut self, progress: Option<f64>) -> Self {
+        self.set_progress(progress);
+        self
+    }
+
+    /// Sets the overlay's scrim color, returning the overlay (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The overlay with the scrim color assigned
+    ///
+    /// # Arguments
+    /// * `scrim_color` - the color drawn over the widgets behind the overlay
+    pub fn with_scrim_color(mut self, scrim_color: Color) -> Self {
+        self.set_scrim_color(scrim_color);
+        self
+    }
+
+    /// Sets the overlay's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the overlay
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the overlay's style, overriding the properties of the current theme, returning the
+    /// overlay (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The overlay with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the overlay
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for BusyOverlayWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        BusyOverlayWidget {
+            style: Style::new(),
+            ..BusyOverlayWidget::new(
+                Vector2D::new(260., 140.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.accent,
+            )
+        }
+    }
+}
+
+impl Widget for BusyOverlayWidget {
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        // The overlay blocks every event until it is hidden
+        EventStatus::Captured
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let overlay_box = self.box_area();
+        let diameter = self.font_size as f64 * SPINNER_SIZE;
+        let radius = diameter * 0.5;
+        let center = Vector2D::new(
+            overlay_box.position.x + overlay_box.size.x * 0.5,
+            overlay_box.position.y + PADDING + radius,
+        );
+        let elapsed = clock::elapsed(self.spin_start).as_millis() % SPIN_PERIOD;
+        let start_angle = (elapsed * 360 / SPIN_PERIOD) as usize;
+        let message_width = text::measure_text(&self.message, self.font_size).x;
+        let baseline = center.y + radius + PADDING + self.font_size as f64;
+
+        let mut instructions = vec![
+            // Scrim
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: self.scrim_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Box background
+            RenderInstruction::DrawRect {
+                point: overlay_box.position,
+                size: overlay_box.size,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Spinner track
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: radius as usize,
+                color: self.text_color.with_opacity(0.15),
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Spinner arc
+            RenderInstruction::DrawArc {
+                point: center,
+                r: radius as usize,
+                s_ang: start_angle,
+                e_ang: start_angle + SPIN_SWEEP,
+                color: self.accent_color,
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Hollow the center of the spinner to display a ring
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: (radius * 0.75) as usize,
+                color: self.background_color,
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Message
+            RenderInstruction::DrawText {
+                point: Vector2D::new(center.x - message_width * 0.5, baseline),
+                font_size: self.font_size,
+                string: self.message.clone(),
+                color: self.text_color,
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                font: FontDescriptor::default(),
+            },
+        ];
+
+        // Progress bar
+        if let Some(progress) = self.progress {
+            let point = Vector2D::new(overlay_box.position.x + PADDING, baseline + PADDING);
+            let size = Vector2D::new(
+                (overlay_box.size.x - 2. * PADDING).max(0.),
+                PROGRESS_BAR_HEIGHT,
+            );
+            instructions.push(RenderInstruction::DrawRect {
+                point,
+                size,
+                color: self.text_color.with_opacity(0.15),
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                blend_mode: BlendMode::Normal,
+            });
+            instructions.push(RenderInstruction::DrawRect {
+                point,
+                size: Vector2D::new(size.x * progress / 100., size.y),
+                color: self.accent_color,
+                clip_point: overlay_box.position,
+                clip_size: overlay_box.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, _value: bool) {}
+
+    fn is_dirty(&self) -> bool {
+        // The spinner needs to be redrawn on every frame
+        true
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            true,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.accent_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+    }
+}
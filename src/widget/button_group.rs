@@ -0,0 +1,525 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::checkbox::CheckBoxWidget;
+use crate::widget::{
+    deliver_event, is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget,
+    WidgetData, WidgetRef,
+};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Button group is a widget that hosts checkboxes and buttons along an axis,
+/// keeping track of which of them are selected. Clicking a member toggles its
+/// selection, or selects it alone when the group is exclusive (i.e., behaving
+/// like radio buttons).
+///
+/// Checkbox members are checked and unchecked by the group (their own `on_change`
+/// messages are not handled), while the other members keep handling their presses
+/// and are framed with the selected color when selected. Whenever the selection
+/// changes, the group handles its `on_change` message, filled with the indexes of
+/// the selected members when it is a [`ValueChanged<Vec<usize>>`](`ValueChanged`).
+#[derive(Clone)]
+pub struct ButtonGroupWidget {
+    /// The button group's identifier
+    id: usize,
+
+    /// The data attached to the button group through its handle
+    widget_data: WidgetData,
+
+    /// Whether only one member can be selected at a time
+    is_exclusive: bool,
+
+    /// The indexes of the selected members, in ascending order
+    selected: Vec<usize>,
+
+    /// The color of the frame drawn around the selected members
+    selected_color: Color,
+
+    /// The thickness of the frame drawn around the selected members
+    selected_thickness: f64,
+
+    /// The message to be handled when the selection changes
+    on_change: Option<Box<dyn Message>>,
+
+    /// The cursor's position
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The button group's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The button group's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The button group's current size (width and height)
+    size: Vector2D,
+
+    /// The button group's original size (width and height)
+    original_size: Vector2D,
+
+    /// The button group's layout
+    layout: Layout,
+
+    /// The button group's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ButtonGroupWidget {
+    /// Creates a new `ButtonGroupWidget`
+    ///
+    /// # Returns
+    /// The button group created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the button group
+    /// * `axis` - the axis direction to be assigned to the button group
+    /// * `is_exclusive` - boolean indicating if only one member can be selected at a time
+    /// * `selected_color` - the color of the frame drawn around the selected members
+    /// * `on_change` - the message to be handled when the selection changes
+    pub fn new(
+        size: Vector2D,
+        axis: Axis,
+        is_exclusive: bool,
+        selected_color: Color,
+        on_change: Option<Box<dyn Message>>,
+    ) -> ButtonGroupWidget {
+        ButtonGroupWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_accent_color(selected_color),
+            is_exclusive,
+            selected: Vec::new(),
+            selected_color,
+            selected_thickness: 2.,
+            on_change,
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Box(axis),
+        }
+    }
+
+    /// Sets whether only one member can be selected at a time
+    ///
+    /// When becoming exclusive, only the first selected member is kept selected.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_exclusive` - boolean indicating if only one member can be selected at a time
+    pub fn set_exclusive(&mut self, is_exclusive: bool) {
+        self.is_exclusive = is_exclusive;
+        if is_exclusive {
+            self.selected.truncate(1);
+            self.apply_selection();
+        }
+    }
+
+    /// Gets the indexes of the selected members
+    ///
+    /// # Returns
+    /// The indexes of the selected members, in ascending order
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    /// Sets the selected members, without handling the `on_change` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the indexes of the members to be selected
+    pub fn set_selected(&mut self, mut selected: Vec<usize>) {
+        selected.sort_unstable();
+        selected.dedup();
+        if self.is_exclusive {
+            selected.truncate(1);
+        }
+        self.selected = selected;
+        self.apply_selection();
+    }
+
+    /// Sets the style of the frame drawn around the selected members
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `color` - the color of the frame
+    /// * `thickness` - the thickness of the frame
+    pub fn set_selected_style(&mut self, color: Color, thickness: f64) {
+        self.selected_color = color;
+        self.selected_thickness = thickness;
+        self.dirty = true;
+    }
+
+    /// Sets the message to be handled when the selection changes
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_change` - the new message to be handled when the selection changes
+    pub fn set_message(&mut self, on_change: Option<Box<dyn Message>>) {
+        self.on_change = on_change;
+    }
+
+    /// Sets the button group's size (width and height), returning the button group (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button group with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the button group
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the button group's exclusive flag, returning the button group (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button group with the exclusive flag assigned
+    ///
+    /// # Arguments
+    /// * `is_exclusive` - the exclusive flag to be assigned to the button group
+    pub fn with_exclusive(mut self, is_exclusive: bool) -> Self {
+        self.is_exclusive = is_exclusive;
+        self
+    }
+
+    /// Sets the button group's selected color, returning the button group (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button group with the selected color assigned
+    ///
+    /// # Arguments
+    /// * `selected_color` - the selected color to be assigned to the button group
+    pub fn with_selected_color(mut self, selected_color: Color) -> Self {
+        self.style.accent_color = Some(selected_color);
+        self.selected_color = selected_color;
+        self
+    }
+
+    /// Sets the button group's message to be handled when the selection changes, returning the button group (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button group with the given message to be handled when the selection changes
+    ///
+    /// # Arguments
+    /// * `on_change` - the message to be handled when the selection changes
+    pub fn with_on_change(mut self, on_change: Option<Box<dyn Message>>) -> Self {
+        self.on_change = on_change;
+        self
+    }
+
+    /// Toggles the selection of a member, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the member clicked
+    fn toggle(&mut self, index: usize) {
+        if self.is_exclusive {
+            self.selected = vec![index];
+        } else if let Some(position) = self.selected.iter().position(|value| *value == index) {
+            self.selected.remove(position);
+        } else {
+            self.selected.push(index);
+            self.selected.sort_unstable();
+        }
+        self.apply_selection();
+    }
+
+    /// Checks the checkbox members according to the selection, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn apply_selection(&mut self) {
+        for (index, value) in self.children.iter().enumerate() {
+            let is_selected = self.selected.contains(&index);
+            value.with_widget(|checkbox: &mut CheckBoxWidget| {
+                if checkbox.get_is_checked() != is_selected {
+                    checkbox.set_checked(is_selected);
+                }
+            });
+        }
+        // Rebuild the group to update the frames around the selected members
+        self.dirty = true;
+    }
+
+    /// Sets the button group's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the button group
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the button group's style, overriding the properties of the current theme, returning
+    /// the button group (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button group with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the button group
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ButtonGroupWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ButtonGroupWidget {
+            style: Style::new(),
+            ..ButtonGroupWidget::new(
+                Vector2D::new(200., 30.),
+                Axis::Horizontal,
+                true,
+                theme.palette.accent,
+                None,
+            )
+        }
+    }
+}
+
+impl Widget for ButtonGroupWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                propagate_event(&self.children, event, messages)
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                let mut pressed = None;
+                for (index, value) in self.children.iter().enumerate() {
+                    if let Some(child) = value.upgrade() {
+                        if child.borrow_mut().is_cursor_inside(self.cursor_pos) {
+                            pressed = Some(index);
+                        }
+                        // Checkboxes are checked by the group, not by themselves
+                        if !value.is::<CheckBoxWidget>() {
+                            deliver_event(&mut *child.borrow_mut(), event, messages);
+                        }
+                    }
+                }
+
+                if let Some(index) = pressed {
+                    let previous = self.selected.clone();
+                    self.toggle(index);
+                    if self.selected != previous {
+                        if let Some(mut message) = self.on_change.clone() {
+                            message.set_event(event);
+                            if let Some(message) = message.downcast_mut::<ValueChanged<Vec<usize>>>() {
+                                message.value = self.selected.clone();
+                            }
+                            messages.enqueue(message);
+                        }
+                    }
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
+                }
+            }
+            _ => {
+                propagate_event(&self.children, event, messages)
+            }
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        let mut instructions = Vec::new();
+        let thickness = self.selected_thickness;
+        for index in self.selected.iter() {
+            let child = match self.children.get(*index).and_then(|value| value.upgrade()) {
+                Some(child) => child,
+                None => continue,
+            };
+            // Checkboxes display their selection by themselves
+            if self.children[*index].is::<CheckBoxWidget>() {
+                continue;
+            }
+
+            let position = child.borrow_mut().position();
+            let size = child.borrow_mut().size();
+            // Frame around the selected member: top, bottom, left and right edges
+            let edges = [
+                (position, Vector2D::new(size.x, thickness)),
+                (
+                    Vector2D::new(position.x, position.y + size.y - thickness),
+                    Vector2D::new(size.x, thickness),
+                ),
+                (position, Vector2D::new(thickness, size.y)),
+                (
+                    Vector2D::new(position.x + size.x - thickness, position.y),
+                    Vector2D::new(thickness, size.y),
+                ),
+            ];
+            for (point, size) in edges.iter() {
+                instructions.push(RenderInstruction::DrawRect {
+                    point: *point,
+                    color: self.selected_color,
+                    size: *size,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+        }
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        Vector2D::new(0., 0.)
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            Vector2D::new(0., 0.),
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, _offset: Vector2D) {}
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.selected_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
+}
@@ -1,8 +1,11 @@
+use crate::clock;
 use crate::event;
 use crate::event::Event;
+use crate::interaction::{Interaction, InteractionState, InteractionStateMachine};
 use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{propagate_event, EventStatus, Flex, Layout, TabIndex, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -19,6 +22,9 @@ pub struct ButtonViewWidget {
     /// The button's identifier
     id: usize,
 
+    /// The data attached to the button through its handle
+    widget_data: WidgetData,
+
     /// Whether the button is clickable
     is_clickable: bool,
 
@@ -31,8 +37,12 @@ pub struct ButtonViewWidget {
     /// The message to be handled when a user long press
     on_long_press: Option<Box<dyn Message>>,
 
-    /// Whether the button state is pressed
-    is_pressed: bool,
+    /// The message to be handled when a user double-clicks, after the message of the
+    /// first press
+    on_double_press: Option<Box<dyn Message>>,
+
+    /// Whether the button is hovered or pressed
+    interaction: InteractionStateMachine,
 
     /// The instant when the button was clicked
     click_time: Instant,
@@ -61,6 +71,22 @@ pub struct ButtonViewWidget {
 
     /// The button's offset vector coordinates
     offset: Vector2D,
+
+    /// The message to be handled when a user presses any button other than the
+    /// left and right ones (e.g. the middle button)
+    on_auxiliary_press: Option<Box<dyn Message>>,
+
+    /// The auxiliary button being pressed, if any
+    auxiliary_pressed: Option<event::MouseButton>,
+
+    /// The button's position in the focus traversal
+    tab_index: TabIndex,
+
+    /// The button's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
+
+    /// The button's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl ButtonViewWidget {
@@ -85,12 +111,16 @@ impl ButtonViewWidget {
     ) -> ButtonViewWidget {
         ButtonViewWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color),
             background_color: background_color,
             is_clickable: is_clickable,
             on_press: on_press,
             on_long_press: on_long_press,
-            is_pressed: false,
-            click_time: Instant::now(),
+            on_double_press: None,
+            interaction: InteractionStateMachine::new(),
+            click_time: clock::now(),
             cursor_pos: Vector2D::new(-1., -1.),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
@@ -99,6 +129,10 @@ impl ButtonViewWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            on_auxiliary_press: None,
+            auxiliary_pressed: None,
+            tab_index: TabIndex::Auto,
+            flex: Flex::default(),
         }
     }
 
@@ -112,58 +146,301 @@ impl ButtonViewWidget {
     pub fn set_is_clickable(&mut self, value: bool) {
         self.is_clickable = value;
     }
+
+    /// Gets whether the button is hovered or pressed, to be displayed by its children
+    ///
+    /// # Returns
+    /// The button's interaction state
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn interaction_state(&self) -> InteractionState {
+        self.interaction.state()
+    }
+
+    /// Sets the button's size (width and height), returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the button
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the button's clickable flag, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the clickable flag assigned
+    ///
+    /// # Arguments
+    /// * `is_clickable` - the clickable flag to be assigned to the button
+    pub fn with_is_clickable(mut self, is_clickable: bool) -> Self {
+        self.is_clickable = is_clickable;
+        self
+    }
+
+    /// Sets the button's background color, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the button
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the button's message to be handled when pressed, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the given message to be handled when pressed
+    ///
+    /// # Arguments
+    /// * `on_press` - the message to be handled when pressed
+    pub fn with_on_press(mut self, on_press: Option<Box<dyn Message>>) -> Self {
+        self.on_press = on_press;
+        self
+    }
+
+    /// Sets the button's message to be handled when long pressed, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the given message to be handled when long pressed
+    ///
+    /// # Arguments
+    /// * `on_long_press` - the message to be handled when long pressed
+    pub fn with_on_long_press(mut self, on_long_press: Option<Box<dyn Message>>) -> Self {
+        self.on_long_press = on_long_press;
+        self
+    }
+
+    /// Sets the button's message to be handled when double-clicked, returning the button
+    /// (i.e., fluent setter)
+    ///
+    /// The message of the first press is handled too, as the double-click is only known
+    /// once the button is pressed again.
+    ///
+    /// # Returns
+    /// The button with the given message to be handled when double-clicked
+    ///
+    /// # Arguments
+    /// * `on_double_press` - the message to be handled when double-clicked
+    pub fn with_on_double_press(mut self, on_double_press: Option<Box<dyn Message>>) -> Self {
+        self.on_double_press = on_double_press;
+        self
+    }
+
+    /// Sets the button's message to be handled when double-clicked
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_double_press` - the message to be handled when double-clicked
+    pub fn set_on_double_press(&mut self, on_double_press: Option<Box<dyn Message>>) {
+        self.on_double_press = on_double_press;
+    }
+
+    /// Sets the message to be handled when a user presses any button other than the
+    /// left and right ones (e.g. the middle button)
+    ///
+    /// The message's event is the button release, so the button pressed can be
+    /// told apart.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_auxiliary_press` - the message to be handled when an auxiliary button is pressed
+    pub fn set_auxiliary_message(&mut self, on_auxiliary_press: Option<Box<dyn Message>>) {
+        self.on_auxiliary_press = on_auxiliary_press;
+    }
+
+    /// Sets the button's position in the focus traversal (e.g. to exclude it from the traversal)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `tab_index` - the button's tab index
+    pub fn set_tab_index(&mut self, tab_index: TabIndex) {
+        self.tab_index = tab_index;
+        self.dirty = true;
+    }
+
+    /// Sets the button's position in the focus traversal, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the tab index assigned
+    ///
+    /// # Arguments
+    /// * `tab_index` - the tab index to be assigned to the button
+    pub fn with_tab_index(mut self, tab_index: TabIndex) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Sets how the button is sized by a flex layout
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `flex` - the button's flex properties
+    pub fn set_flex(&mut self, flex: Flex) {
+        self.flex = flex;
+        self.dirty = true;
+    }
+
+    /// Sets how the button is sized by a flex layout, returning the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the button
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    /// Sets the button's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the button
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the button's style, overriding the properties of the current theme, returning
+    /// the button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The button with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the button
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ButtonViewWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ButtonViewWidget {
+            style: Style::new(),
+            ..ButtonViewWidget::new(
+                Vector2D::new(100., 30.),
+                true,
+                theme.palette.surface,
+                None,
+                None,
+            )
+        }
+    }
 }
 
 impl Widget for ButtonViewWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let area = Rect::new(self.position, self.size);
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 //update cursor_pos on mouse move
                 self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                self.interaction.on_event(event, area);
+                propagate_event(&self.children, event, messages)
             }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
                 //when left mouse button is pressed do something if button is clickable and if messages aren't empty
-                if self.is_clickable && (self.on_press.is_some() || self.on_long_press.is_some()) {
-                    //check if cursor is inside button area
-                    if self.is_cursor_inside(self.cursor_pos) {
-                        self.is_pressed = true;
-                        self.click_time = Instant::now();
-                    }
+                if self.is_clickable
+                    && (self.on_press.is_some() || self.on_long_press.is_some())
+                    && self.interaction.on_event(event, area) == Some(Interaction::Pressed)
+                {
+                    self.click_time = clock::now();
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
             event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
                 //when left mouse button is released do something if button state is pressed
-                if self.is_pressed {
-                    self.is_pressed = false;
-                    //check if cursor is inside button area
-                    //if the release it's outside we don't consider it as a click event
+                //if the release it's outside we don't consider it as a click event
+                if self.interaction.on_event(event, area) == Some(Interaction::Clicked) {
+                    if clock::elapsed(self.click_time).as_millis() < ON_LONG_PRESS_TIME {
+                        if let Some(mut message) = self.on_press.clone() {
+                            message.set_event(event);
+                            messages.enqueue(message);
+                        }
+                    } else {
+                        if let Some(mut message) = self.on_long_press.clone() {
+                            message.set_event(event);
+                            messages.enqueue(message);
+                        }
+                    }
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonDoubleClicked(event::MouseButton::Left)) => {
+                if self.is_clickable && self.is_cursor_inside(self.cursor_pos) {
+                    if let Some(mut message) = self.on_double_press.clone() {
+                        message.set_event(event);
+                        messages.enqueue(message);
+                        return EventStatus::Captured;
+                    }
+                }
+                propagate_event(&self.children, event, messages)
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(
+                button @ (event::MouseButton::Middle | event::MouseButton::Other(_)),
+            )) => {
+                if self.is_clickable
+                    && self.on_auxiliary_press.is_some()
+                    && self.is_cursor_inside(self.cursor_pos)
+                {
+                    self.auxiliary_pressed = Some(button);
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(
+                button @ (event::MouseButton::Middle | event::MouseButton::Other(_)),
+            )) => {
+                if self.auxiliary_pressed == Some(button) {
+                    self.auxiliary_pressed = None;
+                    // A release outside of the button area is not considered a press
                     if self.is_cursor_inside(self.cursor_pos) {
-                        if self.click_time.elapsed().as_millis() < ON_LONG_PRESS_TIME {
-                            if let Some(mut message) = self.on_press.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
-                            }
-                        } else {
-                            if let Some(mut message) = self.on_long_press.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
-                            }
+                        if let Some(mut message) = self.on_auxiliary_press.clone() {
+                            message.set_event(event);
+                            messages.enqueue(message);
                         }
                     }
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
                 }
             }
             _ => {
+                self.interaction.on_event(event, area);
                 //call on_event to button children
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
             }
         }
     }
@@ -176,6 +453,25 @@ impl Widget for ButtonViewWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.is_clickable && self.on_press.is_some()
+    }
+
+    fn tab_index(&self) -> TabIndex {
+        self.tab_index
+    }
+
+    fn activate(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(mut message) = self.on_press.clone() {
+            message.set_event(event);
+            messages.enqueue(message);
+        }
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         vec![]
     }
@@ -211,6 +507,10 @@ impl Widget for ButtonViewWidget {
         &self.layout
     }
 
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
     fn offset(&mut self) -> Vector2D {
         self.offset
     }
@@ -255,13 +555,9 @@ impl Widget for ButtonViewWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
     
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -274,4 +570,9 @@ impl Widget for ButtonViewWidget {
             false
         }
     }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,966 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Names of the months, starting on January
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Abbreviated names of the days of the week, starting on Monday
+const WEEKDAY_NAMES: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// Number of rows of the calendar: the header, the names of the days and six weeks
+const ROWS: usize = 8;
+
+/// Average width of a glyph, as a fraction of the font size, used to center the text
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// A day of the proleptic Gregorian calendar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year
+    pub year: i32,
+    /// The month, from 1 (January) to 12 (December)
+    pub month: u32,
+    /// The day of the month, starting on 1
+    pub day: u32,
+}
+
+impl Date {
+    /// Creates a new `Date`
+    ///
+    /// # Returns
+    /// The date created, with the day clamped to the days of the month
+    ///
+    /// # Arguments
+    /// * `year` - the year
+    /// * `month` - the month, from 1 (January) to 12 (December)
+    /// * `day` - the day of the month, starting on 1
+    pub fn new(year: i32, month: u32, day: u32) -> Date {
+        let month = month.clamp(1, 12);
+        Date {
+            year,
+            month,
+            day: day.clamp(1, Date::days_in_month(year, month)),
+        }
+    }
+
+    /// Checks if a year is a leap year
+    ///
+    /// # Returns
+    /// True, if the year has 366 days, false otherwise
+    ///
+    /// # Arguments
+    /// * `year` - the year to be checked
+    pub fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    /// Gets the number of days of a month
+    ///
+    /// # Returns
+    /// The number of days of the month
+    ///
+    /// # Arguments
+    /// * `year` - the year of the month
+    /// * `month` - the month, from 1 (January) to 12 (December)
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Gets the number of days since the 1st of January of 1970
+    ///
+    /// # Returns
+    /// The number of days since the 1st of January of 1970, negative for earlier dates
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn to_days(&self) -> i64 {
+        let year = if self.month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// Creates a new `Date` from the number of days since the 1st of January of 1970
+    ///
+    /// # Returns
+    /// The date created
+    ///
+    /// # Arguments
+    /// * `days` - the number of days since the 1st of January of 1970, negative for earlier dates
+    pub fn from_days(days: i64) -> Date {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    /// Gets the date a number of days apart
+    ///
+    /// # Returns
+    /// The date a number of days apart
+    ///
+    /// # Arguments
+    /// * `days` - the number of days to be added, negative to go back
+    pub fn add_days(&self, days: i64) -> Date {
+        Date::from_days(self.to_days() + days)
+    }
+
+    /// Gets the day of the week
+    ///
+    /// # Returns
+    /// The day of the week, from 0 (Monday) to 6 (Sunday)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn weekday(&self) -> u32 {
+        // The 1st of January of 1970 was a Thursday
+        (self.to_days() + 3).rem_euclid(7) as u32
+    }
+
+    /// Gets the ISO 8601 week number (i.e., weeks start on Monday and the first
+    /// week of the year is the one with its first Thursday)
+    ///
+    /// # Returns
+    /// The week number, from 1 to 53
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn week_number(&self) -> u32 {
+        // The week of a day is the week of its Thursday
+        let thursday = self.add_days(3 - self.weekday() as i64);
+        let day_of_year = thursday.to_days() - Date::new(thursday.year, 1, 1).to_days();
+        (day_of_year / 7 + 1) as u32
+    }
+}
+
+impl Default for Date {
+    fn default() -> Self {
+        Date::new(1970, 1, 1)
+    }
+}
+
+/// How the days of a calendar are selected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarSelection {
+    /// A single day is selected at a time
+    Single,
+    /// Each day clicked is added to or removed from the selection
+    Multiple,
+    /// The days between two days clicked are selected
+    Range,
+}
+
+/// Calendar is a widget that displays the days of a month as a grid, reusable
+/// inside scheduling interfaces.
+///
+/// The header navigates to the previous and next months. Days can be selected
+/// one at a time, several at a time or as a range, according to the
+/// [`CalendarSelection`] mode, and the days outside the minimum and maximum
+/// dates cannot be selected. When the selection changes, the `on_selection_change`
+/// message is handled, which is filled with the selected days if it is a
+/// [`ValueChanged<Vec<Date>>`](`ValueChanged`).
+#[derive(Clone)]
+pub struct CalendarWidget {
+    /// The calendar's identifier
+    id: usize,
+
+    /// The data attached to the calendar through its handle
+    widget_data: WidgetData,
+
+    /// The year of the month displayed
+    year: i32,
+
+    /// The month displayed, from 1 (January) to 12 (December)
+    month: u32,
+
+    /// How the days are selected
+    selection_mode: CalendarSelection,
+
+    /// The days selected, sorted. On range selection, the first and last days of the range
+    selected: Vec<Date>,
+
+    /// Whether the range selection is waiting for its last day
+    is_range_open: bool,
+
+    /// The earliest day that can be selected
+    min_date: Option<Date>,
+
+    /// The latest day that can be selected
+    max_date: Option<Date>,
+
+    /// Whether the week numbers are displayed on a column before the days
+    show_week_numbers: bool,
+
+    /// The message to be handled when the selection changes
+    on_selection_change: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The calendar's font size
+    font_size: usize,
+
+    /// The calendar's background color
+    background_color: Color,
+
+    /// The calendar's foreground color (i.e., text color)
+    foreground_color: Color,
+
+    /// The color of the days outside the month displayed or that cannot be selected
+    muted_color: Color,
+
+    /// The background color of the days selected
+    selection_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The calendar's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The calendar's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The calendar's current size (width and height)
+    size: Vector2D,
+
+    /// The calendar's original size (width and height)
+    original_size: Vector2D,
+
+    /// The calendar's layout
+    layout: Layout,
+
+    /// The calendar's offset vector coordinates
+    offset: Vector2D,
+
+    /// The calendar's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl CalendarWidget {
+    /// Creates a new `CalendarWidget`
+    ///
+    /// # Returns
+    /// The calendar created
+    ///
+    /// # Arguments
+    /// * `date` - the day whose month is displayed
+    /// * `selection_mode` - how the days are selected
+    /// * `size` - the size (width and height) to be assigned to the calendar
+    /// * `font_size` - the font size to be assigned to the calendar
+    /// * `background_color` - the color to be assigned to the calendar's background
+    /// * `foreground_color` - the color to be assigned to the calendar's text
+    /// * `selection_color` - the color to be assigned to the background of the days selected
+    pub fn new(
+        date: Date,
+        selection_mode: CalendarSelection,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        foreground_color: Color,
+        selection_color: Color,
+    ) -> CalendarWidget {
+        CalendarWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(foreground_color)
+                .with_selection_color(selection_color),
+            year: date.year,
+            month: date.month,
+            selection_mode,
+            selected: Vec::new(),
+            is_range_open: false,
+            min_date: None,
+            max_date: None,
+            show_week_numbers: false,
+            on_selection_change: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            foreground_color,
+            muted_color: foreground_color.with_opacity(0.4),
+            selection_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when the selection changes
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_selection_change` - the message to be handled when the selection changes
+    pub fn set_message(&mut self, on_selection_change: Option<Box<dyn Message>>) {
+        self.on_selection_change = on_selection_change;
+    }
+
+    /// Sets the month displayed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `year` - the year of the month
+    /// * `month` - the month, from 1 (January) to 12 (December)
+    pub fn set_month(&mut self, year: i32, month: u32) {
+        self.year = year;
+        self.month = month.clamp(1, 12);
+        self.dirty = true;
+    }
+
+    /// Gets the month displayed
+    ///
+    /// # Returns
+    /// The year and the month displayed, from 1 (January) to 12 (December)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_month(&self) -> (i32, u32) {
+        (self.year, self.month)
+    }
+
+    /// Sets how the days are selected, clearing the selection
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selection_mode` - how the days are selected
+    pub fn set_selection_mode(&mut self, selection_mode: CalendarSelection) {
+        self.selection_mode = selection_mode;
+        self.selected.clear();
+        self.is_range_open = false;
+        self.dirty = true;
+    }
+
+    /// Sets the days selected, without handling the `on_selection_change` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the days to be selected. On range selection, the first and last days of the range
+    pub fn set_selected(&mut self, mut selected: Vec<Date>) {
+        selected.sort();
+        selected.dedup();
+        self.selected = match self.selection_mode {
+            CalendarSelection::Single => selected.into_iter().take(1).collect(),
+            CalendarSelection::Multiple => selected,
+            CalendarSelection::Range => match (selected.first(), selected.last()) {
+                (Some(first), Some(last)) => vec![*first, *last],
+                _ => Vec::new(),
+            },
+        };
+        self.is_range_open = false;
+        self.dirty = true;
+    }
+
+    /// Gets the days selected
+    ///
+    /// # Returns
+    /// The days selected, sorted. On range selection, the first and last days of the range
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_selected(&self) -> &Vec<Date> {
+        &self.selected
+    }
+
+    /// Sets the earliest and latest days that can be selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `min_date` - the earliest day that can be selected, or `None` for no constraint
+    /// * `max_date` - the latest day that can be selected, or `None` for no constraint
+    pub fn set_date_range(&mut self, min_date: Option<Date>, max_date: Option<Date>) {
+        self.min_date = min_date;
+        self.max_date = max_date;
+        self.dirty = true;
+    }
+
+    /// Sets whether the week numbers are displayed on a column before the days
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `show_week_numbers` - boolean indicating if the week numbers are displayed
+    pub fn set_show_week_numbers(&mut self, show_week_numbers: bool) {
+        self.show_week_numbers = show_week_numbers;
+        self.dirty = true;
+    }
+
+    /// Sets the color of the days outside the month displayed or that cannot be selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `muted_color` - the color to be assigned
+    pub fn set_muted_color(&mut self, muted_color: Color) {
+        self.style.muted_color = Some(muted_color);
+        self.muted_color = muted_color;
+        self.dirty = true;
+    }
+
+    /// Checks if a day can be selected, according to the minimum and maximum dates
+    ///
+    /// # Returns
+    /// True, if the day can be selected, false otherwise
+    ///
+    /// # Arguments
+    /// * `date` - the day to be checked
+    pub fn is_selectable(&self, date: Date) -> bool {
+        self.min_date.is_none_or(|min_date| date >= min_date)
+            && self.max_date.is_none_or(|max_date| date <= max_date)
+    }
+
+    /// Checks if a day is selected
+    ///
+    /// # Returns
+    /// True, if the day is selected (or within the range selected), false otherwise
+    ///
+    /// # Arguments
+    /// * `date` - the day to be checked
+    pub fn is_selected(&self, date: Date) -> bool {
+        match self.selection_mode {
+            CalendarSelection::Range => match (self.selected.first(), self.selected.last()) {
+                (Some(first), Some(last)) => date >= *first && date <= *last,
+                _ => false,
+            },
+            _ => self.selected.contains(&date),
+        }
+    }
+
+    /// Gets the size of the cells of the grid, for internal use only
+    ///
+    /// # Returns
+    /// The size (width and height) of every cell
+    ///
+    /// # Arguments
+    /// No arguments
+    fn cell_size(&self) -> Vector2D {
+        let columns = if self.show_week_numbers { 8. } else { 7. };
+        Vector2D::new(self.size.x / columns, self.size.y / ROWS as f64)
+    }
+
+    /// Gets the first day displayed on the grid (i.e., the Monday of the first week of the month), for internal use only
+    ///
+    /// # Returns
+    /// The first day displayed
+    ///
+    /// # Arguments
+    /// No arguments
+    fn first_day(&self) -> Date {
+        let first = Date::new(self.year, self.month, 1);
+        first.add_days(-(first.weekday() as i64))
+    }
+
+    /// Gets the position of a cell of the grid, for internal use only
+    ///
+    /// # Returns
+    /// The top left corner of the cell
+    ///
+    /// # Arguments
+    /// * `column` - the column of the cell, including the week numbers column if displayed
+    /// * `row` - the row of the cell, including the header and the names of the days
+    fn cell_position(&self, column: usize, row: usize) -> Vector2D {
+        self.position + self.cell_size() * Vector2D::new(column as f64, row as f64)
+    }
+
+    /// Gets the day under a position, for internal use only
+    ///
+    /// # Returns
+    /// The day under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn date_at(&self, position: Vector2D) -> Option<Date> {
+        let cell = (position - self.position) / self.cell_size();
+        if cell.x < 0. || cell.y < 2. || cell.y >= ROWS as f64 {
+            return None;
+        }
+        let column = cell.x as usize;
+        let column = if self.show_week_numbers {
+            column.checked_sub(1)?
+        } else {
+            column
+        };
+        if column >= 7 {
+            return None;
+        }
+        let row = cell.y as usize - 2;
+        Some(self.first_day().add_days((row * 7 + column) as i64))
+    }
+
+    /// Moves the month displayed, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `months` - the number of months to be moved, negative to go back
+    fn move_month(&mut self, months: i32) {
+        let index = self.year * 12 + self.month as i32 - 1 + months;
+        self.set_month(index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+    }
+
+    /// Selects a day according to the selection mode, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `date` - the day clicked
+    fn select(&mut self, date: Date) {
+        match self.selection_mode {
+            CalendarSelection::Single => self.selected = vec![date],
+            CalendarSelection::Multiple => match self.selected.binary_search(&date) {
+                Ok(index) => {
+                    self.selected.remove(index);
+                }
+                Err(index) => self.selected.insert(index, date),
+            },
+            CalendarSelection::Range => {
+                if self.is_range_open {
+                    let start = self.selected[0];
+                    self.selected = vec![start.min(date), start.max(date)];
+                    self.is_range_open = false;
+                } else {
+                    self.selected = vec![date];
+                    self.is_range_open = true;
+                }
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Gets the render instructions of a text centered on a cell, for internal use only
+    ///
+    /// # Returns
+    /// The render instruction of the text
+    ///
+    /// # Arguments
+    /// * `text` - the text to be drawn
+    /// * `point` - the top left corner of the cell
+    /// * `size` - the size (width and height) of the cell
+    /// * `color` - the text color
+    fn text_recipe(&self, text: String, point: Vector2D, size: Vector2D, color: Color) -> RenderInstruction {
+        let width = text.chars().count() as f64 * self.font_size as f64 * GLYPH_WIDTH;
+        RenderInstruction::DrawText {
+            point: Vector2D::new(
+                point.x + (size.x - width) * 0.5,
+                point.y + (size.y + self.font_size as f64) * 0.5,
+            ),
+            font_size: self.font_size,
+            string: text,
+            color,
+            clip_point: self.position,
+            clip_size: self.size,
+            font: FontDescriptor::default(),
+        }
+    }
+
+    /// Sets the calendar's month displayed, returning the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the month displayed assigned
+    ///
+    /// # Arguments
+    /// * `year` - the year of the month
+    /// * `month` - the month, from 1 (January) to 12 (December)
+    pub fn with_month(mut self, year: i32, month: u32) -> Self {
+        self.year = year;
+        self.month = month.clamp(1, 12);
+        self
+    }
+
+    /// Sets the calendar's selection mode, returning the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the selection mode assigned
+    ///
+    /// # Arguments
+    /// * `selection_mode` - how the days of the calendar are selected
+    pub fn with_selection_mode(mut self, selection_mode: CalendarSelection) -> Self {
+        self.selection_mode = selection_mode;
+        self
+    }
+
+    /// Sets the calendar's size (width and height), returning the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the calendar
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the calendar's font size, returning the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size to be assigned to the calendar
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.style.font_size = Some(font_size);
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the calendar's message to be handled when the selection changes, returning the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the given message to be handled when the selection changes
+    ///
+    /// # Arguments
+    /// * `on_selection_change` - the message to be handled when the selection changes
+    pub fn with_on_selection_change(mut self, on_selection_change: Option<Box<dyn Message>>) -> Self {
+        self.on_selection_change = on_selection_change;
+        self
+    }
+
+    /// Sets the calendar's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the calendar
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the calendar's style, overriding the properties of the current theme, returning
+    /// the calendar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The calendar with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the calendar
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for CalendarWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        CalendarWidget {
+            style: Style::new(),
+            ..CalendarWidget::new(
+                Date::default(),
+                CalendarSelection::Single,
+                Vector2D::new(280., 256.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.selection,
+            )
+        }
+    }
+}
+
+impl Widget for CalendarWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                let cell_size = self.cell_size();
+                let cell = (self.cursor_pos - self.position) / cell_size;
+                if cell.y < 1. {
+                    // The header navigates to the previous and next months
+                    if cell.x < 1. {
+                        self.move_month(-1);
+                    } else if cell.x >= self.size.x / cell_size.x - 1. {
+                        self.move_month(1);
+                    }
+                } else if let Some(date) = self.date_at(self.cursor_pos) {
+                    if self.is_selectable(date) {
+                        // Display the month of the day clicked (e.g. a day of the next month)
+                        if (date.year, date.month) != (self.year, self.month) {
+                            self.set_month(date.year, date.month);
+                        }
+                        self.select(date);
+                        if let Some(mut message) = self.on_selection_change.clone() {
+                            message.set_event(event);
+                            if let Some(message) = message.downcast_mut::<ValueChanged<Vec<Date>>>() {
+                                message.value = self.selected.clone();
+                            }
+                            messages.enqueue(message);
+                        }
+                    }
+                }
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let cell_size = self.cell_size();
+        let first_column = if self.show_week_numbers { 1 } else { 0 };
+        let mut instructions = vec![
+            // Calendar rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Header, with the buttons to navigate between the months
+        let header_size = Vector2D::new(self.size.x, cell_size.y);
+        instructions.push(self.text_recipe(
+            format!("{} {}", MONTH_NAMES[self.month as usize - 1], self.year),
+            self.position,
+            header_size,
+            self.foreground_color,
+        ));
+        instructions.push(self.text_recipe(
+            String::from("<"),
+            self.position,
+            cell_size,
+            self.foreground_color,
+        ));
+        instructions.push(self.text_recipe(
+            String::from(">"),
+            self.position + Vector2D::new(self.size.x - cell_size.x, 0.),
+            cell_size,
+            self.foreground_color,
+        ));
+
+        // Names of the days of the week
+        for (column, name) in WEEKDAY_NAMES.iter().enumerate() {
+            instructions.push(self.text_recipe(
+                String::from(*name),
+                self.cell_position(first_column + column, 1),
+                cell_size,
+                self.muted_color,
+            ));
+        }
+
+        let first_day = self.first_day();
+        for row in 0..(ROWS - 2) {
+            let monday = first_day.add_days((row * 7) as i64);
+            if self.show_week_numbers {
+                instructions.push(self.text_recipe(
+                    monday.week_number().to_string(),
+                    self.cell_position(0, row + 2),
+                    cell_size,
+                    self.muted_color,
+                ));
+            }
+
+            for column in 0..7 {
+                let date = monday.add_days(column as i64);
+                let point = self.cell_position(first_column + column, row + 2);
+                if self.is_selected(date) {
+                    instructions.push(RenderInstruction::DrawRect {
+                        point,
+                        color: self.selection_color,
+                        size: cell_size,
+                        clip_point: self.position,
+                        clip_size: self.size,
+                        blend_mode: BlendMode::Normal,
+                    });
+                }
+                let color = if date.month == self.month && self.is_selectable(date) {
+                    self.foreground_color
+                } else {
+                    self.muted_color
+                };
+                instructions.push(self.text_recipe(date.day.to_string(), point, cell_size, color));
+            }
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.muted_color = self
+            .style
+            .muted_color
+            .unwrap_or(self.foreground_color.with_opacity(0.4));
+        self.selection_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.dirty = true;
+    }
+}
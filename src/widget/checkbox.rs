@@ -1,8 +1,11 @@
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::interaction::{Interaction, InteractionStateMachine};
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{propagate_event, EventStatus, Layout, TabIndex, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -15,6 +18,9 @@ pub struct CheckBoxWidget {
     /// The checkbox's identifier
     id: usize,
 
+    /// The data attached to the checkbox through its handle
+    widget_data: WidgetData,
+
     /// The checkbox's background color
     background_color: Color,
 
@@ -35,8 +41,8 @@ pub struct CheckBoxWidget {
     /// The checkbox's relative border size when checked
     selected_relative_size: f64,
     
-    /// The cursor's position
-    cursor_pos: Vector2D,
+    /// Whether the checkbox is hovered or pressed
+    interaction: InteractionStateMachine,
     
     /// Whether the checkbox is checked
     is_checked: bool,
@@ -62,6 +68,16 @@ pub struct CheckBoxWidget {
 
     /// The checkbox's offset vector coordinates
     offset: Vector2D,
+
+    /// Whether the checkbox's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// The checkbox's position in the focus traversal
+    tab_index: TabIndex,
+
+    /// The checkbox's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl CheckBoxWidget {
@@ -91,6 +107,12 @@ impl CheckBoxWidget {
     ) -> CheckBoxWidget {
         CheckBoxWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_accent_color(selected_color)
+                .with_border_color(border_color)
+                .with_border_thickness(border_size),
             background_color: background_color,
             selected_color: selected_color,
             border_color: border_color,
@@ -98,7 +120,7 @@ impl CheckBoxWidget {
             is_checked: is_checked,
             border_size: border_size,
             selected_relative_size: selected_relative_size,
-            cursor_pos: Vector2D::new(-1., -1.),
+            interaction: InteractionStateMachine::new(),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
             position: Vector2D::new(0., 0.),
@@ -106,6 +128,8 @@ impl CheckBoxWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            tab_index: TabIndex::Auto,
         }
     }
 
@@ -130,39 +154,250 @@ impl CheckBoxWidget {
     pub fn get_is_checked(&self) -> bool {
         self.is_checked
     }
+
+    /// Sets whether the checkbox is checked, without handling the `on_change` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_checked` - boolean indicating if the checkbox is checked
+    pub fn set_checked(&mut self, is_checked: bool) {
+        self.is_checked = is_checked;
+        self.dirty = true;
+    }
+
+    /// Sets whether the checkbox's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the checkbox's size (width and height), returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the checkbox
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the checkbox's background color, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the checkbox
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the checkbox's color of the selection mark, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the color of the selection mark assigned
+    ///
+    /// # Arguments
+    /// * `selected_color` - the color of the selection mark to be assigned to the checkbox
+    pub fn with_selected_color(mut self, selected_color: Color) -> Self {
+        self.style.accent_color = Some(selected_color);
+        self.selected_color = selected_color;
+        self
+    }
+
+    /// Sets the checkbox's border color, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the border color assigned
+    ///
+    /// # Arguments
+    /// * `border_color` - the border color to be assigned to the checkbox
+    pub fn with_border_color(mut self, border_color: Color) -> Self {
+        self.style.border_color = Some(border_color);
+        self.border_color = border_color;
+        self
+    }
+
+    /// Sets the checkbox's message to be handled when checked or unchecked, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the given message to be handled when checked or unchecked
+    ///
+    /// # Arguments
+    /// * `on_change` - the message to be handled when checked or unchecked
+    pub fn with_on_change(mut self, on_change: Option<Box<dyn Message>>) -> Self {
+        self.on_change = on_change;
+        self
+    }
+
+    /// Sets the checkbox's checked state, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the checked state assigned
+    ///
+    /// # Arguments
+    /// * `is_checked` - the checked state to be assigned to the checkbox
+    pub fn with_is_checked(mut self, is_checked: bool) -> Self {
+        self.is_checked = is_checked;
+        self
+    }
+
+    /// Sets the checkbox's border size, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the border size assigned
+    ///
+    /// # Arguments
+    /// * `border_size` - the border size to be assigned to the checkbox
+    pub fn with_border_size(mut self, border_size: f64) -> Self {
+        self.style.border_thickness = Some(border_size);
+        self.border_size = border_size;
+        self
+    }
+
+    /// Sets the checkbox's size of the selection mark, relative to the checkbox, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the size of the selection mark, relative to the checkbox assigned
+    ///
+    /// # Arguments
+    /// * `selected_relative_size` - the size of the selection mark, relative to the checkbox to be assigned to the checkbox
+    pub fn with_selected_relative_size(mut self, selected_relative_size: f64) -> Self {
+        self.selected_relative_size = selected_relative_size;
+        self
+    }
+
+    /// Toggles the checked state of the checkbox, handling its message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that toggled the checkbox
+    /// * `messages` - queue of messages
+    fn toggle(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(mut message) = self.on_change.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<bool>>() {
+                message.value = !self.is_checked;
+            }
+            messages.enqueue(message);
+        }
+        self.is_checked = !self.is_checked;
+        //set widget as dirty when we switch is_checked state to update UI
+        self.set_dirty(true);
+    }
+
+    /// Sets the checkbox's position in the focus traversal (e.g. to exclude it from the traversal)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `tab_index` - the checkbox's tab index
+    pub fn set_tab_index(&mut self, tab_index: TabIndex) {
+        self.tab_index = tab_index;
+        self.dirty = true;
+    }
+
+    /// Sets the checkbox's position in the focus traversal, returning the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the tab index assigned
+    ///
+    /// # Arguments
+    /// * `tab_index` - the tab index to be assigned to the checkbox
+    pub fn with_tab_index(mut self, tab_index: TabIndex) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Sets the checkbox's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the checkbox
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the checkbox's style, overriding the properties of the current theme, returning
+    /// the checkbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The checkbox with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the checkbox
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for CheckBoxWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        CheckBoxWidget {
+            style: Style::new(),
+            ..CheckBoxWidget::new(
+                Vector2D::new(20., 20.),
+                theme.palette.surface,
+                theme.palette.accent,
+                theme.palette.border,
+                None,
+                false,
+                theme.border_thickness,
+                0.5,
+            )
+        }
+    }
 }
 
 impl Widget for CheckBoxWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let area = Rect::new(self.position, self.size);
         match event {
-            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
-                //update cursor_pos on mouse move
-                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
-            }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
                 //check if cursor is inside checkbox area
-                if self.is_cursor_inside(self.cursor_pos) {
-                    if let Some(mut message) = self.on_change.clone() {
-                        message.set_event(event);
-                        messages.enqueue(message);
-                    }
-                    self.is_checked = !self.is_checked;
-                    //set widget as dirty when we switch is_checked state to update UI
-                    self.set_dirty(true);
+                if self.interaction.on_event(event, area) == Some(Interaction::Pressed) {
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
-            _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                //toggle the checkbox if the release is inside it, after a press inside it
+                if self.interaction.on_event(event, area) == Some(Interaction::Clicked) {
+                    self.toggle(event, messages);
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
+            _ => {
+                self.interaction.on_event(event, area);
+                propagate_event(&self.children, event, messages)
+            }
         }
     }
 
@@ -174,6 +409,22 @@ impl Widget for CheckBoxWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn tab_index(&self) -> TabIndex {
+        self.tab_index
+    }
+
+    fn activate(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.toggle(event, messages);
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         if self.is_checked {
             vec![
@@ -183,6 +434,7 @@ impl Widget for CheckBoxWidget {
                     size: self.size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
                 RenderInstruction::DrawRect {
                     point: Vector2D::new(
@@ -196,6 +448,7 @@ impl Widget for CheckBoxWidget {
                     ),
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
             ]
         } else {
@@ -206,6 +459,7 @@ impl Widget for CheckBoxWidget {
                     size: self.size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
                 RenderInstruction::DrawRect {
                     point: Vector2D::new(
@@ -219,6 +473,7 @@ impl Widget for CheckBoxWidget {
                     ),
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
             ]
         }
@@ -299,13 +554,9 @@ impl Widget for CheckBoxWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -318,4 +569,16 @@ impl Widget for CheckBoxWidget {
             false
         }
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.selected_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.border_color = self.style.border_color.unwrap_or(theme.palette.border);
+        self.border_size = self.style.border_thickness.unwrap_or(theme.border_thickness);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,492 @@
+use crate::clock;
+use crate::event::Event;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use std::time::Instant;
+
+/// Time taken by the indeterminate arc to perform a full turn, in milliseconds
+const SPIN_PERIOD: u128 = 1000;
+
+/// Angle swept by the indeterminate arc, in degrees
+const SPIN_SWEEP: usize = 90;
+
+/// Circular progress is a widget that displays the progress of a task as an arc
+/// around a circle, the circular counterpart of the progress bar. When the
+/// progress of the task is unknown, the circular progress can be set as
+/// indeterminate, displaying an arc spinning around the circle.
+#[derive(Clone)]
+pub struct CircularProgressWidget {
+    /// The circular progress's identifier
+    id: usize,
+
+    /// The data attached to the circular progress through its handle
+    widget_data: WidgetData,
+
+    /// The current progress, from 0 to 100
+    progress: f64,
+
+    /// The thickness of the ring. If it is equal to or bigger than the radius,
+    /// the progress is displayed as a pie
+    thickness: f64,
+
+    /// The angle where the progress arc starts, in degrees
+    start_angle: usize,
+
+    /// Whether the progress is unknown (i.e., the arc keeps spinning)
+    is_indeterminate: bool,
+
+    /// The instant when the indeterminate arc started spinning
+    spin_start: Instant,
+
+    /// The circular progress's background color
+    background_color: Color,
+
+    /// The color of the ring not yet filled by the progress
+    track_color: Color,
+
+    /// The color of the progress arc
+    foreground_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The circular progress's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The circular progress's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The circular progress's current size (width and height)
+    size: Vector2D,
+
+    /// The circular progress's original size (width and height)
+    original_size: Vector2D,
+
+    /// The circular progress's layout
+    layout: Layout,
+
+    /// The circular progress's offset vector coordinates
+    offset: Vector2D,
+
+    /// The circular progress's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl CircularProgressWidget {
+    /// Creates a new `CircularProgressWidget`
+    ///
+    /// # Returns
+    /// The circular progress created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the circular progress
+    /// * `thickness` - the thickness to be assigned to the ring
+    /// * `progress` - the initial progress, from 0 to 100
+    /// * `background_color` - the color to be assigned to the circular progress's background
+    /// * `track_color` - the color to be assigned to the ring not yet filled by the progress
+    /// * `foreground_color` - the color to be assigned to the progress arc
+    pub fn new(
+        size: Vector2D,
+        thickness: f64,
+        progress: f64,
+        background_color: Color,
+        track_color: Color,
+        foreground_color: Color,
+    ) -> CircularProgressWidget {
+        CircularProgressWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_muted_color(track_color)
+                .with_accent_color(foreground_color),
+            progress,
+            thickness,
+            start_angle: 270,
+            is_indeterminate: false,
+            spin_start: clock::now(),
+            background_color,
+            track_color,
+            foreground_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the current progress
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `value` - the progress to be assigned, from 0 to 100
+    pub fn set_progress(&mut self, value: f64) {
+        self.progress = value.clamp(0., 100.);
+        self.dirty = true;
+    }
+
+    /// Gets the current progress
+    ///
+    /// # Returns
+    /// The current progress, from 0 to 100
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// Sets the thickness of the ring
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `thickness` - the thickness to be assigned to the ring
+    pub fn set_thickness(&mut self, thickness: f64) {
+        self.thickness = thickness;
+        self.dirty = true;
+    }
+
+    /// Sets the angle where the progress arc starts
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `start_angle` - the angle where the progress arc starts, in degrees
+    pub fn set_start_angle(&mut self, start_angle: usize) {
+        self.start_angle = start_angle % 360;
+        self.dirty = true;
+    }
+
+    /// Sets whether the progress is unknown, making the arc spin around the circle
+    ///
+    /// While indeterminate, the circular progress is rebuilt on every frame.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_indeterminate` - boolean indicating if the progress is unknown
+    pub fn set_indeterminate(&mut self, is_indeterminate: bool) {
+        if is_indeterminate && !self.is_indeterminate {
+            self.spin_start = clock::now();
+        }
+        self.is_indeterminate = is_indeterminate;
+        self.dirty = true;
+    }
+
+    /// Gets the start and end angles of the arc to be drawn, for internal use only
+    ///
+    /// # Returns
+    /// The start and end angles of the arc, in degrees
+    ///
+    /// # Arguments
+    /// No arguments
+    fn arc_angles(&self) -> (usize, usize) {
+        if self.is_indeterminate {
+            let elapsed = clock::elapsed(self.spin_start).as_millis() % SPIN_PERIOD;
+            let rotation = (elapsed * 360 / SPIN_PERIOD) as usize;
+            let start = (self.start_angle + rotation) % 360;
+            (start, start + SPIN_SWEEP)
+        } else {
+            let sweep = (self.progress * 3.6).round() as usize;
+            (self.start_angle, self.start_angle + sweep)
+        }
+    }
+
+    /// Sets the circular progress's size (width and height), returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the circular progress
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the circular progress's thickness of the ring, returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the thickness of the ring assigned
+    ///
+    /// # Arguments
+    /// * `thickness` - the thickness of the ring to be assigned to the circular progress
+    pub fn with_thickness(mut self, thickness: f64) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the circular progress's progress, from 0 to 100, returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the progress, from 0 to 100 assigned
+    ///
+    /// # Arguments
+    /// * `progress` - the progress, from 0 to 100 to be assigned to the circular progress
+    pub fn with_progress(mut self, progress: f64) -> Self {
+        self.progress = progress.clamp(0., 100.);
+        self
+    }
+
+    /// Sets the circular progress's background color, returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the circular progress
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the circular progress's color of the ring not yet filled by the progress, returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the color of the ring not yet filled by the progress assigned
+    ///
+    /// # Arguments
+    /// * `track_color` - the color of the ring not yet filled by the progress to be assigned to the circular progress
+    pub fn with_track_color(mut self, track_color: Color) -> Self {
+        self.style.muted_color = Some(track_color);
+        self.track_color = track_color;
+        self
+    }
+
+    /// Sets the circular progress's foreground color, returning the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the foreground color assigned
+    ///
+    /// # Arguments
+    /// * `foreground_color` - the foreground color to be assigned to the circular progress
+    pub fn with_foreground_color(mut self, foreground_color: Color) -> Self {
+        self.style.accent_color = Some(foreground_color);
+        self.foreground_color = foreground_color;
+        self
+    }
+
+    /// Sets the circular progress's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the circular progress
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the circular progress's style, overriding the properties of the current theme, returning
+    /// the circular progress (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The circular progress with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the circular progress
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for CircularProgressWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        CircularProgressWidget {
+            style: Style::new(),
+            ..CircularProgressWidget::new(
+                Vector2D::new(48., 48.),
+                4.,
+                0.,
+                theme.palette.surface,
+                theme.palette.muted,
+                theme.palette.accent,
+            )
+        }
+    }
+}
+
+impl Widget for CircularProgressWidget {
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let radius = self.size.x.min(self.size.y) * 0.5;
+        let center = self.position + self.size * 0.5;
+        let (s_ang, e_ang) = self.arc_angles();
+
+        let mut instructions = vec![
+            // Circular progress rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Ring track
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: radius as usize,
+                color: self.track_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Progress arc
+            RenderInstruction::DrawArc {
+                point: center,
+                r: radius as usize,
+                s_ang,
+                e_ang,
+                color: self.foreground_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Hollow the center of the circle to display a ring instead of a pie
+        if self.thickness < radius {
+            instructions.push(RenderInstruction::DrawCircle {
+                point: center,
+                r: (radius - self.thickness) as usize,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        // The indeterminate arc needs to be redrawn on every frame
+        self.dirty || self.is_indeterminate
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, _cursor_pos: Vector2D) -> bool {
+        false
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.track_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.foreground_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
+}
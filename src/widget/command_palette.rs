@@ -0,0 +1,907 @@
+use crate::event;
+use crate::event::{Event, ModifiersState};
+use crate::key_code::{shortcut_to_string, KeyCode};
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget, WidgetData,
+};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Maximum number of commands listed at once, the remaining ones being reached by scrolling
+const MAX_VISIBLE_ROWS: usize = 8;
+
+/// Average width of a glyph, as a fraction of the font size, used to place the text
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// A command registered on a [`CommandPaletteWidget`]
+#[derive(Clone)]
+pub struct Command {
+    /// The command's label, matched against the filter
+    pub label: String,
+
+    /// The command's own shortcut, which executes it while the palette is closed
+    pub shortcut: Option<(ModifiersState, KeyCode)>,
+
+    /// The message to be handled when the command is executed
+    pub message: Box<dyn Message>,
+}
+
+/// Command palette is a widget that wraps the user interface and, when its shortcut
+/// is pressed, opens a searchable list of the registered commands over it.
+///
+/// While open, the palette takes every keyboard and mouse button event: the characters
+/// typed filter the commands by their label, the up and down arrows move the selection,
+/// enter (or a click) executes the selected command, handling its message, and escape
+/// (or a click outside the palette) closes it. While closed, every event is forwarded
+/// to its children, laid out as a vertical list, except for the shortcuts of the palette
+/// and of the registered commands.
+#[derive(Clone)]
+pub struct CommandPaletteWidget {
+    /// The command palette's identifier
+    id: usize,
+
+    /// The data attached to the command palette through its handle
+    widget_data: WidgetData,
+
+    /// The commands registered
+    commands: Vec<Command>,
+
+    /// The shortcut that opens and closes the command palette
+    shortcut: (ModifiersState, KeyCode),
+
+    /// Whether the command palette is open
+    is_open: bool,
+
+    /// The text typed to filter the commands
+    filter: String,
+
+    /// The text displayed while the filter is empty
+    placeholder: String,
+
+    /// The indexes of the commands matching the filter, from the best match to the worst
+    matches: Vec<usize>,
+
+    /// The index, on the matches, of the command selected
+    selected: usize,
+
+    /// The index, on the matches, of the first command listed
+    first_visible: usize,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The command palette's font size
+    font_size: usize,
+
+    /// The command palette's background color
+    background_color: Color,
+
+    /// The command palette's text color
+    text_color: Color,
+
+    /// The color of the command selected
+    selection_color: Color,
+
+    /// The color drawn over the children while the command palette is open
+    backdrop_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The command palette's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The command palette's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The command palette's current size (width and height)
+    size: Vector2D,
+
+    /// The command palette's original size (width and height)
+    original_size: Vector2D,
+
+    /// The command palette's layout
+    layout: Layout,
+
+    /// The command palette's offset vector coordinates
+    offset: Vector2D,
+
+    /// The command palette's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl CommandPaletteWidget {
+    /// Creates a new `CommandPaletteWidget`, opened by `Ctrl+Shift+P`
+    ///
+    /// # Returns
+    /// The command palette created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the command palette
+    /// * `font_size` - the font size to be assigned to the command palette
+    /// * `background_color` - the color to be assigned to the command palette's background
+    /// * `text_color` - the color to be assigned to the command palette's text
+    /// * `selection_color` - the color to be assigned to the command selected
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        text_color: Color,
+        selection_color: Color,
+    ) -> CommandPaletteWidget {
+        CommandPaletteWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color)
+                .with_selection_color(selection_color),
+            commands: Vec::new(),
+            shortcut: (
+                ModifiersState {
+                    shift: true,
+                    control: true,
+                    alt: false,
+                    logo: false,
+                },
+                KeyCode::P,
+            ),
+            is_open: false,
+            filter: String::new(),
+            placeholder: String::from("Type a command"),
+            matches: Vec::new(),
+            selected: 0,
+            first_visible: 0,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            text_color,
+            selection_color,
+            backdrop_color: Color::from_hex(0x66000000),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Box(Axis::Vertical),
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Registers a command on the command palette
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `label` - the command's label, matched against the filter
+    /// * `shortcut` - the command's own shortcut (e.g. parsed by [`parse_shortcut`](`crate::key_code::parse_shortcut`)), displayed next to its label
+    /// * `message` - the message to be handled when the command is executed
+    pub fn add_command(
+        &mut self,
+        label: &str,
+        shortcut: Option<(ModifiersState, KeyCode)>,
+        message: Box<dyn Message>,
+    ) {
+        self.commands.push(Command {
+            label: String::from(label),
+            shortcut,
+            message,
+        });
+        self.update_matches();
+    }
+
+    /// Removes the commands registered with a label
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `label` - the label of the commands to be removed
+    pub fn remove_command(&mut self, label: &str) {
+        self.commands.retain(|command| command.label != label);
+        self.update_matches();
+    }
+
+    /// Removes every command registered
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn clear_commands(&mut self) {
+        self.commands.clear();
+        self.update_matches();
+    }
+
+    /// Gets the commands registered
+    ///
+    /// # Returns
+    /// The commands registered, in the order they were registered
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Sets the shortcut that opens and closes the command palette
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `modifiers` - the modifiers of the shortcut
+    /// * `key_code` - the key of the shortcut
+    pub fn set_shortcut(&mut self, modifiers: ModifiersState, key_code: KeyCode) {
+        self.shortcut = (modifiers, key_code);
+    }
+
+    /// Sets the text displayed while the filter is empty
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `placeholder` - the text to be displayed
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = String::from(placeholder);
+        self.dirty = true;
+    }
+
+    /// Sets the color drawn over the children while the command palette is open
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `backdrop_color` - the color to be assigned, usually translucent
+    pub fn set_backdrop_color(&mut self, backdrop_color: Color) {
+        self.backdrop_color = backdrop_color;
+        self.dirty = true;
+    }
+
+    /// Opens the command palette, with an empty filter
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.filter.clear();
+        self.update_matches();
+    }
+
+    /// Closes the command palette
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.dirty = true;
+    }
+
+    /// Checks if the command palette is open
+    ///
+    /// # Returns
+    /// True, if the command palette is open, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Gets the text typed to filter the commands
+    ///
+    /// # Returns
+    /// The text typed to filter the commands
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Ranks how well a label matches a filter, for internal use only
+    ///
+    /// # Returns
+    /// The rank of the match, lower being better, or `None` if the label does not match
+    ///
+    /// # Arguments
+    /// * `label` - the label to be matched
+    /// * `filter` - the filter, in lowercase
+    fn match_rank(label: &str, filter: &str) -> Option<usize> {
+        let label = label.to_lowercase();
+        if label.starts_with(filter) {
+            Some(0)
+        } else if label.contains(filter) {
+            Some(1)
+        } else {
+            // The characters of the filter appear in the label, in the same order
+            let mut characters = label.chars();
+            if filter
+                .chars()
+                .all(|character| characters.any(|value| value == character))
+            {
+                Some(2)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Updates the commands matching the filter and resets the selection, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn update_matches(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let mut ranks: Vec<(usize, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                Self::match_rank(&command.label, &filter).map(|rank| (rank, index))
+            })
+            .collect();
+        ranks.sort_by_key(|(rank, _)| *rank);
+        self.matches = ranks.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+        self.first_visible = 0;
+        self.dirty = true;
+    }
+
+    /// Moves the selection, wrapping around the matches, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_forward` - boolean indicating if the selection moves down the list
+    fn move_selection(&mut self, is_forward: bool) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let count = self.matches.len();
+        self.selected = if is_forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+        // Scroll the list so the selection stays visible
+        if self.selected < self.first_visible {
+            self.first_visible = self.selected;
+        } else if self.selected >= self.first_visible + MAX_VISIBLE_ROWS {
+            self.first_visible = self.selected + 1 - MAX_VISIBLE_ROWS;
+        }
+        self.dirty = true;
+    }
+
+    /// Executes a command, handling its message, and closes the command palette,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the command on the commands registered
+    /// * `event` - the event that executed the command
+    /// * `messages` - queue of messages
+    fn execute(&mut self, index: usize, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(command) = self.commands.get(index) {
+            let mut message = command.message.clone();
+            message.set_event(event);
+            messages.enqueue(message);
+        }
+        self.close();
+    }
+
+    /// Gets the height of each row of the command palette, for internal use only
+    ///
+    /// # Returns
+    /// The height of each row
+    ///
+    /// # Arguments
+    /// No arguments
+    fn row_height(&self) -> f64 {
+        self.font_size as f64 * 2.
+    }
+
+    /// Gets the area of the palette, centered horizontally near the top of the
+    /// command palette's area, for internal use only
+    ///
+    /// # Returns
+    /// The position and the size of the palette
+    ///
+    /// # Arguments
+    /// No arguments
+    fn palette_area(&self) -> (Vector2D, Vector2D) {
+        let rows = self.matches.len().clamp(1, MAX_VISIBLE_ROWS) + 1;
+        let width = (self.size.x * 0.6).max(self.size.x.min(240.)).min(560.);
+        let size = Vector2D::new(width, rows as f64 * self.row_height() + 2.);
+        let point = Vector2D::new(
+            self.position.x + (self.size.x - width) * 0.5,
+            self.position.y + self.size.y * 0.15,
+        );
+        (point, size)
+    }
+
+    /// Gets the index, on the matches, of the command under a position, for internal use only
+    ///
+    /// # Returns
+    /// The index of the command under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn match_at(&self, position: Vector2D) -> Option<usize> {
+        let (point, size) = self.palette_area();
+        let local = position - point;
+        if local.x < 0. || local.x > size.x || local.y < self.row_height() + 1. {
+            return None;
+        }
+        let row = ((local.y - self.row_height() - 1.) / self.row_height()) as usize;
+        let index = self.first_visible + row;
+        if row < MAX_VISIBLE_ROWS && index < self.matches.len() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if a position is inside the palette, for internal use only
+    ///
+    /// # Returns
+    /// True, if the position is inside the palette, false otherwise
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn is_inside_palette(&self, position: Vector2D) -> bool {
+        let (point, size) = self.palette_area();
+        position.x >= point.x
+            && position.x <= point.x + size.x
+            && position.y >= point.y
+            && position.y <= point.y + size.y
+    }
+
+    /// Sets the command palette's size (width and height), returning the command palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The command palette with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the command palette
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the command palette's shortcut, returning the command palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The command palette with the shortcut assigned
+    ///
+    /// # Arguments
+    /// * `modifiers` - the modifiers of the shortcut
+    /// * `key_code` - the key of the shortcut
+    pub fn with_shortcut(mut self, modifiers: ModifiersState, key_code: KeyCode) -> Self {
+        self.set_shortcut(modifiers, key_code);
+        self
+    }
+
+    /// Registers a command on the command palette, returning the command palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The command palette with the command registered
+    ///
+    /// # Arguments
+    /// * `label` - the command's label, matched against the filter
+    /// * `shortcut` - the command's own shortcut, displayed next to its label
+    /// * `message` - the message to be handled when the command is executed
+    pub fn with_command(
+        mut self,
+        label: &str,
+        shortcut: Option<(ModifiersState, KeyCode)>,
+        message: Box<dyn Message>,
+    ) -> Self {
+        self.add_command(label, shortcut, message);
+        self
+    }
+
+    /// Sets the command palette's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the command palette
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the command palette's style, overriding the properties of the current theme,
+    /// returning the command palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The command palette with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the command palette
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for CommandPaletteWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        CommandPaletteWidget {
+            style: Style::new(),
+            ..CommandPaletteWidget::new(
+                Vector2D::new(100., 100.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.selection,
+            )
+        }
+    }
+}
+
+impl Widget for CommandPaletteWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        if let event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) = event {
+            self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+            if self.is_open {
+                if let Some(index) = self.match_at(self.cursor_pos) {
+                    if index != self.selected {
+                        self.selected = index;
+                        self.dirty = true;
+                    }
+                }
+            }
+        }
+
+        if self.is_open {
+            match event {
+                event::Event::Keyboard(event::Keyboard::KeyPressed {
+                    key_code,
+                    modifiers,
+                }) if key_code == self.shortcut.1 && modifiers.matches(self.shortcut.0) => {
+                    self.close();
+                }
+                event::Event::Keyboard(event::Keyboard::KeyPressed { key_code, .. }) => {
+                    match key_code {
+                        KeyCode::Escape => self.close(),
+                        KeyCode::Up => self.move_selection(false),
+                        KeyCode::Down => self.move_selection(true),
+                        KeyCode::Enter | KeyCode::NumpadEnter => {
+                            if let Some(index) = self.matches.get(self.selected).copied() {
+                                self.execute(index, event, messages);
+                            }
+                        }
+                        KeyCode::Backspace if !self.filter.is_empty() => {
+                            self.filter.pop();
+                            self.update_matches();
+                        }
+                        _ => {}
+                    }
+                }
+                event::Event::Keyboard(event::Keyboard::CharacterReceived(character))
+                    if !character.is_control() =>
+                {
+                    self.filter.push(character);
+                    self.update_matches();
+                }
+                event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                    if let Some(index) = self.match_at(self.cursor_pos) {
+                        self.execute(self.matches[index], event, messages);
+                    } else if !self.is_inside_palette(self.cursor_pos) {
+                        self.close();
+                    }
+                }
+                _ => {}
+            }
+            // The palette is modal, so only the cursor movements reach the children
+            if !matches!(event, event::Event::Mouse(event::Mouse::CursorMoved { .. })) {
+                return EventStatus::Captured;
+            }
+        } else if let event::Event::Keyboard(event::Keyboard::KeyPressed {
+            key_code,
+            modifiers,
+        }) = event
+        {
+            if key_code == self.shortcut.1 && modifiers.matches(self.shortcut.0) {
+                self.open();
+                return EventStatus::Captured;
+            }
+            let command = self.commands.iter().position(|command| {
+                command
+                    .shortcut
+                    .is_some_and(|(value, key)| key == key_code && modifiers.matches(value))
+            });
+            if let Some(index) = command {
+                self.execute(index, event, messages);
+                return EventStatus::Captured;
+            }
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        if !self.is_open {
+            return vec![];
+        }
+
+        let (point, size) = self.palette_area();
+        let row_height = self.row_height();
+        let font_size = self.font_size as f64;
+        let mut instructions = vec![
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: self.backdrop_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Palette border
+            RenderInstruction::DrawRect {
+                point,
+                size,
+                color: self.text_color.with_opacity(0.4),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            RenderInstruction::DrawRect {
+                point: point + 1.,
+                size: size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Separator between the filter and the commands
+            RenderInstruction::DrawRect {
+                point: Vector2D::new(point.x + 1., point.y + row_height),
+                size: Vector2D::new(size.x - 2., 1.),
+                color: self.text_color.with_opacity(0.2),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        let (filter, filter_color) = if self.filter.is_empty() {
+            (self.placeholder.clone(), self.text_color.with_opacity(0.5))
+        } else {
+            (self.filter.clone(), self.text_color)
+        };
+        instructions.push(RenderInstruction::DrawText {
+            point: Vector2D::new(point.x + 8., point.y + (row_height + font_size) * 0.5),
+            font_size: self.font_size,
+            string: filter,
+            color: filter_color,
+            clip_point: point,
+            clip_size: Vector2D::new(size.x, row_height),
+            font: FontDescriptor::default(),
+        });
+
+        if self.matches.is_empty() {
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    point.x + 8.,
+                    point.y + row_height + 1. + (row_height + font_size) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: String::from("No matching commands"),
+                color: self.text_color.with_opacity(0.5),
+                clip_point: point,
+                clip_size: size,
+                font: FontDescriptor::default(),
+            });
+        }
+
+        let visible = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.first_visible)
+            .take(MAX_VISIBLE_ROWS);
+        for (row, (index, command_index)) in visible.enumerate() {
+            let command = &self.commands[*command_index];
+            let row_point =
+                Vector2D::new(point.x + 1., point.y + 1. + row_height * (row + 1) as f64);
+            let row_size = Vector2D::new(size.x - 2., row_height);
+            if index == self.selected {
+                instructions.push(RenderInstruction::DrawRect {
+                    point: row_point,
+                    size: row_size,
+                    color: self.selection_color,
+                    clip_point: row_point,
+                    clip_size: row_size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            let baseline = row_point.y + (row_height + font_size) * 0.5;
+
+            // Shortcut, aligned to the right end of the row
+            let mut label_width = row_size.x - 14.;
+            if let Some((modifiers, key_code)) = command.shortcut {
+                let hint = shortcut_to_string(modifiers, key_code);
+                let width = hint.chars().count() as f64 * font_size * GLYPH_WIDTH;
+                label_width -= width + 8.;
+                instructions.push(RenderInstruction::DrawText {
+                    point: Vector2D::new(row_point.x + row_size.x - width - 7., baseline),
+                    font_size: self.font_size,
+                    string: hint,
+                    color: self.text_color.with_opacity(0.6),
+                    clip_point: row_point,
+                    clip_size: row_size,
+                    font: FontDescriptor::default(),
+                });
+            }
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(row_point.x + 7., baseline),
+                font_size: self.font_size,
+                string: command.label.clone(),
+                color: self.text_color,
+                clip_point: row_point,
+                clip_size: Vector2D::new(label_width.max(0.) + 7., row_height),
+                font: FontDescriptor::default(),
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.selection_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,584 @@
+use crate::event;
+use crate::event::Event;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, MemoKey, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Weak;
+
+/// Height of a line, as a multiple of the font size
+const LINE_HEIGHT: f64 = 1.4;
+
+/// Average width of a glyph, as a fraction of the font size, used to place the spans
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Space between the console's border and its lines
+const PADDING: f64 = 4.;
+
+/// Span of a console's line, with its own color (e.g. a highlighted word)
+#[derive(Clone)]
+pub struct ConsoleSpan {
+    /// The span's text
+    pub text: String,
+
+    /// The span's color
+    pub color: Color,
+}
+
+impl ConsoleSpan {
+    /// Creates a new `ConsoleSpan`
+    ///
+    /// # Returns
+    /// The span created
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the span
+    /// * `color` - the color to be assigned to the span
+    pub fn new(text: &str, color: Color) -> ConsoleSpan {
+        ConsoleSpan {
+            text: String::from(text),
+            color,
+        }
+    }
+}
+
+/// Console is a widget to display a log of lines, optimized for appending them.
+///
+/// The console keeps up to a capacity of lines, dropping the oldest ones as new lines
+/// are appended (i.e., a ring buffer), and each line is made of spans with their own
+/// colors. While the console is scrolled to the bottom, it follows the lines appended;
+/// once the user scrolls up with the mouse wheel, the lines displayed stay still until
+/// the user scrolls back to the bottom.
+///
+/// Only the lines displayed are turned into render instructions. The console also opts
+/// in to memoization (see [`Widget::memo_key`]), so appending lines while it is
+/// scrolled up keeps its previous render instructions instead of generating them again.
+#[derive(Clone)]
+pub struct ConsoleWidget {
+    /// The console's identifier
+    id: usize,
+
+    /// The data attached to the console through its handle
+    widget_data: WidgetData,
+
+    /// The console's lines, each with its sequence number, from the oldest to the newest
+    lines: VecDeque<(u64, Vec<ConsoleSpan>)>,
+
+    /// The sequence number of the next line appended
+    next_sequence: u64,
+
+    /// The maximum number of lines kept
+    capacity: usize,
+
+    /// The number of lines scrolled up from the bottom, or 0 if following the lines appended
+    scroll: usize,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The console's font size
+    font_size: usize,
+
+    /// The console's background color
+    background_color: Color,
+
+    /// The color of the lines appended as plain text
+    text_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The console's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The console's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The console's current size (width and height)
+    size: Vector2D,
+
+    /// The console's original size (width and height)
+    original_size: Vector2D,
+
+    /// The console's layout
+    layout: Layout,
+
+    /// The console's offset vector coordinates
+    offset: Vector2D,
+
+    /// The console's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ConsoleWidget {
+    /// Creates a new `ConsoleWidget`
+    ///
+    /// # Returns
+    /// The console created, with no lines
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the console
+    /// * `font_size` - the font size to be assigned to the console
+    /// * `capacity` - the maximum number of lines kept by the console
+    /// * `background_color` - the color to be assigned to the console's background
+    /// * `text_color` - the color to be assigned to the lines appended as plain text
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        capacity: usize,
+        background_color: Color,
+        text_color: Color,
+    ) -> ConsoleWidget {
+        ConsoleWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color),
+            lines: VecDeque::new(),
+            next_sequence: 0,
+            capacity: capacity.max(1),
+            scroll: 0,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            text_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Appends lines of plain text, with the console's text color
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `text` - the text to be appended, split into lines at each line break
+    pub fn push_text(&mut self, text: &str) {
+        for line in text.lines() {
+            self.push_spans(vec![ConsoleSpan::new(line, self.text_color)]);
+        }
+    }
+
+    /// Appends a line made of colored spans, dropping the oldest line if the console
+    /// is full
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `spans` - the spans of the line, from left to right
+    pub fn push_spans(&mut self, spans: Vec<ConsoleSpan>) {
+        self.lines.push_back((self.next_sequence, spans));
+        self.next_sequence += 1;
+        if self.lines.len() > self.capacity {
+            self.lines.pop_front();
+        }
+        // Keep the lines displayed still while scrolled up
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.max_scroll());
+        }
+        self.dirty = true;
+    }
+
+    /// Removes every line
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll = 0;
+        self.dirty = true;
+    }
+
+    /// Gets the number of lines kept
+    ///
+    /// # Returns
+    /// The number of lines kept, up to the console's capacity
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Gets a line kept
+    ///
+    /// # Returns
+    /// The spans of the line, or `None` if there is no such line
+    ///
+    /// # Arguments
+    /// * `index` - the index of the line, 0 being the oldest line kept
+    pub fn line(&self, index: usize) -> Option<&[ConsoleSpan]> {
+        self.lines.get(index).map(|(_, spans)| spans.as_slice())
+    }
+
+    /// Sets the maximum number of lines kept, dropping the oldest lines exceeding it
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `capacity` - the maximum number of lines kept by the console
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.lines.len() > self.capacity {
+            self.lines.pop_front();
+        }
+        self.scroll = self.scroll.min(self.max_scroll());
+        self.dirty = true;
+    }
+
+    /// Checks if the console follows the lines appended (i.e., it is scrolled to the bottom)
+    ///
+    /// # Returns
+    /// True, if the console is scrolled to the bottom, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_following(&self) -> bool {
+        self.scroll == 0
+    }
+
+    /// Scrolls the console to the bottom, following the lines appended again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn scroll_to_bottom(&mut self) {
+        if self.scroll != 0 {
+            self.scroll = 0;
+            self.dirty = true;
+        }
+    }
+
+    /// Gets the height of a line, for internal use only
+    ///
+    /// # Returns
+    /// The height of a line
+    ///
+    /// # Arguments
+    /// No arguments
+    fn line_height(&self) -> f64 {
+        self.font_size as f64 * LINE_HEIGHT
+    }
+
+    /// Gets the number of lines fitting in the console, for internal use only
+    ///
+    /// # Returns
+    /// The number of lines fitting in the console
+    ///
+    /// # Arguments
+    /// No arguments
+    fn visible_lines(&self) -> usize {
+        ((self.size.y - 2. * PADDING) / self.line_height())
+            .floor()
+            .max(0.) as usize
+    }
+
+    /// Gets the maximum number of lines the console can be scrolled up, for internal use only
+    ///
+    /// # Returns
+    /// The number of lines not fitting in the console
+    ///
+    /// # Arguments
+    /// No arguments
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.visible_lines())
+    }
+
+    /// Gets the index of the first line displayed, for internal use only
+    ///
+    /// # Returns
+    /// The index of the first line displayed
+    ///
+    /// # Arguments
+    /// No arguments
+    fn first_visible(&self) -> usize {
+        self.lines
+            .len()
+            .saturating_sub(self.visible_lines() + self.scroll)
+    }
+
+    /// Sets the console's size (width and height), returning the console (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The console with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the console
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the console's capacity, returning the console (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The console with the capacity assigned
+    ///
+    /// # Arguments
+    /// * `capacity` - the maximum number of lines kept by the console
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.set_capacity(capacity);
+        self
+    }
+
+    /// Sets the console's text color, returning the console (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The console with the text color assigned
+    ///
+    /// # Arguments
+    /// * `text_color` - the color to be assigned to the lines appended as plain text
+    pub fn with_text_color(mut self, text_color: Color) -> Self {
+        self.style.foreground_color = Some(text_color);
+        self.text_color = text_color;
+        self
+    }
+
+    /// Sets the console's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the console
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the console's style, overriding the properties of the current theme, returning the
+    /// console (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The console with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the console
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ConsoleWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ConsoleWidget {
+            style: Style::new(),
+            ..ConsoleWidget::new(
+                Vector2D::new(480., 240.),
+                theme.font_size,
+                1000,
+                theme.palette.surface,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for ConsoleWidget {
+    fn on_event(&mut self, event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::WheelScrolled {
+                delta: event::ScrollDelta::Pixels { y, .. },
+            }) if y != 0. && self.is_cursor_inside(self.cursor_pos) => {
+                let lines = (y.abs() / self.line_height()).ceil() as usize;
+                let scroll = if y > 0. {
+                    (self.scroll + lines).min(self.max_scroll())
+                } else {
+                    self.scroll.saturating_sub(lines)
+                };
+                if scroll != self.scroll {
+                    self.scroll = scroll;
+                    self.dirty = true;
+                }
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let mut instructions = vec![RenderInstruction::DrawRect {
+            point: self.position,
+            size: self.size,
+            color: self.background_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        }];
+
+        let line_height = self.line_height();
+        let glyph_width = self.font_size as f64 * GLYPH_WIDTH;
+        let first = self.first_visible();
+        let lines = self.lines.iter().skip(first).take(self.visible_lines());
+        for (row, (_, spans)) in lines.enumerate() {
+            let baseline =
+                self.position.y + PADDING + row as f64 * line_height + self.font_size as f64;
+            let mut x = self.position.x + PADDING;
+            for span in spans.iter() {
+                instructions.push(RenderInstruction::DrawText {
+                    point: Vector2D::new(x, baseline),
+                    font_size: self.font_size,
+                    string: span.text.clone(),
+                    color: span.color,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    font: FontDescriptor::default(),
+                });
+                x += span.text.chars().count() as f64 * glyph_width;
+            }
+        }
+
+        instructions
+    }
+
+    fn memo_key(&self) -> Option<u64> {
+        // The lines never change once appended, so their sequence numbers identify them
+        let first = self.first_visible();
+        let last = (first + self.visible_lines()).min(self.lines.len());
+        let sequence = |index: usize| self.lines.get(index).map(|(sequence, _)| *sequence);
+        Some(
+            MemoKey::new()
+                .with(&sequence(first))
+                .with(&(last - first))
+                .with(&self.font_size)
+                .with_color(self.background_color)
+                .finish(),
+        )
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+        // Fitting more lines may leave less to scroll
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
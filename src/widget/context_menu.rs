@@ -0,0 +1,546 @@
+use crate::event;
+use crate::event::Event;
+use crate::key_code::KeyCode;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text;
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Height of the items, as a multiple of the font size
+const ROW_HEIGHT: f64 = 2.;
+
+/// Space between the edges of the menu and the labels
+const PADDING: f64 = 12.;
+
+/// Minimum width of the menu
+const MIN_WIDTH: f64 = 120.;
+
+/// Item of a context menu, with the message handled when it is chosen
+#[derive(Clone)]
+pub struct ContextMenuItem {
+    /// The item's label
+    pub label: String,
+
+    /// The message to be handled when the item is chosen
+    pub message: Option<Box<dyn Message>>,
+}
+
+impl ContextMenuItem {
+    /// Creates a new `ContextMenuItem`
+    ///
+    /// # Returns
+    /// The context menu item created
+    ///
+    /// # Arguments
+    /// * `label` - the label to be assigned to the item
+    /// * `message` - the message to be handled when the item is chosen
+    pub fn new(label: &str, message: Option<Box<dyn Message>>) -> ContextMenuItem {
+        ContextMenuItem {
+            label: String::from(label),
+            message,
+        }
+    }
+}
+
+/// Context menu is a list of items opened at the cursor, as a popup over the whole
+/// display, whose items handle their message when chosen.
+///
+/// Context menus are usually registered on widgets with
+/// [`WidgetRef::set_context_menu`](`crate::widget::WidgetRef::set_context_menu`), such
+/// that right-clicking the widget opens the menu with its items. Releasing the left
+/// button over an item handles its message and dismisses the menu, as does clicking
+/// outside the menu or pressing `Escape`. The context closes the menu once dismissed.
+#[derive(Clone)]
+pub struct ContextMenuWidget {
+    /// The context menu's identifier
+    id: usize,
+
+    /// The data attached to the context menu through its handle
+    widget_data: WidgetData,
+
+    /// The context menu's items
+    items: Vec<ContextMenuItem>,
+
+    /// The point where the menu is opened (i.e., its top left corner, unless the menu
+    /// would not fit the display)
+    anchor: Vector2D,
+
+    /// The index of the item under the cursor, if any
+    hovered: Option<usize>,
+
+    /// Whether the menu was dismissed, to be closed
+    is_dismissed: bool,
+
+    /// The context menu's font size
+    font_size: usize,
+
+    /// The context menu's background color
+    background_color: Color,
+
+    /// The context menu's text color
+    text_color: Color,
+
+    /// The color of the item under the cursor
+    highlight_color: Color,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The context menu's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The context menu's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The context menu's current size (width and height), covering the whole display
+    size: Vector2D,
+
+    /// The context menu's original size (width and height)
+    original_size: Vector2D,
+
+    /// The context menu's layout
+    layout: Layout,
+
+    /// The context menu's offset vector coordinates
+    offset: Vector2D,
+
+    /// The context menu's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ContextMenuWidget {
+    /// Creates a new `ContextMenuWidget`
+    ///
+    /// # Returns
+    /// The context menu created
+    ///
+    /// # Arguments
+    /// * `items` - the items to be assigned to the context menu
+    /// * `font_size` - the font size to be assigned to the context menu
+    /// * `background_color` - the color to be assigned to the context menu's background
+    /// * `text_color` - the color to be assigned to the context menu's text
+    pub fn new(
+        items: Vec<ContextMenuItem>,
+        font_size: usize,
+        background_color: Color,
+        text_color: Color,
+    ) -> ContextMenuWidget {
+        ContextMenuWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color),
+            items,
+            anchor: Vector2D::new(0., 0.),
+            hovered: None,
+            is_dismissed: false,
+            font_size,
+            background_color,
+            text_color,
+            highlight_color: Color::from_hex(0xffcce4ff),
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size: Vector2D::new(0., 0.),
+            original_size: Vector2D::new(0., 0.),
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Prepares the menu to be opened at a point, with the given items
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `anchor` - the point where the menu is opened
+    /// * `items` - the items to be assigned to the context menu
+    pub fn open_at(&mut self, anchor: Vector2D, items: Vec<ContextMenuItem>) {
+        self.anchor = anchor;
+        self.items = items;
+        self.cursor_pos = anchor;
+        self.hovered = None;
+        self.is_dismissed = false;
+        self.dirty = true;
+    }
+
+    /// Gets the context menu's items
+    ///
+    /// # Returns
+    /// The context menu's items
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn items(&self) -> &[ContextMenuItem] {
+        &self.items
+    }
+
+    /// Checks whether the menu was dismissed (i.e., an item was chosen or the menu
+    /// was cancelled), to be closed
+    ///
+    /// # Returns
+    /// True, if the menu was dismissed, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_dismissed(&self) -> bool {
+        self.is_dismissed
+    }
+
+    /// Sets the color of the item under the cursor
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `highlight_color` - the color to be assigned to the item under the cursor
+    pub fn set_highlight_color(&mut self, highlight_color: Color) {
+        self.style.selection_color = Some(highlight_color);
+        self.highlight_color = highlight_color;
+        self.dirty = true;
+    }
+
+    /// Gets the height of the items, for internal use only
+    ///
+    /// # Returns
+    /// The height of the items
+    ///
+    /// # Arguments
+    /// No arguments
+    fn row_height(&self) -> f64 {
+        self.font_size as f64 * ROW_HEIGHT
+    }
+
+    /// Gets the area of the menu, moved from its anchor to fit the display, for
+    /// internal use only
+    ///
+    /// # Returns
+    /// The area of the menu
+    ///
+    /// # Arguments
+    /// No arguments
+    fn menu_area(&self) -> Rect {
+        let width = self
+            .items
+            .iter()
+            .map(|item| text::measure_text(&item.label, self.font_size).x + 2. * PADDING)
+            .fold(MIN_WIDTH, f64::max);
+        let size = Vector2D::new(width, self.items.len() as f64 * self.row_height());
+        let end = self.position + self.size;
+        let position = Vector2D::new(
+            self.anchor.x.min(end.x - size.x).max(self.position.x),
+            self.anchor.y.min(end.y - size.y).max(self.position.y),
+        );
+        Rect::new(position, size)
+    }
+
+    /// Gets the item under a position, for internal use only
+    ///
+    /// # Returns
+    /// The index of the item under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn item_at(&self, position: Vector2D) -> Option<usize> {
+        let area = self.menu_area();
+        if self.items.is_empty() || !area.contains(position) {
+            return None;
+        }
+        let index = ((position.y - area.position.y) / self.row_height()) as usize;
+        Some(index.min(self.items.len() - 1))
+    }
+
+    /// Sets the context menu's items, returning the context menu (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The context menu with the items assigned
+    ///
+    /// # Arguments
+    /// * `items` - the items to be assigned to the context menu
+    pub fn with_items(mut self, items: Vec<ContextMenuItem>) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Sets the context menu's font size, returning the context menu (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The context menu with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size to be assigned to the context menu
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.style.font_size = Some(font_size);
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the context menu's highlight color, returning the context menu (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The context menu with the highlight color assigned
+    ///
+    /// # Arguments
+    /// * `highlight_color` - the color of the item under the cursor
+    pub fn with_highlight_color(mut self, highlight_color: Color) -> Self {
+        self.style.selection_color = Some(highlight_color);
+        self.highlight_color = highlight_color;
+        self
+    }
+
+    /// Sets the context menu's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the context menu
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the context menu's style, overriding the properties of the current theme, returning
+    /// the context menu (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The context menu with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the context menu
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ContextMenuWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ContextMenuWidget {
+            style: Style::new(),
+            highlight_color: theme.palette.selection,
+            ..ContextMenuWidget::new(
+                Vec::new(),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for ContextMenuWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                let hovered = self.item_at(self.cursor_pos);
+                if hovered != self.hovered {
+                    self.hovered = hovered;
+                    self.dirty = true;
+                }
+            }
+            // Clicking outside the menu dismisses it
+            event::Event::Mouse(event::Mouse::ButtonPressed(_))
+                if !self.menu_area().contains(self.cursor_pos) =>
+            {
+                self.is_dismissed = true;
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                if let Some(index) = self.item_at(self.cursor_pos) {
+                    if let Some(mut message) = self.items[index].message.clone() {
+                        message.set_event(event);
+                        messages.enqueue(message);
+                    }
+                    self.is_dismissed = true;
+                }
+            }
+            event::Event::Keyboard(event::Keyboard::KeyPressed {
+                key_code: KeyCode::Escape,
+                ..
+            }) => {
+                self.is_dismissed = true;
+            }
+            _ => {}
+        }
+        // The menu covers the whole display, so it consumes every event
+        EventStatus::Captured
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let area = self.menu_area();
+        let row_height = self.row_height();
+
+        let mut instructions = vec![
+            // Border
+            RenderInstruction::DrawRect {
+                point: area.position,
+                size: area.size,
+                color: self.text_color.with_opacity(0.4),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Background
+            RenderInstruction::DrawRect {
+                point: area.position + 1.,
+                size: area.size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        for (index, item) in self.items.iter().enumerate() {
+            let point = Vector2D::new(area.position.x, area.position.y + index as f64 * row_height);
+            let size = Vector2D::new(area.size.x, row_height);
+            if self.hovered == Some(index) {
+                instructions.push(RenderInstruction::DrawRect {
+                    point: point + 1.,
+                    size: size - 2.,
+                    color: self.highlight_color,
+                    clip_point: area.position,
+                    clip_size: area.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    point.x + PADDING,
+                    point.y + (row_height + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: item.label.clone(),
+                color: self.text_color,
+                clip_point: point,
+                clip_size: size,
+                font: FontDescriptor::default(),
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.highlight_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.dirty = true;
+    }
+}
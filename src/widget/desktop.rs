@@ -0,0 +1,581 @@
+use crate::event;
+use crate::event::Event;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::internal_window::InternalWindowWidget;
+use crate::widget::{
+    deliver_event, is_batching_updates, EventStatus, Layout, Widget, WidgetData, WidgetRef,
+};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Height of the bar of minimized windows, as a multiple of the font size
+const BAR_HEIGHT: f64 = 2.;
+
+/// Maximum width of each minimized window on the bar
+const BAR_ITEM_WIDTH: f64 = 160.;
+
+/// Desktop is a container of [`InternalWindowWidget`]s (i.e., windows within the
+/// display), each placed on its own frame, relative to the top left corner of the desktop.
+///
+/// The windows are drawn in the order of the children, so that clicking a window
+/// raises it over the others and makes it the active one, which receives the keyboard
+/// events. The mouse buttons only reach the window under the cursor. Minimized windows
+/// are listed on a bar at the bottom of the desktop, where clicking them restores them.
+/// Children that are not internal windows fill the desktop, below every window.
+#[derive(Clone)]
+pub struct DesktopWidget {
+    /// The desktop's identifier
+    id: usize,
+
+    /// The data attached to the desktop through its handle
+    widget_data: WidgetData,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The desktop's font size, used on the bar of minimized windows
+    font_size: usize,
+
+    /// The desktop's background color
+    background_color: Color,
+
+    /// The color of the bar of minimized windows
+    bar_color: Color,
+
+    /// The color of the titles on the bar of minimized windows
+    text_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The desktop's children (i.e., his widgets tree), from the bottom one to the top one
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The desktop's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The desktop's current size (width and height)
+    size: Vector2D,
+
+    /// The desktop's original size (width and height)
+    original_size: Vector2D,
+
+    /// The desktop's layout
+    layout: Layout,
+
+    /// The desktop's offset vector coordinates
+    offset: Vector2D,
+
+    /// The desktop's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl DesktopWidget {
+    /// Creates a new `DesktopWidget`
+    ///
+    /// # Returns
+    /// The desktop created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the desktop
+    /// * `font_size` - the font size to be assigned to the bar of minimized windows
+    /// * `background_color` - the color to be assigned to the desktop's background
+    /// * `bar_color` - the color to be assigned to the bar of minimized windows
+    /// * `text_color` - the color to be assigned to the titles on the bar of minimized windows
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        bar_color: Color,
+        text_color: Color,
+    ) -> DesktopWidget {
+        DesktopWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_muted_color(bar_color)
+                .with_foreground_color(text_color),
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            bar_color,
+            text_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Raises a window over the others, making it the active one
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the window on the desktop's children
+    pub fn raise(&mut self, index: usize) {
+        if index + 1 < self.children.len() {
+            let child = self.children.remove(index);
+            if let Some(child) = child.upgrade() {
+                child.borrow_mut().set_dirty(true);
+            }
+            self.children.push(child);
+        }
+        self.update_active();
+    }
+
+    /// Gets the index of the topmost visible window under a position, for internal use only
+    ///
+    /// # Returns
+    /// The index of the window under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn window_at(&self, position: Vector2D) -> Option<usize> {
+        (0..self.children.len()).rev().find(|index| {
+            self.children[*index]
+                .with_widget(|window: &mut InternalWindowWidget| {
+                    is_visible(window) && window.is_cursor_inside(position)
+                })
+                .unwrap_or(false)
+        })
+    }
+
+    /// Gets the index of the topmost visible window, for internal use only
+    ///
+    /// # Returns
+    /// The index of the active window, or `None` if there is none
+    ///
+    /// # Arguments
+    /// No arguments
+    fn active_window(&self) -> Option<usize> {
+        (0..self.children.len()).rev().find(|index| {
+            self.children[*index]
+                .with_widget(|window: &mut InternalWindowWidget| is_visible(window))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Makes the topmost visible window the active one, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn update_active(&mut self) {
+        let active = self.active_window();
+        for (index, value) in self.children.iter().enumerate() {
+            value.with_widget(|window: &mut InternalWindowWidget| {
+                window.set_active(Some(index) == active)
+            });
+        }
+    }
+
+    /// Gets the state of every window, to detect whether the events changed it,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// Whether each child is a visible window
+    ///
+    /// # Arguments
+    /// No arguments
+    fn visibility(&self) -> Vec<bool> {
+        self.children
+            .iter()
+            .map(|value| {
+                value
+                    .with_widget(|window: &mut InternalWindowWidget| is_visible(window))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Gets the minimized windows with their areas on the bar, for internal use only
+    ///
+    /// # Returns
+    /// The index of each minimized window, with its title and its area on the bar
+    ///
+    /// # Arguments
+    /// No arguments
+    fn bar_items(&self) -> Vec<(usize, String, Rect)> {
+        let minimized: Vec<(usize, String)> = self
+            .children
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| {
+                value
+                    .with_widget(|window: &mut InternalWindowWidget| {
+                        if window.is_minimized() && !window.is_closed() {
+                            Some((index, String::from(window.get_title())))
+                        } else {
+                            None
+                        }
+                    })
+                    .flatten()
+            })
+            .collect();
+        if minimized.is_empty() {
+            return vec![];
+        }
+
+        let height = self.font_size as f64 * BAR_HEIGHT;
+        let width = (self.size.x / minimized.len() as f64).min(BAR_ITEM_WIDTH);
+        let y = self.position.y + self.size.y - height;
+        minimized
+            .into_iter()
+            .enumerate()
+            .map(|(order, (index, title))| {
+                let point = Vector2D::new(self.position.x + width * order as f64, y);
+                (index, title, Rect::new(point, Vector2D::new(width, height)))
+            })
+            .collect()
+    }
+
+    /// Sets the desktop's size (width and height), returning the desktop (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The desktop with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the desktop
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the desktop's background color, returning the desktop (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The desktop with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the color to be assigned to the desktop's background
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the desktop's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the desktop
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the desktop's style, overriding the properties of the current theme, returning the
+    /// desktop (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The desktop with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the desktop
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+/// Checks if an internal window is displayed on the desktop, for internal use only
+///
+/// # Returns
+/// True, if the window is neither minimized nor closed, false otherwise
+///
+/// # Arguments
+/// * `window` - the window to be checked
+fn is_visible(window: &InternalWindowWidget) -> bool {
+    !window.is_minimized() && !window.is_closed()
+}
+
+impl Default for DesktopWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        DesktopWidget {
+            style: Style::new(),
+            ..DesktopWidget::new(
+                Vector2D::new(100., 100.),
+                theme.font_size,
+                theme.palette.background,
+                theme.palette.muted,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for DesktopWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let visibility = self.visibility();
+
+        // The children that receive the event
+        let targets: Vec<usize> = match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                (0..self.children.len()).collect()
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(_)) => {
+                let bar_item = self
+                    .bar_items()
+                    .into_iter()
+                    .find(|(_, _, area)| area.contains(self.cursor_pos));
+                if let Some((index, _, _)) = bar_item {
+                    self.children[index]
+                        .with_widget(|window: &mut InternalWindowWidget| window.restore());
+                    self.raise(index);
+                    self.set_dirty(true);
+                    return EventStatus::Captured;
+                }
+
+                match self.window_at(self.cursor_pos) {
+                    Some(index) => {
+                        self.raise(index);
+                        vec![self.children.len() - 1]
+                    }
+                    // Only the children that are not windows are below the cursor
+                    None => (0..self.children.len())
+                        .filter(|index| !self.children[*index].is::<InternalWindowWidget>())
+                        .collect(),
+                }
+            }
+            event::Event::Keyboard(_) => match self.active_window() {
+                Some(index) => vec![index],
+                None => (0..self.children.len())
+                    .filter(|index| !self.children[*index].is::<InternalWindowWidget>())
+                    .collect(),
+            },
+            _ => (0..self.children.len()).collect(),
+        };
+
+        let mut status = EventStatus::Ignored;
+        for index in targets {
+            if let Some(child) = self.children[index].upgrade() {
+                status = status.merge(deliver_event(&mut *child.borrow_mut(), event, messages));
+            }
+        }
+
+        // Update the bar and the active window when a window was minimized or closed
+        if self.visibility() != visibility {
+            self.update_active();
+            self.set_dirty(true);
+        }
+        status
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![RenderInstruction::DrawRect {
+            point: self.position,
+            size: self.size,
+            color: self.background_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        }]
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        // The bar of minimized windows is drawn over the windows
+        let bar_items = self.bar_items();
+        let bar_area = match bar_items.first() {
+            Some((_, _, area)) => Rect::new(
+                Vector2D::new(self.position.x, area.position.y),
+                Vector2D::new(self.size.x, area.size.y),
+            ),
+            None => return vec![],
+        };
+
+        let mut instructions = vec![RenderInstruction::DrawRect {
+            point: bar_area.position,
+            size: bar_area.size,
+            color: self.bar_color,
+            clip_point: bar_area.position,
+            clip_size: bar_area.size,
+            blend_mode: BlendMode::Normal,
+        }];
+        for (_, title, area) in bar_items {
+            instructions.push(RenderInstruction::DrawRect {
+                point: Vector2D::new(area.position.x + area.size.x - 1., area.position.y),
+                size: Vector2D::new(1., area.size.y),
+                color: self.text_color.with_opacity(0.3),
+                clip_point: bar_area.position,
+                clip_size: bar_area.size,
+                blend_mode: BlendMode::Normal,
+            });
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    area.position.x + 8.,
+                    area.position.y + (area.size.y + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: title,
+                color: self.text_color,
+                clip_point: area.position,
+                clip_size: Vector2D::new((area.size.x - 8.).max(0.), area.size.y),
+                font: FontDescriptor::default(),
+            });
+        }
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        self.children
+            .iter()
+            .map(|value| {
+                value
+                    .with_widget(|window: &mut InternalWindowWidget| {
+                        if is_visible(window) {
+                            let frame = window.get_frame();
+                            Some(Rect::new(area.position + frame.position, frame.size))
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap_or(Some(area))
+            })
+            .collect()
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+        self.update_active();
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.background);
+        self.bar_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
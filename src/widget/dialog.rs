@@ -0,0 +1,736 @@
+use crate::event;
+use crate::event::Event;
+use crate::key_code::KeyCode;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Height of the title bar and of the buttons, as a multiple of the font size
+const BAR_HEIGHT: f64 = 2.;
+
+/// Space between the dialog's box, its content and its buttons
+const PADDING: f64 = 8.;
+
+/// Average width of a glyph, as a fraction of the font size, used to size the buttons
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Button of the dialog, for internal use only
+#[derive(Clone, Copy, PartialEq)]
+enum DialogButton {
+    /// Confirms the dialog
+    Confirm,
+    /// Cancels the dialog
+    Cancel,
+}
+
+/// Dialog is a modal box with a title, a content and the confirm and cancel buttons,
+/// opened as a popup over the whole display.
+///
+/// The dialog is opened with [`Context::open_popup`](`crate::context::Context::open_popup`),
+/// which draws it above the widget tree and makes it grab every event, so the widgets
+/// behind it cannot be interacted with while it is open. The background is dimmed and
+/// the dialog's children are stacked vertically inside its box, each with the height of
+/// its original size.
+///
+/// Clicking the confirm button or pressing `Enter` handles the `on_confirm` message,
+/// while clicking the cancel button, pressing `Escape` or, if the dialog is dismissible,
+/// clicking outside its box handles the `on_cancel` message. The dialog does not close
+/// itself: the application closes it with
+/// [`Context::close_popup`](`crate::context::Context::close_popup`) when handling these
+/// messages (e.g. after validating the content).
+#[derive(Clone)]
+pub struct DialogWidget {
+    /// The dialog's identifier
+    id: usize,
+
+    /// The data attached to the dialog through its handle
+    widget_data: WidgetData,
+
+    /// The dialog's title
+    title: String,
+
+    /// The label of the confirm button
+    confirm_label: String,
+
+    /// The label of the cancel button
+    cancel_label: String,
+
+    /// Whether clicking outside the dialog's box cancels it
+    is_dismissible: bool,
+
+    /// The message to be handled when the dialog is confirmed
+    on_confirm: Option<Box<dyn Message>>,
+
+    /// The message to be handled when the dialog is cancelled
+    on_cancel: Option<Box<dyn Message>>,
+
+    /// The button being pressed, which acts when released over it
+    pressed_button: Option<DialogButton>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The dialog's font size
+    font_size: usize,
+
+    /// The color drawn over the widgets behind the dialog
+    dim_color: Color,
+
+    /// The dialog's background color
+    background_color: Color,
+
+    /// The dialog's text color
+    text_color: Color,
+
+    /// The buttons' background color
+    button_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The dialog's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The dialog's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The dialog's current size (width and height), covering the whole display
+    size: Vector2D,
+
+    /// The dialog's original size (width and height), which is the size of its box
+    original_size: Vector2D,
+
+    /// The dialog's layout
+    layout: Layout,
+
+    /// The dialog's offset vector coordinates
+    offset: Vector2D,
+
+    /// The dialog's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl DialogWidget {
+    /// Creates a new `DialogWidget`
+    ///
+    /// # Returns
+    /// The dialog created
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the dialog
+    /// * `size` - the size (width and height) to be assigned to the dialog's box
+    /// * `font_size` - the font size to be assigned to the dialog
+    /// * `background_color` - the color to be assigned to the dialog's background
+    /// * `text_color` - the color to be assigned to the dialog's text
+    /// * `on_confirm` - the message to be handled when the dialog is confirmed
+    /// * `on_cancel` - the message to be handled when the dialog is cancelled
+    pub fn new(
+        title: &str,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        text_color: Color,
+        on_confirm: Option<Box<dyn Message>>,
+        on_cancel: Option<Box<dyn Message>>,
+    ) -> DialogWidget {
+        DialogWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color),
+            title: String::from(title),
+            confirm_label: String::from("OK"),
+            cancel_label: String::from("Cancel"),
+            is_dismissible: true,
+            on_confirm,
+            on_cancel,
+            pressed_button: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            dim_color: Color::from_hex(0x80000000),
+            background_color,
+            text_color,
+            button_color: Color::from_hex(0xffe0e0e0),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the dialog's title
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the dialog
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+        self.dirty = true;
+    }
+
+    /// Sets the labels of the confirm and cancel buttons
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `confirm_label` - the label to be assigned to the confirm button
+    /// * `cancel_label` - the label to be assigned to the cancel button
+    pub fn set_button_labels(&mut self, confirm_label: &str, cancel_label: &str) {
+        self.confirm_label = String::from(confirm_label);
+        self.cancel_label = String::from(cancel_label);
+        self.dirty = true;
+    }
+
+    /// Sets whether clicking outside the dialog's box cancels it
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_dismissible` - boolean indicating if clicking outside the box cancels the dialog
+    pub fn set_dismissible(&mut self, is_dismissible: bool) {
+        self.is_dismissible = is_dismissible;
+    }
+
+    /// Sets the messages to be handled when the dialog is confirmed and cancelled
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_confirm` - the message to be handled when the dialog is confirmed
+    /// * `on_cancel` - the message to be handled when the dialog is cancelled
+    pub fn set_message(
+        &mut self,
+        on_confirm: Option<Box<dyn Message>>,
+        on_cancel: Option<Box<dyn Message>>,
+    ) {
+        self.on_confirm = on_confirm;
+        self.on_cancel = on_cancel;
+    }
+
+    /// Sets the color drawn over the widgets behind the dialog
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `dim_color` - the color to be assigned, usually translucent
+    pub fn set_dim_color(&mut self, dim_color: Color) {
+        self.dim_color = dim_color;
+        self.dirty = true;
+    }
+
+    /// Sets the buttons' background color
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `button_color` - the color to be assigned to the buttons' background
+    pub fn set_button_color(&mut self, button_color: Color) {
+        self.style.muted_color = Some(button_color);
+        self.button_color = button_color;
+        self.dirty = true;
+    }
+
+    /// Gets the area of the dialog's box, centered on the display, for internal use only
+    ///
+    /// # Returns
+    /// The area of the dialog's box
+    ///
+    /// # Arguments
+    /// * `area` - the area assigned to the dialog (i.e., the whole display)
+    fn box_area(&self, area: Rect) -> Rect {
+        let size = Vector2D::new(
+            self.original_size.x.min(area.size.x),
+            self.original_size.y.min(area.size.y),
+        );
+        Rect::new(area.position + (area.size - size) * 0.5, size)
+    }
+
+    /// Gets the height of the title bar and of the buttons, for internal use only
+    ///
+    /// # Returns
+    /// The height of the title bar and of the buttons
+    ///
+    /// # Arguments
+    /// No arguments
+    fn bar_height(&self) -> f64 {
+        self.font_size as f64 * BAR_HEIGHT
+    }
+
+    /// Gets the buttons with their areas, for internal use only
+    ///
+    /// # Returns
+    /// The buttons, from left to right, with their areas on the bottom right corner of
+    /// the dialog's box
+    ///
+    /// # Arguments
+    /// No arguments
+    fn buttons(&self) -> [(DialogButton, Rect); 2] {
+        let dialog_box = self.box_area(Rect::new(self.position, self.size));
+        let height = self.bar_height();
+        let top = dialog_box.position.y + dialog_box.size.y - height - PADDING;
+        let mut right = dialog_box.position.x + dialog_box.size.x - PADDING;
+        let mut button = |label: &str| {
+            let width = (label.chars().count() as f64 * GLYPH_WIDTH + 2.) * self.font_size as f64;
+            right -= width;
+            let area = Rect::new(Vector2D::new(right, top), Vector2D::new(width, height));
+            right -= PADDING;
+            area
+        };
+        let confirm = button(&self.confirm_label);
+        let cancel = button(&self.cancel_label);
+        [
+            (DialogButton::Cancel, cancel),
+            (DialogButton::Confirm, confirm),
+        ]
+    }
+
+    /// Gets the button under a position, for internal use only
+    ///
+    /// # Returns
+    /// The button under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn button_at(&self, position: Vector2D) -> Option<DialogButton> {
+        self.buttons()
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(button, _)| *button)
+    }
+
+    /// Handles the message of a button, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `button` - the button whose message is handled
+    /// * `event` - the event that triggered the button
+    /// * `messages` - queue of messages
+    fn trigger(&self, button: DialogButton, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        let message = match button {
+            DialogButton::Confirm => &self.on_confirm,
+            DialogButton::Cancel => &self.on_cancel,
+        };
+        if let Some(mut message) = message.clone() {
+            message.set_event(event);
+            messages.enqueue(message);
+        }
+    }
+
+    /// Sets the dialog's size (width and height) of the box, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the size (width and height) of the box assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the dialog's box
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the dialog's title, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the title assigned
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the dialog
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.set_title(title);
+        self
+    }
+
+    /// Sets the dialog's button labels, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the button labels assigned
+    ///
+    /// # Arguments
+    /// * `confirm_label` - the label to be assigned to the confirm button
+    /// * `cancel_label` - the label to be assigned to the cancel button
+    pub fn with_button_labels(mut self, confirm_label: &str, cancel_label: &str) -> Self {
+        self.set_button_labels(confirm_label, cancel_label);
+        self
+    }
+
+    /// Sets whether the dialog is dismissible, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with whether it is dismissible assigned
+    ///
+    /// # Arguments
+    /// * `is_dismissible` - boolean indicating if clicking outside the box cancels the dialog
+    pub fn with_dismissible(mut self, is_dismissible: bool) -> Self {
+        self.set_dismissible(is_dismissible);
+        self
+    }
+
+    /// Sets the dialog's message to be handled when confirmed, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the given message to be handled when confirmed
+    ///
+    /// # Arguments
+    /// * `on_confirm` - the message to be handled when the dialog is confirmed
+    pub fn with_on_confirm(mut self, on_confirm: Option<Box<dyn Message>>) -> Self {
+        self.on_confirm = on_confirm;
+        self
+    }
+
+    /// Sets the dialog's message to be handled when cancelled, returning the dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the given message to be handled when cancelled
+    ///
+    /// # Arguments
+    /// * `on_cancel` - the message to be handled when the dialog is cancelled
+    pub fn with_on_cancel(mut self, on_cancel: Option<Box<dyn Message>>) -> Self {
+        self.on_cancel = on_cancel;
+        self
+    }
+
+    /// Sets the dialog's style, overriding the properties of the current theme and the ones set
+    /// before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dialog
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the dialog's style, overriding the properties of the current theme, returning the
+    /// dialog (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dialog with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dialog
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for DialogWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        DialogWidget {
+            style: Style::new(),
+            button_color: theme.palette.muted,
+            ..DialogWidget::new(
+                "",
+                Vector2D::new(360., 200.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                None,
+                None,
+            )
+        }
+    }
+}
+
+impl Widget for DialogWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                if let Some(button) = self.button_at(self.cursor_pos) {
+                    self.pressed_button = Some(button);
+                    return EventStatus::Captured;
+                }
+                let dialog_box = self.box_area(Rect::new(self.position, self.size));
+                if !dialog_box.contains(self.cursor_pos) {
+                    // The widgets behind the dialog cannot be clicked
+                    if self.is_dismissible {
+                        self.trigger(DialogButton::Cancel, event, messages);
+                    }
+                    return EventStatus::Captured;
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                if let Some(button) = self.pressed_button.take() {
+                    // The buttons act when released over the same button
+                    if self.button_at(self.cursor_pos) == Some(button) {
+                        self.trigger(button, event, messages);
+                    }
+                    return EventStatus::Captured;
+                }
+            }
+            event::Event::Keyboard(event::Keyboard::KeyPressed {
+                key_code: KeyCode::Enter | KeyCode::NumpadEnter,
+                ..
+            }) => {
+                self.trigger(DialogButton::Confirm, event, messages);
+                return EventStatus::Captured;
+            }
+            event::Event::Keyboard(event::Keyboard::KeyPressed {
+                key_code: KeyCode::Escape,
+                ..
+            }) => {
+                self.trigger(DialogButton::Cancel, event, messages);
+                return EventStatus::Captured;
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let area = Rect::new(self.position, self.size);
+        let dialog_box = self.box_area(area);
+        let bar_height = self.bar_height();
+        let baseline = |top: f64| top + (bar_height + self.font_size as f64) * 0.5;
+
+        let mut instructions = vec![
+            // Dimmed background
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: self.dim_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Box border
+            RenderInstruction::DrawRect {
+                point: dialog_box.position,
+                size: dialog_box.size,
+                color: self.text_color.with_opacity(0.4),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Box background
+            RenderInstruction::DrawRect {
+                point: dialog_box.position + 1.,
+                size: dialog_box.size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Title
+            RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    dialog_box.position.x + PADDING,
+                    baseline(dialog_box.position.y),
+                ),
+                font_size: self.font_size,
+                string: self.title.clone(),
+                color: self.text_color,
+                clip_point: dialog_box.position,
+                clip_size: dialog_box.size,
+                font: FontDescriptor::default(),
+            },
+        ];
+
+        // Buttons
+        for (button, button_area) in self.buttons().iter() {
+            let label = match button {
+                DialogButton::Confirm => &self.confirm_label,
+                DialogButton::Cancel => &self.cancel_label,
+            };
+            let label_width = label.chars().count() as f64 * self.font_size as f64 * GLYPH_WIDTH;
+            instructions.push(RenderInstruction::DrawRect {
+                point: button_area.position,
+                size: button_area.size,
+                color: self.button_color,
+                clip_point: dialog_box.position,
+                clip_size: dialog_box.size,
+                blend_mode: BlendMode::Normal,
+            });
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    button_area.position.x + (button_area.size.x - label_width) * 0.5,
+                    baseline(button_area.position.y),
+                ),
+                font_size: self.font_size,
+                string: label.clone(),
+                color: self.text_color,
+                clip_point: button_area.position,
+                clip_size: button_area.size,
+                font: FontDescriptor::default(),
+            });
+        }
+
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        // The children are stacked between the title bar and the buttons
+        let dialog_box = self.box_area(area);
+        let bar_height = self.bar_height();
+        let left = dialog_box.position.x + PADDING;
+        let width = (dialog_box.size.x - 2. * PADDING).max(0.);
+        let bottom = dialog_box.position.y + dialog_box.size.y - bar_height - 2. * PADDING;
+        let mut top = dialog_box.position.y + bar_height;
+        let mut child_areas = Vec::new();
+        for value in self.children.iter() {
+            let height = match value.upgrade() {
+                Some(child) => child.borrow_mut().original_size().y,
+                None => 0.,
+            };
+            let height = height.min(bottom - top).max(0.);
+            child_areas.push(Some(Rect::new(
+                Vector2D::new(left, top),
+                Vector2D::new(width, height),
+            )));
+            top += height;
+        }
+        child_areas
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.button_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,1056 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Weak;
+use std::str::FromStr;
+
+/// Distance the cursor must move with the left button held to start dragging a panel
+const DRAG_THRESHOLD: f64 = 4.;
+
+/// Size of the zones near the edges of the dock area where a dragged panel is docked,
+/// as a fraction of the dock area's size
+const EDGE_ZONE: f64 = 0.2;
+
+/// Height of the panels' headers, as a multiple of the font size
+const HEADER_HEIGHT: f64 = 1.8;
+
+/// Placement of a panel on a [`DockAreaWidget`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DockPlacement {
+    /// The panel is docked on the left edge of the dock area
+    Left,
+    /// The panel is docked on the right edge of the dock area
+    Right,
+    /// The panel is docked on the top edge of the dock area
+    Top,
+    /// The panel is docked on the bottom edge of the dock area
+    Bottom,
+    /// The panel floats over the dock area, on its floating area
+    Floating,
+}
+
+impl DockPlacement {
+    /// Gets the name of the placement, as written on a persisted [`DockLayout`]
+    ///
+    /// # Returns
+    /// The name of the placement (e.g. "left")
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn name(&self) -> &'static str {
+        match self {
+            DockPlacement::Left => "left",
+            DockPlacement::Right => "right",
+            DockPlacement::Top => "top",
+            DockPlacement::Bottom => "bottom",
+            DockPlacement::Floating => "floating",
+        }
+    }
+}
+
+/// The layout of a panel on a [`DockLayout`]
+#[derive(Clone)]
+pub struct DockPanelLayout {
+    /// The panel's title, identifying the panel
+    pub title: String,
+
+    /// The panel's placement
+    pub placement: DockPlacement,
+
+    /// The panel's thickness (i.e., width when docked on the left or right edges,
+    /// and height when docked on the top or bottom edges)
+    pub extent: f64,
+
+    /// The panel's area when floating, relative to the top left corner of the dock area
+    pub floating_area: Rect,
+}
+
+/// The layout of the panels of a [`DockAreaWidget`], from the bottom one to the top one
+///
+/// The layout can be persisted as text, through its [`Display`](`std::fmt::Display`)
+/// and [`FromStr`] implementations, with a line per panel: the placement, its
+/// geometry (the extent, or the floating area's position and size) and the title.
+///
+/// # Examples
+///
+/// ```
+/// use hyber::widget::dock_area::{DockLayout, DockPlacement};
+///
+/// let layout: DockLayout = "left 240 Explorer\nfloating 80 60 320 200 Output"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(layout.panels[0].placement, DockPlacement::Left);
+/// assert_eq!(layout.panels[1].title, "Output");
+/// assert_eq!(layout.to_string(), "left 240 Explorer\nfloating 80 60 320 200 Output\n");
+/// ```
+#[derive(Clone, Default)]
+pub struct DockLayout {
+    /// The layout of each panel
+    pub panels: Vec<DockPanelLayout>,
+}
+
+/// Error returned when a string does not describe a [`DockLayout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDockLayoutError {
+    /// The line that could not be parsed
+    pub line: String,
+}
+
+impl fmt::Display for ParseDockLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid dock layout line: \"{}\"", self.line)
+    }
+}
+
+impl std::error::Error for ParseDockLayoutError {}
+
+impl fmt::Display for DockLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for panel in self.panels.iter() {
+            match panel.placement {
+                DockPlacement::Floating => writeln!(
+                    f,
+                    "floating {} {} {} {} {}",
+                    panel.floating_area.position.x,
+                    panel.floating_area.position.y,
+                    panel.floating_area.size.x,
+                    panel.floating_area.size.y,
+                    panel.title
+                )?,
+                placement => writeln!(f, "{} {} {}", placement.name(), panel.extent, panel.title)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DockLayout {
+    type Err = ParseDockLayoutError;
+
+    /// Parses a dock layout, written with a line per panel
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut panels = Vec::new();
+        for line in s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+        {
+            let error = || ParseDockLayoutError {
+                line: String::from(line),
+            };
+            let placement = match line.split(' ').next() {
+                Some("left") => DockPlacement::Left,
+                Some("right") => DockPlacement::Right,
+                Some("top") => DockPlacement::Top,
+                Some("bottom") => DockPlacement::Bottom,
+                Some("floating") => DockPlacement::Floating,
+                _ => return Err(error()),
+            };
+            // The title is the last part, so that it may contain spaces
+            let count = if placement == DockPlacement::Floating {
+                4
+            } else {
+                1
+            };
+            let parts: Vec<&str> = line.splitn(count + 2, ' ').collect();
+            if parts.len() != count + 2 || parts[count + 1].is_empty() {
+                return Err(error());
+            }
+            let numbers = parts[1..=count]
+                .iter()
+                .map(|part| part.parse::<f64>().map_err(|_| error()))
+                .collect::<Result<Vec<f64>, _>>()?;
+            let (extent, floating_area) = match placement {
+                DockPlacement::Floating => (
+                    numbers[2],
+                    Rect::new(
+                        Vector2D::new(numbers[0], numbers[1]),
+                        Vector2D::new(numbers[2], numbers[3]),
+                    ),
+                ),
+                _ => (
+                    numbers[0],
+                    Rect::new(Vector2D::new(0., 0.), Vector2D::new(numbers[0], numbers[0])),
+                ),
+            };
+            panels.push(DockPanelLayout {
+                title: String::from(parts[count + 1]),
+                placement,
+                extent,
+                floating_area,
+            });
+        }
+        Ok(DockLayout { panels })
+    }
+}
+
+/// A panel of a dock area, for internal use only
+#[derive(Clone)]
+struct DockPanel {
+    /// The panel's layout (i.e., its title, placement and geometry)
+    layout: DockPanelLayout,
+
+    /// The panel's content
+    widget: Weak<RefCell<dyn Widget>>,
+}
+
+/// A panel being dragged by its header, for internal use only
+#[derive(Clone, Copy)]
+struct DockDrag {
+    /// The index of the panel dragged
+    panel: usize,
+
+    /// The cursor's position when the header was pressed
+    origin: Vector2D,
+
+    /// The distance between the cursor and the top left corner of the panel
+    grab: Vector2D,
+
+    /// Whether the cursor moved enough to start dragging the panel
+    is_dragging: bool,
+}
+
+/// Dock area is a container for tool windows: its children fill the center of the
+/// area, surrounded by the panels docked on its edges, while floating panels are
+/// drawn over them.
+///
+/// Each panel has a header with its title, which can be dragged to dock the panel
+/// on an edge of the dock area, when released near that edge, or to float it,
+/// when released anywhere else. While dragging, the zone where the panel would be
+/// placed is highlighted. Panels docked on the same edge share it, and clicking a
+/// floating panel raises it over the others. The layout of the panels can be
+/// persisted through [`get_layout`](`DockAreaWidget::get_layout`) and
+/// [`set_layout`](`DockAreaWidget::set_layout`), and whenever the user changes it,
+/// the `on_layout_change` message is handled, which is filled with the new layout
+/// if it is a [`ValueChanged<DockLayout>`].
+///
+/// Floating panels are placed by the dock area itself, instead of through the
+/// [`AbsoluteWidgetCollection`](`crate::renderer::AbsoluteWidgetCollection`), so that
+/// their content is built with its children, as the content of the docked panels.
+#[derive(Clone)]
+pub struct DockAreaWidget {
+    /// The dock area's identifier
+    id: usize,
+
+    /// The data attached to the dock area through its handle
+    widget_data: WidgetData,
+
+    /// The dock area's panels, from the bottom one to the top one
+    panels: Vec<DockPanel>,
+
+    /// The panel being dragged by its header, if any
+    drag: Option<DockDrag>,
+
+    /// The message to be handled when the user changes the layout of the panels
+    on_layout_change: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The dock area's font size, used on the panels' headers
+    font_size: usize,
+
+    /// The color of the panels' headers
+    header_color: Color,
+
+    /// The color of the panels' titles
+    text_color: Color,
+
+    /// The color of the zone where a dragged panel would be placed
+    highlight_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The dock area's children (i.e., his widgets tree), followed by the panels' content
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The dock area's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The dock area's current size (width and height)
+    size: Vector2D,
+
+    /// The dock area's original size (width and height)
+    original_size: Vector2D,
+
+    /// The dock area's layout
+    layout: Layout,
+
+    /// The dock area's offset vector coordinates
+    offset: Vector2D,
+
+    /// The dock area's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl DockAreaWidget {
+    /// Creates a new `DockAreaWidget`
+    ///
+    /// # Returns
+    /// The dock area created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the dock area
+    /// * `font_size` - the font size to be assigned to the panels' titles
+    /// * `header_color` - the color to be assigned to the panels' headers
+    /// * `text_color` - the color to be assigned to the panels' titles
+    /// * `highlight_color` - the color to be assigned to the zone where a dragged panel would be placed
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        header_color: Color,
+        text_color: Color,
+        highlight_color: Color,
+    ) -> DockAreaWidget {
+        DockAreaWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_muted_color(header_color)
+                .with_foreground_color(text_color)
+                .with_accent_color(highlight_color),
+            panels: Vec::new(),
+            drag: None,
+            on_layout_change: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            header_color,
+            text_color,
+            highlight_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when the user changes the layout of the panels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_layout_change` - the message to be handled when the layout changes
+    pub fn set_message(&mut self, on_layout_change: Option<Box<dyn Message>>) {
+        self.on_layout_change = on_layout_change;
+    }
+
+    /// Adds a panel to the dock area, over the existing ones
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the panel's title, displayed on its header and identifying the panel
+    /// * `widget` - the panel's content
+    /// * `placement` - the panel's placement
+    /// * `extent` - the panel's thickness when docked, and the initial size of its floating area
+    pub fn add_panel(
+        &mut self,
+        title: &str,
+        widget: Weak<RefCell<dyn Widget>>,
+        placement: DockPlacement,
+        extent: f64,
+    ) {
+        let cascade = 24. * (self.panels.len() + 1) as f64;
+        self.panels.push(DockPanel {
+            layout: DockPanelLayout {
+                title: String::from(title),
+                placement,
+                extent,
+                floating_area: Rect::new(
+                    Vector2D::new(cascade, cascade),
+                    Vector2D::new(extent, extent),
+                ),
+            },
+            widget: widget.clone(),
+        });
+        self.children.push(widget);
+        self.set_dirty(true);
+    }
+
+    /// Sets the placement of a panel
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title of the panel
+    /// * `placement` - the placement to be assigned to the panel
+    pub fn set_panel_placement(&mut self, title: &str, placement: DockPlacement) {
+        if let Some(panel) = self
+            .panels
+            .iter_mut()
+            .find(|panel| panel.layout.title == title)
+        {
+            panel.layout.placement = placement;
+            self.set_dirty(true);
+        }
+    }
+
+    /// Sets the area of a panel when floating
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title of the panel
+    /// * `floating_area` - the area, relative to the top left corner of the dock area
+    pub fn set_floating_area(&mut self, title: &str, floating_area: Rect) {
+        if let Some(panel) = self
+            .panels
+            .iter_mut()
+            .find(|panel| panel.layout.title == title)
+        {
+            panel.layout.floating_area = floating_area;
+            self.set_dirty(true);
+        }
+    }
+
+    /// Gets the layout of the panels, to be persisted
+    ///
+    /// # Returns
+    /// The layout of the panels, from the bottom one to the top one
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_layout(&self) -> DockLayout {
+        DockLayout {
+            panels: self
+                .panels
+                .iter()
+                .map(|panel| panel.layout.clone())
+                .collect(),
+        }
+    }
+
+    /// Sets the layout of the panels (e.g. a persisted one), without handling the
+    /// `on_layout_change` message
+    ///
+    /// The panels are matched by their titles: the ones on the layout are placed and
+    /// ordered as described, while the others are kept below them.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `layout` - the layout to be assigned
+    pub fn set_layout(&mut self, layout: &DockLayout) {
+        let center_count = self.center_count();
+        let mut panels = std::mem::take(&mut self.panels);
+        let mut ordered = Vec::with_capacity(panels.len());
+        for panel_layout in layout.panels.iter() {
+            if let Some(index) = panels
+                .iter()
+                .position(|panel| panel.layout.title == panel_layout.title)
+            {
+                let mut panel = panels.remove(index);
+                let floating_area = panel.layout.floating_area;
+                panel.layout = panel_layout.clone();
+                // Docked panels keep their floating area, which is not persisted
+                if panel.layout.placement != DockPlacement::Floating {
+                    panel.layout.floating_area = floating_area;
+                }
+                ordered.push(panel);
+            }
+        }
+        panels.extend(ordered);
+        self.panels = panels;
+
+        self.children.truncate(center_count);
+        self.children
+            .extend(self.panels.iter().map(|panel| panel.widget.clone()));
+        self.set_dirty(true);
+    }
+
+    /// Gets the number of children filling the center of the dock area, for internal use only
+    ///
+    /// # Returns
+    /// The number of children that are not panels
+    ///
+    /// # Arguments
+    /// No arguments
+    fn center_count(&self) -> usize {
+        self.children.len() - self.panels.len()
+    }
+
+    /// Gets the height of the panels' headers, for internal use only
+    ///
+    /// # Returns
+    /// The height of the panels' headers
+    ///
+    /// # Arguments
+    /// No arguments
+    fn header_height(&self) -> f64 {
+        self.font_size as f64 * HEADER_HEIGHT
+    }
+
+    /// Gets the area of the center and of each panel (header included), for internal use only
+    ///
+    /// The edges are taken in order (left, right, top and bottom), each one by the panels
+    /// docked on it, which split it evenly. Every edge takes at most half of the area left.
+    ///
+    /// # Returns
+    /// The area of the center and the areas of the panels, in the order of the panels
+    ///
+    /// # Arguments
+    /// * `area` - the area of the dock area
+    fn areas(&self, area: Rect) -> (Rect, Vec<Rect>) {
+        let mut areas = vec![Rect::new(area.position, Vector2D::new(0., 0.)); self.panels.len()];
+        let mut rest = area;
+        let edges = [
+            DockPlacement::Left,
+            DockPlacement::Right,
+            DockPlacement::Top,
+            DockPlacement::Bottom,
+        ];
+        for edge in edges.iter() {
+            let docked: Vec<usize> = (0..self.panels.len())
+                .filter(|index| self.panels[*index].layout.placement == *edge)
+                .collect();
+            if docked.is_empty() {
+                continue;
+            }
+            let extent = docked
+                .iter()
+                .map(|index| self.panels[*index].layout.extent)
+                .fold(0., f64::max);
+            let is_horizontal = *edge == DockPlacement::Left || *edge == DockPlacement::Right;
+            let strip = if is_horizontal {
+                let width = extent.min(rest.size.x * 0.5);
+                rest.size.x -= width;
+                let x = if *edge == DockPlacement::Left {
+                    rest.position.x += width;
+                    rest.position.x - width
+                } else {
+                    rest.position.x + rest.size.x
+                };
+                Rect::new(
+                    Vector2D::new(x, rest.position.y),
+                    Vector2D::new(width, rest.size.y),
+                )
+            } else {
+                let height = extent.min(rest.size.y * 0.5);
+                rest.size.y -= height;
+                let y = if *edge == DockPlacement::Top {
+                    rest.position.y += height;
+                    rest.position.y - height
+                } else {
+                    rest.position.y + rest.size.y
+                };
+                Rect::new(
+                    Vector2D::new(rest.position.x, y),
+                    Vector2D::new(rest.size.x, height),
+                )
+            };
+
+            // The panels docked on the same edge split it evenly
+            let count = docked.len() as f64;
+            for (order, index) in docked.iter().enumerate() {
+                areas[*index] = if is_horizontal {
+                    let height = strip.size.y / count;
+                    Rect::new(
+                        Vector2D::new(strip.position.x, strip.position.y + height * order as f64),
+                        Vector2D::new(strip.size.x, height),
+                    )
+                } else {
+                    let width = strip.size.x / count;
+                    Rect::new(
+                        Vector2D::new(strip.position.x + width * order as f64, strip.position.y),
+                        Vector2D::new(width, strip.size.y),
+                    )
+                };
+            }
+        }
+
+        for (index, panel) in self.panels.iter().enumerate() {
+            if panel.layout.placement == DockPlacement::Floating {
+                areas[index] = Rect::new(
+                    area.position + panel.layout.floating_area.position,
+                    panel.layout.floating_area.size,
+                );
+            }
+        }
+        (rest, areas)
+    }
+
+    /// Gets the zone where a panel would be placed if released under a position,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// The edge near the position, or floating if the position is not near any edge
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn drop_zone(&self, position: Vector2D) -> DockPlacement {
+        let start = position - self.position;
+        let end = self.position + self.size - position;
+        let zone = self.size * EDGE_ZONE;
+        if start.x < 0. || start.y < 0. || end.x < 0. || end.y < 0. {
+            DockPlacement::Floating
+        } else if start.x <= zone.x {
+            DockPlacement::Left
+        } else if end.x <= zone.x {
+            DockPlacement::Right
+        } else if start.y <= zone.y {
+            DockPlacement::Top
+        } else if end.y <= zone.y {
+            DockPlacement::Bottom
+        } else {
+            DockPlacement::Floating
+        }
+    }
+
+    /// Gets the area highlighted while a panel is dragged, for internal use only
+    ///
+    /// # Returns
+    /// The area where the panel would be placed, or `None` if no panel is being dragged
+    ///
+    /// # Arguments
+    /// No arguments
+    fn drop_area(&self) -> Option<Rect> {
+        let drag = self.drag.filter(|drag| drag.is_dragging)?;
+        let panel = &self.panels[drag.panel].layout;
+        let extent = Vector2D::new(
+            panel.extent.min(self.size.x * 0.5),
+            panel.extent.min(self.size.y * 0.5),
+        );
+        let area = match self.drop_zone(self.cursor_pos) {
+            DockPlacement::Left => Rect::new(self.position, Vector2D::new(extent.x, self.size.y)),
+            DockPlacement::Right => Rect::new(
+                Vector2D::new(self.position.x + self.size.x - extent.x, self.position.y),
+                Vector2D::new(extent.x, self.size.y),
+            ),
+            DockPlacement::Top => Rect::new(self.position, Vector2D::new(self.size.x, extent.y)),
+            DockPlacement::Bottom => Rect::new(
+                Vector2D::new(self.position.x, self.position.y + self.size.y - extent.y),
+                Vector2D::new(self.size.x, extent.y),
+            ),
+            DockPlacement::Floating => {
+                Rect::new(self.cursor_pos - drag.grab, panel.floating_area.size)
+            }
+        };
+        Some(area)
+    }
+
+    /// Moves a panel over the other ones, for internal use only
+    ///
+    /// # Returns
+    /// The new index of the panel
+    ///
+    /// # Arguments
+    /// * `index` - the index of the panel to be raised
+    fn raise(&mut self, index: usize) -> usize {
+        let last = self.panels.len() - 1;
+        if index != last {
+            let center_count = self.center_count();
+            let panel = self.panels.remove(index);
+            self.panels.push(panel);
+            let child = self.children.remove(center_count + index);
+            self.children.push(child);
+            self.set_dirty(true);
+        }
+        last
+    }
+
+    /// Handles a change of the layout made by the user, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that changed the layout
+    /// * `messages` - queue of messages
+    fn on_layout_changed(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.set_dirty(true);
+        if let Some(mut message) = self.on_layout_change.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<DockLayout>>() {
+                message.value = self.get_layout();
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Gets the render instructions of a panel's header, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the header
+    ///
+    /// # Arguments
+    /// * `title` - the panel's title
+    /// * `area` - the panel's area, header included
+    fn header_recipe(&self, title: &str, area: Rect) -> Vec<RenderInstruction> {
+        let size = Vector2D::new(area.size.x, self.header_height().min(area.size.y));
+        vec![
+            RenderInstruction::DrawRect {
+                point: area.position,
+                size,
+                color: self.header_color,
+                clip_point: area.position,
+                clip_size: size,
+                blend_mode: BlendMode::Normal,
+            },
+            RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    area.position.x + 8.,
+                    area.position.y + (size.y + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: String::from(title),
+                color: self.text_color,
+                clip_point: area.position,
+                clip_size: size,
+                font: FontDescriptor::default(),
+            },
+        ]
+    }
+
+    /// Sets the dock area's size (width and height), returning the dock area (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dock area with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the dock area
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Adds a panel to the dock area, returning the dock area (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dock area with the panel added
+    ///
+    /// # Arguments
+    /// * `title` - the panel's title, displayed on its header and identifying the panel
+    /// * `widget` - the panel's content
+    /// * `placement` - the panel's placement
+    /// * `extent` - the panel's thickness when docked
+    pub fn with_panel(
+        mut self,
+        title: &str,
+        widget: Weak<RefCell<dyn Widget>>,
+        placement: DockPlacement,
+        extent: f64,
+    ) -> Self {
+        self.add_panel(title, widget, placement, extent);
+        self
+    }
+
+    /// Sets the dock area's message to be handled when the layout changes, returning the dock area (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dock area with the given message to be handled when the layout changes
+    ///
+    /// # Arguments
+    /// * `on_layout_change` - the message to be handled when the layout changes
+    pub fn with_on_layout_change(mut self, on_layout_change: Option<Box<dyn Message>>) -> Self {
+        self.on_layout_change = on_layout_change;
+        self
+    }
+
+    /// Sets the dock area's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dock area
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the dock area's style, overriding the properties of the current theme, returning
+    /// the dock area (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dock area with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dock area
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for DockAreaWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        DockAreaWidget {
+            style: Style::new(),
+            ..DockAreaWidget::new(
+                Vector2D::new(100., 100.),
+                theme.font_size,
+                theme.palette.muted,
+                theme.palette.foreground,
+                theme.palette.accent.with_opacity(0.4),
+            )
+        }
+    }
+}
+
+impl Widget for DockAreaWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                if let Some(drag) = self.drag.as_mut() {
+                    let moved = self.cursor_pos - drag.origin;
+                    if moved.x.abs() >= DRAG_THRESHOLD || moved.y.abs() >= DRAG_THRESHOLD {
+                        drag.is_dragging = true;
+                    }
+                    if drag.is_dragging {
+                        // Only the highlighted zone changes while dragging
+                        self.dirty = true;
+                    }
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                let header_height = self.header_height();
+                let (_, areas) = self.areas(Rect::new(self.position, self.size));
+                // Floating panels are over the docked ones, and the last ones over the first ones
+                let pressed = (0..self.panels.len())
+                    .rev()
+                    .filter(|index| self.panels[*index].layout.placement == DockPlacement::Floating)
+                    .chain((0..self.panels.len()).filter(|index| {
+                        self.panels[*index].layout.placement != DockPlacement::Floating
+                    }))
+                    .find(|index| areas[*index].contains(self.cursor_pos));
+                if let Some(mut index) = pressed {
+                    let area = areas[index];
+                    if self.panels[index].layout.placement == DockPlacement::Floating {
+                        index = self.raise(index);
+                    }
+                    if self.cursor_pos.y <= area.position.y + header_height {
+                        self.drag = Some(DockDrag {
+                            panel: index,
+                            origin: self.cursor_pos,
+                            grab: self.cursor_pos - area.position,
+                            is_dragging: false,
+                        });
+                        // The press on a header does not reach the panels
+                        return EventStatus::Captured;
+                    }
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                if let Some(drag) = self.drag.take() {
+                    if drag.is_dragging {
+                        let placement = self.drop_zone(self.cursor_pos);
+                        let position = self.cursor_pos - drag.grab - self.position;
+                        let index = self.raise(drag.panel);
+                        let panel = &mut self.panels[index].layout;
+                        panel.placement = placement;
+                        if placement == DockPlacement::Floating {
+                            panel.floating_area.position = position;
+                        }
+                        self.on_layout_changed(event, messages);
+                    }
+                    return EventStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        // Headers of the docked panels, whose content is drawn below them
+        let (_, areas) = self.areas(Rect::new(self.position, self.size));
+        self.panels
+            .iter()
+            .zip(areas)
+            .filter(|(panel, _)| panel.layout.placement != DockPlacement::Floating)
+            .flat_map(|(panel, area)| self.header_recipe(&panel.layout.title, area))
+            .collect()
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        // Headers of the floating panels, which are drawn over every docked panel
+        let (_, areas) = self.areas(Rect::new(self.position, self.size));
+        let mut instructions: Vec<RenderInstruction> = self
+            .panels
+            .iter()
+            .zip(areas)
+            .filter(|(panel, _)| panel.layout.placement == DockPlacement::Floating)
+            .flat_map(|(panel, area)| self.header_recipe(&panel.layout.title, area))
+            .collect();
+
+        if let Some(area) = self.drop_area() {
+            instructions.push(RenderInstruction::DrawRect {
+                point: area.position,
+                size: area.size,
+                color: self.highlight_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        let header_height = self.header_height();
+        let (center, areas) = self.areas(area);
+        let mut child_areas = vec![Some(center); self.center_count()];
+        // The panels' content is placed below their headers
+        child_areas.extend(areas.into_iter().map(|area| {
+            let height = header_height.min(area.size.y);
+            Some(Rect::new(
+                Vector2D::new(area.position.x, area.position.y + height),
+                Vector2D::new(area.size.x, area.size.y - height),
+            ))
+        }));
+        child_areas
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        // The children filling the center are kept before the panels' content
+        let center_count = self.center_count();
+        self.children.insert(center_count, child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.header_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.highlight_color = self
+            .style
+            .accent_color
+            .unwrap_or(theme.palette.accent.with_opacity(0.4));
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,970 @@
+use crate::event;
+use crate::event::Event;
+use crate::key_code::KeyCode;
+use crate::message::ValueChanged;
+use crate::renderer::{
+    AbsoluteWidgetCollection, BlendMode, FontDescriptor, Message, RenderInstruction,
+    RenderInstructionCollection,
+};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Height of the items of the list, as a multiple of the font size
+const ROW_HEIGHT: f64 = 2.;
+
+/// Space between the edges and the texts
+const PADDING: f64 = 6.;
+
+/// List of items of a dropdown, drawn on an absolute position below its header, for
+/// internal use only
+#[derive(Clone)]
+struct DropdownListWidget {
+    /// The list's identifier
+    id: usize,
+
+    /// The data attached to the list through its handle
+    widget_data: WidgetData,
+
+    /// The list's items
+    items: Vec<String>,
+
+    /// The index of the item selected, if any
+    selected: Option<usize>,
+
+    /// The index of the item under the cursor, if any
+    hovered: Option<usize>,
+
+    /// The list's font size
+    font_size: usize,
+
+    /// The list's background color
+    background_color: Color,
+
+    /// The list's text color
+    text_color: Color,
+
+    /// The color of the item under the cursor
+    highlight_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The list's children, which are always empty
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The list's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    /// relative to the top left corner
+    position: Vector2D,
+
+    /// The list's current size (width and height)
+    size: Vector2D,
+
+    /// The list's layout
+    layout: Layout,
+}
+
+impl DropdownListWidget {
+    /// Creates a new `DropdownListWidget`, without items
+    ///
+    /// # Returns
+    /// The list created
+    ///
+    /// # Arguments
+    /// No arguments
+    fn new() -> DropdownListWidget {
+        DropdownListWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            items: Vec::new(),
+            selected: None,
+            hovered: None,
+            font_size: 14,
+            background_color: Color::from_hex(0xffffffff),
+            text_color: Color::from_hex(0xff000000),
+            highlight_color: Color::from_hex(0xffcce4ff),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size: Vector2D::new(0., 0.),
+            layout: Layout::None,
+        }
+    }
+}
+
+impl Widget for DropdownListWidget {
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let row_height = self.font_size as f64 * ROW_HEIGHT;
+        let mut instructions = vec![
+            // Border
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: self.text_color.with_opacity(0.4),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Background
+            RenderInstruction::DrawRect {
+                point: self.position + 1.,
+                size: self.size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        for (index, item) in self.items.iter().enumerate() {
+            let point = Vector2D::new(self.position.x, self.position.y + index as f64 * row_height);
+            let size = Vector2D::new(self.size.x, row_height);
+            if self.hovered == Some(index)
+                || (self.hovered.is_none() && self.selected == Some(index))
+            {
+                instructions.push(RenderInstruction::DrawRect {
+                    point: point + 1.,
+                    size: size - 2.,
+                    color: self.highlight_color,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    point.x + PADDING,
+                    point.y + (row_height + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: item.clone(),
+                color: self.text_color,
+                clip_point: point,
+                clip_size: size,
+                font: FontDescriptor::default(),
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, _child: Weak<RefCell<dyn Widget>>) {}
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        Vector2D::new(0., 0.)
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.size,
+            &self.layout,
+            Vector2D::new(0., 0.),
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_offset(&mut self, _offset: Vector2D) {}
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        Rect::new(self.position, self.size).contains(cursor_pos)
+    }
+}
+
+/// Dropdown is a combo box: a header showing the item selected which, when clicked,
+/// opens the list of items below it.
+///
+/// The list is drawn over every other widget, as an absolute widget inserted in the
+/// [`AbsoluteWidgetCollection`] while open, so the dropdown needs the pointers to the
+/// absolute widgets collection and to the render instructions collection given to the
+/// event loop (see
+/// [`with_absolute_widget_collection_ptr`](`DropdownWidget::with_absolute_widget_collection_ptr`)).
+///
+/// Clicking an item selects it, handling the `on_select` message, and closes the list,
+/// as does clicking anywhere outside the list or pressing `Escape`. The index of the
+/// item selected is filled in the `on_select` message if it is a
+/// [`ValueChanged<usize>`](`crate::message::ValueChanged`).
+#[derive(Clone)]
+pub struct DropdownWidget {
+    /// The dropdown's identifier
+    id: usize,
+
+    /// The data attached to the dropdown through its handle
+    widget_data: WidgetData,
+
+    /// The dropdown's items
+    items: Vec<String>,
+
+    /// The index of the item selected, if any
+    selected: Option<usize>,
+
+    /// The text shown on the header while no item is selected
+    placeholder: String,
+
+    /// Whether the list of items is open
+    is_open: bool,
+
+    /// The message to be handled when an item is selected
+    on_select: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The dropdown's font size
+    font_size: usize,
+
+    /// The dropdown's background color
+    background_color: Color,
+
+    /// The dropdown's text color
+    text_color: Color,
+
+    /// The color of the item under the cursor
+    highlight_color: Color,
+
+    /// The list of items, drawn on an absolute position while open
+    list: Rc<RefCell<DropdownListWidget>>,
+
+    /// The collection of render instructions with the draw primitives of the list
+    render_instruction_collection_ptr: Weak<RefCell<RenderInstructionCollection>>,
+
+    /// The collection of widgets with absolute positions, where the list is inserted
+    absolute_widget_collection_ptr: Weak<RefCell<AbsoluteWidgetCollection>>,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The dropdown's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The dropdown's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The dropdown's current size (width and height) of the header
+    size: Vector2D,
+
+    /// The dropdown's original size (width and height) of the header
+    original_size: Vector2D,
+
+    /// The dropdown's layout
+    layout: Layout,
+
+    /// The dropdown's offset vector coordinates
+    offset: Vector2D,
+
+    /// The dropdown's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl DropdownWidget {
+    /// Creates a new `DropdownWidget`
+    ///
+    /// # Returns
+    /// The dropdown created, without any item selected
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the dropdown's header
+    /// * `font_size` - the font size to be assigned to the dropdown
+    /// * `items` - the items to be assigned to the dropdown
+    /// * `background_color` - the color to be assigned to the dropdown's background
+    /// * `text_color` - the color to be assigned to the dropdown's text
+    /// * `on_select` - the message to be handled when an item is selected
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        items: Vec<String>,
+        background_color: Color,
+        text_color: Color,
+        on_select: Option<Box<dyn Message>>,
+    ) -> DropdownWidget {
+        DropdownWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color),
+            items,
+            selected: None,
+            placeholder: String::new(),
+            is_open: false,
+            on_select,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            text_color,
+            highlight_color: Color::from_hex(0xffcce4ff),
+            list: Rc::new(RefCell::new(DropdownListWidget::new())),
+            render_instruction_collection_ptr: Weak::new(),
+            absolute_widget_collection_ptr: Weak::new(),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Gets the dropdown's items
+    ///
+    /// # Returns
+    /// The dropdown's items
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Sets the dropdown's items, closing its list and clearing the selection if the
+    /// item selected no longer exists
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `items` - the items to be assigned to the dropdown
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.close();
+        self.items = items;
+        self.selected = self.selected.filter(|index| *index < self.items.len());
+        self.dirty = true;
+    }
+
+    /// Gets the index of the item selected
+    ///
+    /// # Returns
+    /// The index of the item selected, or `None` if there is none
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects an item, without handling the `on_select` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the index of the item to be selected, or `None` to clear the selection
+    pub fn set_selected(&mut self, selected: Option<usize>) {
+        self.selected = selected.filter(|index| *index < self.items.len());
+        self.dirty = true;
+    }
+
+    /// Sets the text shown on the header while no item is selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `placeholder` - the text to be assigned to the placeholder
+    pub fn set_placeholder(&mut self, placeholder: &str) {
+        self.placeholder = String::from(placeholder);
+        self.dirty = true;
+    }
+
+    /// Sets the message to be handled when an item is selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_select` - the message to be handled when an item is selected
+    pub fn set_message(&mut self, on_select: Option<Box<dyn Message>>) {
+        self.on_select = on_select;
+    }
+
+    /// Sets the color of the item under the cursor
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `highlight_color` - the color to be assigned to the item under the cursor
+    pub fn set_highlight_color(&mut self, highlight_color: Color) {
+        self.style.selection_color = Some(highlight_color);
+        self.highlight_color = highlight_color;
+    }
+
+    /// Checks whether the list of items is open
+    ///
+    /// # Returns
+    /// True, if the list is open, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Opens the list of items below the header, if there are items and the dropdown
+    /// has a pointer to the absolute widgets collection
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn open(&mut self) {
+        if self.is_open || self.items.is_empty() {
+            return;
+        }
+        if let Some(absolute_widget_collection) = self.absolute_widget_collection_ptr.upgrade() {
+            let area = self.list_area();
+            {
+                let mut list = self.list.borrow_mut();
+                list.items = self.items.clone();
+                list.selected = self.selected;
+                list.hovered = None;
+                list.font_size = self.font_size;
+                list.background_color = self.background_color;
+                list.text_color = self.text_color;
+                list.highlight_color = self.highlight_color;
+                list.dirty = true;
+            }
+            let list: Rc<RefCell<dyn Widget>> = self.list.clone();
+            absolute_widget_collection.borrow_mut().insert(
+                Rc::downgrade(&list),
+                area.position,
+                area.size,
+            );
+            self.is_open = true;
+            self.dirty = true;
+        }
+    }
+
+    /// Closes the list of items, without changing the selection
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn close(&mut self) {
+        if !self.is_open {
+            return;
+        }
+        let id = self.list.borrow().id;
+        if let Some(render_instruction_collection) =
+            self.render_instruction_collection_ptr.upgrade()
+        {
+            render_instruction_collection.borrow_mut().remove(id);
+        }
+        if let Some(absolute_widget_collection) = self.absolute_widget_collection_ptr.upgrade() {
+            absolute_widget_collection.borrow_mut().remove(id);
+        }
+        self.list.borrow_mut().id = 0;
+        self.is_open = false;
+        self.dirty = true;
+    }
+
+    /// Gets the area of the list of items, below the header, for internal use only
+    ///
+    /// # Returns
+    /// The area of the list of items
+    ///
+    /// # Arguments
+    /// No arguments
+    fn list_area(&self) -> Rect {
+        Rect::new(
+            Vector2D::new(self.position.x, self.position.y + self.size.y),
+            Vector2D::new(
+                self.size.x,
+                self.items.len() as f64 * self.font_size as f64 * ROW_HEIGHT,
+            ),
+        )
+    }
+
+    /// Gets the item of the list under a position, for internal use only
+    ///
+    /// # Returns
+    /// The index of the item under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn item_at(&self, position: Vector2D) -> Option<usize> {
+        let area = self.list_area();
+        if !area.contains(position) {
+            return None;
+        }
+        let index =
+            ((position.y - area.position.y) / (self.font_size as f64 * ROW_HEIGHT)) as usize;
+        Some(index.min(self.items.len() - 1))
+    }
+
+    /// Selects an item, handling the `on_select` message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the item selected
+    /// * `event` - the event that selected the item
+    /// * `messages` - queue of messages
+    fn select(&mut self, index: usize, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.selected = Some(index);
+        if let Some(mut message) = self.on_select.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<usize>>() {
+                message.value = index;
+            }
+            messages.enqueue(message);
+        }
+        self.dirty = true;
+    }
+
+    /// Sets the dropdown's size (width and height) of the header, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the size (width and height) of the header assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the dropdown's header
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the dropdown's items, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the items assigned
+    ///
+    /// # Arguments
+    /// * `items` - the items to be assigned to the dropdown
+    pub fn with_items(mut self, items: Vec<String>) -> Self {
+        self.set_items(items);
+        self
+    }
+
+    /// Sets the dropdown's item selected, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the item selected assigned
+    ///
+    /// # Arguments
+    /// * `selected` - the index of the item to be selected, or `None` to clear the selection
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.set_selected(selected);
+        self
+    }
+
+    /// Sets the dropdown's placeholder, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the placeholder assigned
+    ///
+    /// # Arguments
+    /// * `placeholder` - the text shown on the header while no item is selected
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.set_placeholder(placeholder);
+        self
+    }
+
+    /// Sets the dropdown's message to be handled when an item is selected, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the given message to be handled when an item is selected
+    ///
+    /// # Arguments
+    /// * `on_select` - the message to be handled when an item is selected
+    pub fn with_on_select(mut self, on_select: Option<Box<dyn Message>>) -> Self {
+        self.on_select = on_select;
+        self
+    }
+
+    /// Sets the dropdown's pointer to the render instructions collection, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the pointer to the render instructions collection assigned
+    ///
+    /// # Arguments
+    /// * `render_instruction_collection_ptr` - the pointer to the render instructions collection to be assigned to the dropdown
+    pub fn with_render_instruction_collection_ptr(
+        mut self,
+        render_instruction_collection_ptr: Weak<RefCell<RenderInstructionCollection>>,
+    ) -> Self {
+        self.render_instruction_collection_ptr = render_instruction_collection_ptr;
+        self
+    }
+
+    /// Sets the dropdown's pointer to the absolute widgets collection, returning the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the pointer to the absolute widgets collection assigned
+    ///
+    /// # Arguments
+    /// * `absolute_widget_collection_ptr` - the pointer to the absolute widgets collection to be assigned to the dropdown
+    pub fn with_absolute_widget_collection_ptr(
+        mut self,
+        absolute_widget_collection_ptr: Weak<RefCell<AbsoluteWidgetCollection>>,
+    ) -> Self {
+        self.absolute_widget_collection_ptr = absolute_widget_collection_ptr;
+        self
+    }
+
+    /// Sets the dropdown's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dropdown
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the dropdown's style, overriding the properties of the current theme, returning
+    /// the dropdown (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The dropdown with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the dropdown
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for DropdownWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        let mut dropdown = DropdownWidget::new(
+            Vector2D::new(160., 28.),
+            theme.font_size,
+            Vec::new(),
+            theme.palette.surface,
+            theme.palette.foreground,
+            None,
+        );
+        dropdown.set_highlight_color(theme.palette.selection);
+        // The properties read from the theme are not overrides
+        dropdown.style = Style::new();
+        dropdown
+    }
+}
+
+impl Widget for DropdownWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                if self.is_open {
+                    let hovered = self.item_at(self.cursor_pos);
+                    let mut list = self.list.borrow_mut();
+                    if list.hovered != hovered {
+                        list.hovered = hovered;
+                        list.dirty = true;
+                    }
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                if self.is_open {
+                    // Any click closes the list, selecting the item clicked, if any
+                    if let Some(index) = self.item_at(self.cursor_pos) {
+                        self.select(index, event, messages);
+                    }
+                    self.close();
+                    return EventStatus::Captured;
+                }
+                if self.is_cursor_inside(self.cursor_pos) {
+                    self.open();
+                    return EventStatus::Captured;
+                }
+            }
+            event::Event::Keyboard(event::Keyboard::KeyPressed {
+                key_code: KeyCode::Escape,
+                ..
+            }) if self.is_open => {
+                self.close();
+                return EventStatus::Captured;
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let (text, color) = match self.selected.and_then(|index| self.items.get(index)) {
+            Some(item) => (item.clone(), self.text_color),
+            None => (self.placeholder.clone(), self.text_color.with_opacity(0.5)),
+        };
+        // Arrow pointing down while closed and up while open
+        let arrow_size = self.font_size as f64 * 0.5;
+        let arrow_center = Vector2D::new(
+            self.position.x + self.size.x - PADDING - arrow_size * 0.5,
+            self.position.y + self.size.y * 0.5,
+        );
+        let direction = if self.is_open { -1. } else { 1. };
+
+        vec![
+            // Border
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: self.text_color.with_opacity(0.4),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Background
+            RenderInstruction::DrawRect {
+                point: self.position + 1.,
+                size: self.size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Item selected
+            RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    self.position.x + PADDING,
+                    self.position.y + (self.size.y + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: text,
+                color,
+                clip_point: self.position,
+                clip_size: Vector2D::new(
+                    (self.size.x - 2. * PADDING - arrow_size).max(0.),
+                    self.size.y,
+                ),
+                font: FontDescriptor::default(),
+            },
+            // Arrow
+            RenderInstruction::DrawTriangle {
+                point_a: Vector2D::new(
+                    arrow_center.x - arrow_size * 0.5,
+                    arrow_center.y - direction * arrow_size * 0.25,
+                ),
+                point_b: Vector2D::new(
+                    arrow_center.x + arrow_size * 0.5,
+                    arrow_center.y - direction * arrow_size * 0.25,
+                ),
+                point_c: Vector2D::new(
+                    arrow_center.x,
+                    arrow_center.y + direction * arrow_size * 0.25,
+                ),
+                color: self.text_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ]
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.highlight_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.dirty = true;
+    }
+}
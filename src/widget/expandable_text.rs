@@ -0,0 +1,654 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text::{self, TextAlignment, TextLayout};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{EventStatus, Flex, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Text of the toggle shown while the text is collapsed
+const SHOW_MORE: &str = "Show more";
+
+/// Text of the toggle shown while the text is expanded
+const SHOW_LESS: &str = "Show less";
+
+/// Expandable text is a widget that displays a long text wrapped on multiple lines,
+/// showing only its first lines until expanded (e.g. a post on a feed or a description
+/// on a detail pane).
+///
+/// When the text has more lines than the ones shown while collapsed, a "show more"
+/// toggle follows them, which expands the text (and then collapses it back, as "show
+/// less"). The widget's height is measured from the lines shown, such that its
+/// siblings are laid out again on every toggle, and the `on_toggle` message is
+/// handled, which is filled with whether the text is expanded if it is a
+/// [`ValueChanged<bool>`](`ValueChanged`).
+#[derive(Clone)]
+pub struct ExpandableTextWidget {
+    /// The expandable text's identifier
+    id: usize,
+
+    /// The data attached to the expandable text through its handle
+    widget_data: WidgetData,
+
+    /// The expandable text's text
+    text: String,
+
+    /// The expandable text's font size
+    font_size: usize,
+
+    /// The number of lines shown while the text is collapsed
+    max_lines: usize,
+
+    /// Whether all the lines of the text are shown
+    is_expanded: bool,
+
+    /// The texts of the toggle, while the text is collapsed and expanded
+    toggle_texts: (String, String),
+
+    /// The message to be handled when the text is expanded or collapsed
+    on_toggle: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The expandable text's background color
+    background_color: Color,
+
+    /// The expandable text's foreground color (i.e., text color)
+    foreground_color: Color,
+
+    /// The color of the toggle's text
+    toggle_color: Color,
+
+    /// How the text is broken into lines and aligned
+    text_layout: TextLayout,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The expandable text's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The expandable text's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The expandable text's current size (width and height)
+    size: Vector2D,
+
+    /// The expandable text's original size (width and height), whose height is
+    /// replaced by the height of the lines shown
+    original_size: Vector2D,
+
+    /// The expandable text's layout
+    layout: Layout,
+
+    /// The expandable text's offset vector coordinates
+    offset: Vector2D,
+
+    /// The expandable text's clipping point, or `None` to clip to its own area
+    clip_point: Option<Vector2D>,
+
+    /// The expandable text's clipping size, or `None` to clip to its own area
+    clip_size: Option<Vector2D>,
+
+    /// The expandable text's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
+
+    /// The expandable text's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ExpandableTextWidget {
+    /// Creates a new `ExpandableTextWidget`
+    ///
+    /// # Returns
+    /// The expandable text created, collapsed
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the expandable text
+    /// * `max_lines` - the number of lines shown while the text is collapsed
+    /// * `width` - the width available for the lines of the text
+    /// * `font_size` - the font size to be assigned to the text
+    /// * `background_color` - the color to be assigned to the expandable text's background
+    /// * `foreground_color` - the color to be assigned to the expandable text's text
+    /// * `toggle_color` - the color to be assigned to the toggle's text
+    pub fn new(
+        text: String,
+        max_lines: usize,
+        width: f64,
+        font_size: usize,
+        background_color: Color,
+        foreground_color: Color,
+        toggle_color: Color,
+    ) -> ExpandableTextWidget {
+        let mut expandable_text = ExpandableTextWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_foreground_color(foreground_color)
+                .with_accent_color(toggle_color)
+                .with_font_size(font_size),
+            text,
+            font_size,
+            max_lines: max_lines.max(1),
+            is_expanded: false,
+            toggle_texts: (String::from(SHOW_MORE), String::from(SHOW_LESS)),
+            on_toggle: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            background_color,
+            foreground_color,
+            toggle_color,
+            text_layout: TextLayout::default().with_wrap(true),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size: Vector2D::new(width, 0.),
+            original_size: Vector2D::new(width, 0.),
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+            clip_point: None,
+            clip_size: None,
+            flex: Flex::default(),
+        };
+        expandable_text.remeasure();
+        expandable_text
+    }
+
+    /// Sets the expandable text's text
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the expandable text
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.remeasure();
+    }
+
+    /// Sets the number of lines shown while the text is collapsed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `max_lines` - the number of lines shown while collapsed, at least one
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.max(1);
+        self.remeasure();
+    }
+
+    /// Sets the message to be handled when the text is expanded or collapsed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_toggle` - the message to be handled when the text is expanded or collapsed
+    pub fn set_message(&mut self, on_toggle: Option<Box<dyn Message>>) {
+        self.on_toggle = on_toggle;
+    }
+
+    /// Expands or collapses the text, without handling the `on_toggle` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_expanded` - boolean indicating if all the lines of the text are shown
+    pub fn set_expanded(&mut self, is_expanded: bool) {
+        self.is_expanded = is_expanded;
+        self.remeasure();
+    }
+
+    /// Checks whether all the lines of the text are shown
+    ///
+    /// # Returns
+    /// True, if the text is expanded, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_expanded(&self) -> bool {
+        self.is_expanded
+    }
+
+    /// Checks whether the text has more lines than the ones shown while collapsed
+    /// (i.e., whether the toggle is shown)
+    ///
+    /// # Returns
+    /// True, if the text is longer than the lines shown while collapsed, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_truncated(&self) -> bool {
+        self.lines().len() > self.max_lines
+    }
+
+    /// Sets the texts of the toggle
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `show_more` - the text of the toggle while the text is collapsed
+    /// * `show_less` - the text of the toggle while the text is expanded
+    pub fn set_toggle_texts(&mut self, show_more: &str, show_less: &str) {
+        self.toggle_texts = (String::from(show_more), String::from(show_less));
+        self.dirty = true;
+    }
+
+    /// Sets how the lines of the text are aligned
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment to be assigned to the text
+    pub fn set_alignment(&mut self, alignment: TextAlignment) {
+        self.text_layout = self.text_layout.with_alignment(alignment);
+        self.dirty = true;
+    }
+
+    /// Breaks the text into the lines fitting the expandable text's width, for internal use only
+    ///
+    /// # Returns
+    /// The lines of the text, each with whether it ends a paragraph
+    ///
+    /// # Arguments
+    /// No arguments
+    fn lines(&self) -> Vec<(String, bool)> {
+        self.text_layout.lines(&self.text, self.size.x, &|text| {
+            text::measure_text(text, self.font_size).x
+        })
+    }
+
+    /// Gets the number of lines shown, for internal use only
+    ///
+    /// # Returns
+    /// All the lines if the text is expanded or fits the lines shown while collapsed,
+    /// those lines otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn visible_lines(&self) -> usize {
+        let lines = self.lines().len();
+        if self.is_expanded {
+            lines
+        } else {
+            lines.min(self.max_lines)
+        }
+    }
+
+    /// Measures the height of the lines shown, along with the toggle's, as the
+    /// expandable text's height and marks it dirty, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn remeasure(&mut self) {
+        let rows = self.visible_lines() + usize::from(self.is_truncated());
+        let height = rows as f64 * self.text_layout.line_height(self.font_size);
+        self.original_size.y = height;
+        self.size.y = height;
+        self.dirty = true;
+    }
+
+    /// Gets the toggle's text and area, for internal use only
+    ///
+    /// # Returns
+    /// The toggle's text, position and size, or `None` if the text is not truncated
+    ///
+    /// # Arguments
+    /// No arguments
+    fn toggle(&self) -> Option<(&str, Vector2D, Vector2D)> {
+        if !self.is_truncated() {
+            return None;
+        }
+        let text = if self.is_expanded {
+            &self.toggle_texts.1
+        } else {
+            &self.toggle_texts.0
+        };
+        let line_height = self.text_layout.line_height(self.font_size);
+        Some((
+            text,
+            Vector2D::new(
+                self.position.x,
+                self.position.y + self.visible_lines() as f64 * line_height,
+            ),
+            Vector2D::new(text::measure_text(text, self.font_size).x, line_height),
+        ))
+    }
+
+    /// Sets the number of lines shown while the text is collapsed, returning the
+    /// expandable text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the number of lines assigned
+    ///
+    /// # Arguments
+    /// * `max_lines` - the number of lines shown while collapsed, at least one
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.set_max_lines(max_lines);
+        self
+    }
+
+    /// Sets the expandable text's text, returning the expandable text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the text assigned
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the expandable text
+    pub fn with_text(mut self, text: String) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Sets the width available for the lines of the text, returning the expandable
+    /// text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the width assigned
+    ///
+    /// # Arguments
+    /// * `width` - the width available for the lines of the text
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.size.x = width;
+        self.original_size.x = width;
+        self.remeasure();
+        self
+    }
+
+    /// Sets the message to be handled when the text is expanded or collapsed, returning
+    /// the expandable text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the message assigned
+    ///
+    /// # Arguments
+    /// * `on_toggle` - the message to be handled when the text is expanded or collapsed
+    pub fn with_on_toggle(mut self, on_toggle: Option<Box<dyn Message>>) -> Self {
+        self.on_toggle = on_toggle;
+        self
+    }
+
+    /// Sets the texts of the toggle, returning the expandable text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the toggle's texts assigned
+    ///
+    /// # Arguments
+    /// * `show_more` - the text of the toggle while the text is collapsed
+    /// * `show_less` - the text of the toggle while the text is expanded
+    pub fn with_toggle_texts(mut self, show_more: &str, show_less: &str) -> Self {
+        self.set_toggle_texts(show_more, show_less);
+        self
+    }
+
+    /// Sets how the expandable text is sized by a flex layout, returning the expandable
+    /// text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the expandable text
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    /// Sets the expandable text's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the expandable text
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the expandable text's style, overriding the properties of the current theme,
+    /// returning the expandable text (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The expandable text with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the expandable text
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ExpandableTextWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ExpandableTextWidget {
+            style: Style::new(),
+            ..ExpandableTextWidget::new(
+                String::new(),
+                3,
+                200.,
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.accent,
+            )
+        }
+    }
+}
+
+impl Widget for ExpandableTextWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                let is_on_toggle = self.toggle().is_some_and(|(_, point, size)| {
+                    self.cursor_pos.x >= point.x
+                        && self.cursor_pos.x <= point.x + size.x
+                        && self.cursor_pos.y >= point.y
+                        && self.cursor_pos.y <= point.y + size.y
+                });
+                if !is_on_toggle {
+                    return EventStatus::Ignored;
+                }
+                self.is_expanded = !self.is_expanded;
+                self.remeasure();
+                if let Some(mut message) = self.on_toggle.clone() {
+                    message.set_event(event);
+                    if let Some(message) = message.downcast_mut::<ValueChanged<bool>>() {
+                        message.value = self.is_expanded;
+                    }
+                    messages.enqueue(message);
+                }
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let clip_point = self.clip_point.unwrap_or(self.position);
+        let clip_size = self.clip_size.unwrap_or(self.size);
+        let text = |point: Vector2D, color: Color, string: String| RenderInstruction::DrawText {
+            point: Vector2D::new(point.x, point.y + self.font_size as f64),
+            color,
+            font_size: self.font_size,
+            string,
+            clip_point,
+            clip_size,
+            font: FontDescriptor::default(),
+        };
+
+        let mut instructions = vec![
+            // Expandable text rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point,
+                clip_size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Lines shown, skipping the runs laid out on the lines hidden
+        let line_height = self.text_layout.line_height(self.font_size);
+        let height = self.visible_lines() as f64 * line_height;
+        let runs = self
+            .text_layout
+            .layout(&self.text, self.font_size, self.size.x, &|text| {
+                text::measure_text(text, self.font_size).x
+            });
+        for run in runs
+            .into_iter()
+            .filter(|run| run.point.y < height - line_height * 0.5)
+        {
+            instructions.push(text(
+                self.position + run.point,
+                self.foreground_color,
+                run.text,
+            ));
+        }
+
+        if let Some((string, point, _)) = self.toggle() {
+            instructions.push(text(point, self.toggle_color, String::from(string)));
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.original_size = size;
+        self.remeasure();
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, clip_point: Option<Vector2D>) {
+        self.clip_point = clip_point;
+    }
+
+    fn set_clip_size(&mut self, clip_size: Option<Vector2D>) {
+        self.clip_size = clip_size;
+    }
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.toggle_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.remeasure();
+    }
+}
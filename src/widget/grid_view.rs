@@ -1,19 +1,30 @@
 use crate::event::Event;
 use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Queue, Vector2D};
-use crate::widget::{Axis, Layout, Widget};
+use crate::selection::RubberBandSelection;
+use crate::util::{Queue, Rect, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Flex, GridCell, GridTemplate,
+    GridTrack, Layout, Widget, WidgetData,
+};
 
 use std::cell::RefCell;
 use std::rc::Weak;
 
 /// A Grid View is a view with an array of widgets that are 
 /// placed in a two dimensional grid of cells (rows and columns).
-/// Each component takes all the available space within its cell,
-/// and each cell is exactly the same size.
+/// Each component takes all the available space within its cell, and each
+/// cell is exactly the same size, unless the rows and columns are sized by
+/// a [`GridTemplate`] (e.g. with fixed, percentage or automatic lengths) and
+/// the components are placed on cells spanning several rows and columns.
+///
+/// Its children can be bound to a list of items with an [`ItemsSource`](`crate::template::ItemsSource`).
 #[derive(Clone)]
 pub struct GridViewWidget {
     /// The grid's identifier
     id: usize,
+
+    /// The data attached to the grid through its handle
+    widget_data: WidgetData,
     
     /// The grid's current size (width and height)
     size: Vector2D,
@@ -23,12 +34,29 @@ pub struct GridViewWidget {
     
     /// The grid's layout
     layout: Layout,
+
+    /// The sizing of the grid's rows and columns
+    template: GridTemplate,
+
+    /// The cells where the children are placed, along with their child, the other
+    /// children flowing along the axis
+    cells: Vec<(Weak<RefCell<dyn Widget>>, GridCell)>,
     
     /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
     dirty: bool,
     
     /// The grid's children (i.e., his widgets tree)
     children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The grid's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    /// relative to the top left corner
+    position: Vector2D,
+
+    /// The grid's rubber band selection of its children, if enabled
+    selection: Option<RubberBandSelection>,
+
+    /// The grid view's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
 }
 
 impl GridViewWidget {
@@ -44,22 +72,212 @@ impl GridViewWidget {
     pub fn new(size: Vector2D, axis: Axis, axis_length: usize) -> GridViewWidget {
         GridViewWidget {
             id: 0,
+            widget_data: WidgetData::default(),
             size: size,
             original_size: size,
             layout: Layout::Grid(axis, axis_length),
+            template: GridTemplate::default(),
+            cells: Vec::new(),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            selection: None,
+            flex: Flex::default(),
+        }
+    }
+
+    /// Sets the grid view's size (width and height), returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the grid view
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the grid view's axis direction, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the axis direction assigned
+    ///
+    /// # Arguments
+    /// * `axis` - the axis direction to be assigned to the grid view
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        if let Layout::Grid(grid_axis, _) = &mut self.layout {
+            *grid_axis = axis;
+        }
+        self
+    }
+
+    /// Sets the grid view's number of widgets along the axis, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the number of widgets along the axis assigned
+    ///
+    /// # Arguments
+    /// * `axis_length` - the number of widgets along the axis to be assigned to the grid view
+    pub fn with_axis_length(mut self, axis_length: usize) -> Self {
+        if let Layout::Grid(_, grid_axis_length) = &mut self.layout {
+            *grid_axis_length = axis_length;
+        }
+        self
+    }
+
+    /// Sets the grid view's template (i.e., the sizing of its rows and columns)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `template` - the template to be assigned to the grid view
+    pub fn set_template(&mut self, template: GridTemplate) {
+        self.template = template;
+        self.set_dirty(true);
+    }
+
+    /// Sets the grid view's template, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the template assigned
+    ///
+    /// # Arguments
+    /// * `template` - the template to be assigned to the grid view
+    pub fn with_template(mut self, template: GridTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Sets the sizing of the grid view's first columns, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the sizing of the columns assigned
+    ///
+    /// # Arguments
+    /// * `columns` - the sizing of the columns, from the left one
+    pub fn with_columns(mut self, columns: Vec<GridTrack>) -> Self {
+        self.template.columns = columns;
+        self
+    }
+
+    /// Sets the sizing of the grid view's first rows, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the sizing of the rows assigned
+    ///
+    /// # Arguments
+    /// * `rows` - the sizing of the rows, from the top one
+    pub fn with_rows(mut self, rows: Vec<GridTrack>) -> Self {
+        self.template.rows = rows;
+        self
+    }
+
+    /// Sets the cell where a child is placed
+    ///
+    /// The cell follows the child when the children before it are added or removed.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `child` - the child
+    /// * `cell` - the cell where the child is placed, or `None` to flow it along the axis
+    pub fn set_cell(&mut self, child: &Weak<RefCell<dyn Widget>>, cell: Option<GridCell>) {
+        // Drop the cells of the children dropped, and the child's previous cell
+        self.cells.retain(|(value, _)| value.strong_count() > 0 && !value.ptr_eq(child));
+        if let Some(cell) = cell {
+            self.cells.push((child.clone(), cell));
         }
+        self.set_dirty(true);
+    }
+
+    /// Adds a child placed on a cell
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `child` - the child to be added
+    /// * `cell` - the cell where the child is placed, spanning one or more rows and columns
+    pub fn add_child_at(&mut self, child: Weak<RefCell<dyn Widget>>, cell: GridCell) {
+        self.set_cell(&child, Some(cell));
+        self.children.push(child);
+    }
+
+    /// Sets the grid view's rubber band selection, allowing to select its children by
+    /// dragging a rectangle from its empty space
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selection` - the rubber band selection, or `None` to disable it
+    pub fn set_rubber_band_selection(&mut self, selection: Option<RubberBandSelection>) {
+        self.selection = selection;
+        self.dirty = true;
+    }
+
+    /// Sets the grid view's rubber band selection, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the rubber band selection assigned
+    ///
+    /// # Arguments
+    /// * `selection` - the rubber band selection to be assigned to the grid view
+    pub fn with_rubber_band_selection(mut self, selection: RubberBandSelection) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Sets how the grid view is sized by a flex layout
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `flex` - the grid view's flex properties
+    pub fn set_flex(&mut self, flex: Flex) {
+        self.flex = flex;
+        self.dirty = true;
+    }
+
+    /// Sets how the grid view is sized by a flex layout, returning the grid view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The grid view with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the grid view
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+}
+
+impl Default for GridViewWidget {
+    fn default() -> Self {
+        GridViewWidget::new(
+            Vector2D::new(100., 100.),
+            Axis::Vertical,
+            1,
+        )
     }
 }
 
 impl Widget for GridViewWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
-        for value in self.children.iter_mut() {
-            if let Some(child) = value.upgrade() {
-                child.borrow_mut().on_event(event, messages);
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let status = propagate_event(&self.children, event, messages);
+        if let Some(selection) = self.selection.as_mut() {
+            let area = Rect::new(self.position, self.size);
+            // Only the overlay is redrawn, so the children are not marked dirty
+            if selection.on_event(event, area, &self.children, messages) {
+                self.dirty = true;
             }
         }
+        status
     }
 
     fn set_id(&mut self, id: usize) {
@@ -70,13 +288,39 @@ impl Widget for GridViewWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         vec![]
     }
 
+    fn grid_template(&self) -> Option<GridTemplate> {
+        Some(self.template.clone())
+    }
+
+    fn grid_cell(&self, child: &Weak<RefCell<dyn Widget>>) -> Option<GridCell> {
+        self.cells
+            .iter()
+            .find(|(value, _)| value.ptr_eq(child))
+            .map(|(_, cell)| *cell)
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        match &self.selection {
+            Some(selection) => selection.overlay_recipe(Rect::new(self.position, self.size)),
+            None => vec![],
+        }
+    }
+
     fn set_dirty(&mut self, value: bool) {
         if value {
             self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
             for value in self.get_children() {
                 if let Some(child) = value.upgrade() {
                     if child.borrow_mut().is_dirty() {
@@ -103,7 +347,7 @@ impl Widget for GridViewWidget {
     }
 
     fn position(&mut self) -> Vector2D {
-        Vector2D::new(0., 0.)
+        self.position
     }
 
     fn size(&mut self) -> Vector2D {
@@ -119,6 +363,10 @@ impl Widget for GridViewWidget {
         &self.layout
     }
 
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
     fn offset(&mut self) -> Vector2D {
         Vector2D::new(0., 0.)
     }
@@ -137,7 +385,7 @@ impl Widget for GridViewWidget {
         (
             self.dirty,
             &mut self.children,
-            Vector2D::new(0., 0.),
+            self.position,
             self.size,
             self.original_size,
             &self.layout,
@@ -145,7 +393,9 @@ impl Widget for GridViewWidget {
         )
     }
 
-    fn set_position(&mut self, _position: Vector2D) {}
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
 
     fn set_size(&mut self, size: Vector2D) {
         self.set_dirty(true);
@@ -159,13 +409,9 @@ impl Widget for GridViewWidget {
 
     fn set_offset(&mut self, _offset: Vector2D) {}
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, _cursor_pos : Vector2D) -> bool {
       false 
@@ -1,7 +1,11 @@
 use crate::event::Event;
-use crate::renderer::{DrawImageOptions, Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::renderer::{
+    AtlasRegion, BlendMode, DrawImageOptions, ImageSource, Message, RenderInstruction,
+};
+use crate::resources::ImageHandle;
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -13,8 +17,18 @@ pub struct IconWidget {
     /// The icon's identifier
     id: usize,
 
-    /// The icon's picture absolute path
-    path: String,
+    /// The data attached to the icon through its handle
+    widget_data: WidgetData,
+
+    /// The icon's picture, in one or more resolutions
+    source: ImageSource,
+
+    /// The icon's picture as a region of an image atlas, drawn instead of the source if any
+    atlas_region: Option<AtlasRegion>,
+
+    /// The icon's picture as an image registered on the context's resources, drawn
+    /// instead of the source if any
+    image: Option<ImageHandle>,
 
     /// The icon's draw settings
     options: DrawImageOptions,
@@ -47,6 +61,16 @@ pub struct IconWidget {
 
     /// The icon's offset vector coordinates
     offset: Vector2D,
+
+    /// Whether the icon's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// Whether the icon's picture is mirrored when the layout direction is right-to-left
+    mirror_in_rtl: bool,
+
+    /// The icon's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl IconWidget {
@@ -68,7 +92,12 @@ impl IconWidget {
     ) -> IconWidget {
         IconWidget {
             id: 0,
-            path: path,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color),
+            source: ImageSource::new(path),
+            atlas_region: None,
+            image: None,
             options: options,
             background_color: background_color,
             dirty: true,
@@ -78,12 +107,227 @@ impl IconWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            mirror_in_rtl: false,
+        }
+    }
+
+    /// Sets whether the icon's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the icon's path of the image file, returning the icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the path of the image file assigned
+    ///
+    /// # Arguments
+    /// * `path` - the path of the image file to be assigned to the icon
+    pub fn with_path(mut self, path: String) -> Self {
+        self.source = ImageSource::new(path);
+        self.atlas_region = None;
+        self.image = None;
+        self
+    }
+
+    /// Sets the icon's picture, in one or more resolutions (e.g. 1x, 2x and 3x)
+    ///
+    /// The resolution best matching the display's scale factor is drawn.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `source` - the resolutions of the picture to be assigned to the icon
+    pub fn set_source(&mut self, source: ImageSource) {
+        self.source = source;
+        self.atlas_region = None;
+        self.image = None;
+        self.dirty = true;
+    }
+
+    /// Sets the icon's picture, in one or more resolutions, returning the icon
+    /// (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the picture assigned
+    ///
+    /// # Arguments
+    /// * `source` - the resolutions of the picture to be assigned to the icon
+    pub fn with_source(mut self, source: ImageSource) -> Self {
+        self.source = source;
+        self.atlas_region = None;
+        self.image = None;
+        self
+    }
+
+    /// Sets the icon's picture as a named region of an image atlas registered on the
+    /// [`Context`](`crate::context::Context`), drawn stretched to the icon's size
+    ///
+    /// Many icons drawn from the same atlas share the image loaded once by the renderer.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `atlas_region` - the region of the atlas to be drawn, or `None` to draw the icon's source
+    pub fn set_atlas_region(&mut self, atlas_region: Option<AtlasRegion>) {
+        self.atlas_region = atlas_region;
+        self.image = None;
+        self.dirty = true;
+    }
+
+    /// Sets the icon's picture as a named region of an image atlas, returning the icon
+    /// (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the atlas region assigned
+    ///
+    /// # Arguments
+    /// * `atlas_region` - the region of the atlas to be drawn
+    pub fn with_atlas_region(mut self, atlas_region: AtlasRegion) -> Self {
+        self.atlas_region = Some(atlas_region);
+        self.image = None;
+        self
+    }
+
+    /// Sets the icon's picture as an image registered on the
+    /// [`Context`](`crate::context::Context`)'s resources, drawn stretched to the icon's size
+    ///
+    /// Unlike the source, the image is referred to by its handle, so that its path is
+    /// not copied whenever the icon is rebuilt.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `image` - the handle of the image to be drawn, or `None` to draw the icon's source
+    pub fn set_image(&mut self, image: Option<ImageHandle>) {
+        self.image = image;
+        self.atlas_region = None;
+        self.dirty = true;
+    }
+
+    /// Sets the icon's picture as an image registered on the context's resources,
+    /// returning the icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the image assigned
+    ///
+    /// # Arguments
+    /// * `image` - the handle of the image to be drawn
+    pub fn with_image(mut self, image: ImageHandle) -> Self {
+        self.image = Some(image);
+        self.atlas_region = None;
+        self
+    }
+
+    /// Sets the icon's size (width and height), returning the icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the icon
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the icon's image drawing options, returning the icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the image drawing options assigned
+    ///
+    /// # Arguments
+    /// * `options` - the image drawing options to be assigned to the icon
+    pub fn with_options(mut self, options: DrawImageOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the icon's background color, returning the icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the icon
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets whether the icon's picture is mirrored when the layout direction is
+    /// right-to-left (e.g. for arrows or back buttons)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `mirror_in_rtl` - boolean indicating if the picture is mirrored on right-to-left layouts
+    pub fn set_mirror_in_rtl(&mut self, mirror_in_rtl: bool) {
+        self.mirror_in_rtl = mirror_in_rtl;
+        self.dirty = true;
+    }
+
+    /// Sets the icon's style, overriding the properties of the current theme and the ones set
+    /// before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the icon
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the icon's style, overriding the properties of the current theme, returning the
+    /// icon (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The icon with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the icon
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for IconWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        IconWidget {
+            style: Style::new(),
+            ..IconWidget::new(
+                String::new(),
+                Vector2D::new(32., 32.),
+                DrawImageOptions::OriginalSize,
+                theme.palette.surface,
+            )
         }
     }
 }
 
 impl Widget for IconWidget {
-    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) {}
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
 
     fn set_id(&mut self, id: usize) {
         self.id = id;
@@ -93,21 +337,60 @@ impl Widget for IconWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
+        // Icon rectangle.
+        let background = RenderInstruction::DrawRect {
+            point: self.position,
+            color: self.background_color.clone(),
+            size: self.size,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        };
+        if let Some(image) = self.image {
+            return vec![
+                background,
+                // Icon Image, loaded once by the renderer
+                RenderInstruction::DrawImageHandle {
+                    point: self.position,
+                    size: self.size,
+                    image,
+                    mirrored: false,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                },
+            ];
+        }
+        if let Some(atlas_region) = self.atlas_region.as_ref() {
+            return vec![
+                background,
+                // Icon Image, resolved from the atlas' regions when built
+                RenderInstruction::DrawImageRegion {
+                    point: self.position,
+                    size: self.size,
+                    atlas: atlas_region.atlas,
+                    region: Rect::new(Vector2D::new(0., 0.), Vector2D::new(0., 0.)),
+                    region_name: Some(atlas_region.name.clone()),
+                    mirrored: false,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                },
+            ];
+        }
         vec![
-            // Icon rectangle.
-            RenderInstruction::DrawRect {
-                point: self.position,
-                color: self.background_color.clone(),
-                size: self.size,
-                clip_point: self.position,
-                clip_size: self.size,
-            },
+            background,
             // Icon Image
             RenderInstruction::DrawImage {
                 point: self.position, // todo: CHANGE after testing
-                path: self.path.clone(),
+                path: self.source.best_match(1.).1.to_string(),
+                density: 1.,
+                source: Some(self.source.clone()),
                 options: self.options.clone(),
+                mirrored: false,
                 clip_point: self.position,
                 clip_size: self.size,
             },
@@ -190,15 +473,24 @@ impl Widget for IconWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, _cursor_pos : Vector2D) -> bool {
         false
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn mirror_in_rtl(&self) -> bool {
+        self.mirror_in_rtl
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.dirty = true;
+    }
 }
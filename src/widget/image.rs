@@ -0,0 +1,344 @@
+use crate::event::Event;
+use crate::renderer::{DrawImageOptions, ImageSource, Message, RenderInstruction};
+use crate::util::{ImageData, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Picture displayed by an [`ImageWidget`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageContent {
+    /// Image read from a file, in one or more resolutions
+    Source(ImageSource),
+    /// Image held in memory (e.g. generated procedurally, or decoded from a network
+    /// response), shared with the widget instead of being copied
+    Data(Rc<ImageData>),
+}
+
+impl From<ImageSource> for ImageContent {
+    fn from(source: ImageSource) -> Self {
+        ImageContent::Source(source)
+    }
+}
+
+impl From<Rc<ImageData>> for ImageContent {
+    fn from(image: Rc<ImageData>) -> Self {
+        ImageContent::Data(image)
+    }
+}
+
+impl From<ImageData> for ImageContent {
+    fn from(image: ImageData) -> Self {
+        ImageContent::Data(Rc::new(image))
+    }
+}
+
+/// Image is a widget that displays a picture stretched to its size, read either
+/// from a file or from pixels held in memory (see [`ImageData`])
+#[derive(Clone)]
+pub struct ImageWidget {
+    /// The image's identifier
+    id: usize,
+
+    /// The data attached to the image through its handle
+    widget_data: WidgetData,
+
+    /// The image's picture, if any
+    content: Option<ImageContent>,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The image's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The image's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    /// relative to the top left corner
+    position: Vector2D,
+
+    /// The image's current size (width and height)
+    size: Vector2D,
+
+    /// The image's original size (width and height)
+    original_size: Vector2D,
+
+    /// The image's layout
+    layout: Layout,
+
+    /// The image's offset vector coordinates
+    offset: Vector2D,
+
+    /// Whether the image's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// Whether the image's picture is mirrored when the layout direction is right-to-left
+    mirror_in_rtl: bool,
+}
+
+impl ImageWidget {
+    /// Creates a new `ImageWidget`
+    ///
+    /// # Returns
+    /// The image created
+    ///
+    /// # Arguments
+    /// * `content` - the picture to be assigned to the image, if any
+    /// * `size` - the size (width and height) to be assigned to the image
+    pub fn new(content: Option<ImageContent>, size: Vector2D) -> ImageWidget {
+        ImageWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            content,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            mirror_in_rtl: false,
+        }
+    }
+
+    /// Gets the image's picture
+    ///
+    /// # Returns
+    /// The picture displayed by the image, if any
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn content(&self) -> Option<&ImageContent> {
+        self.content.as_ref()
+    }
+
+    /// Sets the image's picture
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `content` - the picture to be assigned to the image, or `None` to display nothing
+    pub fn set_content<C: Into<ImageContent>>(&mut self, content: Option<C>) {
+        self.content = content.map(Into::into);
+        self.dirty = true;
+    }
+
+    /// Sets the image's picture, returning the image (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The image with the picture assigned
+    ///
+    /// # Arguments
+    /// * `content` - the picture to be assigned to the image (e.g. an `Rc<ImageData>`)
+    pub fn with_content<C: Into<ImageContent>>(mut self, content: C) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Sets the image's path of the image file, returning the image (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The image with the path of the image file assigned
+    ///
+    /// # Arguments
+    /// * `path` - the path of the image file to be assigned to the image
+    pub fn with_path(self, path: String) -> Self {
+        self.with_content(ImageSource::new(path))
+    }
+
+    /// Sets the image's pixels held in memory, returning the image (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The image with the pixels assigned
+    ///
+    /// # Arguments
+    /// * `image` - the pixels to be assigned to the image, shared with the caller
+    pub fn with_data(self, image: Rc<ImageData>) -> Self {
+        self.with_content(image)
+    }
+
+    /// Sets the image's size, returning the image (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The image with the size assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the image
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets whether the image's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets whether the image's picture is mirrored when the layout direction is
+    /// right-to-left
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `mirror_in_rtl` - boolean indicating if the picture is mirrored on right-to-left layouts
+    pub fn set_mirror_in_rtl(&mut self, mirror_in_rtl: bool) {
+        self.mirror_in_rtl = mirror_in_rtl;
+        self.dirty = true;
+    }
+}
+
+impl Default for ImageWidget {
+    fn default() -> Self {
+        ImageWidget::new(None, Vector2D::new(64., 64.))
+    }
+}
+
+impl Widget for ImageWidget {
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        match self.content.as_ref() {
+            // The file's image is drawn around its center, resized to the widget
+            Some(ImageContent::Source(source)) => vec![RenderInstruction::DrawImage {
+                point: self.position + self.size / 2.,
+                path: source.best_match(1.).1.to_string(),
+                density: 1.,
+                source: Some(source.clone()),
+                options: DrawImageOptions::Resize {
+                    width: self.size.x.round() as usize,
+                    height: self.size.y.round() as usize,
+                },
+                mirrored: false,
+                clip_point: self.position,
+                clip_size: self.size,
+            }],
+            Some(ImageContent::Data(image)) => vec![RenderInstruction::DrawImageData {
+                point: self.position,
+                size: self.size,
+                image: Rc::clone(image),
+                mirrored: false,
+                clip_point: self.position,
+                clip_size: self.size,
+            }],
+            None => vec![],
+        }
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, _cursor_pos: Vector2D) -> bool {
+        false
+    }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn mirror_in_rtl(&self) -> bool {
+        self.mirror_in_rtl
+    }
+}
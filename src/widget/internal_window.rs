@@ -0,0 +1,841 @@
+use crate::display::WindowEdge;
+use crate::event;
+use crate::event::Event;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Thickness of the border of the internal window, where dragging resizes it
+const RESIZE_BORDER: f64 = 4.;
+
+/// Height of the title bar, as a multiple of the font size
+const TITLE_BAR_HEIGHT: f64 = 2.;
+
+/// Button of the internal window's title bar, for internal use only
+#[derive(Clone, Copy, PartialEq)]
+enum InternalWindowButton {
+    /// Minimizes the internal window to the desktop's bar
+    Minimize,
+    /// Closes the internal window
+    Close,
+}
+
+/// Dragging of the internal window, for internal use only
+#[derive(Clone, Copy)]
+enum InternalWindowDrag {
+    /// The title bar is being dragged, moving the internal window
+    Move,
+    /// An edge or corner is being dragged, resizing the internal window
+    Resize(WindowEdge),
+}
+
+/// Internal window is a draggable and resizable window within the display, managed
+/// by a [`DesktopWidget`](`crate::widget::desktop::DesktopWidget`).
+///
+/// Dragging its title bar moves it and dragging its left, right or bottom edges
+/// resizes it, while the buttons of the title bar minimize it to the desktop's bar or
+/// close it, handling the `on_close` message. Its children fill the area below the
+/// title bar. The internal window's frame (i.e., its position and size) is relative to
+/// the top left corner of the desktop.
+#[derive(Clone)]
+pub struct InternalWindowWidget {
+    /// The internal window's identifier
+    id: usize,
+
+    /// The data attached to the internal window through its handle
+    widget_data: WidgetData,
+
+    /// The internal window's title
+    title: String,
+
+    /// The internal window's position and size, relative to the top left corner of the desktop
+    frame: Rect,
+
+    /// The internal window's minimum size, when resized
+    min_size: Vector2D,
+
+    /// Whether the internal window is minimized to the desktop's bar
+    is_minimized: bool,
+
+    /// Whether the internal window is closed
+    is_closed: bool,
+
+    /// Whether the internal window is the active one (i.e., over the other ones)
+    is_active: bool,
+
+    /// The dragging of the internal window, with the cursor's position and the frame
+    /// when it started
+    drag: Option<(InternalWindowDrag, Vector2D, Rect)>,
+
+    /// The button of the title bar pressed, if any
+    pressed_button: Option<InternalWindowButton>,
+
+    /// The button of the title bar under the cursor, if any
+    hovered_button: Option<InternalWindowButton>,
+
+    /// The message to be handled when the internal window is closed by its close button
+    on_close: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The internal window's font size, used on its title
+    font_size: usize,
+
+    /// The internal window's background color
+    background_color: Color,
+
+    /// The color of the title bar of the internal window, when active
+    title_bar_color: Color,
+
+    /// The color of the title and of the title bar's buttons
+    text_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The internal window's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The internal window's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The internal window's current size (width and height)
+    size: Vector2D,
+
+    /// The internal window's original size (width and height)
+    original_size: Vector2D,
+
+    /// The internal window's layout
+    layout: Layout,
+
+    /// The internal window's offset vector coordinates
+    offset: Vector2D,
+
+    /// The internal window's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl InternalWindowWidget {
+    /// Creates a new `InternalWindowWidget`
+    ///
+    /// # Returns
+    /// The internal window created
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the internal window
+    /// * `frame` - the position and size to be assigned to the internal window, relative to the desktop
+    /// * `font_size` - the font size to be assigned to the internal window's title
+    /// * `background_color` - the color to be assigned to the internal window's background
+    /// * `title_bar_color` - the color to be assigned to the internal window's title bar
+    /// * `text_color` - the color to be assigned to the internal window's title
+    pub fn new(
+        title: &str,
+        frame: Rect,
+        font_size: usize,
+        background_color: Color,
+        title_bar_color: Color,
+        text_color: Color,
+    ) -> InternalWindowWidget {
+        InternalWindowWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_selection_color(title_bar_color)
+                .with_foreground_color(text_color),
+            title: String::from(title),
+            frame,
+            min_size: Vector2D::new(120., font_size as f64 * TITLE_BAR_HEIGHT * 2.),
+            is_minimized: false,
+            is_closed: false,
+            is_active: false,
+            drag: None,
+            pressed_button: None,
+            hovered_button: None,
+            on_close: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            title_bar_color,
+            text_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size: frame.size,
+            original_size: frame.size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when the internal window is closed by its close button
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_close` - the message to be handled when the internal window is closed
+    pub fn set_message(&mut self, on_close: Option<Box<dyn Message>>) {
+        self.on_close = on_close;
+    }
+
+    /// Sets the internal window's title
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+        self.dirty = true;
+    }
+
+    /// Gets the internal window's title
+    ///
+    /// # Returns
+    /// The internal window's title
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Sets the internal window's position and size, relative to the top left corner of the desktop
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `frame` - the position and size to be assigned
+    pub fn set_frame(&mut self, frame: Rect) {
+        self.frame = frame;
+        self.set_dirty(true);
+    }
+
+    /// Gets the internal window's position and size, relative to the top left corner of the desktop
+    ///
+    /// # Returns
+    /// The internal window's position and size
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_frame(&self) -> Rect {
+        self.frame
+    }
+
+    /// Sets the internal window's minimum size, when resized
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `min_size` - the minimum size (width and height)
+    pub fn set_min_size(&mut self, min_size: Vector2D) {
+        self.min_size = min_size;
+    }
+
+    /// Minimizes the internal window to the desktop's bar
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn minimize(&mut self) {
+        self.is_minimized = true;
+        self.drag = None;
+    }
+
+    /// Restores the internal window from the desktop's bar
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn restore(&mut self) {
+        self.is_minimized = false;
+        self.set_dirty(true);
+    }
+
+    /// Checks if the internal window is minimized to the desktop's bar
+    ///
+    /// # Returns
+    /// True, if the internal window is minimized, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_minimized(&self) -> bool {
+        self.is_minimized
+    }
+
+    /// Closes the internal window, without handling the `on_close` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn close(&mut self) {
+        self.is_closed = true;
+        self.drag = None;
+    }
+
+    /// Opens the internal window again, after being closed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn open(&mut self) {
+        self.is_closed = false;
+        self.is_minimized = false;
+        self.set_dirty(true);
+    }
+
+    /// Checks if the internal window is closed
+    ///
+    /// # Returns
+    /// True, if the internal window is closed, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    /// Sets whether the internal window is the active one, which is displayed with
+    /// its title bar highlighted
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_active` - boolean indicating if the internal window is the active one
+    pub fn set_active(&mut self, is_active: bool) {
+        if is_active != self.is_active {
+            self.is_active = is_active;
+            self.dirty = true;
+        }
+    }
+
+    /// Checks if the internal window is the active one
+    ///
+    /// # Returns
+    /// True, if the internal window is the active one, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Gets the height of the title bar, for internal use only
+    ///
+    /// # Returns
+    /// The height of the title bar
+    ///
+    /// # Arguments
+    /// No arguments
+    fn title_bar_height(&self) -> f64 {
+        (self.font_size as f64 * TITLE_BAR_HEIGHT).min(self.size.y)
+    }
+
+    /// Gets the buttons of the title bar with their top left corners, for internal use only
+    ///
+    /// # Returns
+    /// The buttons of the title bar, from left to right, with their top left corners
+    ///
+    /// # Arguments
+    /// No arguments
+    fn buttons(&self) -> [(InternalWindowButton, Vector2D); 2] {
+        let side = self.title_bar_height();
+        let right = self.position.x + self.size.x;
+        [
+            (
+                InternalWindowButton::Minimize,
+                Vector2D::new(right - side * 2., self.position.y),
+            ),
+            (
+                InternalWindowButton::Close,
+                Vector2D::new(right - side, self.position.y),
+            ),
+        ]
+    }
+
+    /// Gets the button of the title bar under a position, for internal use only
+    ///
+    /// # Returns
+    /// The button under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn button_at(&self, position: Vector2D) -> Option<InternalWindowButton> {
+        let side = self.title_bar_height();
+        self.buttons()
+            .iter()
+            .find(|(_, point)| Rect::new(*point, Vector2D::new(side, side)).contains(position))
+            .map(|(button, _)| *button)
+    }
+
+    /// Gets the edge or corner under a position where dragging resizes the internal
+    /// window, for internal use only
+    ///
+    /// # Returns
+    /// The edge or corner under the position, or `None` if the position is not on the
+    /// left, right or bottom borders
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn edge_at(&self, position: Vector2D) -> Option<WindowEdge> {
+        let start = position - self.position;
+        let end = self.position + self.size - position;
+        if start.x < 0. || start.y < self.title_bar_height() || end.x < 0. || end.y < 0. {
+            return None;
+        }
+        match (
+            start.x <= RESIZE_BORDER,
+            end.x <= RESIZE_BORDER,
+            end.y <= RESIZE_BORDER,
+        ) {
+            (true, _, true) => Some(WindowEdge::BottomLeft),
+            (_, true, true) => Some(WindowEdge::BottomRight),
+            (true, _, _) => Some(WindowEdge::Left),
+            (_, true, _) => Some(WindowEdge::Right),
+            (_, _, true) => Some(WindowEdge::Bottom),
+            _ => None,
+        }
+    }
+
+    /// Drags the internal window, moving or resizing it, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `drag` - the dragging of the internal window
+    /// * `origin` - the cursor's position when the dragging started
+    /// * `frame` - the internal window's frame when the dragging started
+    fn drag_to(&mut self, drag: InternalWindowDrag, origin: Vector2D, frame: Rect) {
+        let moved = self.cursor_pos - origin;
+        let mut new_frame = frame;
+        match drag {
+            InternalWindowDrag::Move => {
+                new_frame.position = frame.position + moved;
+                // Keep the title bar reachable
+                new_frame.position.y = new_frame.position.y.max(0.);
+            }
+            InternalWindowDrag::Resize(edge) => {
+                if let WindowEdge::Left | WindowEdge::BottomLeft = edge {
+                    let width = (frame.size.x - moved.x).max(self.min_size.x);
+                    new_frame.position.x = frame.position.x + frame.size.x - width;
+                    new_frame.size.x = width;
+                }
+                if let WindowEdge::Right | WindowEdge::BottomRight = edge {
+                    new_frame.size.x = (frame.size.x + moved.x).max(self.min_size.x);
+                }
+                if let WindowEdge::Bottom | WindowEdge::BottomLeft | WindowEdge::BottomRight = edge
+                {
+                    new_frame.size.y = (frame.size.y + moved.y).max(self.min_size.y);
+                }
+            }
+        }
+        self.frame = new_frame;
+        self.set_dirty(true);
+    }
+
+    /// Gets the render instructions of a button's glyph, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the glyph
+    ///
+    /// # Arguments
+    /// * `button` - the button whose glyph is drawn
+    /// * `point` - the button's top left corner
+    fn glyph_recipe(
+        &self,
+        button: InternalWindowButton,
+        point: Vector2D,
+    ) -> Vec<RenderInstruction> {
+        let side = self.title_bar_height();
+        let glyph = (side * 0.3).round();
+        let start = point + (side - glyph) * 0.5;
+        let end = start + glyph;
+        let line = |point_a: Vector2D, point_b: Vector2D| RenderInstruction::DrawLine {
+            point_a,
+            point_b,
+            color: self.text_color,
+            clip_point: point,
+            clip_size: Vector2D::new(side, side),
+        };
+
+        match button {
+            InternalWindowButton::Minimize => {
+                let y = point.y + side * 0.5;
+                vec![line(Vector2D::new(start.x, y), Vector2D::new(end.x, y))]
+            }
+            InternalWindowButton::Close => vec![
+                line(start, end),
+                line(Vector2D::new(end.x, start.y), Vector2D::new(start.x, end.y)),
+            ],
+        }
+    }
+
+    /// Sets the internal window's title, returning the internal window (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The internal window with the title assigned
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the internal window
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = String::from(title);
+        self
+    }
+
+    /// Sets the internal window's frame, returning the internal window (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The internal window with the frame assigned
+    ///
+    /// # Arguments
+    /// * `frame` - the position and size to be assigned to the internal window, relative to the desktop
+    pub fn with_frame(mut self, frame: Rect) -> Self {
+        self.frame = frame;
+        self.size = frame.size;
+        self.original_size = frame.size;
+        self
+    }
+
+    /// Sets the internal window's message to be handled when it is closed, returning the internal window (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The internal window with the given message to be handled when it is closed
+    ///
+    /// # Arguments
+    /// * `on_close` - the message to be handled when the internal window is closed
+    pub fn with_on_close(mut self, on_close: Option<Box<dyn Message>>) -> Self {
+        self.on_close = on_close;
+        self
+    }
+
+    /// Sets the internal window's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the internal window
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the internal window's style, overriding the properties of the current theme,
+    /// returning the internal window (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The internal window with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the internal window
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for InternalWindowWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        InternalWindowWidget {
+            style: Style::new(),
+            ..InternalWindowWidget::new(
+                "",
+                Rect::new(Vector2D::new(0., 0.), Vector2D::new(320., 240.)),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.selection,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for InternalWindowWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                if let Some((drag, origin, frame)) = self.drag {
+                    self.drag_to(drag, origin, frame);
+                }
+                let hovered_button = self.button_at(self.cursor_pos);
+                if hovered_button != self.hovered_button {
+                    self.hovered_button = hovered_button;
+                    self.dirty = true;
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                if let Some(button) = self.button_at(self.cursor_pos) {
+                    self.pressed_button = Some(button);
+                    return EventStatus::Captured;
+                }
+                let drag = if self.cursor_pos.y <= self.position.y + self.title_bar_height() {
+                    Some(InternalWindowDrag::Move)
+                } else {
+                    self.edge_at(self.cursor_pos)
+                        .map(InternalWindowDrag::Resize)
+                };
+                if let Some(drag) = drag {
+                    self.drag = Some((drag, self.cursor_pos, self.frame));
+                    // The press started a drag, so it does not reach the children
+                    return EventStatus::Captured;
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                self.drag = None;
+                if let Some(button) = self.pressed_button.take() {
+                    // The buttons act when released over the same button
+                    if self.button_at(self.cursor_pos) == Some(button) {
+                        match button {
+                            InternalWindowButton::Minimize => self.minimize(),
+                            InternalWindowButton::Close => {
+                                self.close();
+                                if let Some(mut message) = self.on_close.clone() {
+                                    message.set_event(event);
+                                    messages.enqueue(message);
+                                }
+                            }
+                        }
+                    }
+                    return EventStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let title_bar_height = self.title_bar_height();
+        let title_bar_color = if self.is_active {
+            self.title_bar_color
+        } else {
+            self.title_bar_color.desaturate(0.8)
+        };
+        let mut instructions = vec![
+            // Border, with the title bar's color
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: self.size,
+                color: title_bar_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            RenderInstruction::DrawRect {
+                point: Vector2D::new(self.position.x + 1., self.position.y + title_bar_height),
+                size: Vector2D::new(
+                    (self.size.x - 2.).max(0.),
+                    (self.size.y - title_bar_height - 1.).max(0.),
+                ),
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Title, vertically centered and clipped before the buttons
+        let padding = (title_bar_height - self.font_size as f64) * 0.5;
+        instructions.push(RenderInstruction::DrawText {
+            point: Vector2D::new(
+                self.position.x + padding,
+                self.position.y + title_bar_height - padding,
+            ),
+            font_size: self.font_size,
+            string: self.title.clone(),
+            color: self.text_color,
+            clip_point: self.position,
+            clip_size: Vector2D::new(
+                (self.size.x - title_bar_height * 2.).max(0.),
+                title_bar_height,
+            ),
+            font: FontDescriptor::default(),
+        });
+
+        for (button, point) in self.buttons().iter() {
+            if self.hovered_button == Some(*button) {
+                let color = if *button == InternalWindowButton::Close {
+                    Color::from_hex(0xffe81123)
+                } else {
+                    self.text_color.with_opacity(0.1)
+                };
+                instructions.push(RenderInstruction::DrawRect {
+                    point: *point,
+                    size: Vector2D::new(title_bar_height, title_bar_height),
+                    color,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            instructions.append(&mut self.glyph_recipe(*button, *point));
+        }
+
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        // The children fill the area below the title bar, inside the border
+        let title_bar_height = self.title_bar_height();
+        let content = Rect::new(
+            Vector2D::new(area.position.x + 1., area.position.y + title_bar_height),
+            Vector2D::new(
+                (area.size.x - 2.).max(0.),
+                (area.size.y - title_bar_height - 1.).max(0.),
+            ),
+        );
+        vec![Some(content); self.children.len()]
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.title_bar_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,557 @@
+use crate::event;
+use crate::event::{Event, ModifiersState};
+use crate::key_code::KeyCode;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget, WidgetData,
+};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Average width of a glyph, as a fraction of the font size, used to size the hints
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Keyboard hints is a widget that wraps the user interface and, when its shortcut is
+/// pressed, displays a small numbered hint over each widget that can be activated from
+/// the keyboard (i.e., [`is_focusable`](`Widget::is_focusable`) and not disabled).
+///
+/// While the hints are displayed, typing a hint's number activates its widget, as if it
+/// was clicked. The number is activated as soon as no other hint starts with it, or when
+/// enter is pressed. Escape, the shortcut or a click hides the hints. While hidden, every
+/// event is forwarded to its children, laid out as a vertical list, except for the shortcut.
+#[derive(Clone)]
+pub struct KeyboardHintsWidget {
+    /// The keyboard hints' identifier
+    id: usize,
+
+    /// The data attached to the keyboard hints through its handle
+    widget_data: WidgetData,
+
+    /// The shortcut that displays and hides the hints
+    shortcut: (ModifiersState, KeyCode),
+
+    /// Whether the hints are displayed
+    is_active: bool,
+
+    /// The digits typed so far
+    typed: String,
+
+    /// The widgets hinted, along with the position of their hint, numbered from 1
+    targets: Vec<(Weak<RefCell<dyn Widget>>, Vector2D)>,
+
+    /// The keyboard hints' font size
+    font_size: usize,
+
+    /// The hints' background color
+    hint_color: Color,
+
+    /// The hints' text color
+    text_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The keyboard hints' children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The keyboard hints' position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The keyboard hints' current size (width and height)
+    size: Vector2D,
+
+    /// The keyboard hints' original size (width and height)
+    original_size: Vector2D,
+
+    /// The keyboard hints' layout
+    layout: Layout,
+
+    /// The keyboard hints' offset vector coordinates
+    offset: Vector2D,
+
+    /// The keyboard hints' style, overriding the properties of the theme
+    style: Style,
+}
+
+impl KeyboardHintsWidget {
+    /// Creates a new `KeyboardHintsWidget`, toggled by `Ctrl+Shift+H`
+    ///
+    /// # Returns
+    /// The keyboard hints created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the keyboard hints
+    /// * `font_size` - the font size to be assigned to the hints
+    /// * `hint_color` - the color to be assigned to the hints' background
+    /// * `text_color` - the color to be assigned to the hints' text
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        hint_color: Color,
+        text_color: Color,
+    ) -> KeyboardHintsWidget {
+        KeyboardHintsWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_selection_color(hint_color)
+                .with_foreground_color(text_color),
+            shortcut: (
+                ModifiersState {
+                    shift: true,
+                    control: true,
+                    alt: false,
+                    logo: false,
+                },
+                KeyCode::H,
+            ),
+            is_active: false,
+            typed: String::new(),
+            targets: Vec::new(),
+            font_size,
+            hint_color,
+            text_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Box(Axis::Vertical),
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the shortcut that displays and hides the hints
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `modifiers` - the modifiers of the shortcut
+    /// * `key_code` - the key of the shortcut
+    pub fn set_shortcut(&mut self, modifiers: ModifiersState, key_code: KeyCode) {
+        self.shortcut = (modifiers, key_code);
+    }
+
+    /// Displays a hint over each widget that can be activated from the keyboard
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn show_hints(&mut self) {
+        self.targets.clear();
+        for value in self.children.clone().iter() {
+            if let Some(child) = value.upgrade() {
+                self.collect_targets(&child);
+            }
+        }
+        self.typed.clear();
+        self.is_active = true;
+        self.dirty = true;
+    }
+
+    /// Hides the hints
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn hide_hints(&mut self) {
+        self.targets.clear();
+        self.typed.clear();
+        self.is_active = false;
+        self.dirty = true;
+    }
+
+    /// Checks if the hints are displayed
+    ///
+    /// # Returns
+    /// True, if the hints are displayed, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    /// Gets the number of widgets hinted
+    ///
+    /// # Returns
+    /// The number of widgets hinted, or 0 if the hints are hidden
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_hint_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Adds the widgets that can be activated from the keyboard, along with their
+    /// descendants, to the widgets hinted, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `widget` - the widget to be checked along with its descendants
+    fn collect_targets(&mut self, widget: &Rc<RefCell<dyn Widget>>) {
+        let (position, size) = {
+            let mut widget = widget.borrow_mut();
+            (widget.position(), widget.size())
+        };
+        let is_visible = size.x > 0.
+            && size.y > 0.
+            && position.x < self.position.x + self.size.x
+            && position.y < self.position.y + self.size.y
+            && position.x + size.x > self.position.x
+            && position.y + size.y > self.position.y;
+        let is_target = {
+            let widget = widget.borrow();
+            widget.is_focusable() && !widget.is_disabled()
+        };
+        if is_visible && is_target {
+            let point = Vector2D::new(
+                position.x.max(self.position.x),
+                position.y.max(self.position.y),
+            );
+            self.targets.push((Rc::downgrade(widget), point));
+        }
+        let children = widget.borrow_mut().get_children().clone();
+        for value in children.iter() {
+            if let Some(child) = value.upgrade() {
+                self.collect_targets(&child);
+            }
+        }
+    }
+
+    /// Counts the hints starting with the digits typed, for internal use only
+    ///
+    /// # Returns
+    /// The number of hints starting with the digits typed
+    ///
+    /// # Arguments
+    /// * `typed` - the digits typed
+    fn count_candidates(&self, typed: &str) -> usize {
+        (1..=self.targets.len())
+            .filter(|number| number.to_string().starts_with(typed))
+            .count()
+    }
+
+    /// Activates the widget whose hint is the number typed, if any, and hides the
+    /// hints, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the keyboard event that activated the widget
+    /// * `messages` - queue of messages
+    fn activate_typed(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        let target = self
+            .typed
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .and_then(|index| self.targets.get(index))
+            .and_then(|(value, _)| value.upgrade());
+        self.hide_hints();
+        if let Some(widget) = target {
+            widget.borrow_mut().activate(event, messages);
+        }
+    }
+
+    /// Sets the keyboard hints' size (width and height), returning the keyboard hints (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The keyboard hints with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the keyboard hints
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the keyboard hints' shortcut, returning the keyboard hints (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The keyboard hints with the shortcut assigned
+    ///
+    /// # Arguments
+    /// * `modifiers` - the modifiers of the shortcut
+    /// * `key_code` - the key of the shortcut
+    pub fn with_shortcut(mut self, modifiers: ModifiersState, key_code: KeyCode) -> Self {
+        self.set_shortcut(modifiers, key_code);
+        self
+    }
+
+    /// Sets the keyboard hints' style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the keyboard hints
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the keyboard hints' style, overriding the properties of the current theme,
+    /// returning the keyboard hints (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The keyboard hints with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the keyboard hints
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for KeyboardHintsWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        KeyboardHintsWidget {
+            style: Style::new(),
+            ..KeyboardHintsWidget::new(
+                Vector2D::new(100., 100.),
+                theme.font_size,
+                theme.palette.selection,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for KeyboardHintsWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        if self.is_active {
+            match event {
+                event::Event::Keyboard(event::Keyboard::KeyPressed {
+                    key_code,
+                    modifiers,
+                }) if key_code == self.shortcut.1 && modifiers.matches(self.shortcut.0) => {
+                    self.hide_hints();
+                }
+                event::Event::Keyboard(event::Keyboard::KeyPressed { key_code, .. }) => {
+                    match key_code {
+                        KeyCode::Escape => self.hide_hints(),
+                        KeyCode::Enter | KeyCode::NumpadEnter => {
+                            self.activate_typed(event, messages)
+                        }
+                        KeyCode::Backspace if !self.typed.is_empty() => {
+                            self.typed.pop();
+                            self.dirty = true;
+                        }
+                        _ => {}
+                    }
+                }
+                event::Event::Keyboard(event::Keyboard::CharacterReceived(character))
+                    if character.is_ascii_digit() =>
+                {
+                    let mut typed = self.typed.clone();
+                    typed.push(character);
+                    // Digits which no hint starts with are ignored
+                    let candidates = self.count_candidates(&typed);
+                    if candidates > 0 {
+                        self.typed = typed;
+                        self.dirty = true;
+                        if candidates == 1 {
+                            self.activate_typed(event, messages);
+                        }
+                    }
+                }
+                event::Event::Mouse(event::Mouse::ButtonPressed(_)) => self.hide_hints(),
+                _ => {}
+            }
+            // The hints are modal, so only the cursor movements reach the children
+            if !matches!(event, event::Event::Mouse(event::Mouse::CursorMoved { .. })) {
+                return EventStatus::Captured;
+            }
+        } else if let event::Event::Keyboard(event::Keyboard::KeyPressed {
+            key_code,
+            modifiers,
+        }) = event
+        {
+            if key_code == self.shortcut.1 && modifiers.matches(self.shortcut.0) {
+                self.show_hints();
+                return EventStatus::Captured;
+            }
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        let font_size = self.font_size as f64;
+        let mut instructions = Vec::new();
+        for (index, (_, point)) in self.targets.iter().enumerate() {
+            let hint = (index + 1).to_string();
+            if !hint.starts_with(&self.typed) {
+                continue;
+            }
+            let size = Vector2D::new(
+                hint.len() as f64 * font_size * GLYPH_WIDTH + 6.,
+                font_size * 1.4,
+            );
+            instructions.push(RenderInstruction::DrawRect {
+                point: *point,
+                size,
+                color: self.hint_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(point.x + 3., point.y + (size.y + font_size) * 0.5 - 1.),
+                font_size: self.font_size,
+                string: hint,
+                color: self.text_color,
+                clip_point: *point,
+                clip_size: size,
+                font: FontDescriptor::default(),
+            });
+        }
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.hint_color = self.style.selection_color.unwrap_or(theme.palette.selection);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
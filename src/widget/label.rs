@@ -1,10 +1,46 @@
+use crate::clock;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text::{self, TextAlignment, TextLayout};
+use crate::util::{Color, MemoKey, Queue, Vector2D};
+use crate::widget::{EventStatus, Flex, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
-use std::rc::Weak;
+use std::rc::{Rc, Weak};
+use std::time::Instant;
+
+/// Character appended to the text truncated by [`TextOverflow::Ellipsis`]
+const ELLIPSIS: char = '\u{2026}';
+
+/// Maximum width of the fade drawn by [`TextOverflow::Fade`]
+const FADE_WIDTH: f64 = 24.;
+
+/// Number of steps of the fade drawn by [`TextOverflow::Fade`]
+const FADE_STEPS: usize = 8;
+
+/// Speed of the text scrolled by [`TextOverflow::Marquee`], in pixels per second
+const MARQUEE_SPEED: f64 = 40.;
+
+/// Gap between the copies of the text scrolled by [`TextOverflow::Marquee`], relative to the font size
+const MARQUEE_GAP: f64 = 2.;
+
+/// Callback measuring the width of a text with the given font size
+pub type TextMeasure = Rc<dyn Fn(&str, usize) -> f64>;
+
+/// How a text wider than its widget is displayed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextOverflow {
+    /// The text is cut at the widget's edge
+    #[default]
+    Clip,
+    /// The text is truncated, ending with an ellipsis (i.e., …)
+    Ellipsis,
+    /// The end of the text fades into the background
+    Fade,
+    /// The text scrolls continuously, as a marquee
+    Marquee,
+}
 
 /// Label is a widget that displays a short text string. Does not react to input events. 
 /// As a result, it cannot get the keyboard focus. A label can, however, display a keyboard
@@ -14,12 +50,18 @@ use std::rc::Weak;
 pub struct LabelWidget {
     /// The label's identifier
     id: usize,
+
+    /// The data attached to the label through its handle
+    widget_data: WidgetData,
     
     /// The label's text
     text: String,
     
     /// The label's font size
     font_size: usize,
+
+    /// The label's font (i.e., family, weight and style)
+    font: FontDescriptor,
     
     /// The label's background color
     background_color: Color,
@@ -54,6 +96,32 @@ pub struct LabelWidget {
 
     /// TODO: documentar
     clip_size: Option<Vector2D>,
+
+    /// Whether the label's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// How the label's text is displayed when wider than the label
+    text_overflow: TextOverflow,
+
+    /// The callback measuring the label's text, or `None` to measure it with the
+    /// metrics of the current thread
+    text_measure: Option<TextMeasure>,
+
+    /// The instant when the label's text started scrolling, with [`TextOverflow::Marquee`]
+    marquee_start: Instant,
+
+    /// The label's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
+
+    /// How the label's text is broken into lines and aligned
+    text_layout: TextLayout,
+
+    /// Whether the label's original size is the size of its text
+    is_auto_sized: bool,
+
+    /// The label's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl LabelWidget {
@@ -77,8 +145,14 @@ impl LabelWidget {
     ) -> LabelWidget {
         LabelWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(foreground_color),
             text: text,
             font_size: font_size,
+            font: FontDescriptor::default(),
             background_color: background_color,
             foreground_color: foreground_color,
             dirty: true,
@@ -90,6 +164,13 @@ impl LabelWidget {
             offset: Vector2D::new(0., 0.),
             clip_point: None,
             clip_size: None,
+            pixel_snapping: None,
+            text_overflow: TextOverflow::Clip,
+            text_measure: None,
+            marquee_start: clock::now(),
+            flex: Flex::default(),
+            text_layout: TextLayout::default(),
+            is_auto_sized: false,
         }
     }
 
@@ -102,12 +183,395 @@ impl LabelWidget {
     /// * `text` - the text to be assigned to the label
     pub fn set_text(&mut self, text: String) {
         self.text = text;
+        self.marquee_start = clock::now();
+        self.dirty = true;
+    }
+
+    /// Sets how the label's text is displayed when wider than the label
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `text_overflow` - the overflow mode to be assigned to the label's text
+    pub fn set_text_overflow(&mut self, text_overflow: TextOverflow) {
+        self.text_overflow = text_overflow;
+        self.marquee_start = clock::now();
+        self.dirty = true;
+    }
+
+    /// Sets the callback measuring the label's text, used to detect the overflow and
+    /// to find where the text is truncated
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `text_measure` - the callback measuring the text, or `None` to measure it with the metrics of the current thread
+    pub fn set_text_measure(&mut self, text_measure: Option<TextMeasure>) {
+        self.text_measure = text_measure;
+        self.dirty = true;
+    }
+
+    /// Gets the width of a text displayed with the label's font size, for internal use only
+    ///
+    /// # Returns
+    /// The width measured by the label's callback, or by the metrics of the current
+    /// thread (see [`text::measure_text`])
+    ///
+    /// # Arguments
+    /// * `text` - the text to be measured
+    fn text_width(&self, text: &str) -> f64 {
+        match &self.text_measure {
+            Some(text_measure) => text_measure(text, self.font_size),
+            None => text::measure_text(text, self.font_size).x,
+        }
+    }
+
+    /// Gets the size of the label's text (i.e., the size of the label fitting its text)
+    ///
+    /// # Returns
+    /// The width of the widest line of the text and the height of its lines
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn intrinsic_size(&self) -> Vector2D {
+        let lines = self.text.split('\n');
+        let line_count = lines.clone().count();
+        let width = lines.map(|line| self.text_width(line)).fold(0., f64::max);
+        let height = text::measure_text(&self.text, self.font_size).y
+            + (line_count - 1) as f64 * self.text_layout.line_height(self.font_size);
+        Vector2D::new(width, height)
+    }
+
+    /// Sets whether the label's original size is the size of its text, such that the
+    /// label grows and shrinks with its text
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_auto_sized` - boolean indicating if the label is sized to its text
+    pub fn set_auto_size(&mut self, is_auto_sized: bool) {
+        self.is_auto_sized = is_auto_sized;
+        self.dirty = true;
+    }
+
+    /// Checks whether the label's text is wider than the label
+    ///
+    /// # Returns
+    /// True, if any line of the label's text overflows, false otherwise (e.g. if the
+    /// text is wrapped)
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_overflowing(&self) -> bool {
+        !self.text_layout.is_wrapping()
+            && self.text.split('\n').any(|line| self.text_width(line) > self.size.x)
+    }
+
+    /// Sets whether the label's text is wrapped on multiple lines when wider than the label
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_wrapping` - boolean indicating if the text is wrapped
+    pub fn set_wrap(&mut self, is_wrapping: bool) {
+        self.text_layout = self.text_layout.with_wrap(is_wrapping);
+        self.dirty = true;
+    }
+
+    /// Sets how the lines of the label's text are aligned
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment to be assigned to the label's text
+    pub fn set_alignment(&mut self, alignment: TextAlignment) {
+        self.text_layout = self.text_layout.with_alignment(alignment);
+        self.dirty = true;
+    }
+
+    /// Sets the distance between the baselines of consecutive lines of the label's text
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `line_spacing` - the distance between the baselines, as a multiple of the font size
+    pub fn set_line_spacing(&mut self, line_spacing: f64) {
+        self.text_layout = self.text_layout.with_line_spacing(line_spacing);
+        self.dirty = true;
+    }
+
+    /// Checks whether the label's text is scrolling as a marquee, for internal use only
+    ///
+    /// # Returns
+    /// True, if the text overflows with [`TextOverflow::Marquee`], false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_scrolling(&self) -> bool {
+        self.text_overflow == TextOverflow::Marquee && self.is_overflowing()
+    }
+
+    /// Gets the longest start of the label's text that fits the label with an
+    /// ellipsis appended, for internal use only
+    ///
+    /// # Returns
+    /// The text truncated, ending with an ellipsis
+    ///
+    /// # Arguments
+    /// No arguments
+    fn truncated_text(&self) -> String {
+        let chars: Vec<char> = self.text.chars().collect();
+        for count in (0..chars.len()).rev() {
+            let mut text: String = chars[..count].iter().collect();
+            text.push(ELLIPSIS);
+            if self.text_width(&text) <= self.size.x {
+                return text;
+            }
+        }
+        String::new()
+    }
+
+    /// Sets how the label's text is displayed when wider than the label, returning
+    /// the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the overflow mode assigned
+    ///
+    /// # Arguments
+    /// * `text_overflow` - the overflow mode to be assigned to the label's text
+    pub fn with_text_overflow(mut self, text_overflow: TextOverflow) -> Self {
+        self.text_overflow = text_overflow;
+        self
+    }
+
+    /// Sets whether the label's text is wrapped, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with whether the text is wrapped assigned
+    ///
+    /// # Arguments
+    /// * `is_wrapping` - boolean indicating if the text is wrapped
+    pub fn with_wrap(mut self, is_wrapping: bool) -> Self {
+        self.text_layout = self.text_layout.with_wrap(is_wrapping);
+        self
+    }
+
+    /// Sets the alignment of the label's text, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the alignment assigned
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment to be assigned to the label's text
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.text_layout = self.text_layout.with_alignment(alignment);
+        self
+    }
+
+    /// Sets whether the label is sized to its text, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with whether it is sized to its text assigned
+    ///
+    /// # Arguments
+    /// * `is_auto_sized` - boolean indicating if the label is sized to its text
+    pub fn with_auto_size(mut self, is_auto_sized: bool) -> Self {
+        self.is_auto_sized = is_auto_sized;
+        self
+    }
+
+    /// Sets the callback measuring the label's text, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the callback assigned
+    ///
+    /// # Arguments
+    /// * `text_measure` - the callback measuring the text
+    pub fn with_text_measure(mut self, text_measure: TextMeasure) -> Self {
+        self.text_measure = Some(text_measure);
+        self
+    }
+
+    /// Sets whether the label's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the label's text, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the text assigned
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the label
+    pub fn with_text(mut self, text: String) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Sets the label's size (width and height), returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the label
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the label's font size, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size to be assigned to the label
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.style.font_size = Some(font_size);
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the label's font (e.g. a family other than the renderer's default one, or
+    /// its bold and italic variants)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the label's text
+    pub fn set_font(&mut self, font: FontDescriptor) {
+        self.font = font;
         self.dirty = true;
     }
+
+    /// Sets the label's font, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the font assigned
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the label's text
+    pub fn with_font(mut self, font: FontDescriptor) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the label's background color, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the label
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the label's foreground color, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the foreground color assigned
+    ///
+    /// # Arguments
+    /// * `foreground_color` - the foreground color to be assigned to the label
+    pub fn with_foreground_color(mut self, foreground_color: Color) -> Self {
+        self.style.foreground_color = Some(foreground_color);
+        self.foreground_color = foreground_color;
+        self
+    }
+
+    /// Sets how the label is sized by a flex layout
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `flex` - the label's flex properties
+    pub fn set_flex(&mut self, flex: Flex) {
+        self.flex = flex;
+        self.dirty = true;
+    }
+
+    /// Sets how the label is sized by a flex layout, returning the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the label
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    /// Sets the label's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the label
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the label's style, overriding the properties of the current theme, returning
+    /// the label (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The label with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the label
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for LabelWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        LabelWidget {
+            style: Style::new(),
+            ..LabelWidget::new(
+                String::new(),
+                Vector2D::new(100., 30.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+            )
+        }
+    }
 }
 
 impl Widget for LabelWidget {
-    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) {}
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
 
     fn set_id(&mut self, id: usize) {
         self.id = id;
@@ -117,11 +581,15 @@ impl Widget for LabelWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         let clip_point = if let Some(clip_point) = self.clip_point {clip_point} else {self.position};
         let clip_size = if let Some(clip_size) = self.clip_size {clip_size} else {self.size};
 
-        vec![
+        let mut instructions = vec![
             // Label rectangle.
             RenderInstruction::DrawRect {
                 point: self.position,
@@ -129,17 +597,104 @@ impl Widget for LabelWidget {
                 size: self.size,
                 clip_point: clip_point,
                 clip_size: clip_size,
+                blend_mode: BlendMode::Normal,
             },
-            // Label Text
-            RenderInstruction::DrawText {
-                point: Vector2D::new(self.position.x, self.position.y + self.size.y),
-                color: self.foreground_color,
-                font_size: self.font_size,
-                string: self.text.clone(),
-                clip_point: clip_point,
-                clip_size: clip_size,
-            },
-        ]
+        ];
+
+        // Label Text, adjusted if it overflows
+        let text = |x: f64, string: String| RenderInstruction::DrawText {
+            point: Vector2D::new(x, self.position.y + self.size.y),
+            color: self.foreground_color,
+            font_size: self.font_size,
+            string,
+            clip_point,
+            clip_size,
+            font: self.font.clone(),
+        };
+        if !self.is_overflowing() {
+            let runs = self.text_layout.layout(&self.text, self.font_size, self.size.x, &|text| {
+                self.text_width(text)
+            });
+            // A single line keeps its baseline at the bottom of the label, while several
+            // lines start from the top
+            let baseline = if runs.iter().all(|run| run.point.y == 0.) {
+                self.position.y + self.size.y
+            } else {
+                self.position.y + self.font_size as f64
+            };
+            for run in runs {
+                instructions.push(RenderInstruction::DrawText {
+                    point: Vector2D::new(self.position.x + run.point.x, baseline + run.point.y),
+                    color: self.foreground_color,
+                    font_size: self.font_size,
+                    string: run.text,
+                    clip_point,
+                    clip_size,
+                    font: self.font.clone(),
+                });
+            }
+            return instructions;
+        }
+        match self.text_overflow {
+            TextOverflow::Clip => instructions.push(text(self.position.x, self.text.clone())),
+            TextOverflow::Ellipsis => instructions.push(text(self.position.x, self.truncated_text())),
+            TextOverflow::Fade => {
+                instructions.push(text(self.position.x, self.text.clone()));
+                // Cover the end of the text with increasingly opaque steps of the background
+                let width = FADE_WIDTH.min(self.size.x * 0.25);
+                let step = width / FADE_STEPS as f64;
+                for index in 0..FADE_STEPS {
+                    let opacity = (index + 1) as f64 / FADE_STEPS as f64;
+                    instructions.push(RenderInstruction::DrawRect {
+                        point: Vector2D::new(
+                            self.position.x + self.size.x - width + index as f64 * step,
+                            self.position.y,
+                        ),
+                        color: self.background_color.with_opacity(opacity),
+                        size: Vector2D::new(step, self.size.y),
+                        clip_point,
+                        clip_size,
+                        blend_mode: BlendMode::Normal,
+                    });
+                }
+            }
+            TextOverflow::Marquee => {
+                // Scroll two copies of the text, such that the second one follows the first
+                let period = self.text_width(&self.text) + self.font_size as f64 * MARQUEE_GAP;
+                let shift = (clock::elapsed(self.marquee_start).as_secs_f64() * MARQUEE_SPEED) % period;
+                instructions.push(text(self.position.x - shift, self.text.clone()));
+                instructions.push(text(self.position.x - shift + period, self.text.clone()));
+            }
+        }
+        instructions
+    }
+
+    fn memo_key(&self) -> Option<u64> {
+        // The scrolling text changes on every frame
+        if self.is_scrolling() {
+            return None;
+        }
+        let mut key = MemoKey::new()
+            .with(&self.text)
+            .with(&self.font_size)
+            .with(&self.font)
+            .with_color(self.background_color)
+            .with_color(self.foreground_color)
+            .with(&self.pixel_snapping)
+            .with(&self.text_overflow)
+            .with(&self.text_layout.alignment())
+            .with(&self.text_layout.is_wrapping())
+            .with_f64(self.text_layout.line_height(1))
+            .with(&self.text_measure.as_ref().map(|text_measure| {
+                Rc::as_ptr(text_measure) as *const () as usize
+            }));
+        for clip in [self.clip_point, self.clip_size].iter() {
+            key = match clip {
+                Some(clip) => key.with(&true).with_vector(*clip),
+                None => key.with(&false),
+            };
+        }
+        Some(key.finish())
     }
 
     fn set_dirty(&mut self, value: bool) {
@@ -147,7 +702,8 @@ impl Widget for LabelWidget {
     }
 
     fn is_dirty(&self) -> bool {
-        self.dirty
+        // The scrolling text needs to be redrawn on every frame
+        self.dirty || self.is_scrolling()
     }
 
     fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
@@ -167,13 +723,21 @@ impl Widget for LabelWidget {
     }
 
     fn original_size(&mut self) -> Vector2D {
-        self.original_size
+        if self.is_auto_sized {
+            self.intrinsic_size()
+        } else {
+            self.original_size
+        }
     }
 
     fn layout(&mut self) -> &Layout {
         &self.layout
     }
 
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
     fn offset(&mut self) -> Vector2D {
         self.offset
     }
@@ -189,12 +753,13 @@ impl Widget for LabelWidget {
         &Layout,
         Vector2D,
     ) {
+        let original_size = self.original_size();
         (
             self.dirty,
             &mut self.children,
             self.position,
             self.size,
-            self.original_size,
+            original_size,
             &self.layout,
             self.offset,
         )
@@ -229,4 +794,15 @@ impl Widget for LabelWidget {
     fn set_clip_size(&mut self, clip_size: Option<Vector2D>) {
         self.clip_size = clip_size;
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.dirty = true;
+    }
 }
@@ -1,16 +1,24 @@
 use crate::event::Event;
 use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Queue, Vector2D};
-use crate::widget::{Axis, Layout, Widget};
+use crate::selection::RubberBandSelection;
+use crate::util::{Queue, Rect, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Flex, Layout, Widget, WidgetData,
+};
 
 use std::cell::RefCell;
 use std::rc::Weak;
 
 /// List is a widget that displays multiple widgets in one column.
+///
+/// Its children can be bound to a list of items with an [`ItemsSource`](`crate::template::ItemsSource`).
 #[derive(Clone)]
 pub struct ListViewWidget {
     /// The list's identifier
     id: usize,
+
+    /// The data attached to the list through its handle
+    widget_data: WidgetData,
     
     /// The list's current size (width and height)
     size: Vector2D,
@@ -26,6 +34,16 @@ pub struct ListViewWidget {
     
     /// The list's children (i.e., his widgets tree)
     children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The list's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    /// relative to the top left corner
+    position: Vector2D,
+
+    /// The list's rubber band selection of its children, if enabled
+    selection: Option<RubberBandSelection>,
+
+    /// The list view's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
 }
 
 impl ListViewWidget {
@@ -40,22 +58,113 @@ impl ListViewWidget {
     pub fn new(size: Vector2D, axis: Axis) -> ListViewWidget {
         ListViewWidget {
             id: 0,
+            widget_data: WidgetData::default(),
             size: size,
             original_size: size,
             layout: Layout::Box(axis),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            selection: None,
+            flex: Flex::default(),
         }
     }
+
+    /// Sets the list view's size (width and height), returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the list view
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the list view's axis direction, returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the axis direction assigned
+    ///
+    /// # Arguments
+    /// * `axis` - the axis direction to be assigned to the list view
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.layout = Layout::Box(axis);
+        self
+    }
+
+    /// Sets the list view's rubber band selection, allowing to select its children by
+    /// dragging a rectangle from its empty space
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selection` - the rubber band selection, or `None` to disable it
+    pub fn set_rubber_band_selection(&mut self, selection: Option<RubberBandSelection>) {
+        self.selection = selection;
+        self.dirty = true;
+    }
+
+    /// Sets the list view's rubber band selection, returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the rubber band selection assigned
+    ///
+    /// # Arguments
+    /// * `selection` - the rubber band selection to be assigned to the list view
+    pub fn with_rubber_band_selection(mut self, selection: RubberBandSelection) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
+    /// Sets how the list view is sized by a flex layout
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `flex` - the list view's flex properties
+    pub fn set_flex(&mut self, flex: Flex) {
+        self.flex = flex;
+        self.dirty = true;
+    }
+
+    /// Sets how the list view is sized by a flex layout, returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the list view
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+}
+
+impl Default for ListViewWidget {
+    fn default() -> Self {
+        ListViewWidget::new(
+            Vector2D::new(100., 100.),
+            Axis::Vertical,
+        )
+    }
 }
 
 impl Widget for ListViewWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
-        for value in self.children.iter_mut() {
-            if let Some(child) = value.upgrade() {
-                child.borrow_mut().on_event(event, messages);
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let status = propagate_event(&self.children, event, messages);
+        if let Some(selection) = self.selection.as_mut() {
+            let area = Rect::new(self.position, self.size);
+            // Only the overlay is redrawn, so the children are not marked dirty
+            if selection.on_event(event, area, &self.children, messages) {
+                self.dirty = true;
             }
         }
+        status
     }
 
     fn set_id(&mut self, id: usize) {
@@ -66,13 +175,28 @@ impl Widget for ListViewWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         vec![]
     }
 
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        match &self.selection {
+            Some(selection) => selection.overlay_recipe(Rect::new(self.position, self.size)),
+            None => vec![],
+        }
+    }
+
     fn set_dirty(&mut self, value: bool) {
         if value {
             self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
             for value in self.get_children() {
                 if let Some(child) = value.upgrade() {
                     if child.borrow_mut().is_dirty() {
@@ -99,7 +223,7 @@ impl Widget for ListViewWidget {
     }
 
     fn position(&mut self) -> Vector2D {
-        Vector2D::new(0., 0.)
+        self.position
     }
 
     fn size(&mut self) -> Vector2D {
@@ -115,6 +239,10 @@ impl Widget for ListViewWidget {
         &self.layout
     }
 
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
     fn offset(&mut self) -> Vector2D {
         Vector2D::new(0., 0.)
     }
@@ -133,7 +261,7 @@ impl Widget for ListViewWidget {
         (
             self.dirty,
             &mut self.children,
-            Vector2D::new(0., 0.),
+            self.position,
             self.size,
             self.original_size,
             &self.layout,
@@ -141,7 +269,9 @@ impl Widget for ListViewWidget {
         )
     }
 
-    fn set_position(&mut self, _position: Vector2D) {}
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
 
     fn set_size(&mut self, size: Vector2D) {
         self.set_dirty(true);
@@ -155,13 +285,9 @@ impl Widget for ListViewWidget {
 
     fn set_offset(&mut self, _offset: Vector2D) {}
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, _cursor_pos : Vector2D) -> bool {
         false
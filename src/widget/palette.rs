@@ -0,0 +1,606 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Gap between a swatch and the edges of its cell, as a fraction of the cell size
+const SWATCH_MARGIN: f64 = 0.15;
+
+/// Colors of the default palette
+const DEFAULT_COLORS: [u32; 16] = [
+    0xff000000, 0xff808080, 0xffc0c0c0, 0xffffffff, 0xfff44336, 0xffff9800, 0xffffeb3b, 0xff4caf50,
+    0xff009688, 0xff03a9f4, 0xff3f51b5, 0xff9c27b0, 0xffe91e63, 0xff795548, 0xff607d8b, 0xff8bc34a,
+];
+
+/// Palette is a widget that displays a grid of predefined color swatches to pick
+/// from, a lighter-weight complement to a full color picker.
+///
+/// The swatch of the color selected is highlighted. When a swatch is clicked, the
+/// `on_color_selected` message is handled, which is filled with the color selected
+/// if it is a [`ValueChanged<Color>`](`ValueChanged`). Optionally, a row below the
+/// grid keeps the colors selected most recently, updated on every selection.
+#[derive(Clone)]
+pub struct PaletteWidget {
+    /// The palette's identifier
+    id: usize,
+
+    /// The data attached to the palette through its handle
+    widget_data: WidgetData,
+
+    /// The palette's predefined colors, laid out row by row
+    colors: Vec<Color>,
+
+    /// The number of swatches on every row
+    columns: usize,
+
+    /// The color selected, if any
+    selected: Option<Color>,
+
+    /// The colors selected most recently, the most recent first
+    recent: Vec<Color>,
+
+    /// The maximum number of recent colors kept, or 0 to hide the recent colors row
+    recent_capacity: usize,
+
+    /// The message to be handled when a color is selected
+    on_color_selected: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The palette's background color
+    background_color: Color,
+
+    /// The color of the highlight around the swatch selected
+    highlight_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The palette's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The palette's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The palette's current size (width and height)
+    size: Vector2D,
+
+    /// The palette's original size (width and height)
+    original_size: Vector2D,
+
+    /// The palette's layout
+    layout: Layout,
+
+    /// The palette's offset vector coordinates
+    offset: Vector2D,
+
+    /// The palette's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl PaletteWidget {
+    /// Creates a new `PaletteWidget`
+    ///
+    /// # Returns
+    /// The palette created, with no color selected and no recent colors row
+    ///
+    /// # Arguments
+    /// * `colors` - the predefined colors, laid out row by row
+    /// * `columns` - the number of swatches on every row
+    /// * `size` - the size (width and height) to be assigned to the palette
+    /// * `background_color` - the color to be assigned to the palette's background
+    /// * `highlight_color` - the color to be assigned to the highlight around the swatch selected
+    pub fn new(
+        colors: Vec<Color>,
+        columns: usize,
+        size: Vector2D,
+        background_color: Color,
+        highlight_color: Color,
+    ) -> PaletteWidget {
+        PaletteWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_accent_color(highlight_color),
+            colors,
+            columns: columns.max(1),
+            selected: None,
+            recent: Vec::new(),
+            recent_capacity: 0,
+            on_color_selected: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            background_color,
+            highlight_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when a color is selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_color_selected` - the message to be handled when a color is selected
+    pub fn set_message(&mut self, on_color_selected: Option<Box<dyn Message>>) {
+        self.on_color_selected = on_color_selected;
+    }
+
+    /// Sets the predefined colors
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `colors` - the predefined colors, laid out row by row
+    pub fn set_colors(&mut self, colors: Vec<Color>) {
+        self.colors = colors;
+        self.dirty = true;
+    }
+
+    /// Gets the predefined colors
+    ///
+    /// # Returns
+    /// The predefined colors, laid out row by row
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    /// Sets the color selected, without handling the `on_color_selected` message nor
+    /// updating the recent colors
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the color to be selected, or `None` to clear the selection
+    pub fn set_selected(&mut self, selected: Option<Color>) {
+        self.selected = selected;
+        self.dirty = true;
+    }
+
+    /// Gets the color selected
+    ///
+    /// # Returns
+    /// The color selected, or `None` if there is none
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn selected(&self) -> Option<Color> {
+        self.selected
+    }
+
+    /// Sets the maximum number of recent colors kept, displayed on a row below the grid
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `recent_capacity` - the maximum number of recent colors, up to the number of columns, or 0 to hide the row
+    pub fn set_recent_capacity(&mut self, recent_capacity: usize) {
+        self.recent_capacity = recent_capacity.min(self.columns);
+        self.recent.truncate(self.recent_capacity);
+        self.dirty = true;
+    }
+
+    /// Gets the colors selected most recently
+    ///
+    /// # Returns
+    /// The recent colors, the most recent first
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn recent(&self) -> &[Color] {
+        &self.recent
+    }
+
+    /// Sets the color of the highlight around the swatch selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `highlight_color` - the color to be assigned
+    pub fn set_highlight_color(&mut self, highlight_color: Color) {
+        self.style.accent_color = Some(highlight_color);
+        self.highlight_color = highlight_color;
+        self.dirty = true;
+    }
+
+    /// Gets the number of rows of swatches, including the recent colors row, for internal use only
+    ///
+    /// # Returns
+    /// The number of rows
+    ///
+    /// # Arguments
+    /// No arguments
+    fn rows(&self) -> usize {
+        let rows = self.colors.len().div_ceil(self.columns);
+        if self.recent_capacity > 0 {
+            rows + 1
+        } else {
+            rows.max(1)
+        }
+    }
+
+    /// Gets the size of the cells of the grid, for internal use only
+    ///
+    /// # Returns
+    /// The size (width and height) of every cell
+    ///
+    /// # Arguments
+    /// No arguments
+    fn cell_size(&self) -> Vector2D {
+        Vector2D::new(
+            self.size.x / self.columns as f64,
+            self.size.y / self.rows() as f64,
+        )
+    }
+
+    /// Gets the colors of every swatch, along with the position of its cell, for internal use only
+    ///
+    /// # Returns
+    /// The color and the top left corner of the cell of every swatch, the recent colors last
+    ///
+    /// # Arguments
+    /// No arguments
+    fn swatches(&self) -> Vec<(Color, Vector2D)> {
+        let cell_size = self.cell_size();
+        let recent_row = self.rows() - 1;
+        let recent = self
+            .recent
+            .iter()
+            .enumerate()
+            .map(|(column, color)| (*color, column, recent_row));
+        self.colors
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (*color, index % self.columns, index / self.columns))
+            .chain(recent)
+            .map(|(color, column, row)| {
+                let cell = Vector2D::new(column as f64, row as f64);
+                (color, self.position + cell_size * cell)
+            })
+            .collect()
+    }
+
+    /// Gets the color of the swatch under a position, for internal use only
+    ///
+    /// # Returns
+    /// The color of the swatch under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn color_at(&self, position: Vector2D) -> Option<Color> {
+        let cell_size = self.cell_size();
+        self.swatches()
+            .into_iter()
+            .find(|(_, point)| {
+                position.x >= point.x
+                    && position.x < point.x + cell_size.x
+                    && position.y >= point.y
+                    && position.y < point.y + cell_size.y
+            })
+            .map(|(color, _)| color)
+    }
+
+    /// Selects a color, moving it to the front of the recent colors and handling the
+    /// `on_color_selected` message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `color` - the color selected
+    /// * `event` - the event that selected the color
+    /// * `messages` - the queue where the message is placed
+    fn select(&mut self, color: Color, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.selected = Some(color);
+        if self.recent_capacity > 0 {
+            self.recent.retain(|recent| *recent != color);
+            self.recent.insert(0, color);
+            self.recent.truncate(self.recent_capacity);
+        }
+        self.dirty = true;
+        if let Some(mut message) = self.on_color_selected.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<Color>>() {
+                message.value = color;
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Sets the palette's predefined colors, returning the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the predefined colors assigned
+    ///
+    /// # Arguments
+    /// * `colors` - the predefined colors, laid out row by row
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets the palette's number of swatches on every row, returning the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the number of columns assigned
+    ///
+    /// # Arguments
+    /// * `columns` - the number of swatches on every row
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self.recent_capacity = self.recent_capacity.min(self.columns);
+        self
+    }
+
+    /// Sets the palette's size (width and height), returning the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the palette
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the palette's maximum number of recent colors kept, returning the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the recent colors row displayed, unless the capacity is 0
+    ///
+    /// # Arguments
+    /// * `recent_capacity` - the maximum number of recent colors, up to the number of columns, or 0 to hide the row
+    pub fn with_recent_capacity(mut self, recent_capacity: usize) -> Self {
+        self.set_recent_capacity(recent_capacity);
+        self
+    }
+
+    /// Sets the palette's message to be handled when a color is selected, returning the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the given message to be handled when a color is selected
+    ///
+    /// # Arguments
+    /// * `on_color_selected` - the message to be handled when a color is selected
+    pub fn with_on_color_selected(mut self, on_color_selected: Option<Box<dyn Message>>) -> Self {
+        self.on_color_selected = on_color_selected;
+        self
+    }
+
+    /// Sets the palette's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the palette
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the palette's style, overriding the properties of the current theme, returning
+    /// the palette (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The palette with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the palette
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for PaletteWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        PaletteWidget {
+            style: Style::new(),
+            ..PaletteWidget::new(
+                DEFAULT_COLORS
+                    .iter()
+                    .map(|hex| Color::from_hex(*hex))
+                    .collect(),
+                8,
+                Vector2D::new(192., 72.),
+                theme.palette.surface,
+                theme.palette.accent,
+            )
+        }
+        .with_recent_capacity(8)
+    }
+}
+
+impl Widget for PaletteWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                if let Some(color) = self.color_at(self.cursor_pos) {
+                    self.select(color, event, messages);
+                }
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let cell_size = self.cell_size();
+        let margin = cell_size * SWATCH_MARGIN;
+        let mut instructions = vec![
+            // Palette rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        for (color, point) in self.swatches() {
+            // Highlight around the swatch selected
+            if self.selected == Some(color) {
+                instructions.push(RenderInstruction::DrawRect {
+                    point: point + margin * 0.5,
+                    color: self.highlight_color,
+                    size: cell_size - margin,
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            instructions.push(RenderInstruction::DrawRect {
+                point: point + margin,
+                color,
+                size: cell_size - margin * 2.,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.highlight_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
+}
@@ -1,8 +1,11 @@
+use crate::clock;
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
+use crate::message::ContextMenuRequested;
+use crate::renderer::{ClipShape, Fill, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
 use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::widget::{propagate_event, EventStatus, Flex, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -14,6 +17,9 @@ const ON_LONG_PRESS_TIME: u128 = 500;
 #[derive(Clone)]
 pub struct PanelWidget {
     id: usize,
+
+    /// The data attached to the panel through its handle
+    widget_data: WidgetData,
     is_clickable: bool,
     background_color: Color,
     on_press: Option<Box<dyn Message>>,
@@ -28,6 +34,21 @@ pub struct PanelWidget {
     original_size: Vector2D,
     layout: Layout,
     offset: Vector2D,
+    on_auxiliary_press: Option<Box<dyn Message>>,
+    auxiliary_pressed: Option<event::MouseButton>,
+    on_chord: Option<Box<dyn Message>>,
+    on_context_menu: Option<Box<dyn Message>>,
+    is_context_pressed: bool,
+    corner_radius: Option<f64>,
+
+    /// The fill drawn behind the panel's children (e.g. a gradient on a header), if any
+    background_fill: Option<Fill>,
+
+    /// The panel's flex properties (i.e., how it is sized by a flex layout)
+    flex: Flex,
+
+    /// The panel's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl PanelWidget {
@@ -40,12 +61,15 @@ impl PanelWidget {
     ) -> PanelWidget {
         PanelWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color),
             background_color: background_color,
             is_clickable: is_clickable,
             on_press: on_press,
             on_long_press: on_long_press,
             is_pressed: false,
-            click_time: Instant::now(),
+            click_time: clock::now(),
             cursor_pos: Vector2D::new(-1., -1.),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
@@ -54,34 +78,272 @@ impl PanelWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            on_auxiliary_press: None,
+            auxiliary_pressed: None,
+            on_chord: None,
+            on_context_menu: None,
+            is_context_pressed: false,
+            corner_radius: None,
+            background_fill: None,
+            flex: Flex::default(),
         }
     }
 
     pub fn set_is_clickable(&mut self, value: bool) {
         self.is_clickable = value;
     }
+
+    /// Sets the radius of the panel's rounded corners, to which its children are clipped
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `corner_radius` - the radius of the corners, or `None` to not clip the children
+    pub fn set_corner_radius(&mut self, corner_radius: Option<f64>) {
+        self.corner_radius = corner_radius;
+        self.set_dirty(true);
+        // The children are clipped when they are built
+        for value in self.children.iter() {
+            if let Some(child) = value.upgrade() {
+                child.borrow_mut().set_dirty(true);
+            }
+        }
+    }
+
+    /// Sets the fill drawn behind the panel's children, within its rounded corners
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `background_fill` - the fill of the panel (e.g. a gradient), or `None` to draw no background
+    pub fn set_background_fill(&mut self, background_fill: Option<Fill>) {
+        self.background_fill = background_fill;
+        self.dirty = true;
+    }
+
+    /// Sets the panel's size (width and height), returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the panel
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the panel's clickable flag, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the clickable flag assigned
+    ///
+    /// # Arguments
+    /// * `is_clickable` - the clickable flag to be assigned to the panel
+    pub fn with_is_clickable(mut self, is_clickable: bool) -> Self {
+        self.is_clickable = is_clickable;
+        self
+    }
+
+    /// Sets the radius of the panel's rounded corners, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the radius of the rounded corners assigned
+    ///
+    /// # Arguments
+    /// * `corner_radius` - the radius of the corners, to which the panel's children are clipped
+    pub fn with_corner_radius(mut self, corner_radius: f64) -> Self {
+        self.corner_radius = Some(corner_radius);
+        self
+    }
+
+    /// Sets the fill drawn behind the panel's children, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the fill assigned
+    ///
+    /// # Arguments
+    /// * `background_fill` - the fill to be assigned to the panel (e.g. a gradient)
+    pub fn with_background_fill(mut self, background_fill: Fill) -> Self {
+        self.background_fill = Some(background_fill);
+        self
+    }
+
+    /// Sets the panel's background color, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the panel
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the panel's message to be handled when pressed, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the given message to be handled when pressed
+    ///
+    /// # Arguments
+    /// * `on_press` - the message to be handled when pressed
+    pub fn with_on_press(mut self, on_press: Option<Box<dyn Message>>) -> Self {
+        self.on_press = on_press;
+        self
+    }
+
+    /// Sets the panel's message to be handled when long pressed, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the given message to be handled when long pressed
+    ///
+    /// # Arguments
+    /// * `on_long_press` - the message to be handled when long pressed
+    pub fn with_on_long_press(mut self, on_long_press: Option<Box<dyn Message>>) -> Self {
+        self.on_long_press = on_long_press;
+        self
+    }
+
+    /// Sets the message to be handled when a user presses any button other than the
+    /// left and right ones (e.g. the middle button)
+    ///
+    /// The message's event is the button release, so the button pressed can be
+    /// told apart.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_auxiliary_press` - the message to be handled when an auxiliary button is pressed
+    pub fn set_auxiliary_message(&mut self, on_auxiliary_press: Option<Box<dyn Message>>) {
+        self.on_auxiliary_press = on_auxiliary_press;
+    }
+
+    /// Sets the message to be handled when a user presses two mouse buttons
+    /// together (i.e., a chord) inside the panel
+    ///
+    /// The message's event is the [`Chord`](`event::Mouse::Chord`) event, holding
+    /// both buttons.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_chord` - the message to be handled when a chord is pressed
+    pub fn set_chord_message(&mut self, on_chord: Option<Box<dyn Message>>) {
+        self.on_chord = on_chord;
+    }
+
+    /// Sets the message to be handled when a user right clicks inside the panel
+    /// (i.e., requests a context menu)
+    ///
+    /// The message is handled on the right button release. When it is a
+    /// [`ContextMenuRequested`], it is filled with the cursor's position, to
+    /// position the menu.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_context_menu` - the message to be handled when a context menu is requested
+    pub fn set_context_menu_message(&mut self, on_context_menu: Option<Box<dyn Message>>) {
+        self.on_context_menu = on_context_menu;
+    }
+
+    /// Sets how the panel is sized by a flex layout
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `flex` - the panel's flex properties
+    pub fn set_flex(&mut self, flex: Flex) {
+        self.flex = flex;
+        self.dirty = true;
+    }
+
+    /// Sets how the panel is sized by a flex layout, returning the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the flex properties assigned
+    ///
+    /// # Arguments
+    /// * `flex` - the flex properties to be assigned to the panel
+    pub fn with_flex(mut self, flex: Flex) -> Self {
+        self.flex = flex;
+        self
+    }
+
+    /// Sets the panel's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the panel
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the panel's style, overriding the properties of the current theme, returning
+    /// the panel (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The panel with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the panel
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for PanelWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        PanelWidget {
+            style: Style::new(),
+            ..PanelWidget::new(
+                Vector2D::new(100., 100.),
+                false,
+                theme.palette.surface,
+                None,
+                None,
+            )
+        }
+    }
 }
 
 impl Widget for PanelWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 //update cursor_pos on mouse move
                 self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
             }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
                 //when left mouse button is pressed do something if button is clickable and if messages aren't empty
-                if self.is_clickable && (self.on_press.is_some() || self.on_long_press.is_some()) {
-                    //check if cursor is inside button area
-                    if self.is_cursor_inside(self.cursor_pos) {
-                        self.is_pressed = true;
-                        self.click_time = Instant::now();
-                    }
+                //and if cursor is inside button area
+                if self.is_clickable
+                    && (self.on_press.is_some() || self.on_long_press.is_some())
+                    && self.is_cursor_inside(self.cursor_pos)
+                {
+                    self.is_pressed = true;
+                    self.click_time = clock::now();
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
             event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
@@ -91,7 +353,7 @@ impl Widget for PanelWidget {
                     //check if cursor is inside button area
                     //if the release it's outside we don't consider it as a click event
                     if self.is_cursor_inside(self.cursor_pos) {
-                        if self.click_time.elapsed().as_millis() < ON_LONG_PRESS_TIME {
+                        if clock::elapsed(self.click_time).as_millis() < ON_LONG_PRESS_TIME {
                             if let Some(mut message) = self.on_press.clone() {
                                 message.set_event(event);
                                 messages.enqueue(message);
@@ -103,48 +365,82 @@ impl Widget for PanelWidget {
                             }
                         }
                     }
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Right)) => {
-                //when left mouse button is pressed do something if button is clickable and if messages aren't empty
-                if self.is_clickable && (self.on_press.is_some() || self.on_long_press.is_some()) {
-                    //check if cursor is inside button area
-                    if !self.is_cursor_inside(self.cursor_pos) {
-                        self.is_pressed = true;
-                        self.click_time = Instant::now();
-                    }
+                if self.on_context_menu.is_some() && self.is_cursor_inside(self.cursor_pos) {
+                    self.is_context_pressed = true;
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
                 }
             }
             event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Right)) => {
-                //when left mouse button is released do something if button state is pressed
-                if self.is_pressed {
-                    self.is_pressed = false;
-                    //check if cursor is inside button area
-                    //if the release it's outside we don't consider it as a click event
-                    if !self.is_cursor_inside(self.cursor_pos) {
-                        if self.click_time.elapsed().as_millis() < ON_LONG_PRESS_TIME {
-                            if let Some(mut message) = self.on_press.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
-                            }
-                        } else {
-                            if let Some(mut message) = self.on_long_press.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
+                if self.is_context_pressed {
+                    self.is_context_pressed = false;
+                    // A release outside of the panel area does not open the context menu
+                    if self.is_cursor_inside(self.cursor_pos) {
+                        if let Some(mut message) = self.on_context_menu.clone() {
+                            message.set_event(event);
+                            if let Some(message) = message.downcast_mut::<ContextMenuRequested>() {
+                                message.position = self.cursor_pos;
                             }
+                            messages.enqueue(message);
                         }
                     }
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
                 }
             }
-
-            _ => {
-                //call on_event to button children
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
+            event::Event::Mouse(event::Mouse::Chord { .. }) => {
+                if self.on_chord.is_some() && self.is_cursor_inside(self.cursor_pos) {
+                    if let Some(mut message) = self.on_chord.clone() {
+                        message.set_event(event);
+                        messages.enqueue(message);
+                    }
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(
+                button @ (event::MouseButton::Middle | event::MouseButton::Other(_)),
+            )) => {
+                if self.is_clickable
+                    && self.on_auxiliary_press.is_some()
+                    && self.is_cursor_inside(self.cursor_pos)
+                {
+                    self.auxiliary_pressed = Some(button);
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(
+                button @ (event::MouseButton::Middle | event::MouseButton::Other(_)),
+            )) => {
+                if self.auxiliary_pressed == Some(button) {
+                    self.auxiliary_pressed = None;
+                    // A release outside of the panel area is not considered a press
+                    if self.is_cursor_inside(self.cursor_pos) {
+                        if let Some(mut message) = self.on_auxiliary_press.clone() {
+                            message.set_event(event);
+                            messages.enqueue(message);
+                        }
                     }
+                    EventStatus::Captured
+                } else {
+                    propagate_event(&self.children, event, messages)
                 }
             }
+            _ => {
+                //call on_event to button children
+                propagate_event(&self.children, event, messages)
+            }
         }
     }
 
@@ -156,8 +452,28 @@ impl Widget for PanelWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
-        vec![]
+        let fill = match self.background_fill {
+            Some(fill) => fill.instruction(self.position, self.size, self.position, self.size),
+            None => return vec![],
+        };
+        // The fill is clipped to the rounded corners, as the children are
+        match self.clip_shape() {
+            Some(shape) => vec![RenderInstruction::PushClip { shape }, fill, RenderInstruction::PopClip],
+            None => vec![fill],
+        }
+    }
+
+    fn clip_shape(&self) -> Option<ClipShape> {
+        self.corner_radius.map(|radius| ClipShape::RoundedRect {
+            point: self.position,
+            size: self.size,
+            radius,
+        })
     }
 
     fn set_dirty(&mut self, value: bool) {
@@ -191,6 +507,10 @@ impl Widget for PanelWidget {
         &self.layout
     }
 
+    fn flex(&self) -> Flex {
+        self.flex
+    }
+
     fn offset(&mut self) -> Vector2D {
         self.offset
     }
@@ -235,13 +555,9 @@ impl Widget for PanelWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -254,4 +570,9 @@ impl Widget for PanelWidget {
             false
         }
     }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,729 @@
+use crate::drag::{DropEvent, DropTarget};
+use crate::event;
+use crate::event::Event;
+use crate::key_code::KeyCode;
+use crate::message::TextChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+
+/// Width of the browse button, as a multiple of the path input's height
+const BUTTON_WIDTH: f64 = 2.5;
+
+/// Average width of a glyph, as a fraction of the font size, used to place the text
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// Custom validation of a path, returning the message to be displayed when the path is invalid
+pub type PathValidator = Rc<dyn Fn(&Path) -> Result<(), String>>;
+
+/// Path input is a widget to type a file path, combined with a "browse" button.
+///
+/// Since hyber cannot open the native file dialogs itself, the browse button
+/// only handles the `on_browse` message: the application opens the dialog and
+/// answers by assigning the path chosen through [`set_path`](`PathInputWidget::set_path`).
+/// The path is validated on every change, according to whether it must exist, its
+/// allowed extensions and an optional custom validator, and the validation message
+/// is displayed inside the field. When the path is typed or dropped, the `on_path_change`
+/// message is handled, which is filled with the path if it is a [`TextChanged`].
+/// A list of file paths or a text can also be dragged and dropped on the path input.
+#[derive(Clone)]
+pub struct PathInputWidget {
+    /// The path input's identifier
+    id: usize,
+
+    /// The data attached to the path input through its handle
+    widget_data: WidgetData,
+
+    /// The path typed or chosen
+    path: String,
+
+    /// The label of the browse button
+    button_label: String,
+
+    /// Whether the path must exist on the file system
+    must_exist: bool,
+
+    /// The extensions allowed (without the leading dot), or empty to allow any extension
+    extensions: Vec<String>,
+
+    /// The custom validation of the path
+    validator: Option<PathValidator>,
+
+    /// The message displayed when the path is invalid, or `None` if it is valid
+    validation_error: Option<String>,
+
+    /// The message to be handled when the browse button is clicked
+    on_browse: Option<Box<dyn Message>>,
+
+    /// The message to be handled when the path is typed
+    on_path_change: Option<Box<dyn Message>>,
+
+    /// Whether the path input is focused (i.e., receives the characters typed)
+    is_focused: bool,
+
+    /// The area where file paths and texts are dropped
+    drop_target: DropTarget,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The path input's font size
+    font_size: usize,
+
+    /// The path input's background color
+    background_color: Color,
+
+    /// The path input's text color
+    text_color: Color,
+
+    /// The browse button's background color
+    button_color: Color,
+
+    /// The color of the border and the validation message when the path is invalid
+    error_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The path input's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The path input's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The path input's current size (width and height)
+    size: Vector2D,
+
+    /// The path input's original size (width and height)
+    original_size: Vector2D,
+
+    /// The path input's layout
+    layout: Layout,
+
+    /// The path input's offset vector coordinates
+    offset: Vector2D,
+
+    /// The path input's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl PathInputWidget {
+    /// Creates a new `PathInputWidget`
+    ///
+    /// # Returns
+    /// The path input created
+    ///
+    /// # Arguments
+    /// * `path` - the path initially assigned
+    /// * `size` - the size (width and height) to be assigned to the path input
+    /// * `font_size` - the font size to be assigned to the path input
+    /// * `background_color` - the color to be assigned to the path input's background
+    /// * `text_color` - the color to be assigned to the path input's text
+    /// * `button_color` - the color to be assigned to the browse button's background
+    /// * `on_browse` - the message to be handled when the browse button is clicked
+    pub fn new(
+        path: &str,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        text_color: Color,
+        button_color: Color,
+        on_browse: Option<Box<dyn Message>>,
+    ) -> PathInputWidget {
+        let mut path_input = PathInputWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(text_color)
+                .with_muted_color(button_color),
+            path: String::from(path),
+            button_label: String::from("Browse"),
+            must_exist: false,
+            extensions: Vec::new(),
+            validator: None,
+            validation_error: None,
+            on_browse,
+            on_path_change: None,
+            is_focused: false,
+            drop_target: DropTarget::new(None).accept::<Vec<PathBuf>>().accept::<String>(),
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            text_color,
+            button_color,
+            error_color: Color::from_hex(0xffd32f2f),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        };
+        path_input.validate();
+        path_input
+    }
+
+    /// Sets the message to be handled when the browse button is clicked
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_browse` - the message to be handled when the browse button is clicked
+    pub fn set_message(&mut self, on_browse: Option<Box<dyn Message>>) {
+        self.on_browse = on_browse;
+    }
+
+    /// Sets the message to be handled when the path is typed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_path_change` - the message to be handled when the path is typed
+    pub fn set_path_change_message(&mut self, on_path_change: Option<Box<dyn Message>>) {
+        self.on_path_change = on_path_change;
+    }
+
+    /// Sets the path (e.g. the one chosen on the file dialog opened by the application),
+    /// without handling the `on_path_change` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `path` - the path to be assigned
+    pub fn set_path(&mut self, path: &str) {
+        self.path = String::from(path);
+        self.validate();
+        self.dirty = true;
+    }
+
+    /// Gets the path typed or chosen
+    ///
+    /// # Returns
+    /// The path typed or chosen
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Sets the label of the browse button
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `button_label` - the label to be assigned to the browse button
+    pub fn set_button_label(&mut self, button_label: &str) {
+        self.button_label = String::from(button_label);
+        self.dirty = true;
+    }
+
+    /// Sets whether the path must exist on the file system to be valid
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `must_exist` - boolean indicating if the path must exist
+    pub fn set_must_exist(&mut self, must_exist: bool) {
+        self.must_exist = must_exist;
+        self.validate();
+        self.dirty = true;
+    }
+
+    /// Sets the extensions allowed for the path to be valid (e.g. `["png", "jpg"]`)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `extensions` - the extensions allowed, without the leading dot, or empty to allow any extension
+    pub fn set_extensions(&mut self, extensions: &[&str]) {
+        self.extensions = extensions
+            .iter()
+            .map(|extension| extension.trim_start_matches('.').to_lowercase())
+            .collect();
+        self.validate();
+        self.dirty = true;
+    }
+
+    /// Sets the custom validation of the path, checked after the existence and the extension
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `validator` - the custom validation, or `None` to remove it
+    pub fn set_validator(&mut self, validator: Option<PathValidator>) {
+        self.validator = validator;
+        self.validate();
+        self.dirty = true;
+    }
+
+    /// Sets the color of the border and the validation message when the path is invalid
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `error_color` - the color to be assigned
+    pub fn set_error_color(&mut self, error_color: Color) {
+        self.error_color = error_color;
+        self.dirty = true;
+    }
+
+    /// Checks if the path is valid
+    ///
+    /// # Returns
+    /// True, if the path is not empty and passes every validation, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_valid(&self) -> bool {
+        !self.path.is_empty() && self.validation_error.is_none()
+    }
+
+    /// Gets the message displayed when the path is invalid
+    ///
+    /// # Returns
+    /// The validation message, or `None` if the path is valid or empty
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_validation_error(&self) -> Option<&str> {
+        self.validation_error.as_deref()
+    }
+
+    /// Validates the path, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn validate(&mut self) {
+        let path = Path::new(&self.path);
+        self.validation_error = if self.path.is_empty() {
+            None
+        } else if self.must_exist && !path.exists() {
+            Some(String::from("Not found"))
+        } else if !self.extensions.is_empty()
+            && !path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase())
+                .is_some_and(|extension| self.extensions.contains(&extension))
+        {
+            Some(format!("Expected .{}", self.extensions.join(", .")))
+        } else if let Some(validator) = &self.validator {
+            validator(path).err()
+        } else {
+            None
+        };
+    }
+
+    /// Gets the width of the browse button, for internal use only
+    ///
+    /// # Returns
+    /// The width of the browse button
+    ///
+    /// # Arguments
+    /// No arguments
+    fn button_width(&self) -> f64 {
+        (self.size.y * BUTTON_WIDTH).min(self.size.x * 0.5)
+    }
+
+    /// Handles a change of the path typed, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that changed the path
+    /// * `messages` - queue of messages
+    fn on_path_typed(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.validate();
+        self.dirty = true;
+        if let Some(mut message) = self.on_path_change.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<TextChanged>() {
+                message.text = self.path.clone();
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Sets the path input's path, returning the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with the path assigned
+    ///
+    /// # Arguments
+    /// * `path` - the path to be assigned to the path input
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Sets the path input's size (width and height), returning the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the path input
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the path input's extensions allowed, returning the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with the extensions allowed assigned
+    ///
+    /// # Arguments
+    /// * `extensions` - the extensions allowed to be assigned to the path input
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Self {
+        self.set_extensions(extensions);
+        self
+    }
+
+    /// Sets whether the path input's path must exist, returning the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with whether the path must exist assigned
+    ///
+    /// # Arguments
+    /// * `must_exist` - boolean indicating if the path must exist
+    pub fn with_must_exist(mut self, must_exist: bool) -> Self {
+        self.set_must_exist(must_exist);
+        self
+    }
+
+    /// Sets the path input's message to be handled when the browse button is clicked, returning the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with the given message to be handled when the browse button is clicked
+    ///
+    /// # Arguments
+    /// * `on_browse` - the message to be handled when the browse button is clicked
+    pub fn with_on_browse(mut self, on_browse: Option<Box<dyn Message>>) -> Self {
+        self.on_browse = on_browse;
+        self
+    }
+
+    /// Sets the path input's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the path input
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the path input's style, overriding the properties of the current theme, returning
+    /// the path input (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The path input with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the path input
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for PathInputWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        PathInputWidget {
+            style: Style::new(),
+            ..PathInputWidget::new(
+                "",
+                Vector2D::new(320., 30.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.muted,
+                None,
+            )
+        }
+    }
+}
+
+impl Widget for PathInputWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let area = Rect::new(self.position, self.size);
+        match self.drop_target.on_event(event, area, messages) {
+            Some(DropEvent::Dropped(payload)) => {
+                let path = match payload.get::<Vec<PathBuf>>() {
+                    Some(paths) => paths.first().map(|path| path.to_string_lossy().into_owned()),
+                    None => payload.get::<String>().cloned(),
+                };
+                if let Some(path) = path {
+                    self.path = path;
+                    self.on_path_typed(event, messages);
+                }
+                return EventStatus::Captured;
+            }
+            Some(_) => self.dirty = true,
+            None => {}
+        }
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                let is_inside = self.is_cursor_inside(self.cursor_pos);
+                let is_on_button = is_inside
+                    && self.cursor_pos.x >= self.position.x + self.size.x - self.button_width();
+                if is_on_button {
+                    if let Some(mut message) = self.on_browse.clone() {
+                        message.set_event(event);
+                        messages.enqueue(message);
+                    }
+                }
+                let is_focused = is_inside && !is_on_button;
+                if is_focused != self.is_focused {
+                    self.is_focused = is_focused;
+                    self.dirty = true;
+                }
+                if is_inside {
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
+                }
+            }
+            event::Event::Keyboard(event::Keyboard::CharacterReceived(character))
+                if self.is_focused && !character.is_control() =>
+            {
+                self.path.push(character);
+                self.on_path_typed(event, messages);
+                EventStatus::Captured
+            }
+            event::Event::Keyboard(event::Keyboard::KeyPressed {
+                key_code: KeyCode::Backspace,
+                ..
+            }) if self.is_focused && !self.path.is_empty() => {
+                self.path.pop();
+                self.on_path_typed(event, messages);
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let button_width = self.button_width();
+        let field_size = Vector2D::new(self.size.x - button_width, self.size.y);
+        let border_color = if self.validation_error.is_some() {
+            self.error_color
+        } else if self.is_focused || self.drop_target.is_over() {
+            self.text_color
+        } else {
+            self.text_color.with_opacity(0.4)
+        };
+        let baseline = self.position.y + (self.size.y + self.font_size as f64) * 0.5;
+
+        let mut instructions = vec![
+            // Field border
+            RenderInstruction::DrawRect {
+                point: self.position,
+                size: field_size,
+                color: border_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Field background
+            RenderInstruction::DrawRect {
+                point: self.position + 1.,
+                size: field_size - 2.,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Validation message, aligned to the right end of the field
+        let mut text_width = field_size.x - 8.;
+        if let Some(validation_error) = &self.validation_error {
+            let width = validation_error.chars().count() as f64 * self.font_size as f64 * GLYPH_WIDTH;
+            text_width -= width + 8.;
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(self.position.x + field_size.x - width - 4., baseline),
+                font_size: self.font_size,
+                string: validation_error.clone(),
+                color: self.error_color,
+                clip_point: self.position,
+                clip_size: field_size,
+                font: FontDescriptor::default(),
+            });
+        }
+
+        // Path, clipped before the validation message
+        instructions.push(RenderInstruction::DrawText {
+            point: Vector2D::new(self.position.x + 4., baseline),
+            font_size: self.font_size,
+            string: self.path.clone(),
+            color: self.text_color,
+            clip_point: self.position,
+            clip_size: Vector2D::new(text_width.max(0.) + 4., self.size.y),
+            font: FontDescriptor::default(),
+        });
+
+        // Browse button
+        let button_point = self.position + Vector2D::new(field_size.x, 0.);
+        let button_size = Vector2D::new(button_width, self.size.y);
+        let label_width = self.button_label.chars().count() as f64 * self.font_size as f64 * GLYPH_WIDTH;
+        instructions.push(RenderInstruction::DrawRect {
+            point: button_point,
+            size: button_size,
+            color: self.button_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        });
+        instructions.push(RenderInstruction::DrawText {
+            point: Vector2D::new(button_point.x + (button_width - label_width) * 0.5, baseline),
+            font_size: self.font_size,
+            string: self.button_label.clone(),
+            color: self.text_color,
+            clip_point: button_point,
+            clip_size: button_size,
+            font: FontDescriptor::default(),
+        });
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.button_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.dirty = true;
+    }
+}
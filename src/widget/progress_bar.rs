@@ -1,7 +1,9 @@
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
+use crate::renderer::{BlendMode, Fill, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text;
 use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -9,10 +11,19 @@ use std::rc::Weak;
 #[derive(Clone)]
 pub struct ProgressBarWidget {
     id: usize,
+
+    /// The data attached to the progress bar through its handle
+    widget_data: WidgetData,
     progress: f64,
     font_size: usize,
+
+    /// The progress bar's font (i.e., family, weight and style)
+    font: FontDescriptor,
     background_color: Color,
     foreground_color: Color,
+
+    /// The color of the progress bar's text (i.e., its percentage)
+    text_color: Color,
     dirty: bool,
     children: Vec<Weak<RefCell<dyn Widget>>>,
     position: Vector2D,
@@ -20,6 +31,16 @@ pub struct ProgressBarWidget {
     original_size: Vector2D,
     layout: Layout,
     offset: Vector2D,
+    pixel_snapping: Option<bool>,
+
+    /// The fill of the progress bar's track, overriding its background color, if any
+    background_fill: Option<Fill>,
+
+    /// The fill of the progress bar's progress, overriding its foreground color, if any
+    foreground_fill: Option<Fill>,
+
+    /// The progress bar's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl ProgressBarWidget {
@@ -32,10 +53,17 @@ impl ProgressBarWidget {
     ) -> ProgressBarWidget {
         ProgressBarWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_accent_color(foreground_color)
+                .with_font_size(font_size),
             font_size: font_size,
+            font: FontDescriptor::default(),
             progress: progress,
             background_color: background_color,
             foreground_color: foreground_color,
+            text_color: style::current_theme().palette.foreground,
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
             position: Vector2D::new(0., 0.),
@@ -43,6 +71,9 @@ impl ProgressBarWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            background_fill: None,
+            foreground_fill: None,
         }
     }
 
@@ -51,10 +82,212 @@ impl ProgressBarWidget {
         self.progress = value;
         self.dirty = true;
     }
+
+    /// Sets whether the progress bar's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the fill of the progress bar's track (e.g. a gradient), overriding its
+    /// background color
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `background_fill` - the fill of the track, or `None` to fill it with the background color
+    pub fn set_background_fill(&mut self, background_fill: Option<Fill>) {
+        self.background_fill = background_fill;
+        self.dirty = true;
+    }
+
+    /// Sets the fill of the progress bar's progress (e.g. a gradient), overriding its
+    /// foreground color
+    ///
+    /// A gradient spans the whole progress bar, of which only the part up to the
+    /// progress is drawn.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `foreground_fill` - the fill of the progress, or `None` to fill it with the foreground color
+    pub fn set_foreground_fill(&mut self, foreground_fill: Option<Fill>) {
+        self.foreground_fill = foreground_fill;
+        self.dirty = true;
+    }
+
+    /// Sets the progress bar's size (width and height), returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the progress bar
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the progress bar's font size, returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size to be assigned to the progress bar
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.style.font_size = Some(font_size);
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the progress bar's font (e.g. a family other than the renderer's default one,
+    /// or its bold and italic variants)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the progress bar's text
+    pub fn set_font(&mut self, font: FontDescriptor) {
+        self.font = font;
+        self.dirty = true;
+    }
+
+    /// Sets the progress bar's font, returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the font assigned
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the progress bar's text
+    pub fn with_font(mut self, font: FontDescriptor) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the progress bar's progress, from 0 to 100, returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the progress, from 0 to 100 assigned
+    ///
+    /// # Arguments
+    /// * `progress` - the progress, from 0 to 100 to be assigned to the progress bar
+    pub fn with_progress(mut self, progress: f64) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Sets the progress bar's background color, returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the progress bar
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the progress bar's foreground color, returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the foreground color assigned
+    ///
+    /// # Arguments
+    /// * `foreground_color` - the foreground color to be assigned to the progress bar
+    pub fn with_foreground_color(mut self, foreground_color: Color) -> Self {
+        self.style.accent_color = Some(foreground_color);
+        self.foreground_color = foreground_color;
+        self
+    }
+
+    /// Sets the fill of the progress bar's track, overriding its background color,
+    /// returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the track's fill assigned
+    ///
+    /// # Arguments
+    /// * `background_fill` - the fill to be assigned to the track
+    pub fn with_background_fill(mut self, background_fill: Fill) -> Self {
+        self.background_fill = Some(background_fill);
+        self
+    }
+
+    /// Sets the fill of the progress bar's progress, overriding its foreground color,
+    /// returning the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the progress' fill assigned
+    ///
+    /// # Arguments
+    /// * `foreground_fill` - the fill to be assigned to the progress
+    pub fn with_foreground_fill(mut self, foreground_fill: Fill) -> Self {
+        self.foreground_fill = Some(foreground_fill);
+        self
+    }
+
+    /// Sets the progress bar's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the progress bar
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the progress bar's style, overriding the properties of the current theme, returning
+    /// the progress bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The progress bar with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the progress bar
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ProgressBarWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ProgressBarWidget {
+            style: Style::new(),
+            ..ProgressBarWidget::new(
+                Vector2D::new(200., 20.),
+                theme.font_size,
+                0.,
+                theme.palette.surface,
+                theme.palette.accent,
+            )
+        }
+    }
 }
 
 impl Widget for ProgressBarWidget {
-    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) {}
+    fn on_event(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        EventStatus::Ignored
+    }
 
     fn set_id(&mut self, id: usize) {
         self.id = id;
@@ -64,27 +297,58 @@ impl Widget for ProgressBarWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         let progress_perc = Vector2D::new(self.original_size.x * (self.progress/100.0),self.original_size.y);
-        
-        vec![
-            // Progress bar rectangle.
-            RenderInstruction::DrawRect {
+
+        // Progress bar rectangle.
+        let background = match self.background_fill {
+            Some(fill) => fill.instruction(self.position, self.original_size, self.position, self.size),
+            None => RenderInstruction::DrawRect {
                 point: self.position,
                 color: self.background_color.clone(),
                 size: self.original_size,
                 clip_point: self.position,
                 clip_size: self.size,
+                blend_mode: BlendMode::Normal,
             },
-            // Background progress bar rectangle.
-            RenderInstruction::DrawRect {
+        };
+        // Background progress bar rectangle, a gradient being clipped to the progress.
+        let foreground = match self.foreground_fill {
+            Some(fill) => fill.instruction(
+                self.position,
+                self.original_size,
+                self.position,
+                progress_perc.min(self.size),
+            ),
+            None => RenderInstruction::DrawRect {
                 point: self.position,
                 color: self.foreground_color.clone(),
                 size: progress_perc,
                 clip_point: self.position,
                 clip_size: self.size,
+                blend_mode: BlendMode::Normal,
             },
-        ]
+        };
+        // Percentage, centered on the progress bar
+        let percentage = format!("{}%", self.progress.round());
+        let text_size = text::measure_text(&percentage, self.font_size);
+        let text = RenderInstruction::DrawText {
+            point: Vector2D::new(
+                self.position.x + (self.size.x - text_size.x) * 0.5,
+                self.position.y + (self.size.y + text_size.y) * 0.5,
+            ),
+            color: self.text_color,
+            font_size: self.font_size,
+            string: percentage,
+            clip_point: self.position,
+            clip_size: self.size,
+            font: self.font.clone(),
+        };
+        vec![background, foreground, text]
     }
 
     fn set_dirty(&mut self, value: bool) {
@@ -162,15 +426,23 @@ impl Widget for ProgressBarWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
     
     fn is_cursor_inside(&mut self, _cursor_pos: Vector2D) -> bool {
         false
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,678 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{propagate_event, EventStatus, Layout, TabIndex, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Space between the radio button's circle and its label
+const PADDING: f64 = 6.;
+
+/// Group of radio buttons of which only one is checked at a time.
+///
+/// The members of a group may be anywhere in the widget tree (e.g. siblings of
+/// different containers). Checking a member unchecks the one previously checked and
+/// handles the group's `on_change` message, filled with the index of the member
+/// checked when it is a [`ValueChanged<usize>`](`ValueChanged`). The members are
+/// indexed in the order they were added with [`add`](`RadioGroup::add`).
+///
+/// # Examples
+///
+/// ```
+/// use hyber::widget::radio::{RadioButtonWidget, RadioGroup};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let group = Rc::new(RefCell::new(RadioGroup::new(None)));
+/// let first = Rc::new(RefCell::new(RadioButtonWidget::default().with_label("First")));
+/// let second = Rc::new(RefCell::new(RadioButtonWidget::default().with_label("Second")));
+/// RadioGroup::add(&group, &first);
+/// RadioGroup::add(&group, &second);
+///
+/// group.borrow_mut().set_selected(Some(1));
+/// assert!(!first.borrow().is_checked());
+/// assert!(second.borrow().is_checked());
+/// ```
+pub struct RadioGroup {
+    /// The group's members, in the order they were added
+    members: Vec<Weak<RefCell<RadioButtonWidget>>>,
+
+    /// The index of the member checked, if any
+    selected: Option<usize>,
+
+    /// The message to be handled when another member is checked
+    on_change: Option<Box<dyn Message>>,
+}
+
+impl RadioGroup {
+    /// Creates a new `RadioGroup`, without members
+    ///
+    /// # Returns
+    /// The radio group created
+    ///
+    /// # Arguments
+    /// * `on_change` - the message to be handled when another member is checked
+    pub fn new(on_change: Option<Box<dyn Message>>) -> RadioGroup {
+        RadioGroup {
+            members: Vec::new(),
+            selected: None,
+            on_change,
+        }
+    }
+
+    /// Adds a radio button to a group, as its last member
+    ///
+    /// If the radio button is already checked, it becomes the member checked of the
+    /// group, unchecking the previous one.
+    ///
+    /// # Returns
+    /// The index of the radio button within the group
+    ///
+    /// # Arguments
+    /// * `group` - the group to which the radio button is added
+    /// * `radio_button` - the radio button to be added
+    pub fn add(
+        group: &Rc<RefCell<RadioGroup>>,
+        radio_button: &Rc<RefCell<RadioButtonWidget>>,
+    ) -> usize {
+        let index = group.borrow().members.len();
+        group.borrow_mut().members.push(Rc::downgrade(radio_button));
+        let is_checked = {
+            let mut radio_button = radio_button.borrow_mut();
+            radio_button.group = Rc::downgrade(group);
+            radio_button.index = index;
+            radio_button.is_checked
+        };
+        if is_checked {
+            group.borrow_mut().set_selected(Some(index));
+        }
+        index
+    }
+
+    /// Gets the number of members of the group
+    ///
+    /// # Returns
+    /// The number of members, including the ones already dropped
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Checks whether the group has no members
+    ///
+    /// # Returns
+    /// True, if the group has no members, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Gets the index of the member checked
+    ///
+    /// # Returns
+    /// The index of the member checked, or `None` if there is none
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Checks a member, unchecking the others, without handling the `on_change` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the index of the member to be checked, or `None` to uncheck every member
+    pub fn set_selected(&mut self, selected: Option<usize>) {
+        self.selected = selected.filter(|index| *index < self.members.len());
+        for (index, member) in self.members.iter().enumerate() {
+            if let Some(member) = member.upgrade() {
+                member
+                    .borrow_mut()
+                    .set_checked(self.selected == Some(index));
+            }
+        }
+    }
+
+    /// Sets the message to be handled when another member is checked
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_change` - the message to be handled when another member is checked
+    pub fn set_message(&mut self, on_change: Option<Box<dyn Message>>) {
+        self.on_change = on_change;
+    }
+
+    /// Checks the member clicked, unchecking the others and handling the `on_change`
+    /// message, for internal use only
+    ///
+    /// The member clicked is borrowed while handling its event, so it checks itself.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `selected` - the index of the member clicked
+    /// * `event` - the event that checked the member
+    /// * `messages` - queue of messages
+    fn select(&mut self, selected: usize, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if self.selected == Some(selected) {
+            return;
+        }
+        self.selected = Some(selected);
+        for (index, member) in self.members.iter().enumerate() {
+            if index == selected {
+                continue;
+            }
+            if let Some(member) = member.upgrade() {
+                member.borrow_mut().set_checked(false);
+            }
+        }
+        if let Some(mut message) = self.on_change.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<usize>>() {
+                message.value = selected;
+            }
+            messages.enqueue(message);
+        }
+    }
+}
+
+/// Radio button is a stateful widget displaying a circle, filled when checked, next
+/// to a label.
+///
+/// Clicking a radio button checks it, while it can only be unchecked by checking
+/// another member of its [`RadioGroup`], which keeps a single member checked and
+/// handles the message of the group.
+#[derive(Clone)]
+pub struct RadioButtonWidget {
+    /// The radio button's identifier
+    id: usize,
+
+    /// The data attached to the radio button through its handle
+    widget_data: WidgetData,
+
+    /// The radio button's label
+    label: String,
+
+    /// The radio button's font size
+    font_size: usize,
+
+    /// Whether the radio button is checked
+    is_checked: bool,
+
+    /// The group the radio button is a member of, if any
+    group: Weak<RefCell<RadioGroup>>,
+
+    /// The radio button's index within its group
+    index: usize,
+
+    /// The radio button's background color, inside the circle
+    background_color: Color,
+
+    /// The color of the circle's center when checked
+    selected_color: Color,
+
+    /// The radio button's text and circle's border color
+    text_color: Color,
+
+    /// The cursor's position
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The radio button's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The radio button's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The radio button's current size (width and height)
+    size: Vector2D,
+
+    /// The radio button's original size (width and height)
+    original_size: Vector2D,
+
+    /// The radio button's layout
+    layout: Layout,
+
+    /// The radio button's offset vector coordinates
+    offset: Vector2D,
+
+    /// The radio button's position in the focus traversal
+    tab_index: TabIndex,
+
+    /// The radio button's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl RadioButtonWidget {
+    /// Creates a new `RadioButtonWidget`, unchecked and without group
+    ///
+    /// # Returns
+    /// The radio button created
+    ///
+    /// # Arguments
+    /// * `label` - the label to be assigned to the radio button
+    /// * `size` - the size (width and height) to be assigned to the radio button
+    /// * `font_size` - the font size to be assigned to the radio button's label
+    /// * `background_color` - the color to be assigned to the inside of the circle
+    /// * `selected_color` - the color to be assigned to the circle's center when checked
+    /// * `text_color` - the color to be assigned to the label and to the circle's border
+    pub fn new(
+        label: &str,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        selected_color: Color,
+        text_color: Color,
+    ) -> RadioButtonWidget {
+        RadioButtonWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_accent_color(selected_color)
+                .with_foreground_color(text_color)
+                .with_font_size(font_size),
+            label: String::from(label),
+            font_size,
+            is_checked: false,
+            group: Weak::new(),
+            index: 0,
+            background_color,
+            selected_color,
+            text_color,
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+            tab_index: TabIndex::Auto,
+        }
+    }
+
+    /// Checks if the radio button is checked
+    ///
+    /// # Returns
+    /// True, if the radio button is checked, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_checked(&self) -> bool {
+        self.is_checked
+    }
+
+    /// Sets whether the radio button is checked, without updating its group
+    ///
+    /// To keep a single member of the group checked, use
+    /// [`RadioGroup::set_selected`] instead.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_checked` - boolean indicating if the radio button is checked
+    pub fn set_checked(&mut self, is_checked: bool) {
+        if self.is_checked != is_checked {
+            self.is_checked = is_checked;
+            self.dirty = true;
+        }
+    }
+
+    /// Sets the radio button's label
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `label` - the label to be assigned to the radio button
+    pub fn set_label(&mut self, label: &str) {
+        self.label = String::from(label);
+        self.dirty = true;
+    }
+
+    /// Sets the radio button's position in the focus traversal (e.g. to exclude it from the traversal)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `tab_index` - the radio button's tab index
+    pub fn set_tab_index(&mut self, tab_index: TabIndex) {
+        self.tab_index = tab_index;
+        self.dirty = true;
+    }
+
+    /// Checks the radio button, updating its group, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that checked the radio button
+    /// * `messages` - queue of messages
+    fn check(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if self.is_checked {
+            return;
+        }
+        self.set_checked(true);
+        if let Some(group) = self.group.upgrade() {
+            group.borrow_mut().select(self.index, event, messages);
+        }
+    }
+
+    /// Sets the radio button's size (width and height), returning the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the radio button
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the radio button's label, returning the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the label assigned
+    ///
+    /// # Arguments
+    /// * `label` - the label to be assigned to the radio button
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = String::from(label);
+        self
+    }
+
+    /// Sets the radio button's checked state, returning the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the checked state assigned
+    ///
+    /// # Arguments
+    /// * `is_checked` - the checked state to be assigned to the radio button
+    pub fn with_is_checked(mut self, is_checked: bool) -> Self {
+        self.is_checked = is_checked;
+        self
+    }
+
+    /// Sets the radio button's color of the circle's center when checked, returning the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the color of the circle's center when checked assigned
+    ///
+    /// # Arguments
+    /// * `selected_color` - the color of the circle's center when checked
+    pub fn with_selected_color(mut self, selected_color: Color) -> Self {
+        self.style.accent_color = Some(selected_color);
+        self.selected_color = selected_color;
+        self
+    }
+
+    /// Sets the radio button's position in the focus traversal, returning the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the tab index assigned
+    ///
+    /// # Arguments
+    /// * `tab_index` - the tab index to be assigned to the radio button
+    pub fn with_tab_index(mut self, tab_index: TabIndex) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Sets the radio button's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the radio button
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the radio button's style, overriding the properties of the current theme, returning
+    /// the radio button (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The radio button with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the radio button
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for RadioButtonWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        RadioButtonWidget {
+            style: Style::new(),
+            ..RadioButtonWidget::new(
+                "",
+                Vector2D::new(120., 24.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.accent,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for RadioButtonWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                self.check(event, messages);
+                return EventStatus::Captured;
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn tab_index(&self) -> TabIndex {
+        self.tab_index
+    }
+
+    fn activate(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        self.check(event, messages);
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let radius = self.size.y * 0.4;
+        let center = self.position + self.size.y * 0.5;
+
+        let mut instructions = vec![
+            // Circle border
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: radius as usize,
+                color: self.text_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Circle background
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: (radius - 2.).max(0.) as usize,
+                color: self.background_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Label
+            RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    self.position.x + self.size.y + PADDING,
+                    self.position.y + (self.size.y + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: self.label.clone(),
+                color: self.text_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                font: FontDescriptor::default(),
+            },
+        ];
+
+        if self.is_checked {
+            instructions.push(RenderInstruction::DrawCircle {
+                point: center,
+                r: (radius * 0.5) as usize,
+                color: self.selected_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.selected_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.dirty = true;
+    }
+}
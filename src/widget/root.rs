@@ -1,16 +1,91 @@
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::renderer::{DrawImageOptions, Fill, ImageSource, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{propagate_event, Axis, EventStatus, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
 
+/// Layer drawn behind the widgets of a [`RootWidget`], over its background color
+#[derive(Clone, PartialEq)]
+pub enum BackgroundLayer {
+    /// The window is filled with a flat color or a gradient
+    Fill(Fill),
+    /// The image is stretched over the whole window
+    StretchedImage(ImageSource),
+    /// The image is repeated over the window, from its upper left corner
+    TiledImage {
+        /// The image drawn on each tile
+        source: ImageSource,
+        /// The size (width and height) of each tile
+        tile_size: Vector2D,
+    },
+}
+
+impl BackgroundLayer {
+    /// Gets the collection of renderer instructions drawing the layer, for internal use only
+    ///
+    /// # Returns
+    /// The instructions drawing the layer over the area
+    ///
+    /// # Arguments
+    /// * `area` - the area covered by the layer (i.e., the window)
+    fn recipe(&self, area: Rect) -> Vec<RenderInstruction> {
+        // The image is drawn around its center, resized to the given area
+        let image = |source: &ImageSource, tile: Rect| RenderInstruction::DrawImage {
+            point: tile.position + tile.size / 2.,
+            path: source.best_match(1.).1.to_string(),
+            density: 1.,
+            source: Some(source.clone()),
+            options: DrawImageOptions::Resize {
+                width: tile.size.x.round() as usize,
+                height: tile.size.y.round() as usize,
+            },
+            mirrored: false,
+            clip_point: area.position,
+            clip_size: area.size,
+        };
+        match self {
+            BackgroundLayer::Fill(fill) => {
+                vec![fill.instruction(area.position, area.size, area.position, area.size)]
+            }
+            BackgroundLayer::StretchedImage(source) => vec![image(source, area)],
+            BackgroundLayer::TiledImage { source, tile_size } => {
+                if tile_size.x <= 0. || tile_size.y <= 0. {
+                    return vec![];
+                }
+                let columns = (area.size.x / tile_size.x).ceil() as usize;
+                let rows = (area.size.y / tile_size.y).ceil() as usize;
+                (0..rows)
+                    .flat_map(|row| (0..columns).map(move |column| (row, column)))
+                    .map(|(row, column)| {
+                        let position = Vector2D::new(
+                            area.position.x + column as f64 * tile_size.x,
+                            area.position.y + row as f64 * tile_size.y,
+                        );
+                        image(source, Rect::new(position, *tile_size))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl From<Color> for BackgroundLayer {
+    fn from(color: Color) -> Self {
+        BackgroundLayer::Fill(Fill::Solid(color))
+    }
+}
+
 /// 
 #[derive(Clone)]
 pub struct RootWidget {
     /// The list's identifier
     id: usize,
+
+    /// The data attached to the list through its handle
+    widget_data: WidgetData,
     
     /// The list's identifier
     size: Vector2D,
@@ -21,6 +96,10 @@ pub struct RootWidget {
     /// The list's identifier
     background_color: Color,
 
+    /// The root's background layers, drawn over the background color and behind the
+    /// widgets, in order
+    background_layers: Vec<BackgroundLayer>,
+
     /// The list's identifier
     layout: Layout,
     
@@ -29,6 +108,9 @@ pub struct RootWidget {
     
     /// The list's identifier
     children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The root's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl RootWidget {
@@ -50,27 +132,139 @@ impl RootWidget {
     ) -> RootWidget {
         RootWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color),
             size: size,
             original_size: size,
             background_color: background_color,
+            background_layers: Vec::new(),
             layout: layout,
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
         }
     }
+
+    /// Sets the root's size (width and height), returning the root (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The root with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the root
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the root's background color, returning the root (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The root with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the root
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the root's background layers, drawn over the background color and behind
+    /// the widgets, replacing the previous ones
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `background_layers` - the layers to be drawn, from the bottom one to the top one
+    pub fn set_background_layers(&mut self, background_layers: Vec<BackgroundLayer>) {
+        self.background_layers = background_layers;
+        self.dirty = true;
+    }
+
+    /// Gets the root's background layers
+    ///
+    /// # Returns
+    /// The layers drawn behind the widgets, from the bottom one to the top one
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn background_layers(&self) -> &[BackgroundLayer] {
+        &self.background_layers
+    }
+
+    /// Sets the root's background layers, returning the root (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The root with the background layers assigned
+    ///
+    /// # Arguments
+    /// * `background_layers` - the layers to be drawn, from the bottom one to the top one
+    pub fn with_background_layers(mut self, background_layers: Vec<BackgroundLayer>) -> Self {
+        self.set_background_layers(background_layers);
+        self
+    }
+
+    /// Sets the root's layout, returning the root (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The root with the layout assigned
+    ///
+    /// # Arguments
+    /// * `layout` - the layout to be assigned to the root
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets the root's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the root
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the root's style, overriding the properties of the current theme, returning
+    /// the root (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The root with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the root
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for RootWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        RootWidget {
+            style: Style::new(),
+            ..RootWidget::new(
+                Vector2D::new(640., 480.),
+                theme.palette.background,
+                Layout::Box(Axis::Vertical),
+            )
+        }
+    }
 }
 
 impl Widget for RootWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
-        match event {
-            _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
-            }
-        }
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        propagate_event(&self.children, event, messages)
     }
 
     fn set_id(&mut self, id: usize) {
@@ -81,10 +275,21 @@ impl Widget for RootWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
-        vec![RenderInstruction::Clear {
+        let area = Rect::new(Vector2D::new(0., 0.), self.size);
+        let mut instructions = vec![RenderInstruction::Clear {
             color: self.background_color,
-        }]
+        }];
+        instructions.extend(
+            self.background_layers
+                .iter()
+                .flat_map(|layer| layer.recipe(area)),
+        );
+        instructions
     }
 
     fn set_dirty(&mut self, value: bool) {
@@ -167,4 +372,12 @@ impl Widget for RootWidget {
     fn is_cursor_inside(&mut self, _cursor_pos: Vector2D) -> bool {
         false
     }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        // The widgets created afterwards read the theme as well
+        style::set_current_theme(theme);
+        style::cascade_theme(&self.children, theme);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.background);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,748 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{set_tree_dirty, BlendMode, ClipShape, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::scrollbar::ScrollExtent;
+use crate::widget::{propagate_event, Axis, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Minimum length of the scroll bars' thumbs
+const MIN_THUMB: f64 = 16.;
+
+/// Scroll view is a widget scrolling its content along both axes, with independent
+/// horizontal and vertical offsets.
+///
+/// The content is the widget's children, placed at the scroll view's upper left corner
+/// with their original sizes, and its size is the one of the largest child. A scroll
+/// bar is shown along each axis the content overflows, the square where both bars meet
+/// (i.e., the corner gutter) being left out of both tracks and of the viewport.
+///
+/// The mouse wheel scrolls the content along both axes, a vertical scroll with Shift
+/// held being mapped to a horizontal one, while dragging a thumb, or clicking a track
+/// to move by a page, scrolls along the bar's axis. The `on_scroll` message is
+/// handled whenever the content is scrolled, filled with the new offsets when it is a
+/// [`ValueChanged<Vector2D>`](`ValueChanged`).
+#[derive(Clone)]
+pub struct ScrollViewWidget {
+    /// The scroll view's identifier
+    id: usize,
+
+    /// The data attached to the scroll view through its handle
+    widget_data: WidgetData,
+
+    /// The distance the content is scrolled by, along each axis
+    scroll: Vector2D,
+
+    /// The thickness of the scroll bars
+    bar_thickness: f64,
+
+    /// The axis of the thumb being dragged and the distance between the cursor and
+    /// the start of the thumb, if a thumb is being dragged
+    thumb_grab: Option<(Axis, f64)>,
+
+    /// The message to be handled when the content is scrolled
+    on_scroll: Option<Box<dyn Message>>,
+
+    /// The tracks' color
+    track_color: Color,
+
+    /// The thumbs' color
+    thumb_color: Color,
+
+    /// The corner gutter's color
+    gutter_color: Color,
+
+    /// The cursor's position
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The scroll view's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The scroll view's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The scroll view's current size (width and height)
+    size: Vector2D,
+
+    /// The scroll view's original size (width and height)
+    original_size: Vector2D,
+
+    /// The scroll view's layout
+    layout: Layout,
+
+    /// The scroll view's offset vector coordinates
+    offset: Vector2D,
+
+    /// The scroll view's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ScrollViewWidget {
+    /// Creates a new `ScrollViewWidget`
+    ///
+    /// # Returns
+    /// The scroll view created, scrolled to the upper left corner of its content
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the scroll view
+    /// * `track_color` - the color to be assigned to the tracks and the corner gutter
+    /// * `thumb_color` - the color to be assigned to the thumbs
+    /// * `on_scroll` - the message to be handled when the content is scrolled
+    pub fn new(
+        size: Vector2D,
+        track_color: Color,
+        thumb_color: Color,
+        on_scroll: Option<Box<dyn Message>>,
+    ) -> ScrollViewWidget {
+        ScrollViewWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_muted_color(track_color)
+                .with_accent_color(thumb_color),
+            scroll: Vector2D::new(0., 0.),
+            bar_thickness: 12.,
+            thumb_grab: None,
+            on_scroll,
+            track_color,
+            thumb_color,
+            gutter_color: track_color,
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when the content is scrolled
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_scroll` - the message to be handled when the content is scrolled
+    pub fn set_message(&mut self, on_scroll: Option<Box<dyn Message>>) {
+        self.on_scroll = on_scroll;
+    }
+
+    /// Gets the distance the content is scrolled by, along each axis
+    ///
+    /// # Returns
+    /// The horizontal and vertical offsets
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn scroll_offset(&self) -> Vector2D {
+        self.scroll
+    }
+
+    /// Scrolls the content along both axes
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `offset` - the horizontal and vertical offsets, each clamped to the content
+    pub fn set_scroll_offset(&mut self, offset: Vector2D) {
+        let max_offset = Vector2D::new(
+            self.extent(Axis::Horizontal).max_offset(),
+            self.extent(Axis::Vertical).max_offset(),
+        );
+        let offset = offset.clamp(Vector2D::new(0., 0.), max_offset);
+        if offset == self.scroll {
+            return;
+        }
+        self.scroll = offset;
+        self.dirty = true;
+        // The content moved, so the whole subtree is rebuilt at its new position
+        for child in self.children.iter().filter_map(Weak::upgrade) {
+            set_tree_dirty(&child);
+        }
+    }
+
+    /// Gets the extent of the content along an axis
+    ///
+    /// # Returns
+    /// The offset, the length of the content and the length of the viewport along the axis
+    ///
+    /// # Arguments
+    /// * `axis` - the axis of the extent
+    pub fn extent(&self, axis: Axis) -> ScrollExtent {
+        let content = self.content_size();
+        let viewport = self.viewport().size;
+        match axis {
+            Axis::Horizontal => ScrollExtent::new(self.scroll.x, content.x, viewport.x),
+            Axis::Vertical => ScrollExtent::new(self.scroll.y, content.y, viewport.y),
+        }
+    }
+
+    /// Gets the size of the content, for internal use only
+    ///
+    /// # Returns
+    /// The largest original width and height among the children
+    ///
+    /// # Arguments
+    /// No arguments
+    fn content_size(&self) -> Vector2D {
+        self.children
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|child| {
+                child
+                    .try_borrow_mut()
+                    .ok()
+                    .map(|mut child| child.original_size())
+            })
+            .fold(Vector2D::new(0., 0.), Vector2D::max)
+    }
+
+    /// Checks which scroll bars are shown, for internal use only
+    ///
+    /// A bar takes space from the other axis, so showing one may cause the content to
+    /// overflow along the other axis too.
+    ///
+    /// # Returns
+    /// Whether the horizontal and the vertical scroll bars are shown
+    ///
+    /// # Arguments
+    /// No arguments
+    fn shown_bars(&self) -> (bool, bool) {
+        let content = self.content_size();
+        let mut horizontal = content.x > self.size.x;
+        let mut vertical = content.y > self.size.y;
+        if horizontal && !vertical {
+            vertical = content.y > self.size.y - self.bar_thickness;
+        }
+        if vertical && !horizontal {
+            horizontal = content.x > self.size.x - self.bar_thickness;
+        }
+        (horizontal, vertical)
+    }
+
+    /// Gets the area where the content is visible, for internal use only
+    ///
+    /// # Returns
+    /// The scroll view's area, without the scroll bars shown
+    ///
+    /// # Arguments
+    /// No arguments
+    fn viewport(&self) -> Rect {
+        let (horizontal, vertical) = self.shown_bars();
+        let bar = |shown: bool| if shown { self.bar_thickness } else { 0. };
+        Rect::new(
+            self.position,
+            Vector2D::new(
+                (self.size.x - bar(vertical)).max(0.),
+                (self.size.y - bar(horizontal)).max(0.),
+            ),
+        )
+    }
+
+    /// Gets the track of the scroll bar along an axis, for internal use only
+    ///
+    /// # Returns
+    /// The track's area, shortened to leave the corner gutter out, or `None` if the
+    /// scroll bar is not shown
+    ///
+    /// # Arguments
+    /// * `axis` - the axis of the scroll bar
+    fn track(&self, axis: Axis) -> Option<Rect> {
+        let (horizontal, vertical) = self.shown_bars();
+        let viewport = self.viewport();
+        match axis {
+            Axis::Horizontal if horizontal => Some(Rect::new(
+                Vector2D::new(self.position.x, self.position.y + viewport.size.y),
+                Vector2D::new(viewport.size.x, self.bar_thickness),
+            )),
+            Axis::Vertical if vertical => Some(Rect::new(
+                Vector2D::new(self.position.x + viewport.size.x, self.position.y),
+                Vector2D::new(self.bar_thickness, viewport.size.y),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Gets the thumb of the scroll bar along an axis, for internal use only
+    ///
+    /// The thumb's length is proportional to the viewport-to-content ratio.
+    ///
+    /// # Returns
+    /// The track's area and the thumb's start along the axis, relative to the start of
+    /// the track, and its length, or `None` if the scroll bar is not shown
+    ///
+    /// # Arguments
+    /// * `axis` - the axis of the scroll bar
+    fn thumb(&self, axis: Axis) -> Option<(Rect, f64, f64)> {
+        let track = self.track(axis)?;
+        let extent = self.extent(axis);
+        let length = along(axis, track.size);
+        if extent.content <= 0. {
+            return None;
+        }
+        let thumb = (length * extent.viewport / extent.content)
+            .max(MIN_THUMB)
+            .min(length);
+        let start = if extent.max_offset() > 0. {
+            (extent.offset / extent.max_offset()).clamp(0., 1.) * (length - thumb)
+        } else {
+            0.
+        };
+        Some((track, start, thumb))
+    }
+
+    /// Gets the corner gutter, for internal use only
+    ///
+    /// # Returns
+    /// The square where both scroll bars meet, or `None` if both are not shown
+    ///
+    /// # Arguments
+    /// No arguments
+    fn gutter(&self) -> Option<Rect> {
+        match self.shown_bars() {
+            (true, true) => Some(Rect::new(
+                self.position + self.viewport().size,
+                Vector2D::new(self.bar_thickness, self.bar_thickness),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Scrolls the content, handling the `on_scroll` message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `offset` - the offsets to be scrolled to, clamped to the content
+    /// * `event` - the event that scrolled the content
+    /// * `messages` - queue of messages
+    fn scroll_to(
+        &mut self,
+        offset: Vector2D,
+        event: Event,
+        messages: &mut Queue<Box<dyn Message>>,
+    ) {
+        let previous = self.scroll;
+        self.set_scroll_offset(offset);
+        if self.scroll == previous {
+            return;
+        }
+        if let Some(mut message) = self.on_scroll.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<Vector2D>>() {
+                message.value = self.scroll;
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Scrolls the content along an axis, keeping the offset along the other one, for
+    /// internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `axis` - the axis along which the content is scrolled
+    /// * `offset` - the offset to be scrolled to along the axis
+    /// * `event` - the event that scrolled the content
+    /// * `messages` - queue of messages
+    fn scroll_axis_to(
+        &mut self,
+        axis: Axis,
+        offset: f64,
+        event: Event,
+        messages: &mut Queue<Box<dyn Message>>,
+    ) {
+        let offset = match axis {
+            Axis::Horizontal => Vector2D::new(offset, self.scroll.y),
+            Axis::Vertical => Vector2D::new(self.scroll.x, offset),
+        };
+        self.scroll_to(offset, event, messages);
+    }
+
+    /// Sets the scroll view's size (width and height), returning the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the scroll view
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the scroll bars' thickness, returning the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the scroll bars' thickness assigned
+    ///
+    /// # Arguments
+    /// * `bar_thickness` - the thickness to be assigned to the scroll bars
+    pub fn with_bar_thickness(mut self, bar_thickness: f64) -> Self {
+        self.bar_thickness = bar_thickness;
+        self
+    }
+
+    /// Sets the scroll view's message to be handled when scrolling, returning the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the given message to be handled when scrolling
+    ///
+    /// # Arguments
+    /// * `on_scroll` - the message to be handled when the content is scrolled
+    pub fn with_on_scroll(mut self, on_scroll: Option<Box<dyn Message>>) -> Self {
+        self.on_scroll = on_scroll;
+        self
+    }
+
+    /// Sets the scroll view's thumb color, returning the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the thumb color assigned
+    ///
+    /// # Arguments
+    /// * `thumb_color` - the color to be assigned to the thumbs
+    pub fn with_thumb_color(mut self, thumb_color: Color) -> Self {
+        self.style.accent_color = Some(thumb_color);
+        self.thumb_color = thumb_color;
+        self
+    }
+
+    /// Sets the scroll view's corner gutter color, returning the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the corner gutter color assigned
+    ///
+    /// # Arguments
+    /// * `gutter_color` - the color to be assigned to the corner gutter
+    pub fn with_gutter_color(mut self, gutter_color: Color) -> Self {
+        self.style.background_color = Some(gutter_color);
+        self.gutter_color = gutter_color;
+        self
+    }
+
+    /// Sets the scroll view's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the scroll view
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the scroll view's style, overriding the properties of the current theme, returning
+    /// the scroll view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll view with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the scroll view
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+/// Gets the coordinate of a vector along an axis, for internal use only
+///
+/// # Returns
+/// The vector's x-coordinate for the horizontal axis, or its y-coordinate otherwise
+///
+/// # Arguments
+/// * `axis` - the axis of the coordinate
+/// * `vector` - the vector whose coordinate is read
+fn along(axis: Axis, vector: Vector2D) -> f64 {
+    match axis {
+        Axis::Horizontal => vector.x,
+        Axis::Vertical => vector.y,
+    }
+}
+
+impl Default for ScrollViewWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ScrollViewWidget {
+            style: Style::new(),
+            ..ScrollViewWidget::new(
+                Vector2D::new(200., 200.),
+                theme.palette.muted,
+                theme.palette.accent,
+                None,
+            )
+        }
+    }
+}
+
+impl Widget for ScrollViewWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                // Drag the thumb, mapping its position back to the offset
+                if let Some((axis, grab)) = self.thumb_grab {
+                    if let Some((track, _, length)) = self.thumb(axis) {
+                        let free = along(axis, track.size) - length;
+                        if free > 0. {
+                            let cursor = along(axis, self.cursor_pos - track.position);
+                            let ratio = ((cursor - grab) / free).clamp(0., 1.);
+                            let offset = ratio * self.extent(axis).max_offset();
+                            self.scroll_axis_to(axis, offset, event, messages);
+                        }
+                    }
+                }
+            }
+            event::Event::Mouse(event::Mouse::WheelScrolled { delta }) => {
+                if !self.is_cursor_inside(self.cursor_pos) {
+                    return EventStatus::Ignored;
+                }
+                // A nested scrollable widget under the cursor scrolls first
+                if propagate_event(&self.children, event, messages) == EventStatus::Captured {
+                    return EventStatus::Captured;
+                }
+                let event::ScrollDelta::Pixels { x, y } = delta;
+                // Shift maps the vertical scroll to a horizontal one
+                let delta = if event::modifiers().shift {
+                    Vector2D::new(y, x)
+                } else {
+                    Vector2D::new(x, y)
+                };
+                self.scroll_to(self.scroll + delta, event, messages);
+                return EventStatus::Captured;
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                for axis in [Axis::Horizontal, Axis::Vertical] {
+                    if let Some((track, start, length)) = self.thumb(axis) {
+                        if !track.contains(self.cursor_pos) {
+                            continue;
+                        }
+                        let extent = self.extent(axis);
+                        let cursor = along(axis, self.cursor_pos - track.position);
+                        if cursor < start {
+                            // Move by a page towards the cursor
+                            let offset = extent.offset - extent.viewport;
+                            self.scroll_axis_to(axis, offset, event, messages);
+                        } else if cursor > start + length {
+                            let offset = extent.offset + extent.viewport;
+                            self.scroll_axis_to(axis, offset, event, messages);
+                        } else {
+                            self.thumb_grab = Some((axis, cursor - start));
+                            self.capture_pointer();
+                        }
+                        return EventStatus::Captured;
+                    }
+                }
+                // The corner gutter does not reach the content below it
+                if self
+                    .gutter()
+                    .is_some_and(|gutter| gutter.contains(self.cursor_pos))
+                {
+                    return EventStatus::Captured;
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                self.thumb_grab = None;
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        // The scroll bars and the corner gutter are drawn over the content
+        let mut instructions = Vec::new();
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            if let Some((track, start, length)) = self.thumb(axis) {
+                let thumb = match axis {
+                    Axis::Horizontal => Rect::new(
+                        Vector2D::new(track.position.x + start, track.position.y),
+                        Vector2D::new(length, track.size.y),
+                    ),
+                    Axis::Vertical => Rect::new(
+                        Vector2D::new(track.position.x, track.position.y + start),
+                        Vector2D::new(track.size.x, length),
+                    ),
+                };
+                for (rect, color) in [(track, self.track_color), (thumb, self.thumb_color)] {
+                    instructions.push(RenderInstruction::DrawRect {
+                        point: rect.position,
+                        size: rect.size,
+                        color,
+                        clip_point: self.position,
+                        clip_size: self.size,
+                        blend_mode: BlendMode::Normal,
+                    });
+                }
+            }
+        }
+        if let Some(gutter) = self.gutter() {
+            instructions.push(RenderInstruction::DrawRect {
+                point: gutter.position,
+                size: gutter.size,
+                color: self.gutter_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+        instructions
+    }
+
+    fn clip_shape(&self) -> Option<ClipShape> {
+        let viewport = self.viewport();
+        Some(ClipShape::Rect {
+            point: viewport.position,
+            size: viewport.size,
+        })
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        let viewport = Rect::new(area.position, self.viewport().size);
+        self.children
+            .iter()
+            .map(|child| {
+                let size = child.upgrade()?.borrow_mut().original_size();
+                // The children scrolled out of the viewport are hidden
+                let child_area = Rect::new(area.position - self.scroll, size);
+                if child_area.intersects(viewport) {
+                    Some(child_area)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.track_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.thumb_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.gutter_color = self.style.background_color.unwrap_or(theme.palette.muted);
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,651 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{propagate_event, Axis, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::{Cell, RefCell};
+use std::rc::Weak;
+
+/// Minimum length of the scroll bar's thumb
+const MIN_THUMB: f64 = 16.;
+
+/// Extent of the content of a scrollable widget along its axis
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollExtent {
+    /// The distance the content is scrolled by
+    pub offset: f64,
+
+    /// The length of the whole content
+    pub content: f64,
+
+    /// The length of the visible part of the content (i.e., the viewport)
+    pub viewport: f64,
+}
+
+impl ScrollExtent {
+    /// Creates a new `ScrollExtent`
+    ///
+    /// # Returns
+    /// The scroll extent created
+    ///
+    /// # Arguments
+    /// * `offset` - the distance the content is scrolled by
+    /// * `content` - the length of the whole content
+    /// * `viewport` - the length of the visible part of the content
+    pub fn new(offset: f64, content: f64, viewport: f64) -> ScrollExtent {
+        ScrollExtent {
+            offset,
+            content,
+            viewport,
+        }
+    }
+
+    /// Gets the largest offset, at which the end of the content is visible
+    ///
+    /// # Returns
+    /// The largest offset, or 0 if the content fits in the viewport
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn max_offset(&self) -> f64 {
+        (self.content - self.viewport).max(0.)
+    }
+}
+
+/// Widget whose content can be scrolled, which a [`ScrollBarWidget`] can be linked to
+pub trait Scrollable {
+    /// Gets the extent of the widget's content along its axis
+    ///
+    /// # Returns
+    /// The offset, the length of the content and the length of the viewport
+    ///
+    /// # Arguments
+    /// No arguments
+    fn extent(&self) -> ScrollExtent;
+
+    /// Scrolls the widget's content
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `offset` - the distance to scroll the content by, from its start
+    fn scroll_to_offset(&mut self, offset: f64);
+}
+
+/// Scroll bar is a widget displaying a track and a thumb, whose length and position
+/// reflect the portion of the content visible and how far it is scrolled.
+///
+/// Dragging the thumb, or clicking the track to move by a page, scrolls the content
+/// and handles the `on_scroll` message, filled with the new offset when it is a
+/// [`ValueChanged<f64>`](`ValueChanged`). The scroll bar may be linked to a
+/// [`Scrollable`] widget (e.g. a
+/// [`SliverViewWidget`](`crate::widget::sliver_view::SliverViewWidget`)), which it then
+/// scrolls and follows, even when scrolled by other means (e.g. the mouse wheel).
+/// Otherwise, the application sets the extent displayed with
+/// [`set_extent`](`ScrollBarWidget::set_extent`) and scrolls the content itself when
+/// handling the `on_scroll` message.
+///
+/// The linked widget is not scrolled while it is being built or handling an event,
+/// so the scroll bar should not be one of its descendants.
+#[derive(Clone)]
+pub struct ScrollBarWidget {
+    /// The scroll bar's identifier
+    id: usize,
+
+    /// The data attached to the scroll bar through its handle
+    widget_data: WidgetData,
+
+    /// The axis along which the thumb moves
+    axis: Axis,
+
+    /// The widget scrolled by the scroll bar, if linked
+    scrollable: Option<Weak<RefCell<dyn Scrollable>>>,
+
+    /// The extent displayed when not linked to a widget, or the last extent read from
+    /// the linked widget
+    extent: Cell<ScrollExtent>,
+
+    /// The extent displayed on the last build, used to rebuild the scroll bar when
+    /// the linked widget is scrolled
+    drawn_extent: Cell<Option<ScrollExtent>>,
+
+    /// The distance between the cursor and the start of the thumb, if the thumb is being dragged
+    thumb_grab: Option<f64>,
+
+    /// The message to be handled when the scroll bar scrolls the content
+    on_scroll: Option<Box<dyn Message>>,
+
+    /// The track's color
+    track_color: Color,
+
+    /// The thumb's color
+    thumb_color: Color,
+
+    /// The cursor's position
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The scroll bar's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The scroll bar's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The scroll bar's current size (width and height)
+    size: Vector2D,
+
+    /// The scroll bar's original size (width and height)
+    original_size: Vector2D,
+
+    /// The scroll bar's layout
+    layout: Layout,
+
+    /// The scroll bar's offset vector coordinates
+    offset: Vector2D,
+
+    /// The scroll bar's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl ScrollBarWidget {
+    /// Creates a new `ScrollBarWidget`, not linked to any widget
+    ///
+    /// # Returns
+    /// The scroll bar created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the scroll bar
+    /// * `axis` - the axis along which the thumb moves
+    /// * `track_color` - the color to be assigned to the track
+    /// * `thumb_color` - the color to be assigned to the thumb
+    /// * `on_scroll` - the message to be handled when the scroll bar scrolls the content
+    pub fn new(
+        size: Vector2D,
+        axis: Axis,
+        track_color: Color,
+        thumb_color: Color,
+        on_scroll: Option<Box<dyn Message>>,
+    ) -> ScrollBarWidget {
+        ScrollBarWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_muted_color(track_color)
+                .with_accent_color(thumb_color),
+            axis,
+            scrollable: None,
+            extent: Cell::new(ScrollExtent::new(0., 0., 0.)),
+            drawn_extent: Cell::new(None),
+            thumb_grab: None,
+            on_scroll,
+            track_color,
+            thumb_color,
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Links the scroll bar to a widget, which it then scrolls and follows
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `scrollable` - the widget to be scrolled, or `None` to unlink the scroll bar
+    pub fn link(&mut self, scrollable: Option<Weak<RefCell<dyn Scrollable>>>) {
+        self.scrollable = scrollable;
+        self.dirty = true;
+    }
+
+    /// Gets the extent displayed by the scroll bar
+    ///
+    /// # Returns
+    /// The extent of the linked widget, if any, or the extent set otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn extent(&self) -> ScrollExtent {
+        let linked = self
+            .scrollable
+            .as_ref()
+            .and_then(|scrollable| scrollable.upgrade())
+            .and_then(|scrollable| {
+                scrollable
+                    .try_borrow()
+                    .ok()
+                    .map(|scrollable| scrollable.extent())
+            });
+        // The linked widget cannot be read while borrowed, so its last extent is kept
+        if let Some(extent) = linked {
+            self.extent.set(extent);
+        }
+        self.extent.get()
+    }
+
+    /// Sets the extent displayed when the scroll bar is not linked to a widget
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `extent` - the extent to be displayed
+    pub fn set_extent(&mut self, extent: ScrollExtent) {
+        self.extent.set(extent);
+        self.dirty = true;
+    }
+
+    /// Sets the message to be handled when the scroll bar scrolls the content
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_scroll` - the message to be handled when the scroll bar scrolls the content
+    pub fn set_message(&mut self, on_scroll: Option<Box<dyn Message>>) {
+        self.on_scroll = on_scroll;
+    }
+
+    /// Gets the start and length of the thumb along the axis, for internal use only
+    ///
+    /// The thumb's length is proportional to the viewport-to-content ratio.
+    ///
+    /// # Returns
+    /// The thumb's start, relative to the start of the track, and its length, or
+    /// `None` if the content fits in the viewport
+    ///
+    /// # Arguments
+    /// * `extent` - the extent displayed
+    fn thumb(&self, extent: ScrollExtent) -> Option<(f64, f64)> {
+        let track = self.track_length();
+        if extent.content <= extent.viewport || extent.content <= 0. {
+            return None;
+        }
+        let length = (track * extent.viewport / extent.content)
+            .max(MIN_THUMB)
+            .min(track);
+        let start = (extent.offset / extent.max_offset()).clamp(0., 1.) * (track - length);
+        Some((start, length))
+    }
+
+    /// Gets the length of the track along the axis, for internal use only
+    ///
+    /// # Returns
+    /// The length of the track
+    ///
+    /// # Arguments
+    /// No arguments
+    fn track_length(&self) -> f64 {
+        match self.axis {
+            Axis::Horizontal => self.size.x,
+            Axis::Vertical => self.size.y,
+        }
+    }
+
+    /// Gets the cursor's coordinate along the axis, relative to the start of the
+    /// track, for internal use only
+    ///
+    /// # Returns
+    /// The cursor's coordinate along the axis
+    ///
+    /// # Arguments
+    /// No arguments
+    fn cursor_along_axis(&self) -> f64 {
+        match self.axis {
+            Axis::Horizontal => self.cursor_pos.x - self.position.x,
+            Axis::Vertical => self.cursor_pos.y - self.position.y,
+        }
+    }
+
+    /// Scrolls the content, handling the `on_scroll` message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `offset` - the offset to be scrolled to, clamped to the content
+    /// * `event` - the event that scrolled the content
+    /// * `messages` - queue of messages
+    fn scroll_to(&mut self, offset: f64, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        let extent = self.extent();
+        let offset = offset.clamp(0., extent.max_offset());
+        if offset == extent.offset {
+            return;
+        }
+        self.extent.set(ScrollExtent { offset, ..extent });
+        if let Some(scrollable) = self
+            .scrollable
+            .as_ref()
+            .and_then(|scrollable| scrollable.upgrade())
+        {
+            if let Ok(mut scrollable) = scrollable.try_borrow_mut() {
+                scrollable.scroll_to_offset(offset);
+            }
+        }
+        if let Some(mut message) = self.on_scroll.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<f64>>() {
+                message.value = offset;
+            }
+            messages.enqueue(message);
+        }
+        self.dirty = true;
+    }
+
+    /// Sets the scroll bar's size (width and height), returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the scroll bar
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the scroll bar's axis, returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the axis assigned
+    ///
+    /// # Arguments
+    /// * `axis` - the axis along which the thumb moves
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Sets the widget scrolled by the scroll bar, returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar linked to the widget
+    ///
+    /// # Arguments
+    /// * `scrollable` - the widget to be scrolled
+    pub fn with_scrollable(mut self, scrollable: Weak<RefCell<dyn Scrollable>>) -> Self {
+        self.link(Some(scrollable));
+        self
+    }
+
+    /// Sets the extent displayed when not linked, returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the extent assigned
+    ///
+    /// # Arguments
+    /// * `extent` - the extent to be displayed
+    pub fn with_extent(mut self, extent: ScrollExtent) -> Self {
+        self.set_extent(extent);
+        self
+    }
+
+    /// Sets the scroll bar's message to be handled when scrolling, returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the given message to be handled when scrolling
+    ///
+    /// # Arguments
+    /// * `on_scroll` - the message to be handled when the scroll bar scrolls the content
+    pub fn with_on_scroll(mut self, on_scroll: Option<Box<dyn Message>>) -> Self {
+        self.on_scroll = on_scroll;
+        self
+    }
+
+    /// Sets the scroll bar's thumb color, returning the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the thumb color assigned
+    ///
+    /// # Arguments
+    /// * `thumb_color` - the color to be assigned to the thumb
+    pub fn with_thumb_color(mut self, thumb_color: Color) -> Self {
+        self.style.accent_color = Some(thumb_color);
+        self.thumb_color = thumb_color;
+        self
+    }
+
+    /// Sets the scroll bar's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the scroll bar
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the scroll bar's style, overriding the properties of the current theme, returning
+    /// the scroll bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The scroll bar with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the scroll bar
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for ScrollBarWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        ScrollBarWidget {
+            style: Style::new(),
+            ..ScrollBarWidget::new(
+                Vector2D::new(12., 160.),
+                Axis::Vertical,
+                theme.palette.muted,
+                theme.palette.accent,
+                None,
+            )
+        }
+    }
+}
+
+impl Widget for ScrollBarWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                // Drag the thumb, mapping its position back to the offset
+                if let Some(grab) = self.thumb_grab {
+                    let extent = self.extent();
+                    if let Some((_, length)) = self.thumb(extent) {
+                        let free = self.track_length() - length;
+                        if free > 0. {
+                            let ratio = ((self.cursor_along_axis() - grab) / free).clamp(0., 1.);
+                            self.scroll_to(ratio * extent.max_offset(), event, messages);
+                        }
+                    }
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                let extent = self.extent();
+                if let Some((start, length)) = self.thumb(extent) {
+                    let cursor = self.cursor_along_axis();
+                    if cursor < start {
+                        // Move by a page towards the cursor
+                        self.scroll_to(extent.offset - extent.viewport, event, messages);
+                    } else if cursor > start + length {
+                        self.scroll_to(extent.offset + extent.viewport, event, messages);
+                    } else {
+                        self.thumb_grab = Some(cursor - start);
+                        self.capture_pointer();
+                    }
+                }
+                return EventStatus::Captured;
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                self.thumb_grab = None;
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let extent = self.extent();
+        self.drawn_extent.set(Some(extent));
+
+        let mut instructions = vec![RenderInstruction::DrawRect {
+            point: self.position,
+            size: self.size,
+            color: self.track_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        }];
+
+        if let Some((start, length)) = self.thumb(extent) {
+            let thumb = match self.axis {
+                Axis::Horizontal => Rect::new(
+                    Vector2D::new(self.position.x + start, self.position.y),
+                    Vector2D::new(length, self.size.y),
+                ),
+                Axis::Vertical => Rect::new(
+                    Vector2D::new(self.position.x, self.position.y + start),
+                    Vector2D::new(self.size.x, length),
+                ),
+            };
+            instructions.push(RenderInstruction::DrawRect {
+                point: thumb.position,
+                size: thumb.size,
+                color: self.thumb_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        // The linked widget may have been scrolled by other means since the last build
+        self.dirty || self.drawn_extent.get() != Some(self.extent())
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.track_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.thumb_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
+}
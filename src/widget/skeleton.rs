@@ -0,0 +1,507 @@
+use crate::clock;
+use crate::event::Event;
+use crate::renderer::{BlendMode, ClipShape, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{
+    deliver_frame, is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData,
+};
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::time::Instant;
+
+/// Time taken by the shimmer to sweep across the placeholder, in milliseconds
+const SHIMMER_PERIOD: u128 = 1500;
+
+/// Width of the shimmer relative to the placeholder's width
+const SHIMMER_WIDTH: f64 = 0.4;
+
+/// Number of steps of the shimmer, from transparent to the highlight color and back
+const SHIMMER_STEPS: usize = 8;
+
+/// Height of the [`SkeletonShape::Line`] placeholder relative to the skeleton's height
+const LINE_HEIGHT: f64 = 0.6;
+
+/// Shape of the placeholder displayed by a skeleton
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SkeletonShape {
+    /// A bar with rounded ends, vertically centered (e.g. standing for a line of text)
+    #[default]
+    Line,
+    /// A rectangle filling the skeleton (e.g. standing for an image or a card)
+    Rect,
+    /// A circle centered on the skeleton (e.g. standing for an avatar)
+    Circle,
+}
+
+/// Skeleton is a widget that displays a shimmering placeholder shape while content
+/// loads. It can wrap the widget being loaded, displaying it instead of the
+/// placeholder as soon as the widget reports being ready, through
+/// [`is_ready`](`Widget::is_ready`).
+#[derive(Clone)]
+pub struct SkeletonWidget {
+    /// The skeleton's identifier
+    id: usize,
+
+    /// The data attached to the skeleton through its handle
+    widget_data: WidgetData,
+
+    /// The shape of the skeleton's placeholder
+    shape: SkeletonShape,
+
+    /// The color of the skeleton's placeholder
+    base_color: Color,
+
+    /// The color of the shimmer sweeping across the skeleton's placeholder
+    highlight_color: Color,
+
+    /// The instant when the shimmer started sweeping
+    shimmer_start: Instant,
+
+    /// The widget being loaded, displayed once ready
+    content: Option<Rc<RefCell<dyn Widget>>>,
+
+    /// Whether the widget being loaded was ready on the last frame
+    is_ready: bool,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The skeleton's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The skeleton's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The skeleton's current size (width and height)
+    size: Vector2D,
+
+    /// The skeleton's original size (width and height)
+    original_size: Vector2D,
+
+    /// The skeleton's layout
+    layout: Layout,
+
+    /// The skeleton's offset vector coordinates
+    offset: Vector2D,
+
+    /// The skeleton's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl SkeletonWidget {
+    /// Creates a new `SkeletonWidget`
+    ///
+    /// # Returns
+    /// The skeleton created, displaying its placeholder
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the skeleton
+    /// * `shape` - the shape to be assigned to the skeleton's placeholder
+    /// * `base_color` - the color to be assigned to the skeleton's placeholder
+    /// * `highlight_color` - the color to be assigned to the shimmer
+    pub fn new(
+        size: Vector2D,
+        shape: SkeletonShape,
+        base_color: Color,
+        highlight_color: Color,
+    ) -> SkeletonWidget {
+        SkeletonWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_muted_color(base_color)
+                .with_background_color(highlight_color),
+            shape,
+            base_color,
+            highlight_color,
+            shimmer_start: clock::now(),
+            content: None,
+            is_ready: false,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the shape of the skeleton's placeholder
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `shape` - the shape to be assigned to the skeleton's placeholder
+    pub fn set_shape(&mut self, shape: SkeletonShape) {
+        self.shape = shape;
+        self.dirty = true;
+    }
+
+    /// Sets the widget being loaded, displayed instead of the placeholder once it is ready
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `content` - the widget being loaded, or `None` to keep displaying the placeholder
+    pub fn set_content(&mut self, content: Option<Rc<RefCell<dyn Widget>>>) {
+        self.children = content.iter().map(Rc::downgrade).collect();
+        self.content = content;
+        self.shimmer_start = clock::now();
+        self.is_ready = self.is_content_ready();
+        self.set_dirty(true);
+    }
+
+    /// Checks whether the widget being loaded is ready, for internal use only
+    ///
+    /// # Returns
+    /// True, if there is a widget being loaded and it is ready, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    fn is_content_ready(&self) -> bool {
+        self.content
+            .as_ref()
+            .is_some_and(|content| content.borrow().is_ready())
+    }
+
+    /// Gets the shape clipping the skeleton's placeholder, for internal use only
+    ///
+    /// # Returns
+    /// The shape of the placeholder, on the skeleton's area
+    ///
+    /// # Arguments
+    /// No arguments
+    fn placeholder_shape(&self) -> ClipShape {
+        match self.shape {
+            SkeletonShape::Line => {
+                let height = self.size.y * LINE_HEIGHT;
+                ClipShape::RoundedRect {
+                    point: Vector2D::new(
+                        self.position.x,
+                        self.position.y + (self.size.y - height) * 0.5,
+                    ),
+                    size: Vector2D::new(self.size.x, height),
+                    radius: height * 0.5,
+                }
+            }
+            SkeletonShape::Rect => ClipShape::Rect {
+                point: self.position,
+                size: self.size,
+            },
+            SkeletonShape::Circle => ClipShape::Circle {
+                point: self.position + self.size * 0.5,
+                r: self.size.x.min(self.size.y) * 0.5,
+            },
+        }
+    }
+
+    /// Sets the skeleton's size (width and height), returning the skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the skeleton
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the shape of the skeleton's placeholder, returning the skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the shape assigned
+    ///
+    /// # Arguments
+    /// * `shape` - the shape to be assigned to the skeleton's placeholder
+    pub fn with_shape(mut self, shape: SkeletonShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets the color of the skeleton's placeholder, returning the skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the color of the placeholder assigned
+    ///
+    /// # Arguments
+    /// * `base_color` - the color to be assigned to the skeleton's placeholder
+    pub fn with_base_color(mut self, base_color: Color) -> Self {
+        self.style.muted_color = Some(base_color);
+        self.base_color = base_color;
+        self
+    }
+
+    /// Sets the color of the skeleton's shimmer, returning the skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the color of the shimmer assigned
+    ///
+    /// # Arguments
+    /// * `highlight_color` - the color to be assigned to the shimmer
+    pub fn with_highlight_color(mut self, highlight_color: Color) -> Self {
+        self.style.background_color = Some(highlight_color);
+        self.highlight_color = highlight_color;
+        self
+    }
+
+    /// Sets the widget being loaded, returning the skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the widget being loaded assigned
+    ///
+    /// # Arguments
+    /// * `content` - the widget being loaded, displayed once ready
+    pub fn with_content(mut self, content: Rc<RefCell<dyn Widget>>) -> Self {
+        self.set_content(Some(content));
+        self
+    }
+
+    /// Sets the skeleton's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the skeleton
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the skeleton's style, overriding the properties of the current theme, returning the
+    /// skeleton (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The skeleton with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the skeleton
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for SkeletonWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        SkeletonWidget {
+            style: Style::new(),
+            ..SkeletonWidget::new(
+                Vector2D::new(100., 20.),
+                SkeletonShape::Line,
+                theme.palette.muted,
+                theme.palette.surface,
+            )
+        }
+    }
+}
+
+impl Widget for SkeletonWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        // The widget being loaded only handles events once displayed
+        if self.is_ready {
+            propagate_event(&self.children, event, messages)
+        } else {
+            EventStatus::Ignored
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        if self.is_ready {
+            return vec![];
+        }
+
+        let mut instructions = vec![
+            RenderInstruction::PushClip {
+                shape: self.placeholder_shape(),
+            },
+            // Placeholder
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.base_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Shimmer sweeping from the left edge to the right edge, fading in and out
+        let width = self.size.x * SHIMMER_WIDTH;
+        let step = width / SHIMMER_STEPS as f64;
+        let progress = (clock::elapsed(self.shimmer_start).as_millis() % SHIMMER_PERIOD) as f64
+            / SHIMMER_PERIOD as f64;
+        let start = self.position.x - width + progress * (self.size.x + width);
+        for index in 0..SHIMMER_STEPS {
+            let distance = (index as f64 + 0.5) / SHIMMER_STEPS as f64 * 2. - 1.;
+            instructions.push(RenderInstruction::DrawRect {
+                point: Vector2D::new(start + index as f64 * step, self.position.y),
+                color: self.highlight_color.with_opacity(1. - distance.abs()),
+                size: Vector2D::new(step, self.size.y),
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+
+        instructions.push(RenderInstruction::PopClip);
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        // The widget being loaded fills the skeleton, once ready
+        let child_area = if self.is_ready { Some(area) } else { None };
+        vec![child_area; self.children.len()]
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        // The shimmer needs to be redrawn on every frame while the placeholder is displayed
+        self.dirty || !self.is_ready
+    }
+
+    fn is_ready(&self) -> bool {
+        self.is_ready
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, _cursor_pos: Vector2D) -> bool {
+        false
+    }
+
+    fn on_frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        for value in self.children.iter_mut() {
+            if let Some(child) = value.upgrade() {
+                deliver_frame(&mut *child.borrow_mut(), messages);
+            }
+        }
+
+        // Swap the placeholder and the widget being loaded when it becomes (or stops being) ready
+        let is_ready = self.is_content_ready();
+        if is_ready != self.is_ready {
+            self.is_ready = is_ready;
+            self.shimmer_start = clock::now();
+            self.set_dirty(true);
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.base_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.highlight_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.dirty = true;
+    }
+}
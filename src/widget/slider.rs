@@ -1,8 +1,11 @@
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::value_editor::{EditEvent, ValueEditor};
+use crate::widget::{propagate_event, EventStatus, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -20,10 +23,18 @@ pub struct Position {
 /// by sliding a button within a bounded interval. The button 
 /// is always positioned at the points that match integer values
 /// within the specified interval.
+///
+/// Double-clicking the slider swaps in a text field pre-filled with its value (see
+/// [`ValueEditor`]), where a value or an arithmetic expression can be typed. Pressing
+/// Enter assigns the value typed, clamped to the range and rounded to the nearest step,
+/// while pressing Escape keeps the current value.
 #[derive(Clone)]
 pub struct SliderWidget {
     /// The slider's identifier
     id: usize,
+
+    /// The data attached to the slider through its handle
+    widget_data: WidgetData,
     
     /// The slider background color
     background_color: Color,
@@ -79,6 +90,19 @@ pub struct SliderWidget {
     
     /// The slider's offset vector coordinates
     offset: Vector2D,
+
+    /// Whether the slider's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// Whether double-clicking the slider swaps in a text field to type its value
+    is_value_editable: bool,
+
+    /// The text field swapped in to type the slider's value
+    editor: ValueEditor,
+
+    /// The slider's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl SliderWidget {
@@ -110,6 +134,10 @@ impl SliderWidget {
             SliderWidget::get_slider_positions(range.0, range.1, step, Vector2D::new(0., 0.), size);
         SliderWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_accent_color(button_color),
             background_color: background_color,
             button_color: button_color,
             button_size: button_size,
@@ -128,6 +156,9 @@ impl SliderWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            is_value_editable: true,
+            editor: ValueEditor::default(),
         }
     }
 
@@ -153,6 +184,19 @@ impl SliderWidget {
         self.slider_value
     }
 
+    /// Sets the slider's current value, without handling the message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `slider_value` - the current value to be assigned to the slider
+    pub fn set_value(&mut self, slider_value: i32) {
+        self.slider_value = slider_value;
+        self.update_slider_positions();
+        self.dirty = true;
+    }
+
     /// Gets all the possible slider positions for a given configuration, for internal use only
     ///
     /// # Returns
@@ -205,10 +249,288 @@ impl SliderWidget {
         }
         0
     }
+
+    /// Recomputes the slider's possible positions and current index, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn update_slider_positions(&mut self) {
+        self.slider_positions = SliderWidget::get_slider_positions(
+            self.range.0,
+            self.range.1,
+            self.step,
+            self.position,
+            self.size,
+        );
+        self.slider_index = SliderWidget::get_slider_index(self.slider_value, &self.slider_positions);
+    }
+
+    /// Assigns a value typed on the text field, clamped to the slider's range and rounded
+    /// to the nearest step, handling the message if it changed, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `value` - the value typed
+    /// * `event` - the event that committed the value
+    /// * `messages` - queue of messages
+    fn commit_value(
+        &mut self,
+        value: f64,
+        event: Event,
+        messages: &mut Queue<Box<dyn Message>>,
+    ) {
+        self.dirty = true;
+        let distance = |position: &Position| (position.slider_value as f64 - value).abs();
+        let nearest = self
+            .slider_positions
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)));
+        if let Some((index, position)) = nearest {
+            if position.slider_value != self.slider_value {
+                self.slider_index = index;
+                self.slider_value = position.slider_value;
+                self.on_value_changed(event, messages);
+            }
+        }
+    }
+
+    /// Handles the message of the slider with its new value, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that changed the value
+    /// * `messages` - queue of messages
+    fn on_value_changed(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(mut message) = self.on_slide.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<i32>>() {
+                message.value = self.slider_value;
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Sets whether double-clicking the slider swaps in a text field to type its value
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_value_editable` - whether the value can be typed
+    pub fn set_value_editable(&mut self, is_value_editable: bool) {
+        self.is_value_editable = is_value_editable;
+    }
+
+    /// Sets whether the slider's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the slider's size (width and height), returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the slider
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self.update_slider_positions();
+        self
+    }
+
+    /// Sets the slider's background color, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the slider
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the slider's button color, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the button color assigned
+    ///
+    /// # Arguments
+    /// * `button_color` - the button color to be assigned to the slider
+    pub fn with_button_color(mut self, button_color: Color) -> Self {
+        self.style.accent_color = Some(button_color);
+        self.button_color = button_color;
+        self
+    }
+
+    /// Sets the slider's button size (width and height), returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the button size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `button_size` - the button size (width and height) to be assigned to the slider
+    pub fn with_button_size(mut self, button_size: Vector2D) -> Self {
+        self.button_size = button_size;
+        self
+    }
+
+    /// Sets the slider's range of values (start and end), returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the range of values (start and end) assigned
+    ///
+    /// # Arguments
+    /// * `range` - the range of values (start and end) to be assigned to the slider
+    pub fn with_range(mut self, range: (i32, i32)) -> Self {
+        self.range = range;
+        self.update_slider_positions();
+        self
+    }
+
+    /// Sets the slider's step between values, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the step between values assigned
+    ///
+    /// # Arguments
+    /// * `step` - the step between values to be assigned to the slider
+    pub fn with_step(mut self, step: i32) -> Self {
+        self.step = step;
+        self.update_slider_positions();
+        self
+    }
+
+    /// Sets the slider's current value, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the current value assigned
+    ///
+    /// # Arguments
+    /// * `slider_value` - the current value to be assigned to the slider
+    pub fn with_value(mut self, slider_value: i32) -> Self {
+        self.slider_value = slider_value;
+        self.update_slider_positions();
+        self
+    }
+
+    /// Sets the slider's message to be handled when the value changes, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the given message to be handled when the value changes
+    ///
+    /// # Arguments
+    /// * `on_slide` - the message to be handled when the value changes
+    pub fn with_on_slide(mut self, on_slide: Option<Box<dyn Message>>) -> Self {
+        self.on_slide = on_slide;
+        self
+    }
+
+    /// Sets whether the slider's value can be typed, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the value editing enabled or disabled
+    ///
+    /// # Arguments
+    /// * `is_value_editable` - whether double-clicking the slider swaps in a text field to type its value
+    pub fn with_value_editable(mut self, is_value_editable: bool) -> Self {
+        self.is_value_editable = is_value_editable;
+        self
+    }
+
+    /// Sets the text field swapped in to type the slider's value, returning the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the text field assigned
+    ///
+    /// # Arguments
+    /// * `editor` - the text field, styled with its colors
+    pub fn with_value_editor(mut self, editor: ValueEditor) -> Self {
+        self.editor = editor;
+        self
+    }
+
+    /// Sets the slider's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the slider
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the slider's style, overriding the properties of the current theme, returning
+    /// the slider (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The slider with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the slider
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for SliderWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        SliderWidget {
+            style: Style::new(),
+            ..SliderWidget::new(
+                Vector2D::new(200., 20.),
+                theme.palette.muted,
+                theme.palette.accent,
+                Vector2D::new(10., 20.),
+                (0, 100),
+                1,
+                0,
+                None,
+            )
+        }
+    }
 }
 
 impl Widget for SliderWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        // While the text field is swapped in, it takes every event
+        if self.editor.is_editing() {
+            if let event::Event::Mouse(event::Mouse::CursorMoved { x, y }) = event {
+                self.cursor_pos = Vector2D::new(x as f64, y as f64);
+            }
+            let area = Rect::new(self.position, self.size);
+            match self.editor.on_event(event, area, self.cursor_pos) {
+                Some(EditEvent::Committed(value)) => self.commit_value(value, event, messages),
+                Some(_) => self.dirty = true,
+                None => {}
+            }
+            return EventStatus::Captured;
+        }
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 //update cursor_pos on mouse move
@@ -223,19 +545,31 @@ impl Widget for SliderWidget {
                     }
                     //update the UI when we make a move on slider
                     self.set_dirty(true);
+                    EventStatus::Captured
                 } else {
-                    for value in self.children.iter_mut() {
-                        if let Some(child) = value.upgrade() {
-                            child.borrow_mut().on_event(event, messages);
-                        }
-                    }
+                    propagate_event(&self.children, event, messages)
                 }
             }
+            event::Event::Mouse(event::Mouse::ButtonDoubleClicked(event::MouseButton::Left))
+                if self.is_value_editable
+                    && Rect::new(self.position, self.size).contains(self.cursor_pos) =>
+            {
+                //swap in the text field on double-click, dropping the drag started by the press
+                self.is_pressed = false;
+                self.editor.begin(&self.slider_value.to_string());
+                self.dirty = true;
+                EventStatus::Captured
+            }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
-                //if cursor is inside slider button switch the state of is_pressed
                 if self.is_cursor_inside(self.cursor_pos) {
+                    //if cursor is inside slider button switch the state of is_pressed
                     self.is_pressed = true;
+                    //keep receiving the cursor moves and the release outside of the slider
+                    self.capture_pointer();
+                } else {
+                    return EventStatus::Ignored;
                 }
+                EventStatus::Captured
             }
             event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
                 if self.is_pressed {
@@ -257,10 +591,7 @@ impl Widget for SliderWidget {
                             }
                             self.slider_value =
                                 self.slider_positions[self.slider_index].slider_value;
-                            if let Some(mut message) = self.on_slide.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
-                            }
+                            self.on_value_changed(event, messages);
                         }
                     } else if self.cursor_pos.x
                         < self.slider_positions[self.slider_index].x_coordinate - half_step_size
@@ -274,24 +605,18 @@ impl Widget for SliderWidget {
                             }
                             self.slider_value =
                                 self.slider_positions[self.slider_index].slider_value;
-                            if let Some(mut message) = self.on_slide.clone() {
-                                message.set_event(event);
-                                messages.enqueue(message);
-                            }
+                            self.on_value_changed(event, messages);
                         }
                     }
                     //update UI
                     self.set_dirty(true);
                     self.is_pressed = false;
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
-            _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
-            }
+            _ => propagate_event(&self.children, event, messages),
         }
     }
 
@@ -303,8 +628,14 @@ impl Widget for SliderWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
-        if self.is_pressed {
+        if self.editor.is_editing() {
+            self.editor.recipe(Rect::new(self.position, self.size))
+        } else if self.is_pressed {
             vec![
                 RenderInstruction::DrawRect {
                     point: self.position,
@@ -312,6 +643,7 @@ impl Widget for SliderWidget {
                     size: self.size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
                 RenderInstruction::DrawRect {
                     point: Vector2D::new(
@@ -322,6 +654,7 @@ impl Widget for SliderWidget {
                     size: self.button_size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
             ]
         } else {
@@ -332,6 +665,7 @@ impl Widget for SliderWidget {
                     size: self.size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
                 RenderInstruction::DrawRect {
                     point: Vector2D::new(
@@ -343,6 +677,7 @@ impl Widget for SliderWidget {
                     size: self.button_size,
                     clip_point: self.position,
                     clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
                 },
             ]
         }
@@ -431,13 +766,9 @@ impl Widget for SliderWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
     
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         let button_upper_left_corner_x =
@@ -454,4 +785,14 @@ impl Widget for SliderWidget {
             false
         }
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.muted);
+        self.button_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
 }
@@ -1,17 +1,60 @@
+use crate::clock;
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Queue, Vector2D};
-use crate::widget::{Axis, Layout, Widget};
+use crate::renderer::{BlendMode, ClipShape, Message, RenderInstruction};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::scrollbar::{ScrollExtent, Scrollable};
+use crate::widget::{
+    deliver_frame, is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget,
+    WidgetData,
+};
 
 use std::cell::RefCell;
-use std::rc::Weak;
+use std::rc::{Rc, Weak};
+use std::time::Instant;
+
+/// Time taken by the scroll indicator to fade in, in milliseconds
+const INDICATOR_FADE_IN: u128 = 150;
+
+/// Time the scroll indicator stays fully visible after the last scroll, in milliseconds
+const INDICATOR_IDLE: u128 = 800;
+
+/// Time taken by the scroll indicator to fade out, in milliseconds
+const INDICATOR_FADE_OUT: u128 = 300;
+
+/// Minimum length of the scroll indicator's thumb
+const INDICATOR_MIN_THUMB: f64 = 16.;
+
+/// Time without overscrolling after which an incomplete pull retracts, in milliseconds
+const PULL_IDLE: u128 = 200;
+
+/// Time taken by the refresh indicator to retract, in milliseconds
+const PULL_RETRACT: u128 = 250;
+
+/// Fraction of the overscroll converted into pull distance
+const PULL_DAMPING: f64 = 0.5;
+
+/// Distance the cursor must move with the left button held to start a drag
+const DRAG_THRESHOLD: f64 = 4.;
+
+/// Radius of the refresh indicator
+const REFRESH_RADIUS: f64 = 10.;
+
+/// Time taken by the refreshing arc to perform a full turn, in milliseconds
+const REFRESH_PERIOD: u128 = 1000;
+
+/// Callback used to lazily create the item at the given index of a sliver view.
+/// Returns `None` while the item is not available (e.g. still being fetched)
+pub type SliverDataSource = Rc<RefCell<dyn FnMut(usize) -> Option<Rc<RefCell<dyn Widget>>>>>;
 
 /// List is a widget that displays multiple widgets in one column.
 #[derive(Clone)]
 pub struct SliverViewWidget {
     /// The list's identifier
     id: usize,
+
+    /// The data attached to the list through its handle
+    widget_data: WidgetData,
     
     /// The list's current size (width and height)
     size: Vector2D,
@@ -34,6 +77,87 @@ pub struct SliverViewWidget {
     
     /// The list's children (i.e., his widgets tree)
     children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// Whether the scroll indicator is displayed while scrolling
+    has_scroll_indicator: bool,
+
+    /// The scroll indicator's thumb color
+    indicator_color: Color,
+
+    /// The scroll indicator's thickness
+    indicator_thickness: f64,
+
+    /// The instant when the scroll indicator started fading in, if it is visible
+    indicator_start: Option<Instant>,
+
+    /// The instant of the last scroll, from which the scroll indicator fades out
+    last_scroll: Instant,
+
+    /// The distance between the cursor and the start of the thumb, if the thumb is being dragged
+    thumb_grab: Option<f64>,
+
+    /// The callback used to lazily create the list's items, if any
+    data_source: Option<SliverDataSource>,
+
+    /// The index of the next item to be requested from the data source
+    next_index: usize,
+
+    /// The items created by the data source (i.e., owned by the list)
+    items: Vec<Rc<RefCell<dyn Widget>>>,
+
+    /// The widget displayed at the end of the list while items are loading
+    placeholder: Option<Rc<RefCell<dyn Widget>>>,
+
+    /// The message to be handled when the end of the list is near
+    on_near_end: Option<Box<dyn Message>>,
+
+    /// The number of items left to be displayed below which the end of the list is near
+    near_end_threshold: usize,
+
+    /// Whether the `on_near_end` message was handled and no items were created since
+    is_near_end_pending: bool,
+
+    /// The message to be handled when the list is pulled to refresh
+    on_refresh: Option<Box<dyn Message>>,
+
+    /// The pull distance needed to refresh
+    refresh_threshold: f64,
+
+    /// The current pull distance (i.e., overscroll at the start of the list)
+    pull: f64,
+
+    /// The instant of the last overscroll
+    last_pull: Instant,
+
+    /// Whether the list is refreshing (i.e., waiting for `finish_refresh`)
+    is_refreshing: bool,
+
+    /// The instant when the refresh indicator started retracting and its pull distance by then
+    retract: Option<(Instant, f64)>,
+
+    /// The cursor's coordinate along the axis and the shift when the middle button
+    /// was pressed, if the list is being panned
+    pan_start: Option<(f64, f64)>,
+
+    /// The size of the zones near the edges where dragging auto-scrolls the list,
+    /// or 0 if auto-scrolling is disabled
+    auto_scroll_zone: f64,
+
+    /// The auto-scrolling speed at the very edge, in pixels per second
+    auto_scroll_speed: f64,
+
+    /// The cursor's position when the left button was pressed, if it is held
+    drag_origin: Option<Vector2D>,
+
+    /// The instant of the last frame, used to compute the auto-scrolling distance
+    last_frame: Instant,
+
+    /// Boolean indicating if the visible content stays put when children are added or removed before it
+    has_scroll_anchoring: bool,
+
+    /// The first child visible on the last frame and its start along the axis, relative
+    /// to the start of the content, if scroll anchoring is enabled
+    anchor: Option<(Weak<RefCell<dyn Widget>>, f64)>,
 }
 
 impl SliverViewWidget {
@@ -48,6 +172,7 @@ impl SliverViewWidget {
     pub fn new(size: Vector2D, axis: Axis) -> SliverViewWidget {
         SliverViewWidget {
             id: 0,
+            widget_data: WidgetData::default(),
             size: size,
             cursor_pos: Vector2D::new(-1., -1.),
             original_size: size,
@@ -55,50 +180,827 @@ impl SliverViewWidget {
             layout: Layout::Sliver(axis, 0., 0),
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            has_scroll_indicator: false,
+            indicator_color: Color::from_hex(0x80000000),
+            indicator_thickness: 6.,
+            indicator_start: None,
+            last_scroll: clock::now(),
+            thumb_grab: None,
+            data_source: None,
+            next_index: 0,
+            items: Vec::new(),
+            placeholder: None,
+            on_near_end: None,
+            near_end_threshold: 0,
+            is_near_end_pending: false,
+            on_refresh: None,
+            refresh_threshold: 64.,
+            pull: 0.,
+            last_pull: clock::now(),
+            is_refreshing: false,
+            retract: None,
+            pan_start: None,
+            auto_scroll_zone: 0.,
+            auto_scroll_speed: 600.,
+            drag_origin: None,
+            last_frame: clock::now(),
+            has_scroll_anchoring: false,
+            anchor: None,
+        }
+    }
+
+    /// Sets the message to be handled when the list is pulled to refresh
+    ///
+    /// Overscrolling at the start of the list by the threshold displays a refresh
+    /// indicator and handles the message. The indicator keeps spinning until
+    /// `finish_refresh` is called.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_refresh` - the message to be handled when the list is pulled to refresh
+    /// * `threshold` - the pull distance needed to refresh
+    pub fn set_on_refresh(&mut self, on_refresh: Option<Box<dyn Message>>, threshold: f64) {
+        self.on_refresh = on_refresh;
+        self.refresh_threshold = threshold;
+    }
+
+    /// Finishes the refresh, retracting the refresh indicator
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn finish_refresh(&mut self) {
+        if self.is_refreshing {
+            self.is_refreshing = false;
+            self.pull = 0.;
+            self.retract = Some((clock::now(), self.refresh_threshold));
+            self.dirty = true;
+        }
+    }
+
+    /// Checks if the list is refreshing
+    ///
+    /// # Returns
+    /// True if the list was pulled to refresh and `finish_refresh` was not called yet, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_refreshing(&self) -> bool {
+        self.is_refreshing
+    }
+
+    /// Gets the current distance of the refresh indicator, for internal use only
+    ///
+    /// # Returns
+    /// The current distance of the refresh indicator from the start of the list
+    ///
+    /// # Arguments
+    /// No arguments
+    fn pull_distance(&self) -> f64 {
+        if self.is_refreshing {
+            return self.refresh_threshold;
+        }
+        let (from, elapsed) = match self.retract {
+            Some((start, from)) => (from, clock::elapsed(start).as_millis()),
+            None => {
+                // An incomplete pull retracts once the overscroll stops
+                let idle = clock::elapsed(self.last_pull).as_millis();
+                (self.pull, idle.saturating_sub(PULL_IDLE))
+            }
+        };
+        (from * (1. - elapsed as f64 / PULL_RETRACT as f64)).max(0.)
+    }
+
+    /// Overscrolls the start of the list, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `delta` - the overscroll, positive when scrolling past the start of the list
+    /// * `messages` - the queue of messages to be handled
+    fn overscroll(&mut self, delta: f64, messages: &mut Queue<Box<dyn Message>>) {
+        if self.is_refreshing {
+            return;
+        }
+        self.pull = (self.pull_distance() + delta * PULL_DAMPING).max(0.);
+        self.last_pull = clock::now();
+        self.retract = None;
+        if self.pull >= self.refresh_threshold {
+            self.is_refreshing = true;
+            if let Some(mut message) = self.on_refresh.clone() {
+                message.set_event(event::Event::Mouse(event::Mouse::WheelScrolled {
+                    delta: event::ScrollDelta::Pixels { x: 0., y: delta },
+                }));
+                messages.enqueue(message);
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Gets the collection of renderer instructions of the refresh indicator, for internal use only
+    ///
+    /// # Returns
+    /// The refresh indicator's instructions, empty if the indicator is hidden
+    ///
+    /// # Arguments
+    /// No arguments
+    fn refresh_recipe(&self) -> Vec<RenderInstruction> {
+        let pull = self.pull_distance();
+        if self.on_refresh.is_none() || pull <= 0. {
+            return vec![];
+        }
+        let center = match self.layout {
+            Layout::Sliver(Axis::Horizontal, _, _) => Vector2D::new(
+                self.position.x + pull - REFRESH_RADIUS,
+                self.position.y + self.size.y * 0.5,
+            ),
+            _ => Vector2D::new(
+                self.position.x + self.size.x * 0.5,
+                self.position.y + pull - REFRESH_RADIUS,
+            ),
+        };
+        let (s_ang, e_ang) = if self.is_refreshing {
+            let elapsed = clock::elapsed(self.last_pull).as_millis() % REFRESH_PERIOD;
+            let start = (elapsed * 360 / REFRESH_PERIOD) as usize;
+            (start, start + 270)
+        } else {
+            let sweep = (pull / self.refresh_threshold).min(1.) * 360.;
+            (270, 270 + sweep as usize)
+        };
+        vec![RenderInstruction::DrawArc {
+            point: center,
+            r: REFRESH_RADIUS as usize,
+            s_ang,
+            e_ang,
+            color: self.indicator_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        }]
+    }
+
+    /// Sets the data source used to lazily create the list's items
+    ///
+    /// Items are requested by index, in order, whenever the content is not enough
+    /// to fill the visible area. Items created are appended after the list's children.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `data_source` - the callback to be used to create the item at a given index
+    pub fn set_data_source(&mut self, data_source: SliverDataSource) {
+        self.data_source = Some(data_source);
+        self.fetch_items();
+    }
+
+    /// Sets the widget displayed at the end of the list while items are loading
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `placeholder` - the widget to be displayed while items are loading
+    pub fn set_placeholder(&mut self, placeholder: Option<Rc<RefCell<dyn Widget>>>) {
+        self.remove_placeholder();
+        self.placeholder = placeholder;
+        self.fetch_items();
+    }
+
+    /// Sets the message to be handled when the end of the list is near
+    ///
+    /// The message is handled once until new items are created by the data source,
+    /// allowing the application to fetch more data (i.e., infinite scrolling).
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_near_end` - the message to be handled when the end of the list is near
+    /// * `threshold` - the number of items left to be displayed below which the end is near
+    pub fn set_on_near_end(&mut self, on_near_end: Option<Box<dyn Message>>, threshold: usize) {
+        self.on_near_end = on_near_end;
+        self.near_end_threshold = threshold;
+        self.is_near_end_pending = false;
+    }
+
+    /// Notifies the list that its data source has new items available
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn notify_data_changed(&mut self) {
+        self.fetch_items();
+    }
+
+    /// Gets the length of the visible area along the axis, for internal use only
+    ///
+    /// # Returns
+    /// The length of the visible area along the axis
+    ///
+    /// # Arguments
+    /// No arguments
+    fn viewport(&self) -> f64 {
+        match self.layout {
+            Layout::Sliver(Axis::Horizontal, _, _) => self.size.x,
+            _ => self.size.y,
+        }
+    }
+
+    /// Removes the placeholder from the list's children, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn remove_placeholder(&mut self) {
+        if let Some(placeholder) = &self.placeholder {
+            let placeholder = Rc::downgrade(placeholder);
+            self.children.retain(|child| !child.ptr_eq(&placeholder));
+        }
+    }
+
+    /// Requests items from the data source until the visible area is filled, for internal use only
+    ///
+    /// Items are requested until the content covers one more visible area past the
+    /// current one, or until the data source has no item available. In the latter
+    /// case the placeholder is displayed at the end of the list.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn fetch_items(&mut self) {
+        let data_source = match &self.data_source {
+            Some(data_source) => data_source.clone(),
+            None => return,
+        };
+        self.remove_placeholder();
+
+        let (_, shift, mut content) = self.scroll_extent();
+        let limit = shift + 2. * self.viewport();
+        let mut is_loading = false;
+        while content < limit {
+            match (*data_source.borrow_mut())(self.next_index) {
+                Some(item) => {
+                    let size = item.borrow_mut().original_size();
+                    content += match self.layout {
+                        Layout::Sliver(Axis::Horizontal, _, _) => size.x,
+                        _ => size.y,
+                    };
+                    self.children.push(Rc::downgrade(&item));
+                    self.items.push(item);
+                    self.next_index += 1;
+                    self.is_near_end_pending = false;
+                }
+                None => {
+                    is_loading = true;
+                    break;
+                }
+            }
+        }
+
+        if is_loading {
+            if let Some(placeholder) = &self.placeholder {
+                placeholder.borrow_mut().set_dirty(true);
+                self.children.push(Rc::downgrade(placeholder));
+            }
+        }
+        self.set_dirty(true);
+    }
+
+    /// Handles the `on_near_end` message if the end of the list is near, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `messages` - the queue of messages to be handled
+    fn check_near_end(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        if self.is_near_end_pending || self.on_near_end.is_none() {
+            return;
+        }
+
+        let (axis, shift, _) = self.scroll_extent();
+        let visible_end = shift + self.viewport();
+        let placeholder = self.placeholder.as_ref().map(Rc::downgrade);
+        let mut end = 0.;
+        let mut items_left = 0;
+        for child in self.children.iter() {
+            if let Some(placeholder) = &placeholder {
+                if child.ptr_eq(placeholder) {
+                    continue;
+                }
+            }
+            if let Some(child) = child.upgrade() {
+                if end >= visible_end {
+                    items_left += 1;
+                }
+                let size = child.borrow_mut().original_size();
+                end += match axis {
+                    Axis::Horizontal => size.x,
+                    Axis::Vertical => size.y,
+                };
+            }
+        }
+
+        if items_left <= self.near_end_threshold {
+            if let Some(message) = self.on_near_end.clone() {
+                messages.enqueue(message);
+            }
+            self.is_near_end_pending = true;
+        }
+    }
+
+    /// Sets whether the scroll indicator is displayed while scrolling
+    ///
+    /// The indicator fades in when the list is scrolled and fades out after being
+    /// idle. Its thumb can be dragged for fast navigation.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `has_scroll_indicator` - boolean indicating if the scroll indicator is displayed
+    pub fn set_scroll_indicator(&mut self, has_scroll_indicator: bool) {
+        self.has_scroll_indicator = has_scroll_indicator;
+        self.dirty = true;
+    }
+
+    /// Sets the scroll indicator's style
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `color` - the color to be assigned to the scroll indicator's thumb
+    /// * `thickness` - the thickness to be assigned to the scroll indicator
+    pub fn set_scroll_indicator_style(&mut self, color: Color, thickness: f64) {
+        self.indicator_color = color;
+        self.indicator_thickness = thickness;
+        self.dirty = true;
+    }
+
+    /// Sets whether the visible content stays put when children are added or removed
+    /// before it (e.g. new messages loaded above a chat history)
+    ///
+    /// The first child visible is tracked as an anchor and, whenever the children
+    /// before it change, the shift is adjusted by the same amount that the anchor moved.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `has_scroll_anchoring` - boolean indicating if the scroll is anchored to the visible content
+    pub fn set_scroll_anchoring(&mut self, has_scroll_anchoring: bool) {
+        self.has_scroll_anchoring = has_scroll_anchoring;
+        self.anchor = None;
+        if has_scroll_anchoring {
+            self.update_anchor();
+        }
+    }
+
+    /// Gets the start of a child along the axis, relative to the start of the content, for internal use only
+    ///
+    /// # Returns
+    /// The sum of the lengths of the children before the given child, or `None` if
+    /// it is no longer a child of the list
+    ///
+    /// # Arguments
+    /// * `child` - the child whose start is computed
+    fn content_start(&self, child: &Weak<RefCell<dyn Widget>>) -> Option<f64> {
+        let mut start = 0.;
+        for value in self.children.iter() {
+            if value.ptr_eq(child) {
+                return Some(start);
+            }
+            if let Some(value) = value.upgrade() {
+                let size = value.borrow_mut().original_size();
+                start += match self.layout {
+                    Layout::Sliver(Axis::Horizontal, _, _) => size.x,
+                    _ => size.y,
+                };
+            }
+        }
+        None
+    }
+
+    /// Tracks the first visible child as the scroll anchor, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn update_anchor(&mut self) {
+        self.anchor = None;
+        if let Layout::Sliver(axis, shift, _) = self.layout {
+            let mut start = 0.;
+            for value in self.children.iter() {
+                if let Some(child) = value.upgrade() {
+                    let size = child.borrow_mut().original_size();
+                    let length = match axis {
+                        Axis::Horizontal => size.x,
+                        Axis::Vertical => size.y,
+                    };
+                    if start + length > shift {
+                        self.anchor = Some((value.clone(), start));
+                        return;
+                    }
+                    start += length;
+                }
+            }
+        }
+    }
+
+    /// Adjusts the shift by the amount the scroll anchor moved since the last frame,
+    /// such that the visible content stays put, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn anchor_scroll(&mut self) {
+        if let Some((anchor, start)) = self.anchor.take() {
+            if let (Some(new_start), Layout::Sliver(axis, shift, first)) =
+                (self.content_start(&anchor), self.layout.clone())
+            {
+                if new_start != start {
+                    self.layout = Layout::Sliver(axis, (shift + new_start - start).max(0.), first);
+                    self.set_dirty(true);
+                }
+            }
+        }
+        self.update_anchor();
+    }
+
+    /// Gets the axis, current shift and the content's length along the axis, for internal use only
+    ///
+    /// # Returns
+    /// The axis, the current shift and the sum of the children's lengths along the axis
+    ///
+    /// # Arguments
+    /// No arguments
+    fn scroll_extent(&self) -> (Axis, f64, f64) {
+        if let Layout::Sliver(axis, shift, _) = &self.layout {
+            let content = self
+                .children
+                .iter()
+                .filter_map(|value| value.upgrade())
+                .map(|child| {
+                    let size = child.borrow_mut().original_size();
+                    match axis {
+                        Axis::Horizontal => size.x,
+                        Axis::Vertical => size.y,
+                    }
+                })
+                .sum();
+            (*axis, *shift, content)
+        } else {
+            (Axis::Vertical, 0., 0.)
+        }
+    }
+
+    /// Gets the scroll indicator's thumb position and size, for internal use only
+    ///
+    /// The thumb's length is proportional to the content-to-viewport ratio.
+    ///
+    /// # Returns
+    /// The thumb's position and size, or `None` if the content fits in the viewport
+    ///
+    /// # Arguments
+    /// No arguments
+    fn thumb_rect(&self) -> Option<(Vector2D, Vector2D)> {
+        let (axis, shift, content) = self.scroll_extent();
+        let viewport = match axis {
+            Axis::Horizontal => self.size.x,
+            Axis::Vertical => self.size.y,
+        };
+        if content <= viewport || viewport <= 0. {
+            return None;
+        }
+
+        let length = (viewport * viewport / content)
+            .max(INDICATOR_MIN_THUMB)
+            .min(viewport);
+        let start = (shift / (content - viewport)).min(1.) * (viewport - length);
+        match axis {
+            Axis::Horizontal => Some((
+                Vector2D::new(
+                    self.position.x + start,
+                    self.position.y + self.size.y - self.indicator_thickness,
+                ),
+                Vector2D::new(length, self.indicator_thickness),
+            )),
+            Axis::Vertical => Some((
+                Vector2D::new(
+                    self.position.x + self.size.x - self.indicator_thickness,
+                    self.position.y + start,
+                ),
+                Vector2D::new(self.indicator_thickness, length),
+            )),
+        }
+    }
+
+    /// Gets the scroll indicator's opacity, from 0 to 1, for internal use only
+    ///
+    /// # Returns
+    /// The scroll indicator's current opacity
+    ///
+    /// # Arguments
+    /// No arguments
+    fn indicator_opacity(&self) -> f64 {
+        if self.thumb_grab.is_some() {
+            return 1.;
+        }
+        match self.indicator_start {
+            Some(start) => {
+                let fade_in = clock::elapsed(start).as_millis() as f64 / INDICATOR_FADE_IN as f64;
+                let idle = clock::elapsed(self.last_scroll).as_millis();
+                let fade_out = if idle > INDICATOR_IDLE {
+                    1. - (idle - INDICATOR_IDLE) as f64 / INDICATOR_FADE_OUT as f64
+                } else {
+                    1.
+                };
+                fade_in.min(fade_out).clamp(0., 1.)
+            }
+            None => 0.,
+        }
+    }
+
+    /// Shows the scroll indicator, restarting its idle time, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn show_indicator(&mut self) {
+        if self.has_scroll_indicator {
+            if self.indicator_start.is_none() {
+                self.indicator_start = Some(clock::now());
+            }
+            self.last_scroll = clock::now();
+        }
+    }
+
+    /// Scrolls the list to the given shift, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `shift` - the shift to be assigned to the list
+    fn scroll_to(&mut self, shift: f64) {
+        if let Layout::Sliver(axis, _, start) = self.layout {
+            self.layout = Layout::Sliver(axis, shift.max(0.), start);
+        }
+        self.show_indicator();
+        self.set_dirty(true);
+    }
+
+    /// Sets the list view's size (width and height), returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the list view
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the list view's axis direction, returning the list view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The list view with the axis direction assigned
+    ///
+    /// # Arguments
+    /// * `axis` - the axis direction to be assigned to the list view
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        if let Layout::Sliver(_, shift, start) = self.layout {
+            self.layout = Layout::Sliver(axis, shift, start);
+        }
+        self
+    }
+
+    /// Sets the auto-scrolling of the list while dragging near its edges
+    ///
+    /// While dragging (e.g. an item) inside the list, resting the cursor within
+    /// `zone` of the start or end edge scrolls the list towards that edge. The speed
+    /// ramps up quadratically from 0, at the inner border of the zone, to `speed`,
+    /// at the edge.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `zone` - the size of the zones near the edges, or 0 to disable auto-scrolling
+    /// * `speed` - the auto-scrolling speed at the very edge, in pixels per second
+    pub fn set_auto_scroll(&mut self, zone: f64, speed: f64) {
+        self.auto_scroll_zone = zone.max(0.);
+        self.auto_scroll_speed = speed;
+    }
+
+    /// Gets the auto-scrolling velocity for the current cursor position, for internal use only
+    ///
+    /// # Returns
+    /// The auto-scrolling velocity along the axis, in pixels per second (negative
+    /// towards the start of the list), or 0 if not dragging within a zone
+    ///
+    /// # Arguments
+    /// No arguments
+    fn auto_scroll_velocity(&self) -> f64 {
+        let origin = match self.drag_origin {
+            Some(origin) => origin,
+            None => return 0.,
+        };
+        if self.auto_scroll_zone <= 0. || self.thumb_grab.is_some() || self.pan_start.is_some() {
+            return 0.;
+        }
+        let moved = self.cursor_pos - origin;
+        if moved.x.abs() < DRAG_THRESHOLD && moved.y.abs() < DRAG_THRESHOLD {
+            return 0.;
+        }
+
+        let (cursor, start, length) = match self.layout {
+            Layout::Sliver(Axis::Horizontal, _, _) => {
+                (self.cursor_pos.x, self.position.x, self.size.x)
+            }
+            _ => (self.cursor_pos.y, self.position.y, self.size.y),
+        };
+        let zone = self.auto_scroll_zone.min(length * 0.5);
+        let from_start = cursor - start;
+        let from_end = start + length - cursor;
+        let ramp = |distance: f64| {
+            let depth = ((zone - distance) / zone).clamp(0., 1.);
+            depth * depth * self.auto_scroll_speed
+        };
+        if from_start < zone {
+            -ramp(from_start)
+        } else if from_end < zone {
+            ramp(from_end)
+        } else {
+            0.
         }
     }
 }
 
+impl Default for SliverViewWidget {
+    fn default() -> Self {
+        SliverViewWidget::new(
+            Vector2D::new(100., 100.),
+            Axis::Vertical,
+        )
+    }
+}
+
 impl Widget for SliverViewWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
-        match event {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let shift = match self.layout {
+            Layout::Sliver(_, shift, _) => shift,
+            _ => 0.,
+        };
+
+        let status = match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
+                // Pan the list, following the cursor
+                if let (Some((start, shift)), Layout::Sliver(axis, _, _)) =
+                    (self.pan_start, &self.layout)
+                {
+                    let cursor = match axis {
+                        Axis::Horizontal => self.cursor_pos.x,
+                        Axis::Vertical => self.cursor_pos.y,
+                    };
+                    self.scroll_to(shift - (cursor - start));
+                }
+                // Drag the thumb, mapping its position back to the list's shift
+                if let (Some(grab), Some((_, thumb_size))) = (self.thumb_grab, self.thumb_rect()) {
+                    let (axis, _, content) = self.scroll_extent();
+                    let (cursor, origin, viewport, length) = match axis {
+                        Axis::Horizontal => {
+                            (self.cursor_pos.x, self.position.x, self.size.x, thumb_size.x)
+                        }
+                        Axis::Vertical => {
+                            (self.cursor_pos.y, self.position.y, self.size.y, thumb_size.y)
+                        }
+                    };
+                    if viewport > length {
+                        let ratio = ((cursor - grab - origin) / (viewport - length)).clamp(0., 1.);
+                        self.scroll_to(ratio * (content - viewport));
                     }
                 }
+                propagate_event(&self.children, event, messages)
             }
             event::Event::Mouse(event::Mouse::WheelScrolled { delta }) => {
                 if self.is_cursor_inside(self.cursor_pos) {
                     if let event::ScrollDelta::Pixels { x, y } = delta {
-                        if let Layout::Sliver(axis, shift, start) = self.layout {
+                        if let Layout::Sliver(axis, shift, _) = self.layout {
 
 
                             // TODO: Update `start` -- the index of the first widget drawn on the screen -- and 
                             // establish a lower limit for scrolling -- should not scroll past the end of the list!
 
-                            self.layout = Layout::Sliver(axis, (shift - y).max(0.), start);
+                            let delta = match axis {
+                                Axis::Horizontal => x,
+                                Axis::Vertical => y,
+                            };
+                            if self.on_refresh.is_some()
+                                && shift <= 0.
+                                && (delta > 0. || self.pull_distance() > 0.)
+                            {
+                                // Scrolling past the start of the list pulls it to refresh
+                                self.overscroll(delta, messages);
+                            } else {
+                                self.scroll_to(shift - y);
+                            }
                         }
-                        self.set_dirty(true);
                     }
 
-                    for value in self.children.iter_mut() {
-                        if let Some(child) = value.upgrade() {
-                            child.borrow_mut().on_event(event, messages);
-                        }
+                    propagate_event(&self.children, event, messages);
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
+                }
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Middle)) => {
+                // Pan the list while the middle button is held
+                if self.is_cursor_inside(self.cursor_pos) {
+                    if let Layout::Sliver(axis, shift, _) = self.layout {
+                        let cursor = match axis {
+                            Axis::Horizontal => self.cursor_pos.x,
+                            Axis::Vertical => self.cursor_pos.y,
+                        };
+                        self.pan_start = Some((cursor, shift));
                     }
                 }
+                propagate_event(&self.children, event, messages)
             }
-            _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Middle)) => {
+                self.pan_start = None;
+                propagate_event(&self.children, event, messages)
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                if self.is_cursor_inside(self.cursor_pos) {
+                    self.drag_origin = Some(self.cursor_pos);
+                }
+                // Grab the thumb if the indicator is visible and the cursor is over it
+                if self.indicator_start.is_some() {
+                    if let Some((thumb_pos, thumb_size)) = self.thumb_rect() {
+                        if self.cursor_pos.x >= thumb_pos.x
+                            && self.cursor_pos.x <= thumb_pos.x + thumb_size.x
+                            && self.cursor_pos.y >= thumb_pos.y
+                            && self.cursor_pos.y <= thumb_pos.y + thumb_size.y
+                        {
+                            let (axis, _, _) = self.scroll_extent();
+                            self.thumb_grab = Some(match axis {
+                                Axis::Horizontal => self.cursor_pos.x - thumb_pos.x,
+                                Axis::Vertical => self.cursor_pos.y - thumb_pos.y,
+                            });
+                            self.show_indicator();
+                            return EventStatus::Captured;
+                        }
                     }
                 }
+                propagate_event(&self.children, event, messages)
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                self.drag_origin = None;
+                if self.thumb_grab.take().is_some() {
+                    // Restart the idle time so that the indicator fades out after the drag
+                    self.show_indicator();
+                    self.dirty = true;
+                }
+                propagate_event(&self.children, event, messages)
+            }
+            _ => {
+                propagate_event(&self.children, event, messages)
             }
+        };
+
+        // Lazily create the items scrolled into view
+        if let Layout::Sliver(_, new_shift, _) = self.layout {
+            if new_shift > shift {
+                self.fetch_items();
+            }
+        }
+        if self.data_source.is_some() {
+            self.check_near_end(messages);
         }
+        status
     }
 
     fn set_id(&mut self, id: usize) {
@@ -109,14 +1011,51 @@ impl Widget for SliverViewWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         // TODO: Debater se isto deve ser usado como clear do ecrã.
         vec![]
     }
 
+    fn clip_shape(&self) -> Option<ClipShape> {
+        // The children partially scrolled out of the viewport are cut at its edges
+        Some(ClipShape::Rect {
+            point: self.position,
+            size: self.size,
+        })
+    }
+
+    fn overlay_recipe(&self) -> Vec<RenderInstruction> {
+        let mut instructions = self.refresh_recipe();
+        let opacity = self.indicator_opacity();
+        if !self.has_scroll_indicator || opacity <= 0. {
+            return instructions;
+        }
+        if let Some((point, size)) = self.thumb_rect() {
+            let mut color = self.indicator_color;
+            color.a = (color.a as f64 * opacity).round() as u8;
+            instructions.push(RenderInstruction::DrawRect {
+                point,
+                color,
+                size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+        }
+        instructions
+    }
+
     fn set_dirty(&mut self, value: bool) {
         if value {
             self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
             for value in self.get_children() {
                 if let Some(child) = value.upgrade() {
                     if child.borrow_mut().is_dirty() {
@@ -127,11 +1066,27 @@ impl Widget for SliverViewWidget {
                     }
                 }
             }
-        } else {self.dirty = false;}
+        } else {
+            self.dirty = false;
+            // Hide the indicator once it has faded out
+            if self.indicator_start.is_some() && self.indicator_opacity() <= 0. {
+                self.indicator_start = None;
+            }
+            // Reset the refresh indicator once it has retracted
+            if !self.is_refreshing && self.pull_distance() <= 0. {
+                self.pull = 0.;
+                self.retract = None;
+            }
+        }
     }
 
     fn is_dirty(&self) -> bool {
+        // The scroll and refresh indicators need to be redrawn on every frame while visible
         self.dirty
+            || self.indicator_start.is_some()
+            || self.is_refreshing
+            || self.pull > 0.
+            || self.retract.is_some()
     }
 
     fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
@@ -143,7 +1098,7 @@ impl Widget for SliverViewWidget {
     }
 
     fn position(&mut self) -> Vector2D {
-        Vector2D::new(0., 0.)
+        self.position
     }
 
     fn size(&mut self) -> Vector2D {
@@ -177,7 +1132,7 @@ impl Widget for SliverViewWidget {
         (
             self.dirty,
             &mut self.children,
-            Vector2D::new(0., 0.),
+            self.position,
             self.size,
             self.original_size,
             &self.layout,
@@ -185,7 +1140,9 @@ impl Widget for SliverViewWidget {
         )
     }
 
-    fn set_position(&mut self, _position: Vector2D) {}
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
 
     fn set_size(&mut self, size: Vector2D) {
         self.set_dirty(true);
@@ -199,13 +1156,9 @@ impl Widget for SliverViewWidget {
 
     fn set_offset(&mut self, _offset: Vector2D) {}
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
     
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -218,4 +1171,44 @@ impl Widget for SliverViewWidget {
             false
         }
     }
+
+    fn on_frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        let elapsed = clock::elapsed(self.last_frame).as_secs_f64();
+        self.last_frame = clock::now();
+
+        // Keep the visible content in place if children were added or removed before it
+        if self.has_scroll_anchoring {
+            self.anchor_scroll();
+        }
+
+        // Auto-scroll while dragging near the edges
+        let velocity = self.auto_scroll_velocity();
+        if velocity != 0. {
+            if let Layout::Sliver(_, shift, _) = self.layout {
+                self.scroll_to(shift + velocity * elapsed);
+                self.fetch_items();
+            }
+        }
+
+        for value in self.children.iter_mut() {
+            if let Some(child) = value.upgrade() {
+                deliver_frame(&mut *child.borrow_mut(), messages);
+            }
+        }
+    }
+}
+
+impl Scrollable for SliverViewWidget {
+    fn extent(&self) -> ScrollExtent {
+        let (axis, shift, content) = self.scroll_extent();
+        let viewport = match axis {
+            Axis::Horizontal => self.size.x,
+            Axis::Vertical => self.size.y,
+        };
+        ScrollExtent::new(shift, content, viewport)
+    }
+
+    fn scroll_to_offset(&mut self, offset: f64) {
+        self.scroll_to(offset);
+    }
 }
@@ -0,0 +1,425 @@
+use crate::event::Event;
+use crate::renderer::{Message, RenderInstruction};
+use crate::util::{Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Alignment of a child within a [`StackWidget`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StackAlignment {
+    /// The child is placed on the top left corner
+    #[default]
+    TopLeft,
+    /// The child is placed on the top edge, horizontally centered
+    Top,
+    /// The child is placed on the top right corner
+    TopRight,
+    /// The child is placed on the left edge, vertically centered
+    Left,
+    /// The child is centered
+    Center,
+    /// The child is placed on the right edge, vertically centered
+    Right,
+    /// The child is placed on the bottom left corner
+    BottomLeft,
+    /// The child is placed on the bottom edge, horizontally centered
+    Bottom,
+    /// The child is placed on the bottom right corner
+    BottomRight,
+    /// The child fills the stack, whatever its size
+    Fill,
+}
+
+impl StackAlignment {
+    /// Gets the fraction of the space left by a child before it, along each axis,
+    /// for internal use only
+    ///
+    /// # Returns
+    /// The fraction (from 0 to 1) of the space left placed before the child, horizontally
+    /// and vertically
+    ///
+    /// # Arguments
+    /// No arguments
+    fn factors(self) -> Vector2D {
+        match self {
+            StackAlignment::TopLeft | StackAlignment::Fill => Vector2D::new(0., 0.),
+            StackAlignment::Top => Vector2D::new(0.5, 0.),
+            StackAlignment::TopRight => Vector2D::new(1., 0.),
+            StackAlignment::Left => Vector2D::new(0., 0.5),
+            StackAlignment::Center => Vector2D::new(0.5, 0.5),
+            StackAlignment::Right => Vector2D::new(1., 0.5),
+            StackAlignment::BottomLeft => Vector2D::new(0., 1.),
+            StackAlignment::Bottom => Vector2D::new(0.5, 1.),
+            StackAlignment::BottomRight => Vector2D::new(1., 1.),
+        }
+    }
+}
+
+/// Placement of a child within a [`StackWidget`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct StackPlacement {
+    /// The child's alignment within the stack
+    pub alignment: StackAlignment,
+
+    /// The distance the child is moved by from its aligned position (e.g. to overlap
+    /// the corner of the widget below it)
+    pub offset: Vector2D,
+}
+
+impl StackPlacement {
+    /// Creates a new `StackPlacement`, with no offset
+    ///
+    /// # Returns
+    /// The placement created
+    ///
+    /// # Arguments
+    /// * `alignment` - the child's alignment within the stack
+    pub fn new(alignment: StackAlignment) -> StackPlacement {
+        StackPlacement {
+            alignment,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the distance the child is moved by from its aligned position, returning the
+    /// placement (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The placement with the offset assigned
+    ///
+    /// # Arguments
+    /// * `offset` - the distance the child is moved by, horizontally and vertically
+    pub fn with_offset(mut self, offset: Vector2D) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Stack is a container that places its children on top of each other within its
+/// bounds (e.g. a badge over an icon, or a label over an image).
+///
+/// Each child keeps its original size, fitting the stack, and is aligned within it
+/// and moved by an offset, as described by its [`StackPlacement`]. The children are
+/// drawn in the order they were added, the last one on top, and receive the events
+/// in the reverse order, so the topmost child gets them first.
+///
+/// # Examples
+///
+/// ```
+/// use hyber::util::Vector2D;
+/// use hyber::widget::icon::IconWidget;
+/// use hyber::widget::label::LabelWidget;
+/// use hyber::widget::stack::{StackAlignment, StackPlacement, StackWidget};
+/// use hyber::widget::Widget;
+///
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let icon: Rc<RefCell<dyn Widget>> = Rc::new(RefCell::new(IconWidget::default()));
+/// let badge: Rc<RefCell<dyn Widget>> = Rc::new(RefCell::new(LabelWidget::default()));
+/// let mut stack = StackWidget::default();
+/// stack.add_as_child(Rc::downgrade(&icon));
+/// stack.add_child_with(
+///     Rc::downgrade(&badge),
+///     StackPlacement::new(StackAlignment::TopRight).with_offset(Vector2D::new(4., -4.)),
+/// );
+/// assert_eq!(stack.placement(1).unwrap().alignment, StackAlignment::TopRight);
+/// ```
+#[derive(Clone)]
+pub struct StackWidget {
+    /// The stack's identifier
+    id: usize,
+
+    /// The data attached to the stack through its handle
+    widget_data: WidgetData,
+
+    /// The placement of each child, in the order of the children
+    placements: Vec<StackPlacement>,
+
+    /// The alignment of the children added without a placement
+    alignment: StackAlignment,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The stack's children (i.e., his widgets tree), from the bottom one to the top one
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The stack's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The stack's current size (width and height)
+    size: Vector2D,
+
+    /// The stack's original size (width and height)
+    original_size: Vector2D,
+
+    /// The stack's layout
+    layout: Layout,
+
+    /// The stack's offset vector coordinates
+    offset: Vector2D,
+}
+
+impl StackWidget {
+    /// Creates a new `StackWidget`
+    ///
+    /// # Returns
+    /// The stack created, with no children
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the stack
+    /// * `alignment` - the alignment of the children added without a placement
+    pub fn new(size: Vector2D, alignment: StackAlignment) -> StackWidget {
+        StackWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            placements: Vec::new(),
+            alignment,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Adds a child over the existing ones, with the given placement
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `child` - the child to be added
+    /// * `placement` - the child's alignment and offset within the stack
+    pub fn add_child_with(&mut self, child: Weak<RefCell<dyn Widget>>, placement: StackPlacement) {
+        self.children.push(child);
+        self.placements.push(placement);
+        self.set_dirty(true);
+    }
+
+    /// Gets the placement of a child
+    ///
+    /// # Returns
+    /// The placement of the child, or `None` if there is no child with the index
+    ///
+    /// # Arguments
+    /// * `index` - the index of the child
+    pub fn placement(&self, index: usize) -> Option<StackPlacement> {
+        self.placements.get(index).copied()
+    }
+
+    /// Sets the placement of a child
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the child
+    /// * `placement` - the child's alignment and offset within the stack
+    pub fn set_placement(&mut self, index: usize, placement: StackPlacement) {
+        if let Some(child_placement) = self.placements.get_mut(index) {
+            *child_placement = placement;
+            self.set_dirty(true);
+        }
+    }
+
+    /// Sets the stack's size (width and height), returning the stack (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The stack with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the stack
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the alignment of the children added without a placement, returning the
+    /// stack (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The stack with the alignment assigned
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment of the children added without a placement
+    pub fn with_alignment(mut self, alignment: StackAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Adds a child over the existing ones, with the given placement, returning the
+    /// stack (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The stack with the child added
+    ///
+    /// # Arguments
+    /// * `child` - the child to be added
+    /// * `placement` - the child's alignment and offset within the stack
+    pub fn with_child(
+        mut self,
+        child: Weak<RefCell<dyn Widget>>,
+        placement: StackPlacement,
+    ) -> Self {
+        self.add_child_with(child, placement);
+        self
+    }
+}
+
+impl Default for StackWidget {
+    fn default() -> Self {
+        StackWidget::new(Vector2D::new(100., 100.), StackAlignment::TopLeft)
+    }
+}
+
+impl Widget for StackWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        self.children
+            .iter()
+            .zip(self.placements.iter())
+            .map(|(value, placement)| {
+                let child = value.upgrade()?;
+                let size = match placement.alignment {
+                    StackAlignment::Fill => area.size,
+                    _ => child.borrow_mut().original_size().min(area.size),
+                };
+                let position = area.position
+                    + (area.size - size) * placement.alignment.factors()
+                    + placement.offset;
+                Some(Rect::new(position, size))
+            })
+            .collect()
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.add_child_with(child, StackPlacement::new(self.alignment));
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+}
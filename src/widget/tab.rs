@@ -1,8 +1,11 @@
+use crate::clock;
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
-use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::interaction::{Interaction, InteractionStateMachine};
+use crate::renderer::{BlendMode, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{is_batching_updates, propagate_event, EventStatus, Layout, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
@@ -17,6 +20,9 @@ const ON_LONG_PRESS_TIME: u128 = 300;
 pub struct TabWidget {
     /// The tab's identifier
     id: usize,
+
+    /// The data attached to the tab through its handle
+    widget_data: WidgetData,
     
     /// The tab's background color
     background_color: Color,
@@ -49,8 +55,8 @@ pub struct TabWidget {
     /// The message to be handled when a user long press (i.e., a user drag some tab)
     tab_moved: Option<Box<dyn Message>>,
     
-    /// Wheter the tab is pressed
-    is_pressed: bool,
+    /// Whether the tab is hovered or pressed
+    interaction: InteractionStateMachine,
     
     /// The instant when the tab was clicked
     click_time: Instant,
@@ -60,6 +66,13 @@ pub struct TabWidget {
     
     /// The cursor's position where the mouse button was released after a long press (i.e., drag)
     moved_cursor_pos: Vector2D,
+
+    /// Whether the tab's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// The tab's style, overriding the properties of the theme
+    style: Style,
 }
 impl TabWidget {
     /// Creates a new `TabWidget`
@@ -81,6 +94,9 @@ impl TabWidget {
     ) -> TabWidget {
         TabWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color),
             background_color: background_color,
             dirty: true,
             children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
@@ -91,10 +107,11 @@ impl TabWidget {
             offset: Vector2D::new(0., 0.),
             on_press: on_press,
             tab_moved: tab_moved,
-            is_pressed: false,
-            click_time: Instant::now(),
+            interaction: InteractionStateMachine::new(),
+            click_time: clock::now(),
             cursor_pos: Vector2D::new(-1., -1.),
             moved_cursor_pos: Vector2D::new(-1., -1.),
+            pixel_snapping: None,
         }
     }
 
@@ -122,10 +139,115 @@ impl TabWidget {
     pub fn get_moved_cursor_pos(&mut self) -> Vector2D {
         self.moved_cursor_pos
     }
+
+    /// Sets whether the tab's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets the tab's size (width and height), returning the tab (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the tab
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the tab's background color, returning the tab (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the tab
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the tab's message to be handled when pressed, returning the tab (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab with the given message to be handled when pressed
+    ///
+    /// # Arguments
+    /// * `on_press` - the message to be handled when pressed
+    pub fn with_on_press(mut self, on_press: Option<Box<dyn Message>>) -> Self {
+        self.on_press = on_press;
+        self
+    }
+
+    /// Sets the tab's message to be handled when the tab is moved, returning the tab (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab with the given message to be handled when the tab is moved
+    ///
+    /// # Arguments
+    /// * `tab_moved` - the message to be handled when the tab is moved
+    pub fn with_tab_moved(mut self, tab_moved: Option<Box<dyn Message>>) -> Self {
+        self.tab_moved = tab_moved;
+        self
+    }
+
+    /// Sets the tab's style, overriding the properties of the current theme and the ones set
+    /// before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the tab
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the tab's style, overriding the properties of the current theme, returning the tab
+    /// (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the tab
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for TabWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        TabWidget {
+            style: Style::new(),
+            ..TabWidget::new(
+                Vector2D::new(100., 30.),
+                theme.palette.surface,
+                None,
+                None,
+            )
+        }
+    }
 }
 
 impl Widget for TabWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        let area = Rect::new(self.position, self.size);
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved {
                 x: x_mouse,
@@ -133,24 +255,26 @@ impl Widget for TabWidget {
             }) => {
                 self.cursor_pos.x = x_mouse as f64;
                 self.cursor_pos.y = y_mouse as f64;
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                self.interaction.on_event(event, area);
+                propagate_event(&self.children, event, messages)
             }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
                 //CHECK IF INSIDE THE TAB
-                if self.is_cursor_inside(self.cursor_pos) {
-                    self.is_pressed = true;
-                    self.click_time = Instant::now();
+                if self.interaction.on_event(event, area) == Some(Interaction::Pressed) {
+                    self.click_time = clock::now();
+                    //keep receiving the cursor moves and the release while the tab is moved
+                    self.capture_pointer();
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
             event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
-                if self.is_pressed {
-                    if self.is_cursor_inside(self.cursor_pos) {
+                let interaction = self.interaction.on_event(event, area);
+                if interaction.is_some() {
+                    if interaction == Some(Interaction::Clicked) {
                         //Tab pressed
-                        if self.click_time.elapsed().as_millis() < ON_LONG_PRESS_TIME {
+                        if clock::elapsed(self.click_time).as_millis() < ON_LONG_PRESS_TIME {
                             if let Some(mut message) = self.on_press.clone() {
                                 message.set_event(event);
                                 messages.enqueue(message);
@@ -158,7 +282,7 @@ impl Widget for TabWidget {
                         }
                     }
                     //TAB MOVED
-                    if self.click_time.elapsed().as_millis() > ON_LONG_PRESS_TIME {
+                    if clock::elapsed(self.click_time).as_millis() > ON_LONG_PRESS_TIME {
                         self.moved_cursor_pos.x = self.cursor_pos.x;
                         self.moved_cursor_pos.y = self.cursor_pos.y;
                         if let Some(mut message) = self.tab_moved.clone() {
@@ -166,15 +290,14 @@ impl Widget for TabWidget {
                             messages.enqueue(message);
                         }
                     }
-                    self.is_pressed = false;
+                    EventStatus::Captured
+                } else {
+                    EventStatus::Ignored
                 }
             }
             _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                self.interaction.on_event(event, area);
+                propagate_event(&self.children, event, messages)
             }
         }
     }
@@ -187,6 +310,10 @@ impl Widget for TabWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         vec![
             // Tab rectangle
@@ -196,6 +323,7 @@ impl Widget for TabWidget {
                 size: self.size,
                 clip_point: self.position,
                 clip_size: self.size,
+                blend_mode: BlendMode::Normal,
             },
         ]
     }
@@ -203,6 +331,10 @@ impl Widget for TabWidget {
     fn set_dirty(&mut self, value: bool) {
         if value {
             self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
             for value in self.get_children() {
                 if let Some(child) = value.upgrade() {
                     if child.borrow_mut().is_dirty() {
@@ -289,13 +421,9 @@ impl Widget for TabWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -308,4 +436,13 @@ impl Widget for TabWidget {
             false
         }
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,805 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Rect, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget, WidgetData,
+};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Height of the tabs' headers, as a multiple of the font size
+const HEADER_HEIGHT: f64 = 1.8;
+
+/// A tab of a tab bar, for internal use only
+#[derive(Clone)]
+struct TabBarTab {
+    /// The tab's title, displayed on its header
+    title: String,
+
+    /// The tab's content, displayed while the tab is selected
+    content: Weak<RefCell<dyn Widget>>,
+
+    /// Whether the tab's header has a close button
+    is_closable: bool,
+}
+
+/// Tab bar is a container of tabs, each one made of a header with its title and a
+/// content pane, of which only the selected tab's content is displayed.
+///
+/// The headers are placed along the tab bar's axis (i.e., in a row on the top edge
+/// when horizontal, or in a column on the left edge when vertical) and the selected
+/// content fills the rest of the tab bar. Pressing a header selects its tab, handling
+/// the `on_tab_changed` message, and pressing the close button of a closable tab
+/// removes it, handling the `on_tab_closed` message. Both messages are filled with the
+/// index of the tab if they are a [`ValueChanged<usize>`].
+///
+/// # Examples
+///
+/// ```
+/// use hyber::widget::label::LabelWidget;
+/// use hyber::widget::tab_bar::TabBarWidget;
+/// use hyber::widget::Widget;
+///
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let home: Rc<RefCell<dyn Widget>> = Rc::new(RefCell::new(LabelWidget::default()));
+/// let settings: Rc<RefCell<dyn Widget>> = Rc::new(RefCell::new(LabelWidget::default()));
+/// let mut tab_bar = TabBarWidget::default()
+///     .with_tab("Home", Rc::downgrade(&home), false)
+///     .with_tab("Settings", Rc::downgrade(&settings), true);
+/// assert_eq!(tab_bar.selected_tab(), Some(0));
+///
+/// tab_bar.select_tab(1);
+/// tab_bar.remove_tab(1);
+/// assert_eq!(tab_bar.selected_tab(), Some(0));
+/// assert_eq!(tab_bar.tab_count(), 1);
+/// ```
+#[derive(Clone)]
+pub struct TabBarWidget {
+    /// The tab bar's identifier
+    id: usize,
+
+    /// The data attached to the tab bar through its handle
+    widget_data: WidgetData,
+
+    /// The tab bar's tabs, in the order of their headers
+    tabs: Vec<TabBarTab>,
+
+    /// The index of the selected tab, if any
+    selected: Option<usize>,
+
+    /// The axis along which the headers are placed
+    axis: Axis,
+
+    /// The length of each header along the axis when horizontal, or across it when vertical
+    header_width: f64,
+
+    /// The message to be handled when the user selects another tab
+    on_tab_changed: Option<Box<dyn Message>>,
+
+    /// The message to be handled when the user closes a tab
+    on_tab_closed: Option<Box<dyn Message>>,
+
+    /// The number of closed tabs' contents hidden by the last build, kept right after
+    /// the tabs' contents until the next build
+    hidden_count: usize,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The tab bar's font size, used on the tabs' headers
+    font_size: usize,
+
+    /// The color of the tabs' headers
+    header_color: Color,
+
+    /// The color of the selected tab's header
+    selected_color: Color,
+
+    /// The color of the tabs' titles and close buttons
+    text_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The tab bar's children (i.e., the tabs' contents, in the order of the tabs)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The tab bar's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The tab bar's current size (width and height)
+    size: Vector2D,
+
+    /// The tab bar's original size (width and height)
+    original_size: Vector2D,
+
+    /// The tab bar's layout
+    layout: Layout,
+
+    /// The tab bar's offset vector coordinates
+    offset: Vector2D,
+
+    /// The tab bar's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl TabBarWidget {
+    /// Creates a new `TabBarWidget`
+    ///
+    /// # Returns
+    /// The tab bar created, with no tabs
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the tab bar
+    /// * `font_size` - the font size to be assigned to the tabs' titles
+    /// * `header_color` - the color to be assigned to the tabs' headers
+    /// * `selected_color` - the color to be assigned to the selected tab's header
+    /// * `text_color` - the color to be assigned to the tabs' titles and close buttons
+    pub fn new(
+        size: Vector2D,
+        font_size: usize,
+        header_color: Color,
+        selected_color: Color,
+        text_color: Color,
+    ) -> TabBarWidget {
+        TabBarWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_muted_color(header_color)
+                .with_background_color(selected_color)
+                .with_foreground_color(text_color),
+            tabs: Vec::new(),
+            selected: None,
+            axis: Axis::Horizontal,
+            header_width: 120.,
+            on_tab_changed: None,
+            on_tab_closed: None,
+            hidden_count: 0,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            header_color,
+            selected_color,
+            text_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Custom,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Adds a tab after the existing ones, selecting it if it is the only one
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the tab's title, displayed on its header
+    /// * `content` - the tab's content, displayed while the tab is selected
+    /// * `is_closable` - whether the tab's header has a close button
+    pub fn add_tab(&mut self, title: &str, content: Weak<RefCell<dyn Widget>>, is_closable: bool) {
+        // The contents of the tabs are kept before the closed ones
+        self.children.insert(self.tabs.len(), content.clone());
+        self.tabs.push(TabBarTab {
+            title: String::from(title),
+            content,
+            is_closable,
+        });
+        if self.selected.is_none() {
+            self.selected = Some(0);
+        }
+        self.set_dirty(true);
+    }
+
+    /// Removes a tab, without handling the `on_tab_closed` message
+    ///
+    /// If the tab is the selected one, the tab after it (or the last one) is selected.
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the tab to be removed
+    pub fn remove_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        self.tabs.remove(index);
+        // The content is removed from the display on the next build
+        let content = self.children.remove(index);
+        self.children.push(content);
+        self.selected = match self.selected {
+            _ if self.tabs.is_empty() => None,
+            Some(selected) if selected > index => Some(selected - 1),
+            Some(selected) => Some(selected.min(self.tabs.len() - 1)),
+            None => None,
+        };
+        self.set_dirty(true);
+    }
+
+    /// Selects a tab, displaying its content, without handling the `on_tab_changed` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the tab to be selected
+    pub fn select_tab(&mut self, index: usize) {
+        if index < self.tabs.len() && self.selected != Some(index) {
+            self.selected = Some(index);
+            self.set_dirty(true);
+        }
+    }
+
+    /// Gets the index of the selected tab
+    ///
+    /// # Returns
+    /// The index of the selected tab, or `None` if the tab bar has no tabs
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn selected_tab(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Gets the number of tabs
+    ///
+    /// # Returns
+    /// The number of tabs of the tab bar
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Gets the title of a tab
+    ///
+    /// # Returns
+    /// The title of the tab, or `None` if there is no tab with the index
+    ///
+    /// # Arguments
+    /// * `index` - the index of the tab
+    pub fn tab_title(&self, index: usize) -> Option<&str> {
+        self.tabs.get(index).map(|tab| tab.title.as_str())
+    }
+
+    /// Sets the title of a tab
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `index` - the index of the tab
+    /// * `title` - the title to be assigned to the tab
+    pub fn set_tab_title(&mut self, index: usize, title: &str) {
+        if let Some(tab) = self.tabs.get_mut(index) {
+            tab.title = String::from(title);
+            self.set_dirty(true);
+        }
+    }
+
+    /// Gets the content of a tab
+    ///
+    /// # Returns
+    /// The content of the tab, or `None` if there is no tab with the index
+    ///
+    /// # Arguments
+    /// * `index` - the index of the tab
+    pub fn tab_content(&self, index: usize) -> Option<Weak<RefCell<dyn Widget>>> {
+        self.tabs.get(index).map(|tab| tab.content.clone())
+    }
+
+    /// Sets the axis along which the headers are placed
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `axis` - the axis of the headers (i.e., horizontal for a row on the top edge)
+    pub fn set_axis(&mut self, axis: Axis) {
+        self.axis = axis;
+        self.set_dirty(true);
+    }
+
+    /// Sets the length of each header along the axis when horizontal, or across it when vertical
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `header_width` - the width of each header
+    pub fn set_header_width(&mut self, header_width: f64) {
+        self.header_width = header_width;
+        self.set_dirty(true);
+    }
+
+    /// Sets the message to be handled when the user selects another tab
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_tab_changed` - the message to be handled when the selected tab changes
+    pub fn set_on_tab_changed(&mut self, on_tab_changed: Option<Box<dyn Message>>) {
+        self.on_tab_changed = on_tab_changed;
+    }
+
+    /// Sets the message to be handled when the user closes a tab
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_tab_closed` - the message to be handled when a tab is closed
+    pub fn set_on_tab_closed(&mut self, on_tab_closed: Option<Box<dyn Message>>) {
+        self.on_tab_closed = on_tab_closed;
+    }
+
+    /// Gets the height of the tabs' headers, for internal use only
+    ///
+    /// # Returns
+    /// The height of the tabs' headers
+    ///
+    /// # Arguments
+    /// No arguments
+    fn header_height(&self) -> f64 {
+        self.font_size as f64 * HEADER_HEIGHT
+    }
+
+    /// Gets the area of a tab's header, for internal use only
+    ///
+    /// # Returns
+    /// The area of the header
+    ///
+    /// # Arguments
+    /// * `area` - the area of the tab bar
+    /// * `index` - the index of the tab
+    fn header_area(&self, area: Rect, index: usize) -> Rect {
+        let size = Vector2D::new(self.header_width, self.header_height());
+        let position = match self.axis {
+            Axis::Horizontal => Vector2D::new(size.x * index as f64, 0.),
+            Axis::Vertical => Vector2D::new(0., size.y * index as f64),
+        };
+        Rect::new(area.position + position, size)
+    }
+
+    /// Gets the area of the close button on a tab's header, for internal use only
+    ///
+    /// # Returns
+    /// The area of the close button, a square on the end of the header
+    ///
+    /// # Arguments
+    /// * `header` - the area of the header
+    fn close_area(&self, header: Rect) -> Rect {
+        let size = header.size.y.min(header.size.x);
+        Rect::new(
+            Vector2D::new(header.position.x + header.size.x - size, header.position.y),
+            Vector2D::new(size, size),
+        )
+    }
+
+    /// Gets the area of the selected tab's content, for internal use only
+    ///
+    /// # Returns
+    /// The area of the tab bar not taken by the headers
+    ///
+    /// # Arguments
+    /// * `area` - the area of the tab bar
+    fn content_area(&self, area: Rect) -> Rect {
+        match self.axis {
+            Axis::Horizontal => {
+                let height = self.header_height().min(area.size.y);
+                Rect::new(
+                    Vector2D::new(area.position.x, area.position.y + height),
+                    Vector2D::new(area.size.x, area.size.y - height),
+                )
+            }
+            Axis::Vertical => {
+                let width = self.header_width.min(area.size.x);
+                Rect::new(
+                    Vector2D::new(area.position.x + width, area.position.y),
+                    Vector2D::new(area.size.x - width, area.size.y),
+                )
+            }
+        }
+    }
+
+    /// Handles a message of the tab bar, filled with the index of a tab, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `message` - the message to be handled, if any
+    /// * `index` - the index of the tab
+    /// * `event` - the event that triggered the message
+    /// * `messages` - queue of messages
+    fn enqueue_message(
+        message: &Option<Box<dyn Message>>,
+        index: usize,
+        event: Event,
+        messages: &mut Queue<Box<dyn Message>>,
+    ) {
+        if let Some(mut message) = message.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<usize>>() {
+                message.value = index;
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Sets the tab bar's size (width and height), returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the tab bar
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Adds a tab to the tab bar, returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the tab added
+    ///
+    /// # Arguments
+    /// * `title` - the tab's title, displayed on its header
+    /// * `content` - the tab's content, displayed while the tab is selected
+    /// * `is_closable` - whether the tab's header has a close button
+    pub fn with_tab(
+        mut self,
+        title: &str,
+        content: Weak<RefCell<dyn Widget>>,
+        is_closable: bool,
+    ) -> Self {
+        self.add_tab(title, content, is_closable);
+        self
+    }
+
+    /// Sets the axis along which the headers are placed, returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the axis assigned
+    ///
+    /// # Arguments
+    /// * `axis` - the axis of the headers (i.e., horizontal for a row on the top edge)
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Sets the width of each header, returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the width of the headers assigned
+    ///
+    /// # Arguments
+    /// * `header_width` - the width of each header
+    pub fn with_header_width(mut self, header_width: f64) -> Self {
+        self.header_width = header_width;
+        self
+    }
+
+    /// Sets the tab bar's message to be handled when the selected tab changes, returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the given message to be handled when the selected tab changes
+    ///
+    /// # Arguments
+    /// * `on_tab_changed` - the message to be handled when the selected tab changes
+    pub fn with_on_tab_changed(mut self, on_tab_changed: Option<Box<dyn Message>>) -> Self {
+        self.on_tab_changed = on_tab_changed;
+        self
+    }
+
+    /// Sets the tab bar's message to be handled when a tab is closed, returning the tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the given message to be handled when a tab is closed
+    ///
+    /// # Arguments
+    /// * `on_tab_closed` - the message to be handled when a tab is closed
+    pub fn with_on_tab_closed(mut self, on_tab_closed: Option<Box<dyn Message>>) -> Self {
+        self.on_tab_closed = on_tab_closed;
+        self
+    }
+
+    /// Sets the tab bar's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the tab bar
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the tab bar's style, overriding the properties of the current theme, returning the
+    /// tab bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tab bar with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the tab bar
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for TabBarWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        TabBarWidget {
+            style: Style::new(),
+            ..TabBarWidget::new(
+                Vector2D::new(400., 300.),
+                theme.font_size,
+                theme.palette.muted,
+                theme.palette.surface,
+                theme.palette.foreground,
+            )
+        }
+    }
+}
+
+impl Widget for TabBarWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                let area = Rect::new(self.position, self.size);
+                let pressed = (0..self.tabs.len())
+                    .find(|index| self.header_area(area, *index).contains(self.cursor_pos));
+                if let Some(index) = pressed {
+                    let header = self.header_area(area, index);
+                    if self.tabs[index].is_closable
+                        && self.close_area(header).contains(self.cursor_pos)
+                    {
+                        let selected = self.selected;
+                        self.remove_tab(index);
+                        TabBarWidget::enqueue_message(&self.on_tab_closed, index, event, messages);
+                        // Closing the selected tab selects another one
+                        if selected == Some(index) {
+                            if let Some(selected) = self.selected {
+                                TabBarWidget::enqueue_message(
+                                    &self.on_tab_changed,
+                                    selected,
+                                    event,
+                                    messages,
+                                );
+                            }
+                        }
+                    } else if self.selected != Some(index) {
+                        self.select_tab(index);
+                        TabBarWidget::enqueue_message(&self.on_tab_changed, index, event, messages);
+                    }
+                    // The press on a header does not reach the content
+                    return EventStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        // Only the selected tab's content is displayed
+        match self.selected {
+            Some(selected) => propagate_event(&self.children[selected..=selected], event, messages),
+            None => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let area = Rect::new(self.position, self.size);
+        let height = self.header_height();
+        let mut instructions = Vec::new();
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let header = self.header_area(area, index);
+            let color = if self.selected == Some(index) {
+                self.selected_color
+            } else {
+                self.header_color
+            };
+            instructions.push(RenderInstruction::DrawRect {
+                point: header.position,
+                size: header.size,
+                color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            });
+            // The title is clipped before the close button, if any
+            let mut title_size = header.size;
+            if tab.is_closable {
+                let close = self.close_area(header);
+                title_size.x -= close.size.x;
+                instructions.push(RenderInstruction::DrawText {
+                    point: Vector2D::new(
+                        close.position.x + (close.size.x - self.font_size as f64) * 0.5,
+                        close.position.y + (height + self.font_size as f64) * 0.5,
+                    ),
+                    font_size: self.font_size,
+                    string: String::from("x"),
+                    color: self.text_color,
+                    clip_point: close.position,
+                    clip_size: close.size,
+                    font: FontDescriptor::default(),
+                });
+            }
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(
+                    header.position.x + 8.,
+                    header.position.y + (height + self.font_size as f64) * 0.5,
+                ),
+                font_size: self.font_size,
+                string: tab.title.clone(),
+                color: self.text_color,
+                clip_point: header.position,
+                clip_size: title_size,
+                font: FontDescriptor::default(),
+            });
+        }
+        instructions
+    }
+
+    fn child_areas(&mut self, area: Rect) -> Vec<Option<Rect>> {
+        // The contents closed before the last build were hidden by it, while the ones
+        // closed since then are hidden by this build
+        let count = self.tabs.len();
+        self.children.drain(count..count + self.hidden_count);
+        self.hidden_count = self.children.len() - count;
+
+        let content_area = self.content_area(area);
+        (0..self.children.len())
+            .map(|index| Some(content_area).filter(|_| self.selected == Some(index)))
+            .collect()
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        // A child added to the tab bar becomes the content of an untitled tab
+        self.add_tab("", child, false);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.header_color = self.style.muted_color.unwrap_or(theme.palette.muted);
+        self.selected_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
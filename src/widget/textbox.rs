@@ -1,16 +1,27 @@
 use crate::event;
 use crate::event::Event;
-use crate::renderer::{Message, RenderInstruction};
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::text::{self, TextAlignment, TextLayout};
 use crate::util::{Color, Queue, Vector2D};
-use crate::widget::{Layout, Widget};
+use crate::widget::{propagate_event, EventStatus, Layout, TabIndex, Widget, WidgetData};
 
 use std::cell::RefCell;
 use std::rc::Weak;
 
+/// Font size of the textbox's text
+const FONT_SIZE: usize = 22;
+
+/// Default space between the textbox's edges and its text
+const PADDING: f64 = 10.;
+
 #[derive(Clone)]
 pub struct TextBoxWidget {
     /// The textbox's identifier
     id: usize,
+
+    /// The data attached to the textbox through its handle
+    widget_data: WidgetData,
     
     /// The textbox's background color
     background_color: Color,
@@ -20,9 +31,18 @@ pub struct TextBoxWidget {
     
     /// The textbox's border thickness
     border_thickness: f64,
+
+    /// The textbox's border color
+    border_color: Color,
+
+    /// The space between the textbox's edges and its text
+    padding: f64,
     
     /// The textbox's text
     text: String,
+
+    /// The textbox's font (i.e., family, weight and style)
+    font: FontDescriptor,
     
     /// The message to be handled when the text changes
     on_text_change: Option<Box<dyn Message>>,
@@ -54,6 +74,19 @@ pub struct TextBoxWidget {
 
     /// The textbox's offset vector coordinates
     offset: Vector2D,
+
+    /// Whether the textbox's render instructions are snapped to device pixels,
+    /// or `None` to follow the global setting
+    pixel_snapping: Option<bool>,
+
+    /// The textbox's position in the focus traversal
+    tab_index: TabIndex,
+
+    /// How the textbox's text is broken into lines and aligned
+    text_layout: TextLayout,
+
+    /// The textbox's style, overriding the properties of the theme
+    style: Style,
 }
 
 impl TextBoxWidget {
@@ -79,10 +112,18 @@ impl TextBoxWidget {
     ) -> TextBoxWidget {
         TextBoxWidget {
             id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_background_color(background_color)
+                .with_foreground_color(text_color)
+                .with_border_thickness(border_thickness),
             background_color: background_color,
             text_color: text_color,
             border_thickness: border_thickness,
+            border_color: Color::from_hex(0xFF000000),
+            padding: PADDING,
             text: text,
+            font: FontDescriptor::default(),
             on_text_change: on_text_change,
             is_focused: false,
             cursor_pos: Vector2D::new(-1., -1.),
@@ -93,6 +134,9 @@ impl TextBoxWidget {
             original_size: size,
             layout: Layout::None,
             offset: Vector2D::new(0., 0.),
+            pixel_snapping: None,
+            tab_index: TabIndex::Auto,
+            text_layout: TextLayout::default(),
         }
     }
 
@@ -106,32 +150,256 @@ impl TextBoxWidget {
     pub fn set_message(&mut self, on_text_change: Option<Box<dyn Message>>) {
         self.on_text_change = on_text_change;
     }
+
+    /// Sets whether the textbox's render instructions are snapped to device pixels
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `pixel_snapping` - whether to snap to device pixels, or `None` to follow the global setting
+    pub fn set_pixel_snapping(&mut self, pixel_snapping: Option<bool>) {
+        self.pixel_snapping = pixel_snapping;
+        self.dirty = true;
+    }
+
+    /// Sets whether the textbox's text is wrapped on multiple lines when wider than the textbox
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_wrapping` - boolean indicating if the text is wrapped
+    pub fn set_wrap(&mut self, is_wrapping: bool) {
+        self.text_layout = self.text_layout.with_wrap(is_wrapping);
+        self.dirty = true;
+    }
+
+    /// Sets how the lines of the textbox's text are aligned
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment to be assigned to the textbox's text
+    pub fn set_alignment(&mut self, alignment: TextAlignment) {
+        self.text_layout = self.text_layout.with_alignment(alignment);
+        self.dirty = true;
+    }
+
+    /// Sets the textbox's size (width and height), returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the textbox
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the textbox's background color, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the textbox
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the textbox's text color, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the text color assigned
+    ///
+    /// # Arguments
+    /// * `text_color` - the text color to be assigned to the textbox
+    pub fn with_text_color(mut self, text_color: Color) -> Self {
+        self.style.foreground_color = Some(text_color);
+        self.text_color = text_color;
+        self
+    }
+
+    /// Sets the textbox's border thickness, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the border thickness assigned
+    ///
+    /// # Arguments
+    /// * `border_thickness` - the border thickness to be assigned to the textbox
+    pub fn with_border_thickness(mut self, border_thickness: f64) -> Self {
+        self.style.border_thickness = Some(border_thickness);
+        self.border_thickness = border_thickness;
+        self
+    }
+
+    /// Sets the textbox's text, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the text assigned
+    ///
+    /// # Arguments
+    /// * `text` - the text to be assigned to the textbox
+    pub fn with_text(mut self, text: String) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Sets the textbox's font (e.g. a family other than the renderer's default one, or
+    /// its bold and italic variants)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the textbox's text
+    pub fn set_font(&mut self, font: FontDescriptor) {
+        self.font = font;
+        self.dirty = true;
+    }
+
+    /// Sets the textbox's font, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the font assigned
+    ///
+    /// # Arguments
+    /// * `font` - the font to be assigned to the textbox's text
+    pub fn with_font(mut self, font: FontDescriptor) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the textbox's message to be handled when the text changes, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the given message to be handled when the text changes
+    ///
+    /// # Arguments
+    /// * `on_text_change` - the message to be handled when the text changes
+    pub fn with_on_text_change(mut self, on_text_change: Option<Box<dyn Message>>) -> Self {
+        self.on_text_change = on_text_change;
+        self
+    }
+
+    /// Sets whether the textbox's text is wrapped, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with whether the text is wrapped assigned
+    ///
+    /// # Arguments
+    /// * `is_wrapping` - boolean indicating if the text is wrapped
+    pub fn with_wrap(mut self, is_wrapping: bool) -> Self {
+        self.text_layout = self.text_layout.with_wrap(is_wrapping);
+        self
+    }
+
+    /// Sets the alignment of the textbox's text, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the alignment assigned
+    ///
+    /// # Arguments
+    /// * `alignment` - the alignment to be assigned to the textbox's text
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.text_layout = self.text_layout.with_alignment(alignment);
+        self
+    }
+
+    /// Sets the textbox's position in the focus traversal (e.g. to exclude it from the traversal)
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `tab_index` - the textbox's tab index
+    pub fn set_tab_index(&mut self, tab_index: TabIndex) {
+        self.tab_index = tab_index;
+        self.dirty = true;
+    }
+
+    /// Sets the textbox's position in the focus traversal, returning the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the tab index assigned
+    ///
+    /// # Arguments
+    /// * `tab_index` - the tab index to be assigned to the textbox
+    pub fn with_tab_index(mut self, tab_index: TabIndex) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Sets the textbox's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the
+    /// theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the textbox
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the textbox's style, overriding the properties of the current theme, returning
+    /// the textbox (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The textbox with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the textbox
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for TextBoxWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        TextBoxWidget {
+            style: Style::new(),
+            ..TextBoxWidget::new(
+                Vector2D::new(200., 30.),
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.border_thickness,
+                String::new(),
+                None,
+            )
+        }
+    }
 }
 
 impl Widget for TextBoxWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
             }
             event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
                 if self.is_cursor_inside(self.cursor_pos) {
                     self.is_focused = true;
+                    EventStatus::Captured
                 } else {
                     self.is_focused = false;
+                    EventStatus::Ignored
                 }
             }
             _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
             }
         }
     }
@@ -144,14 +412,31 @@ impl Widget for TextBoxWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn tab_index(&self) -> TabIndex {
+        self.tab_index
+    }
+
+    fn activate(&mut self, _event: Event, _messages: &mut Queue<Box<dyn Message>>) {
+        self.is_focused = true;
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
-        vec![
+        let mut instructions = vec![
             RenderInstruction::DrawRect {
                 point: self.position,
                 size: self.size,
-                color: Color::from_hex(0xFF000000),
+                color: self.border_color,
                 clip_point: self.position,
                 clip_size: self.size,
+                blend_mode: BlendMode::Normal,
             },
             RenderInstruction::DrawRect {
                 point: Vector2D::new(
@@ -165,16 +450,30 @@ impl Widget for TextBoxWidget {
                 color: self.background_color,
                 clip_point: self.position,
                 clip_size: self.size,
+                blend_mode: BlendMode::Normal,
             },
-            RenderInstruction::DrawText {
-                point: Vector2D::new(self.position.x + 10., self.position.y + 20.),
-                font_size: 22,
-                string: self.text.clone(),
+        ];
+
+        // Text, laid out on lines within the padding
+        let runs = self.text_layout.layout(
+            &self.text,
+            FONT_SIZE,
+            self.size.x - 2. * self.padding,
+            &|text| text::measure_text(text, FONT_SIZE).x,
+        );
+        for run in runs {
+            instructions.push(RenderInstruction::DrawText {
+                point: Vector2D::new(self.position.x + self.padding, self.position.y + 20.) + run.point,
+                font_size: FONT_SIZE,
+                string: run.text,
                 color: self.text_color,
                 clip_point: self.position,
                 clip_size: self.size,
-            },
-        ]
+                font: self.font.clone(),
+            });
+        }
+
+        instructions
     }
 
     fn set_dirty(&mut self, value: bool) {
@@ -252,13 +551,9 @@ impl Widget for TextBoxWidget {
         self.offset = offset;
     }
 
-    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
 
-    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {
-        unimplemented!();
-    }
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
 
     fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
         if cursor_pos.x >= self.position.x
@@ -271,4 +566,17 @@ impl Widget for TextBoxWidget {
             false
         }
     }
+
+    fn pixel_snapping(&self) -> Option<bool> {
+        self.pixel_snapping
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.text_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.border_color = self.style.border_color.unwrap_or(theme.palette.border);
+        self.border_thickness = self.style.border_thickness.unwrap_or(theme.border_thickness);
+        self.padding = self.style.padding.unwrap_or(theme.padding);
+        self.dirty = true;
+    }
 }
@@ -0,0 +1,760 @@
+use crate::event;
+use crate::event::Event;
+use crate::message::ValueChanged;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Weak;
+
+/// Fraction of the time picker's height taken by the header displaying the time
+const HEADER_HEIGHT: f64 = 0.2;
+
+/// Distance from the center of the dial to the outer ring of values, as a fraction of the radius
+const OUTER_RING: f64 = 0.8;
+
+/// Distance from the center of the dial to the inner ring of hours, as a fraction of the radius
+const INNER_RING: f64 = 0.55;
+
+/// Average width of a glyph, as a fraction of the font size, used to center the text
+const GLYPH_WIDTH: f64 = 0.6;
+
+/// A time of the day, with minute precision
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    /// The hour, from 0 to 23
+    pub hour: u32,
+    /// The minute, from 0 to 59
+    pub minute: u32,
+}
+
+impl Time {
+    /// Creates a new `Time`
+    ///
+    /// # Returns
+    /// The time created, with the hour and minute clamped to their ranges
+    ///
+    /// # Arguments
+    /// * `hour` - the hour, from 0 to 23
+    /// * `minute` - the minute, from 0 to 59
+    pub fn new(hour: u32, minute: u32) -> Time {
+        Time {
+            hour: hour.min(23),
+            minute: minute.min(59),
+        }
+    }
+
+    /// Checks if the time is after noon
+    ///
+    /// # Returns
+    /// True, if the time is between 12:00 and 23:59, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_pm(&self) -> bool {
+        self.hour >= 12
+    }
+
+    /// Gets the hour on the 12-hour clock
+    ///
+    /// # Returns
+    /// The hour, from 1 to 12
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn hour_12(&self) -> u32 {
+        match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        }
+    }
+}
+
+/// How the hours are displayed and selected on a time picker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourFormat {
+    /// Hours from 1 to 12, followed by AM or PM
+    TwelveHour,
+    /// Hours from 0 to 23
+    TwentyFourHour,
+}
+
+/// Values selected on the dial of the time picker, for internal use only
+#[derive(Clone, Copy, PartialEq)]
+enum DialMode {
+    /// The dial selects the hour
+    Hours,
+    /// The dial selects the minute
+    Minutes,
+}
+
+/// Time picker is a widget that selects a time of the day on a clock-face dial,
+/// complementing the calendar.
+///
+/// The header displays the time selected; clicking the hour or the minute
+/// switches the dial between selecting hours and minutes, and clicking AM or PM
+/// toggles the half of the day on the 12-hour format. On the 24-hour format the
+/// dial has an inner ring with the hours from 13 to 00. Clicking or dragging on
+/// the dial selects the value under the cursor, moving on to the minutes once an
+/// hour is selected. When a value is selected, the `on_time_selected` message is
+/// handled, which is filled with the time if it is a [`ValueChanged<Time>`](`ValueChanged`).
+#[derive(Clone)]
+pub struct TimePickerWidget {
+    /// The time picker's identifier
+    id: usize,
+
+    /// The data attached to the time picker through its handle
+    widget_data: WidgetData,
+
+    /// The time selected
+    time: Time,
+
+    /// How the hours are displayed and selected
+    hour_format: HourFormat,
+
+    /// The values selected on the dial
+    dial_mode: DialMode,
+
+    /// Whether a value is being dragged on the dial
+    is_dragging: bool,
+
+    /// The message to be handled when a value is selected
+    on_time_selected: Option<Box<dyn Message>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The time picker's font size
+    font_size: usize,
+
+    /// The time picker's background color
+    background_color: Color,
+
+    /// The time picker's foreground color (i.e., text color)
+    foreground_color: Color,
+
+    /// The color of the clock face
+    dial_color: Color,
+
+    /// The color of the hand pointing to the value selected
+    accent_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The time picker's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The time picker's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The time picker's current size (width and height)
+    size: Vector2D,
+
+    /// The time picker's original size (width and height)
+    original_size: Vector2D,
+
+    /// The time picker's layout
+    layout: Layout,
+
+    /// The time picker's offset vector coordinates
+    offset: Vector2D,
+
+    /// The time picker's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl TimePickerWidget {
+    /// Creates a new `TimePickerWidget`
+    ///
+    /// # Returns
+    /// The time picker created
+    ///
+    /// # Arguments
+    /// * `time` - the time initially selected
+    /// * `hour_format` - how the hours are displayed and selected
+    /// * `size` - the size (width and height) to be assigned to the time picker
+    /// * `font_size` - the font size to be assigned to the time picker
+    /// * `background_color` - the color to be assigned to the time picker's background
+    /// * `foreground_color` - the color to be assigned to the time picker's text
+    /// * `accent_color` - the color to be assigned to the hand pointing to the value selected
+    pub fn new(
+        time: Time,
+        hour_format: HourFormat,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        foreground_color: Color,
+        accent_color: Color,
+    ) -> TimePickerWidget {
+        TimePickerWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(foreground_color)
+                .with_accent_color(accent_color),
+            time,
+            hour_format,
+            dial_mode: DialMode::Hours,
+            is_dragging: false,
+            on_time_selected: None,
+            cursor_pos: Vector2D::new(-1., -1.),
+            font_size,
+            background_color,
+            foreground_color,
+            dial_color: foreground_color.with_opacity(0.1),
+            accent_color,
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the message to be handled when a value is selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `on_time_selected` - the message to be handled when a value is selected
+    pub fn set_message(&mut self, on_time_selected: Option<Box<dyn Message>>) {
+        self.on_time_selected = on_time_selected;
+    }
+
+    /// Sets the time selected, without handling the `on_time_selected` message
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `time` - the time to be selected
+    pub fn set_time(&mut self, time: Time) {
+        self.time = time;
+        self.dirty = true;
+    }
+
+    /// Gets the time selected
+    ///
+    /// # Returns
+    /// The time selected
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_time(&self) -> Time {
+        self.time
+    }
+
+    /// Sets how the hours are displayed and selected
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `hour_format` - how the hours are displayed and selected
+    pub fn set_hour_format(&mut self, hour_format: HourFormat) {
+        self.hour_format = hour_format;
+        self.dirty = true;
+    }
+
+    /// Sets the color of the clock face
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `dial_color` - the color to be assigned to the clock face
+    pub fn set_dial_color(&mut self, dial_color: Color) {
+        self.style.muted_color = Some(dial_color);
+        self.dial_color = dial_color;
+        self.dirty = true;
+    }
+
+    /// Gets the center and radius of the dial, for internal use only
+    ///
+    /// # Returns
+    /// The center of the dial and its radius
+    ///
+    /// # Arguments
+    /// No arguments
+    fn dial(&self) -> (Vector2D, f64) {
+        let header = self.size.y * HEADER_HEIGHT;
+        let area = Vector2D::new(self.size.x, self.size.y - header);
+        let radius = (area.x.min(area.y) * 0.5 - 4.).max(0.);
+        (self.position + Vector2D::new(area.x * 0.5, header + area.y * 0.5), radius)
+    }
+
+    /// Gets the point of the dial at a position of the clock, for internal use only
+    ///
+    /// # Returns
+    /// The point of the dial
+    ///
+    /// # Arguments
+    /// * `step` - the position of the clock, from 0 (top) to 12 (top again), clockwise
+    /// * `ring` - the distance from the center, as a fraction of the radius
+    fn dial_point(&self, step: f64, ring: f64) -> Vector2D {
+        let (center, radius) = self.dial();
+        let angle = step / 12. * 2. * PI;
+        center + Vector2D::new(angle.sin(), -angle.cos()) * (radius * ring)
+    }
+
+    /// Selects the value of the dial under a position, for internal use only
+    ///
+    /// # Returns
+    /// True, if the time changed, false otherwise
+    ///
+    /// # Arguments
+    /// * `position` - the position on the dial
+    fn select_at(&mut self, position: Vector2D) -> bool {
+        let (center, radius) = self.dial();
+        let delta = position - center;
+        let distance = (delta.x * delta.x + delta.y * delta.y).sqrt();
+        // The angle from the top of the dial, clockwise, from 0 to 1
+        let turn = (delta.x.atan2(-delta.y) / (2. * PI)).rem_euclid(1.);
+
+        let time = match self.dial_mode {
+            DialMode::Hours => {
+                let step = (turn * 12.).round() as u32 % 12;
+                let hour = match self.hour_format {
+                    HourFormat::TwelveHour if self.time.is_pm() => step + 12,
+                    HourFormat::TwelveHour => step,
+                    // The inner ring holds the hours from 13 to 00
+                    HourFormat::TwentyFourHour
+                        if distance < radius * (OUTER_RING + INNER_RING) * 0.5 =>
+                    {
+                        if step == 0 { 0 } else { step + 12 }
+                    }
+                    HourFormat::TwentyFourHour if step == 0 => 12,
+                    HourFormat::TwentyFourHour => step,
+                };
+                Time::new(hour, self.time.minute)
+            }
+            DialMode::Minutes => Time::new(self.time.hour, (turn * 60.).round() as u32 % 60),
+        };
+
+        let is_changed = time != self.time;
+        self.time = time;
+        self.dirty |= is_changed;
+        is_changed
+    }
+
+    /// Enqueues the `on_time_selected` message, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `event` - the event that selected the time
+    /// * `messages` - queue of messages
+    fn notify(&self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(mut message) = self.on_time_selected.clone() {
+            message.set_event(event);
+            if let Some(message) = message.downcast_mut::<ValueChanged<Time>>() {
+                message.value = self.time;
+            }
+            messages.enqueue(message);
+        }
+    }
+
+    /// Gets the render instruction of a text centered on a point, for internal use only
+    ///
+    /// # Returns
+    /// The render instruction of the text
+    ///
+    /// # Arguments
+    /// * `text` - the text to be drawn
+    /// * `center` - the point where the text is centered
+    /// * `font_size` - the text font size
+    /// * `color` - the text color
+    fn text_recipe(&self, text: String, center: Vector2D, font_size: usize, color: Color) -> RenderInstruction {
+        let width = text.chars().count() as f64 * font_size as f64 * GLYPH_WIDTH;
+        RenderInstruction::DrawText {
+            point: Vector2D::new(center.x - width * 0.5, center.y + font_size as f64 * 0.5),
+            font_size,
+            string: text,
+            color,
+            clip_point: self.position,
+            clip_size: self.size,
+            font: FontDescriptor::default(),
+        }
+    }
+
+    /// Sets the time picker's time selected, returning the time picker (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The time picker with the time selected assigned
+    ///
+    /// # Arguments
+    /// * `time` - the time selected to be assigned to the time picker
+    pub fn with_time(mut self, time: Time) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Sets the time picker's hour format, returning the time picker (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The time picker with the hour format assigned
+    ///
+    /// # Arguments
+    /// * `hour_format` - the hour format to be assigned to the time picker
+    pub fn with_hour_format(mut self, hour_format: HourFormat) -> Self {
+        self.hour_format = hour_format;
+        self
+    }
+
+    /// Sets the time picker's size (width and height), returning the time picker (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The time picker with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the time picker
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the time picker's message to be handled when a value is selected, returning the time picker (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The time picker with the given message to be handled when a value is selected
+    ///
+    /// # Arguments
+    /// * `on_time_selected` - the message to be handled when a value is selected
+    pub fn with_on_time_selected(mut self, on_time_selected: Option<Box<dyn Message>>) -> Self {
+        self.on_time_selected = on_time_selected;
+        self
+    }
+
+    /// Sets the time picker's style, overriding the properties of the current theme and the
+    /// ones set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the time picker
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the time picker's style, overriding the properties of the current theme, returning
+    /// the time picker (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The time picker with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the time picker
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for TimePickerWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        TimePickerWidget {
+            style: Style::new(),
+            ..TimePickerWidget::new(
+                Time::default(),
+                HourFormat::TwentyFourHour,
+                Vector2D::new(256., 320.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                theme.palette.accent,
+            )
+        }
+    }
+}
+
+impl Widget for TimePickerWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                if self.is_dragging {
+                    self.select_at(self.cursor_pos);
+                }
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                let header = self.size.y * HEADER_HEIGHT;
+                if self.cursor_pos.y - self.position.y < header {
+                    // The header is split into the hour, the minute and, on the
+                    // 12-hour format, the half of the day
+                    let columns = match self.hour_format {
+                        HourFormat::TwelveHour => 3.,
+                        HourFormat::TwentyFourHour => 2.,
+                    };
+                    let column = ((self.cursor_pos.x - self.position.x) / self.size.x * columns) as usize;
+                    match column {
+                        0 => self.dial_mode = DialMode::Hours,
+                        1 => self.dial_mode = DialMode::Minutes,
+                        _ => {
+                            self.time = Time::new((self.time.hour + 12) % 24, self.time.minute);
+                            self.notify(event, messages);
+                        }
+                    }
+                    self.dirty = true;
+                } else {
+                    self.is_dragging = true;
+                    self.select_at(self.cursor_pos);
+                }
+                EventStatus::Captured
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left))
+                if self.is_dragging =>
+            {
+                self.is_dragging = false;
+                self.notify(event, messages);
+                // Move on to the minutes once an hour is selected
+                if self.dial_mode == DialMode::Hours {
+                    self.dial_mode = DialMode::Minutes;
+                    self.dirty = true;
+                }
+                EventStatus::Captured
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let (center, radius) = self.dial();
+        let mut instructions = vec![
+            // Time picker rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+            // Clock face
+            RenderInstruction::DrawCircle {
+                point: center,
+                r: radius as usize,
+                color: self.dial_color,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        // Header, highlighting the part selected on the dial
+        let header = self.size.y * HEADER_HEIGHT;
+        let header_font_size = (header * 0.5) as usize;
+        let (hour, half) = match self.hour_format {
+            HourFormat::TwelveHour => (
+                self.time.hour_12(),
+                Some(if self.time.is_pm() { "PM" } else { "AM" }),
+            ),
+            HourFormat::TwentyFourHour => (self.time.hour, None),
+        };
+        let mut parts = vec![format!("{:02}", hour), format!("{:02}", self.time.minute)];
+        if let Some(half) = half {
+            parts.push(String::from(half));
+        }
+        let column_width = self.size.x / parts.len() as f64;
+        for (column, part) in parts.into_iter().enumerate() {
+            let is_selected = match self.dial_mode {
+                DialMode::Hours => column == 0,
+                DialMode::Minutes => column == 1,
+            };
+            let color = if is_selected {
+                self.accent_color
+            } else {
+                self.foreground_color
+            };
+            let part_center = self.position
+                + Vector2D::new(column_width * (column as f64 + 0.5), header * 0.5);
+            instructions.push(self.text_recipe(part, part_center, header_font_size, color));
+        }
+
+        // Hand pointing to the value selected
+        let (step, ring) = match self.dial_mode {
+            DialMode::Hours => {
+                let is_inner = self.hour_format == HourFormat::TwentyFourHour
+                    && (self.time.hour == 0 || self.time.hour > 12);
+                let ring = if is_inner { INNER_RING } else { OUTER_RING };
+                ((self.time.hour % 12) as f64, ring)
+            }
+            DialMode::Minutes => (self.time.minute as f64 / 5., OUTER_RING),
+        };
+        let hand = self.dial_point(step, ring);
+        instructions.push(RenderInstruction::DrawLine {
+            point_a: center,
+            point_b: hand,
+            color: self.accent_color,
+            clip_point: self.position,
+            clip_size: self.size,
+        });
+        instructions.push(RenderInstruction::DrawCircle {
+            point: hand,
+            r: self.font_size,
+            color: self.accent_color,
+            clip_point: self.position,
+            clip_size: self.size,
+            blend_mode: BlendMode::Normal,
+        });
+
+        // Values around the dial
+        for step in 0..12 {
+            let (outer, inner) = match self.dial_mode {
+                DialMode::Hours => match self.hour_format {
+                    HourFormat::TwelveHour => (if step == 0 { 12 } else { step }, None),
+                    HourFormat::TwentyFourHour => {
+                        (if step == 0 { 12 } else { step }, Some(if step == 0 { 0 } else { step + 12 }))
+                    }
+                },
+                DialMode::Minutes => (step * 5, None),
+            };
+            let text = match self.dial_mode {
+                DialMode::Hours => outer.to_string(),
+                DialMode::Minutes => format!("{:02}", outer),
+            };
+            instructions.push(self.text_recipe(
+                text,
+                self.dial_point(step as f64, OUTER_RING),
+                self.font_size,
+                self.foreground_color,
+            ));
+            if let Some(inner) = inner {
+                instructions.push(self.text_recipe(
+                    format!("{:02}", inner),
+                    self.dial_point(step as f64, INNER_RING),
+                    self.font_size * 3 / 4,
+                    self.foreground_color,
+                ));
+            }
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dial_color = self.style.muted_color.unwrap_or(self.foreground_color.with_opacity(0.1));
+        self.accent_color = self.style.accent_color.unwrap_or(theme.palette.accent);
+        self.dirty = true;
+    }
+}
@@ -0,0 +1,646 @@
+use crate::context::{Context, WindowRequest};
+use crate::event;
+use crate::event::Event;
+use crate::renderer::{BlendMode, FontDescriptor, Message, RenderInstruction};
+use crate::style::{self, Style, Theme};
+use crate::util::{Color, Queue, Vector2D};
+use crate::widget::{deliver_frame, EventStatus, Layout, Widget, WidgetData};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Buttons of the title bar, for internal use only
+#[derive(Clone, Copy, PartialEq)]
+enum TitleBarButton {
+    /// The button minimizing the display
+    Minimize,
+    /// The button maximizing or restoring the display
+    Maximize,
+    /// The button closing the display
+    Close,
+}
+
+/// Title bar is a widget that replaces the title bar of a borderless display,
+/// completing its custom chrome.
+///
+/// Dragging the title bar moves the display and the minimize, maximize and close
+/// buttons on its right end act on the display, through the requests placed on the
+/// [`Context`]. The title displayed is also assigned to the display, through
+/// [`set_title`](`crate::display::Display::set_title`).
+#[derive(Clone)]
+pub struct TitleBarWidget {
+    /// The title bar's identifier
+    id: usize,
+
+    /// The data attached to the title bar through its handle
+    widget_data: WidgetData,
+
+    /// The title displayed on the title bar and assigned to the display
+    title: String,
+
+    /// The title's font size
+    font_size: usize,
+
+    /// Whether the title still needs to be assigned to the display
+    is_title_pending: bool,
+
+    /// Whether the title bar has a minimize button
+    is_minimizable: bool,
+
+    /// Whether the title bar has a maximize button
+    is_maximizable: bool,
+
+    /// The button under the cursor
+    hovered_button: Option<TitleBarButton>,
+
+    /// The button pressed, waiting for the mouse button to be released
+    pressed_button: Option<TitleBarButton>,
+
+    /// The context where the display requests are placed
+    context: Weak<RefCell<Context>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The title bar's background color
+    background_color: Color,
+
+    /// The title bar's foreground color (i.e., the title and the glyphs of the buttons)
+    foreground_color: Color,
+
+    /// The background color of the button under the cursor
+    hover_color: Color,
+
+    /// The background color of the close button, when under the cursor
+    close_hover_color: Color,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The title bar's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The title bar's position, on a two-dimensional space (x-coordinate and
+    /// y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The title bar's current size (width and height)
+    size: Vector2D,
+
+    /// The title bar's original size (width and height)
+    original_size: Vector2D,
+
+    /// The title bar's layout
+    layout: Layout,
+
+    /// The title bar's offset vector coordinates
+    offset: Vector2D,
+
+    /// The title bar's style, overriding the properties of the theme
+    style: Style,
+}
+
+impl TitleBarWidget {
+    /// Creates a new `TitleBarWidget`
+    ///
+    /// # Returns
+    /// The title bar created
+    ///
+    /// # Arguments
+    /// * `title` - the title to be displayed and assigned to the display
+    /// * `size` - the size (width and height) to be assigned to the title bar
+    /// * `font_size` - the font size to be assigned to the title
+    /// * `background_color` - the color to be assigned to the title bar's background
+    /// * `foreground_color` - the color to be assigned to the title and the glyphs of the buttons
+    /// * `context` - the context where the display requests are placed
+    pub fn new(
+        title: &str,
+        size: Vector2D,
+        font_size: usize,
+        background_color: Color,
+        foreground_color: Color,
+        context: Weak<RefCell<Context>>,
+    ) -> TitleBarWidget {
+        TitleBarWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            style: Style::new()
+                .with_font_size(font_size)
+                .with_background_color(background_color)
+                .with_foreground_color(foreground_color),
+            title: String::from(title),
+            font_size,
+            is_title_pending: true,
+            is_minimizable: true,
+            is_maximizable: true,
+            hovered_button: None,
+            pressed_button: None,
+            context,
+            cursor_pos: Vector2D::new(-1., -1.),
+            background_color,
+            foreground_color,
+            hover_color: Color::from_hex(0x20000000),
+            close_hover_color: Color::from_hex(0xffe81123),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::None,
+            offset: Vector2D::new(0., 0.),
+        }
+    }
+
+    /// Sets the title displayed on the title bar, which is also assigned to the display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+        self.is_title_pending = true;
+        self.dirty = true;
+    }
+
+    /// Gets the title displayed on the title bar
+    ///
+    /// # Returns
+    /// The title displayed on the title bar
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Sets whether the title bar has a minimize button
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_minimizable` - boolean indicating if the title bar has a minimize button
+    pub fn set_minimizable(&mut self, is_minimizable: bool) {
+        self.is_minimizable = is_minimizable;
+        self.dirty = true;
+    }
+
+    /// Sets whether the title bar has a maximize button
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `is_maximizable` - boolean indicating if the title bar has a maximize button
+    pub fn set_maximizable(&mut self, is_maximizable: bool) {
+        self.is_maximizable = is_maximizable;
+        self.dirty = true;
+    }
+
+    /// Sets the background colors of the buttons under the cursor
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `hover_color` - the background color of the minimize and maximize buttons under the cursor
+    /// * `close_hover_color` - the background color of the close button under the cursor
+    pub fn set_hover_colors(&mut self, hover_color: Color, close_hover_color: Color) {
+        self.hover_color = hover_color;
+        self.close_hover_color = close_hover_color;
+        self.dirty = true;
+    }
+
+    /// Gets the buttons displayed on the title bar, from right to left, for internal use only
+    ///
+    /// # Returns
+    /// The buttons displayed, with their position
+    ///
+    /// # Arguments
+    /// No arguments
+    fn buttons(&self) -> Vec<(TitleBarButton, Vector2D)> {
+        let mut buttons = vec![TitleBarButton::Close];
+        if self.is_maximizable {
+            buttons.push(TitleBarButton::Maximize);
+        }
+        if self.is_minimizable {
+            buttons.push(TitleBarButton::Minimize);
+        }
+
+        // Buttons are squares as high as the title bar, placed from the right end
+        buttons
+            .into_iter()
+            .enumerate()
+            .map(|(index, button)| {
+                let x = self.position.x + self.size.x - self.size.y * (index + 1) as f64;
+                (button, Vector2D::new(x, self.position.y))
+            })
+            .collect()
+    }
+
+    /// Gets the button under a position, for internal use only
+    ///
+    /// # Returns
+    /// The button under the position, or `None` if there is none
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn button_at(&self, position: Vector2D) -> Option<TitleBarButton> {
+        self.buttons()
+            .into_iter()
+            .find(|(_, point)| {
+                position.x >= point.x
+                    && position.x <= point.x + self.size.y
+                    && position.y >= point.y
+                    && position.y <= point.y + self.size.y
+            })
+            .map(|(button, _)| button)
+    }
+
+    /// Places a request on the context, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `request` - the request to be forwarded to the display
+    fn request_window(&self, request: WindowRequest) {
+        if let Some(context) = self.context.upgrade() {
+            context.borrow_mut().request_window(request);
+        }
+    }
+
+    /// Gets the render instructions of a button's glyph, for internal use only
+    ///
+    /// # Returns
+    /// The render instructions of the glyph
+    ///
+    /// # Arguments
+    /// * `button` - the button whose glyph is drawn
+    /// * `point` - the top left corner of the button
+    fn glyph_recipe(&self, button: TitleBarButton, point: Vector2D) -> Vec<RenderInstruction> {
+        let side = self.size.y;
+        let glyph = (side * 0.3).round();
+        let start = point + (side - glyph) * 0.5;
+        let end = start + glyph;
+        let line = |point_a: Vector2D, point_b: Vector2D| RenderInstruction::DrawLine {
+            point_a,
+            point_b,
+            color: self.foreground_color,
+            clip_point: point,
+            clip_size: Vector2D::new(side, side),
+        };
+
+        match button {
+            TitleBarButton::Minimize => {
+                let y = point.y + side * 0.5;
+                vec![line(Vector2D::new(start.x, y), Vector2D::new(end.x, y))]
+            }
+            TitleBarButton::Maximize => vec![
+                line(start, Vector2D::new(end.x, start.y)),
+                line(Vector2D::new(end.x, start.y), end),
+                line(end, Vector2D::new(start.x, end.y)),
+                line(Vector2D::new(start.x, end.y), start),
+            ],
+            TitleBarButton::Close => vec![
+                line(start, end),
+                line(Vector2D::new(end.x, start.y), Vector2D::new(start.x, end.y)),
+            ],
+        }
+    }
+
+    /// Sets the title bar's title, returning the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the title assigned
+    ///
+    /// # Arguments
+    /// * `title` - the title to be assigned to the title bar
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = String::from(title);
+        self
+    }
+
+    /// Sets the title bar's size (width and height), returning the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the title bar
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the title bar's font size, returning the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the font size assigned
+    ///
+    /// # Arguments
+    /// * `font_size` - the font size to be assigned to the title bar
+    pub fn with_font_size(mut self, font_size: usize) -> Self {
+        self.style.font_size = Some(font_size);
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the title bar's background color, returning the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the background color assigned
+    ///
+    /// # Arguments
+    /// * `background_color` - the background color to be assigned to the title bar
+    pub fn with_background_color(mut self, background_color: Color) -> Self {
+        self.style.background_color = Some(background_color);
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets the title bar's foreground color, returning the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the foreground color assigned
+    ///
+    /// # Arguments
+    /// * `foreground_color` - the foreground color to be assigned to the title bar
+    pub fn with_foreground_color(mut self, foreground_color: Color) -> Self {
+        self.style.foreground_color = Some(foreground_color);
+        self.foreground_color = foreground_color;
+        self
+    }
+
+    /// Sets the title bar's style, overriding the properties of the current theme and the ones
+    /// set before, which are kept unless the style sets them, and applying the theme again
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the title bar
+    pub fn set_style(&mut self, style: Style) {
+        self.style = self.style.merge(&style);
+        self.set_theme(&style::current_theme());
+    }
+
+    /// Sets the title bar's style, overriding the properties of the current theme, returning
+    /// the title bar (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The title bar with the style assigned
+    ///
+    /// # Arguments
+    /// * `style` - the style to be assigned to the title bar
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.set_style(style);
+        self
+    }
+}
+
+impl Default for TitleBarWidget {
+    fn default() -> Self {
+        let theme = style::current_theme();
+        // The properties read from the theme are not overrides
+        TitleBarWidget {
+            style: Style::new(),
+            ..TitleBarWidget::new(
+                "",
+                Vector2D::new(640., 32.),
+                theme.font_size,
+                theme.palette.surface,
+                theme.palette.foreground,
+                Weak::new(),
+            )
+        }
+    }
+}
+
+impl Widget for TitleBarWidget {
+    fn on_event(&mut self, event: Event, _messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+                let hovered_button = self.button_at(self.cursor_pos);
+                if hovered_button != self.hovered_button {
+                    self.hovered_button = hovered_button;
+                    self.dirty = true;
+                }
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::CursorLeft) if self.hovered_button.is_some() => {
+                self.hovered_button = None;
+                self.dirty = true;
+                EventStatus::Ignored
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left))
+                if self.is_cursor_inside(self.cursor_pos) =>
+            {
+                match self.button_at(self.cursor_pos) {
+                    Some(button) => self.pressed_button = Some(button),
+                    // Dragging the rest of the title bar moves the display
+                    None => self.request_window(WindowRequest::BeginMove),
+                }
+                EventStatus::Captured
+            }
+            event::Event::Mouse(event::Mouse::ButtonReleased(event::MouseButton::Left)) => {
+                // The button only acts if the mouse button is released over it
+                match self.pressed_button.take() {
+                    Some(button) => {
+                        if self.button_at(self.cursor_pos) == Some(button) {
+                            self.request_window(match button {
+                                TitleBarButton::Minimize => WindowRequest::Minimize,
+                                TitleBarButton::Maximize => WindowRequest::ToggleMaximized,
+                                TitleBarButton::Close => WindowRequest::Close,
+                            });
+                        }
+                        EventStatus::Captured
+                    }
+                    None => EventStatus::Ignored,
+                }
+            }
+            _ => EventStatus::Ignored,
+        }
+    }
+
+    fn on_frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        // Keep the display's title bound to the displayed one
+        if self.is_title_pending {
+            if let Some(context) = self.context.upgrade() {
+                context
+                    .borrow_mut()
+                    .request_window(WindowRequest::SetTitle(self.title.clone()));
+                self.is_title_pending = false;
+            }
+        }
+
+        for value in self.children.iter_mut() {
+            if let Some(child) = value.upgrade() {
+                deliver_frame(&mut *child.borrow_mut(), messages);
+            }
+        }
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        let mut instructions = vec![
+            // Title bar rectangle
+            RenderInstruction::DrawRect {
+                point: self.position,
+                color: self.background_color,
+                size: self.size,
+                clip_point: self.position,
+                clip_size: self.size,
+                blend_mode: BlendMode::Normal,
+            },
+        ];
+
+        let buttons = self.buttons();
+        let buttons_width = self.size.y * buttons.len() as f64;
+
+        // Title, vertically centered and clipped before the buttons
+        let padding = (self.size.y - self.font_size as f64) * 0.5;
+        instructions.push(RenderInstruction::DrawText {
+            point: Vector2D::new(
+                self.position.x + padding,
+                self.position.y + self.size.y - padding,
+            ),
+            color: self.foreground_color,
+            font_size: self.font_size,
+            string: self.title.clone(),
+            clip_point: self.position,
+            clip_size: Vector2D::new((self.size.x - buttons_width).max(0.), self.size.y),
+            font: FontDescriptor::default(),
+        });
+
+        for (button, point) in buttons {
+            if self.hovered_button == Some(button) {
+                let color = if button == TitleBarButton::Close {
+                    self.close_hover_color
+                } else {
+                    self.hover_color
+                };
+                instructions.push(RenderInstruction::DrawRect {
+                    point,
+                    color,
+                    size: Vector2D::new(self.size.y, self.size.y),
+                    clip_point: self.position,
+                    clip_size: self.size,
+                    blend_mode: BlendMode::Normal,
+                });
+            }
+            instructions.append(&mut self.glyph_recipe(button, point));
+        }
+
+        instructions
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        self.dirty = value;
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.dirty = true;
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.font_size = self.style.font_size.unwrap_or(theme.font_size);
+        self.background_color = self.style.background_color.unwrap_or(theme.palette.surface);
+        self.foreground_color = self.style.foreground_color.unwrap_or(theme.palette.foreground);
+        self.dirty = true;
+    }
+}
@@ -1,20 +1,158 @@
+use crate::clock;
+use crate::context::Context;
 use crate::event;
 use crate::event::Event;
 use crate::renderer::{
     AbsoluteWidgetCollection, Message, RenderInstruction, RenderInstructionCollection,
 };
-use crate::util::{Queue, Vector2D};
-use crate::widget::{Axis, Layout, Widget};
+use crate::util::{Queue, Rect, Vector2D};
+use crate::widget::{
+    deliver_frame, is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget,
+    WidgetData,
+};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::rc::Weak;
+use std::time::{Duration, Instant};
+
+/// Default time the cursor rests over the tooltip view before the tooltip is shown
+const SHOW_DELAY: Duration = Duration::from_millis(500);
+
+/// Space between the tooltip and the widget it describes
+const GAP: f64 = 4.;
+
+/// Where a tooltip is placed, relative to the widget it describes (i.e., the
+/// tooltip view's content)
+///
+/// # Examples
+///
+/// ```
+/// use hyber::util::{Rect, Vector2D};
+/// use hyber::widget::tooltip_view::TooltipPlacement;
+///
+/// let host = Rect::new(Vector2D::new(10., 560.), Vector2D::new(100., 30.));
+/// let size = Vector2D::new(80., 20.);
+///
+/// // Below the host, unless it would overflow the display, then above it
+/// let position = TooltipPlacement::Below.place(host, host.position, size, None);
+/// assert_eq!(position.y, 594.);
+/// let position = TooltipPlacement::Below.place(
+///     host,
+///     host.position,
+///     size,
+///     Some(Vector2D::new(800., 600.)),
+/// );
+/// assert_eq!(position.y, 536.);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TooltipPlacement {
+    /// At the cursor's position, as when the tooltip was shown
+    Cursor,
+    /// Above the widget, horizontally centered on it
+    Above,
+    /// Below the widget, horizontally centered on it
+    #[default]
+    Below,
+    /// Left of the widget, vertically centered on it
+    Left,
+    /// Right of the widget, vertically centered on it
+    Right,
+}
+
+impl TooltipPlacement {
+    /// Places a tooltip, flipped to the opposite side of the widget (or of the cursor)
+    /// along the axes it overflows the display on, then clamped within the display
+    ///
+    /// # Returns
+    /// The tooltip's upper left corner
+    ///
+    /// # Arguments
+    /// * `host` - the area of the widget the tooltip describes
+    /// * `cursor` - the cursor's position
+    /// * `size` - the tooltip's size (width and height)
+    /// * `display_size` - the display's size (width and height), or `None` if unbounded
+    pub fn place(
+        self,
+        host: Rect,
+        cursor: Vector2D,
+        size: Vector2D,
+        display_size: Option<Vector2D>,
+    ) -> Vector2D {
+        let mut position = self.position(host, cursor, size, false);
+        let display_size = match display_size {
+            Some(display_size) => display_size,
+            None => return position,
+        };
+        let end = display_size - size;
+        let flipped = self.position(host, cursor, size, true);
+        let is_horizontal = matches!(
+            self,
+            TooltipPlacement::Cursor | TooltipPlacement::Left | TooltipPlacement::Right
+        );
+        let is_vertical = matches!(
+            self,
+            TooltipPlacement::Cursor | TooltipPlacement::Above | TooltipPlacement::Below
+        );
+        if is_horizontal
+            && (position.x < 0. || position.x > end.x)
+            && flipped.x >= 0.
+            && flipped.x <= end.x
+        {
+            position.x = flipped.x;
+        }
+        if is_vertical
+            && (position.y < 0. || position.y > end.y)
+            && flipped.y >= 0.
+            && flipped.y <= end.y
+        {
+            position.y = flipped.y;
+        }
+        Vector2D::new(position.x.min(end.x).max(0.), position.y.min(end.y).max(0.))
+    }
+
+    /// Places a tooltip on one side of the widget (or of the cursor), for internal use only
+    ///
+    /// # Returns
+    /// The tooltip's upper left corner, possibly outside of the display
+    ///
+    /// # Arguments
+    /// * `host` - the area of the widget the tooltip describes
+    /// * `cursor` - the cursor's position
+    /// * `size` - the tooltip's size (width and height)
+    /// * `is_flipped` - whether the tooltip is placed on the opposite side
+    fn position(self, host: Rect, cursor: Vector2D, size: Vector2D, is_flipped: bool) -> Vector2D {
+        let center = host.position + (host.size - size) / 2.;
+        match (self, is_flipped) {
+            (TooltipPlacement::Cursor, false) => cursor,
+            (TooltipPlacement::Cursor, true) => cursor - size,
+            (TooltipPlacement::Above, false) | (TooltipPlacement::Below, true) => {
+                Vector2D::new(center.x, host.position.y - size.y - GAP)
+            }
+            (TooltipPlacement::Below, false) | (TooltipPlacement::Above, true) => {
+                Vector2D::new(center.x, host.position.y + host.size.y + GAP)
+            }
+            (TooltipPlacement::Left, false) | (TooltipPlacement::Right, true) => {
+                Vector2D::new(host.position.x - size.x - GAP, center.y)
+            }
+            (TooltipPlacement::Right, false) | (TooltipPlacement::Left, true) => {
+                Vector2D::new(host.position.x + host.size.x + GAP, center.y)
+            }
+        }
+    }
+}
 
 /// Tooltip is a widget that is used to display a "Tip" for another widget.
+///
+/// The tip is shown once the cursor rests over the tooltip view's content for the
+/// show delay, placed around the content and kept within the display.
 #[derive(Clone)]
 pub struct TooltipViewWidget {
     /// The tooltip's identifier
     id: usize,
+
+    /// The data attached to the tooltip through its handle
+    widget_data: WidgetData,
     
     /// The tooltip's current size (width and height)
     size: Vector2D,
@@ -47,6 +185,19 @@ pub struct TooltipViewWidget {
     
     /// The widget to be presented whether the tooltip is enabled
     tooltip_widget: Weak<RefCell<dyn Widget>>,
+
+    /// The context asked for a frame once the show delay elapses, and bounding the
+    /// tooltip within the display
+    context: Weak<RefCell<Context>>,
+
+    /// The time the cursor rests over the tooltip view before the tooltip is shown
+    show_delay: Duration,
+
+    /// Where the tooltip is placed, relative to the tooltip view
+    placement: TooltipPlacement,
+
+    /// The instant when the cursor entered the tooltip view, if it is over it
+    hover_start: Option<Instant>,
 }
 
 impl TooltipViewWidget {
@@ -69,6 +220,7 @@ impl TooltipViewWidget {
     ) -> TooltipViewWidget {
         TooltipViewWidget {
             id: 0,
+            widget_data: WidgetData::default(),
             size: size,
             original_size: size,
             position: Vector2D::new(0., 0.),
@@ -79,65 +231,264 @@ impl TooltipViewWidget {
             render_instruction_collection_ptr: render_instruction_collection_ptr,
             absolute_widget_collection_ptr: absolute_widget_collection_ptr,
             tooltip_widget: tooltip_widget,
+            context: Weak::new(),
+            show_delay: SHOW_DELAY,
+            placement: TooltipPlacement::default(),
+            hover_start: None,
         }
     }
+
+    /// Sets the context asked for a frame once the show delay elapses (such that the
+    /// tooltip is shown even if the event loop is waiting) and bounding the tooltip
+    /// within the display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    pub fn set_context(&mut self, context: Weak<RefCell<Context>>) {
+        self.context = context;
+    }
+
+    /// Sets the context asked for a frame once the show delay elapses, returning the
+    /// tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the context assigned
+    ///
+    /// # Arguments
+    /// * `context` - the pointer to the context
+    pub fn with_context(mut self, context: Weak<RefCell<Context>>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Sets the time the cursor rests over the tooltip view before the tooltip is shown
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `show_delay` - the delay before the tooltip is shown, zero to show it at once
+    pub fn set_show_delay(&mut self, show_delay: Duration) {
+        self.show_delay = show_delay;
+    }
+
+    /// Sets the time the cursor rests over the tooltip view before the tooltip is shown,
+    /// returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the show delay assigned
+    ///
+    /// # Arguments
+    /// * `show_delay` - the delay before the tooltip is shown, zero to show it at once
+    pub fn with_show_delay(mut self, show_delay: Duration) -> Self {
+        self.show_delay = show_delay;
+        self
+    }
+
+    /// Sets where the tooltip is placed, relative to the tooltip view
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `placement` - the placement of the tooltip
+    pub fn set_placement(&mut self, placement: TooltipPlacement) {
+        self.placement = placement;
+    }
+
+    /// Sets where the tooltip is placed, returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the placement assigned
+    ///
+    /// # Arguments
+    /// * `placement` - the placement of the tooltip
+    pub fn with_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Checks if the tooltip is shown
+    ///
+    /// # Returns
+    /// True, if the tooltip widget is on the absolute widgets collection, false otherwise
+    ///
+    /// # Arguments
+    /// No arguments
+    pub fn is_shown(&self) -> bool {
+        self.tooltip_widget
+            .upgrade()
+            .is_some_and(|widget| widget.borrow().id() != 0)
+    }
+
+    /// Shows the tooltip, placed around the tooltip view, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn show(&mut self) {
+        if let Some(absolute_widget_collection) = self.absolute_widget_collection_ptr.upgrade() {
+            if let Some(widget) = self.tooltip_widget.upgrade() {
+                widget.borrow_mut().set_dirty(true);
+                let size = widget.borrow_mut().original_size();
+                let display_size = self
+                    .context
+                    .upgrade()
+                    .and_then(|context| context.borrow().display_size());
+                let position = self.placement.place(
+                    Rect::new(self.position, self.size),
+                    self.cursor_pos,
+                    size,
+                    display_size,
+                );
+                absolute_widget_collection
+                    .borrow_mut()
+                    .insert(Rc::downgrade(&widget), position, size);
+            }
+        }
+    }
+
+    /// Hides the tooltip and stops waiting for the show delay, for internal use only
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// No arguments
+    fn hide(&mut self) {
+        self.hover_start = None;
+        if !self.is_shown() {
+            return;
+        }
+        if let Some(render_instruction_collection) =
+            self.render_instruction_collection_ptr.upgrade()
+        {
+            if let Some(absolute_widget_collection) = self.absolute_widget_collection_ptr.upgrade()
+            {
+                if let Some(widget) = self.tooltip_widget.upgrade() {
+                    render_instruction_collection
+                        .borrow_mut()
+                        .remove(widget.borrow_mut().id());
+                    absolute_widget_collection
+                        .borrow_mut()
+                        .remove(widget.borrow_mut().id());
+                    widget.borrow_mut().set_id(0);
+                }
+            }
+        }
+    }
+
+    /// Sets the tooltip view's size (width and height), returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the tooltip view
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the tooltip view's pointer to the render instructions collection, returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the pointer to the render instructions collection assigned
+    ///
+    /// # Arguments
+    /// * `render_instruction_collection_ptr` - the pointer to the render instructions collection to be assigned to the tooltip view
+    pub fn with_render_instruction_collection_ptr(mut self, render_instruction_collection_ptr: Weak<RefCell<RenderInstructionCollection>>) -> Self {
+        self.render_instruction_collection_ptr = render_instruction_collection_ptr;
+        self
+    }
+
+    /// Sets the tooltip view's pointer to the absolute widgets collection, returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the pointer to the absolute widgets collection assigned
+    ///
+    /// # Arguments
+    /// * `absolute_widget_collection_ptr` - the pointer to the absolute widgets collection to be assigned to the tooltip view
+    pub fn with_absolute_widget_collection_ptr(mut self, absolute_widget_collection_ptr: Weak<RefCell<AbsoluteWidgetCollection>>) -> Self {
+        self.absolute_widget_collection_ptr = absolute_widget_collection_ptr;
+        self
+    }
+
+    /// Sets the tooltip view's widget displayed as tooltip, returning the tooltip view (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The tooltip view with the widget displayed as tooltip assigned
+    ///
+    /// # Arguments
+    /// * `tooltip_widget` - the widget displayed as tooltip to be assigned to the tooltip view
+    pub fn with_tooltip_widget(mut self, tooltip_widget: Weak<RefCell<dyn Widget>>) -> Self {
+        self.tooltip_widget = tooltip_widget;
+        self
+    }
+}
+
+impl Default for TooltipViewWidget {
+    fn default() -> Self {
+        TooltipViewWidget::new(
+            Vector2D::new(100., 30.),
+            Weak::new(),
+            Weak::new(),
+            Weak::<RefCell<TooltipViewWidget>>::new(),
+        )
+    }
 }
 
 impl Widget for TooltipViewWidget {
-    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
         match event {
             event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
                 self.cursor_pos.x = x_pos as f64;
                 self.cursor_pos.y = y_pos as f64;
 
                 if self.is_cursor_inside(self.cursor_pos) {
-                    if let Some(absolute_widget_collection) =
-                        self.absolute_widget_collection_ptr.upgrade()
-                    {
-                        if let Some(widget) = self.tooltip_widget.upgrade() {
-                            if widget.borrow_mut().id() == 0 {
-                                widget.borrow_mut().set_dirty(true);
-                                let size = widget.borrow_mut().original_size();
-                                absolute_widget_collection.borrow_mut().insert(
-                                    Rc::downgrade(&widget),
-                                    self.cursor_pos,
-                                    size,
-                                );
-                            }
+                    // Wait for the cursor to rest over the tooltip view, the tooltip being
+                    // shown on the frame the delay elapses
+                    if self.hover_start.is_none() && !self.is_shown() {
+                        let hover_start = clock::now();
+                        self.hover_start = Some(hover_start);
+                        if let Some(context) = self.context.upgrade() {
+                            context
+                                .borrow_mut()
+                                .request_redraw_at(hover_start + self.show_delay);
                         }
                     }
                 } else {
-                    if let Some(render_instruction_collection) =
-                        self.render_instruction_collection_ptr.upgrade()
-                    {
-                        if let Some(absolute_widget_collection) =
-                            self.absolute_widget_collection_ptr.upgrade()
-                        {
-                            if let Some(widget) = self.tooltip_widget.upgrade() {
-                                render_instruction_collection
-                                    .borrow_mut()
-                                    .remove(widget.borrow_mut().id());
-                                absolute_widget_collection
-                                    .borrow_mut()
-                                    .remove(widget.borrow_mut().id());
-                                widget.borrow_mut().set_id(0);
-                            }
-                        }
-                    }
+                    self.hide();
                 }
 
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
+            }
+            event::Event::Mouse(event::Mouse::CursorLeft) => {
+                self.hide();
+                propagate_event(&self.children, event, messages)
             }
             _ => {
-                for value in self.children.iter_mut() {
-                    if let Some(child) = value.upgrade() {
-                        child.borrow_mut().on_event(event, messages);
-                    }
-                }
+                propagate_event(&self.children, event, messages)
+            }
+        }
+    }
+
+    fn on_frame(&mut self, messages: &mut Queue<Box<dyn Message>>) {
+        if let Some(hover_start) = self.hover_start {
+            if !self.is_shown() && clock::elapsed(hover_start) >= self.show_delay {
+                self.show();
+            }
+        }
+
+        for value in self.children.iter_mut() {
+            if let Some(child) = value.upgrade() {
+                deliver_frame(&mut *child.borrow_mut(), messages);
             }
         }
     }
@@ -150,6 +501,10 @@ impl Widget for TooltipViewWidget {
         self.id
     }
 
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
     fn recipe(&self) -> Vec<RenderInstruction> {
         vec![]
     }
@@ -157,6 +512,10 @@ impl Widget for TooltipViewWidget {
     fn set_dirty(&mut self, value: bool) {
         if value {
             self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
             for value in self.get_children() {
                 if let Some(child) = value.upgrade() {
                     if child.borrow_mut().is_dirty() {
@@ -0,0 +1,338 @@
+use crate::context::{Context, WindowRequest};
+use crate::display::WindowEdge;
+use crate::event;
+use crate::event::Event;
+use crate::renderer::{Message, RenderInstruction};
+use crate::util::{Queue, Vector2D};
+use crate::widget::{
+    is_batching_updates, propagate_event, Axis, EventStatus, Layout, Widget, WidgetData,
+};
+
+use std::cell::RefCell;
+use std::rc::Weak;
+
+/// Window resize handle is a widget that makes a borderless display resizable by
+/// dragging its edges and corners, when the display system does not provide them.
+///
+/// It is meant to wrap the whole user interface (i.e., to be the single child of the
+/// root widget): pressing the left mouse button within the border of the handle starts
+/// resizing the display from the edge or corner under the cursor, through
+/// [`begin_resize`](`crate::display::Display::begin_resize`). Every other event is
+/// forwarded to its children, laid out as a vertical list inside the border.
+#[derive(Clone)]
+pub struct WindowResizeHandleWidget {
+    /// The window resize handle's identifier
+    id: usize,
+
+    /// The data attached to the window resize handle through its handle
+    widget_data: WidgetData,
+
+    /// The thickness of the border where dragging resizes the display
+    border: f64,
+
+    /// The length of the corners, along each edge, where dragging resizes the display
+    /// on both directions
+    corner: f64,
+
+    /// The context where the resize requests are placed
+    context: Weak<RefCell<Context>>,
+
+    /// The cursor's position, on a two-dimensional space (x-coordinate and y-coordinate)
+    cursor_pos: Vector2D,
+
+    /// The dirty flag (i.e., flag used to mark the widgets needed to be rebuilt)
+    dirty: bool,
+
+    /// The window resize handle's children (i.e., his widgets tree)
+    children: Vec<Weak<RefCell<dyn Widget>>>,
+
+    /// The window resize handle's position, on a two-dimensional space (x-coordinate
+    /// and y-coordinate) relative to the top left corner
+    position: Vector2D,
+
+    /// The window resize handle's current size (width and height)
+    size: Vector2D,
+
+    /// The window resize handle's original size (width and height)
+    original_size: Vector2D,
+
+    /// The window resize handle's layout
+    layout: Layout,
+
+    /// The window resize handle's offset vector coordinates
+    offset: Vector2D,
+}
+
+impl WindowResizeHandleWidget {
+    /// Creates a new `WindowResizeHandleWidget`
+    ///
+    /// # Returns
+    /// The window resize handle created
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the window resize handle
+    /// * `border` - the thickness of the border where dragging resizes the display
+    /// * `context` - the context where the resize requests are placed
+    pub fn new(
+        size: Vector2D,
+        border: f64,
+        context: Weak<RefCell<Context>>,
+    ) -> WindowResizeHandleWidget {
+        WindowResizeHandleWidget {
+            id: 0,
+            widget_data: WidgetData::default(),
+            border,
+            corner: border * 2.,
+            context,
+            cursor_pos: Vector2D::new(-1., -1.),
+            dirty: true,
+            children: Vec::<Weak<RefCell<dyn Widget>>>::new(),
+            position: Vector2D::new(0., 0.),
+            size,
+            original_size: size,
+            layout: Layout::Box(Axis::Vertical),
+            offset: Vector2D::new(border, border),
+        }
+    }
+
+    /// Sets the thickness of the border where dragging resizes the display
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `border` - the thickness of the border
+    pub fn set_border(&mut self, border: f64) {
+        self.border = border;
+        self.offset = Vector2D::new(border, border);
+        self.set_dirty(true);
+    }
+
+    /// Sets the length of the corners, along each edge, where dragging resizes the
+    /// display on both directions
+    ///
+    /// # Returns
+    /// No returns
+    ///
+    /// # Arguments
+    /// * `corner` - the length of the corners, which defaults to twice the border
+    pub fn set_corner(&mut self, corner: f64) {
+        self.corner = corner;
+    }
+
+    /// Gets the edge or corner of the display under a position, for internal use only
+    ///
+    /// # Returns
+    /// The edge or corner under the position, or `None` if the position is outside the border
+    ///
+    /// # Arguments
+    /// * `position` - the position to be checked
+    fn edge_at(&self, position: Vector2D) -> Option<WindowEdge> {
+        let start = position - self.position;
+        let end = self.position + self.size - position;
+        if start.x < 0. || start.y < 0. || end.x < 0. || end.y < 0. {
+            return None;
+        }
+
+        let is_left = start.x <= self.border;
+        let is_right = end.x <= self.border;
+        let is_top = start.y <= self.border;
+        let is_bottom = end.y <= self.border;
+        if !(is_left || is_right || is_top || is_bottom) {
+            return None;
+        }
+
+        // Near a corner, the edges extend along the corner's length
+        let is_left = start.x <= self.corner;
+        let is_right = end.x <= self.corner;
+        let is_top = start.y <= self.corner;
+        let is_bottom = end.y <= self.corner;
+        match (is_left, is_right, is_top, is_bottom) {
+            (true, _, true, _) => Some(WindowEdge::TopLeft),
+            (_, true, true, _) => Some(WindowEdge::TopRight),
+            (true, _, _, true) => Some(WindowEdge::BottomLeft),
+            (_, true, _, true) => Some(WindowEdge::BottomRight),
+            (true, _, _, _) => Some(WindowEdge::Left),
+            (_, true, _, _) => Some(WindowEdge::Right),
+            (_, _, true, _) => Some(WindowEdge::Top),
+            _ => Some(WindowEdge::Bottom),
+        }
+    }
+
+    /// Sets the window resize handle's size (width and height), returning the window resize handle (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The window resize handle with the size (width and height) assigned
+    ///
+    /// # Arguments
+    /// * `size` - the size (width and height) to be assigned to the window resize handle
+    pub fn with_size(mut self, size: Vector2D) -> Self {
+        self.size = size;
+        self.original_size = size;
+        self
+    }
+
+    /// Sets the window resize handle's thickness of the border, returning the window resize handle (i.e., fluent setter)
+    ///
+    /// # Returns
+    /// The window resize handle with the thickness of the border assigned
+    ///
+    /// # Arguments
+    /// * `border` - the thickness of the border to be assigned to the window resize handle
+    pub fn with_border(mut self, border: f64) -> Self {
+        self.border = border;
+        self.corner = border * 2.;
+        self.offset = Vector2D::new(border, border);
+        self
+    }
+}
+
+impl Default for WindowResizeHandleWidget {
+    fn default() -> Self {
+        WindowResizeHandleWidget::new(Vector2D::new(100., 100.), 4., Weak::new())
+    }
+}
+
+impl Widget for WindowResizeHandleWidget {
+    fn on_event(&mut self, event: Event, messages: &mut Queue<Box<dyn Message>>) -> EventStatus {
+        match event {
+            event::Event::Mouse(event::Mouse::CursorMoved { x: x_pos, y: y_pos }) => {
+                self.cursor_pos = Vector2D::new(x_pos as f64, y_pos as f64);
+            }
+            event::Event::Mouse(event::Mouse::ButtonPressed(event::MouseButton::Left)) => {
+                if let Some(edge) = self.edge_at(self.cursor_pos) {
+                    if let Some(context) = self.context.upgrade() {
+                        context
+                            .borrow_mut()
+                            .request_window(WindowRequest::BeginResize(edge));
+                    }
+                    // The press started a resize, so it does not reach the children
+                    return EventStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        propagate_event(&self.children, event, messages)
+    }
+
+    fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn widget_data(&mut self) -> Option<&mut WidgetData> {
+        Some(&mut self.widget_data)
+    }
+
+    fn recipe(&self) -> Vec<RenderInstruction> {
+        vec![]
+    }
+
+    fn set_dirty(&mut self, value: bool) {
+        if value {
+            self.dirty = true;
+            // The children are marked once the batch update ends
+            if is_batching_updates() {
+                return;
+            }
+            for value in self.get_children() {
+                if let Some(child) = value.upgrade() {
+                    if child.borrow_mut().is_dirty() {
+                        break;
+                    } else {
+                        child.borrow_mut().set_dirty(true);
+                    }
+                }
+            }
+        } else {
+            self.dirty = false;
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn add_as_child(&mut self, child: Weak<RefCell<dyn Widget>>) {
+        self.children.push(child);
+    }
+
+    fn get_children(&mut self) -> &mut Vec<Weak<RefCell<dyn Widget>>> {
+        &mut self.children
+    }
+
+    fn position(&mut self) -> Vector2D {
+        self.position
+    }
+
+    fn size(&mut self) -> Vector2D {
+        self.size
+    }
+
+    fn original_size(&mut self) -> Vector2D {
+        self.original_size
+    }
+
+    fn layout(&mut self) -> &Layout {
+        &self.layout
+    }
+
+    fn offset(&mut self) -> Vector2D {
+        self.offset
+    }
+
+    fn get_fields(
+        &mut self,
+    ) -> (
+        bool,
+        &mut Vec<Weak<RefCell<dyn Widget>>>,
+        Vector2D,
+        Vector2D,
+        Vector2D,
+        &Layout,
+        Vector2D,
+    ) {
+        (
+            self.dirty,
+            &mut self.children,
+            self.position,
+            self.size,
+            self.original_size,
+            &self.layout,
+            self.offset,
+        )
+    }
+
+    fn set_position(&mut self, position: Vector2D) {
+        self.position = position;
+    }
+
+    fn set_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.size = size;
+    }
+
+    fn set_original_size(&mut self, size: Vector2D) {
+        self.set_dirty(true);
+        self.original_size = size;
+    }
+
+    fn set_offset(&mut self, offset: Vector2D) {
+        self.offset = offset;
+    }
+
+    fn set_clip_point(&mut self, _clip_point: Option<Vector2D>) {}
+
+    fn set_clip_size(&mut self, _clip_size: Option<Vector2D>) {}
+
+    fn is_cursor_inside(&mut self, cursor_pos: Vector2D) -> bool {
+        cursor_pos.x >= self.position.x
+            && cursor_pos.x <= (self.position.x + self.size.x)
+            && cursor_pos.y >= self.position.y
+            && cursor_pos.y <= (self.position.y + self.size.y)
+    }
+}